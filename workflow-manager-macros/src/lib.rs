@@ -9,28 +9,56 @@ pub fn derive_workflow_definition(input: TokenStream) -> TokenStream {
     // Extract workflow metadata from #[workflow(...)]
     let workflow_meta = extract_workflow_meta(&input.attrs);
 
-    // Extract field schemas from struct fields
-    let field_schemas: Vec<proc_macro2::TokenStream> = match &input.data {
+    // Extract field schemas from struct fields, along with the statements
+    // collecting each field's current value for validate()
+    let (field_schemas, field_values): (Vec<proc_macro2::TokenStream>, Vec<proc_macro2::TokenStream>) = match &input.data {
         Data::Struct(data) => match &data.fields {
             Fields::Named(fields) => {
                 fields.named.iter().filter_map(|f| {
-                    let name = f.ident.as_ref().unwrap().to_string();
+                    let ident = f.ident.as_ref().unwrap();
+                    let name = ident.to_string();
 
                     // Skip the workflow_metadata field
                     if name == "workflow_metadata" {
                         return None;
                     }
 
-                    let field_type = infer_field_type(&f.ty);
-                    let (label, description, field_type_override, required_for_phases) = extract_field_meta(&f.attrs);
+                    let (default_value, default_env) = extract_default(&f.attrs, &name);
+                    let field_type = infer_field_type(&f.ty, default_value.as_deref());
+                    let (label, description, field_type_override, required_for_phases, (min, max, pattern), group) = extract_field_meta(&f.attrs, default_value.as_deref());
                     let cli_arg = extract_cli_arg(&f.attrs, &name);
-                    let required = !is_option_type(&f.ty);
-                    let default = extract_default(&f.attrs);
+                    // Flags are simply off when not set
+                    let required = !is_option_type(&f.ty) && !is_bool_type(&f.ty);
+                    let default = default_value
+                        .map(|val| quote! { Some(#val.to_string()) })
+                        .unwrap_or(quote! { None });
+                    // An env default is read when the schema is built and wins over the literal
+                    let (default, default_env) = match default_env {
+                        Some(env) => (
+                            quote! {
+                                std::env::var(#env).ok().filter(|v| !v.is_empty()).or(#default)
+                            },
+                            quote! { Some(#env.to_string()) },
+                        ),
+                        None => (default, quote! { None }),
+                    };
 
                     // Use override if provided, otherwise infer
                     let final_field_type = field_type_override.unwrap_or(field_type);
 
-                    Some(quote! {
+                    let field_value = if is_option_type(&f.ty) {
+                        let value = value_to_string(option_inner_type(&f.ty), quote! { value });
+                        quote! {
+                            if let Some(value) = &self.#ident {
+                                values.insert(#name.to_string(), #value);
+                            }
+                        }
+                    } else {
+                        let value = value_to_string(&f.ty, quote! { self.#ident });
+                        quote! { values.insert(#name.to_string(), #value); }
+                    };
+
+                    Some((quote! {
                         workflow_manager_sdk::FieldSchema {
                             name: #name.to_string(),
                             field_type: #final_field_type,
@@ -39,10 +67,15 @@ pub fn derive_workflow_definition(input: TokenStream) -> TokenStream {
                             cli_arg: #cli_arg.to_string(),
                             required: #required,
                             default: #default,
+                            default_env: #default_env,
                             required_for_phases: #required_for_phases,
+                            min: #min,
+                            max: #max,
+                            pattern: #pattern,
+                            group: #group,
                         }
-                    })
-                }).collect()
+                    }, field_value))
+                }).unzip()
             }
             _ => panic!("WorkflowDefinition only supports named fields")
         },
@@ -53,6 +86,10 @@ pub fn derive_workflow_definition(input: TokenStream) -> TokenStream {
     let workflow_id = &workflow_meta.id;
     let workflow_name = &workflow_meta.name;
     let workflow_desc = &workflow_meta.description;
+    let restart_on_failure = match workflow_meta.restart_on_failure {
+        Some(max) => quote! { Some(workflow_manager_sdk::RestartPolicy { max: #max }) },
+        None => quote! { None },
+    };
 
     let expanded = quote! {
         impl workflow_manager_sdk::WorkflowDefinition for #struct_name {
@@ -61,6 +98,7 @@ pub fn derive_workflow_definition(input: TokenStream) -> TokenStream {
                     id: #workflow_id.to_string(),
                     name: #workflow_name.to_string(),
                     description: #workflow_desc.to_string(),
+                    restart_on_failure: #restart_on_failure,
                 }
             }
 
@@ -73,6 +111,7 @@ pub fn derive_workflow_definition(input: TokenStream) -> TokenStream {
                     id: #workflow_id.to_string(),
                     name: #workflow_name.to_string(),
                     description: #workflow_desc.to_string(),
+                    restart_on_failure: #restart_on_failure,
                 };
                 let full_metadata = workflow_manager_sdk::FullWorkflowMetadata {
                     metadata,
@@ -81,6 +120,12 @@ pub fn derive_workflow_definition(input: TokenStream) -> TokenStream {
                 let json = serde_json::to_string_pretty(&full_metadata).unwrap();
                 println!("{}", json);
             }
+
+            fn field_values(&self) -> std::collections::HashMap<String, String> {
+                let mut values = std::collections::HashMap::new();
+                #(#field_values)*
+                values
+            }
         }
     };
 
@@ -91,6 +136,8 @@ struct WorkflowMeta {
     id: String,
     name: String,
     description: String,
+    /// `restart_on_failure = N`: re-runs allowed after a failed run
+    restart_on_failure: Option<usize>,
 }
 
 fn extract_workflow_meta(attrs: &[Attribute]) -> WorkflowMeta {
@@ -99,6 +146,7 @@ fn extract_workflow_meta(attrs: &[Attribute]) -> WorkflowMeta {
             let mut id = String::new();
             let mut name = String::new();
             let mut description = String::new();
+            let mut restart_on_failure = None;
 
             let _ = attr.parse_nested_meta(|meta| {
                 if meta.path.is_ident("id") {
@@ -119,18 +167,32 @@ fn extract_workflow_meta(attrs: &[Attribute]) -> WorkflowMeta {
                     if let Lit::Str(s) = lit {
                         description = s.value();
                     }
+                } else if meta.path.is_ident("restart_on_failure") {
+                    let value = meta.value()?;
+                    let lit: Lit = value.parse()?;
+                    if let Lit::Int(i) = lit {
+                        restart_on_failure = Some(i.base10_parse::<usize>()?);
+                    }
                 }
                 Ok(())
             });
 
-            return WorkflowMeta { id, name, description };
+            return WorkflowMeta {
+                id,
+                name,
+                description,
+                restart_on_failure,
+            };
         }
     }
 
     panic!("Missing #[workflow(...)] attribute");
 }
 
-fn extract_field_meta(attrs: &[Attribute]) -> (String, String, Option<proc_macro2::TokenStream>, Option<proc_macro2::TokenStream>) {
+// Schema-level validation tokens: (min, max, pattern)
+type ValidationTokens = (proc_macro2::TokenStream, proc_macro2::TokenStream, proc_macro2::TokenStream);
+
+fn extract_field_meta(attrs: &[Attribute], default_value: Option<&str>) -> (String, String, Option<proc_macro2::TokenStream>, Option<proc_macro2::TokenStream>, ValidationTokens, proc_macro2::TokenStream) {
     let mut label = String::new();
     let mut description = String::new();
     let mut field_type = None;
@@ -140,6 +202,8 @@ fn extract_field_meta(attrs: &[Attribute]) -> (String, String, Option<proc_macro
     let mut total_phases = None;
     let mut phase = None;
     let mut required_for_phases = None;
+    let mut options = None;
+    let mut group = None;
 
     for attr in attrs {
         if attr.path().is_ident("field") {
@@ -198,16 +262,48 @@ fn extract_field_meta(attrs: &[Attribute]) -> (String, String, Option<proc_macro
                     if let Lit::Str(s) = lit {
                         required_for_phases = Some(s.value());
                     }
+                } else if meta.path.is_ident("options") {
+                    let value = meta.value()?;
+                    let lit: Lit = value.parse()?;
+                    if let Lit::Str(s) = lit {
+                        options = Some(s.value());
+                    }
+                } else if meta.path.is_ident("group") {
+                    let value = meta.value()?;
+                    let lit: Lit = value.parse()?;
+                    if let Lit::Str(s) = lit {
+                        group = Some(s.value());
+                    }
                 }
                 Ok(())
             });
         }
     }
 
+    // Validation metadata kept on the FieldSchema itself. File patterns are
+    // globs for the file pickers, so only other fields get a value regex.
+    let is_file_field = matches!(field_type.as_deref(), Some("file_path") | Some("state_file"));
+    // Checked here so a typo in a value regex fails the build, not every launch
+    if let Some(pattern) = pattern.as_ref().filter(|_| !is_file_field) {
+        if let Err(e) = regex::Regex::new(pattern) {
+            panic!("Invalid pattern {:?}: {}", pattern, e);
+        }
+    }
+    let validation_tokens = (
+        min.map(|m| quote! { Some(#m) }).unwrap_or(quote! { None }),
+        max.map(|m| quote! { Some(#m) }).unwrap_or(quote! { None }),
+        pattern
+            .as_ref()
+            .filter(|_| !is_file_field)
+            .map(|p| quote! { Some(#p.to_string()) })
+            .unwrap_or(quote! { None }),
+    );
+
     // Build field type from parsed values
     let field_type_token = field_type.map(|ft| {
         match ft.as_str() {
             "text" => quote! { workflow_manager_sdk::FieldType::Text },
+            "secret" => quote! { workflow_manager_sdk::FieldType::Secret },
             "number" => {
                 let min_token = min.map(|m| quote! { Some(#m) }).unwrap_or(quote! { None });
                 let max_token = max.map(|m| quote! { Some(#m) }).unwrap_or(quote! { None });
@@ -226,6 +322,25 @@ fn extract_field_meta(attrs: &[Attribute]) -> (String, String, Option<proc_macro
                 let phase_token = phase.map(|p| quote! { Some(#p) }).unwrap_or(quote! { None });
                 quote! { workflow_manager_sdk::FieldType::StateFile { pattern: #pattern_str.to_string(), phase: #phase_token } }
             }
+            "boolean" => boolean_field_type(default_value),
+            "select" | "multi_select" => {
+                // e.g., "debug, release,test" -> vec!["debug", "release", "test"]
+                let options: Vec<String> = options
+                    .as_deref()
+                    .unwrap_or("")
+                    .split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect();
+                if options.is_empty() {
+                    panic!("#[field(type = \"{}\")] requires a non-empty `options` list, e.g. options = \"a,b,c\"", ft);
+                }
+                if ft == "select" {
+                    quote! { workflow_manager_sdk::FieldType::Select { options: vec![#(#options.to_string()),*] } }
+                } else {
+                    quote! { workflow_manager_sdk::FieldType::MultiSelect { options: vec![#(#options.to_string()),*] } }
+                }
+            }
             _ => quote! { workflow_manager_sdk::FieldType::Text },
         }
     });
@@ -239,7 +354,11 @@ fn extract_field_meta(attrs: &[Attribute]) -> (String, String, Option<proc_macro
         quote! { Some(vec![#(#phases),*]) }
     });
 
-    (label, description, field_type_token, Some(required_for_phases_token.unwrap_or(quote! { None })))
+    let group_token = group
+        .map(|g| quote! { Some(#g.to_string()) })
+        .unwrap_or(quote! { None });
+
+    (label, description, field_type_token, Some(required_for_phases_token.unwrap_or(quote! { None })), validation_tokens, group_token)
 }
 
 fn extract_cli_arg(attrs: &[Attribute], field_name: &str) -> String {
@@ -272,18 +391,18 @@ fn extract_cli_arg(attrs: &[Attribute], field_name: &str) -> String {
     format!("--{}", field_name.replace("_", "-"))
 }
 
-fn infer_field_type(ty: &Type) -> proc_macro2::TokenStream {
+fn infer_field_type(ty: &Type, default_value: Option<&str>) -> proc_macro2::TokenStream {
     // Check if it's Option<T>
     if let Type::Path(type_path) = ty {
         if let Some(segment) = type_path.path.segments.last() {
             if segment.ident == "Option" {
                 if let PathArguments::AngleBracketed(args) = &segment.arguments {
                     if let Some(GenericArgument::Type(inner_ty)) = args.args.first() {
-                        return infer_field_type_inner(inner_ty);
+                        return infer_field_type_inner(inner_ty, default_value);
                     }
                 }
             } else {
-                return infer_field_type_inner(ty);
+                return infer_field_type_inner(ty, default_value);
             }
         }
     }
@@ -291,12 +410,13 @@ fn infer_field_type(ty: &Type) -> proc_macro2::TokenStream {
     quote! { workflow_manager_sdk::FieldType::Text }
 }
 
-fn infer_field_type_inner(ty: &Type) -> proc_macro2::TokenStream {
+fn infer_field_type_inner(ty: &Type, default_value: Option<&str>) -> proc_macro2::TokenStream {
     if let Type::Path(type_path) = ty {
         if let Some(segment) = type_path.path.segments.last() {
             let type_name = segment.ident.to_string();
             match type_name.as_str() {
                 "String" => quote! { workflow_manager_sdk::FieldType::Text },
+                "bool" => boolean_field_type(default_value),
                 "PathBuf" => quote! { workflow_manager_sdk::FieldType::FilePath { pattern: None } },
                 "usize" | "u32" | "u64" | "i32" | "i64" => {
                     quote! { workflow_manager_sdk::FieldType::Number { min: None, max: None } }
@@ -311,36 +431,106 @@ fn infer_field_type_inner(ty: &Type) -> proc_macro2::TokenStream {
     }
 }
 
-fn is_option_type(ty: &Type) -> bool {
+// Boolean flag, on by default only with #[arg(default_value = "true")]
+fn boolean_field_type(default_value: Option<&str>) -> proc_macro2::TokenStream {
+    let default = default_value.map(|v| v.eq_ignore_ascii_case("true")).unwrap_or(false);
+    quote! { workflow_manager_sdk::FieldType::Boolean { default: #default } }
+}
+
+fn is_bool_type(ty: &Type) -> bool {
     if let Type::Path(type_path) = ty {
         if let Some(segment) = type_path.path.segments.last() {
-            return segment.ident == "Option";
+            return segment.ident == "bool";
         }
     }
     false
 }
 
-fn extract_default(attrs: &[Attribute]) -> proc_macro2::TokenStream {
-    for attr in attrs {
-        if attr.path().is_ident("arg") {
-            let mut default_value = None;
-
-            let _ = attr.parse_nested_meta(|meta| {
-                if meta.path.is_ident("default_value") {
-                    let value = meta.value()?;
-                    let lit: Lit = value.parse()?;
-                    if let Lit::Str(s) = lit {
-                        default_value = Some(s.value());
+// `T` for `Option<T>`, otherwise the type itself
+fn option_inner_type(ty: &Type) -> &Type {
+    if let Type::Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            if segment.ident == "Option" {
+                if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                    if let Some(GenericArgument::Type(inner_ty)) = args.args.first() {
+                        return inner_ty;
                     }
                 }
-                Ok(())
-            });
+            }
+        }
+    }
+    ty
+}
 
-            if let Some(val) = default_value {
-                return quote! { Some(#val.to_string()) };
+// Render a field value the way it is passed on the command line; types
+// without Display (e.g. a ValueEnum that only derives Debug) use Debug
+fn value_to_string(ty: &Type, value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    if let Type::Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            if segment.ident == "PathBuf" {
+                return quote! { #value.display().to_string() };
+            }
+            // Multi-select style values are comma-separated
+            if segment.ident == "Vec" {
+                let item = render_value(quote! { v });
+                return quote! {
+                    #value.iter().map(|v| #item).collect::<Vec<_>>().join(",")
+                };
             }
         }
     }
+    render_value(quote! { &#value })
+}
 
-    quote! { None }
+// Display of the value behind the reference `value`, or Debug without it
+fn render_value(value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    quote! {
+        {
+            #[allow(unused_imports)]
+            use workflow_manager_sdk::__private::{DebugValue as _, DisplayValue as _};
+            (&workflow_manager_sdk::__private::FieldValue(#value)).render()
+        }
+    }
+}
+
+fn is_option_type(ty: &Type) -> bool {
+    if let Type::Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            return segment.ident == "Option";
+        }
+    }
+    false
+}
+
+// `default_value` and `env` of #[arg(...)]; a bare `env` is clap's
+// upper-cased field name
+fn extract_default(attrs: &[Attribute], field_name: &str) -> (Option<String>, Option<String>) {
+    let mut default_value = None;
+    let mut env = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("arg")) {
+        let parsed = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("default_value") {
+                if let Lit::Str(s) = meta.value()?.parse()? {
+                    default_value = Some(s.value());
+                }
+            } else if meta.path.is_ident("env") {
+                env = Some(if meta.input.peek(syn::Token![=]) {
+                    match meta.value()?.parse()? {
+                        Lit::Str(s) => s.value(),
+                        _ => return Err(meta.error("expected a string literal")),
+                    }
+                } else {
+                    field_name.to_uppercase()
+                });
+            } else if meta.input.peek(syn::Token![=]) {
+                // Skip the values of other keys, e.g. `value_parser = ...`
+                let _: syn::Expr = meta.value()?.parse()?;
+            }
+            Ok(())
+        });
+        if let Err(e) = parsed {
+            panic!("Invalid #[arg] on field {}: {}", field_name, e);
+        }
+    }
+    (default_value, env)
 }
@@ -0,0 +1,94 @@
+//! Errors of [`WorkflowRuntime`](crate::WorkflowRuntime) operations
+//!
+//! Callers match on the variant to react appropriately, e.g. the TUI sends
+//! validation errors back to the edit form and reports an unknown workflow
+//! in a notification.
+
+use std::fmt;
+
+use crate::validation::FieldError;
+
+/// Error of a workflow runtime operation
+#[derive(Debug)]
+pub enum WorkflowError {
+    /// No such workflow or execution, e.g. "Workflow 'research_agent'"
+    NotFound(String),
+    /// The inputs were rejected, one error per problem, named after the
+    /// parameter it is about
+    Validation(Vec<FieldError>),
+    /// The workflow process could not be started
+    Spawn(std::io::Error),
+    /// The execution with this id is already running
+    AlreadyRunning(String),
+    /// The execution was cancelled before it finished
+    Cancelled,
+    /// The execution was stopped by its timeout
+    Timeout,
+    /// Anything else, e.g. a database or file system failure
+    Other(String),
+}
+
+impl WorkflowError {
+    /// Wrap an error that has no variant of its own
+    pub fn other(error: impl fmt::Display) -> Self {
+        Self::Other(error.to_string())
+    }
+}
+
+impl fmt::Display for WorkflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(what) => write!(f, "{} not found", what),
+            Self::Validation(problems) => {
+                write!(f, "Invalid inputs:")?;
+                for problem in problems {
+                    write!(f, "\n  - {}", problem)?;
+                }
+                Ok(())
+            }
+            Self::Spawn(e) => write!(f, "Failed to spawn workflow process: {}", e),
+            Self::AlreadyRunning(id) => write!(f, "Execution {} is already running", id),
+            Self::Cancelled => write!(f, "Workflow was cancelled"),
+            Self::Timeout => write!(f, "Workflow timed out"),
+            Self::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for WorkflowError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Spawn(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Result type for workflow operations
+pub type WorkflowResult<T> = Result<T, WorkflowError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_lists_validation_problems() {
+        let error = WorkflowError::Validation(vec![
+            FieldError {
+                field: "verbose".to_string(),
+                message: "Unknown parameter".to_string(),
+            },
+            FieldError {
+                field: "input".to_string(),
+                message: "Required".to_string(),
+            },
+        ]);
+        assert_eq!(
+            error.to_string(),
+            "Invalid inputs:\n  - verbose: Unknown parameter\n  - input: Required"
+        );
+
+        let error = WorkflowError::Spawn(std::io::Error::from(std::io::ErrorKind::NotFound));
+        assert!(std::error::Error::source(&error).is_some());
+    }
+}
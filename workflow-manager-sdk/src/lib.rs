@@ -6,18 +6,44 @@ pub use claude_agent_sdk;
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
 use uuid::Uuid;
 use chrono::{DateTime, Local};
 
 // Re-export async trait for convenience
 pub use async_trait::async_trait;
 
+pub mod error;
+pub use error::{WorkflowError, WorkflowResult};
+
+pub mod protocol;
+pub use protocol::{
+    event_framing, event_prefix, frame_event, parse_event_line, write_event, EventDecoder,
+    EventFraming, StderrLine, EVENT_FRAMING_ENV, EVENT_PREFIX, EVENT_PREFIX_ENV,
+};
+
+pub mod validation;
+pub use validation::FieldError;
+
 /// Workflow metadata (id, name, description)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct WorkflowMetadata {
     pub id: String,
     pub name: String,
     pub description: String,
+    /// Re-run the whole workflow when its process fails
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restart_on_failure: Option<RestartPolicy>,
+}
+
+/// When the runtime re-runs a failed workflow
+///
+/// A process that exits nonzero before completing its last phase is spawned
+/// again with the same parameters, up to `max` times. Cancelled and timed
+/// out executions are never re-run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RestartPolicy {
+    pub max: usize,
 }
 
 /// Complete workflow metadata with fields (for JSON export)
@@ -29,7 +55,7 @@ pub struct FullWorkflowMetadata {
 }
 
 /// Field schema definition
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct FieldSchema {
     pub name: String,
     pub field_type: FieldType,
@@ -38,14 +64,76 @@ pub struct FieldSchema {
     pub cli_arg: String,
     pub required: bool,
     pub default: Option<String>,
+    /// Environment variable that, when set and non-empty, takes the place of
+    /// `default` (clap's `env`, e.g. "WF_TARGET_DIR")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_env: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub required_for_phases: Option<Vec<usize>>,
+    /// Inclusive lower bound for number fields
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<i64>,
+    /// Inclusive upper bound for number fields
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<i64>,
+    /// Regex the whole value must match (file fields use a glob in their type instead)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// Section of the edit form the field is shown under (default "General")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+}
+
+impl FieldSchema {
+    /// Optional field labelled after `name`, with clap's flag for it (e.g.
+    /// `--batch-size`); the rest can follow with struct update syntax
+    pub fn new(name: &str, field_type: FieldType) -> Self {
+        Self {
+            name: name.to_string(),
+            field_type,
+            label: name.to_string(),
+            cli_arg: format!("--{}", name.replace('_', "-")),
+            ..Self::default()
+        }
+    }
+
+    /// Value the field starts with: `default_env` if set, else `default`
+    pub fn resolved_default(&self) -> Option<String> {
+        self.default_env
+            .as_deref()
+            .and_then(|name| std::env::var(name).ok())
+            .filter(|value| !value.is_empty())
+            .or_else(|| self.default.clone())
+    }
+}
+
+/// Command-line arguments a workflow is launched with for `values` keyed by
+/// field name, the inverse of [`WorkflowDefinition::field_values`]
+///
+/// Flags are passed bare when "true" and left out otherwise; empty values
+/// are left out.
+pub fn cli_args(fields: &[FieldSchema], values: &HashMap<String, String>) -> Vec<String> {
+    let mut args = Vec::new();
+    for field in fields {
+        if let Some(value) = values.get(&field.name) {
+            if matches!(field.field_type, FieldType::Boolean { .. }) {
+                if value.eq_ignore_ascii_case("true") {
+                    args.push(field.cli_arg.clone());
+                }
+            } else if !value.is_empty() {
+                args.push(field.cli_arg.clone());
+                args.push(value.clone());
+            }
+        }
+    }
+    args
 }
 
 /// Field type enum
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum FieldType {
+    #[default]
     Text,
     Number {
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -60,6 +148,10 @@ pub enum FieldType {
     Select {
         options: Vec<String>,
     },
+    /// Any subset of `options`, passed as a comma-separated value
+    MultiSelect {
+        options: Vec<String>,
+    },
     PhaseSelector {
         total_phases: usize,
     },
@@ -68,6 +160,14 @@ pub enum FieldType {
         #[serde(skip_serializing_if = "Option::is_none")]
         phase: Option<usize>,
     },
+    /// On/off flag, passed as a bare `--flag` when "true" and omitted otherwise
+    Boolean {
+        #[serde(default)]
+        default: bool,
+    },
+    /// Text such as an API key: masked in the TUI and kept out of history,
+    /// the database and raw output
+    Secret,
 }
 
 /// Trait that workflows must implement (auto-implemented by derive macro)
@@ -75,13 +175,77 @@ pub trait WorkflowDefinition {
     fn metadata() -> WorkflowMetadata;
     fn fields() -> Vec<FieldSchema>;
     fn print_metadata(&self);
+
+    /// Current values keyed by field name, as the TUI would pass them
+    /// (unset options are left out, flags are "true"/"false")
+    ///
+    /// The derive implements this; the default has no values, so
+    /// hand-written definitions that don't override it fail `validate()` on
+    /// their required fields.
+    fn field_values(&self) -> HashMap<String, String> {
+        HashMap::new()
+    }
+
+    /// Check the values against the schema from `fields()`, the same rules
+    /// the TUI applies before launching
+    fn validate(&self) -> Result<(), Vec<FieldError>> {
+        validation::validate_values(&Self::fields(), &self.field_values())
+    }
+
+    /// Like [`validate`](Self::validate), but print each error to stderr and
+    /// exit with status 2 if any field is invalid
+    fn validate_or_exit(&self) {
+        if let Err(errors) = self.validate() {
+            for error in &errors {
+                eprintln!("❌ {}", error);
+            }
+            std::process::exit(2);
+        }
+    }
+}
+
+/// Support code for the `WorkflowDefinition` derive; not a public API
+#[doc(hidden)]
+pub mod __private {
+    use std::fmt::{Debug, Display};
+
+    /// Field value rendered with `Display` when the type has it and with
+    /// `Debug` otherwise: `(&FieldValue(&value)).render()` resolves to
+    /// [`DisplayValue`] first, since it needs one autoref less
+    pub struct FieldValue<'a, T>(pub &'a T);
+
+    pub trait DisplayValue {
+        fn render(&self) -> String;
+    }
+
+    impl<T: Display> DisplayValue for FieldValue<'_, T> {
+        fn render(&self) -> String {
+            self.0.to_string()
+        }
+    }
+
+    pub trait DebugValue {
+        fn render(&self) -> String;
+    }
+
+    impl<T: Debug> DebugValue for &FieldValue<'_, T> {
+        fn render(&self) -> String {
+            format!("{:?}", self.0)
+        }
+    }
 }
 
 /// Workflow status for TUI tracking
+///
+/// Non-exhaustive, so runtimes can report new states without breaking
+/// consumers; match with a fallback arm.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum WorkflowStatus {
     NotStarted,
     Running,
+    /// Suspended with `pause_workflow`; still holds its process and slot
+    Paused,
     Completed,
     Failed,
 }
@@ -121,9 +285,63 @@ pub struct Workflow {
     pub source: WorkflowSource,
 }
 
+/// Pipeline workflow definition: runs other workflows in sequence
+///
+/// Loaded from JSON files in `~/.config/workflow-manager/pipelines/`. Each
+/// stage is surfaced as a phase of the pipeline execution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineDefinition {
+    #[serde(flatten)]
+    pub metadata: WorkflowMetadata,
+    /// Pipeline-level inputs, referenced by stages via `StageInput::Param`
+    #[serde(default)]
+    pub fields: Vec<FieldSchema>,
+    pub stages: Vec<PipelineStage>,
+    /// Keep running later stages after a stage fails
+    #[serde(default)]
+    pub continue_on_failure: bool,
+}
+
+/// A single stage of a pipeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineStage {
+    /// Id of the workflow to run for this stage
+    pub workflow_id: String,
+    /// Display name for the stage phase (defaults to the workflow name)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Stage field name -> where its value comes from
+    #[serde(default)]
+    pub inputs: HashMap<String, StageInput>,
+    /// Overrides the pipeline-level `continue_on_failure` for this stage
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub continue_on_failure: Option<bool>,
+}
+
+/// Source of a stage input value
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "from", rename_all = "snake_case")]
+pub enum StageInput {
+    /// Literal value
+    Value { value: String },
+    /// Value of a pipeline-level field
+    Param { name: String },
+    /// Last state file created by an earlier stage, optionally filtered by
+    /// a substring of its description
+    StateFile {
+        stage: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+    },
+}
+
 /// Structured logging events emitted by workflows
+///
+/// Non-exhaustive, so new kinds of events (like `Result`) can be added
+/// without breaking consumers; match with a fallback arm.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
 pub enum WorkflowLog {
     /// Phase started
     PhaseStarted {
@@ -165,16 +383,27 @@ pub enum WorkflowLog {
         error: String,
     },
     /// Agent started (sub-agent within a task)
+    ///
+    /// An agent started by another agent of the task names it in
+    /// `parent_agent` and is shown nested under it. Without a parent, the
+    /// agent sits directly under the task.
     AgentStarted {
         task_id: String,
         agent_name: String,
         description: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        parent_agent: Option<String>,
     },
     /// Agent message (streaming text)
+    ///
+    /// A message from an agent that was never started, but names its
+    /// `parent_agent`, starts it under that parent.
     AgentMessage {
         task_id: String,
         agent_name: String,
         message: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        parent_agent: Option<String>,
     },
     /// Agent completed
     AgentCompleted {
@@ -199,18 +428,138 @@ pub enum WorkflowLog {
         stream: String,  // "stdout" or "stderr"
         line: String,
     },
+    /// Numeric measurement for a task (counters, rates, progress)
+    ///
+    /// A metric named "progress" with a value in 0.0-1.0 is drawn as a gauge
+    /// next to the task.
+    Metric {
+        task_id: String,
+        name: String,
+        value: f64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        unit: Option<String>,
+    },
+    /// Token usage and cost of one or more Claude queries
+    ///
+    /// Each event is added to the execution's running totals, so workflows
+    /// that wrap several queries can report them one by one.
+    Usage {
+        input_tokens: u64,
+        output_tokens: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cost_usd: Option<f64>,
+    },
+    /// What the run produced, emitted once at the end
+    ///
+    /// The TUI shows it in a banner at the top of the tab, with the artifact
+    /// paths ready to open.
+    Result {
+        summary: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        artifacts: Vec<String>,
+    },
+}
+
+/// Severity of a log event, used to hide noisy output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// Level announced by the start of a text line, if any
+    ///
+    /// Recognizes prefixes such as `error:`, `[WARN]`, `Warning -` or
+    /// `DEBUG`, ignoring case and leading whitespace.
+    pub fn from_prefix(line: &str) -> Option<LogLevel> {
+        const KEYWORDS: [(&str, LogLevel); 8] = [
+            ("error", LogLevel::Error),
+            ("fatal", LogLevel::Error),
+            ("panic", LogLevel::Error),
+            ("warning", LogLevel::Warn),
+            ("warn", LogLevel::Warn),
+            ("info", LogLevel::Info),
+            ("debug", LogLevel::Debug),
+            ("trace", LogLevel::Debug),
+        ];
+
+        let word = line.trim_start().trim_start_matches('[');
+        KEYWORDS.iter().find_map(|(keyword, level)| {
+            let head = word.get(..keyword.len())?;
+            let rest = &word[keyword.len()..];
+            let whole_word = !rest.starts_with(|c: char| c.is_alphanumeric());
+            (head.eq_ignore_ascii_case(keyword) && whole_word).then_some(*level)
+        })
+    }
+
+    /// Next level in the Debug -> Info -> Warn -> Error -> Debug cycle
+    pub fn next(self) -> LogLevel {
+        match self {
+            LogLevel::Debug => LogLevel::Info,
+            LogLevel::Info => LogLevel::Warn,
+            LogLevel::Warn => LogLevel::Error,
+            LogLevel::Error => LogLevel::Debug,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LogLevel::Debug => "Debug",
+            LogLevel::Info => "Info",
+            LogLevel::Warn => "Warn",
+            LogLevel::Error => "Error",
+        }
+    }
 }
 
 impl WorkflowLog {
-    /// Emit this log event to stderr for TUI parsing
-    pub fn emit(&self) {
-        if let Ok(json) = serde_json::to_string(self) {
-            use std::io::Write;
-            eprintln!("__WF_EVENT__:{}", json);
-            // Force flush stderr in async/concurrent contexts
-            let _ = std::io::stderr().flush();
+    /// Severity of this event
+    ///
+    /// Failures are errors and metrics are debug output. Raw output counts
+    /// as debug and messages as info unless their text starts with a level
+    /// (see [`LogLevel::from_prefix`]).
+    pub fn level(&self) -> LogLevel {
+        match self {
+            WorkflowLog::PhaseFailed { .. }
+            | WorkflowLog::TaskFailed { .. }
+            | WorkflowLog::AgentFailed { .. } => LogLevel::Error,
+            WorkflowLog::RawOutput { line, .. } => {
+                LogLevel::from_prefix(line).unwrap_or(LogLevel::Debug)
+            }
+            WorkflowLog::TaskProgress { message, .. }
+            | WorkflowLog::AgentMessage { message, .. } => {
+                LogLevel::from_prefix(message).unwrap_or(LogLevel::Info)
+            }
+            WorkflowLog::Metric { .. } | WorkflowLog::Usage { .. } => LogLevel::Debug,
+            _ => LogLevel::Info,
         }
     }
+
+    /// Emit this log event to stderr for TUI parsing, after the
+    /// [`event_prefix`] and in the [`event_framing`]
+    pub fn emit(&self) {
+        // Flushed too, for async/concurrent contexts
+        let mut stderr = std::io::stderr().lock();
+        let _ = write_event(&mut stderr, self, event_prefix(), event_framing());
+    }
+}
+
+/// Control messages the runtime sends a workflow, the inbound counterpart of
+/// [`WorkflowLog`]
+///
+/// They are written to the workflow's stdin as one JSON object per line,
+/// e.g. `{"cancel_task":"research_3"}`. Workflows opt in by reading stdin;
+/// the others never see them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkflowControl {
+    /// Stop the task with this id (as in `TaskStarted`), leaving the rest of
+    /// the run alone
+    CancelTask(String),
 }
 
 /// Helper macros for workflow logging
@@ -318,6 +667,16 @@ macro_rules! log_agent_start {
             task_id: $task_id.to_string(),
             agent_name: $agent.to_string(),
             description: $desc.to_string(),
+            parent_agent: None,
+        }
+        .emit();
+    };
+    ($task_id:expr, $agent:expr, $desc:expr, parent = $parent:expr) => {
+        $crate::WorkflowLog::AgentStarted {
+            task_id: $task_id.to_string(),
+            agent_name: $agent.to_string(),
+            description: $desc.to_string(),
+            parent_agent: Some($parent.to_string()),
         }
         .emit();
     };
@@ -330,6 +689,16 @@ macro_rules! log_agent_message {
             task_id: $task_id.to_string(),
             agent_name: $agent.to_string(),
             message: $msg.to_string(),
+            parent_agent: None,
+        }
+        .emit();
+    };
+    ($task_id:expr, $agent:expr, $msg:expr, parent = $parent:expr) => {
+        $crate::WorkflowLog::AgentMessage {
+            task_id: $task_id.to_string(),
+            agent_name: $agent.to_string(),
+            message: $msg.to_string(),
+            parent_agent: Some($parent.to_string()),
         }
         .emit();
     };
@@ -379,6 +748,66 @@ macro_rules! log_state_file {
     };
 }
 
+#[macro_export]
+macro_rules! log_metric {
+    ($task_id:expr, $name:expr, $value:expr) => {
+        $crate::WorkflowLog::Metric {
+            task_id: $task_id.to_string(),
+            name: $name.to_string(),
+            value: $value as f64,
+            unit: None,
+        }
+        .emit();
+    };
+    ($task_id:expr, $name:expr, $value:expr, $unit:expr) => {
+        $crate::WorkflowLog::Metric {
+            task_id: $task_id.to_string(),
+            name: $name.to_string(),
+            value: $value as f64,
+            unit: Some($unit.to_string()),
+        }
+        .emit();
+    };
+}
+
+#[macro_export]
+macro_rules! log_usage {
+    ($input:expr, $output:expr) => {
+        $crate::WorkflowLog::Usage {
+            input_tokens: $input as u64,
+            output_tokens: $output as u64,
+            cost_usd: None,
+        }
+        .emit();
+    };
+    ($input:expr, $output:expr, $cost:expr) => {
+        $crate::WorkflowLog::Usage {
+            input_tokens: $input as u64,
+            output_tokens: $output as u64,
+            cost_usd: Some($cost as f64),
+        }
+        .emit();
+    };
+}
+
+#[macro_export]
+macro_rules! log_result {
+    ($summary:expr) => {
+        $crate::WorkflowLog::Result {
+            summary: $summary.to_string(),
+            artifacts: Vec::new(),
+        }
+        .emit();
+    };
+    ($summary:expr, $artifacts:expr) => {
+        $crate::WorkflowLog::Result {
+            summary: $summary.to_string(),
+            artifacts: $artifacts.into_iter().map(|a| a.to_string()).collect(),
+        }
+        .emit();
+    };
+}
+
 /// Workflow execution handle for tracking async execution
 #[derive(Debug, Clone)]
 pub struct WorkflowHandle {
@@ -396,9 +825,6 @@ impl WorkflowHandle {
     }
 }
 
-/// Result type for workflow operations
-pub type WorkflowResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
-
 /// Lightweight execution summary for listing (excludes logs and params)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecutionSummary {
@@ -409,10 +835,34 @@ pub struct ExecutionSummary {
     pub start_time: DateTime<Local>,
     pub end_time: Option<DateTime<Local>>,
     pub exit_code: Option<i32>,
+    /// Token usage totals, if the workflow reported any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_tokens: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_tokens: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_cost_usd: Option<f64>,
+}
+
+/// Log events of an execution read after a cursor, see
+/// [`WorkflowRuntime::get_logs_since`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogPage {
+    /// Events after the requested index, oldest first
+    pub logs: Vec<WorkflowLog>,
+    /// Index of the last event recorded so far (None while there are none);
+    /// pass it as `after_index` to get only newer events next time
+    pub latest_index: Option<usize>,
+    /// Status at the time of the read, to know when to stop following
+    pub status: WorkflowStatus,
 }
 
 /// Runtime trait for workflow discovery and execution
 /// This provides a unified API for both TUI and MCP consumers
+///
+/// Methods added after the first release have default bodies, so existing
+/// runtimes keep compiling: optional operations fail with an "unsupported"
+/// [`WorkflowError::Other`], and the rest are built on the required methods.
 #[async_trait]
 pub trait WorkflowRuntime: Send + Sync {
     /// List all discovered workflows with metadata
@@ -428,6 +878,19 @@ pub trait WorkflowRuntime: Send + Sync {
         params: HashMap<String, String>,
     ) -> WorkflowResult<()>;
 
+    /// Resolve the command `execute_workflow` would run, without running it
+    ///
+    /// Returns a shell-style command line with the working directory and
+    /// environment changes, one line per stage for pipelines.
+    fn preview_command(
+        &self,
+        id: &str,
+        params: HashMap<String, String>,
+    ) -> WorkflowResult<String> {
+        let _ = (id, params);
+        Err(unsupported("preview_command"))
+    }
+
     /// Execute a workflow asynchronously
     async fn execute_workflow(
         &self,
@@ -441,15 +904,107 @@ pub trait WorkflowRuntime: Send + Sync {
         handle_id: &Uuid,
     ) -> WorkflowResult<tokio::sync::broadcast::Receiver<WorkflowLog>>;
 
+    /// Subscribe to logs together with every event recorded so far
+    ///
+    /// The snapshot and the receiver are taken atomically: each event is
+    /// either in the snapshot or the first ones the receiver yields, never
+    /// both and never neither, and the snapshot comes first in log order.
+    /// A subscriber that gets `RecvError::Lagged` can call this again and
+    /// rebuild its state from the new snapshot.
+    ///
+    /// The default subscribes before reading [`get_logs`](Self::get_logs),
+    /// so nothing is missed, but an event published in between is both in
+    /// the snapshot and the first one the receiver yields.
+    async fn subscribe_logs_with_backlog(
+        &self,
+        handle_id: &Uuid,
+    ) -> WorkflowResult<(Vec<WorkflowLog>, tokio::sync::broadcast::Receiver<WorkflowLog>)> {
+        let logs_rx = self.subscribe_logs(handle_id).await?;
+        let logs = self.get_logs(handle_id, None).await?;
+        Ok((logs, logs_rx))
+    }
+
     /// Get historical logs from a workflow execution (for MCP tools)
     async fn get_logs(&self, handle_id: &Uuid, limit: Option<usize>) -> WorkflowResult<Vec<WorkflowLog>>;
 
+    /// Get the log events recorded after index `after_index` (all of them
+    /// when None), to follow an execution without re-reading old events
+    ///
+    /// The default reads every event with [`get_logs`](Self::get_logs).
+    async fn get_logs_since(
+        &self,
+        handle_id: &Uuid,
+        after_index: Option<usize>,
+    ) -> WorkflowResult<LogPage> {
+        let status = self.get_status(handle_id).await?;
+        let logs = self.get_logs(handle_id, None).await?;
+        let start = after_index.map_or(0, |index| index + 1);
+        Ok(LogPage {
+            latest_index: logs.len().checked_sub(1),
+            logs: logs.get(start..).unwrap_or_default().to_vec(),
+            status,
+        })
+    }
+
     /// Get current status of a running workflow
+    ///
+    /// Once the process exits, its exit code decides between `Completed`
+    /// (zero) and `Failed` (anything else), even if it logged no failure.
     async fn get_status(&self, handle_id: &Uuid) -> WorkflowResult<WorkflowStatus>;
 
+    /// List the executions still in flight (queued, running or paused) as
+    /// `(handle id, workflow id, status)`, oldest first
+    async fn list_running(&self) -> WorkflowResult<Vec<(Uuid, String, WorkflowStatus)>> {
+        Err(unsupported("list_running"))
+    }
+
+    /// Wait until an execution finishes and return its final status
+    ///
+    /// Returns at once for an execution that already finished, including one
+    /// that was cancelled. The default polls [`get_status`](Self::get_status);
+    /// runtimes that are told of status changes should override it.
+    async fn wait_for_completion(&self, handle_id: &Uuid) -> WorkflowResult<WorkflowStatus> {
+        loop {
+            match self.get_status(handle_id).await? {
+                WorkflowStatus::NotStarted | WorkflowStatus::Running | WorkflowStatus::Paused => {
+                    tokio::time::sleep(WAIT_POLL_INTERVAL).await
+                }
+                status => return Ok(status),
+            }
+        }
+    }
+
+    /// Get the summary of an execution, including its exit code once finished
+    async fn get_execution(&self, handle_id: &Uuid) -> WorkflowResult<ExecutionSummary> {
+        let _ = handle_id;
+        Err(unsupported("get_execution"))
+    }
+
     /// Cancel a running workflow
     async fn cancel_workflow(&self, handle_id: &Uuid) -> WorkflowResult<()>;
 
+    /// Suspend a running workflow without killing it; its status becomes
+    /// `Paused` until [`resume_workflow`](Self::resume_workflow)
+    async fn pause_workflow(&self, handle_id: &Uuid) -> WorkflowResult<()> {
+        let _ = handle_id;
+        Err(unsupported("pause_workflow"))
+    }
+
+    /// Continue a paused workflow where it stopped
+    async fn resume_workflow(&self, handle_id: &Uuid) -> WorkflowResult<()> {
+        let _ = handle_id;
+        Err(unsupported("resume_workflow"))
+    }
+
+    /// Ask a running workflow to cancel one of its tasks
+    ///
+    /// Sends [`WorkflowControl::CancelTask`]; it only has an effect if the
+    /// workflow reads control messages from stdin.
+    async fn cancel_task(&self, handle_id: &Uuid, task_id: &str) -> WorkflowResult<()> {
+        let _ = (handle_id, task_id);
+        Err(unsupported("cancel_task"))
+    }
+
     /// List workflow executions with pagination and optional filtering
     ///
     /// # Arguments
@@ -474,4 +1029,88 @@ pub trait WorkflowRuntime: Send + Sync {
     /// # Returns
     /// HashMap of parameter names to values used in the execution
     async fn get_params(&self, handle_id: &Uuid) -> WorkflowResult<HashMap<String, String>>;
+
+    /// Export the full log of a workflow execution as newline-delimited JSON
+    ///
+    /// The first line is the execution's `ExecutionSummary`, so the file is
+    /// self-describing; every following line is one `WorkflowLog`.
+    async fn export_logs(&self, handle_id: &Uuid, path: &Path) -> WorkflowResult<()> {
+        let summary = self.get_execution(handle_id).await?;
+        let logs = self.get_logs(handle_id, None).await?;
+
+        let mut output = serde_json::to_string(&summary).map_err(WorkflowError::other)?;
+        output.push('\n');
+        for log in &logs {
+            output.push_str(&serde_json::to_string(log).map_err(WorkflowError::other)?);
+            output.push('\n');
+        }
+
+        std::fs::write(path, output).map_err(|e| {
+            WorkflowError::other(format!("Failed to write {}: {}", path.display(), e))
+        })
+    }
+}
+
+/// How often the default [`WorkflowRuntime::wait_for_completion`] checks the status
+const WAIT_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Error of a [`WorkflowRuntime`] method the runtime does not implement
+fn unsupported(operation: &str) -> WorkflowError {
+    WorkflowError::other(format!("{} is not supported by this runtime", operation))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Removes its environment variable when dropped, even if the test fails
+    struct EnvVarGuard(String);
+
+    impl Drop for EnvVarGuard {
+        fn drop(&mut self) {
+            std::env::remove_var(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_resolved_default_prefers_environment() {
+        // Unique, so tests running in parallel never see it
+        let var = EnvVarGuard(format!("WF_TEST_DEFAULT_{}", Uuid::new_v4().simple()));
+        let mut field = FieldSchema {
+            default: Some(".".to_string()),
+            ..FieldSchema::new("target", FieldType::Text)
+        };
+        assert_eq!(field.resolved_default().as_deref(), Some("."));
+
+        field.default_env = Some(var.0.clone());
+        assert_eq!(field.resolved_default().as_deref(), Some("."));
+        std::env::set_var(&var.0, "/srv/projects");
+        assert_eq!(field.resolved_default().as_deref(), Some("/srv/projects"));
+        std::env::set_var(&var.0, "");
+        assert_eq!(field.resolved_default().as_deref(), Some("."));
+    }
+
+    #[test]
+    fn test_result_artifacts_are_optional() {
+        let log: WorkflowLog =
+            serde_json::from_str(r#"{"type":"result","summary":"Done"}"#).unwrap();
+        assert!(matches!(&log, WorkflowLog::Result { artifacts, .. } if artifacts.is_empty()));
+        assert_eq!(serde_json::to_string(&log).unwrap(), r#"{"type":"result","summary":"Done"}"#);
+        assert_eq!(log.level(), LogLevel::Info);
+    }
+
+    #[test]
+    #[allow(clippy::needless_borrow)] // The derive always borrows, as Debug needs it
+    fn test_field_value_falls_back_to_debug() {
+        use __private::{DebugValue as _, DisplayValue as _, FieldValue};
+
+        #[derive(Debug)]
+        enum Mode {
+            Fast,
+        }
+
+        assert_eq!((&FieldValue(&"text")).render(), "text");
+        assert_eq!((&FieldValue(&3u32)).render(), "3");
+        assert_eq!((&FieldValue(&Mode::Fast)).render(), "Fast");
+    }
 }
@@ -0,0 +1,277 @@
+//! Framing of [`WorkflowLog`] events on a workflow's stderr
+//!
+//! Each event is one line: the event prefix followed by the JSON of the
+//! event. Everything else on stderr is raw output. The prefix can be changed
+//! with `WF_EVENT_PREFIX`, so a workflow that prints the default one as part
+//! of its own output can pick a less likely sentinel. Workflows inherit the
+//! environment of the runtime, so both sides agree.
+//!
+//! With `WF_EVENT_FRAMING=base64`, the JSON is base64-encoded and split into
+//! frames of the form `<prefix>b64:<index>/<count>:<chunk>`. Every frame is
+//! written at once and is small enough for the pipe to keep it whole, so raw
+//! output of other writers can only land between frames, never inside one.
+//! [`EventDecoder`] reads both framings.
+
+use std::io::Write;
+use std::sync::OnceLock;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+
+use crate::WorkflowLog;
+
+/// Prefix of event lines unless `WF_EVENT_PREFIX` is set
+pub const EVENT_PREFIX: &str = "__WF_EVENT__:";
+
+/// Environment variable overriding [`EVENT_PREFIX`]
+pub const EVENT_PREFIX_ENV: &str = "WF_EVENT_PREFIX";
+
+/// Environment variable selecting the framing, `line` (default) or `base64`
+pub const EVENT_FRAMING_ENV: &str = "WF_EVENT_FRAMING";
+
+/// Marker after the prefix of a base64 frame
+const BASE64_MARKER: &str = "b64:";
+
+/// Most base64 characters in one frame, which keeps frames below the
+/// 4096 bytes a pipe writes atomically
+const FRAME_CHUNK: usize = 3072;
+
+/// How events are written to stderr
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventFraming {
+    /// One line of JSON per event
+    Line,
+    /// Base64 frames that survive interleaved writes
+    Base64,
+}
+
+/// Prefix of event lines in this process, read once from the environment
+pub fn event_prefix() -> &'static str {
+    static PREFIX: OnceLock<String> = OnceLock::new();
+    PREFIX.get_or_init(|| {
+        std::env::var(EVENT_PREFIX_ENV)
+            .ok()
+            .filter(|prefix| !prefix.is_empty())
+            .unwrap_or_else(|| EVENT_PREFIX.to_string())
+    })
+}
+
+/// Framing of events emitted by this process, read once from the
+/// environment
+pub fn event_framing() -> EventFraming {
+    static FRAMING: OnceLock<EventFraming> = OnceLock::new();
+    *FRAMING.get_or_init(|| match std::env::var(EVENT_FRAMING_ENV) {
+        Ok(value) if value.trim().eq_ignore_ascii_case("base64") => EventFraming::Base64,
+        _ => EventFraming::Line,
+    })
+}
+
+/// Lines that carry `log` on stderr, each with its newline
+///
+/// Each line is meant for a single write, see [`WorkflowLog::emit`].
+pub fn frame_event(log: &WorkflowLog, prefix: &str, framing: EventFraming) -> Vec<String> {
+    let Ok(json) = serde_json::to_string(log) else {
+        return Vec::new();
+    };
+    match framing {
+        EventFraming::Line => vec![format!("{}{}\n", prefix, json)],
+        EventFraming::Base64 => {
+            let encoded = BASE64.encode(json.as_bytes());
+            let chunks: Vec<&str> = (0..encoded.len())
+                .step_by(FRAME_CHUNK)
+                .map(|start| &encoded[start..encoded.len().min(start + FRAME_CHUNK)])
+                .collect();
+            chunks
+                .iter()
+                .enumerate()
+                .map(|(index, chunk)| {
+                    format!("{}{}{}/{}:{}\n", prefix, BASE64_MARKER, index, chunks.len(), chunk)
+                })
+                .collect()
+        }
+    }
+}
+
+/// Write the frames of `log` through one locked handle, one write per frame
+///
+/// Holding the lock keeps the frames of concurrent emitters in this process
+/// apart; each write is small enough to stay whole in the pipe.
+pub fn write_event(
+    out: &mut impl Write,
+    log: &WorkflowLog,
+    prefix: &str,
+    framing: EventFraming,
+) -> std::io::Result<()> {
+    for frame in frame_event(log, prefix, framing) {
+        out.write_all(frame.as_bytes())?;
+    }
+    out.flush()
+}
+
+/// Event on a stderr line that starts with `prefix`
+///
+/// A line that merely contains the prefix, or whose JSON is not an event,
+/// is raw output and yields `None`.
+pub fn parse_event_line(line: &str, prefix: &str) -> Option<WorkflowLog> {
+    serde_json::from_str(line.strip_prefix(prefix)?).ok()
+}
+
+/// Part of a stderr line, as read by [`EventDecoder`]
+#[derive(Debug, Clone)]
+pub enum StderrLine {
+    Event(WorkflowLog),
+    Raw(String),
+}
+
+/// Reads events in either framing from the lines of a workflow's stderr
+///
+/// Base64 frames are collected until their event is complete. They are also
+/// found after raw output that did not end its line, since base64 never
+/// contains the prefix.
+#[derive(Debug)]
+pub struct EventDecoder {
+    prefix: String,
+    frame_marker: String,
+    chunks: Vec<String>,
+}
+
+impl EventDecoder {
+    pub fn new(prefix: &str) -> Self {
+        Self {
+            prefix: prefix.to_string(),
+            frame_marker: format!("{}{}", prefix, BASE64_MARKER),
+            chunks: Vec::new(),
+        }
+    }
+
+    /// Raw output and completed event, in this order, of one stderr line
+    pub fn push_line(&mut self, line: &str) -> Vec<StderrLine> {
+        let Some(start) = line.find(&self.frame_marker) else {
+            return vec![match parse_event_line(line, &self.prefix) {
+                Some(log) => StderrLine::Event(log),
+                None => StderrLine::Raw(line.to_string()),
+            }];
+        };
+
+        let mut parts = Vec::new();
+        if start > 0 {
+            parts.push(StderrLine::Raw(line[..start].to_string()));
+        }
+        let frame = &line[start + self.frame_marker.len()..];
+        match self.push_frame(frame) {
+            Ok(Some(log)) => parts.push(StderrLine::Event(log)),
+            Ok(None) => {}
+            Err(()) => parts.push(StderrLine::Raw(line[start..].to_string())),
+        }
+        parts
+    }
+
+    /// Add `<index>/<count>:<chunk>`, returning the event once complete
+    ///
+    /// A frame that does not continue the pending event starts over; one
+    /// that is not a frame at all is an error.
+    fn push_frame(&mut self, frame: &str) -> Result<Option<WorkflowLog>, ()> {
+        let (position, chunk) = frame.split_once(':').ok_or(())?;
+        let (index, count) = position.split_once('/').ok_or(())?;
+        let index: usize = index.parse().map_err(|_| ())?;
+        let count: usize = count.parse().map_err(|_| ())?;
+        let base64_byte = |b: u8| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'=');
+        if index >= count || !chunk.bytes().all(base64_byte) {
+            return Err(());
+        }
+
+        if index != self.chunks.len() {
+            self.chunks.clear();
+            if index != 0 {
+                return Ok(None);
+            }
+        }
+        self.chunks.push(chunk.to_string());
+        if self.chunks.len() < count {
+            return Ok(None);
+        }
+
+        let encoded = std::mem::take(&mut self.chunks).concat();
+        let json = BASE64.decode(encoded).map_err(|_| ())?;
+        serde_json::from_slice(&json).map(Some).map_err(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_event_line_requires_prefix_at_start() {
+        let event = r#"{"type":"phase_started","phase":0,"name":"Analyze","total_phases":3}"#;
+
+        let line = format!("{}{}", EVENT_PREFIX, event);
+        assert!(matches!(
+            parse_event_line(&line, EVENT_PREFIX),
+            Some(WorkflowLog::PhaseStarted { phase: 0, .. })
+        ));
+
+        let quoted = format!("echo {}{}", EVENT_PREFIX, event);
+        assert!(parse_event_line(&quoted, EVENT_PREFIX).is_none());
+        let not_json = format!("{}see docs", EVENT_PREFIX);
+        assert!(parse_event_line(&not_json, EVENT_PREFIX).is_none());
+
+        let custom = format!("@@wf@@{}", event);
+        assert!(parse_event_line(&custom, "@@wf@@").is_some());
+        assert!(parse_event_line(&custom, EVENT_PREFIX).is_none());
+    }
+
+    #[test]
+    fn test_write_event_writes_every_frame() {
+        let log = WorkflowLog::PhaseStarted {
+            phase: 0,
+            name: "Analyze".to_string(),
+            total_phases: 1,
+        };
+        let mut out = Vec::new();
+        write_event(&mut out, &log, EVENT_PREFIX, EventFraming::Base64).unwrap();
+        let frames = frame_event(&log, EVENT_PREFIX, EventFraming::Base64);
+        assert_eq!(String::from_utf8(out).unwrap(), frames.concat());
+
+        let mut decoder = EventDecoder::new(EVENT_PREFIX);
+        let parts = decoder.push_line(frames[0].trim_end());
+        assert!(matches!(parts[..], [StderrLine::Event(WorkflowLog::PhaseStarted { .. })]));
+    }
+
+    #[test]
+    fn test_base64_frames_survive_interleaved_writes() {
+        let log = WorkflowLog::AgentMessage {
+            task_id: "t1".to_string(),
+            agent_name: "writer".to_string(),
+            message: "x".repeat(2 * FRAME_CHUNK),
+            parent_agent: None,
+        };
+        let frames = frame_event(&log, EVENT_PREFIX, EventFraming::Base64);
+        assert_eq!(frames.len(), 3);
+
+        // Raw writes land before, between and after the frames, one of them
+        // without ending its line
+        let mut stream = String::from("progress 10%");
+        for frame in &frames {
+            stream.push_str(frame);
+            stream.push_str("raw line\n");
+        }
+        stream.push_str(&frame_event(&log, EVENT_PREFIX, EventFraming::Line)[0]);
+
+        let mut decoder = EventDecoder::new(EVENT_PREFIX);
+        let mut events = 0;
+        let mut raw = Vec::new();
+        for part in stream.lines().flat_map(|line| decoder.push_line(line)) {
+            match part {
+                StderrLine::Event(WorkflowLog::AgentMessage { message, .. }) => {
+                    assert_eq!(message.len(), 2 * FRAME_CHUNK);
+                    events += 1;
+                }
+                StderrLine::Event(other) => panic!("unexpected event {:?}", other),
+                StderrLine::Raw(line) => raw.push(line),
+            }
+        }
+        assert_eq!(events, 2);
+        assert_eq!(raw, vec!["progress 10%", "raw line", "raw line", "raw line"]);
+    }
+}
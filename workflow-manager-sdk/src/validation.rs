@@ -0,0 +1,396 @@
+//! Field value validation against workflow schemas
+//!
+//! Shared by the TUI (before launching, see `App::validate_launch`), by
+//! `ProcessBasedRuntime::validate_workflow_inputs` and by the `validate()`
+//! method the `WorkflowDefinition` derive gives workflow binaries.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use crate::{FieldSchema, FieldType};
+
+/// Parameter holding the working directory of the workflow process, which
+/// relative file paths are resolved against
+pub const CWD_PARAM: &str = "cwd";
+
+/// A field whose value does not satisfy its schema
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldError {
+    /// Name of the field (e.g. "batch_size")
+    pub field: String,
+    pub message: String,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+impl std::error::Error for FieldError {}
+
+/// Whether `field` must be filled in, given the other field values
+///
+/// Fields with `required_for_phases` are only required when the earliest
+/// selected phase needs them (e.g. phases "1,2,3" only needs phase 1 inputs).
+pub fn is_field_required(field: &FieldSchema, field_values: &HashMap<String, String>) -> bool {
+    match &field.required_for_phases {
+        Some(required_phases) => field_values
+            .get("phases")
+            .and_then(|v| {
+                v.split(',')
+                    .filter_map(|s| s.trim().parse::<usize>().ok())
+                    .min()
+            })
+            .map(|min_phase| required_phases.contains(&min_phase))
+            .unwrap_or(false),
+        None => field.required,
+    }
+}
+
+/// Whether `field` is required but has no value yet
+pub fn is_required_missing(field: &FieldSchema, field_values: &HashMap<String, String>) -> bool {
+    field_values
+        .get(&field.name)
+        .is_none_or(|value| value.trim().is_empty())
+        && is_field_required(field, field_values)
+}
+
+/// Validate a single field, returning a short error message if invalid
+pub fn validate_field(
+    field: &FieldSchema,
+    field_values: &HashMap<String, String>,
+) -> Option<String> {
+    let value = field_values
+        .get(&field.name)
+        .map(|v| v.trim())
+        .unwrap_or("");
+
+    if value.is_empty() {
+        return is_field_required(field, field_values).then(|| "Required".to_string());
+    }
+
+    let type_error = match &field.field_type {
+        FieldType::Number { min, max } => {
+            let Ok(number) = value.parse::<i64>() else {
+                return Some(format!("'{}' is not a number", value));
+            };
+            // Bounds on the schema take precedence over the ones in the type
+            if let Some(min) = field.min.or(*min).filter(|min| number < *min) {
+                return Some(format!("Must be at least {}", min));
+            }
+            if let Some(max) = field.max.or(*max).filter(|max| number > *max) {
+                return Some(format!("Must be at most {}", max));
+            }
+            None
+        }
+        // File patterns are globs used by the file pickers, not value checks,
+        // but a path picked for a pattern must exist where the workflow runs
+        FieldType::FilePath { pattern: Some(_) } => (!resolve_path(value, field_values).exists())
+            .then(|| format!("File not found: {}", value)),
+        FieldType::FilePath { .. } | FieldType::StateFile { .. } => None,
+        FieldType::Select { options } => (!options.iter().any(|o| o == value))
+            .then(|| format!("Must be one of: {}", options.join(", "))),
+        FieldType::MultiSelect { options } => {
+            let unknown: Vec<&str> = value
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !options.iter().any(|o| o == s))
+                .collect();
+            (!unknown.is_empty()).then(|| {
+                format!(
+                    "Unknown option(s) {}; expected any of: {}",
+                    unknown.join(", "),
+                    options.join(", ")
+                )
+            })
+        }
+        FieldType::Boolean { .. } => (value != "true" && value != "false")
+            .then(|| "Must be \"true\" or \"false\"".to_string()),
+        FieldType::PhaseSelector { total_phases } => {
+            let invalid: Vec<&str> = value
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !matches!(s.parse::<usize>(), Ok(p) if p < *total_phases))
+                .collect();
+            (!invalid.is_empty()).then(|| {
+                format!(
+                    "Invalid phase(s) {}; expected 0-{}",
+                    invalid.join(", "),
+                    total_phases.saturating_sub(1)
+                )
+            })
+        }
+        _ => None,
+    };
+
+    // A value of the right type must also match the field's pattern
+    type_error.or_else(|| validate_pattern(field, value))
+}
+
+/// `path` as the workflow process sees it: relative paths are resolved
+/// against the `cwd` parameter when one is set
+fn resolve_path(path: &str, field_values: &HashMap<String, String>) -> PathBuf {
+    let cwd = field_values
+        .get(CWD_PARAM)
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty());
+    match cwd {
+        Some(dir) => Path::new(dir).join(path),
+        None => PathBuf::from(path),
+    }
+}
+
+/// Check `value` against the field's regex `pattern`, which must match the
+/// whole value
+fn validate_pattern(field: &FieldSchema, value: &str) -> Option<String> {
+    let pattern = field.pattern.as_deref()?;
+    match compiled_pattern(pattern) {
+        Ok(regex) if regex.is_match(value) => None,
+        Ok(_) => Some(format!("Must match pattern {}", pattern)),
+        Err(e) => Some(format!("Invalid pattern {}: {}", pattern, e)),
+    }
+}
+
+/// Regex of a field pattern, compiled on first use and cached since fields
+/// are validated on every edit
+fn compiled_pattern(pattern: &str) -> Result<Regex, regex::Error> {
+    static PATTERNS: OnceLock<Mutex<HashMap<String, Regex>>> = OnceLock::new();
+    let mut patterns = PATTERNS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    if let Some(regex) = patterns.get(pattern) {
+        return Ok(regex.clone());
+    }
+    let regex = Regex::new(&format!("^(?:{})$", pattern))?;
+    patterns.insert(pattern.to_string(), regex.clone());
+    Ok(regex)
+}
+
+/// Validate every field, returning errors keyed by field name
+pub fn validate_fields(
+    fields: &[FieldSchema],
+    field_values: &HashMap<String, String>,
+) -> BTreeMap<String, String> {
+    fields
+        .iter()
+        .filter_map(|field| {
+            validate_field(field, field_values).map(|error| (field.name.clone(), error))
+        })
+        .collect()
+}
+
+/// Validate every field, failing with one error per invalid field in schema
+/// order
+pub fn validate_values(
+    fields: &[FieldSchema],
+    field_values: &HashMap<String, String>,
+) -> Result<(), Vec<FieldError>> {
+    let errors: Vec<FieldError> = fields
+        .iter()
+        .filter_map(|field| {
+            validate_field(field, field_values).map(|message| FieldError {
+                field: field.name.clone(),
+                message,
+            })
+        })
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_required_field() {
+        let input = FieldSchema {
+            required: true,
+            ..FieldSchema::new("input", FieldType::Text)
+        };
+        assert_eq!(
+            validate_field(&input, &values(&[])).as_deref(),
+            Some("Required")
+        );
+        assert_eq!(
+            validate_field(&input, &values(&[("input", "  ")])).as_deref(),
+            Some("Required")
+        );
+        assert!(validate_field(&input, &values(&[("input", "x")])).is_none());
+    }
+
+    #[test]
+    fn test_required_for_earliest_phase() {
+        let mut analysis = FieldSchema::new("analysis_file", FieldType::Text);
+        analysis.required_for_phases = Some(vec![1, 2]);
+
+        assert!(validate_field(&analysis, &values(&[("phases", "0,1,2")])).is_none());
+        assert!(validate_field(&analysis, &values(&[("phases", "1,2,3")])).is_some());
+        assert!(validate_field(&analysis, &values(&[])).is_none());
+
+        // Changing the phases changes what is missing
+        assert!(!is_required_missing(&analysis, &values(&[("phases", "0,1")])));
+        assert!(is_required_missing(&analysis, &values(&[("phases", "1")])));
+        assert!(!is_required_missing(
+            &analysis,
+            &values(&[("phases", "1"), ("analysis_file", "a.yaml")])
+        ));
+    }
+
+    #[test]
+    fn test_number_bounds() {
+        let batch = FieldSchema::new(
+            "batch_size",
+            FieldType::Number {
+                min: Some(1),
+                max: Some(10),
+            },
+        );
+        assert!(validate_field(&batch, &values(&[("batch_size", "5")])).is_none());
+        assert!(validate_field(&batch, &values(&[("batch_size", "0")])).is_some());
+        assert!(validate_field(&batch, &values(&[("batch_size", "11")])).is_some());
+        assert!(validate_field(&batch, &values(&[("batch_size", "five")])).is_some());
+    }
+
+    #[test]
+    fn test_select_options() {
+        let options = vec!["debug".to_string(), "release".to_string()];
+        let profile = FieldSchema::new(
+            "profile",
+            FieldType::Select {
+                options: options.clone(),
+            },
+        );
+        assert!(validate_field(&profile, &values(&[("profile", "release")])).is_none());
+        assert!(validate_field(&profile, &values(&[("profile", "fast")])).is_some());
+
+        let targets = FieldSchema::new("targets", FieldType::MultiSelect { options });
+        assert!(validate_field(&targets, &values(&[("targets", "debug, release")])).is_none());
+        assert_eq!(
+            validate_field(&targets, &values(&[("targets", "debug,fast")])).as_deref(),
+            Some("Unknown option(s) fast; expected any of: debug, release")
+        );
+    }
+
+    #[test]
+    fn test_boolean_accepts_only_true_false() {
+        let flag = FieldSchema::new("stream", FieldType::Boolean { default: false });
+        assert!(validate_field(&flag, &values(&[("stream", "true")])).is_none());
+        assert!(validate_field(&flag, &values(&[("stream", "false")])).is_none());
+        assert!(validate_field(&flag, &values(&[("stream", "yes")])).is_some());
+    }
+
+    #[test]
+    fn test_file_path_resolves_against_cwd() {
+        let dir = std::env::temp_dir().join(format!("validation_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("input.yaml"), "").unwrap();
+        let cwd = dir.display().to_string();
+
+        let pattern = Some("*.yaml".to_string());
+        let input = FieldSchema::new("input", FieldType::FilePath { pattern });
+        assert!(validate_field(&input, &values(&[("input", "input.yaml")])).is_some());
+        assert!(
+            validate_field(&input, &values(&[("input", "input.yaml"), ("cwd", &cwd)])).is_none()
+        );
+        let absolute = dir.join("input.yaml").display().to_string();
+        assert!(validate_field(&input, &values(&[("input", &absolute), ("cwd", "/")])).is_none());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_phase_selector_range() {
+        let phases = FieldSchema::new("phases", FieldType::PhaseSelector { total_phases: 3 });
+        assert!(validate_field(&phases, &values(&[("phases", "0, 1,2")])).is_none());
+        assert_eq!(
+            validate_field(&phases, &values(&[("phases", "0,3,x")])).as_deref(),
+            Some("Invalid phase(s) 3, x; expected 0-2")
+        );
+    }
+
+    #[test]
+    fn test_schema_bounds_and_pattern() {
+        let mut count = FieldSchema::new(
+            "count",
+            FieldType::Number {
+                min: None,
+                max: None,
+            },
+        );
+        count.min = Some(2);
+        count.max = Some(4);
+        assert!(validate_field(&count, &values(&[("count", "3")])).is_none());
+        assert_eq!(
+            validate_field(&count, &values(&[("count", "5")])).as_deref(),
+            Some("Must be at most 4")
+        );
+
+        let mut branch = FieldSchema::new("branch", FieldType::Text);
+        branch.pattern = Some("[a-z0-9/-]+".to_string());
+        assert!(validate_field(&branch, &values(&[("branch", "feature/x-1")])).is_none());
+        assert_eq!(
+            validate_field(&branch, &values(&[("branch", "Feature X")])).as_deref(),
+            Some("Must match pattern [a-z0-9/-]+")
+        );
+
+        // The type is checked first, then the pattern
+        let mut mode = FieldSchema::new(
+            "mode",
+            FieldType::Select {
+                options: vec!["fast".to_string(), "Slow".to_string()],
+            },
+        );
+        mode.pattern = Some("[a-z]+".to_string());
+        assert_eq!(
+            validate_field(&mode, &values(&[("mode", "medium")])).as_deref(),
+            Some("Must be one of: fast, Slow")
+        );
+        assert_eq!(
+            validate_field(&mode, &values(&[("mode", "Slow")])).as_deref(),
+            Some("Must match pattern [a-z]+")
+        );
+        assert!(validate_field(&mode, &values(&[("mode", "fast")])).is_none());
+    }
+
+    #[test]
+    fn test_validate_values_collects_errors_in_schema_order() {
+        let fields = vec![
+            FieldSchema {
+                required: true,
+                ..FieldSchema::new("input", FieldType::Text)
+            },
+            FieldSchema::new(
+                "count",
+                FieldType::Number {
+                    min: Some(1),
+                    max: Some(3),
+                },
+            ),
+        ];
+
+        assert!(validate_values(&fields, &values(&[("input", "x"), ("count", "2")])).is_ok());
+
+        let errors = validate_values(&fields, &values(&[("count", "9")])).unwrap_err();
+        assert_eq!(
+            errors.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec!["input: Required", "count: Must be at most 3"]
+        );
+    }
+}
@@ -131,6 +131,7 @@ use std::{
     sync::Arc,
 };
 use tokio::{fs, sync::Semaphore};
+use workflow_manager::workflow_utils::extract_code_block;
 
 // Use flexible YAML instead of rigid structs
 type CodebaseAnalysis = serde_yaml::Value;
@@ -866,17 +867,10 @@ Save the final documentation to: {}"#,
 
 /// Extract YAML from markdown code blocks
 fn extract_yaml(text: &str) -> String {
-    if text.contains("```yaml") {
-        let yaml_start = text.find("```yaml").unwrap() + 7;
-        let yaml_end = text[yaml_start..].find("```").unwrap() + yaml_start;
-        text[yaml_start..yaml_end].trim().to_string()
-    } else if text.contains("```") {
-        let yaml_start = text.find("```").unwrap() + 3;
-        let yaml_end = text[yaml_start..].find("```").unwrap() + yaml_start;
-        text[yaml_start..yaml_end].trim().to_string()
-    } else {
-        text.trim().to_string()
-    }
+    extract_code_block(text, "yaml")
+        .unwrap_or(text)
+        .trim()
+        .to_string()
 }
 
 /// Load file content or return literal string
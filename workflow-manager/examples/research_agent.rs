@@ -5,6 +5,7 @@ use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use tokio::fs;
+use workflow_manager::workflow_utils::extract_code_block;
 
 #[derive(Debug, Serialize, Deserialize)]
 struct ResearchPrompt {
@@ -30,18 +31,12 @@ struct ResearchResult {
 
 /// Clean YAML response by removing markdown code blocks, prose, and document separators
 fn clean_yaml(text: &str) -> anyhow::Result<String> {
-    // Step 1: Extract from markdown code blocks if present (like tasks_agent.rs)
-    let mut yaml = if text.contains("```yaml") {
-        let start = text.find("```yaml").unwrap() + 7;
-        let end = text[start..].find("```").unwrap() + start;
-        text[start..end].trim().to_string()
-    } else if text.contains("```") {
-        let start = text.find("```").unwrap() + 3;
-        let end = text[start..].find("```").unwrap() + start;
-        text[start..end].trim().to_string()
-    } else {
-        text.trim().to_string()
-    };
+    // Step 1: Extract from markdown code blocks if present, keeping any
+    // code blocks nested in the YAML
+    let mut yaml = extract_code_block(text, "yaml")
+        .unwrap_or(text)
+        .trim()
+        .to_string();
 
     // Step 2: Find where actual YAML starts (look for "objective:")
     // This handles prose text like "Perfect! Now I have all the data..."
@@ -105,6 +105,7 @@ use tokio::{
     fs,
     sync::{RwLock, Semaphore},
 };
+use workflow_manager::workflow_utils::{extract_code_block, extract_json};
 
 /// Shared state for live task display
 #[derive(Clone)]
@@ -1229,33 +1230,12 @@ async fn generate_review_report(
 
 /// Clean YAML response by removing markdown code blocks
 fn clean_yaml(text: &str) -> String {
-    if text.contains("```yaml") {
-        let start = text.find("```yaml").unwrap() + 7;
-        let end = text[start..].find("```").unwrap() + start;
-        text[start..end].trim().to_string()
-    } else if text.contains("```") {
-        let start = text.find("```").unwrap() + 3;
-        let end = text[start..].find("```").unwrap() + start;
-        text[start..end].trim().to_string()
-    } else {
-        text.trim().to_string()
-    }
+    extract_code_block(text, "yaml")
+        .unwrap_or(text)
+        .trim()
+        .to_string()
 }
 
-/// Extract JSON from markdown code blocks
-fn extract_json(text: &str) -> String {
-    if text.contains("```json") {
-        let start = text.find("```json").unwrap() + 7;
-        let end = text[start..].find("```").unwrap() + start;
-        text[start..end].trim().to_string()
-    } else if text.contains("```") {
-        let start = text.find("```").unwrap() + 3;
-        let end = text[start..].find("```").unwrap() + start;
-        text[start..end].trim().to_string()
-    } else {
-        text.trim().to_string()
-    }
-}
 
 /// Parse multi-document YAML
 fn parse_multi_doc_yaml<T: for<'de> Deserialize<'de>>(yaml: &str) -> anyhow::Result<Vec<T>> {
@@ -44,6 +44,9 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
+    // Check the arguments against the workflow's field schema
+    args.validate_or_exit();
+
     println!("🚀 Test Workflow Running");
     println!("   Input: {}", args.input.as_deref().unwrap_or("none"));
     println!("   Batch Size: {}", args.batch_size);
@@ -0,0 +1,213 @@
+//! Slash commands typed in the chat input
+//!
+//! Input starting with `/` is handled locally instead of being sent to
+//! Claude. The result is appended to the conversation as a system message.
+
+use workflow_manager_sdk::WorkflowStatus;
+
+use crate::chat::{ChatMessage, ChatRole};
+
+use super::*;
+
+/// How many executions `/runs` lists
+const RECENT_RUNS: usize = 10;
+
+/// Commands and what they do, as listed by `/help`
+const CHAT_COMMANDS: &[(&str, &str)] = &[
+    ("/clear", "Start a new conversation"),
+    ("/runs", "List open tabs, running and recent executions"),
+    ("/cancel <n>", "Cancel the workflow of tab n (as numbered by /runs)"),
+    ("/help", "Show this list"),
+];
+
+/// A parsed slash command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChatCommand {
+    Clear,
+    Runs,
+    /// 1-based tab number
+    Cancel(usize),
+    Help,
+    /// Not a command, or a command with bad arguments
+    Invalid(String),
+}
+
+impl ChatCommand {
+    /// Parse chat input; None when it is a message for Claude
+    ///
+    /// `//` escapes a message that really starts with `/`.
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        if !input.starts_with('/') || input.starts_with("//") {
+            return None;
+        }
+
+        let mut words = input.split_whitespace();
+        let name = words.next().unwrap_or_default();
+        let args: Vec<&str> = words.collect();
+
+        Some(match (name, args.as_slice()) {
+            ("/clear", []) => ChatCommand::Clear,
+            ("/runs", []) => ChatCommand::Runs,
+            ("/help", []) => ChatCommand::Help,
+            ("/cancel", [n]) => match n.parse::<usize>() {
+                Ok(n) if n > 0 => ChatCommand::Cancel(n),
+                _ => ChatCommand::Invalid(format!("'{}' is not a tab number", n)),
+            },
+            ("/cancel", _) => ChatCommand::Invalid("Usage: /cancel <n>".to_string()),
+            ("/clear" | "/runs" | "/help", _) => {
+                ChatCommand::Invalid(format!("{} takes no arguments", name))
+            }
+            _ => ChatCommand::Invalid(format!(
+                "{} is not a command. Type /help for the list.",
+                name
+            )),
+        })
+    }
+}
+
+impl App {
+    /// Run `input` as a slash command if it is one
+    ///
+    /// Returns false when the input is a regular message for Claude.
+    pub fn run_chat_command(&mut self, input: &str) -> bool {
+        let Some(command) = ChatCommand::parse(input) else {
+            return false;
+        };
+
+        let reply = match command {
+            ChatCommand::Clear => {
+                if self.chat.as_ref().is_some_and(|c| c.waiting_for_response) {
+                    "Wait for the current response before starting a new conversation".to_string()
+                } else {
+                    if let Some(chat) = &mut self.chat {
+                        chat.clear_conversation();
+                    }
+                    "Started a new conversation".to_string()
+                }
+            }
+            ChatCommand::Runs => self.describe_runs(),
+            ChatCommand::Cancel(n) => self.cancel_tab_from_chat(n),
+            ChatCommand::Help => CHAT_COMMANDS
+                .iter()
+                .map(|(command, action)| format!("{:<12} {}", command, action))
+                .chain(std::iter::once(
+                    "Start a message with // to send it to Claude starting with /".to_string(),
+                ))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            ChatCommand::Invalid(message) => message,
+        };
+
+        if let Some(chat) = &mut self.chat {
+            chat.messages.push(ChatMessage {
+                role: ChatRole::System,
+                content: reply,
+                tool_calls: Vec::new(),
+            });
+            chat.auto_scroll = true;
+        }
+        true
+    }
+
+    /// Open tabs, numbered for `/cancel`, then the executions in flight and
+    /// the latest ones
+    fn describe_runs(&self) -> String {
+        let mut lines = vec!["Open tabs:".to_string()];
+        if self.open_tabs.is_empty() {
+            lines.push("  (none)".to_string());
+        }
+        for (idx, tab) in self.open_tabs.iter().enumerate() {
+            lines.push(format!(
+                "  {}. {} #{}  {:?}",
+                idx + 1,
+                tab.workflow_name,
+                tab.instance_number,
+                tab.status
+            ));
+        }
+
+        // Including those started by Claude, which have no tab
+        lines.push(String::new());
+        lines.push("Running executions:".to_string());
+        let running = self.runtime.as_ref().map(|runtime| {
+            self.tokio_runtime.block_on(async { runtime.list_running().await })
+        });
+        match running {
+            Some(Ok(running)) if !running.is_empty() => {
+                for (id, workflow_id, status) in running {
+                    lines.push(format!("  {}  {:?}  {}", workflow_id, status, id));
+                }
+            }
+            Some(Err(e)) => lines.push(format!("  Failed to list running executions: {}", e)),
+            _ => lines.push("  (none)".to_string()),
+        }
+
+        lines.push(String::new());
+        lines.push("Recent executions:".to_string());
+        let executions = self.runtime.as_ref().map(|runtime| {
+            self.tokio_runtime
+                .block_on(async { runtime.list_executions(RECENT_RUNS, 0, None).await })
+        });
+        match executions {
+            Some(Ok(executions)) if !executions.is_empty() => {
+                for execution in executions {
+                    lines.push(format!(
+                        "  {}  {}  {:?}  {}",
+                        execution.start_time.format("%Y-%m-%d %H:%M"),
+                        execution.workflow_name,
+                        execution.status,
+                        &execution.id.to_string()[..8]
+                    ));
+                }
+            }
+            Some(Err(e)) => lines.push(format!("  Failed to list executions: {}", e)),
+            _ => lines.push("  (none)".to_string()),
+        }
+
+        lines.join("\n")
+    }
+
+    /// Cancel tab `n` (1-based), if its workflow is still running
+    fn cancel_tab_from_chat(&mut self, n: usize) -> String {
+        let Some(tab) = self.open_tabs.get(n - 1) else {
+            return format!("There is no tab {}. Type /runs to list the open tabs.", n);
+        };
+        let name = format!("{} #{}", tab.workflow_name, tab.instance_number);
+        if !matches!(
+            tab.status,
+            WorkflowStatus::Running | WorkflowStatus::NotStarted | WorkflowStatus::Paused
+        ) {
+            return format!("{} is not running", name);
+        }
+
+        self.kill_tab(n - 1);
+        format!("Cancelled {}", name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_chat_command() {
+        assert_eq!(ChatCommand::parse("/clear"), Some(ChatCommand::Clear));
+        assert_eq!(ChatCommand::parse("  /runs "), Some(ChatCommand::Runs));
+        assert_eq!(ChatCommand::parse("/cancel 2"), Some(ChatCommand::Cancel(2)));
+        assert_eq!(ChatCommand::parse("/help"), Some(ChatCommand::Help));
+
+        assert_eq!(ChatCommand::parse("hello /runs"), None);
+        assert_eq!(ChatCommand::parse("//etc/hosts is what?"), None);
+
+        assert!(matches!(ChatCommand::parse("/cancel"), Some(ChatCommand::Invalid(_))));
+        assert!(matches!(ChatCommand::parse("/cancel 0"), Some(ChatCommand::Invalid(_))));
+        assert!(matches!(ChatCommand::parse("/runs all"), Some(ChatCommand::Invalid(_))));
+        assert_eq!(
+            ChatCommand::parse("/deploy now"),
+            Some(ChatCommand::Invalid(
+                "/deploy is not a command. Type /help for the list.".to_string()
+            ))
+        );
+    }
+}
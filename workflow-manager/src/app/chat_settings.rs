@@ -0,0 +1,214 @@
+//! Model and tools of the chat session
+//!
+//! Ctrl+O in the chat view opens the settings overlay: `m` cycles the model
+//! and `r` toggles read-only tools. Applying them reconnects the client and
+//! keeps the last choice in `~/.config/workflow-manager/chat.toml`:
+//!
+//! ```toml
+//! model = "opus"
+//! read_only = true
+//! ```
+//!
+//! A read-only chat only gets the tools that look at workflows, logs and
+//! history, and no longer bypasses permission checks, so it cannot launch or
+//! cancel anything.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use claude_agent_sdk::types::{PermissionMode, ToolName};
+use serde::{Deserialize, Serialize};
+
+use super::*;
+
+/// Models the overlay cycles through after the Claude CLI's default
+pub const CHAT_MODELS: [&str; 3] = ["sonnet", "opus", "haiku"];
+
+/// Workflow tools that only read
+pub const READ_ONLY_TOOLS: [&str; 9] = [
+    "list_workflows",
+    "get_workflow_schema",
+    "get_workflow_logs",
+    "get_logs_since",
+    "get_workflow_status",
+    "list_running_workflows",
+    "list_execution_history",
+    "get_execution_params",
+    "get_workflow_history",
+];
+
+/// Workflow tools that start or stop workflows
+pub const LAUNCH_TOOLS: [&str; 2] = ["execute_workflow", "cancel_workflow"];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChatSettings {
+    /// Model alias or full name; the Claude CLI's default when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Leave out the tools that launch and cancel workflows
+    pub read_only: bool,
+}
+
+impl ChatSettings {
+    /// Location of the chat settings file, next to the user-defined workflows
+    pub fn path() -> Option<PathBuf> {
+        std::env::var("HOME")
+            .ok()
+            .map(|home| PathBuf::from(home).join(".config/workflow-manager/chat.toml"))
+    }
+
+    /// Load the settings file, or the defaults if it does not exist
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content)
+            .with_context(|| format!("Invalid chat settings {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, toml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Switch to the next model of [`CHAT_MODELS`], then back to the default
+    ///
+    /// A model set by hand in the file is followed by the default.
+    pub fn cycle_model(&mut self) {
+        let position = self
+            .model
+            .as_deref()
+            .map(|model| CHAT_MODELS.iter().position(|&m| m == model));
+        self.model = match position {
+            None => Some(CHAT_MODELS[0].to_string()),
+            Some(Some(i)) if i + 1 < CHAT_MODELS.len() => Some(CHAT_MODELS[i + 1].to_string()),
+            Some(_) => None,
+        };
+    }
+
+    pub fn model_label(&self) -> &str {
+        self.model.as_deref().unwrap_or("default")
+    }
+
+    /// Model and tools in a few words, e.g. `opus, read-only`
+    pub fn summary(&self) -> String {
+        if self.read_only {
+            format!("{}, read-only", self.model_label())
+        } else {
+            self.model_label().to_string()
+        }
+    }
+
+    /// Tools Claude may call without asking
+    pub fn allowed_tools(&self) -> Vec<ToolName> {
+        let launch: &[&str] = if self.read_only { &[] } else { &LAUNCH_TOOLS };
+        READ_ONLY_TOOLS
+            .iter()
+            .chain(launch)
+            .map(|tool| ToolName::new(format!("mcp__workflow_manager__{}", tool)))
+            .collect()
+    }
+
+    /// Read-only chats keep the permission checks, which deny any tool
+    /// outside [`Self::allowed_tools`] since nobody is asked
+    pub fn permission_mode(&self) -> PermissionMode {
+        if self.read_only {
+            PermissionMode::Default
+        } else {
+            PermissionMode::BypassPermissions
+        }
+    }
+}
+
+impl App {
+    /// Open the settings overlay of the chat
+    pub fn open_chat_settings(&mut self) {
+        if let Some(chat) = &mut self.chat {
+            chat.settings_draft = Some(chat.settings.clone());
+        }
+    }
+
+    pub fn cancel_chat_settings(&mut self) {
+        if let Some(chat) = &mut self.chat {
+            chat.settings_draft = None;
+        }
+    }
+
+    /// Use the settings of the overlay, reconnecting the client and saving
+    /// them if they changed
+    pub fn apply_chat_settings(&mut self) {
+        let Some(chat) = &mut self.chat else {
+            return;
+        };
+        let Some(settings) = chat.settings_draft.take() else {
+            return;
+        };
+        if settings == chat.settings {
+            return;
+        }
+        if chat.waiting_for_response {
+            self.notifications.warning(
+                "Chat Busy",
+                "Wait for the current response before changing the chat settings",
+            );
+            return;
+        }
+
+        chat.settings = settings.clone();
+        chat.reconnect();
+        self.notifications.info(
+            "Chat Settings Applied",
+            format!(
+                "Now {}. Claude starts without the context of earlier messages.",
+                settings.summary()
+            ),
+        );
+
+        if let Some(path) = ChatSettings::path() {
+            if let Err(e) = settings.save(&path) {
+                self.notifications
+                    .error("Chat Settings Not Saved", format!("{:#}", e));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chat_settings() {
+        let mut settings = ChatSettings::default();
+        assert_eq!(settings.allowed_tools().len(), 11);
+        assert!(matches!(settings.permission_mode(), PermissionMode::BypassPermissions));
+
+        let mut models = Vec::new();
+        for _ in 0..4 {
+            settings.cycle_model();
+            models.push(settings.model_label().to_string());
+        }
+        assert_eq!(models, vec!["sonnet", "opus", "haiku", "default"]);
+        settings.model = Some("claude-opus-4-1".to_string());
+        settings.cycle_model();
+        assert_eq!(settings.model, None);
+
+        settings.model = Some("opus".to_string());
+        settings.read_only = true;
+        assert_eq!(settings.summary(), "opus, read-only");
+        assert_eq!(settings.allowed_tools().len(), READ_ONLY_TOOLS.len());
+        assert!(matches!(settings.permission_mode(), PermissionMode::Default));
+
+        let toml = toml::to_string(&settings).unwrap();
+        assert_eq!(toml::from_str::<ChatSettings>(&toml).unwrap(), settings);
+        let default = toml::to_string(&ChatSettings::default()).unwrap();
+        assert_eq!(toml::from_str::<ChatSettings>(&default).unwrap(), ChatSettings::default());
+        assert_eq!(toml::from_str::<ChatSettings>("").unwrap(), ChatSettings::default());
+    }
+}
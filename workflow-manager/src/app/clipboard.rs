@@ -0,0 +1,202 @@
+//! Copying agent output out of the TUI
+//!
+//! `y` in the Tabs or Running view copies the messages of the selected
+//! phase, task or agent to the system clipboard, and in the History view
+//! the command that reproduces the selected run. Without a clipboard (e.g.
+//! over SSH) the text goes to a temp file instead.
+
+use super::*;
+
+/// Label and text of the selected node in a phase tree
+///
+/// An agent yields its messages and result, a task its own messages and
+/// result followed by those of its agents, and a phase all of its tasks.
+pub fn selected_node_text(
+    phases: &[WorkflowPhase],
+    selected_phase: usize,
+    selected_task: Option<&str>,
+    selected_agent: Option<&str>,
+) -> Option<(String, String)> {
+    let phase = phases.get(selected_phase)?;
+
+    if let Some(agent_id) = selected_agent {
+        let agent = phase
+            .tasks
+            .iter()
+            .flat_map(|t| &t.agents)
+            .find(|a| a.id == agent_id)?;
+        return Some((
+            format!("Agent {}", agent.name),
+            agent_text(agent).join("\n"),
+        ));
+    }
+
+    if let Some(task_id) = selected_task {
+        let task = phase.tasks.iter().find(|t| t.id == task_id)?;
+        return Some((format!("Task {}", task.id), task_text(task).join("\n")));
+    }
+
+    let lines: Vec<String> = phase.tasks.iter().flat_map(task_text).collect();
+    Some((format!("Phase {}", phase.name), lines.join("\n")))
+}
+
+fn agent_text(agent: &WorkflowAgent) -> Vec<String> {
+    let mut lines: Vec<String> = agent.messages.iter().map(|m| m.text.clone()).collect();
+    lines.extend(agent.result.clone());
+    lines
+}
+
+fn task_text(task: &WorkflowTask) -> Vec<String> {
+    let mut lines: Vec<String> = task.messages.iter().map(|m| m.text.clone()).collect();
+    lines.extend(task.result.clone());
+    for agent in &task.agents {
+        lines.extend(agent_text(agent));
+    }
+    lines
+}
+
+impl App {
+    /// Copy the selected node of the shown execution to the clipboard
+    pub fn copy_selected_output(&mut self) {
+        let selection = match self.current_view {
+            View::Tabs => self.open_tabs.get(self.active_tab_idx).and_then(|tab| {
+                let phases = tab.workflow_phases.lock().unwrap();
+                selected_node_text(
+                    &phases,
+                    tab.selected_phase,
+                    tab.selected_task.as_deref(),
+                    tab.selected_agent.as_deref(),
+                )
+            }),
+            View::WorkflowRunning(_) => {
+                let phases = self.workflow_phases.lock().unwrap();
+                selected_node_text(
+                    &phases,
+                    self.selected_phase,
+                    self.selected_task.as_deref(),
+                    self.selected_agent.as_deref(),
+                )
+            }
+            _ => None,
+        };
+
+        let Some((label, text)) = selection.filter(|(_, text)| !text.is_empty()) else {
+            self.notifications
+                .info("Nothing to Copy", "The selected item has no output yet");
+            return;
+        };
+
+        self.copy_text(&format!("{} output", label), &text);
+    }
+
+    /// Copy `text` to the clipboard, or to a temp file without one
+    ///
+    /// `what` names the text in the notification, e.g. "Task review output".
+    pub fn copy_text(&mut self, what: &str, text: &str) {
+        match self.set_clipboard_text(text) {
+            Ok(()) => {
+                self.notifications
+                    .success("Copied", format!("{} copied to clipboard", what));
+            }
+            Err(clipboard_error) => {
+                let path = std::env::temp_dir().join(format!(
+                    "workflow-manager-copy-{}.txt",
+                    uuid::Uuid::new_v4()
+                ));
+                match std::fs::write(&path, text) {
+                    Ok(()) => self.notifications.warning(
+                        "Clipboard Unavailable",
+                        format!(
+                            "{} written to {} ({})",
+                            what,
+                            path.display(),
+                            clipboard_error
+                        ),
+                    ),
+                    Err(e) => self.notifications.error(
+                        "Copy Failed",
+                        format!(
+                            "{}; writing {} failed: {}",
+                            clipboard_error,
+                            path.display(),
+                            e
+                        ),
+                    ),
+                };
+            }
+        }
+    }
+
+    /// Set the clipboard text, connecting to the clipboard on first use
+    ///
+    /// The connection is kept for the lifetime of the app because on X11 the
+    /// copied text disappears once its owner is dropped.
+    pub fn set_clipboard_text(&mut self, text: &str) -> Result<(), arboard::Error> {
+        if self.clipboard.is_none() {
+            self.clipboard = Some(arboard::Clipboard::new()?);
+        }
+        self.clipboard
+            .as_mut()
+            .expect("clipboard initialized above")
+            .set_text(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use workflow_manager_sdk::LogLevel;
+
+    fn agent(task_id: &str, name: &str, messages: &[&str], result: Option<&str>) -> WorkflowAgent {
+        WorkflowAgent {
+            id: format!("{}:{}", task_id, name),
+            task_id: task_id.to_string(),
+            name: name.to_string(),
+            status: AgentStatus::Completed,
+            messages: messages
+                .iter()
+                .map(|m| LogLine::new(LogLevel::Info, *m))
+                .collect(),
+            result: result.map(String::from),
+            ..Default::default()
+        }
+    }
+
+    fn sample_phases() -> Vec<WorkflowPhase> {
+        vec![WorkflowPhase {
+            id: 0,
+            name: "Review".to_string(),
+            status: PhaseStatus::Completed,
+            tasks: vec![WorkflowTask {
+                id: "review".to_string(),
+                description: "Review the code".to_string(),
+                status: TaskStatus::Completed,
+                agents: vec![agent("review", "linter", &["step 1"], Some("clean"))],
+                messages: vec![LogLine::new(LogLevel::Info, "started")],
+                result: Some("done".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }]
+    }
+
+    #[test]
+    fn test_selected_node_text() {
+        let phases = sample_phases();
+
+        let (label, text) =
+            selected_node_text(&phases, 0, Some("review"), Some("review:linter")).unwrap();
+        assert_eq!(label, "Agent linter");
+        assert_eq!(text, "step 1\nclean");
+
+        let (label, text) = selected_node_text(&phases, 0, Some("review"), None).unwrap();
+        assert_eq!(label, "Task review");
+        assert_eq!(text, "started\ndone\nstep 1\nclean");
+
+        let (label, _) = selected_node_text(&phases, 0, None, None).unwrap();
+        assert_eq!(label, "Phase Review");
+
+        assert!(selected_node_text(&phases, 1, None, None).is_none());
+        assert!(selected_node_text(&phases, 0, None, Some("review:missing")).is_none());
+    }
+}
@@ -6,9 +6,11 @@
 use anyhow::{Result, anyhow};
 use std::collections::HashMap;
 use uuid::Uuid;
-use workflow_manager_sdk::WorkflowLog;
+use workflow_manager_sdk::{LogLevel, WorkflowLog};
 
-use super::{App, AppCommand, View, WorkflowTab, WorkflowPane};
+use crate::runtime::{RESTART_TASK_ID, TIMEOUT_PHASE_NAME};
+
+use super::{push_output_line, App, AppCommand, LogLine, View, WorkflowTab, WorkflowPane};
 
 impl App {
     /// Process a single command
@@ -89,20 +91,31 @@ impl App {
             runtime_handle_id: handle_id,
 
             exit_code: None,
+            completed_at: None,
             workflow_phases: std::sync::Arc::new(std::sync::Mutex::new(Vec::new())),
             workflow_output: std::sync::Arc::new(std::sync::Mutex::new(Vec::new())),
+            usage: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            result: std::sync::Arc::new(std::sync::Mutex::new(None)),
+            last_event: std::sync::Arc::new(std::sync::Mutex::new(None)),
             field_values: params,
             scroll_offset: 0,
+            follow: true,
             expanded_phases: std::collections::HashSet::new(),
             expanded_tasks: std::collections::HashSet::new(),
             expanded_agents: std::collections::HashSet::new(),
             selected_phase: 0,
             selected_task: None,
             selected_agent: None,
+            selected_file: None,
+            selected_artifact: None,
+            search: None,
             agent_scroll_offsets: std::collections::HashMap::new(),
             focused_pane: WorkflowPane::StructuredLogs,
             raw_output_scroll_offset: 0,
+            h_scroll: 0,
+            log_filter: LogLevel::Debug,
             saved_logs: None,
+            read_only: false,
         };
 
         // Add tab and switch to it
@@ -136,16 +149,37 @@ impl App {
                 anyhow!("Tab with handle {} not found", handle_id)
             })?;
 
+        if let Some(message) = Self::timeout_message(&log) {
+            self.notifications.error(
+                "Workflow Timed Out",
+                format!("{}: {}", tab.workflow_name, message),
+            );
+        }
+        if let Some(message) = Self::restart_message(&log) {
+            self.notifications.warning(
+                "Workflow Restarted",
+                format!("{}: {}", tab.workflow_name, message),
+            );
+        }
+
+        if let Ok(mut last_event) = tab.last_event.lock() {
+            *last_event = Some(chrono::Local::now());
+        }
+
         // Update structured logs (phases/tasks/agents)
+        App::handle_usage_event(&log, &tab.usage);
+        App::handle_result_event(&log, &tab.result);
         App::handle_workflow_event(log.clone(), &tab.workflow_phases);
 
         // ONLY append RawOutput to raw output buffer (actual stdout/stderr)
         // Structured logs are already visible in the phases/tasks/agents tree
         if let WorkflowLog::RawOutput { .. } = &log {
             if let Ok(mut output) = tab.workflow_output.lock() {
-                let formatted = Self::format_workflow_log(&log);
-                if !formatted.is_empty() {
-                    output.push(formatted);
+                for formatted in Self::format_workflow_log(&log) {
+                    if !formatted.is_empty() {
+                        let line = LogLine::of(&log, formatted);
+                        push_output_line(&mut output, line, self.max_output_lines);
+                    }
                 }
             }
         }
@@ -153,9 +187,31 @@ impl App {
         Ok(())
     }
 
-    /// Format a WorkflowLog for display in raw output
-    fn format_workflow_log(log: &WorkflowLog) -> String {
+    /// The error of the event the runtime records when it kills a workflow
+    /// that stopped producing events
+    pub(crate) fn timeout_message(log: &WorkflowLog) -> Option<&str> {
+        match log {
+            WorkflowLog::PhaseFailed { name, error, .. } if name == TIMEOUT_PHASE_NAME => {
+                Some(error)
+            }
+            _ => None,
+        }
+    }
+
+    /// The message of the event the runtime records when it re-runs a
+    /// workflow that failed
+    pub(crate) fn restart_message(log: &WorkflowLog) -> Option<&str> {
         match log {
+            WorkflowLog::TaskProgress { task_id, message } if task_id == RESTART_TASK_ID => {
+                Some(message)
+            }
+            _ => None,
+        }
+    }
+
+    /// Format a WorkflowLog for display in raw output, one string per line
+    fn format_workflow_log(log: &WorkflowLog) -> Vec<String> {
+        let line = match log {
             WorkflowLog::PhaseStarted { phase, name, total_phases } => {
                 format!("📋 Phase {}/{}: {}", phase + 1, total_phases, name)
             }
@@ -200,15 +256,35 @@ impl App {
             WorkflowLog::StateFileCreated { phase, file_path, description } => {
                 format!("  💾 Phase {}: Created {} - {}", phase + 1, file_path, description)
             }
-            WorkflowLog::RawOutput { stream, line } => {
-                // Match manual workflow behavior: stderr gets "ERROR:" prefix
-                if stream == "stderr" {
-                    format!("ERROR: {}", line)
-                } else {
-                    line.to_string()
-                }
+            WorkflowLog::Metric { task_id, name, value, unit } => {
+                format!(
+                    "    • [{}] {} = {}{}",
+                    task_id,
+                    name,
+                    value,
+                    unit.as_deref().map(|u| format!(" {}", u)).unwrap_or_default()
+                )
             }
-        }
+            WorkflowLog::Usage { input_tokens, output_tokens, cost_usd } => {
+                format!(
+                    "    • Usage: {} in / {} out tokens{}",
+                    input_tokens,
+                    output_tokens,
+                    cost_usd.map(|c| format!(", ${:.4}", c)).unwrap_or_default()
+                )
+            }
+            WorkflowLog::Result { summary, artifacts } => {
+                let artifacts = artifacts.iter().map(|artifact| format!("    → {}", artifact));
+                return std::iter::once(format!("🏁 Result: {}", summary))
+                    .chain(artifacts)
+                    .collect();
+            }
+            // As in tabs launched from the TUI, stderr is not marked, since
+            // workflows write their progress there too
+            WorkflowLog::RawOutput { line, .. } => line.to_string(),
+            _ => return Vec::new(),
+        };
+        vec![line]
     }
 
     /// Update tab status
@@ -0,0 +1,110 @@
+//! General TUI settings
+//!
+//! Read once at startup from `~/.config/workflow-manager/config.toml`:
+//!
+//! ```toml
+//! max_output_lines = 20000
+//! auto_close_completed = 60
+//! icons = "ascii"
+//! ```
+//!
+//! Missing keys keep their defaults.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+use super::raw_output::DEFAULT_MAX_OUTPUT_LINES;
+use crate::ui::icons::IconMode;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Lines kept in each raw output buffer
+    pub max_output_lines: usize,
+    /// Seconds after which a completed tab closes; kept open when unset
+    pub auto_close_completed: Option<f64>,
+    /// `unicode` or `ascii` glyphs; `WORKFLOW_MANAGER_ICONS` overrides it
+    pub icons: IconMode,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            max_output_lines: DEFAULT_MAX_OUTPUT_LINES,
+            auto_close_completed: None,
+            icons: IconMode::Unicode,
+        }
+    }
+}
+
+impl Config {
+    /// Location of the config file, next to the user-defined workflows
+    pub fn path() -> Option<PathBuf> {
+        std::env::var("HOME")
+            .ok()
+            .map(|home| PathBuf::from(home).join(".config/workflow-manager/config.toml"))
+    }
+
+    /// Load the config file, or the defaults if it does not exist
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::from_toml(&content).with_context(|| format!("Invalid config {}", path.display()))
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let config: Self = toml::from_str(content)?;
+        if config.max_output_lines == 0 {
+            bail!("max_output_lines must be at least 1");
+        }
+        if let Some(secs) = config.auto_close_completed {
+            if !(secs >= 0.0 && secs.is_finite()) {
+                bail!("auto_close_completed must be a number of seconds, got {}", secs);
+            }
+        }
+        Ok(config)
+    }
+
+    /// Delay before a completed tab is closed, if any
+    pub fn auto_close_completed_after(&self) -> Option<Duration> {
+        self.auto_close_completed.map(Duration::from_secs_f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_keys_keep_their_defaults() {
+        assert_eq!(Config::from_toml("").unwrap(), Config::default());
+
+        let config = Config::from_toml("max_output_lines = 500").unwrap();
+        assert_eq!(config.max_output_lines, 500);
+        assert_eq!(config.auto_close_completed_after(), None);
+
+        let config = Config::from_toml("auto_close_completed = 1.5").unwrap();
+        assert_eq!(config.auto_close_completed_after(), Some(Duration::from_millis(1500)));
+        let config = Config::from_toml("auto_close_completed = 60").unwrap();
+        assert_eq!(config.auto_close_completed_after(), Some(Duration::from_secs(60)));
+
+        assert_eq!(Config::default().icons, IconMode::Unicode);
+        let config = Config::from_toml("icons = \"ascii\"").unwrap();
+        assert_eq!(config.icons, IconMode::Ascii);
+    }
+
+    #[test]
+    fn test_invalid_values_are_rejected() {
+        assert!(Config::from_toml("max_output_lines = 0").is_err());
+        assert!(Config::from_toml("max_output_lines = \"many\"").is_err());
+        assert!(Config::from_toml("max_lines = 10").is_err());
+        assert!(Config::from_toml("auto_close_completed = -1.0").is_err());
+        assert!(Config::from_toml("icons = \"emoji\"").is_err());
+    }
+}
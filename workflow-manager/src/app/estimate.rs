@@ -0,0 +1,100 @@
+//! Query estimate of research runs in the edit view
+//!
+//! The estimate may read the prompts file of a resumed run, so it is only
+//! recomputed when the edit view opens or a field value changes, not on
+//! every frame.
+
+use std::collections::HashMap;
+
+use workflow_manager_sdk::WorkflowDefinition;
+
+use super::*;
+use crate::research::{cli::Args, estimate_operations};
+
+impl App {
+    /// Field values of workflow `idx` as typed, including the field being
+    /// edited, so the form can react before the edit is committed
+    pub fn live_field_values(&self, idx: usize) -> HashMap<String, String> {
+        let mut values = self.field_values.clone();
+        if self.is_editing {
+            if let Some(field) = self
+                .workflows
+                .get(idx)
+                .and_then(|w| w.info.fields.get(self.edit_field_index))
+            {
+                values.insert(field.name.clone(), self.edit_buffer.clone());
+            }
+        }
+        values
+    }
+
+    /// Update `operation_estimate` for the edit view of workflow `idx`
+    ///
+    /// Only research runs get an estimate. Values clap rejects (e.g. a
+    /// half-typed number) keep the previous one.
+    pub fn refresh_operation_estimate(&mut self, idx: usize) {
+        let is_research = self
+            .workflows
+            .get(idx)
+            .is_some_and(|w| w.info.id == Args::metadata().id);
+        if !is_research {
+            self.operation_estimate = None;
+            return;
+        }
+
+        let values = self.live_field_values(idx);
+        if matches!(&self.operation_estimate, Some((cached, _)) if *cached == values) {
+            return;
+        }
+        if let Ok(args) = Args::from_field_values(&values) {
+            let estimate = estimate_operations(&args.into());
+            self.operation_estimate = Some((values, estimate));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_follows_research_field_values() {
+        let metadata = Args::metadata();
+        let workflow = workflow_manager_sdk::Workflow {
+            info: workflow_manager_sdk::WorkflowInfo {
+                id: metadata.id.clone(),
+                name: metadata.name.clone(),
+                description: metadata.description.clone(),
+                status: workflow_manager_sdk::WorkflowStatus::NotStarted,
+                metadata,
+                fields: Args::fields(),
+                progress_messages: vec![],
+            },
+            source: workflow_manager_sdk::WorkflowSource::BuiltIn,
+        };
+        let mut app = App::with_workflows(vec![workflow]);
+        app.field_values
+            .insert("phases".to_string(), "0,5".to_string());
+        app.refresh_operation_estimate(0);
+        let total = |app: &App| app.operation_estimate.as_ref().map(|(_, e)| e.total_queries());
+        assert_eq!(total(&app), Some(2));
+
+        // A half-typed number keeps the last estimate
+        app.is_editing = true;
+        app.edit_field_index = Args::fields()
+            .iter()
+            .position(|f| f.name == "batch_size")
+            .unwrap();
+        app.edit_buffer = "x".to_string();
+        app.refresh_operation_estimate(0);
+        assert_eq!(total(&app), Some(2));
+
+        app.edit_field_index = Args::fields().iter().position(|f| f.name == "phases").unwrap();
+        app.edit_buffer = "0".to_string();
+        app.refresh_operation_estimate(0);
+        assert_eq!(total(&app), Some(1));
+
+        app.refresh_operation_estimate(1);
+        assert!(app.operation_estimate.is_none());
+    }
+}
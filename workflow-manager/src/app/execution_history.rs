@@ -0,0 +1,267 @@
+//! Browser for past executions stored in the database
+//!
+//! `H` opens a paginated list of `WorkflowRuntime::list_executions`. Enter
+//! opens the selected run in a tab, rebuilt from its stored logs and params,
+//! so a finished run can be reviewed after its tab was closed. `e` loads the
+//! run's params into the edit view to relaunch it with changes, and `y`
+//! copies the shell command that reproduces the run outside the TUI.
+
+use super::*;
+use workflow_manager_sdk::FieldSchema;
+
+/// Executions shown per page of the history view
+pub const HISTORY_PAGE_SIZE: usize = 20;
+
+/// Split stored params into those the workflow still accepts and the
+/// (sorted) names of those it no longer defines
+pub fn split_known_params(
+    fields: &[FieldSchema],
+    params: HashMap<String, String>,
+) -> (HashMap<String, String>, Vec<String>) {
+    let (known, unknown): (HashMap<_, _>, HashMap<_, _>) = params
+        .into_iter()
+        .partition(|(name, _)| fields.iter().any(|f| &f.name == name));
+    let mut dropped: Vec<String> = unknown.into_keys().collect();
+    dropped.sort();
+    (known, dropped)
+}
+
+impl App {
+    pub fn open_execution_history(&mut self) {
+        self.execution_history_page = 0;
+        self.load_execution_history_page();
+        self.current_view = View::History;
+    }
+
+    /// Fetch the current page; one extra row tells whether another page follows
+    pub fn load_execution_history_page(&mut self) {
+        let Some(runtime) = self.runtime.clone() else {
+            self.notifications
+                .error("History Unavailable", "Runtime not available");
+            return;
+        };
+
+        let offset = self.execution_history_page * HISTORY_PAGE_SIZE;
+        let result = self.tokio_runtime.block_on(async {
+            runtime
+                .list_executions(HISTORY_PAGE_SIZE + 1, offset, None)
+                .await
+        });
+
+        match result {
+            Ok(mut executions) => {
+                self.execution_history_has_more = executions.len() > HISTORY_PAGE_SIZE;
+                executions.truncate(HISTORY_PAGE_SIZE);
+                self.execution_history = executions;
+                self.execution_history_selected = 0;
+            }
+            Err(e) => {
+                self.notifications
+                    .error("Failed to Load History", e.to_string());
+            }
+        }
+    }
+
+    pub fn execution_history_next(&mut self) {
+        if self.execution_history_selected + 1 < self.execution_history.len() {
+            self.execution_history_selected += 1;
+        }
+    }
+
+    pub fn execution_history_previous(&mut self) {
+        self.execution_history_selected = self.execution_history_selected.saturating_sub(1);
+    }
+
+    pub fn execution_history_next_page(&mut self) {
+        if self.execution_history_has_more {
+            self.execution_history_page += 1;
+            self.load_execution_history_page();
+        }
+    }
+
+    pub fn execution_history_previous_page(&mut self) {
+        if self.execution_history_page > 0 {
+            self.execution_history_page -= 1;
+            self.load_execution_history_page();
+        }
+    }
+
+    /// Leave the history view for the tabs, or the workflow list if none are open
+    pub fn close_execution_history(&mut self) {
+        self.current_view = if self.open_tabs.is_empty() {
+            View::WorkflowList
+        } else {
+            View::Tabs
+        };
+    }
+
+    /// Show the selected execution in a tab, reusing its tab if still open
+    pub fn open_execution_history_entry(&mut self) {
+        let Some(execution) = self
+            .execution_history
+            .get(self.execution_history_selected)
+            .cloned()
+        else {
+            return;
+        };
+
+        if let Some(idx) = self
+            .open_tabs
+            .iter()
+            .position(|tab| tab.runtime_handle_id == execution.id)
+        {
+            self.active_tab_idx = idx;
+            self.current_view = View::Tabs;
+            return;
+        }
+
+        let Some(runtime) = self.runtime.clone() else {
+            return;
+        };
+        match self.create_tab_from_database(&runtime, &execution.id) {
+            Some(tab) => {
+                self.open_tabs.push(tab);
+                self.active_tab_idx = self.open_tabs.len() - 1;
+                self.current_view = View::Tabs;
+            }
+            None => {
+                self.notifications.warning(
+                    "Cannot Open Execution",
+                    format!(
+                        "Workflow '{}' is no longer available",
+                        execution.workflow_id
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Load the selected execution's params into the edit view for its workflow
+    ///
+    /// Params the workflow no longer defines are dropped and listed in a
+    /// warning, so a run from an older version can still be relaunched.
+    pub fn edit_execution_history_entry(&mut self) {
+        let Some(execution) = self
+            .execution_history
+            .get(self.execution_history_selected)
+            .cloned()
+        else {
+            return;
+        };
+        let Some(runtime) = self.runtime.clone() else {
+            return;
+        };
+        let Some(workflow_idx) = self
+            .workflows
+            .iter()
+            .position(|w| w.info.id == execution.workflow_id)
+        else {
+            self.notifications.warning(
+                "Cannot Edit Execution",
+                format!(
+                    "Workflow '{}' is no longer available",
+                    execution.workflow_id
+                ),
+            );
+            return;
+        };
+
+        let params = match self
+            .tokio_runtime
+            .block_on(async { runtime.get_params(&execution.id).await })
+        {
+            Ok(params) => params,
+            Err(e) => {
+                self.notifications
+                    .error("Failed to Load Parameters", e.to_string());
+                return;
+            }
+        };
+
+        let (field_values, dropped) =
+            split_known_params(&self.workflows[workflow_idx].info.fields, params);
+        if !dropped.is_empty() {
+            self.notifications.warning(
+                "Parameters Dropped",
+                format!(
+                    "'{}' no longer has: {}",
+                    execution.workflow_name,
+                    dropped.join(", ")
+                ),
+            );
+        }
+
+        self.current_view = View::WorkflowEdit(workflow_idx);
+        self.edit_field_index = 0;
+        self.is_editing = false;
+        self.field_values = field_values;
+        self.revalidate_fields(workflow_idx);
+
+        // Esc returns to the tabs like "edit and rerun", or the list without tabs
+        self.in_new_tab_flow = !self.open_tabs.is_empty();
+    }
+
+    /// Copy the command line of the selected execution to the clipboard
+    ///
+    /// The command is rebuilt like the launch preview: the workflow's binary
+    /// with each stored param passed through its field's `cli_arg`, plus the
+    /// working directory and environment of the run. Params the workflow no
+    /// longer defines are left out; secrets were never stored.
+    pub fn copy_execution_history_command(&mut self) {
+        let Some(execution) = self
+            .execution_history
+            .get(self.execution_history_selected)
+            .cloned()
+        else {
+            return;
+        };
+        let Some(runtime) = self.runtime.clone() else {
+            return;
+        };
+
+        let params = match self
+            .tokio_runtime
+            .block_on(async { runtime.get_params(&execution.id).await })
+        {
+            Ok(params) => params,
+            Err(e) => {
+                self.notifications
+                    .error("Failed to Load Parameters", e.to_string());
+                return;
+            }
+        };
+
+        match runtime.preview_command(&execution.workflow_id, params) {
+            Ok(command) => {
+                self.copy_text(&format!("Command of '{}'", execution.workflow_name), &command);
+            }
+            Err(e) => {
+                self.notifications.error("Cannot Copy Command", e.to_string());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use workflow_manager_sdk::FieldType;
+
+    #[test]
+    fn test_split_known_params() {
+        let fields = vec![
+            FieldSchema::new("input", FieldType::Text),
+            FieldSchema::new("batch_size", FieldType::Text),
+        ];
+        let params = HashMap::from([
+            ("input".to_string(), "a.yaml".to_string()),
+            ("old_flag".to_string(), "true".to_string()),
+            ("model".to_string(), "opus".to_string()),
+        ]);
+
+        let (known, dropped) = split_known_params(&fields, params);
+        assert_eq!(known.len(), 1);
+        assert_eq!(known["input"], "a.yaml");
+        assert_eq!(dropped, vec!["model".to_string(), "old_flag".to_string()]);
+    }
+}
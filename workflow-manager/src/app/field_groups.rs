@@ -0,0 +1,228 @@
+//! Field groups (sections) of the edit form
+//!
+//! Fields are shown under one header per `FieldSchema::group`, in the order
+//! the groups first appear and keeping declaration order within a group.
+//! Ungrouped fields go under "General". A collapsed group shows only its
+//! header, which the selection reaches through the group's first field, so
+//! `edit_field_index` stays an index into the workflow's fields.
+
+use workflow_manager_sdk::FieldSchema;
+
+use super::*;
+
+/// Section of the fields without a `group`
+pub const DEFAULT_FIELD_GROUP: &str = "General";
+
+/// Name of the group `field` is shown under
+pub fn field_group(field: &FieldSchema) -> &str {
+    field.group.as_deref().unwrap_or(DEFAULT_FIELD_GROUP)
+}
+
+/// Field indices of every group, in display order
+pub fn field_groups(fields: &[FieldSchema]) -> Vec<(&str, Vec<usize>)> {
+    let mut groups: Vec<(&str, Vec<usize>)> = Vec::new();
+    for (i, field) in fields.iter().enumerate() {
+        let name = field_group(field);
+        match groups.iter_mut().find(|(group, _)| *group == name) {
+            Some((_, indices)) => indices.push(i),
+            None => groups.push((name, vec![i])),
+        }
+    }
+    groups
+}
+
+/// Whether the form has group headers, i.e. any field declares a group
+pub fn has_field_groups(fields: &[FieldSchema]) -> bool {
+    fields.iter().any(|field| field.group.is_some())
+}
+
+impl App {
+    pub fn is_field_group_collapsed(&self, workflow_id: &str, group: &str) -> bool {
+        self.collapsed_field_groups
+            .get(workflow_id)
+            .is_some_and(|groups| groups.contains(group))
+    }
+
+    /// Field indices the selection stops on, in display order: every field
+    /// of an expanded group and the first field of a collapsed one
+    pub fn edit_field_stops(&self, idx: usize) -> Vec<usize> {
+        let Some(workflow) = self.workflows.get(idx) else {
+            return Vec::new();
+        };
+        field_groups(&workflow.info.fields)
+            .into_iter()
+            .flat_map(|(group, mut indices)| {
+                if self.is_field_group_collapsed(&workflow.info.id, group) {
+                    indices.truncate(1);
+                }
+                indices
+            })
+            .collect()
+    }
+
+    /// Whether the selection is on the header of a collapsed group
+    pub fn is_selected_field_collapsed(&self) -> bool {
+        let View::WorkflowEdit(idx) = self.current_view else {
+            return false;
+        };
+        self.workflows
+            .get(idx)
+            .and_then(|w| {
+                let field = w.info.fields.get(self.edit_field_index)?;
+                Some(self.is_field_group_collapsed(&w.info.id, field_group(field)))
+            })
+            .unwrap_or(false)
+    }
+
+    /// Collapse or expand the group of the selected field
+    ///
+    /// Collapsing moves the selection to the group's header.
+    pub fn toggle_field_group(&mut self) {
+        let View::WorkflowEdit(idx) = self.current_view else {
+            return;
+        };
+        let Some(workflow) = self.workflows.get(idx) else {
+            return;
+        };
+        if !has_field_groups(&workflow.info.fields) {
+            return;
+        }
+        let Some(field) = workflow.info.fields.get(self.edit_field_index) else {
+            return;
+        };
+        let group = field_group(field).to_string();
+        let first = field_groups(&workflow.info.fields)
+            .into_iter()
+            .find(|(name, _)| *name == group)
+            .map(|(_, indices)| indices[0])
+            .unwrap_or(self.edit_field_index);
+
+        let collapsed = self
+            .collapsed_field_groups
+            .entry(workflow.info.id.clone())
+            .or_default();
+        if !collapsed.remove(&group) {
+            collapsed.insert(group);
+            self.edit_field_index = first;
+        }
+    }
+
+    /// Select field `field_idx` of workflow `idx`, expanding its group
+    pub fn select_edit_field(&mut self, idx: usize, field_idx: usize) {
+        if let Some(workflow) = self.workflows.get(idx) {
+            if let Some(field) = workflow.info.fields.get(field_idx) {
+                if let Some(collapsed) = self.collapsed_field_groups.get_mut(&workflow.info.id) {
+                    collapsed.remove(field_group(field));
+                }
+            }
+        }
+        self.edit_field_index = field_idx;
+    }
+
+    /// Approximate line of the selected field in the edit form, for placing
+    /// the dropdowns below it
+    pub fn edit_field_line(&self, idx: usize) -> usize {
+        let Some(workflow) = self.workflows.get(idx) else {
+            return 0;
+        };
+        let with_headers = has_field_groups(&workflow.info.fields);
+        let mut line = 0;
+        for (group, indices) in field_groups(&workflow.info.fields) {
+            if with_headers {
+                line += 1;
+            }
+            if self.is_field_group_collapsed(&workflow.info.id, group) {
+                continue;
+            }
+            for i in indices {
+                if i == self.edit_field_index {
+                    return line;
+                }
+                line += 4; // Each field takes ~4 lines
+            }
+        }
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use workflow_manager_sdk::FieldType;
+
+    fn app_with_fields(fields: Vec<FieldSchema>) -> App {
+        let workflow = workflow_manager_sdk::Workflow {
+            info: workflow_manager_sdk::WorkflowInfo {
+                id: "demo".to_string(),
+                name: "Demo".to_string(),
+                description: String::new(),
+                status: workflow_manager_sdk::WorkflowStatus::NotStarted,
+                metadata: serde_json::from_value(serde_json::json!({
+                    "id": "demo", "name": "Demo", "description": ""
+                }))
+                .unwrap(),
+                fields,
+                progress_messages: vec![],
+            },
+            source: workflow_manager_sdk::WorkflowSource::BuiltIn,
+        };
+        let mut app = App::with_workflows(vec![workflow]);
+        app.current_view = View::WorkflowEdit(0);
+        app
+    }
+
+    #[test]
+    fn test_groups_keep_declaration_order() {
+        let fields = vec![
+            FieldSchema::new("input", FieldType::Text),
+            FieldSchema {
+                group: Some("Output".to_string()),
+                ..FieldSchema::new("output", FieldType::Text)
+            },
+            FieldSchema::new("dir", FieldType::Text),
+            FieldSchema {
+                group: Some("Output".to_string()),
+                ..FieldSchema::new("prefix", FieldType::Text)
+            },
+        ];
+        assert_eq!(
+            field_groups(&fields),
+            vec![("General", vec![0, 2]), ("Output", vec![1, 3])]
+        );
+    }
+
+    #[test]
+    fn test_navigation_follows_groups_and_skips_collapsed_fields() {
+        let mut app = app_with_fields(vec![
+            FieldSchema::new("input", FieldType::Text),
+            FieldSchema {
+                group: Some("Output".to_string()),
+                ..FieldSchema::new("output", FieldType::Text)
+            },
+            FieldSchema::new("dir", FieldType::Text),
+            FieldSchema {
+                group: Some("Output".to_string()),
+                ..FieldSchema::new("prefix", FieldType::Text)
+            },
+        ]);
+
+        let mut visited = vec![app.edit_field_index];
+        for _ in 0..4 {
+            app.next();
+            visited.push(app.edit_field_index);
+        }
+        assert_eq!(visited, vec![0, 2, 1, 3, 3]);
+
+        // Collapsing moves the selection to the group's header
+        app.toggle_field_group();
+        assert_eq!(app.edit_field_index, 1);
+        assert!(app.is_selected_field_collapsed());
+        assert_eq!(app.edit_field_stops(0), vec![0, 2, 1]);
+
+        app.previous();
+        assert_eq!(app.edit_field_index, 2);
+
+        app.select_edit_field(0, 3);
+        assert!(!app.is_field_group_collapsed("demo", "Output"));
+    }
+}
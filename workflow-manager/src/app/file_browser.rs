@@ -1,5 +1,6 @@
 //! File browser and dropdown functionality
 
+use std::collections::HashSet;
 use std::path::PathBuf;
 use workflow_manager_sdk::FieldType;
 
@@ -174,7 +175,16 @@ impl App {
     }
 
     pub fn dropdown_next(&mut self) {
-        if self.dropdown_selected < self.dropdown_items.len().saturating_sub(1) {
+        let item_count = if !self.option_items.is_empty() {
+            self.option_items.len()
+        } else if !self.preset_items.is_empty() {
+            self.preset_items.len()
+        } else if !self.history_items.is_empty() {
+            self.history_items.len()
+        } else {
+            self.dropdown_items.len()
+        };
+        if self.dropdown_selected < item_count.saturating_sub(1) {
             self.dropdown_selected += 1;
         }
     }
@@ -186,8 +196,12 @@ impl App {
     }
 
     pub fn dropdown_select(&mut self) {
-        // Check if we're showing history or file paths
-        if !self.history_items.is_empty() {
+        // Check if we're showing field options, presets, history or file paths
+        if !self.option_items.is_empty() {
+            self.select_options();
+        } else if !self.preset_items.is_empty() {
+            self.load_selected_preset();
+        } else if !self.history_items.is_empty() {
             // History dropdown
             if let Some(value) = self.history_items.get(self.dropdown_selected) {
                 self.edit_buffer = value.clone();
@@ -217,6 +231,98 @@ impl App {
         self.dropdown_items.clear();
         self.dropdown_selected = 0;
         self.history_items.clear();
+        self.preset_items.clear();
+        self.option_items.clear();
+        self.option_checked.clear();
+        self.option_multi = false;
+    }
+
+    /// Open the option dropdown for the current Select/MultiSelect field
+    ///
+    /// Returns false if the current field has no fixed options.
+    pub fn show_option_dropdown(&mut self) -> bool {
+        let View::WorkflowEdit(idx) = self.current_view else {
+            return false;
+        };
+        let Some(field) = self
+            .workflows
+            .get(idx)
+            .and_then(|w| w.info.fields.get(self.edit_field_index))
+        else {
+            return false;
+        };
+        let (options, multi) = match &field.field_type {
+            FieldType::Select { options } => (options.clone(), false),
+            FieldType::MultiSelect { options } => (options.clone(), true),
+            _ => return false,
+        };
+
+        let current = self
+            .field_values
+            .get(&field.name)
+            .cloned()
+            .unwrap_or_default();
+        let current: Vec<&str> = current.split(',').map(str::trim).collect();
+
+        self.dropdown_selected = options
+            .iter()
+            .position(|o| current.contains(&o.as_str()))
+            .unwrap_or(0);
+        self.option_checked = if multi {
+            options
+                .iter()
+                .filter(|o| current.contains(&o.as_str()))
+                .cloned()
+                .collect()
+        } else {
+            HashSet::new()
+        };
+        self.option_items = options;
+        self.option_multi = multi;
+        self.show_dropdown = true;
+        true
+    }
+
+    /// Check/uncheck the highlighted option of a MultiSelect dropdown
+    pub fn toggle_dropdown_option(&mut self) {
+        if !self.option_multi {
+            return;
+        }
+        if let Some(option) = self.option_items.get(self.dropdown_selected) {
+            if !self.option_checked.remove(option) {
+                self.option_checked.insert(option.clone());
+            }
+        }
+    }
+
+    /// Store the chosen option(s) as the current field value
+    fn select_options(&mut self) {
+        let value = if self.option_multi {
+            // Keep the declared option order
+            self.option_items
+                .iter()
+                .filter(|o| self.option_checked.contains(*o))
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(",")
+        } else {
+            match self.option_items.get(self.dropdown_selected) {
+                Some(option) => option.clone(),
+                None => return,
+            }
+        };
+
+        if let View::WorkflowEdit(idx) = self.current_view {
+            if let Some(field) = self
+                .workflows
+                .get(idx)
+                .and_then(|w| w.info.fields.get(self.edit_field_index))
+            {
+                self.field_values.insert(field.name.clone(), value);
+            }
+            self.revalidate_fields(idx);
+        }
+        self.close_dropdown();
     }
 
     pub fn show_history_dropdown(&mut self) {
@@ -225,14 +331,11 @@ impl App {
             if let Some(workflow) = self.workflows.get(idx) {
                 if let Some(field) = workflow.info.fields.get(self.edit_field_index) {
                     // Get history for this workflow + field
-                    if let Some(workflow_history) = self.history.workflows.get(&workflow.info.id) {
-                        if let Some(field_history) = workflow_history.get(&field.name) {
-                            if !field_history.is_empty() {
-                                self.history_items = field_history.clone();
-                                self.dropdown_selected = 0;
-                                self.show_dropdown = true;
-                            }
-                        }
+                    let field_history = self.history.values(&workflow.info.id, &field.name);
+                    if !field_history.is_empty() {
+                        self.history_items = field_history.to_vec();
+                        self.dropdown_selected = 0;
+                        self.show_dropdown = true;
                     }
                 }
             }
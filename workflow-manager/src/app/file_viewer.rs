@@ -0,0 +1,320 @@
+//! Read-only viewer of produced artifacts and browsed files
+//!
+//! `StateFileCreated` events list output files under their phase. `o` in the
+//! Tabs or Running view opens the selected one in an overlay. Ctrl+O in the
+//! file browser opens the highlighted file, and `O` in the Tabs view the
+//! artifacts of the run's `Result` event, with left/right stepping between
+//! them. Relative paths are resolved against the run's `cwd` parameter, or
+//! else the directory the TUI was started in. A file that cannot be read as
+//! text has its path copied to the clipboard instead.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use ratatui::text::Line;
+
+use super::*;
+
+/// Bytes of a file loaded into the viewer; the rest is left out
+pub const MAX_VIEWER_BYTES: u64 = 1024 * 1024;
+
+/// State of the file viewer overlay
+#[derive(Debug, Clone)]
+pub struct FileViewer {
+    /// Path and description of each file to step through, e.g. the
+    /// output files of a phase
+    pub files: Vec<(String, String)>,
+    /// Index of the shown file in `files`
+    pub current: usize,
+    /// Directory relative paths in `files` are resolved against
+    pub base_dir: PathBuf,
+    pub lines: Vec<String>,
+    /// First shown line, of `rendered` once a markdown file has been drawn
+    pub scroll: usize,
+    /// The file is larger than [`MAX_VIEWER_BYTES`] and was cut
+    pub truncated: bool,
+    /// Markdown rendered at the width in the pair, kept until it changes
+    pub rendered: Option<(usize, Vec<Line<'static>>)>,
+}
+
+impl FileViewer {
+    /// Path and description of the shown file
+    pub fn current_file(&self) -> &(String, String) {
+        &self.files[self.current]
+    }
+
+    /// Number of lines to scroll through: rendered ones for markdown
+    pub fn line_count(&self) -> usize {
+        self.rendered
+            .as_ref()
+            .map_or(self.lines.len(), |(_, lines)| lines.len())
+    }
+}
+
+/// Text of the first [`MAX_VIEWER_BYTES`] of `path`, and whether there is more
+///
+/// Fails with `InvalidData` for files that are not UTF-8 text.
+fn read_capped(path: &Path) -> std::io::Result<(String, bool)> {
+    let mut bytes = Vec::new();
+    std::fs::File::open(path)?
+        .take(MAX_VIEWER_BYTES + 1)
+        .read_to_end(&mut bytes)?;
+    let truncated = bytes.len() as u64 > MAX_VIEWER_BYTES;
+    bytes.truncate(MAX_VIEWER_BYTES as usize);
+
+    match String::from_utf8(bytes) {
+        Ok(text) => Ok((text, truncated)),
+        // The cap may split the last character
+        Err(e) if truncated && e.utf8_error().error_len().is_none() => {
+            let valid = e.utf8_error().valid_up_to();
+            let mut bytes = e.into_bytes();
+            bytes.truncate(valid);
+            Ok((String::from_utf8(bytes).expect("valid up to here"), truncated))
+        }
+        Err(_) => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "not a text file")),
+    }
+}
+
+impl App {
+    /// Open the file highlighted in the file browser in the viewer
+    pub fn view_browsed_file(&mut self) {
+        let Some(path) = self.file_browser_items.get(self.file_browser_selected) else {
+            return;
+        };
+        if path.is_dir() {
+            return;
+        }
+        let file = (path.to_string_lossy().to_string(), String::new());
+        let base_dir = self.current_dir.clone();
+        self.show_output_file(vec![file], 0, base_dir);
+    }
+
+    /// Open the output file selected in the phase tree in the viewer
+    pub fn open_output_file(&mut self) {
+        let file = match self.current_view {
+            View::Tabs => self.open_tabs.get(self.active_tab_idx).and_then(|tab| {
+                let phases = tab.workflow_phases.lock().unwrap();
+                let phase = phases.get(tab.selected_phase)?;
+                phase.output_files.get(tab.selected_file?).cloned()
+            }),
+            View::WorkflowRunning(_) => {
+                let phases = self.workflow_phases.lock().unwrap();
+                phases
+                    .get(self.selected_phase)
+                    .and_then(|phase| phase.output_files.get(self.selected_file?).cloned())
+            }
+            _ => None,
+        };
+
+        match file {
+            Some(file) => {
+                let base_dir = self.run_dir();
+                self.show_output_file(vec![file], 0, base_dir);
+            }
+            None => {
+                self.notifications
+                    .info("No Output File", "Select an output file of a phase to open it");
+            }
+        }
+    }
+
+    /// Open the artifact selected in the active tab's result banner in the viewer
+    pub fn open_result_artifact(&mut self) {
+        let artifact = self.open_tabs.get(self.active_tab_idx).and_then(|tab| {
+            let result = tab.result.lock().unwrap();
+            result.as_ref()?.artifacts.get(tab.selected_artifact?).cloned()
+        });
+
+        match artifact {
+            Some(path) => {
+                let base_dir = self.run_dir();
+                self.show_output_file(vec![(path, "Result artifact".to_string())], 0, base_dir);
+            }
+            None => {
+                self.notifications.info(
+                    "No Artifact",
+                    "Select an artifact in the result banner (above the first phase) to open it",
+                );
+            }
+        }
+    }
+
+    /// Working directory of the shown run: its `cwd` parameter, or else the
+    /// directory the TUI was started in
+    fn run_dir(&self) -> PathBuf {
+        let params = match self.current_view {
+            View::Tabs => self.open_tabs.get(self.active_tab_idx).map(|tab| &tab.field_values),
+            View::WorkflowRunning(_) => Some(&self.field_values),
+            _ => None,
+        };
+        params
+            .and_then(|params| params.get(crate::runtime::CWD_PARAM))
+            .map(|dir| dir.trim())
+            .filter(|dir| !dir.is_empty())
+            .map(|dir| self.start_dir.join(dir))
+            .unwrap_or_else(|| self.start_dir.clone())
+    }
+
+    /// Show the next (or previous) output file of the phase, wrapping around
+    pub fn step_output_file(&mut self, forward: bool) {
+        let Some(viewer) = self.file_viewer.take() else {
+            return;
+        };
+        let count = viewer.files.len();
+        let index = if forward {
+            (viewer.current + 1) % count
+        } else {
+            (viewer.current + count - 1) % count
+        };
+        self.show_output_file(viewer.files, index, viewer.base_dir);
+    }
+
+    pub fn close_file_viewer(&mut self) {
+        self.file_viewer = None;
+    }
+
+    pub fn scroll_file_viewer_up(&mut self, amount: usize) {
+        if let Some(viewer) = &mut self.file_viewer {
+            viewer.scroll = viewer.scroll.saturating_sub(amount);
+        }
+    }
+
+    pub fn scroll_file_viewer_down(&mut self, amount: usize) {
+        if let Some(viewer) = &mut self.file_viewer {
+            viewer.scroll = (viewer.scroll + amount).min(viewer.line_count().saturating_sub(1));
+        }
+    }
+
+    /// Load `files[index]` into the viewer, or copy its path if unreadable
+    ///
+    /// Relative paths are resolved against `base_dir`.
+    fn show_output_file(
+        &mut self,
+        files: Vec<(String, String)>,
+        index: usize,
+        base_dir: PathBuf,
+    ) {
+        // An absolute path replaces the base when joined
+        let path = base_dir.join(&files[index].0);
+
+        match read_capped(&path) {
+            Ok((content, truncated)) => {
+                self.file_viewer = Some(FileViewer {
+                    files,
+                    current: index,
+                    base_dir,
+                    lines: content.lines().map(String::from).collect(),
+                    scroll: 0,
+                    truncated,
+                    rendered: None,
+                });
+            }
+            Err(read_error) => {
+                self.file_viewer = None;
+                let shown = path.display().to_string();
+                match self.set_clipboard_text(&shown) {
+                    Ok(()) => self.notifications.warning(
+                        "Cannot Open File",
+                        format!("{}: {} (path copied to clipboard)", shown, read_error),
+                    ),
+                    Err(_) => self
+                        .notifications
+                        .error("Cannot Open File", format!("{}: {}", shown, read_error)),
+                };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_between_output_files() {
+        let dir = std::env::temp_dir().join(format!("file_viewer_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("plan.yaml"), "phase: 1\ntasks: []\n").unwrap();
+        std::fs::write(dir.join("notes.md"), "# Notes\n").unwrap();
+
+        let mut app = App::with_workflows(vec![]);
+        let files = vec![
+            ("plan.yaml".to_string(), "Plan".to_string()),
+            (dir.join("notes.md").to_string_lossy().to_string(), "Notes".to_string()),
+        ];
+        app.show_output_file(files, 0, dir.clone());
+        assert_eq!(app.file_viewer.as_ref().unwrap().lines, vec!["phase: 1", "tasks: []"]);
+
+        app.scroll_file_viewer_down(10);
+        assert_eq!(app.file_viewer.as_ref().unwrap().scroll, 1);
+
+        app.step_output_file(false);
+        let viewer = app.file_viewer.as_ref().unwrap();
+        assert_eq!(viewer.current_file().1, "Notes");
+        assert_eq!((viewer.lines.len(), viewer.scroll), (1, 0));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_open_selected_output_file_in_run_dir() {
+        let name = format!("file_viewer_{}", uuid::Uuid::new_v4());
+        let dir = std::env::temp_dir().join(&name);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("plan.yaml"), "phase: 1\n").unwrap();
+        std::fs::write(dir.join("notes.md"), "# Notes\n").unwrap();
+
+        let mut app = App::with_workflows(vec![]);
+        app.current_view = View::WorkflowRunning(0);
+        app.start_dir = std::env::temp_dir();
+        app.field_values.insert(crate::runtime::CWD_PARAM.to_string(), name);
+        app.workflow_phases.lock().unwrap().push(WorkflowPhase {
+            id: 0,
+            name: "Plan".to_string(),
+            status: PhaseStatus::Completed,
+            output_files: vec![
+                ("plan.yaml".to_string(), "Plan".to_string()),
+                ("notes.md".to_string(), "Notes".to_string()),
+            ],
+            ..Default::default()
+        });
+        app.expanded_phases.insert(0);
+
+        // Nothing opens while the phase itself is selected
+        app.open_output_file();
+        assert!(app.file_viewer.is_none());
+
+        app.navigate_workflow_down();
+        app.navigate_workflow_down();
+        assert_eq!(app.selected_file, Some(1));
+        app.open_output_file();
+        let viewer = app.file_viewer.as_ref().unwrap();
+        assert_eq!(viewer.files.len(), 1);
+        assert_eq!(viewer.lines, vec!["# Notes"]);
+
+        app.navigate_workflow_up();
+        app.navigate_workflow_up();
+        assert_eq!((app.selected_task.as_ref(), app.selected_file), (None, None));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_capped() {
+        let path = std::env::temp_dir().join(format!("file_viewer_{}.txt", uuid::Uuid::new_v4()));
+
+        // A two-byte character across the cap is left out
+        let mut content = "a".repeat(MAX_VIEWER_BYTES as usize - 1);
+        content.push_str("é and more");
+        std::fs::write(&path, &content).unwrap();
+        let (text, truncated) = read_capped(&path).unwrap();
+        assert!(truncated);
+        assert_eq!(text.len(), MAX_VIEWER_BYTES as usize - 1);
+
+        std::fs::write(&path, [0x66, 0x6f, 0xff, 0x0a]).unwrap();
+        let error = read_capped(&path).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -5,17 +5,56 @@ use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
+use crate::runtime::UsageTotals;
+
 use super::*;
 
+/// Format version of `session.json`
+///
+/// Bump this whenever [`SavedSession`] changes shape; files with any other
+/// version are ignored on load. Files from before versioning have the shape
+/// of version 1.
+const SESSION_VERSION: u32 = 1;
+
+/// Open tabs as stored between sessions
+#[derive(Debug, Serialize, Deserialize)]
+struct SavedSession {
+    #[serde(default = "unversioned_session")]
+    version: u32,
+    active_tab_idx: usize,
+    pinned_executions: Vec<String>, // Store UUIDs as strings
+}
+
+fn unversioned_session() -> u32 {
+    1
+}
+
+/// Session of a `session.json`, or why it cannot be restored
+fn parse_session(json: &str) -> Result<SavedSession, String> {
+    let session: SavedSession =
+        serde_json::from_str(json).map_err(|e| format!("session.json is malformed: {}", e))?;
+    if session.version != SESSION_VERSION {
+        return Err(format!(
+            "session.json has format version {}, expected {}",
+            session.version, SESSION_VERSION
+        ));
+    }
+    Ok(session)
+}
+
+fn session_path() -> Option<std::path::PathBuf> {
+    directories::ProjectDirs::from("", "", "workflow-manager")
+        .map(|dirs| dirs.data_dir().join("session.json"))
+}
+
 impl App {
     // Session persistence
-    pub fn save_session(&self) {
-        #[derive(Serialize)]
-        struct MinimalSession {
-            active_tab_idx: usize,
-            pinned_executions: Vec<String>, // Store UUIDs as strings
-        }
 
+    /// Write the open tabs to `session.json`
+    ///
+    /// The file is replaced in one rename, so a process killed mid-write
+    /// leaves the previous session intact.
+    pub fn save_session(&self) {
         // Save all open tabs as "pinned" executions
         let pinned_executions: Vec<String> = self
             .open_tabs
@@ -23,56 +62,68 @@ impl App {
             .map(|t| t.runtime_handle_id.to_string())
             .collect();
 
-        let session = MinimalSession {
+        let session = SavedSession {
+            version: SESSION_VERSION,
             active_tab_idx: self.active_tab_idx,
             pinned_executions,
         };
 
-        if let Some(data_dir) = directories::ProjectDirs::from("", "", "workflow-manager") {
-            let session_path = data_dir.data_dir().join("session.json");
+        if let Some(session_path) = session_path() {
             if let Ok(json) = serde_json::to_string_pretty(&session) {
-                let _ = std::fs::write(session_path, json);
+                if let Some(parent) = session_path.parent() {
+                    let _ = std::fs::create_dir_all(parent);
+                }
+                let _ = crate::utils::write_atomically(&session_path, &json);
             }
         }
-    }
 
-    pub fn restore_session(&mut self) {
-        #[derive(Deserialize)]
-        struct MinimalSession {
-            active_tab_idx: usize,
-            pinned_executions: Vec<String>,
+        if let Some(chat) = &self.chat {
+            chat.save_conversation();
         }
+    }
 
+    /// Reopen the tabs of `session.json`
+    ///
+    /// A malformed file or one of another format version starts an empty
+    /// session with a warning instead.
+    pub fn restore_session(&mut self) {
         // Get runtime reference - if not available, can't restore from database
         let runtime = match &self.runtime {
             Some(r) => r.clone(),
             None => return, // No runtime means no database access
         };
 
-        if let Some(data_dir) = directories::ProjectDirs::from("", "", "workflow-manager") {
-            let session_path = data_dir.data_dir().join("session.json");
-            if let Ok(json) = std::fs::read_to_string(&session_path) {
-                if let Ok(session) = serde_json::from_str::<MinimalSession>(&json) {
-                    // Restore each pinned execution from database
-                    for handle_id_str in session.pinned_executions {
-                        if let Ok(handle_id) = Uuid::parse_str(&handle_id_str) {
-                            if let Some(tab) = self.create_tab_from_database(&runtime, &handle_id) {
-                                self.open_tabs.push(tab);
-                            }
-                        }
-                    }
+        let Some(session_path) = session_path() else {
+            return;
+        };
+        let Ok(json) = std::fs::read_to_string(&session_path) else {
+            return;
+        };
+        let session = match parse_session(&json) {
+            Ok(session) => session,
+            Err(e) => {
+                self.notifications.warning("Session Not Restored", e);
+                return;
+            }
+        };
 
-                    // Restore active tab index (clamp to valid range)
-                    if !self.open_tabs.is_empty() {
-                        self.active_tab_idx = session.active_tab_idx.min(self.open_tabs.len() - 1);
-                    }
+        // Restore each pinned execution from database
+        for handle_id_str in session.pinned_executions {
+            if let Ok(handle_id) = Uuid::parse_str(&handle_id_str) {
+                if let Some(tab) = self.create_tab_from_database(&runtime, &handle_id) {
+                    self.open_tabs.push(tab);
                 }
             }
         }
+
+        // Restore active tab index (clamp to valid range)
+        if !self.open_tabs.is_empty() {
+            self.active_tab_idx = session.active_tab_idx.min(self.open_tabs.len() - 1);
+        }
     }
 
     /// Create a WorkflowTab from database using handle_id
-    fn create_tab_from_database(
+    pub(super) fn create_tab_from_database(
         &mut self,
         runtime: &Arc<dyn workflow_manager_sdk::WorkflowRuntime>,
         handle_id: &Uuid,
@@ -104,6 +155,7 @@ impl App {
         });
 
         // Get logs from database and process them properly
+        let result = Arc::new(Mutex::new(None));
         let (workflow_phases, raw_output) = self.tokio_runtime.block_on(async {
             match runtime.get_logs(handle_id, None).await {
                 Ok(workflow_logs) => {
@@ -114,14 +166,16 @@ impl App {
                     // Process each log
                     for log in workflow_logs {
                         // Process structured logs (phases, tasks, agents)
-                        App::handle_workflow_event(log.clone(), &phases);
+                        App::replay_workflow_event(log.clone(), &phases);
+                        App::handle_result_event(&log, &result);
 
                         // Only add RawOutput to text buffer (same as live execution)
                         if let workflow_manager_sdk::WorkflowLog::RawOutput { line, .. } = &log {
-                            raw_logs.push(line.clone());
+                            raw_logs.push(LogLine::of(&log, line.clone()));
                         }
                     }
 
+                    truncate_output(&mut raw_logs, self.max_output_lines);
                     (phases, raw_logs)
                 }
                 Err(_) => (Arc::new(Mutex::new(Vec::new())), Vec::new()),
@@ -146,20 +200,36 @@ impl App {
             status: execution.status,
             runtime_handle_id: execution.id, // Use REAL handle_id from database!
             exit_code: execution.exit_code,
+            completed_at: None,
             workflow_phases, // Use properly processed phases
             workflow_output: Arc::new(Mutex::new(raw_output)), // Only raw stdout/stderr
+            usage: Arc::new(Mutex::new(UsageTotals::from_parts(
+                execution.input_tokens,
+                execution.output_tokens,
+                execution.total_cost_usd,
+            ))),
+            result,
+            // Stored events have no timestamps; the end is the closest one
+            last_event: Arc::new(Mutex::new(execution.end_time)),
             field_values,
             scroll_offset: 0,
+            follow: true,
             expanded_phases: HashSet::new(),
             expanded_tasks: HashSet::new(),
             expanded_agents: HashSet::new(),
             selected_phase: 0,
             selected_task: None,
             selected_agent: None,
+            selected_file: None,
+            selected_artifact: None,
+            search: None,
             agent_scroll_offsets: HashMap::new(),
             focused_pane: WorkflowPane::StructuredLogs,
             raw_output_scroll_offset: 0,
+            h_scroll: 0,
+            log_filter: workflow_manager_sdk::LogLevel::Debug,
             saved_logs: None,
+            read_only: true,
         })
     }
 
@@ -170,7 +240,8 @@ impl App {
 
             if let Some(workflow_history) = self.history.workflows.get(workflow_id) {
                 // Load the most recent value for each field
-                for field in &workflow.info.fields {
+                // Secrets are not kept in history (older files may have them)
+                for field in workflow.info.fields.iter().filter(|f| !crate::utils::is_secret(f)) {
                     if let Some(field_history) = workflow_history.get(&field.name) {
                         if let Some(latest_value) = field_history.first() {
                             self.field_values
@@ -190,36 +261,66 @@ impl App {
         };
 
         if let Some(workflow) = self.workflows.get(idx) {
-            let workflow_id = workflow.info.id.clone();
+            self.history
+                .record(&workflow.info.id, &workflow.info.fields, &self.field_values);
+            let _ = crate::utils::save_history(&self.history);
+        }
+    }
 
-            // Get or create workflow history
-            let workflow_history = self.history.workflows.entry(workflow_id).or_default();
+    /// Forget the remembered values of the selected field
+    pub fn clear_field_history(&mut self) {
+        let View::WorkflowEdit(idx) = self.current_view else {
+            return;
+        };
+        let Some(field) = self
+            .workflows
+            .get(idx)
+            .and_then(|workflow| workflow.info.fields.get(self.edit_field_index))
+        else {
+            return;
+        };
 
-            // Save each field value
-            for (field_name, value) in &self.field_values {
-                if !value.is_empty() {
-                    let field_history = workflow_history.entry(field_name.clone()).or_default();
+        let workflow_id = &self.workflows[idx].info.id;
+        let removed = self.history.clear_field(workflow_id, &field.name);
+        if removed == 0 {
+            self.notifications
+                .info("No History", format!("{} has no remembered values", field.label));
+            return;
+        }
+        match crate::utils::save_history(&self.history) {
+            Ok(()) => self.notifications.success(
+                "History Cleared",
+                format!("Forgot {} values of {}", removed, field.label),
+            ),
+            Err(e) => self
+                .notifications
+                .error("History Not Saved", format!("{:#}", e)),
+        };
+    }
+}
 
-                    // Add to history if not already present
-                    if !field_history.contains(value) {
-                        field_history.insert(0, value.clone());
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-                        // Keep max 10 history items
-                        if field_history.len() > 10 {
-                            field_history.truncate(10);
-                        }
-                    } else {
-                        // Move to front
-                        if let Some(pos) = field_history.iter().position(|v| v == value) {
-                            let val = field_history.remove(pos);
-                            field_history.insert(0, val);
-                        }
-                    }
-                }
-            }
+    #[test]
+    fn test_parse_session() {
+        let session = parse_session(r#"{"active_tab_idx": 1, "pinned_executions": ["a"]}"#)
+            .expect("unversioned files are version 1");
+        assert_eq!(session.active_tab_idx, 1);
 
-            // Save history to file
-            let _ = crate::utils::save_history(&self.history);
-        }
+        let json = serde_json::to_string(&SavedSession {
+            version: SESSION_VERSION,
+            active_tab_idx: 0,
+            pinned_executions: vec![],
+        })
+        .unwrap();
+        assert!(parse_session(&json).is_ok());
+
+        // A file cut off by a crash, or written by another version
+        assert!(parse_session(&json[..json.len() / 2]).is_err());
+        let error = parse_session(r#"{"version": 2, "active_tab_idx": 0, "pinned_executions": []}"#)
+            .unwrap_err();
+        assert!(error.contains("format version 2"));
     }
 }
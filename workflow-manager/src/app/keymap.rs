@@ -0,0 +1,706 @@
+//! Configurable keybindings
+//!
+//! Keys pressed in the normal views are resolved to an [`Action`] through the
+//! [`KeyMap`] and then dispatched, so the bindings can be changed without
+//! touching the behavior. The defaults are the built-in bindings. Any action
+//! can be rebound in `~/.config/workflow-manager/keys.toml`:
+//!
+//! ```toml
+//! Quit = "ctrl+q"
+//! NavigateUp = ["up"]
+//! NavigateDown = ["down"]
+//! ```
+//!
+//! Actions missing from the file keep their default keys, except keys the
+//! file assigns to another action of a view they share.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+
+use super::View;
+
+/// Something a key can do in the normal (non-modal) views
+///
+/// When a key is bound to several actions, the first one in declaration
+/// order that applies to the current view wins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    Help,
+    NavigateUp,
+    NavigateDown,
+    /// Open, expand or start editing the selected item
+    Select,
+    Back,
+    /// Expand/collapse everything, or flip a boolean field
+    Toggle,
+    /// Expand the phase tree to the depth given by the next digit key
+    ExpandToDepth,
+    ViewWorkflow,
+    Edit,
+    ClearField,
+    /// Forget the remembered values of the selected field
+    ClearHistory,
+    Launch,
+    /// Show the command a launch would run
+    PreviewCommand,
+    /// Save the field values as a named preset
+    SavePreset,
+    /// Pick a preset to load into the field values
+    LoadPreset,
+    /// Write the field values to a JSON or YAML file
+    SaveParamsFile,
+    /// Read the field values from a JSON or YAML file
+    LoadParamsFile,
+    /// Collapse or expand the section of the selected field
+    ToggleFieldGroup,
+    FocusStructuredLogs,
+    FocusRawOutput,
+    ToggleAgentDetails,
+    HalfPageDown,
+    HalfPageUp,
+    /// Select the first row of the phase tree
+    FirstRow,
+    /// Select the last row of the phase tree, following it again
+    LastRow,
+    /// Scroll the phase tree left, back toward the start of its lines
+    ScrollLeft,
+    /// Scroll the phase tree right, to read lines cut off at the pane edge
+    ScrollRight,
+    ScrollMessagesUp,
+    ScrollMessagesDown,
+    NextPage,
+    PreviousPage,
+    NextTab,
+    PreviousTab,
+    /// Move the active tab one place left
+    MoveTabLeft,
+    /// Move the active tab one place right
+    MoveTabRight,
+    NewTab,
+    CloseTab,
+    KillTab,
+    /// Pause the workflow of the active tab, or resume it
+    TogglePause,
+    /// Ask the workflow to cancel the selected task
+    CancelTask,
+    Rerun,
+    OpenChat,
+    RawJson,
+    OpenHistory,
+    /// Discover workflows again, picking up new and changed ones
+    ReloadWorkflows,
+    CycleLogFilter,
+    /// Search the phase tree of the shown execution
+    Search,
+    NextMatch,
+    PreviousMatch,
+    /// Select the next failed phase, task or agent
+    JumpToFailed,
+    /// Keep the end of the phase tree in view as events arrive
+    ToggleFollow,
+    /// Copy the output of the selected node, or the command of the
+    /// selected run in the History view
+    CopyOutput,
+    ExportLogs,
+    /// View the selected output file of a phase
+    OpenOutputFile,
+    /// View the artifact selected in the run's result banner
+    OpenArtifact,
+}
+
+impl Action {
+    /// Every action, in resolution order
+    pub const ALL: [Action; 56] = [
+        Action::Quit,
+        Action::Help,
+        Action::NavigateUp,
+        Action::NavigateDown,
+        Action::Select,
+        Action::Back,
+        Action::Toggle,
+        Action::ExpandToDepth,
+        Action::ViewWorkflow,
+        Action::Edit,
+        Action::ClearField,
+        Action::ClearHistory,
+        Action::Launch,
+        Action::PreviewCommand,
+        Action::SavePreset,
+        Action::LoadPreset,
+        Action::SaveParamsFile,
+        Action::LoadParamsFile,
+        Action::ToggleFieldGroup,
+        Action::FocusStructuredLogs,
+        Action::FocusRawOutput,
+        Action::ToggleAgentDetails,
+        Action::HalfPageDown,
+        Action::HalfPageUp,
+        Action::FirstRow,
+        Action::LastRow,
+        Action::ScrollLeft,
+        Action::ScrollRight,
+        Action::ScrollMessagesUp,
+        Action::ScrollMessagesDown,
+        Action::NextPage,
+        Action::PreviousPage,
+        Action::NextTab,
+        Action::PreviousTab,
+        Action::MoveTabLeft,
+        Action::MoveTabRight,
+        Action::NewTab,
+        Action::CloseTab,
+        Action::KillTab,
+        Action::TogglePause,
+        Action::CancelTask,
+        Action::Rerun,
+        Action::OpenChat,
+        Action::RawJson,
+        Action::OpenHistory,
+        Action::ReloadWorkflows,
+        Action::CycleLogFilter,
+        Action::Search,
+        Action::NextMatch,
+        Action::PreviousMatch,
+        Action::JumpToFailed,
+        Action::ToggleFollow,
+        Action::CopyOutput,
+        Action::ExportLogs,
+        Action::OpenOutputFile,
+        Action::OpenArtifact,
+    ];
+
+    /// Built-in key combos for the action
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["q", "Q"],
+            Action::Help => &["?"],
+            Action::NavigateUp => &["up", "k"],
+            Action::NavigateDown => &["down", "j"],
+            Action::Select => &["enter"],
+            Action::Back => &["esc", "b"],
+            Action::Toggle => &["space"],
+            Action::ExpandToDepth => &["z"],
+            Action::ViewWorkflow => &["v"],
+            Action::Edit => &["e", "E"],
+            Action::ClearField => &["d"],
+            Action::ClearHistory => &["D"],
+            Action::Launch => &["l", "L"],
+            Action::PreviewCommand => &["p"],
+            Action::SavePreset => &["s"],
+            Action::LoadPreset => &["o"],
+            Action::SaveParamsFile => &["ctrl+s"],
+            Action::LoadParamsFile => &["ctrl+o"],
+            Action::ToggleFieldGroup => &["g"],
+            Action::FocusStructuredLogs => &["1"],
+            Action::FocusRawOutput => &["2"],
+            Action::ToggleAgentDetails => &["3"],
+            Action::HalfPageDown => &["ctrl+d", "pagedown"],
+            Action::HalfPageUp => &["ctrl+u", "pageup"],
+            Action::FirstRow => &["home"],
+            Action::LastRow => &["end"],
+            Action::ScrollLeft => &["<"],
+            Action::ScrollRight => &[">"],
+            Action::ScrollMessagesUp => &["left", "h"],
+            Action::ScrollMessagesDown => &["right", "l"],
+            Action::NextPage => &["pagedown", "right", "n"],
+            Action::PreviousPage => &["pageup", "left", "p"],
+            Action::NextTab => &["tab"],
+            Action::PreviousTab => &["backtab"],
+            Action::MoveTabLeft => &["ctrl+shift+left"],
+            Action::MoveTabRight => &["ctrl+shift+right"],
+            Action::NewTab => &["ctrl+t"],
+            Action::CloseTab => &["c", "C", "ctrl+w", "ctrl+c"],
+            Action::KillTab => &["K", "ctrl+c"],
+            Action::TogglePause => &["P"],
+            Action::CancelTask => &["ctrl+k"],
+            Action::Rerun => &["r", "R"],
+            Action::OpenChat => &["a", "A"],
+            Action::RawJson => &["J"],
+            Action::OpenHistory => &["H"],
+            Action::ReloadWorkflows => &["ctrl+r"],
+            Action::CycleLogFilter => &["f"],
+            Action::Search => &["/"],
+            Action::NextMatch => &["n"],
+            Action::PreviousMatch => &["N"],
+            Action::JumpToFailed => &["!"],
+            Action::ToggleFollow => &["F"],
+            Action::CopyOutput => &["y"],
+            Action::ExportLogs => &["x"],
+            Action::OpenOutputFile => &["o"],
+            Action::OpenArtifact => &["O"],
+        }
+    }
+
+    /// Whether the action does anything in the given view
+    pub fn applies_in(self, view: &View) -> bool {
+        match self {
+            Action::Quit | Action::Help | Action::NavigateUp | Action::NavigateDown => true,
+            Action::Back => true,
+            Action::Select => !matches!(view, View::WorkflowDetail(_)),
+            Action::Toggle => matches!(
+                view,
+                View::Tabs | View::WorkflowRunning(_) | View::WorkflowEdit(_)
+            ),
+            Action::ViewWorkflow | Action::ReloadWorkflows => matches!(view, View::WorkflowList),
+            Action::Edit => matches!(view, View::WorkflowDetail(_) | View::Tabs | View::History),
+            Action::ClearField
+            | Action::ClearHistory
+            | Action::SavePreset
+            | Action::LoadPreset
+            | Action::SaveParamsFile
+            | Action::LoadParamsFile
+            | Action::ToggleFieldGroup => {
+                matches!(view, View::WorkflowEdit(_))
+            }
+            Action::Launch | Action::PreviewCommand => {
+                matches!(view, View::WorkflowDetail(_) | View::WorkflowEdit(_))
+            }
+            Action::ToggleAgentDetails => matches!(view, View::WorkflowRunning(_)),
+            Action::FocusStructuredLogs
+            | Action::FocusRawOutput
+            | Action::HalfPageDown
+            | Action::HalfPageUp
+            | Action::FirstRow
+            | Action::LastRow
+            | Action::ScrollLeft
+            | Action::ScrollRight
+            | Action::KillTab
+            | Action::CancelTask
+            | Action::ExpandToDepth
+            | Action::CycleLogFilter
+            | Action::Search
+            | Action::NextMatch
+            | Action::PreviousMatch
+            | Action::JumpToFailed
+            | Action::ToggleFollow
+            | Action::ExportLogs
+            | Action::OpenOutputFile => matches!(view, View::Tabs | View::WorkflowRunning(_)),
+            Action::ScrollMessagesUp
+            | Action::ScrollMessagesDown
+            | Action::NextTab
+            | Action::PreviousTab
+            | Action::MoveTabLeft
+            | Action::MoveTabRight
+            | Action::CloseTab
+            | Action::TogglePause
+            | Action::Rerun
+            | Action::OpenChat
+            | Action::RawJson
+            | Action::OpenArtifact => matches!(view, View::Tabs),
+            Action::CopyOutput => {
+                matches!(view, View::Tabs | View::WorkflowRunning(_) | View::History)
+            }
+            Action::NextPage | Action::PreviousPage => matches!(view, View::History),
+            Action::NewTab => !matches!(view, View::History | View::Chat),
+            Action::OpenHistory => matches!(view, View::Tabs | View::WorkflowList),
+        }
+    }
+}
+
+/// One of each view, to tell whether two actions can meet
+const VIEWS: [View; 7] = [
+    View::WorkflowList,
+    View::WorkflowDetail(0),
+    View::WorkflowEdit(0),
+    View::WorkflowRunning(0),
+    View::Tabs,
+    View::Chat,
+    View::History,
+];
+
+/// Whether some view has both actions, so a key can only go to one of them
+fn share_a_view(a: Action, b: Action) -> bool {
+    VIEWS
+        .iter()
+        .any(|view| a.applies_in(view) && b.applies_in(view))
+}
+
+/// A key together with the modifiers held with it
+///
+/// Written as e.g. `q`, `Q`, `ctrl+d`, `alt+enter` or `pagedown`. Shift is
+/// part of the character itself (`K`, `?`), so it is only accepted with a
+/// letter or a named key such as `tab` or `left`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyCombo {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyCombo {
+    /// Bring Shift into one form, as terminals report it inconsistently
+    ///
+    /// With a character, Shift is part of the character (`K`), and with Tab
+    /// it makes BackTab. Only named keys keep it as a modifier, so a parsed
+    /// combo and the event of the same keys always compare equal.
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers =
+            modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        let shift = modifiers.contains(KeyModifiers::SHIFT);
+        let code = match code {
+            KeyCode::Char(c) if shift => KeyCode::Char(c.to_ascii_uppercase()),
+            KeyCode::Tab if shift => KeyCode::BackTab,
+            code => code,
+        };
+        if matches!(code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Self { code, modifiers }
+    }
+
+    fn from_event(key: &KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers)
+    }
+}
+
+/// Written as in the help overlay, e.g. `q`, `Ctrl+D` or `PgDn`
+impl fmt::Display for KeyCombo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ctrl_or_alt = self
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) if ctrl_or_alt => write!(f, "{}", c.to_ascii_uppercase()),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::Delete => write!(f, "Del"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+impl FromStr for KeyCombo {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (prefix, key) = if s == "+" {
+            ("", "+")
+        } else if let Some(prefix) = s.strip_suffix("++") {
+            (prefix, "+")
+        } else {
+            s.rsplit_once('+').unwrap_or(("", s))
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for modifier in prefix.split('+').filter(|m| !m.is_empty()) {
+            match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => modifiers |= KeyModifiers::CONTROL,
+                "alt" => modifiers |= KeyModifiers::ALT,
+                "shift" => modifiers |= KeyModifiers::SHIFT,
+                other => bail!("unknown modifier '{}' in '{}'", other, s),
+            }
+        }
+        let shift = modifiers.contains(KeyModifiers::SHIFT);
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) if shift && !c.is_alphabetic() => bail!(
+                "shift only combines with a letter or a named key in '{}'",
+                s
+            ),
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_lowercase().as_str() {
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "space" if shift => bail!("shift does not combine with space in '{}'", s),
+                "space" => KeyCode::Char(' '),
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "pageup" | "pgup" => KeyCode::PageUp,
+                "pagedown" | "pgdn" => KeyCode::PageDown,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                name => match name.strip_prefix('f').map(str::parse::<u8>) {
+                    Some(Ok(n)) if (1..=12).contains(&n) => KeyCode::F(n),
+                    _ => bail!("unknown key '{}' in '{}'", key, s),
+                },
+            },
+        };
+
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+/// One key combo or a list of them, as written in `keys.toml`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Binding {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Binding {
+    fn into_vec(self) -> Vec<String> {
+        match self {
+            Binding::One(combo) => vec![combo],
+            Binding::Many(combos) => combos,
+        }
+    }
+}
+
+/// Key combos bound to each action
+#[derive(Debug, Clone)]
+pub struct KeyMap {
+    /// In [`Action::ALL`] order
+    bindings: Vec<(Action, Vec<KeyCombo>)>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        Self {
+            bindings: Action::ALL
+                .iter()
+                .map(|&action| (action, default_combos(action)))
+                .collect(),
+        }
+    }
+}
+
+fn default_combos(action: Action) -> Vec<KeyCombo> {
+    action
+        .default_keys()
+        .iter()
+        .map(|combo| combo.parse().expect("built-in key combos are valid"))
+        .collect()
+}
+
+impl KeyMap {
+    /// Location of the keymap file, next to the user-defined workflows
+    pub fn path() -> Option<PathBuf> {
+        std::env::var("HOME")
+            .ok()
+            .map(|home| PathBuf::from(home).join(".config/workflow-manager/keys.toml"))
+    }
+
+    /// Load the keymap file, or the defaults if it does not exist
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::from_toml(&content).with_context(|| format!("Invalid keymap {}", path.display()))
+    }
+
+    /// Apply the bindings of a `keys.toml` file on top of the defaults
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let table: HashMap<String, Binding> = toml::from_str(content)?;
+
+        let mut overrides = HashMap::new();
+        for (name, binding) in table {
+            // Action names are written as in the enum, e.g. `KillTab`
+            let action = Action::ALL
+                .into_iter()
+                .find(|action| format!("{:?}", action) == name)
+                .ok_or_else(|| anyhow!("unknown action '{}'", name))?;
+            let combos = binding
+                .into_vec()
+                .iter()
+                .map(|combo| combo.parse())
+                .collect::<Result<Vec<KeyCombo>>>()
+                .map_err(|e| anyhow!("{:?}: {}", action, e))?;
+            overrides.insert(action, combos);
+        }
+
+        // Keys the file assigns explicitly are taken away from the defaults
+        // of the actions they would compete with in some view
+        let taken = |action: Action, combo: &KeyCombo| {
+            overrides.iter().any(|(&other, combos)| {
+                other != action && combos.contains(combo) && share_a_view(action, other)
+            })
+        };
+        let bindings = Action::ALL
+            .iter()
+            .map(|&action| {
+                let combos = match overrides.get(&action) {
+                    Some(combos) => combos.clone(),
+                    None => default_combos(action)
+                        .into_iter()
+                        .filter(|combo| !taken(action, combo))
+                        .collect(),
+                };
+                (action, combos)
+            })
+            .collect();
+
+        Ok(Self { bindings })
+    }
+
+    /// The key combos bound to an action
+    pub fn keys(&self, action: Action) -> &[KeyCombo] {
+        self.bindings
+            .iter()
+            .find(|(bound, _)| *bound == action)
+            .map_or(&[], |(_, combos)| combos)
+    }
+
+    /// The action a key triggers in the given view, if any
+    pub fn resolve(&self, key: &KeyEvent, view: &View) -> Option<Action> {
+        let combo = KeyCombo::from_event(key);
+        self.bindings
+            .iter()
+            .find(|(action, combos)| action.applies_in(view) && combos.contains(&combo))
+            .map(|(action, _)| *action)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn test_parse_key_combo() {
+        let parse = |s: &str| s.parse::<KeyCombo>().unwrap();
+        assert_eq!(parse("q").code, KeyCode::Char('q'));
+        assert_eq!(parse("K").code, KeyCode::Char('K'));
+        assert_eq!(parse("shift+k").code, KeyCode::Char('K'));
+        assert_eq!(parse("shift+tab").code, KeyCode::BackTab);
+        assert_eq!(parse("PageDown").code, KeyCode::PageDown);
+        assert_eq!(parse("f5").code, KeyCode::F(5));
+
+        let ctrl_shift_left = parse("ctrl+shift+left");
+        assert_eq!(ctrl_shift_left.code, KeyCode::Left);
+        assert_eq!(
+            ctrl_shift_left.modifiers,
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT
+        );
+
+        let ctrl_plus = parse("ctrl++");
+        assert_eq!(ctrl_plus.code, KeyCode::Char('+'));
+        assert_eq!(ctrl_plus.modifiers, KeyModifiers::CONTROL);
+
+        // Parsed combos take the same form as the events of the keys
+        let event = |code, modifiers| KeyCombo::from_event(&key(code, modifiers));
+        assert_eq!(parse("ctrl+shift+d"), parse("ctrl+D"));
+        assert_eq!(
+            event(
+                KeyCode::Char('d'),
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT
+            ),
+            parse("ctrl+shift+d")
+        );
+        assert_eq!(event(KeyCode::Tab, KeyModifiers::SHIFT), parse("shift+tab"));
+        assert_eq!(
+            event(KeyCode::BackTab, KeyModifiers::SHIFT),
+            parse("backtab")
+        );
+        assert_eq!(event(KeyCode::Up, KeyModifiers::SHIFT), parse("shift+up"));
+        assert_ne!(event(KeyCode::Up, KeyModifiers::SHIFT), parse("up"));
+
+        assert!("hyper+q".parse::<KeyCombo>().is_err());
+        assert!("shift+1".parse::<KeyCombo>().is_err());
+        assert!("f13".parse::<KeyCombo>().is_err());
+    }
+
+    #[test]
+    fn test_default_keymap_resolves_by_view() {
+        let keymap = KeyMap::default();
+        let l = key(KeyCode::Char('l'), KeyModifiers::NONE);
+        let ctrl_c = key(KeyCode::Char('c'), KeyModifiers::CONTROL);
+
+        assert_eq!(
+            keymap.resolve(&l, &View::Tabs),
+            Some(Action::ScrollMessagesDown)
+        );
+        assert_eq!(
+            keymap.resolve(&l, &View::WorkflowEdit(0)),
+            Some(Action::Launch)
+        );
+        assert_eq!(keymap.resolve(&ctrl_c, &View::Tabs), Some(Action::CloseTab));
+        assert_eq!(
+            keymap.resolve(&ctrl_c, &View::WorkflowRunning(0)),
+            Some(Action::KillTab)
+        );
+        assert_eq!(
+            keymap.resolve(&key(KeyCode::Char('K'), KeyModifiers::SHIFT), &View::Tabs),
+            Some(Action::KillTab)
+        );
+        assert_eq!(keymap.resolve(&l, &View::WorkflowList), None);
+
+        let ctrl_shift = KeyModifiers::CONTROL | KeyModifiers::SHIFT;
+        assert_eq!(
+            keymap.resolve(&key(KeyCode::Right, ctrl_shift), &View::Tabs),
+            Some(Action::MoveTabRight)
+        );
+        // Shift is significant for named keys
+        assert_eq!(
+            keymap.resolve(&key(KeyCode::Right, KeyModifiers::CONTROL), &View::Tabs),
+            None
+        );
+        assert_eq!(
+            keymap.resolve(&key(KeyCode::Up, KeyModifiers::SHIFT), &View::Tabs),
+            None
+        );
+        assert_eq!(
+            keymap.resolve(&key(KeyCode::Tab, KeyModifiers::SHIFT), &View::Tabs),
+            Some(Action::PreviousTab)
+        );
+    }
+
+    #[test]
+    fn test_keymap_file_overrides_defaults() {
+        let keymap = KeyMap::from_toml(
+            r#"
+            Quit = "ctrl+q"
+            OpenChat = ["q", "d"]
+            "#,
+        )
+        .unwrap();
+
+        let q = key(KeyCode::Char('q'), KeyModifiers::NONE);
+        let ctrl_q = key(KeyCode::Char('q'), KeyModifiers::CONTROL);
+        assert_eq!(keymap.resolve(&ctrl_q, &View::Tabs), Some(Action::Quit));
+        assert_eq!(keymap.resolve(&q, &View::Tabs), Some(Action::OpenChat));
+        // `q` was taken by OpenChat, which does nothing in the list
+        assert_eq!(keymap.resolve(&q, &View::WorkflowList), None);
+        // ClearField keeps `d` in the edit view, where OpenChat does not apply
+        let d = key(KeyCode::Char('d'), KeyModifiers::NONE);
+        assert_eq!(keymap.resolve(&d, &View::Tabs), Some(Action::OpenChat));
+        assert_eq!(
+            keymap.resolve(&d, &View::WorkflowEdit(0)),
+            Some(Action::ClearField)
+        );
+        // Unmapped actions keep their defaults
+        assert_eq!(
+            keymap.resolve(&key(KeyCode::Char('j'), KeyModifiers::NONE), &View::Tabs),
+            Some(Action::NavigateDown)
+        );
+
+        let ctrl_q = "ctrl+q".parse::<KeyCombo>().unwrap();
+        assert_eq!(keymap.keys(Action::Quit), &[ctrl_q]);
+        assert_eq!(ctrl_q.to_string(), "Ctrl+Q");
+
+        assert!(KeyMap::from_toml("Fly = \"f\"").is_err());
+        assert!(KeyMap::from_toml("Quit = \"hyper+q\"").is_err());
+    }
+}
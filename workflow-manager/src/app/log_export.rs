@@ -0,0 +1,76 @@
+//! Structured log export
+//!
+//! `x` in the Tabs or Running view opens a path prompt for the shown
+//! execution (Tab completes paths like in the edit view). Enter writes the
+//! log as JSON Lines through `WorkflowRuntime::export_logs`.
+
+use std::path::PathBuf;
+
+use super::*;
+
+impl App {
+    /// Open the export prompt for the execution shown in the current view,
+    /// pre-filled with `<workflow id>_<short handle>.jsonl`
+    pub fn open_log_export(&mut self) {
+        let shown = match self.current_view {
+            View::Tabs => self
+                .open_tabs
+                .get(self.active_tab_idx)
+                .map(|tab| (tab.runtime_handle_id, tab.workflow_idx)),
+            View::WorkflowRunning(idx) => self.running_handle_id.map(|handle_id| (handle_id, idx)),
+            _ => None,
+        };
+        let Some((handle_id, workflow_idx)) = shown else {
+            return;
+        };
+
+        let workflow_id = self
+            .workflows
+            .get(workflow_idx)
+            .map(|w| w.info.id.as_str())
+            .unwrap_or("workflow");
+        let handle = handle_id.simple().to_string();
+        let file_name = format!("{}_{}.jsonl", workflow_id, &handle[..8]);
+
+        self.edit_buffer = self
+            .current_dir
+            .join(file_name)
+            .to_string_lossy()
+            .to_string();
+        self.export_handle = Some(handle_id);
+    }
+
+    pub fn cancel_log_export(&mut self) {
+        self.export_handle = None;
+        self.edit_buffer.clear();
+    }
+
+    /// Write the log to the prompted path and report the outcome
+    pub fn confirm_log_export(&mut self) {
+        let Some(handle_id) = self.export_handle.take() else {
+            return;
+        };
+        let path = PathBuf::from(std::mem::take(&mut self.edit_buffer));
+        let path = if path.is_absolute() {
+            path
+        } else {
+            self.current_dir.join(path)
+        };
+
+        let Some(runtime) = self.runtime.clone() else {
+            self.notifications
+                .error("Export Failed", "Runtime not available");
+            return;
+        };
+
+        let result = self
+            .tokio_runtime
+            .block_on(async { runtime.export_logs(&handle_id, &path).await });
+        match result {
+            Ok(()) => self
+                .notifications
+                .success("Logs Exported", path.display().to_string()),
+            Err(e) => self.notifications.error("Export Failed", e.to_string()),
+        };
+    }
+}
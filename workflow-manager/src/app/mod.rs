@@ -14,20 +14,45 @@ pub use models::*;
 
 // Command pattern modules
 pub mod commands;
+pub mod keymap;
 pub mod notifications;
 pub mod task_registry;
 
 // Declare submodules
+mod chat_commands;
+mod chat_settings;
+mod clipboard;
+mod config;
+mod estimate;
+mod execution_history;
+mod field_groups;
 mod file_browser;
+mod file_viewer;
 mod history;
+mod log_export;
 mod navigation;
+mod params_file;
+mod presets;
+mod raw_output;
+mod reload;
+mod search;
 mod tabs;
+mod validation;
 mod workflow_ops;
 mod command_handlers;
 
 // Re-export for convenience
+pub use chat_commands::ChatCommand;
+pub use chat_settings::ChatSettings;
 pub use commands::{AppCommand, NotificationLevel};
+pub use config::Config;
+pub use field_groups::{field_group, field_groups, has_field_groups};
+pub use file_viewer::FileViewer;
+pub use keymap::{Action, KeyMap};
 pub use notifications::NotificationManager;
+pub use params_file::{read_params_file, ParamsFileAction};
+pub use presets::Presets;
+pub use raw_output::{push_output_line, truncate_output};
 pub use task_registry::TaskRegistry;
 
 // Re-export methods from submodules
@@ -35,28 +60,118 @@ pub use task_registry::TaskRegistry;
 impl App {
     pub fn new() -> Self {
         // CHANGE: Discover workflows ONCE
-        let discovered_workflows = crate::discovery::discover_workflows();
+        let (discovered_workflows, discovery_warnings) =
+            crate::discovery::discover_workflows_with_warnings();
 
         // Convert to UI model (clone metadata since we need it for runtime too)
         let workflows = discovered_workflows
             .iter()
-            .map(|dw| workflow_manager_sdk::Workflow {
-                info: workflow_manager_sdk::WorkflowInfo {
-                    id: dw.metadata.id.clone(),
-                    name: dw.metadata.name.clone(),
-                    description: dw.metadata.description.clone(),
-                    status: workflow_manager_sdk::WorkflowStatus::NotStarted,
-                    metadata: dw.metadata.clone(),
-                    fields: dw.fields.clone(),
-                    progress_messages: vec![],
-                },
-                source: workflow_manager_sdk::WorkflowSource::BuiltIn,
-            })
+            .map(reload::workflow_from_discovered)
             .collect();
 
-        let current_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("/"));
-        let history = crate::utils::load_history();
+        let mut app = Self::with_workflows(workflows);
+        app.current_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("/"));
+        app.start_dir = app.current_dir.clone();
+        app.history = crate::utils::load_history();
+        for workflow in &app.workflows {
+            app.history.forget_secrets(&workflow.info.id, &workflow.info.fields);
+        }
+
+        // A broken config file is reported and the defaults are used
+        let mut icon_mode = crate::ui::icons::IconMode::default();
+        if let Some(path) = Config::path() {
+            match Config::load(&path) {
+                Ok(config) => {
+                    app.max_output_lines = config.max_output_lines;
+                    app.auto_close_completed_after = config.auto_close_completed_after();
+                    icon_mode = config.icons;
+                }
+                Err(e) => {
+                    app.notifications.warning("Config Ignored", format!("{:#}", e));
+                }
+            }
+        }
+        app.icons = crate::ui::icons::IconSet::from_env_or(icon_mode);
+
+        // A broken theme file is reported and the dark preset is used
+        if let Some(path) = crate::ui::theme::Theme::path() {
+            match crate::ui::theme::Theme::load(&path) {
+                Ok(theme) => app.theme = theme,
+                Err(e) => {
+                    app.notifications.warning("Theme Ignored", format!("{:#}", e));
+                }
+            }
+        }
+
+        // A broken keymap file is reported and the default keys are used
+        if let Some(path) = KeyMap::path() {
+            match KeyMap::load(&path) {
+                Ok(keymap) => app.keymap = keymap,
+                Err(e) => {
+                    app.notifications.warning("Keymap Ignored", format!("{:#}", e));
+                }
+            }
+        }
 
+        // Broken user-defined workflow files are skipped, not fatal
+        for warning in discovery_warnings {
+            app.notifications.warning("Workflow Skipped", warning);
+        }
+
+        // CHANGE: Initialize runtime WITH discovered workflows
+        match crate::runtime::ProcessBasedRuntime::new_with_workflows(discovered_workflows) {
+            Ok(runtime) => {
+                // Get database reference before moving runtime
+                let database = runtime.get_database();
+                app.workflow_registry = Some(runtime.get_workflow_registry());
+
+                let runtime_arc =
+                    Arc::new(runtime) as Arc<dyn workflow_manager_sdk::WorkflowRuntime>;
+                app.runtime = Some(runtime_arc.clone());
+
+                // Wrap history for sharing with chat interface
+                let history_arc = Arc::new(tokio::sync::Mutex::new(app.history.clone()));
+
+                // A broken chat settings file is reported and the defaults are used
+                let mut chat_settings = ChatSettings::default();
+                if let Some(path) = ChatSettings::path() {
+                    match ChatSettings::load(&path) {
+                        Ok(settings) => chat_settings = settings,
+                        Err(e) => {
+                            app.notifications
+                                .warning("Chat Settings Ignored", format!("{:#}", e));
+                        }
+                    }
+                }
+                app.chat = Some(ChatInterface::new(
+                    runtime_arc,
+                    history_arc,
+                    app.command_tx.clone(),
+                    app.task_registry.clone(),
+                    app.tokio_runtime.handle().clone(),
+                    database,
+                    chat_settings,
+                ));
+            }
+            Err(e) => {
+                eprintln!("Warning: Failed to initialize workflow runtime: {}", e);
+            }
+        }
+
+        // Restore previous session
+        app.restore_session();
+
+        // Start in Tabs view (shows empty state with hint if no tabs)
+        app.current_view = View::Tabs;
+
+        app
+    }
+
+    /// Create bare application state for the given workflows
+    ///
+    /// Does not touch disk, start the workflow runtime or connect the chat
+    /// client, so it is also usable for rendering tests.
+    pub fn with_workflows(workflows: Vec<workflow_manager_sdk::Workflow>) -> Self {
         // Create tokio runtime for async operations
         let tokio_runtime = tokio::runtime::Runtime::new().expect("Failed to create tokio runtime");
 
@@ -69,85 +184,88 @@ impl App {
         // Create task registry
         let task_registry = TaskRegistry::new();
 
-        let mut app = Self {
+        Self {
             workflows,
             // NEW: Tab management
             open_tabs: Vec::new(),
             active_tab_idx: 0,
             workflow_counters: HashMap::new(),
             show_close_confirmation: false,
+            show_raw_json: false,
+            show_help: false,
+            command_preview: None,
+            file_viewer: None,
+            keymap: KeyMap::default(),
+            theme: crate::ui::theme::Theme::default(),
+            raw_json_scroll: 0,
+            export_handle: None,
             in_new_tab_flow: false,
             selected: 0,
             current_view: View::WorkflowList,
             should_quit: false,
+            icons: &crate::ui::icons::UNICODE_ICONS,
             edit_field_index: 0,
             edit_buffer: String::new(),
             is_editing: false,
             field_values: HashMap::new(),
+            validation_errors: HashMap::new(),
+            operation_estimate: None,
+            collapsed_field_groups: HashMap::new(),
             show_file_browser: false,
             file_browser_items: Vec::new(),
             file_browser_selected: 0,
             file_browser_search: String::new(),
-            current_dir,
+            current_dir: PathBuf::from("/"),
+            start_dir: PathBuf::from("/"),
             show_dropdown: false,
             dropdown_items: Vec::new(),
             dropdown_selected: 0,
-            history,
+            option_items: Vec::new(),
+            option_checked: HashSet::new(),
+            option_multi: false,
+            history: WorkflowHistory::default(),
             history_items: Vec::new(),
+            presets: Presets::default(),
+            preset_items: Vec::new(),
+            saving_preset: false,
+            params_file_prompt: None,
+            execution_history: Vec::new(),
+            execution_history_page: 0,
+            execution_history_selected: 0,
+            execution_history_has_more: false,
             workflow_output: Arc::new(Mutex::new(Vec::new())),
+            max_output_lines: raw_output::DEFAULT_MAX_OUTPUT_LINES,
+            auto_close_completed_after: None,
             workflow_running: false,
+            running_handle_id: None,
+            log_events: Arc::new(std::sync::atomic::AtomicU64::new(0)),
             workflow_phases: Arc::new(Mutex::new(Vec::new())),
             expanded_phases: HashSet::new(),
             expanded_tasks: HashSet::new(),
             expanded_agents: HashSet::new(),
+            awaiting_tree_depth: false,
+            search_prompt: None,
+            search: None,
             selected_phase: 0,
             selected_task: None,
             selected_agent: None,
+            selected_file: None,
             workflow_scroll_offset: 0,
+            workflow_follow: true,
             workflow_focused_pane: WorkflowPane::StructuredLogs,
             workflow_raw_output_scroll: 0,
+            workflow_h_scroll: 0,
+            log_filter: workflow_manager_sdk::LogLevel::Debug,
             chat: None,
+            clipboard: None,
             runtime: None,
+            workflow_registry: None,
             tokio_runtime,
-            command_tx: command_tx.clone(),
+            command_tx,
             command_rx,
             notifications,
-            task_registry: task_registry.clone(),
-        };
-
-        // CHANGE: Initialize runtime WITH discovered workflows
-        match crate::runtime::ProcessBasedRuntime::new_with_workflows(discovered_workflows) {
-            Ok(runtime) => {
-                // Get database reference before moving runtime
-                let database = runtime.get_database();
-
-                let runtime_arc =
-                    Arc::new(runtime) as Arc<dyn workflow_manager_sdk::WorkflowRuntime>;
-                app.runtime = Some(runtime_arc.clone());
-
-                // Wrap history for sharing with chat interface
-                let history_arc = Arc::new(tokio::sync::Mutex::new(app.history.clone()));
-                app.chat = Some(ChatInterface::new(
-                    runtime_arc,
-                    history_arc,
-                    command_tx,
-                    task_registry,
-                    app.tokio_runtime.handle().clone(),
-                    database,
-                ));
-            }
-            Err(e) => {
-                eprintln!("Warning: Failed to initialize workflow runtime: {}", e);
-            }
+            task_registry,
         }
-
-        // Restore previous session
-        app.restore_session();
-
-        // Start in Tabs view (shows empty state with hint if no tabs)
-        app.current_view = View::Tabs;
-
-        app
     }
 
     pub fn open_chat(&mut self) {
@@ -1,16 +1,24 @@
 //! Main application state
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
+use std::sync::atomic::AtomicU64;
 use std::sync::{Arc, Mutex};
-use workflow_manager_sdk::Workflow;
+use uuid::Uuid;
+use workflow_manager_sdk::{ExecutionSummary, LogLevel, Workflow};
 use tokio::sync::mpsc::{UnboundedSender, UnboundedReceiver};
 
-use super::{View, WorkflowHistory, WorkflowPhase, WorkflowTab};
+use super::{LogLine, View, WorkflowHistory, WorkflowPhase, WorkflowTab};
 use crate::chat::ChatInterface;
+use crate::discovery::DiscoveredWorkflow;
+use crate::research::OperationEstimate;
 use crate::app::commands::AppCommand;
+use crate::app::keymap::KeyMap;
 use crate::app::notifications::NotificationManager;
+use crate::app::{FileViewer, ParamsFileAction, Presets};
 use crate::app::task_registry::TaskRegistry;
+use crate::ui::icons::IconSet;
+use crate::ui::theme::Theme;
 
 /// Main application state
 pub struct App {
@@ -21,17 +29,32 @@ pub struct App {
     pub active_tab_idx: usize,
     pub workflow_counters: HashMap<String, usize>,
     pub show_close_confirmation: bool,
+    pub show_raw_json: bool, // Raw JSON debug popup for the selected agent
+    pub show_help: bool, // Keybinding help overlay for the current view
+    pub command_preview: Option<String>, // Resolved command shown in the preview overlay
+    pub file_viewer: Option<FileViewer>, // Output file shown in the viewer overlay (`o`)
+    pub keymap: KeyMap, // Normal-mode keybindings, see keys.toml
+    pub raw_json_scroll: usize,
+    pub export_handle: Option<Uuid>, // Execution whose logs the export path prompt writes
     pub in_new_tab_flow: bool, // When true, we're selecting workflow for a new tab
 
     pub selected: usize,
     pub current_view: View,
     pub should_quit: bool,
+    pub icons: &'static IconSet, // Unicode or ASCII glyphs for all views
+    pub theme: Theme, // Color roles for all views, see theme.toml
 
     // Edit mode state
     pub edit_field_index: usize,
     pub edit_buffer: String,
     pub is_editing: bool,
     pub field_values: HashMap<String, String>,
+    /// Errors of the last failed launch: workflow id -> field name -> message
+    pub validation_errors: HashMap<String, BTreeMap<String, String>>,
+    /// Queries of the research run being edited, with the values it is for
+    pub operation_estimate: Option<(HashMap<String, String>, OperationEstimate)>,
+    /// Collapsed sections of the edit form: workflow id -> group names
+    pub collapsed_field_groups: HashMap<String, HashSet<String>>,
 
     // File browser state
     pub show_file_browser: bool,
@@ -39,39 +62,80 @@ pub struct App {
     pub file_browser_selected: usize,
     pub file_browser_search: String,
     pub current_dir: PathBuf,
+    pub start_dir: PathBuf, // Working directory at startup, for relative output paths
 
     // Dropdown state
     pub show_dropdown: bool,
     pub dropdown_items: Vec<PathBuf>,
     pub dropdown_selected: usize,
+    /// Options of the Select/MultiSelect field shown in the dropdown
+    pub option_items: Vec<String>,
+    pub option_checked: HashSet<String>,
+    pub option_multi: bool,
 
     // History
     pub history: WorkflowHistory,
     pub history_items: Vec<String>,
 
+    // Parameter presets
+    pub presets: Presets, // Loaded when the preset dropdown opens
+    pub preset_items: Vec<String>, // Preset names shown in the dropdown
+    pub saving_preset: bool, // Name prompt open; the name is typed into edit_buffer
+    /// Params file path prompt, typed into edit_buffer like the preset name
+    pub params_file_prompt: Option<ParamsFileAction>,
+
+    // Execution history browser (View::History)
+    pub execution_history: Vec<ExecutionSummary>,
+    pub execution_history_page: usize,
+    pub execution_history_selected: usize,
+    pub execution_history_has_more: bool, // Whether a next page exists
+
     // Running workflow state
-    pub workflow_output: Arc<Mutex<Vec<String>>>,
+    pub workflow_output: Arc<Mutex<Vec<LogLine>>>,
+    /// Cap of the raw output buffers here and in the tabs, in lines
+    pub max_output_lines: usize,
+    /// Completed tabs are closed this long after completing, if set
+    pub auto_close_completed_after: Option<std::time::Duration>,
     pub workflow_running: bool,
+    /// Runtime handle of the workflow shown in the WorkflowRunning view
+    pub running_handle_id: Option<Uuid>,
+    /// Log events received by the log streams of all runs, so the main
+    /// loop can redraw only when one arrived
+    pub log_events: Arc<AtomicU64>,
 
     // Hierarchical phase tracking
     pub workflow_phases: Arc<Mutex<Vec<WorkflowPhase>>>,
     pub expanded_phases: HashSet<usize>,
     pub expanded_tasks: HashSet<String>,
     pub expanded_agents: HashSet<String>,
+    /// `z` was pressed; the next digit sets the tree depth
+    pub awaiting_tree_depth: bool,
+    /// Query being typed in the search prompt (`/`)
+    pub search_prompt: Option<String>,
+    /// Active search of the Running view; tabs keep their own
+    pub search: Option<regex::Regex>,
 
     // Navigation state for workflow running view
     pub selected_phase: usize,
     pub selected_task: Option<String>,
     pub selected_agent: Option<String>,
+    pub selected_file: Option<usize>, // Output file of the selected phase
     pub workflow_scroll_offset: usize,
+    pub workflow_follow: bool, // Keep the end of the phase tree in view (`F` toggles)
 
     // Two-pane view state (for non-tab workflow view)
     pub workflow_focused_pane: super::tab::WorkflowPane,
     pub workflow_raw_output_scroll: usize,
+    pub workflow_h_scroll: usize, // Columns the phase tree is scrolled right (`<`/`>`)
+    pub log_filter: LogLevel, // Minimum level shown in the running view (`f` cycles)
 
     // Chat interface
     pub chat: Option<ChatInterface>,
+    /// System clipboard, connected on the first copy (`y`)
+    pub clipboard: Option<arboard::Clipboard>,
     pub runtime: Option<Arc<dyn workflow_manager_sdk::WorkflowRuntime>>,
+    /// The runtime's workflows, updated when the list is reloaded (Ctrl+R)
+    pub workflow_registry: Option<Arc<Mutex<HashMap<String, DiscoveredWorkflow>>>>,
 
     // Tokio runtime for async operations
     pub tokio_runtime: tokio::runtime::Runtime,
@@ -6,7 +6,7 @@ use std::path::PathBuf;
 use uuid::Uuid;
 use workflow_manager_sdk::{WorkflowLog, WorkflowStatus};
 
-use crate::runtime::ExecutionState;
+use crate::runtime::{ExecutionState, UsageTotals};
 
 /// Persisted execution model for database storage
 ///
@@ -44,6 +44,9 @@ pub struct PersistedExecution {
 
     /// All logs emitted during execution (loaded separately from DB)
     pub logs: Vec<WorkflowLog>,
+
+    /// Token usage totals (None if the workflow reported none)
+    pub usage: Option<UsageTotals>,
 }
 
 impl PersistedExecution {
@@ -53,13 +56,14 @@ impl PersistedExecution {
             id,
             workflow_id: state.workflow_id.clone(),
             workflow_name: state.workflow_name.clone(),
-            status: state.status.clone(),
+            status: state.status().clone(),
             start_time: state.start_time,
             end_time: state.end_time,
             exit_code: state.exit_code,
             binary_path: state.binary_path.clone(),
             params: state.params.clone(),
             logs: state.logs_buffer.lock().unwrap().clone(),
+            usage: state.usage,
         }
     }
 
@@ -69,22 +73,19 @@ impl PersistedExecution {
     /// Note: The child process and broadcast sender cannot be restored,
     /// so this is only suitable for completed/failed executions.
     pub fn to_execution_state(&self) -> ExecutionState {
-        use tokio::sync::broadcast;
-
-        let (logs_tx, _) = broadcast::channel(1000);
-
-        ExecutionState {
-            workflow_id: self.workflow_id.clone(),
-            workflow_name: self.workflow_name.clone(),
-            status: self.status.clone(),
-            child: None, // Cannot restore running process
-            logs_tx,
-            binary_path: self.binary_path.clone(),
-            logs_buffer: std::sync::Arc::new(std::sync::Mutex::new(self.logs.clone())),
-            start_time: self.start_time,
-            end_time: self.end_time,
-            params: self.params.clone(),
-            exit_code: self.exit_code,
-        }
+        // Cannot restore running process
+        let mut state = ExecutionState::new(
+            self.workflow_id.clone(),
+            self.workflow_name.clone(),
+            self.binary_path.clone(),
+            self.status.clone(),
+        );
+        state.logs_buffer = std::sync::Arc::new(std::sync::Mutex::new(self.logs.clone()));
+        state.start_time = self.start_time;
+        state.end_time = self.end_time;
+        state.params = self.params.clone();
+        state.exit_code = self.exit_code;
+        state.usage = self.usage;
+        state
     }
 }
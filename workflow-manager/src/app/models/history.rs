@@ -1,10 +1,82 @@
 //! History data structures
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use workflow_manager_sdk::FieldSchema;
+
+/// Most values remembered per field; older ones are dropped
+pub const MAX_VALUES_PER_FIELD: usize = 50;
 
 /// History storage: workflow_id -> field_name -> list of values
+///
+/// Each list holds distinct values, most recently used first.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct WorkflowHistory {
     pub workflows: HashMap<String, HashMap<String, Vec<String>>>,
 }
+
+impl WorkflowHistory {
+    /// Remember the non-empty values of a run
+    ///
+    /// A value already in a field's history moves to the front instead of
+    /// being added again. Secret fields and fields the workflow does not
+    /// define are skipped.
+    pub fn record(
+        &mut self,
+        workflow_id: &str,
+        fields: &[FieldSchema],
+        values: &HashMap<String, String>,
+    ) {
+        let workflow_history = self.workflows.entry(workflow_id.to_string()).or_default();
+        for field in fields.iter().filter(|field| !crate::utils::is_secret(field)) {
+            let Some(value) = values.get(&field.name).filter(|value| !value.is_empty()) else {
+                continue;
+            };
+            let field_history = workflow_history.entry(field.name.clone()).or_default();
+            field_history.retain(|v| v != value);
+            field_history.insert(0, value.clone());
+            field_history.truncate(MAX_VALUES_PER_FIELD);
+        }
+    }
+
+    /// Values of a field, most recent first
+    pub fn values(&self, workflow_id: &str, field_name: &str) -> &[String] {
+        self.workflows
+            .get(workflow_id)
+            .and_then(|fields| fields.get(field_name))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Forget the values of a field, returning how many there were
+    pub fn clear_field(&mut self, workflow_id: &str, field_name: &str) -> usize {
+        let Some(fields) = self.workflows.get_mut(workflow_id) else {
+            return 0;
+        };
+        let removed = fields.remove(field_name).map_or(0, |values| values.len());
+        if fields.is_empty() {
+            self.workflows.remove(workflow_id);
+        }
+        removed
+    }
+
+    /// Forget the secret fields of a workflow, which older versions recorded
+    pub fn forget_secrets(&mut self, workflow_id: &str, fields: &[FieldSchema]) {
+        for field in fields.iter().filter(|field| crate::utils::is_secret(field)) {
+            self.clear_field(workflow_id, &field.name);
+        }
+    }
+
+    /// Drop duplicates, values beyond [`MAX_VALUES_PER_FIELD`] and empty
+    /// lists, as found in files written before the cap
+    pub fn normalize(&mut self) {
+        for fields in self.workflows.values_mut() {
+            for values in fields.values_mut() {
+                let mut seen = HashSet::new();
+                values.retain(|value| !value.is_empty() && seen.insert(value.clone()));
+                values.truncate(MAX_VALUES_PER_FIELD);
+            }
+            fields.retain(|_, values| !values.is_empty());
+        }
+        self.workflows.retain(|_, fields| !fields.is_empty());
+    }
+}
@@ -2,10 +2,11 @@
 
 use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
-use workflow_manager_sdk::WorkflowStatus;
+use workflow_manager_sdk::{LogLevel, WorkflowStatus};
 use uuid::Uuid;
 
-use super::workflow::WorkflowPhase;
+use super::workflow::{LogLine, WorkflowPhase};
+use crate::runtime::UsageTotals;
 
 /// Which pane is focused in the workflow view
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -14,6 +15,13 @@ pub enum WorkflowPane {
     RawOutput,
 }
 
+/// What a run produced, from its `Result` event
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunResult {
+    pub summary: String,
+    pub artifacts: Vec<String>,
+}
+
 /// Per-tab state container for tabbed interface
 #[derive(Debug)]
 pub struct WorkflowTab {
@@ -33,26 +41,42 @@ pub struct WorkflowTab {
     pub runtime_handle_id: Uuid,
 
     pub exit_code: Option<i32>,
+    /// When polling saw the workflow complete, for auto-close
+    pub completed_at: Option<std::time::Instant>,
 
     // Workflow data (per tab)
     pub workflow_phases: Arc<Mutex<Vec<WorkflowPhase>>>,
-    pub workflow_output: Arc<Mutex<Vec<String>>>,
+    pub workflow_output: Arc<Mutex<Vec<LogLine>>>,
+    pub usage: Arc<Mutex<Option<UsageTotals>>>, // Totals of `Usage` events
+    pub result: Arc<Mutex<Option<RunResult>>>,   // The `Result` event, once emitted
+    /// When the latest event arrived, to tell a stalled run apart
+    pub last_event: Arc<Mutex<Option<chrono::DateTime<chrono::Local>>>>,
     pub field_values: HashMap<String, String>,
 
     // UI state (per tab)
     pub scroll_offset: usize,
+    pub follow: bool, // Keep the end of the phase tree in view (`F` toggles)
     pub expanded_phases: HashSet<usize>,
     pub expanded_tasks: HashSet<String>,
     pub expanded_agents: HashSet<String>,
     pub selected_phase: usize,
     pub selected_task: Option<String>,
     pub selected_agent: Option<String>,
+    pub selected_file: Option<usize>, // Output file of the selected phase
+    pub selected_artifact: Option<usize>, // Artifact row of the result banner
     pub agent_scroll_offsets: HashMap<String, usize>, // agent_id -> scroll offset
+    /// Active search, highlighted in this tab's tree and stepped with `n`/`N`
+    pub search: Option<regex::Regex>,
 
     // Two-pane view state
     pub focused_pane: WorkflowPane,
     pub raw_output_scroll_offset: usize,
+    pub h_scroll: usize, // Columns the phase tree is scrolled right (`<`/`>`)
+    pub log_filter: LogLevel, // Minimum level shown in both panes (`f` cycles)
 
     // Session persistence
     pub saved_logs: Option<Vec<String>>,
+    /// Rebuilt from the database (history, restored session): the run is
+    /// shown as stored, so kill, pause and task cancel are refused
+    pub read_only: bool,
 }
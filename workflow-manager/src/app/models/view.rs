@@ -9,4 +9,5 @@ pub enum View {
     WorkflowRunning(usize), // workflow index (will be deprecated)
     Tabs,                   // Main tabbed view
     Chat,                   // Chat interface with Claude
+    History,                // Past executions from the database
 }
@@ -1,8 +1,47 @@
 //! Workflow execution data structures
 
-/// Status of a workflow phase
+use std::time::{Duration, Instant};
+
+use workflow_manager_sdk::{LogLevel, WorkflowLog};
+
+/// A line of raw output or a task/agent message, with the level the log
+/// filter compares
+///
+/// The level is the one of the event the line came from (see
+/// [`WorkflowLog::level`]), so decorations added for display do not change
+/// what the filter hides.
 #[derive(Debug, Clone, PartialEq)]
+pub struct LogLine {
+    pub level: LogLevel,
+    pub text: String,
+}
+
+impl LogLine {
+    pub fn new(level: LogLevel, text: impl Into<String>) -> Self {
+        Self {
+            level,
+            text: text.into(),
+        }
+    }
+
+    /// `text` at the level of the event `log`
+    pub fn of(log: &WorkflowLog, text: impl Into<String>) -> Self {
+        Self::new(log.level(), text)
+    }
+}
+
+impl std::ops::Deref for LogLine {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.text
+    }
+}
+
+/// Status of a workflow phase
+#[derive(Debug, Clone, Default, PartialEq)]
 pub enum PhaseStatus {
+    #[default]
     NotStarted,
     Running,
     Completed,
@@ -10,8 +49,9 @@ pub enum PhaseStatus {
 }
 
 /// Status of a workflow task
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub enum TaskStatus {
+    #[default]
     NotStarted,
     Running,
     Completed,
@@ -19,8 +59,9 @@ pub enum TaskStatus {
 }
 
 /// Status of a workflow agent
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub enum AgentStatus {
+    #[default]
     NotStarted,
     Running,
     Completed,
@@ -28,35 +69,178 @@ pub enum AgentStatus {
 }
 
 /// A workflow agent that executes within a task
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct WorkflowAgent {
     pub id: String, // task_id:agent_name
     pub task_id: String,
     pub name: String,
     pub description: String,
     pub status: AgentStatus,
-    pub messages: Vec<String>,
+    pub messages: Vec<LogLine>,
     pub result: Option<String>,
+    pub raw_events: Vec<serde_json::Value>, // Unprocessed WorkflowLog events (debug view)
+    /// Error of `AgentFailed`
+    pub error: Option<String>,
+    /// Number of agents above this one, counting the `parent_agent` chain
+    pub depth: usize,
 }
 
 /// A task within a workflow phase
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct WorkflowTask {
     pub id: String,
     pub phase: usize,
     pub description: String,
     pub status: TaskStatus,
     pub agents: Vec<WorkflowAgent>,
-    pub messages: Vec<String>,
+    pub messages: Vec<LogLine>,
     pub result: Option<String>,
+    /// Latest "progress" metric, in 0.0-1.0
+    pub progress: Option<f64>,
+    /// When the task started running; unknown for replayed executions
+    pub started_at: Option<Instant>,
+    /// When the task completed or failed
+    pub finished_at: Option<Instant>,
+    /// Error of `TaskFailed`
+    pub error: Option<String>,
+}
+
+impl WorkflowTask {
+    /// Time spent running so far, or in total once finished
+    pub fn elapsed(&self) -> Option<Duration> {
+        let running = self.status == TaskStatus::Running;
+        elapsed_between(self.started_at, self.finished_at, running)
+    }
 }
 
 /// A phase of workflow execution
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct WorkflowPhase {
     pub id: usize,
     pub name: String,
     pub status: PhaseStatus,
     pub tasks: Vec<WorkflowTask>,
     pub output_files: Vec<(String, String)>, // (path, description)
+    /// Number of tasks the phase announced, if any
+    pub total_tasks: Option<usize>,
+    /// When the phase started running; unknown for replayed executions
+    pub started_at: Option<Instant>,
+    /// When the phase completed or failed
+    pub finished_at: Option<Instant>,
+    /// Error of `PhaseFailed`
+    pub error: Option<String>,
+}
+
+impl WorkflowPhase {
+    /// Time spent running so far, or in total once finished
+    pub fn elapsed(&self) -> Option<Duration> {
+        let running = self.status == PhaseStatus::Running;
+        elapsed_between(self.started_at, self.finished_at, running)
+    }
+
+    /// Naive estimate of the time left in a running phase: the average
+    /// duration of its finished tasks times the number of tasks not yet
+    /// finished
+    ///
+    /// Needs the announced task count and at least one finished task.
+    /// Tasks running in parallel make this an overestimate.
+    pub fn eta(&self) -> Option<Duration> {
+        if self.status != PhaseStatus::Running {
+            return None;
+        }
+        let durations: Vec<Duration> = self
+            .tasks
+            .iter()
+            .filter(|t| t.status == TaskStatus::Completed)
+            .filter_map(|t| t.elapsed())
+            .collect();
+        if durations.is_empty() {
+            return None;
+        }
+        let finished = self
+            .tasks
+            .iter()
+            .filter(|t| matches!(t.status, TaskStatus::Completed | TaskStatus::Failed))
+            .count();
+        let remaining = self.total_tasks?.checked_sub(finished).filter(|&n| n > 0)?;
+
+        let average = durations.iter().sum::<Duration>() / durations.len() as u32;
+        Some(average * remaining as u32)
+    }
+}
+
+/// Time from `started` to `finished`, or to now while still `running`
+///
+/// Without a finish time a task or phase that is no longer running (e.g.
+/// one left behind by a killed run) has no known duration, so it does not
+/// keep growing.
+fn elapsed_between(
+    started: Option<Instant>,
+    finished: Option<Instant>,
+    running: bool,
+) -> Option<Duration> {
+    let end = match finished {
+        Some(finished) => finished,
+        None if running => Instant::now(),
+        None => return None,
+    };
+    Some(end.saturating_duration_since(started?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(status: TaskStatus, started: Instant, secs: u64) -> WorkflowTask {
+        WorkflowTask {
+            status,
+            started_at: Some(started),
+            finished_at: Some(started + Duration::from_secs(secs)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_phase_eta_from_average_task_duration() {
+        let start = Instant::now();
+        let mut phase = WorkflowPhase {
+            id: 0,
+            status: PhaseStatus::Running,
+            tasks: vec![
+                task(TaskStatus::Completed, start, 10),
+                task(TaskStatus::Completed, start, 30),
+            ],
+            total_tasks: Some(5),
+            started_at: Some(start),
+            ..Default::default()
+        };
+        assert_eq!(phase.tasks[1].elapsed(), Some(Duration::from_secs(30)));
+        // 3 tasks left at 20s each
+        assert_eq!(phase.eta(), Some(Duration::from_secs(60)));
+
+        phase.total_tasks = None;
+        assert_eq!(phase.eta(), None);
+
+        phase.total_tasks = Some(2);
+        assert_eq!(phase.eta(), None);
+
+        phase.total_tasks = Some(5);
+        phase.status = PhaseStatus::Completed;
+        assert_eq!(phase.eta(), None);
+    }
+
+    #[test]
+    fn test_elapsed_stops_growing_once_not_running() {
+        let start = Instant::now() - Duration::from_secs(10);
+        let mut running = task(TaskStatus::Running, start, 0);
+        running.finished_at = None;
+        assert!(running.elapsed().unwrap() >= Duration::from_secs(10));
+
+        let mut failed = running.clone();
+        failed.status = TaskStatus::Failed;
+        assert_eq!(failed.elapsed(), None);
+
+        failed.finished_at = Some(start + Duration::from_secs(4));
+        assert_eq!(failed.elapsed(), Some(Duration::from_secs(4)));
+    }
 }
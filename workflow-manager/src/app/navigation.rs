@@ -1,7 +1,15 @@
 //! Navigation methods for workflow and tab hierarchies
 
+use unicode_width::UnicodeWidthStr;
+
 use super::*;
 
+/// Columns `<` and `>` move the phase tree sideways
+pub const HORIZONTAL_SCROLL_STEP: usize = 8;
+
+/// Rows PageDown/PageUp (and `Ctrl+D`/`Ctrl+U`) move the tree selection
+pub const TREE_PAGE_ROWS: usize = 15;
+
 impl App {
     pub fn next(&mut self) {
         match self.current_view {
@@ -11,10 +19,11 @@ impl App {
                 }
             }
             View::WorkflowEdit(idx) => {
-                // Navigate to next field
-                if let Some(workflow) = self.workflows.get(idx) {
-                    if self.edit_field_index < workflow.info.fields.len().saturating_sub(1) {
-                        self.edit_field_index += 1;
+                // Navigate to next field in display order, over collapsed groups
+                let stops = self.edit_field_stops(idx);
+                if let Some(pos) = stops.iter().position(|&i| i == self.edit_field_index) {
+                    if let Some(&next) = stops.get(pos + 1) {
+                        self.edit_field_index = next;
                     }
                 }
             }
@@ -29,10 +38,13 @@ impl App {
                     self.selected -= 1;
                 }
             }
-            View::WorkflowEdit(_) => {
-                // Navigate to previous field
-                if self.edit_field_index > 0 {
-                    self.edit_field_index -= 1;
+            View::WorkflowEdit(idx) => {
+                // Navigate to previous field in display order, over collapsed groups
+                let stops = self.edit_field_stops(idx);
+                if let Some(pos) = stops.iter().position(|&i| i == self.edit_field_index) {
+                    if let Some(&previous) = pos.checked_sub(1).and_then(|p| stops.get(p)) {
+                        self.edit_field_index = previous;
+                    }
                 }
             }
             _ => {}
@@ -46,11 +58,30 @@ impl App {
         }
         let tab = &mut self.open_tabs[self.active_tab_idx];
 
+        // In the result banner, move to the next artifact or down to the tree
+        if let Some(artifact_idx) = tab.selected_artifact {
+            let artifacts = result_artifacts(tab);
+            tab.selected_artifact = Some(artifact_idx + 1).filter(|&i| i < artifacts);
+            return;
+        }
+
         if let Ok(phases) = tab.workflow_phases.lock() {
             if phases.is_empty() {
                 return;
             }
 
+            // If an output file is selected, move to the next file or phase
+            if let Some(file_idx) = tab.selected_file {
+                let files = phases.get(tab.selected_phase).map_or(0, |p| p.output_files.len());
+                if file_idx + 1 < files {
+                    tab.selected_file = Some(file_idx + 1);
+                } else if tab.selected_phase + 1 < phases.len() {
+                    tab.selected_file = None;
+                    tab.selected_phase += 1;
+                }
+                return;
+            }
+
             let mut just_exited_agent = false;
 
             // If agent is selected, try to move to next agent
@@ -98,9 +129,12 @@ impl App {
                         }
                     }
                 }
-                // No next task in this phase, move to next phase
+                // No next task in this phase, move to its output files or
+                // the next phase
                 tab.selected_task = None;
-                if tab.selected_phase + 1 < phases.len() {
+                if phases.get(tab.selected_phase).is_some_and(|p| !p.output_files.is_empty()) {
+                    tab.selected_file = Some(0);
+                } else if tab.selected_phase + 1 < phases.len() {
                     tab.selected_phase += 1;
                 }
                 return;
@@ -109,10 +143,16 @@ impl App {
             // Navigate phases or dive into tasks
             let phase = &phases[tab.selected_phase];
 
-            // If current phase is expanded and has tasks, dive into first task
-            if tab.expanded_phases.contains(&tab.selected_phase) && !phase.tasks.is_empty() {
-                tab.selected_task = Some(phase.tasks[0].id.clone());
-                return;
+            // If current phase is expanded, dive into its first task or file
+            if tab.expanded_phases.contains(&tab.selected_phase) {
+                if !phase.tasks.is_empty() {
+                    tab.selected_task = Some(phase.tasks[0].id.clone());
+                    return;
+                }
+                if !phase.output_files.is_empty() {
+                    tab.selected_file = Some(0);
+                    return;
+                }
             }
 
             // Move to next phase
@@ -128,8 +168,30 @@ impl App {
         }
         let tab = &mut self.open_tabs[self.active_tab_idx];
 
+        // In the result banner, move to the previous artifact
+        if let Some(artifact_idx) = tab.selected_artifact {
+            tab.selected_artifact = Some(artifact_idx.saturating_sub(1));
+            return;
+        }
+        let artifacts = result_artifacts(tab);
+
         if let Ok(phases) = tab.workflow_phases.lock() {
             if phases.is_empty() {
+                // Nothing below the banner; its last artifact is above
+                tab.selected_artifact = artifacts.checked_sub(1);
+                return;
+            }
+
+            // If an output file is selected, move to the previous file or the
+            // last task of the phase
+            if let Some(file_idx) = tab.selected_file {
+                tab.selected_file = file_idx.checked_sub(1);
+                if tab.selected_file.is_none() {
+                    if let Some(phase) = phases.get(tab.selected_phase) {
+                        (tab.selected_task, tab.selected_agent) =
+                            last_task_row(phase, &tab.expanded_tasks);
+                    }
+                }
                 return;
             }
 
@@ -187,21 +249,16 @@ impl App {
             if tab.selected_phase > 0 {
                 tab.selected_phase -= 1;
 
-                // If moving to previous phase that's expanded with tasks, select last task
+                // If moving to previous phase that's expanded, select its last row
                 if let Some(phase) = phases.get(tab.selected_phase) {
-                    if tab.expanded_phases.contains(&tab.selected_phase) && !phase.tasks.is_empty()
-                    {
-                        let last_task = phase.tasks.last().unwrap();
-                        tab.selected_task = Some(last_task.id.clone());
-
-                        // If last task is expanded with agents, select last agent
-                        if tab.expanded_tasks.contains(&last_task.id)
-                            && !last_task.agents.is_empty()
-                        {
-                            tab.selected_agent = Some(last_task.agents.last().unwrap().id.clone());
-                        }
+                    if tab.expanded_phases.contains(&tab.selected_phase) {
+                        (tab.selected_task, tab.selected_agent, tab.selected_file) =
+                            last_row_in_phase(phase, &tab.expanded_tasks);
                     }
                 }
+            } else {
+                // Above the first phase is the last artifact of the result
+                tab.selected_artifact = artifacts.checked_sub(1);
             }
         }
     }
@@ -258,6 +315,18 @@ impl App {
                 return;
             }
 
+            // If we're on an output file, move to the next file or phase
+            if let Some(file_idx) = self.selected_file {
+                let files = phases.get(self.selected_phase).map_or(0, |p| p.output_files.len());
+                if file_idx + 1 < files {
+                    self.selected_file = Some(file_idx + 1);
+                } else if self.selected_phase + 1 < phases.len() {
+                    self.selected_file = None;
+                    self.selected_phase += 1;
+                }
+                return;
+            }
+
             let mut just_exited_agent = false;
 
             // If we're on an agent, try to move to next agent in same task
@@ -303,17 +372,23 @@ impl App {
                         return;
                     }
                 }
-                // No more tasks, move to next phase
+                // No more tasks, move to the output files or next phase
                 self.selected_task = None;
-            }
-
-            // Move to next phase or first task if expanded
-            if self.expanded_phases.contains(&self.selected_phase) {
+                if !phases[self.selected_phase].output_files.is_empty() {
+                    self.selected_file = Some(0);
+                    return;
+                }
+            } else if self.expanded_phases.contains(&self.selected_phase) {
+                // Move to first task or output file if expanded
                 let phase = &phases[self.selected_phase];
-                if !phase.tasks.is_empty() && self.selected_task.is_none() {
+                if !phase.tasks.is_empty() {
                     self.selected_task = Some(phase.tasks[0].id.clone());
                     return;
                 }
+                if !phase.output_files.is_empty() {
+                    self.selected_file = Some(0);
+                    return;
+                }
             }
 
             if self.selected_phase + 1 < phases.len() {
@@ -330,6 +405,17 @@ impl App {
                 return;
             }
 
+            // If we're on an output file, move to the previous file or the
+            // last task of the phase
+            if let Some(file_idx) = self.selected_file {
+                self.selected_file = file_idx.checked_sub(1);
+                if self.selected_file.is_none() {
+                    (self.selected_task, self.selected_agent) =
+                        last_task_row(&phases[self.selected_phase], &self.expanded_tasks);
+                }
+                return;
+            }
+
             // If we're on an agent, try to move to previous agent
             if let Some(ref agent_id) = self.selected_agent {
                 let phase = &phases[self.selected_phase];
@@ -380,96 +466,195 @@ impl App {
                 self.selected_task = None;
                 self.selected_agent = None;
 
-                // If new phase is expanded and has tasks, jump to last task
+                // If new phase is expanded, jump to its last row
                 if self.expanded_phases.contains(&self.selected_phase) {
-                    let phase = &phases[self.selected_phase];
-                    if !phase.tasks.is_empty() {
-                        let last_task = &phase.tasks[phase.tasks.len() - 1];
-                        self.selected_task = Some(last_task.id.clone());
-                        // If last task is expanded with agents, jump to last agent
-                        if self.expanded_tasks.contains(&last_task.id)
-                            && !last_task.agents.is_empty()
-                        {
-                            self.selected_agent =
-                                Some(last_task.agents[last_task.agents.len() - 1].id.clone());
-                        }
-                    }
+                    (self.selected_task, self.selected_agent, self.selected_file) =
+                        last_row_in_phase(&phases[self.selected_phase], &self.expanded_tasks);
                 }
             }
         }
     }
 
-    pub fn update_workflow_scroll(&mut self, viewport_height: usize) {
-        // Calculate which line the selected item is on and adjust scroll to keep it visible
-        if let Ok(phases) = self.workflow_phases.lock() {
-            let mut current_line = 0;
-            let mut selected_line = 0;
-
-            for phase in phases.iter() {
-                // Check if this phase is selected
-                if self.selected_phase == phase.id
-                    && self.selected_task.is_none()
-                    && self.selected_agent.is_none()
-                {
-                    selected_line = current_line;
+    /// Follow new activity again only if the selection is on the last row
+    ///
+    /// Called after the selection was moved by hand, so scrolling up stops
+    /// following and coming back to the bottom resumes it.
+    pub fn update_follow(&mut self) {
+        match self.current_view {
+            View::Tabs => {
+                if let Some(tab) = self.open_tabs.get_mut(self.active_tab_idx) {
+                    if let Ok(phases) = tab.workflow_phases.lock() {
+                        let last = last_row(&phases, &tab.expanded_phases, &tab.expanded_tasks);
+                        tab.follow = tab.selected_artifact.is_none()
+                            && last
+                                == (
+                                    tab.selected_phase,
+                                    tab.selected_task.clone(),
+                                    tab.selected_agent.clone(),
+                                    tab.selected_file,
+                                );
+                    }
                 }
-                current_line += 1; // Phase header
-
-                if self.expanded_phases.contains(&phase.id) {
-                    for task in &phase.tasks {
-                        // Check if this task is selected
-                        if self.selected_phase == phase.id
-                            && Some(&task.id) == self.selected_task.as_ref()
-                            && self.selected_agent.is_none()
-                        {
-                            selected_line = current_line;
-                        }
-                        current_line += 1; // Task header
-
-                        if self.expanded_tasks.contains(&task.id) {
-                            // Count task messages
-                            current_line += task.messages.len();
-
-                            for agent in &task.agents {
-                                // Check if this agent is selected
-                                if Some(&agent.id) == self.selected_agent.as_ref() {
-                                    selected_line = current_line;
-                                }
-                                current_line += 1; // Agent header
+            }
+            View::WorkflowRunning(_) => {
+                if let Ok(phases) = self.workflow_phases.lock() {
+                    let last = last_row(&phases, &self.expanded_phases, &self.expanded_tasks);
+                    self.workflow_follow = last
+                        == (
+                            self.selected_phase,
+                            self.selected_task.clone(),
+                            self.selected_agent.clone(),
+                            self.selected_file,
+                        );
+                }
+            }
+            _ => {}
+        }
+    }
 
-                                if self.expanded_agents.contains(&agent.id) {
-                                    current_line += agent.messages.len();
-                                }
-                            }
+    /// Turn following on or off; turning it on selects the last row
+    pub fn toggle_follow(&mut self) {
+        match self.current_view {
+            View::Tabs => {
+                if let Some(tab) = self.open_tabs.get_mut(self.active_tab_idx) {
+                    tab.follow = !tab.follow;
+                    if tab.follow {
+                        tab.selected_artifact = None;
+                        if let Ok(phases) = tab.workflow_phases.lock() {
+                            (
+                                tab.selected_phase,
+                                tab.selected_task,
+                                tab.selected_agent,
+                                tab.selected_file,
+                            ) = last_row(&phases, &tab.expanded_phases, &tab.expanded_tasks);
                         }
                     }
-
-                    // Count output files
-                    if !phase.output_files.is_empty() {
-                        current_line += 1; // "Output files:" header
-                        current_line += phase.output_files.len();
+                }
+            }
+            View::WorkflowRunning(_) => {
+                self.workflow_follow = !self.workflow_follow;
+                if self.workflow_follow {
+                    if let Ok(phases) = self.workflow_phases.lock() {
+                        (
+                            self.selected_phase,
+                            self.selected_task,
+                            self.selected_agent,
+                            self.selected_file,
+                        ) = last_row(&phases, &self.expanded_phases, &self.expanded_tasks);
                     }
                 }
+                self.update_workflow_scroll(30); // Estimate viewport height
+            }
+            _ => {}
+        }
+    }
 
-                current_line += 1; // Empty line after phase
+    /// Scroll the shown phase tree so its selected row is in view
+    ///
+    /// Works on the active tab in the Tabs view, so each tab keeps its own
+    /// scroll position.
+    pub fn update_workflow_scroll(&mut self, viewport_height: usize) {
+        match self.current_view {
+            View::Tabs => {
+                if let Some(tab) = self.open_tabs.get_mut(self.active_tab_idx) {
+                    if let Ok(phases) = tab.workflow_phases.lock() {
+                        let line = selected_line(
+                            &phases,
+                            &tab.expanded_phases,
+                            &tab.expanded_tasks,
+                            &tab.expanded_agents,
+                            (
+                                tab.selected_phase,
+                                &tab.selected_task,
+                                &tab.selected_agent,
+                                tab.selected_file,
+                            ),
+                        );
+                        tab.scroll_offset =
+                            scroll_to_line(tab.scroll_offset, line, viewport_height);
+                    }
+                }
+            }
+            View::WorkflowRunning(_) => {
+                if let Ok(phases) = self.workflow_phases.lock() {
+                    let line = selected_line(
+                        &phases,
+                        &self.expanded_phases,
+                        &self.expanded_tasks,
+                        &self.expanded_agents,
+                        (
+                            self.selected_phase,
+                            &self.selected_task,
+                            &self.selected_agent,
+                            self.selected_file,
+                        ),
+                    );
+                    self.workflow_scroll_offset =
+                        scroll_to_line(self.workflow_scroll_offset, line, viewport_height);
+                }
             }
+            _ => {}
+        }
+    }
 
-            // Adjust scroll offset to keep selected line visible
-            // Leave some padding at top and bottom
-            let padding = 2;
-            let visible_lines = viewport_height.saturating_sub(2); // Account for borders
+    /// Move the tree selection a page down (or up)
+    ///
+    /// Like moving row by row, this stops following unless the selection
+    /// ends on the last row.
+    pub fn page_tree(&mut self, down: bool) {
+        for _ in 0..TREE_PAGE_ROWS {
+            match (&self.current_view, down) {
+                (View::Tabs, true) => self.navigate_tab_down(),
+                (View::Tabs, false) => self.navigate_tab_up(),
+                (View::WorkflowRunning(_), true) => self.navigate_workflow_down(),
+                (View::WorkflowRunning(_), false) => self.navigate_workflow_up(),
+                _ => return,
+            }
+        }
+        self.update_follow();
+        self.update_workflow_scroll(30); // Estimate viewport height
+    }
 
-            if selected_line < self.workflow_scroll_offset + padding {
-                // Selected line is above visible area, scroll up
-                self.workflow_scroll_offset = selected_line.saturating_sub(padding);
-            } else if selected_line
-                >= self.workflow_scroll_offset + visible_lines.saturating_sub(padding)
-            {
-                // Selected line is below visible area, scroll down
-                self.workflow_scroll_offset = selected_line
-                    .saturating_sub(visible_lines.saturating_sub(padding).saturating_sub(1));
+    /// Select the first (or last) row of the tree
+    ///
+    /// The last row resumes following, the first stops it.
+    pub fn select_tree_edge(&mut self, last: bool) {
+        match self.current_view {
+            View::Tabs => {
+                if let Some(tab) = self.open_tabs.get_mut(self.active_tab_idx) {
+                    tab.selected_artifact = None;
+                    if let Ok(phases) = tab.workflow_phases.lock() {
+                        (
+                            tab.selected_phase,
+                            tab.selected_task,
+                            tab.selected_agent,
+                            tab.selected_file,
+                        ) = if last {
+                            last_row(&phases, &tab.expanded_phases, &tab.expanded_tasks)
+                        } else {
+                            (0, None, None, None)
+                        };
+                    }
+                }
+            }
+            View::WorkflowRunning(_) => {
+                if let Ok(phases) = self.workflow_phases.lock() {
+                    (
+                        self.selected_phase,
+                        self.selected_task,
+                        self.selected_agent,
+                        self.selected_file,
+                    ) = if last {
+                        last_row(&phases, &self.expanded_phases, &self.expanded_tasks)
+                    } else {
+                        (0, None, None, None)
+                    };
+                }
             }
+            _ => return,
         }
+        self.update_follow();
+        self.update_workflow_scroll(30); // Estimate viewport height
     }
 
     /// Switch to the left pane in the current workflow view
@@ -527,4 +712,198 @@ impl App {
             }
         }
     }
+
+    /// Scroll the phase tree sideways, to read messages and paths cut off
+    /// at the edge of the pane
+    pub fn scroll_tree_horizontally(&mut self, right: bool) {
+        let (phases, offset) = match self.current_view {
+            View::WorkflowRunning(_) => (&self.workflow_phases, &mut self.workflow_h_scroll),
+            View::Tabs => match self.open_tabs.get_mut(self.active_tab_idx) {
+                Some(tab) => (&tab.workflow_phases, &mut tab.h_scroll),
+                None => return,
+            },
+            _ => return,
+        };
+        let widest = phases.lock().map(|phases| widest_tree_text(&phases)).unwrap_or(0);
+        *offset = if right {
+            (*offset + HORIZONTAL_SCROLL_STEP).min(widest)
+        } else {
+            offset.saturating_sub(HORIZONTAL_SCROLL_STEP)
+        };
+    }
+}
+
+/// Width of the longest name, message or path in the phase tree, plus its
+/// deepest indent
+///
+/// Bounds the horizontal scroll; rendering then clamps it to the lines as
+/// they are drawn.
+fn widest_tree_text(phases: &[WorkflowPhase]) -> usize {
+    let texts = phases.iter().flat_map(|phase| {
+        let files = phase.output_files.iter().map(|(path, _)| path.as_str());
+        let tasks = phase.tasks.iter().flat_map(|task| {
+            let agents = task.agents.iter().flat_map(|agent| {
+                std::iter::once(agent.description.as_str())
+                    .chain(agent.messages.iter().map(|m| m.text.as_str()))
+            });
+            std::iter::once(task.description.as_str())
+                .chain(task.messages.iter().map(|m| m.text.as_str()))
+                .chain(agents)
+        });
+        std::iter::once(phase.name.as_str()).chain(files).chain(tasks)
+    });
+    texts.map(|text| text.width()).max().map_or(0, |width| width + 8)
+}
+
+/// Line of the selected phase, task, agent or output file in the rendered
+/// tree
+fn selected_line(
+    phases: &[WorkflowPhase],
+    expanded_phases: &HashSet<usize>,
+    expanded_tasks: &HashSet<String>,
+    expanded_agents: &HashSet<String>,
+    (selected_phase, selected_task, selected_agent, selected_file): (
+        usize,
+        &Option<String>,
+        &Option<String>,
+        Option<usize>,
+    ),
+) -> usize {
+    let mut current_line = 0;
+    let mut selected = 0;
+
+    for phase in phases {
+        // Check if this phase is selected
+        if selected_phase == phase.id
+            && selected_task.is_none()
+            && selected_agent.is_none()
+            && selected_file.is_none()
+        {
+            selected = current_line;
+        }
+        current_line += 1; // Phase header
+
+        if expanded_phases.contains(&phase.id) {
+            current_line += usize::from(phase.error.is_some()); // Phase error
+            for task in &phase.tasks {
+                // Check if this task is selected
+                if selected_phase == phase.id
+                    && Some(&task.id) == selected_task.as_ref()
+                    && selected_agent.is_none()
+                {
+                    selected = current_line;
+                }
+                current_line += 1; // Task header
+
+                if expanded_tasks.contains(&task.id) {
+                    // Count task messages
+                    current_line += task.messages.len();
+
+                    for agent in &task.agents {
+                        // Check if this agent is selected
+                        if Some(&agent.id) == selected_agent.as_ref() {
+                            selected = current_line;
+                        }
+                        current_line += 1; // Agent header
+
+                        if expanded_agents.contains(&agent.id) {
+                            current_line += agent.messages.len();
+                        }
+                    }
+                }
+            }
+
+            // Count output files
+            if !phase.output_files.is_empty() {
+                current_line += 1; // "Output files:" header
+                if selected_phase == phase.id {
+                    if let Some(file_idx) = selected_file {
+                        selected = current_line + file_idx;
+                    }
+                }
+                current_line += phase.output_files.len();
+            }
+        }
+
+        current_line += 1; // Empty line after phase
+    }
+
+    selected
+}
+
+/// Scroll offset that keeps `line` in a viewport of `viewport_height`,
+/// moving `offset` as little as possible
+fn scroll_to_line(offset: usize, line: usize, viewport_height: usize) -> usize {
+    // Leave some padding at top and bottom
+    let padding = 2;
+    let visible_lines = viewport_height.saturating_sub(2); // Account for borders
+
+    if line < offset + padding {
+        // Selected line is above visible area, scroll up
+        line.saturating_sub(padding)
+    } else if line >= offset + visible_lines.saturating_sub(padding) {
+        // Selected line is below visible area, scroll down
+        line.saturating_sub(visible_lines.saturating_sub(padding).saturating_sub(1))
+    } else {
+        offset
+    }
+}
+
+/// Phase index, task, agent and output file of the last row of the phase
+/// tree
+fn last_row(
+    phases: &[WorkflowPhase],
+    expanded_phases: &HashSet<usize>,
+    expanded_tasks: &HashSet<String>,
+) -> (usize, Option<String>, Option<String>, Option<usize>) {
+    let phase_idx = phases.len().saturating_sub(1);
+    match phases.last() {
+        Some(phase) if expanded_phases.contains(&phase_idx) => {
+            let (task, agent, file) = last_row_in_phase(phase, expanded_tasks);
+            (phase_idx, task, agent, file)
+        }
+        _ => (phase_idx, None, None, None),
+    }
+}
+
+/// Task, agent and output file of the last row of an expanded phase; all
+/// `None` if the phase has no rows below its header
+fn last_row_in_phase(
+    phase: &WorkflowPhase,
+    expanded_tasks: &HashSet<String>,
+) -> (Option<String>, Option<String>, Option<usize>) {
+    match phase.output_files.len().checked_sub(1) {
+        Some(file_idx) => (None, None, Some(file_idx)),
+        None => {
+            let (task, agent) = last_task_row(phase, expanded_tasks);
+            (task, agent, None)
+        }
+    }
+}
+
+/// Task and agent of the last task row of a phase, above its output files
+fn last_task_row(
+    phase: &WorkflowPhase,
+    expanded_tasks: &HashSet<String>,
+) -> (Option<String>, Option<String>) {
+    match phase.tasks.last() {
+        Some(task) => {
+            let agent = task
+                .agents
+                .last()
+                .filter(|_| expanded_tasks.contains(&task.id))
+                .map(|agent| agent.id.clone());
+            (Some(task.id.clone()), agent)
+        }
+        None => (None, None),
+    }
+}
+
+/// Artifacts listed in the tab's result banner, none before the `Result` event
+fn result_artifacts(tab: &WorkflowTab) -> usize {
+    tab.result
+        .lock()
+        .ok()
+        .and_then(|result| result.as_ref().map(|result| result.artifacts.len()))
+        .unwrap_or(0)
 }
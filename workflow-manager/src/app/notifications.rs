@@ -92,13 +92,15 @@ impl NotificationManager {
             .collect()
     }
 
-    /// Remove expired notifications
-    pub fn cleanup_expired(&mut self) {
+    /// Remove expired notifications; true if any was removed
+    pub fn cleanup_expired(&mut self) -> bool {
         let now = Instant::now();
+        let before = self.notifications.len();
         self.notifications.retain(|n| match n.auto_dismiss_after {
             Some(duration) => now.duration_since(n.timestamp) < duration,
             None => true,
         });
+        self.notifications.len() != before
     }
 }
 
@@ -0,0 +1,253 @@
+//! Field values in JSON or YAML files
+//!
+//! `Ctrl+S` in the edit view writes the current values to a file and `Ctrl+O`
+//! reads them back, both through a path prompt (Tab completes paths). The
+//! file is a flat map of field name to value, and `--params-file` of the
+//! headless mode reads the same format:
+//!
+//! ```yaml
+//! input: How does auth work?
+//! phases: 0,1
+//! batch_size: 4
+//! ```
+//!
+//! Files ending in `.json` are written as JSON, any other as YAML. Reading
+//! accepts both, since YAML is a superset of JSON.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use serde_yaml::Value;
+
+use super::*;
+
+/// What the params file prompt does with the path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamsFileAction {
+    Load,
+    Save,
+}
+
+/// Text of a scalar value as a field would hold it
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => Some(String::new()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::String(s) => Some(s.clone()),
+        _ => None,
+    }
+}
+
+/// Field values of a JSON or YAML map of field name to value
+///
+/// Numbers and booleans become their text, and a list becomes the
+/// comma-separated value of a multi-select field. Null leaves a field empty.
+pub fn parse_params(content: &str) -> Result<BTreeMap<String, String>> {
+    let map: BTreeMap<String, Value> = serde_yaml::from_str(content)?;
+    map.into_iter()
+        .map(|(name, value)| {
+            let text = match &value {
+                Value::Sequence(items) => items
+                    .iter()
+                    .map(scalar_text)
+                    .collect::<Option<Vec<_>>>()
+                    .map(|items| items.join(",")),
+                _ => scalar_text(&value),
+            };
+            let text =
+                text.ok_or_else(|| anyhow!("'{}' is not a value or a list of values", name))?;
+            Ok((name, text))
+        })
+        .collect()
+}
+
+/// Field values as JSON, or as YAML unless `json`
+pub fn format_params(values: &BTreeMap<String, String>, json: bool) -> Result<String> {
+    if json {
+        Ok(serde_json::to_string_pretty(values)? + "\n")
+    } else {
+        Ok(serde_yaml::to_string(values)?)
+    }
+}
+
+/// Read the field values of a params file
+pub fn read_params_file(path: &Path) -> Result<BTreeMap<String, String>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    parse_params(&content).with_context(|| format!("Invalid params file {}", path.display()))
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"))
+}
+
+impl App {
+    /// Open the path prompt for loading or saving the field values,
+    /// pre-filled with `<workflow id>.params.yaml`
+    pub fn open_params_file_prompt(&mut self, action: ParamsFileAction) {
+        let View::WorkflowEdit(idx) = self.current_view else {
+            return;
+        };
+        let Some(workflow) = self.workflows.get(idx) else {
+            return;
+        };
+        let file_name = format!("{}.params.yaml", workflow.info.id);
+        self.edit_buffer = self
+            .current_dir
+            .join(file_name)
+            .to_string_lossy()
+            .to_string();
+        self.params_file_prompt = Some(action);
+    }
+
+    pub fn cancel_params_file_prompt(&mut self) {
+        self.params_file_prompt = None;
+        self.edit_buffer.clear();
+    }
+
+    /// Load or save the field values at the prompted path
+    pub fn confirm_params_file_prompt(&mut self) {
+        let Some(action) = self.params_file_prompt.take() else {
+            return;
+        };
+        let path = PathBuf::from(std::mem::take(&mut self.edit_buffer));
+        let path = if path.is_absolute() {
+            path
+        } else {
+            self.current_dir.join(path)
+        };
+        let View::WorkflowEdit(idx) = self.current_view else {
+            return;
+        };
+        match action {
+            ParamsFileAction::Load => self.load_params_file(idx, &path),
+            ParamsFileAction::Save => self.save_params_file(idx, &path),
+        }
+    }
+
+    /// Replace the field values with those of the file
+    ///
+    /// Secrets already entered are kept unless the file sets them, since
+    /// saved files never hold them. Unknown fields are dropped and empty
+    /// required fields are named, each in a warning.
+    fn load_params_file(&mut self, idx: usize, path: &Path) {
+        let Some(workflow) = self.workflows.get(idx) else {
+            return;
+        };
+        let values = match read_params_file(path) {
+            Ok(values) => values,
+            Err(e) => {
+                self.notifications.error("Params Not Loaded", format!("{:#}", e));
+                return;
+            }
+        };
+
+        let fields = &workflow.info.fields;
+        let mut unknown = Vec::new();
+        self.field_values.retain(|name, _| {
+            fields
+                .iter()
+                .any(|field| &field.name == name && crate::utils::is_secret(field))
+        });
+        for (name, value) in values {
+            if fields.iter().any(|field| field.name == name) {
+                self.field_values.insert(name, value);
+            } else {
+                unknown.push(name);
+            }
+        }
+        let missing: Vec<&str> = fields
+            .iter()
+            .filter(|field| field.required)
+            .filter(|field| self.field_values.get(&field.name).is_none_or(|v| v.is_empty()))
+            .map(|field| field.name.as_str())
+            .collect();
+
+        if !unknown.is_empty() {
+            self.notifications.warning(
+                "Unknown Fields Dropped",
+                format!("Not defined by {}: {}", workflow.info.name, unknown.join(", ")),
+            );
+        }
+        if missing.is_empty() {
+            self.notifications
+                .success("Params Loaded", path.display().to_string());
+        } else {
+            self.notifications.warning(
+                "Params Loaded",
+                format!("Required fields still empty: {}", missing.join(", ")),
+            );
+        }
+        self.revalidate_fields(idx);
+    }
+
+    /// Write the non-empty field values to the file, leaving out secrets
+    fn save_params_file(&mut self, idx: usize, path: &Path) {
+        let Some(workflow) = self.workflows.get(idx) else {
+            return;
+        };
+        let values: BTreeMap<String, String> = workflow
+            .info
+            .fields
+            .iter()
+            .filter(|field| !crate::utils::is_secret(field))
+            .filter_map(|field| {
+                let value = self.field_values.get(&field.name)?;
+                (!value.is_empty()).then(|| (field.name.clone(), value.clone()))
+            })
+            .collect();
+
+        let result = format_params(&values, is_json(path)).and_then(|content| {
+            std::fs::write(path, content)
+                .with_context(|| format!("Failed to write {}", path.display()))
+        });
+        match result {
+            Ok(()) => self
+                .notifications
+                .success("Params Saved", path.display().to_string()),
+            Err(e) => self
+                .notifications
+                .error("Params Not Saved", format!("{:#}", e)),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_params_round_trip() {
+        let values = parse_params(
+            r#"
+            input: How does auth work?
+            batch_size: 4
+            dry_run: true
+            phases: [0, 1]
+            notes: null
+            "#,
+        )
+        .unwrap();
+        assert_eq!(values["input"], "How does auth work?");
+        assert_eq!(values["batch_size"], "4");
+        assert_eq!(values["dry_run"], "true");
+        assert_eq!(values["phases"], "0,1");
+        assert_eq!(values["notes"], "");
+
+        let json = parse_params(r#"{"input": "x", "count": 2.5}"#).unwrap();
+        assert_eq!(json["count"], "2.5");
+        assert!(parse_params("nested: {a: 1}").is_err());
+        assert!(parse_params("- not a map").is_err());
+
+        // Numeric-looking text stays text once written
+        for json in [true, false] {
+            let written = format_params(&values, json).unwrap();
+            assert_eq!(parse_params(&written).unwrap(), values);
+        }
+        assert!(is_json(Path::new("run.JSON")));
+        assert!(!is_json(Path::new("run.yaml")));
+    }
+}
@@ -0,0 +1,236 @@
+//! Named parameter presets
+//!
+//! `s` in the edit view saves the current field values under a name, and `o`
+//! lists the saved presets of the workflow to load one back. Unlike the
+//! per-field history, a preset captures the whole parameter set. Presets are
+//! kept in `~/.config/workflow-manager/presets.toml`, one table per workflow
+//! id:
+//!
+//! ```toml
+//! [research.docs]
+//! dir = "/home/me/docs"
+//! system_prompt = "Answer briefly"
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use super::*;
+
+/// Saved field values: workflow id -> preset name -> field name -> value
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Presets {
+    workflows: BTreeMap<String, BTreeMap<String, BTreeMap<String, String>>>,
+}
+
+impl Presets {
+    /// Location of the presets file, next to the user-defined workflows
+    pub fn path() -> Option<PathBuf> {
+        std::env::var("HOME")
+            .ok()
+            .map(|home| PathBuf::from(home).join(".config/workflow-manager/presets.toml"))
+    }
+
+    /// Load the presets file, or no presets if it does not exist
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::from_toml(&content).with_context(|| format!("Invalid presets {}", path.display()))
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self> {
+        Ok(Self {
+            workflows: toml::from_str(content)?,
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, toml::to_string(&self.workflows)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Names of the presets saved for a workflow, sorted
+    pub fn names(&self, workflow_id: &str) -> Vec<String> {
+        self.workflows
+            .get(workflow_id)
+            .map(|presets| presets.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, workflow_id: &str, name: &str) -> Option<&BTreeMap<String, String>> {
+        self.workflows.get(workflow_id)?.get(name)
+    }
+
+    /// Store a preset, replacing one with the same name
+    pub fn insert(&mut self, workflow_id: &str, name: &str, values: BTreeMap<String, String>) {
+        self.workflows
+            .entry(workflow_id.to_string())
+            .or_default()
+            .insert(name.to_string(), values);
+    }
+}
+
+impl App {
+    /// Open the name prompt for saving the current field values
+    pub fn open_preset_save(&mut self) {
+        if matches!(self.current_view, View::WorkflowEdit(_)) {
+            self.edit_buffer.clear();
+            self.saving_preset = true;
+        }
+    }
+
+    pub fn cancel_preset_save(&mut self) {
+        self.saving_preset = false;
+        self.edit_buffer.clear();
+    }
+
+    /// Save the non-empty field values under the prompted name
+    pub fn confirm_preset_save(&mut self) {
+        self.saving_preset = false;
+        let name = std::mem::take(&mut self.edit_buffer).trim().to_string();
+        let View::WorkflowEdit(idx) = self.current_view else {
+            return;
+        };
+        let Some(workflow) = self.workflows.get(idx) else {
+            return;
+        };
+        if name.is_empty() {
+            self.notifications.warning("Preset Not Saved", "A preset needs a name");
+            return;
+        }
+        let Some(path) = Presets::path() else {
+            self.notifications.error("Preset Not Saved", "HOME is not set");
+            return;
+        };
+
+        // Secrets are never written to disk
+        let values = workflow
+            .info
+            .fields
+            .iter()
+            .filter(|field| !crate::utils::is_secret(field))
+            .filter_map(|field| {
+                let value = self.field_values.get(&field.name)?;
+                (!value.is_empty()).then(|| (field.name.clone(), value.clone()))
+            })
+            .collect();
+
+        let result = Presets::load(&path).and_then(|mut presets| {
+            presets.insert(&workflow.info.id, &name, values);
+            presets.save(&path)
+        });
+        match result {
+            Ok(()) => self
+                .notifications
+                .success("Preset Saved", format!("Saved '{}'", name)),
+            Err(e) => self
+                .notifications
+                .error("Preset Not Saved", format!("{:#}", e)),
+        };
+    }
+
+    /// List the presets of the edited workflow in the dropdown
+    pub fn show_preset_dropdown(&mut self) {
+        let View::WorkflowEdit(idx) = self.current_view else {
+            return;
+        };
+        let Some(workflow) = self.workflows.get(idx) else {
+            return;
+        };
+        let presets = match Presets::path().map(|path| Presets::load(&path)) {
+            Some(Ok(presets)) => presets,
+            Some(Err(e)) => {
+                self.notifications
+                    .error("Presets Unavailable", format!("{:#}", e));
+                return;
+            }
+            None => Presets::default(),
+        };
+
+        let names = presets.names(&workflow.info.id);
+        if names.is_empty() {
+            self.notifications
+                .info("No Presets", "Press s to save the current values as a preset");
+            return;
+        }
+        self.presets = presets;
+        self.preset_items = names;
+        self.dropdown_selected = 0;
+        self.show_dropdown = true;
+    }
+
+    /// Replace the field values with the highlighted preset
+    ///
+    /// Values of fields the workflow no longer defines are dropped with a
+    /// warning.
+    pub(super) fn load_selected_preset(&mut self) {
+        let View::WorkflowEdit(idx) = self.current_view else {
+            return;
+        };
+        let Some(workflow) = self.workflows.get(idx) else {
+            return;
+        };
+        let Some(name) = self.preset_items.get(self.dropdown_selected).cloned() else {
+            return;
+        };
+        let Some(values) = self.presets.get(&workflow.info.id, &name).cloned() else {
+            return;
+        };
+
+        let mut dropped = Vec::new();
+        self.field_values.clear();
+        for (field_name, value) in values {
+            if workflow.info.fields.iter().any(|f| f.name == field_name) {
+                self.field_values.insert(field_name, value);
+            } else {
+                dropped.push(field_name);
+            }
+        }
+
+        if !dropped.is_empty() {
+            self.notifications.warning(
+                "Preset Fields Dropped",
+                format!("No longer defined: {}", dropped.join(", ")),
+            );
+        }
+        self.close_dropdown();
+        self.revalidate_fields(idx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_round_trip() {
+        let mut presets = Presets::from_toml(
+            r#"
+            [research.docs]
+            dir = "/home/me/docs"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(presets.names("research"), ["docs"]);
+        assert!(presets.names("task_planner").is_empty());
+
+        presets.insert(
+            "research",
+            "my notes",
+            BTreeMap::from([("dir".to_string(), "~/notes".to_string())]),
+        );
+        let saved = toml::to_string(&presets.workflows).unwrap();
+        let reloaded = Presets::from_toml(&saved).unwrap();
+        assert_eq!(reloaded, presets);
+        assert_eq!(reloaded.names("research"), ["docs", "my notes"]);
+        assert_eq!(reloaded.get("research", "my notes").unwrap()["dir"], "~/notes");
+    }
+}
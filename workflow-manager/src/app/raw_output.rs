@@ -0,0 +1,77 @@
+//! Size cap of the raw output buffers
+//!
+//! Chatty workflows would otherwise grow `workflow_output` without bound
+//! over a long run. Once a buffer holds more lines than the cap, the oldest
+//! ones are dropped and the first line becomes a marker counting them. The
+//! structured phase tree is never capped.
+
+use workflow_manager_sdk::LogLevel;
+
+use super::models::LogLine;
+
+pub const DEFAULT_MAX_OUTPUT_LINES: usize = 10_000;
+
+fn truncation_marker(count: usize) -> LogLine {
+    // Hiding the marker would be misleading, so it passes every filter
+    LogLine::new(LogLevel::Error, format!("[…{} lines truncated…]", count))
+}
+
+/// Number of dropped lines if `line` is the truncation marker
+fn truncated_count(line: &str) -> Option<usize> {
+    line.strip_prefix("[…")?
+        .strip_suffix(" lines truncated…]")?
+        .parse()
+        .ok()
+}
+
+/// Append `line` to a raw output buffer capped at `max_lines`
+pub fn push_output_line(output: &mut Vec<LogLine>, line: LogLine, max_lines: usize) {
+    output.push(line);
+    truncate_output(output, max_lines);
+}
+
+/// Drop the oldest lines beyond `max_lines`, counting them in the marker
+///
+/// A tenth of the cap goes at once, so a full buffer is not shifted for
+/// every new line.
+pub fn truncate_output(output: &mut Vec<LogLine>, max_lines: usize) {
+    let dropped = output.first().and_then(|line| truncated_count(line));
+    let start = usize::from(dropped.is_some());
+    let lines = output.len() - start;
+    if lines <= max_lines {
+        return;
+    }
+
+    let excess = (lines - max_lines + max_lines / 10).min(lines);
+    output.drain(start..start + excess);
+    let marker = truncation_marker(dropped.unwrap_or(0) + excess);
+    if dropped.is_some() {
+        output[0] = marker;
+    } else {
+        output.insert(0, marker);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oldest_lines_are_dropped_and_counted() {
+        let mut output = Vec::new();
+        for n in 0..25 {
+            push_output_line(
+                &mut output,
+                LogLine::new(LogLevel::Debug, format!("line {}", n)),
+                10,
+            );
+        }
+
+        // Lines go two at a time: the one over the cap and a tenth of it
+        assert_eq!(&*output[0], "[…16 lines truncated…]");
+        assert_eq!(truncated_count(&output[0]), Some(16));
+        assert_eq!(output.len(), 10);
+        assert_eq!(&*output[1], "line 16");
+        assert_eq!(&**output.last().unwrap(), "line 24");
+    }
+}
@@ -0,0 +1,143 @@
+//! Reloading workflow definitions without restarting (Ctrl+R in the list)
+//!
+//! New workflows are appended and changed ones replaced in place, so the
+//! workflow indices held by open tabs stay valid. For the same reason,
+//! workflows that are no longer found stay listed until the next start.
+
+use workflow_manager_sdk::Workflow;
+
+use crate::discovery::{discover_workflows_with_warnings, DiscoveredWorkflow};
+
+use super::*;
+
+/// TUI model of a discovered workflow
+pub fn workflow_from_discovered(dw: &DiscoveredWorkflow) -> Workflow {
+    Workflow {
+        info: workflow_manager_sdk::WorkflowInfo {
+            id: dw.metadata.id.clone(),
+            name: dw.metadata.name.clone(),
+            description: dw.metadata.description.clone(),
+            status: workflow_manager_sdk::WorkflowStatus::NotStarted,
+            metadata: dw.metadata.clone(),
+            fields: dw.fields.clone(),
+            progress_messages: vec![],
+        },
+        source: dw.source(),
+    }
+}
+
+/// Whether a rediscovered workflow differs from the listed one
+fn definition_changed(old: &Workflow, new: &Workflow) -> bool {
+    // The schema types have no PartialEq, their JSON form is what is shown
+    let json = |w: &Workflow| serde_json::to_value((&w.info.metadata, &w.info.fields)).ok();
+    old.source != new.source || json(old) != json(new)
+}
+
+impl App {
+    /// Re-run discovery and merge the result into the workflow list and the
+    /// runtime, leaving open tabs untouched
+    pub fn reload_workflows(&mut self) {
+        let (discovered, warnings) = discover_workflows_with_warnings();
+        for warning in warnings {
+            self.notifications.warning("Workflow Skipped", warning);
+        }
+
+        let summary = self.merge_workflows(discovered.iter().map(workflow_from_discovered).collect());
+
+        // Launches look workflows up in the runtime, so it learns them too
+        if let Some(registry) = &self.workflow_registry {
+            let mut registry = registry.lock().unwrap();
+            for workflow in discovered {
+                registry.insert(workflow.metadata.id.clone(), workflow);
+            }
+        }
+
+        self.notifications.info("Workflows Reloaded", summary);
+    }
+
+    /// Add new workflows and replace changed ones, describing what changed
+    fn merge_workflows(&mut self, workflows: Vec<Workflow>) -> String {
+        let missing = self
+            .workflows
+            .iter()
+            .filter(|w| !workflows.iter().any(|new| new.info.id == w.info.id))
+            .count();
+
+        let (mut added, mut updated) = (0, 0);
+        for workflow in workflows {
+            match self
+                .workflows
+                .iter_mut()
+                .find(|w| w.info.id == workflow.info.id)
+            {
+                Some(existing) => {
+                    if definition_changed(existing, &workflow) {
+                        *existing = workflow;
+                        updated += 1;
+                    }
+                }
+                None => {
+                    self.workflows.push(workflow);
+                    added += 1;
+                }
+            }
+        }
+
+        let mut summary = format!("{} new, {} updated", added, updated);
+        if missing > 0 {
+            summary.push_str(&format!(
+                ", {} no longer found (listed until restart)",
+                missing
+            ));
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workflow(id: &str, description: &str) -> Workflow {
+        let metadata: workflow_manager_sdk::WorkflowMetadata =
+            serde_json::from_value(serde_json::json!({
+                "id": id, "name": id, "description": description
+            }))
+            .unwrap();
+        Workflow {
+            info: workflow_manager_sdk::WorkflowInfo {
+                id: id.to_string(),
+                name: id.to_string(),
+                description: description.to_string(),
+                status: workflow_manager_sdk::WorkflowStatus::NotStarted,
+                metadata,
+                fields: vec![],
+                progress_messages: vec![],
+            },
+            source: workflow_manager_sdk::WorkflowSource::UserDefined,
+        }
+    }
+
+    #[test]
+    fn test_merge_keeps_indices_of_listed_workflows() {
+        let mut app = App::with_workflows(vec![workflow("a", "old"), workflow("b", "same")]);
+
+        let summary = app.merge_workflows(vec![
+            workflow("c", "new"),
+            workflow("b", "same"),
+            workflow("a", "changed"),
+        ]);
+
+        assert_eq!(summary, "1 new, 1 updated");
+        let ids: Vec<&str> = app.workflows.iter().map(|w| w.info.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+        assert_eq!(app.workflows[0].info.description, "changed");
+
+        let summary = app.merge_workflows(vec![workflow("a", "changed")]);
+        assert_eq!(
+            summary,
+            "0 new, 0 updated, 2 no longer found (listed until restart)"
+        );
+        assert_eq!(app.workflows.len(), 3);
+    }
+}
@@ -0,0 +1,414 @@
+//! Search in the phase tree of the shown execution
+//!
+//! `/` in the Tabs or Running view opens a prompt. The query is a
+//! case-insensitive regex, or a plain substring if it is not a valid regex.
+//! It matches phase names, task descriptions, agent names and all task and
+//! agent messages. `n`/`N` select the next/previous matching node, expanding
+//! whatever hides the match. `!` selects the next failed node the same way.
+//! Each tab keeps its own search.
+
+use regex::{Regex, RegexBuilder};
+
+use super::*;
+
+/// A node of the phase tree: phase index, task id and agent id
+type TreeNode = (usize, Option<String>, Option<String>);
+
+/// A matching node and how far it must be expanded to show the match
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SearchHit {
+    node: TreeNode,
+    /// The match is in the node's own messages, so expand the node too
+    in_messages: bool,
+}
+
+/// Compile a query as a case-insensitive regex, or as a literal if it is
+/// not a valid regex
+pub fn build_search_regex(query: &str) -> Regex {
+    RegexBuilder::new(query)
+        .case_insensitive(true)
+        .build()
+        .or_else(|_| {
+            RegexBuilder::new(&regex::escape(query))
+                .case_insensitive(true)
+                .build()
+        })
+        .expect("an escaped query is a valid regex")
+}
+
+impl App {
+    pub fn open_search(&mut self) {
+        self.search_prompt = Some(String::new());
+    }
+
+    pub fn cancel_search(&mut self) {
+        self.search_prompt = None;
+    }
+
+    /// Start searching for the prompted query; an empty query clears the
+    /// search
+    pub fn confirm_search(&mut self) {
+        let Some(query) = self.search_prompt.take() else {
+            return;
+        };
+        let re = (!query.is_empty()).then(|| build_search_regex(&query));
+        let searching = re.is_some();
+        if let Some(search) = self.shown_search() {
+            *search = re;
+        }
+        if searching {
+            self.jump_to_match(true);
+        }
+    }
+
+    /// Search of the shown tree: the active tab's, or the Running view's
+    fn shown_search(&mut self) -> Option<&mut Option<Regex>> {
+        match self.current_view {
+            View::Tabs => self
+                .open_tabs
+                .get_mut(self.active_tab_idx)
+                .map(|tab| &mut tab.search),
+            View::WorkflowRunning(_) => Some(&mut self.search),
+            _ => None,
+        }
+    }
+
+    /// Select the next (or previous) node matching the search, wrapping
+    /// around the tree
+    pub fn jump_to_match(&mut self, forward: bool) {
+        let Some(re) = self.shown_search().and_then(|search| search.clone()) else {
+            return;
+        };
+        let found = self.jump_to_node(|phases, current| find_match(phases, &re, current, forward));
+        if found == Some(false) {
+            self.notifications
+                .info("No Matches", "Nothing in this execution matches the search");
+        }
+    }
+
+    /// Select the next failed phase, task or agent, wrapping around the tree
+    pub fn jump_to_failed(&mut self) {
+        let found =
+            self.jump_to_node(|phases, current| find_hit(failed_tree(phases), current, true));
+        if found == Some(false) {
+            self.notifications.info("No Failures", "Nothing in this execution has failed");
+        }
+    }
+
+    /// Reveal and select the node `find` picks from the shown tree
+    ///
+    /// Returns whether a node was found, or `None` outside the tree views.
+    fn jump_to_node(
+        &mut self,
+        find: impl Fn(&[WorkflowPhase], &TreeNode) -> Option<SearchHit>,
+    ) -> Option<bool> {
+        let hit = match self.current_view {
+            View::Tabs => {
+                let tab = self.open_tabs.get_mut(self.active_tab_idx)?;
+                let phases = tab.workflow_phases.lock().ok()?;
+                let current = (
+                    tab.selected_phase,
+                    tab.selected_task.clone(),
+                    tab.selected_agent.clone(),
+                );
+                let hit = find(&phases, &current);
+                if let Some(hit) = &hit {
+                    reveal(
+                        &phases,
+                        hit,
+                        &mut tab.expanded_phases,
+                        &mut tab.expanded_tasks,
+                        &mut tab.expanded_agents,
+                    );
+                    (tab.selected_phase, tab.selected_task, tab.selected_agent) =
+                        hit.node.clone();
+                    tab.selected_file = None;
+                    tab.selected_artifact = None;
+                    // Jumping is moving by hand; stay on the node
+                    tab.follow = false;
+                }
+                hit
+            }
+            View::WorkflowRunning(_) => {
+                let hit = {
+                    let phases = self.workflow_phases.lock().ok()?;
+                    let current = (
+                        self.selected_phase,
+                        self.selected_task.clone(),
+                        self.selected_agent.clone(),
+                    );
+                    let hit = find(&phases, &current);
+                    if let Some(hit) = &hit {
+                        reveal(
+                            &phases,
+                            hit,
+                            &mut self.expanded_phases,
+                            &mut self.expanded_tasks,
+                            &mut self.expanded_agents,
+                        );
+                        (self.selected_phase, self.selected_task, self.selected_agent) =
+                            hit.node.clone();
+                        self.selected_file = None;
+                        self.workflow_follow = false;
+                    }
+                    hit
+                };
+                hit
+            }
+            _ => return None,
+        };
+        self.update_workflow_scroll(30); // Estimate viewport height
+        Some(hit.is_some())
+    }
+}
+
+/// Every node of the tree in display order, with whether it matches
+fn search_tree(phases: &[WorkflowPhase], re: &Regex) -> Vec<(TreeNode, Option<SearchHit>)> {
+    let any_match = |messages: &[LogLine]| messages.iter().any(|m| re.is_match(m));
+    let mut nodes = Vec::new();
+
+    for (phase_idx, phase) in phases.iter().enumerate() {
+        let node = (phase_idx, None, None);
+        let hit = re.is_match(&phase.name).then(|| SearchHit {
+            node: node.clone(),
+            in_messages: false,
+        });
+        nodes.push((node, hit));
+
+        for task in &phase.tasks {
+            let node = (phase_idx, Some(task.id.clone()), None);
+            let hit = if re.is_match(&task.description) {
+                Some(false)
+            } else if any_match(&task.messages) {
+                Some(true)
+            } else {
+                None
+            };
+            let hit = hit.map(|in_messages| SearchHit {
+                node: node.clone(),
+                in_messages,
+            });
+            nodes.push((node, hit));
+
+            for agent in &task.agents {
+                let node = (phase_idx, Some(task.id.clone()), Some(agent.id.clone()));
+                let hit = if re.is_match(&agent.name) || re.is_match(&agent.description) {
+                    Some(false)
+                } else if any_match(&agent.messages) {
+                    Some(true)
+                } else {
+                    None
+                };
+                let hit = hit.map(|in_messages| SearchHit {
+                    node: node.clone(),
+                    in_messages,
+                });
+                nodes.push((node, hit));
+            }
+        }
+    }
+
+    nodes
+}
+
+/// The first node after (or before) `current` matching `re`, wrapping
+fn find_match(
+    phases: &[WorkflowPhase],
+    re: &Regex,
+    current: &TreeNode,
+    forward: bool,
+) -> Option<SearchHit> {
+    find_hit(search_tree(phases, re), current, forward)
+}
+
+/// Every node of the tree in display order, with whether it has failed
+///
+/// Failed tasks and agents are expanded too, so their error shows.
+fn failed_tree(phases: &[WorkflowPhase]) -> Vec<(TreeNode, Option<SearchHit>)> {
+    let hit = |node: &TreeNode, failed: bool, in_messages: bool| {
+        failed.then(|| SearchHit {
+            node: node.clone(),
+            in_messages,
+        })
+    };
+    let mut nodes = Vec::new();
+
+    for (phase_idx, phase) in phases.iter().enumerate() {
+        let node = (phase_idx, None, None);
+        let failed = phase.status == PhaseStatus::Failed;
+        nodes.push((node.clone(), hit(&node, failed, false)));
+
+        for task in &phase.tasks {
+            let node = (phase_idx, Some(task.id.clone()), None);
+            let failed = task.status == TaskStatus::Failed;
+            nodes.push((node.clone(), hit(&node, failed, true)));
+
+            for agent in &task.agents {
+                let node = (phase_idx, Some(task.id.clone()), Some(agent.id.clone()));
+                let failed = agent.status == AgentStatus::Failed;
+                nodes.push((node.clone(), hit(&node, failed, true)));
+            }
+        }
+    }
+
+    nodes
+}
+
+/// The first hit after (or before) `current` in display order, wrapping
+fn find_hit(
+    nodes: Vec<(TreeNode, Option<SearchHit>)>,
+    current: &TreeNode,
+    forward: bool,
+) -> Option<SearchHit> {
+    let start = nodes.iter().position(|(node, _)| node == current);
+    let len = nodes.len();
+
+    (1..=len)
+        .map(|step| match (start, forward) {
+            (Some(start), true) => (start + step) % len,
+            (Some(start), false) => (start + len - step) % len,
+            // Selection not in the tree (e.g. phases just cleared): scan from the top
+            (None, _) => step - 1,
+        })
+        .find_map(|idx| nodes[idx].1.clone())
+}
+
+/// Expand the ancestors of a hit, and the hit itself for message matches
+fn reveal(
+    phases: &[WorkflowPhase],
+    hit: &SearchHit,
+    expanded_phases: &mut HashSet<usize>,
+    expanded_tasks: &mut HashSet<String>,
+    expanded_agents: &mut HashSet<String>,
+) {
+    let (phase_idx, task, agent) = &hit.node;
+    if task.is_some() {
+        if let Some(phase) = phases.get(*phase_idx) {
+            expanded_phases.insert(phase.id);
+        }
+    }
+    match (task, agent) {
+        (Some(_), Some(agent)) if hit.in_messages => {
+            expanded_agents.insert(agent.clone());
+        }
+        (Some(task), None) if hit.in_messages => {
+            expanded_tasks.insert(task.clone());
+        }
+        _ => {}
+    }
+    if let (Some(task), Some(_)) = (task, agent) {
+        expanded_tasks.insert(task.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use workflow_manager_sdk::LogLevel;
+
+    fn task(id: &str, messages: &[&str], agents: &[(&str, &[&str])]) -> WorkflowTask {
+        WorkflowTask {
+            id: id.to_string(),
+            description: format!("Task {}", id),
+            status: TaskStatus::Completed,
+            agents: agents
+                .iter()
+                .map(|(name, messages)| WorkflowAgent {
+                    id: format!("{}:{}", id, name),
+                    task_id: id.to_string(),
+                    name: name.to_string(),
+                    status: AgentStatus::Completed,
+                    messages: messages
+                        .iter()
+                        .map(|m| LogLine::new(LogLevel::Info, *m))
+                        .collect(),
+                    ..Default::default()
+                })
+                .collect(),
+            messages: messages
+                .iter()
+                .map(|m| LogLine::new(LogLevel::Info, *m))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn phases() -> Vec<WorkflowPhase> {
+        vec![WorkflowPhase {
+            id: 0,
+            name: "Analyze".to_string(),
+            status: PhaseStatus::Completed,
+            tasks: vec![
+                task("a", &["read src/main.rs"], &[("reviewer", &["looks fine"])]),
+                task("b", &[], &[("writer", &["Edited SRC/MAIN.RS"])]),
+            ],
+            ..Default::default()
+        }]
+    }
+
+    #[test]
+    fn test_build_search_regex() {
+        assert!(build_search_regex("main\\.rs").is_match("src/MAIN.rs"));
+        // Invalid regex falls back to a substring
+        let re = build_search_regex("foo(");
+        assert!(re.is_match("call foo(bar)"));
+        assert!(!re.is_match("call foo"));
+    }
+
+    #[test]
+    fn test_find_match_cycles_through_hits() {
+        let phases = phases();
+        let re = build_search_regex("main.rs");
+        let top = (0, None, None);
+
+        let first = find_match(&phases, &re, &top, true).unwrap();
+        assert_eq!(first.node, (0, Some("a".to_string()), None));
+        assert!(first.in_messages);
+
+        let second = find_match(&phases, &re, &first.node, true).unwrap();
+        assert_eq!(
+            second.node,
+            (0, Some("b".to_string()), Some("b:writer".to_string()))
+        );
+
+        // Wraps around in both directions
+        assert_eq!(find_match(&phases, &re, &second.node, true), Some(first.clone()));
+        assert_eq!(find_match(&phases, &re, &top, false), Some(second));
+
+        assert!(find_match(&phases, &build_search_regex("nothing"), &top, true).is_none());
+    }
+
+    #[test]
+    fn test_reveal_expands_to_the_match() {
+        let phases = phases();
+        let after_a = (0, Some("a".to_string()), None);
+        let hit = find_match(&phases, &build_search_regex("src/main"), &after_a, true).unwrap();
+        let (mut p, mut t, mut a) = (HashSet::new(), HashSet::new(), HashSet::new());
+        reveal(&phases, &hit, &mut p, &mut t, &mut a);
+
+        assert!(p.contains(&0));
+        assert!(t.contains("b"));
+        assert!(a.contains("b:writer"));
+    }
+
+    #[test]
+    fn test_failed_nodes_are_found_in_order() {
+        let mut phases = phases();
+        let top = (0, None, None);
+        assert!(find_hit(failed_tree(&phases), &top, true).is_none());
+
+        phases[0].tasks[0].agents[0].status = AgentStatus::Failed;
+        phases[0].tasks[1].status = TaskStatus::Failed;
+        let first = find_hit(failed_tree(&phases), &top, true).unwrap();
+        assert_eq!(first.node, (0, Some("a".to_string()), Some("a:reviewer".to_string())));
+        let second = find_hit(failed_tree(&phases), &first.node, true).unwrap();
+        assert_eq!(second.node, (0, Some("b".to_string()), None));
+
+        // The failed task is expanded to show its error
+        let (mut p, mut t, mut a) = (HashSet::new(), HashSet::new(), HashSet::new());
+        reveal(&phases, &second, &mut p, &mut t, &mut a);
+        assert!(p.contains(&0) && t.contains("b"));
+
+        assert_eq!(find_hit(failed_tree(&phases), &second.node, true), Some(first));
+    }
+}
@@ -1,9 +1,23 @@
 //! Tab management operations
 
-use workflow_manager_sdk::WorkflowStatus;
+use std::time::{Duration, Instant};
+
+use workflow_manager_sdk::{LogLevel, WorkflowStatus};
 
 use super::*;
 
+/// Whether a tab is due to be auto-closed: it completed (failed tabs stay
+/// for inspection) at least `delay` before `now`
+fn auto_close_due(
+    status: &WorkflowStatus,
+    completed_at: Option<Instant>,
+    delay: Duration,
+    now: Instant,
+) -> bool {
+    *status == WorkflowStatus::Completed
+        && completed_at.is_some_and(|at| now.saturating_duration_since(at) >= delay)
+}
+
 impl App {
     // Helper to strip ANSI color codes from strings
     fn strip_ansi_codes(s: &str) -> String {
@@ -53,6 +67,22 @@ impl App {
         }
     }
 
+    /// Swap the active tab with its left neighbour, keeping it active
+    pub fn move_tab_left(&mut self) {
+        if self.active_tab_idx > 0 && self.active_tab_idx < self.open_tabs.len() {
+            self.open_tabs.swap(self.active_tab_idx, self.active_tab_idx - 1);
+            self.active_tab_idx -= 1;
+        }
+    }
+
+    /// Swap the active tab with its right neighbour, keeping it active
+    pub fn move_tab_right(&mut self) {
+        if self.active_tab_idx + 1 < self.open_tabs.len() {
+            self.open_tabs.swap(self.active_tab_idx, self.active_tab_idx + 1);
+            self.active_tab_idx += 1;
+        }
+    }
+
     // Tab management actions
     pub fn close_current_tab(&mut self) {
         if self.open_tabs.is_empty() {
@@ -61,8 +91,11 @@ impl App {
 
         let tab = &self.open_tabs[self.active_tab_idx];
 
-        // If running, show confirmation
-        if tab.status == WorkflowStatus::Running {
+        // If running or queued, show confirmation
+        if matches!(
+            tab.status,
+            WorkflowStatus::Running | WorkflowStatus::NotStarted | WorkflowStatus::Paused
+        ) {
             self.show_close_confirmation = true;
             return;
         }
@@ -108,11 +141,27 @@ impl App {
     }
 
     pub fn kill_current_tab(&mut self) {
-        if self.open_tabs.is_empty() {
-            return;
+        self.kill_tab(self.active_tab_idx);
+    }
+
+    /// Whether tab `idx` is read-only, notifying that `what` is refused
+    fn refuse_read_only(&mut self, idx: usize, what: &str) -> bool {
+        let read_only = self.open_tabs.get(idx).is_some_and(|tab| tab.read_only);
+        if read_only {
+            self.notifications.warning(
+                format!("Cannot {}", what),
+                "This run was reopened from history; rerun it to start a new execution",
+            );
         }
+        read_only
+    }
 
-        if let Some(tab) = self.open_tabs.get_mut(self.active_tab_idx) {
+    /// Cancel the workflow of tab `idx` and mark the tab failed
+    pub fn kill_tab(&mut self, idx: usize) {
+        if self.refuse_read_only(idx, "Kill") {
+            return;
+        }
+        if let Some(tab) = self.open_tabs.get_mut(idx) {
             let handle_id = tab.runtime_handle_id;
 
             // Unified path: Cancel ALL workflows via runtime
@@ -130,12 +179,93 @@ impl App {
 
             tab.status = WorkflowStatus::Failed;
             if let Ok(mut output) = tab.workflow_output.lock() {
-                output.push(String::new());
-                output.push("⚠️ Workflow killed by user".to_string());
+                output.push(LogLine::new(LogLevel::Warn, ""));
+                output.push(LogLine::new(
+                    LogLevel::Warn,
+                    format!("{} Workflow killed by user", self.icons.warning),
+                ));
             }
         }
     }
 
+    /// Pause the workflow of the active tab, or resume it if it is paused
+    pub fn toggle_pause_current_tab(&mut self) {
+        let Some(runtime) = self.runtime.clone() else {
+            return;
+        };
+        if self.refuse_read_only(self.active_tab_idx, "Pause") {
+            return;
+        }
+        let Some(tab) = self.open_tabs.get_mut(self.active_tab_idx) else {
+            return;
+        };
+        let handle_id = tab.runtime_handle_id;
+        let pause = tab.status != WorkflowStatus::Paused;
+
+        let result = self.tokio_runtime.block_on(async {
+            if pause {
+                runtime.pause_workflow(&handle_id).await
+            } else {
+                runtime.resume_workflow(&handle_id).await
+            }
+        });
+        match result {
+            Ok(()) => {
+                let message = if pause {
+                    tab.status = WorkflowStatus::Paused;
+                    format!("{} Workflow paused", self.icons.paused)
+                } else {
+                    tab.status = WorkflowStatus::Running;
+                    format!("{} Workflow resumed", self.icons.running)
+                };
+                if let Ok(mut output) = tab.workflow_output.lock() {
+                    output.push(LogLine::new(LogLevel::Info, message));
+                }
+            }
+            Err(e) => {
+                let title = if pause { "Cannot Pause" } else { "Cannot Resume" };
+                self.notifications.error(title, e.to_string());
+            }
+        }
+    }
+
+    /// Ask the shown workflow to cancel the selected task
+    ///
+    /// Only workflows that read control messages from stdin act on it (see
+    /// `workflow_utils::batch::listen_for_control`).
+    pub fn cancel_selected_task(&mut self) {
+        let Some(runtime) = self.runtime.clone() else {
+            return;
+        };
+        if self.current_view == View::Tabs
+            && self.refuse_read_only(self.active_tab_idx, "Cancel Task")
+        {
+            return;
+        }
+        let (handle_id, task_id) = match self.current_view {
+            View::Tabs => match self.open_tabs.get(self.active_tab_idx) {
+                Some(tab) => (Some(tab.runtime_handle_id), tab.selected_task.clone()),
+                None => return,
+            },
+            View::WorkflowRunning(_) => (self.running_handle_id, self.selected_task.clone()),
+            _ => return,
+        };
+        let (Some(handle_id), Some(task_id)) = (handle_id, task_id) else {
+            return;
+        };
+
+        let result = self
+            .tokio_runtime
+            .block_on(async { runtime.cancel_task(&handle_id, &task_id).await });
+        match result {
+            Ok(()) => self.notifications.info(
+                "Task Cancel Requested",
+                format!("Asked the workflow to cancel {}", task_id),
+            ),
+            Err(e) => self.notifications.error("Cannot Cancel Task", e.to_string()),
+        };
+    }
+
     pub fn rerun_current_tab(&mut self) {
         if self.open_tabs.is_empty() {
             return;
@@ -201,6 +331,11 @@ impl App {
         }
         let tab = &mut self.open_tabs[self.active_tab_idx];
 
+        // Output files and result artifacts have nothing to expand
+        if tab.selected_file.is_some() || tab.selected_artifact.is_some() {
+            return;
+        }
+
         // If agent is selected, toggle agent expansion
         if let Some(ref agent_id) = tab.selected_agent {
             if tab.expanded_agents.contains(agent_id) {
@@ -236,6 +371,7 @@ impl App {
 
             if all_expanded {
                 // Collapse all
+                tab.selected_file = None;
                 tab.expanded_phases.clear();
                 tab.expanded_tasks.clear();
                 tab.expanded_agents.clear();
@@ -254,16 +390,136 @@ impl App {
         }
     }
 
+    /// Expand the phase tree of the shown execution down to `depth`
+    ///
+    /// 0 collapses everything, 1 expands phases, 2 also tasks and 3 also
+    /// agents. Selections that end up hidden move up to their parent.
+    pub fn expand_to_depth(&mut self, depth: usize) {
+        match self.current_view {
+            View::Tabs => {
+                if let Some(tab) = self.open_tabs.get_mut(self.active_tab_idx) {
+                    if let Ok(phases) = tab.workflow_phases.lock() {
+                        (tab.expanded_phases, tab.expanded_tasks, tab.expanded_agents) =
+                            expansion_to_depth(&phases, depth);
+                    }
+                    if depth < 2 {
+                        tab.selected_agent = None;
+                    }
+                    if depth < 1 {
+                        tab.selected_task = None;
+                        tab.selected_file = None;
+                    }
+                }
+            }
+            View::WorkflowRunning(_) => {
+                if let Ok(phases) = self.workflow_phases.lock() {
+                    (self.expanded_phases, self.expanded_tasks, self.expanded_agents) =
+                        expansion_to_depth(&phases, depth);
+                }
+                if depth < 2 {
+                    self.selected_agent = None;
+                }
+                if depth < 1 {
+                    self.selected_task = None;
+                    self.selected_file = None;
+                }
+            }
+            _ => return,
+        }
+        self.update_workflow_scroll(30); // Estimate viewport height
+    }
+
+    /// Raise the minimum log level of the shown execution, wrapping back to
+    /// Debug after Error
+    pub fn cycle_log_filter(&mut self) {
+        match self.current_view {
+            View::Tabs => {
+                if let Some(tab) = self.open_tabs.get_mut(self.active_tab_idx) {
+                    tab.log_filter = tab.log_filter.next();
+                    // The filtered raw output is shorter; start again at the top
+                    tab.raw_output_scroll_offset = 0;
+                }
+            }
+            View::WorkflowRunning(_) => {
+                self.log_filter = self.log_filter.next();
+                self.workflow_raw_output_scroll = 0;
+            }
+            _ => {}
+        }
+    }
+
+    /// Open or close the raw JSON debug popup for the selected agent
+    pub fn toggle_raw_json_view(&mut self) {
+        if self.show_raw_json {
+            self.show_raw_json = false;
+            return;
+        }
+        if self.open_tabs.is_empty() {
+            return;
+        }
+        if self.open_tabs[self.active_tab_idx].selected_agent.is_none() {
+            self.notifications
+                .info("Raw JSON", "Select an agent to inspect its raw events");
+            return;
+        }
+        self.show_raw_json = true;
+        self.raw_json_scroll = 0;
+    }
+
+    /// Pretty-printed raw events of the selected agent in the active tab
+    pub fn selected_agent_raw_json(&self) -> Option<(String, Vec<String>)> {
+        let tab = self.open_tabs.get(self.active_tab_idx)?;
+        let agent_id = tab.selected_agent.as_ref()?;
+        let phases = tab.workflow_phases.lock().ok()?;
+
+        for phase in phases.iter() {
+            for task in &phase.tasks {
+                if let Some(agent) = task.agents.iter().find(|a| &a.id == agent_id) {
+                    let mut lines = Vec::new();
+                    for event in &agent.raw_events {
+                        let pretty = serde_json::to_string_pretty(event)
+                            .unwrap_or_else(|_| event.to_string());
+                        lines.extend(pretty.lines().map(String::from));
+                    }
+                    return Some((agent.name.clone(), lines));
+                }
+            }
+        }
+        None
+    }
+
+    pub fn scroll_raw_json_up(&mut self, amount: usize) {
+        self.raw_json_scroll = self.raw_json_scroll.saturating_sub(amount);
+    }
+
+    pub fn scroll_raw_json_down(&mut self, amount: usize) {
+        let total = self
+            .selected_agent_raw_json()
+            .map(|(_, lines)| lines.len())
+            .unwrap_or(0);
+        self.raw_json_scroll = (self.raw_json_scroll + amount).min(total.saturating_sub(1));
+    }
+
     // Poll all running tabs for process status (logs are streamed via runtime)
-    pub fn poll_all_tabs(&mut self) {
+    //
+    // Returns whether a tab changed status or was closed.
+    pub fn poll_all_tabs(&mut self) -> bool {
         if self.runtime.is_none() {
-            return;
+            return false;
         }
 
         let runtime = self.runtime.as_ref().unwrap().clone();
+        let mut changed = false;
 
         for tab in &mut self.open_tabs {
-            if tab.status != WorkflowStatus::Running {
+            // A stored run keeps its stored status
+            if tab.read_only {
+                continue;
+            }
+            if !matches!(
+                tab.status,
+                WorkflowStatus::Running | WorkflowStatus::NotStarted | WorkflowStatus::Paused
+            ) {
                 continue;
             }
 
@@ -275,45 +531,179 @@ impl App {
             });
 
             if let Ok(new_status) = status {
-                if new_status != WorkflowStatus::Running {
+                changed |= tab.status != new_status;
+                // Queued executions start out NotStarted
+                if matches!(
+                    new_status,
+                    WorkflowStatus::Running | WorkflowStatus::NotStarted | WorkflowStatus::Paused
+                ) {
+                    tab.status = new_status;
+                } else {
                     tab.status = new_status.clone();
+                    tab.completed_at = Some(Instant::now());
+                    tab.exit_code = self
+                        .tokio_runtime
+                        .block_on(async { runtime.get_execution(&handle_id).await })
+                        .ok()
+                        .and_then(|execution| execution.exit_code);
 
                     // Add completion message
                     if let Ok(mut output) = tab.workflow_output.lock() {
-                        output.push(String::new());
+                        let level = if new_status == WorkflowStatus::Completed {
+                            LogLevel::Info
+                        } else {
+                            LogLevel::Error
+                        };
+                        output.push(LogLine::new(level, ""));
                         if new_status == WorkflowStatus::Completed {
-                            output.push("✅ Workflow completed successfully".to_string());
+                            output.push(LogLine::new(
+                                level,
+                                format!("{} Workflow completed successfully", self.icons.success),
+                            ));
 
                             // Save to history on success
                             if let Some(workflow) = self.workflows.get(tab.workflow_idx) {
-                                for (field_name, value) in &tab.field_values {
-                                    if !value.is_empty() {
-                                        let workflow_history = self
-                                            .history
-                                            .workflows
-                                            .entry(workflow.info.id.clone())
-                                            .or_default();
-
-                                        let field_history = workflow_history
-                                            .entry(field_name.clone())
-                                            .or_default();
-
-                                        if !field_history.contains(value) {
-                                            field_history.insert(0, value.clone());
-                                            if field_history.len() > 10 {
-                                                field_history.truncate(10);
-                                            }
-                                        }
-                                    }
-                                }
+                                self.history.record(
+                                    &workflow.info.id,
+                                    &workflow.info.fields,
+                                    &tab.field_values,
+                                );
                                 let _ = crate::utils::save_history(&self.history);
                             }
+                        } else if let Some(code) = tab.exit_code {
+                            output.push(LogLine::new(
+                                level,
+                                format!("{} Workflow failed (exit code {})", self.icons.error, code),
+                            ));
                         } else {
-                            output.push("❌ Workflow failed".to_string());
+                            output.push(LogLine::new(
+                                level,
+                                format!("{} Workflow failed", self.icons.error),
+                            ));
                         }
                     }
                 }
             }
         }
+
+        changed |= self.auto_close_completed_tabs();
+        changed
+    }
+
+    /// Close the tabs that completed `auto_close_completed_after` ago or
+    /// longer, with a notification for each; returns whether any closed
+    fn auto_close_completed_tabs(&mut self) -> bool {
+        let Some(delay) = self.auto_close_completed_after else {
+            return false;
+        };
+        let now = Instant::now();
+        let open = self.open_tabs.len();
+
+        let mut idx = 0;
+        while idx < self.open_tabs.len() {
+            let tab = &self.open_tabs[idx];
+            if !auto_close_due(&tab.status, tab.completed_at, delay, now) {
+                idx += 1;
+                continue;
+            }
+
+            let tab = self.open_tabs.remove(idx);
+            self.tokio_runtime.block_on(async {
+                self.task_registry.cancel_all(&tab.runtime_handle_id).await;
+            });
+            if idx < self.active_tab_idx || self.active_tab_idx >= self.open_tabs.len() {
+                self.active_tab_idx = self.active_tab_idx.saturating_sub(1);
+            }
+            self.notifications.info(
+                "Tab Closed",
+                format!("{} #{} completed", tab.workflow_name, tab.instance_number),
+            );
+        }
+        self.open_tabs.len() != open
+    }
+}
+
+/// Expanded phases, tasks and agents that show the tree down to `depth`
+fn expansion_to_depth(
+    phases: &[WorkflowPhase],
+    depth: usize,
+) -> (HashSet<usize>, HashSet<String>, HashSet<String>) {
+    let mut expanded_phases = HashSet::new();
+    let mut expanded_tasks = HashSet::new();
+    let mut expanded_agents = HashSet::new();
+
+    let tasks = phases.iter().flat_map(|phase| &phase.tasks);
+    if depth >= 1 {
+        expanded_phases.extend(phases.iter().map(|phase| phase.id));
+    }
+    if depth >= 2 {
+        expanded_tasks.extend(tasks.clone().map(|task| task.id.clone()));
+    }
+    if depth >= 3 {
+        expanded_agents.extend(tasks.flat_map(|task| &task.agents).map(|agent| agent.id.clone()));
+    }
+
+    (expanded_phases, expanded_tasks, expanded_agents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn phase(id: usize, task_id: &str, agent_names: &[&str]) -> WorkflowPhase {
+        WorkflowPhase {
+            id,
+            name: format!("Phase {}", id),
+            status: PhaseStatus::Running,
+            tasks: vec![WorkflowTask {
+                id: task_id.to_string(),
+                phase: id,
+                status: TaskStatus::Running,
+                agents: agent_names
+                    .iter()
+                    .map(|name| WorkflowAgent {
+                        id: format!("{}:{}", task_id, name),
+                        task_id: task_id.to_string(),
+                        name: name.to_string(),
+                        status: AgentStatus::Running,
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_expansion_to_depth() {
+        let phases = vec![phase(0, "a", &["x"]), phase(1, "b", &["y", "z"])];
+
+        let (p, t, a) = expansion_to_depth(&phases, 0);
+        assert!(p.is_empty() && t.is_empty() && a.is_empty());
+
+        let (p, t, a) = expansion_to_depth(&phases, 2);
+        assert_eq!(p, HashSet::from([0, 1]));
+        assert_eq!(t, HashSet::from(["a".to_string(), "b".to_string()]));
+        assert!(a.is_empty());
+
+        let (_, _, a) = expansion_to_depth(&phases, 3);
+        assert_eq!(a.len(), 3);
+        assert!(a.contains("b:z"));
+    }
+
+    #[test]
+    fn test_only_completed_tabs_are_auto_closed_after_the_delay() {
+        let delay = Duration::from_secs(30);
+        let completed_at = Instant::now();
+        let later = |secs| completed_at + Duration::from_secs(secs);
+
+        let completed = WorkflowStatus::Completed;
+        assert!(!auto_close_due(&completed, Some(completed_at), delay, later(29)));
+        assert!(auto_close_due(&completed, Some(completed_at), delay, later(30)));
+        // Restored tabs have no completion time and stay open
+        assert!(!auto_close_due(&completed, None, delay, later(60)));
+        let failed = WorkflowStatus::Failed;
+        assert!(!auto_close_due(&failed, Some(completed_at), delay, later(60)));
     }
 }
@@ -0,0 +1,195 @@
+//! Launch-time field validation
+//!
+//! Failed launches record one error per field in `App::validation_errors`,
+//! keyed by workflow id. The edit view shows them inline under each field,
+//! and an error is dropped as soon as its field validates again.
+
+use std::collections::{BTreeMap, HashMap};
+
+use workflow_manager_sdk::validation::{validate_field, validate_fields};
+use workflow_manager_sdk::WorkflowError;
+
+use super::*;
+
+impl App {
+    /// Validate the current field values before launching workflow `idx`
+    ///
+    /// On failure the errors are recorded for the edit view, a notification
+    /// is shown and the edit view is opened on the first invalid field.
+    pub fn validate_launch(&mut self, idx: usize) -> bool {
+        let Some(workflow) = self.workflows.get(idx) else {
+            return false;
+        };
+        let title = format!("Cannot launch {}", workflow.info.name);
+
+        let errors = validate_fields(&workflow.info.fields, &self.field_values);
+        if errors.is_empty() {
+            self.validation_errors.remove(&workflow.info.id);
+
+            // The runtime has the final say (e.g. it also sees pipelines'
+            // own rules); all of its problems go in one notification
+            let checked = match &self.runtime {
+                Some(runtime) => {
+                    runtime.validate_workflow_inputs(&workflow.info.id, self.launch_params(idx))
+                }
+                None => Ok(()),
+            };
+            let Err(e) = checked else {
+                return true;
+            };
+            self.notifications.error(title, e.to_string());
+
+            // Rejected inputs are fixed in the form, with the problems of
+            // single fields shown under them; other errors (e.g. the
+            // workflow is gone) are only reported
+            if let WorkflowError::Validation(problems) = &e {
+                let fields = &self.workflows[idx].info.fields;
+                let errors = problems
+                    .iter()
+                    .filter(|problem| fields.iter().any(|f| f.name == problem.field))
+                    .map(|problem| (problem.field.clone(), problem.message.clone()))
+                    .collect();
+                self.show_field_errors(idx, errors);
+            }
+            return false;
+        }
+
+        let summary = workflow
+            .info
+            .fields
+            .iter()
+            .filter_map(|f| errors.get(&f.name).map(|e| format!("{}: {}", f.label, e)))
+            .collect::<Vec<_>>()
+            .join("; ");
+        self.notifications.error(title, summary);
+        self.show_field_errors(idx, errors);
+        false
+    }
+
+    /// Record `errors` of workflow `idx` and open the edit view on the first
+    /// invalid field
+    fn show_field_errors(&mut self, idx: usize, errors: BTreeMap<String, String>) {
+        let Some(workflow) = self.workflows.get(idx) else {
+            return;
+        };
+
+        // First invalid field as the form shows them, i.e. by group
+        let first_invalid = field_groups(&workflow.info.fields)
+            .into_iter()
+            .flat_map(|(_, indices)| indices)
+            .find(|&i| errors.contains_key(&workflow.info.fields[i].name));
+        if !errors.is_empty() {
+            self.validation_errors
+                .insert(workflow.info.id.clone(), errors);
+        }
+
+        self.current_view = View::WorkflowEdit(idx);
+        if let Some(first_invalid) = first_invalid {
+            self.select_edit_field(idx, first_invalid);
+        }
+        self.is_editing = false;
+    }
+
+    /// Non-empty field values of workflow `idx`, as passed to the runtime
+    pub fn launch_params(&self, idx: usize) -> HashMap<String, String> {
+        let Some(workflow) = self.workflows.get(idx) else {
+            return HashMap::new();
+        };
+        workflow
+            .info
+            .fields
+            .iter()
+            .filter_map(|field| {
+                self.field_values
+                    .get(&field.name)
+                    .filter(|value| !value.is_empty())
+                    .map(|value| (field.name.clone(), value.clone()))
+            })
+            .collect()
+    }
+
+    /// Drop recorded errors of workflow `idx` whose fields are now valid
+    ///
+    /// Only clears errors; new ones are recorded on the next launch attempt.
+    pub fn revalidate_fields(&mut self, idx: usize) {
+        let Some(workflow) = self.workflows.get(idx) else {
+            return;
+        };
+        let Some(errors) = self.validation_errors.get_mut(&workflow.info.id) else {
+            return;
+        };
+
+        for field in &workflow.info.fields {
+            if !errors.contains_key(&field.name) {
+                continue;
+            }
+            match validate_field(field, &self.field_values) {
+                Some(error) => {
+                    errors.insert(field.name.clone(), error);
+                }
+                None => {
+                    errors.remove(&field.name);
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            self.validation_errors.remove(&workflow.info.id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use workflow_manager_sdk::{FieldSchema, FieldType};
+
+    #[test]
+    fn test_failed_launch_records_and_clears_errors() {
+        let workflow = workflow_manager_sdk::Workflow {
+            info: workflow_manager_sdk::WorkflowInfo {
+                id: "demo".to_string(),
+                name: "Demo".to_string(),
+                description: String::new(),
+                status: workflow_manager_sdk::WorkflowStatus::NotStarted,
+                metadata: serde_json::from_value(serde_json::json!({
+                    "id": "demo", "name": "Demo", "description": ""
+                }))
+                .unwrap(),
+                fields: vec![
+                    FieldSchema {
+                        required: true,
+                        ..FieldSchema::new("input", FieldType::Text)
+                    },
+                    FieldSchema::new(
+                        "count",
+                        FieldType::Number {
+                            min: Some(1),
+                            max: None,
+                        },
+                    ),
+                ],
+                progress_messages: vec![],
+            },
+            source: workflow_manager_sdk::WorkflowSource::BuiltIn,
+        };
+        let mut app = App::with_workflows(vec![workflow]);
+        app.field_values
+            .insert("count".to_string(), "0".to_string());
+
+        assert!(!app.validate_launch(0));
+        assert_eq!(app.validation_errors["demo"].len(), 2);
+        assert!(matches!(app.current_view, View::WorkflowEdit(0)));
+
+        app.field_values
+            .insert("input".to_string(), "hello".to_string());
+        app.revalidate_fields(0);
+        assert!(!app.validation_errors["demo"].contains_key("input"));
+        assert!(app.validation_errors["demo"].contains_key("count"));
+
+        app.field_values
+            .insert("count".to_string(), "2".to_string());
+        app.revalidate_fields(0);
+        assert!(!app.validation_errors.contains_key("demo"));
+    }
+}
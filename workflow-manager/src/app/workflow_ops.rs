@@ -1,16 +1,108 @@
 //! Workflow operations and execution
 
 use std::collections::{HashMap, HashSet};
-use std::io::{BufRead, BufReader};
 use std::path::PathBuf;
-use std::process::{Command, Stdio};
+use std::process::Command;
+use std::sync::atomic::Ordering;
 use std::sync::{Arc, Mutex};
-use std::thread;
+use std::time::Instant;
+use tokio::sync::broadcast::error::RecvError;
 use uuid::Uuid;
-use workflow_manager_sdk::{WorkflowLog, WorkflowStatus};
+use workflow_manager_sdk::{
+    FieldType, LogLevel, WorkflowLog, WorkflowRuntime, WorkflowSource, WorkflowStatus,
+};
+
+use crate::runtime::UsageTotals;
 
 use super::*;
 
+/// Feed every event of execution `handle_id` to `handle`, starting with
+/// those recorded before the subscription
+///
+/// A receiver that falls behind (`Lagged`) has lost events, so `reset`
+/// clears what the earlier ones built and all events are fed again from a
+/// fresh backlog. `handle` gets `true` for events it has already seen, so
+/// one-off reactions such as notifications are not repeated.
+async fn follow_logs(
+    runtime: &dyn WorkflowRuntime,
+    handle_id: Uuid,
+    mut reset: impl FnMut(),
+    mut handle: impl FnMut(WorkflowLog, bool),
+) {
+    let mut seen = 0;
+    loop {
+        let Ok((backlog, mut logs_rx)) = runtime.subscribe_logs_with_backlog(&handle_id).await
+        else {
+            return;
+        };
+        let handled_before = std::mem::take(&mut seen);
+        for log in backlog {
+            handle(log, seen < handled_before);
+            seen += 1;
+        }
+        loop {
+            match logs_rx.recv().await {
+                Ok(log) => {
+                    handle(log, false);
+                    seen += 1;
+                }
+                Err(RecvError::Lagged(_)) => break,
+                Err(RecvError::Closed) => return,
+            }
+        }
+        reset();
+    }
+}
+
+/// Index of the agent in `task`, adding it if it is not there yet
+///
+/// A new agent whose parent is in the task goes after the agents already
+/// nested under that parent, one level deeper, so the flat list reads as
+/// the tree. Without a known parent it is added at the end, at the top level.
+fn start_agent(
+    task: &mut WorkflowTask,
+    name: String,
+    description: String,
+    parent: Option<&str>,
+) -> usize {
+    let agent_id = format!("{}:{}", task.id, name);
+    if let Some(idx) = task.agents.iter().position(|a| a.id == agent_id) {
+        return idx;
+    }
+
+    let parent_idx = parent.and_then(|parent| {
+        let parent_id = format!("{}:{}", task.id, parent);
+        task.agents.iter().position(|a| a.id == parent_id)
+    });
+    let (idx, depth) = match parent_idx {
+        Some(p) => {
+            let depth = task.agents[p].depth + 1;
+            let end = task.agents[p + 1..]
+                .iter()
+                .position(|a| a.depth < depth)
+                .map_or(task.agents.len(), |n| p + 1 + n);
+            (end, depth)
+        }
+        None => (task.agents.len(), 0),
+    };
+    task.agents.insert(
+        idx,
+        WorkflowAgent {
+            id: agent_id,
+            task_id: task.id.clone(),
+            name,
+            description,
+            status: AgentStatus::Running,
+            messages: Vec::new(),
+            result: None,
+            raw_events: Vec::new(),
+            error: None,
+            depth,
+        },
+    );
+    idx
+}
+
 impl App {
     pub fn view_workflow(&mut self) {
         if self.selected < self.workflows.len() {
@@ -30,19 +122,23 @@ impl App {
             self.edit_field_index = 0;
             self.is_editing = false;
             self.field_values.clear();
+            self.operation_estimate = None;
 
-            // Initialize field values with defaults
+            // Initialize field values with defaults, from the environment first
             if let Some(workflow) = self.workflows.get(self.selected) {
                 for field in &workflow.info.fields {
-                    if let Some(default) = &field.default {
+                    if let Some(default) = field.resolved_default() {
+                        self.field_values.insert(field.name.clone(), default);
+                    } else if let FieldType::Boolean { default } = field.field_type {
                         self.field_values
-                            .insert(field.name.clone(), default.clone());
+                            .insert(field.name.clone(), default.to_string());
                     }
                 }
             }
 
             // Load latest values from history (overrides defaults)
             self.load_latest_values_from_history(self.selected);
+            self.revalidate_fields(self.selected);
         }
     }
 
@@ -61,12 +157,25 @@ impl App {
 
         // Load the tab's current field values
         self.field_values = tab.field_values.clone();
+        self.operation_estimate = None;
+        self.revalidate_fields(workflow_idx);
 
         // Keep track that we're editing from a tab
         self.in_new_tab_flow = true;
     }
 
     pub fn start_editing_field(&mut self) {
+        // Enter on the header of a collapsed group expands it
+        if self.is_selected_field_collapsed() {
+            self.toggle_field_group();
+            return;
+        }
+
+        // Fields with fixed options are picked from a dropdown, not typed
+        if self.show_option_dropdown() || self.toggle_boolean_field() {
+            return;
+        }
+
         if let View::WorkflowEdit(idx) = self.current_view {
             if let Some(workflow) = self.workflows.get(idx) {
                 if let Some(field) = workflow.info.fields.get(self.edit_field_index) {
@@ -82,6 +191,38 @@ impl App {
         }
     }
 
+    /// Flip the current field if it is a Boolean flag
+    ///
+    /// Returns false (and does nothing) for any other field type.
+    pub fn toggle_boolean_field(&mut self) -> bool {
+        let View::WorkflowEdit(idx) = self.current_view else {
+            return false;
+        };
+        if self.is_selected_field_collapsed() {
+            return false;
+        }
+        let Some(field) = self
+            .workflows
+            .get(idx)
+            .and_then(|w| w.info.fields.get(self.edit_field_index))
+        else {
+            return false;
+        };
+        let FieldType::Boolean { default } = field.field_type else {
+            return false;
+        };
+
+        let enabled = self
+            .field_values
+            .get(&field.name)
+            .map(|v| v.eq_ignore_ascii_case("true"))
+            .unwrap_or(default);
+        self.field_values
+            .insert(field.name.clone(), (!enabled).to_string());
+        self.revalidate_fields(idx);
+        true
+    }
+
     pub fn save_edited_field(&mut self) {
         if let View::WorkflowEdit(idx) = self.current_view {
             if let Some(workflow) = self.workflows.get(idx) {
@@ -90,6 +231,7 @@ impl App {
                         .insert(field.name.clone(), self.edit_buffer.clone());
                 }
             }
+            self.revalidate_fields(idx);
         }
         self.is_editing = false;
         self.edit_buffer.clear();
@@ -101,6 +243,9 @@ impl App {
     }
 
     pub fn delete_current_field(&mut self) {
+        if self.is_selected_field_collapsed() {
+            return;
+        }
         if let View::WorkflowEdit(idx) = self.current_view {
             if let Some(workflow) = self.workflows.get(idx) {
                 if let Some(field) = workflow.info.fields.get(self.edit_field_index) {
@@ -108,153 +253,212 @@ impl App {
                     self.field_values.insert(field.name.clone(), String::new());
                 }
             }
+            self.revalidate_fields(idx);
         }
     }
 
     pub fn launch_workflow(&mut self) {
-        // Save field values to history
-        self.save_to_history();
-
         // Get current workflow index
         let idx = match self.current_view {
             View::WorkflowEdit(idx) | View::WorkflowDetail(idx) => idx,
             _ => return,
         };
 
-        if let Some(workflow) = self.workflows.get(idx) {
-            let workflow_id = &workflow.info.id;
-            let binary_path = PathBuf::from("../target/debug").join(workflow_id);
-
-            // Build command arguments from field values
-            let mut args = Vec::new();
-            for field in &workflow.info.fields {
-                if let Some(value) = self.field_values.get(&field.name) {
-                    if !value.is_empty() {
-                        // Use the actual CLI argument from metadata, not the field name
-                        let arg_name = &field.cli_arg;
-
-                        // For boolean flags, check if this looks like a bool field
-                        // (description contains "[BOOL]" or value is "true"/"false")
-                        if field.description.contains("[BOOL]")
-                            || value.eq_ignore_ascii_case("true")
-                            || value.eq_ignore_ascii_case("false")
-                        {
-                            // Only add flag if value is "true" or non-empty
-                            if value.eq_ignore_ascii_case("true") {
-                                args.push(arg_name.clone());
-                            }
-                        } else {
-                            // Regular argument with value
-                            args.push(arg_name.clone());
-                            args.push(value.clone());
-                        }
-                    }
-                }
-            }
+        if !self.validate_launch(idx) {
+            return;
+        }
+
+        // Save field values to history
+        self.save_to_history();
 
-            // Clear output and phase tracking
+        let Some(workflow) = self.workflows.get(idx) else {
+            return;
+        };
+        let workflow_id = workflow.info.id.clone();
+        let params = self.launch_params(idx);
+
+        // Clear output and phase tracking
+        if let Ok(mut output) = self.workflow_output.lock() {
+            output.clear();
+        }
+        if let Ok(mut phases) = self.workflow_phases.lock() {
+            phases.clear();
+        }
+        self.expanded_phases.clear();
+        self.expanded_tasks.clear();
+        self.expanded_agents.clear();
+        self.selected_file = None;
+        self.workflow_follow = true;
+        self.current_view = View::WorkflowRunning(idx);
+
+        let Some(runtime) = self.runtime.clone() else {
             if let Ok(mut output) = self.workflow_output.lock() {
-                output.clear();
+                output.push(LogLine::new(
+                    LogLevel::Error,
+                    format!("{} Runtime not available", self.icons.error),
+                ));
             }
-            if let Ok(mut phases) = self.workflow_phases.lock() {
-                phases.clear();
-            }
-            self.expanded_phases.clear();
-            self.expanded_tasks.clear();
-            self.expanded_agents.clear();
-            self.workflow_running = true;
-            self.current_view = View::WorkflowRunning(idx);
-
-            // Spawn the workflow process
-            match Command::new(&binary_path)
-                .args(&args)
-                .stdin(Stdio::null())
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .spawn()
-            {
-                Ok(mut child) => {
-                    let output_clone = Arc::clone(&self.workflow_output);
-
-                    // Spawn thread to read stdout
-                    if let Some(stdout) = child.stdout.take() {
-                        let output = Arc::clone(&output_clone);
-                        thread::spawn(move || {
-                            let reader = BufReader::new(stdout);
-                            for line in reader.lines().flatten() {
-                                if let Ok(mut output) = output.lock() {
-                                    output.push(line);
-                                }
-                            }
-                        });
-                    }
+            self.workflow_running = false;
+            return;
+        };
 
-                    // Spawn thread to read stderr and parse structured logs
-                    if let Some(stderr) = child.stderr.take() {
-                        let output = Arc::clone(&output_clone);
-                        let phases = Arc::clone(&self.workflow_phases);
-                        thread::spawn(move || {
-                            let reader = BufReader::new(stderr);
-                            for line in reader.lines() {
-                                if let Ok(line) = line {
-                                    // Check for structured log events
-                                    if let Some(json_str) = line.strip_prefix("__WF_EVENT__:") {
-                                        // Parse WorkflowLog event
-                                        match serde_json::from_str::<WorkflowLog>(json_str) {
-                                            Ok(event) => {
-                                                Self::handle_workflow_event(event, &phases);
-                                            }
-                                            Err(e) => {
-                                                if let Ok(mut output) = output.lock() {
-                                                    output.push(format!("ERROR: Failed to parse event: {}", e));
-                                                    output.push(format!("  JSON: {}", &json_str[..json_str.len().min(100)]));
-                                                }
-                                            }
-                                        }
-                                    } else {
-                                        // Regular stderr output
-                                        if let Ok(mut output) = output.lock() {
-                                            output.push(format!("ERROR: {}", line));
-                                        }
-                                    }
-                                }
-                            }
-                        });
-                    }
+        // Execute via runtime so the run can be cancelled by its handle
+        let handle = self
+            .tokio_runtime
+            .block_on(async { runtime.execute_workflow(&workflow_id, params).await });
+        let handle_id = match handle {
+            Ok(h) => *h.id(),
+            Err(e) => {
+                if let Ok(mut output) = self.workflow_output.lock() {
+                    output.push(LogLine::new(
+                        LogLevel::Error,
+                        format!("{} Failed to launch workflow: {}", self.icons.error, e),
+                    ));
+                }
+                self.workflow_running = false;
+                return;
+            }
+        };
+        self.workflow_running = true;
+        self.running_handle_id = Some(handle_id);
 
-                    // Spawn thread to wait for completion
-                    let output = Arc::clone(&output_clone);
-                    thread::spawn(move || match child.wait() {
-                        Ok(status) => {
-                            if let Ok(mut output) = output.lock() {
-                                output.push(String::new());
-                                if status.success() {
-                                    output.push("✅ Workflow completed successfully".to_string());
-                                } else {
-                                    output.push(format!(
-                                        "❌ Workflow failed with exit code: {:?}",
-                                        status.code()
-                                    ));
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            if let Ok(mut output) = output.lock() {
-                                output.push(format!("❌ Error waiting for workflow: {}", e));
-                            }
-                        }
-                    });
+        let output = Arc::clone(&self.workflow_output);
+        let phases = Arc::clone(&self.workflow_phases);
+        let max_lines = self.max_output_lines;
+        let log_events = Arc::clone(&self.log_events);
+        let log_task = self.tokio_runtime.spawn(async move {
+            let reset = || {
+                if let Ok(mut output) = output.lock() {
+                    output.clear();
                 }
-                Err(e) => {
-                    if let Ok(mut output) = self.workflow_output.lock() {
-                        output.push(format!("❌ Failed to launch workflow: {}", e));
-                        output.push(format!("   Binary path: {}", binary_path.display()));
-                        output.push(format!("   Args: {:?}", args));
+                if let Ok(mut phases) = phases.lock() {
+                    phases.clear();
+                }
+            };
+            follow_logs(&*runtime, handle_id, reset, |log, _| {
+                log_events.fetch_add(1, Ordering::Relaxed);
+                match log {
+                    WorkflowLog::RawOutput { ref line, .. } => {
+                        if let Ok(mut output) = output.lock() {
+                            let line = LogLine::of(&log, line.clone());
+                            push_output_line(&mut output, line, max_lines);
+                        }
                     }
-                    self.workflow_running = false;
+                    log => App::handle_workflow_event(log, &phases),
                 }
+            })
+            .await;
+        });
+
+        self.tokio_runtime.block_on(async {
+            self.task_registry.register(handle_id, log_task).await;
+        });
+    }
+
+    /// Pick up the exit of the workflow shown in the WorkflowRunning view,
+    /// returning whether it exited
+    pub fn poll_running_workflow(&mut self) -> bool {
+        if !self.workflow_running {
+            return false;
+        }
+        let (Some(runtime), Some(handle_id)) = (&self.runtime, self.running_handle_id) else {
+            return false;
+        };
+
+        let status = self
+            .tokio_runtime
+            .block_on(async { runtime.get_status(&handle_id).await });
+        let Ok(status) = status else {
+            return false;
+        };
+        if matches!(
+            status,
+            WorkflowStatus::Running | WorkflowStatus::NotStarted | WorkflowStatus::Paused
+        ) {
+            return false;
+        }
+
+        self.workflow_running = false;
+        if let Ok(mut output) = self.workflow_output.lock() {
+            let (level, message) = if status == WorkflowStatus::Completed {
+                (
+                    LogLevel::Info,
+                    format!("{} Workflow completed successfully", self.icons.success),
+                )
+            } else {
+                (LogLevel::Error, format!("{} Workflow failed", self.icons.error))
+            };
+            output.push(LogLine::new(level, ""));
+            output.push(LogLine::new(level, message));
+        }
+        true
+    }
+
+    /// Kill the workflow shown in the WorkflowRunning view, after confirmation
+    pub fn cancel_running_workflow(&mut self) {
+        if self.workflow_running && self.running_handle_id.is_some() {
+            self.show_close_confirmation = true;
+        }
+    }
+
+    pub fn cancel_running_workflow_confirmed(&mut self) {
+        self.show_close_confirmation = false;
+        let Some(handle_id) = self.running_handle_id else {
+            return;
+        };
+
+        if let Some(runtime) = self.runtime.clone() {
+            let result = self
+                .tokio_runtime
+                .block_on(async { runtime.cancel_workflow(&handle_id).await });
+            // Printing would garble the raw-mode screen
+            if let Err(e) = result {
+                self.notifications.error(
+                    "Cancel Failed",
+                    format!("Failed to cancel workflow {}: {}", handle_id, e),
+                );
+            }
+        }
+
+        self.tokio_runtime.block_on(async {
+            self.task_registry.cancel_all(&handle_id).await;
+        });
+
+        self.workflow_running = false;
+        if let Ok(mut output) = self.workflow_output.lock() {
+            output.push(LogLine::new(LogLevel::Warn, ""));
+            output.push(LogLine::new(
+                LogLevel::Warn,
+                format!("{} Workflow killed by user", self.icons.warning),
+            ));
+        }
+    }
+
+    /// Show the command the selected workflow would run with the current
+    /// field values, followed by any input problems the runtime reports
+    pub fn preview_command(&mut self) {
+        let idx = match self.current_view {
+            View::WorkflowEdit(idx) | View::WorkflowDetail(idx) => idx,
+            _ => return,
+        };
+        let (Some(workflow), Some(runtime)) = (self.workflows.get(idx), &self.runtime) else {
+            return;
+        };
+
+        let params = self.launch_params(idx);
+        let mut preview = match runtime.preview_command(&workflow.info.id, params.clone()) {
+            Ok(command) => command,
+            Err(e) => {
+                self.notifications
+                    .error(format!("Cannot preview {}", workflow.info.name), e.to_string());
+                return;
             }
+        };
+        if let Err(e) = runtime.validate_workflow_inputs(&workflow.info.id, params) {
+            preview.push_str("\n\n");
+            preview.push_str(&e.to_string());
         }
+        self.command_preview = Some(preview);
     }
 
     // New: Launch workflow in a tab (for tabbed interface)
@@ -265,6 +469,10 @@ impl App {
             _ => return,
         };
 
+        if !self.validate_launch(idx) {
+            return;
+        }
+
         if let Some(workflow) = self.workflows.get(idx) {
             let workflow_id = &workflow.info.id;
             let _binary_path = PathBuf::from("../target/debug").join(workflow_id);
@@ -279,14 +487,17 @@ impl App {
                 *counter
             };
 
-            // Build the workflow binary first
-            let build_output = Command::new("cargo")
-                .args(["build", "--bin", workflow_id])
-                .current_dir("..")
-                .output();
+            // Build the workflow binary first (user-defined workflows such as
+            // pipelines have no cargo target of their own)
+            let build_output = matches!(workflow.source, WorkflowSource::BuiltIn).then(|| {
+                Command::new("cargo")
+                    .args(["build", "--bin", workflow_id])
+                    .current_dir("..")
+                    .output()
+            });
 
             match build_output {
-                Ok(output) if !output.status.success() => {
+                Some(Ok(output)) if !output.status.success() => {
                     // Build failed - create tab with error
                     let tab_id = format!(
                         "{}_{}",
@@ -302,28 +513,43 @@ impl App {
                         status: WorkflowStatus::Failed,
                         runtime_handle_id: Uuid::new_v4(),
                         exit_code: None,
+                        completed_at: None,
                         workflow_phases: Arc::new(Mutex::new(Vec::new())),
                         workflow_output: Arc::new(Mutex::new(Vec::new())),
+                        usage: Arc::new(Mutex::new(None)),
+                        result: Arc::new(Mutex::new(None)),
+                        last_event: Arc::new(Mutex::new(None)),
                         field_values: self.field_values.clone(),
                         scroll_offset: 0,
+                        follow: true,
                         expanded_phases: HashSet::new(),
                         expanded_tasks: HashSet::new(),
                         expanded_agents: HashSet::new(),
                         selected_phase: 0,
                         selected_task: None,
                         selected_agent: None,
+                        selected_file: None,
+                        selected_artifact: None,
+                        search: None,
                         agent_scroll_offsets: HashMap::new(),
                         focused_pane: WorkflowPane::StructuredLogs,
                         raw_output_scroll_offset: 0,
+                        h_scroll: 0,
+                        log_filter: LogLevel::Debug,
                         saved_logs: None,
+                        read_only: false,
                     };
 
                     if let Ok(mut output_vec) = tab.workflow_output.lock() {
-                        output_vec.push(format!("❌ Build failed for workflow: {}", workflow_id));
-                        output_vec.push(String::new());
+                        output_vec.push(LogLine::new(
+                            LogLevel::Error,
+                            format!("{} Build failed for workflow: {}", self.icons.error, workflow_id),
+                        ));
+                        output_vec.push(LogLine::new(LogLevel::Error, ""));
+                        // The compiler's output explains the failure
                         let stderr = String::from_utf8_lossy(&output.stderr);
                         for line in stderr.lines() {
-                            output_vec.push(line.to_string());
+                            output_vec.push(LogLine::new(LogLevel::Error, line));
                         }
                     }
 
@@ -333,7 +559,7 @@ impl App {
                     self.in_new_tab_flow = false;
                     return;
                 }
-                Err(e) => {
+                Some(Err(e)) => {
                     // Cargo command failed to run
                     let tab_id = format!(
                         "{}_{}",
@@ -349,24 +575,38 @@ impl App {
                         status: WorkflowStatus::Failed,
                         runtime_handle_id: Uuid::new_v4(),
                         exit_code: None,
+                        completed_at: None,
                         workflow_phases: Arc::new(Mutex::new(Vec::new())),
                         workflow_output: Arc::new(Mutex::new(Vec::new())),
+                        usage: Arc::new(Mutex::new(None)),
+                        result: Arc::new(Mutex::new(None)),
+                        last_event: Arc::new(Mutex::new(None)),
                         field_values: self.field_values.clone(),
                         scroll_offset: 0,
+                        follow: true,
                         expanded_phases: HashSet::new(),
                         expanded_tasks: HashSet::new(),
                         expanded_agents: HashSet::new(),
                         selected_phase: 0,
                         selected_task: None,
                         selected_agent: None,
+                        selected_file: None,
+                        selected_artifact: None,
+                        search: None,
                         agent_scroll_offsets: HashMap::new(),
                         focused_pane: WorkflowPane::StructuredLogs,
                         raw_output_scroll_offset: 0,
+                        h_scroll: 0,
+                        log_filter: LogLevel::Debug,
                         saved_logs: None,
+                        read_only: false,
                     };
 
                     if let Ok(mut output_vec) = tab.workflow_output.lock() {
-                        output_vec.push(format!("❌ Failed to run cargo build: {}", e));
+                        output_vec.push(LogLine::new(
+                            LogLevel::Error,
+                            format!("{} Failed to run cargo build: {}", self.icons.error, e),
+                        ));
                     }
 
                     self.open_tabs.push(tab);
@@ -381,14 +621,7 @@ impl App {
             }
 
             // Build parameters map from field values
-            let mut params = HashMap::new();
-            for field in &workflow.info.fields {
-                if let Some(value) = self.field_values.get(&field.name) {
-                    if !value.is_empty() {
-                        params.insert(field.name.clone(), value.clone());
-                    }
-                }
-            }
+            let params = self.launch_params(idx);
 
             // Create tab
             let tab_id = format!(
@@ -406,20 +639,31 @@ impl App {
                 status: WorkflowStatus::Running,
                 runtime_handle_id: Uuid::new_v4(),
                 exit_code: None,
+                completed_at: None,
                 workflow_phases: Arc::new(Mutex::new(Vec::new())),
                 workflow_output: Arc::new(Mutex::new(Vec::new())),
+                usage: Arc::new(Mutex::new(None)),
+                result: Arc::new(Mutex::new(None)),
+                last_event: Arc::new(Mutex::new(None)),
                 field_values: self.field_values.clone(),
                 scroll_offset: 0,
+                follow: true,
                 expanded_phases: HashSet::new(),
                 expanded_tasks: HashSet::new(),
                 expanded_agents: HashSet::new(),
                 selected_phase: 0,
                 selected_task: None,
                 selected_agent: None,
+                selected_file: None,
+                selected_artifact: None,
+                search: None,
                 agent_scroll_offsets: HashMap::new(),
                 focused_pane: WorkflowPane::StructuredLogs,
                 raw_output_scroll_offset: 0,
+                h_scroll: 0,
+                log_filter: LogLevel::Debug,
                 saved_logs: None,
+                read_only: false,
             };
 
             // Get runtime
@@ -429,7 +673,10 @@ impl App {
                     // Create error tab
                     tab.status = WorkflowStatus::Failed;
                     if let Ok(mut output) = tab.workflow_output.lock() {
-                        output.push("❌ Runtime not available".to_string());
+                        output.push(LogLine::new(
+                    LogLevel::Error,
+                    format!("{} Runtime not available", self.icons.error),
+                ));
                     }
                     self.open_tabs.push(tab);
                     self.active_tab_idx = self.open_tabs.len() - 1;
@@ -450,7 +697,10 @@ impl App {
                     // Create error tab
                     tab.status = WorkflowStatus::Failed;
                     if let Ok(mut output) = tab.workflow_output.lock() {
-                        output.push(format!("❌ Failed to launch workflow: {}", e));
+                        output.push(LogLine::new(
+                        LogLevel::Error,
+                        format!("{} Failed to launch workflow: {}", self.icons.error, e),
+                    ));
                     }
                     self.open_tabs.push(tab);
                     self.active_tab_idx = self.open_tabs.len() - 1;
@@ -466,6 +716,10 @@ impl App {
             // Clone Arc references BEFORE pushing tab
             let tab_output = Arc::clone(&tab.workflow_output);
             let tab_phases = Arc::clone(&tab.workflow_phases);
+            let tab_usage = Arc::clone(&tab.usage);
+            let tab_result = Arc::clone(&tab.result);
+            let tab_last_event = Arc::clone(&tab.last_event);
+            let log_events = Arc::clone(&self.log_events);
 
             // Add tab to list
             self.open_tabs.push(tab);
@@ -476,25 +730,63 @@ impl App {
             // Subscribe to logs and spawn streaming task
             let log_task = self.tokio_runtime.spawn({
                 let runtime_clone = runtime.clone();
+                let command_tx = self.command_tx.clone();
+                let workflow_name = workflow.info.name.clone();
+                let max_lines = self.max_output_lines;
 
                 async move {
-                    if let Ok(mut logs_rx) = runtime_clone.subscribe_logs(&handle_id).await {
-                        while let Ok(log) = logs_rx.recv().await {
-                            // Handle both RawOutput and structured logs
-                            match &log {
-                                workflow_manager_sdk::WorkflowLog::RawOutput { stream: _, line } => {
-                                    // Add to raw output
-                                    if let Ok(mut output) = tab_output.lock() {
-                                        output.push(line.clone());
-                                    }
-                                }
-                                _ => {
-                                    // Structured log - update phases
-                                    App::handle_workflow_event(log, &tab_phases);
+                    let reset = || {
+                        if let Ok(mut output) = tab_output.lock() {
+                            output.clear();
+                        }
+                        if let Ok(mut phases) = tab_phases.lock() {
+                            phases.clear();
+                        }
+                        if let Ok(mut usage) = tab_usage.lock() {
+                            *usage = None;
+                        }
+                        if let Ok(mut result) = tab_result.lock() {
+                            *result = None;
+                        }
+                    };
+                    follow_logs(&*runtime_clone, handle_id, reset, |log, seen| {
+                        if let Some(message) = App::timeout_message(&log).filter(|_| !seen) {
+                            let _ = command_tx.send(AppCommand::ShowNotification {
+                                level: NotificationLevel::Error,
+                                title: "Workflow Timed Out".to_string(),
+                                message: format!("{}: {}", workflow_name, message),
+                            });
+                        }
+                        if let Some(message) = App::restart_message(&log).filter(|_| !seen) {
+                            let _ = command_tx.send(AppCommand::ShowNotification {
+                                level: NotificationLevel::Warning,
+                                title: "Workflow Restarted".to_string(),
+                                message: format!("{}: {}", workflow_name, message),
+                            });
+                        }
+                        if let Ok(mut last_event) = tab_last_event.lock() {
+                            *last_event = Some(chrono::Local::now());
+                        }
+                        log_events.fetch_add(1, Ordering::Relaxed);
+
+                        // Handle both RawOutput and structured logs
+                        match &log {
+                            workflow_manager_sdk::WorkflowLog::RawOutput { stream: _, line } => {
+                                // Add to raw output
+                                if let Ok(mut output) = tab_output.lock() {
+                                    let line = LogLine::of(&log, line.clone());
+                                    push_output_line(&mut output, line, max_lines);
                                 }
                             }
+                            _ => {
+                                // Structured log - update usage, result and phases
+                                App::handle_usage_event(&log, &tab_usage);
+                                App::handle_result_event(&log, &tab_result);
+                                App::handle_workflow_event(log, &tab_phases);
+                            }
                         }
-                    }
+                    })
+                    .await;
                 }
             });
 
@@ -505,7 +797,74 @@ impl App {
         }
     }
 
+    /// Add a `Usage` event to a tab's running totals; other events are ignored
+    pub fn handle_usage_event(event: &WorkflowLog, usage: &Arc<Mutex<Option<UsageTotals>>>) {
+        if let WorkflowLog::Usage {
+            input_tokens,
+            output_tokens,
+            cost_usd,
+        } = event
+        {
+            if let Ok(mut usage) = usage.lock() {
+                usage
+                    .get_or_insert_with(UsageTotals::default)
+                    .add(*input_tokens, *output_tokens, *cost_usd);
+            }
+        }
+    }
+
+    /// Keep a `Result` event as the tab's run result; other events are ignored
+    pub fn handle_result_event(event: &WorkflowLog, result: &Arc<Mutex<Option<RunResult>>>) {
+        if let WorkflowLog::Result { summary, artifacts } = event {
+            if let Ok(mut result) = result.lock() {
+                *result = Some(RunResult {
+                    summary: summary.clone(),
+                    artifacts: artifacts.clone(),
+                });
+            }
+        }
+    }
+
+    /// Apply a live event to the phase tree, timing phases and tasks
     pub fn handle_workflow_event(event: WorkflowLog, phases: &Arc<Mutex<Vec<WorkflowPhase>>>) {
+        Self::apply_workflow_event(event, phases, Some(Instant::now()));
+    }
+
+    /// Apply a stored event to the phase tree
+    ///
+    /// Stored events carry no time of their own, so replayed phases and
+    /// tasks show no timing.
+    pub fn replay_workflow_event(event: WorkflowLog, phases: &Arc<Mutex<Vec<WorkflowPhase>>>) {
+        Self::apply_workflow_event(event, phases, None);
+    }
+
+    fn apply_workflow_event(
+        event: WorkflowLog,
+        phases: &Arc<Mutex<Vec<WorkflowPhase>>>,
+        now: Option<Instant>,
+    ) {
+        // Keep the untouched event for agent-level events so the raw JSON
+        // debug view can show exactly what the workflow emitted
+        let raw_agent_event = match &event {
+            WorkflowLog::AgentStarted {
+                task_id, agent_name, ..
+            }
+            | WorkflowLog::AgentMessage {
+                task_id, agent_name, ..
+            }
+            | WorkflowLog::AgentCompleted {
+                task_id, agent_name, ..
+            }
+            | WorkflowLog::AgentFailed {
+                task_id, agent_name, ..
+            } => serde_json::to_value(&event)
+                .ok()
+                .map(|value| (task_id.clone(), format!("{}:{}", task_id, agent_name), value)),
+            _ => None,
+        };
+        // Messages keep the level of their event for the log filter
+        let level = event.level();
+
         if let Ok(mut phases) = phases.lock() {
             match event {
                 WorkflowLog::PhaseStarted {
@@ -523,33 +882,42 @@ impl App {
                                 status: PhaseStatus::NotStarted,
                                 tasks: Vec::new(),
                                 output_files: Vec::new(),
+                                total_tasks: None,
+                                started_at: None,
+                                finished_at: None,
+                                error: None,
                             });
                         }
                     }
                     if let Some(p) = phases.get_mut(phase) {
                         p.name = name;
                         p.status = PhaseStatus::Running;
+                        p.started_at = now;
+                        p.finished_at = None;
                     }
                 }
                 WorkflowLog::PhaseCompleted { phase, name: _ } => {
                     if let Some(p) = phases.get_mut(phase) {
                         p.status = PhaseStatus::Completed;
+                        p.finished_at = now;
                     }
                 }
                 WorkflowLog::PhaseFailed {
                     phase,
                     name: _,
-                    error: _,
+                    error,
                 } => {
                     if let Some(p) = phases.get_mut(phase) {
                         p.status = PhaseStatus::Failed;
+                        p.finished_at = now;
+                        p.error = Some(error);
                     }
                 }
                 WorkflowLog::TaskStarted {
                     phase,
                     task_id,
                     description,
-                    total_tasks: _,
+                    total_tasks,
                 } => {
                     // Ensure phase exists
                     let current_len = phases.len();
@@ -561,13 +929,22 @@ impl App {
                                 status: PhaseStatus::NotStarted,
                                 tasks: Vec::new(),
                                 output_files: Vec::new(),
+                                total_tasks: None,
+                                started_at: None,
+                                finished_at: None,
+                                error: None,
                             });
                         }
                     }
                     if let Some(p) = phases.get_mut(phase) {
+                        if total_tasks.is_some() {
+                            p.total_tasks = total_tasks;
+                        }
                         // Find or create task
                         if let Some(task) = p.tasks.iter_mut().find(|t| t.id == task_id) {
                             task.status = TaskStatus::Running;
+                            task.started_at = now;
+                            task.finished_at = None;
                         } else {
                             p.tasks.push(WorkflowTask {
                                 id: task_id,
@@ -577,6 +954,10 @@ impl App {
                                 agents: Vec::new(),
                                 messages: Vec::new(),
                                 result: None,
+                                progress: None,
+                                started_at: now,
+                                finished_at: None,
+                                error: None,
                             });
                         }
                     }
@@ -585,7 +966,7 @@ impl App {
                     // Find task in any phase
                     for phase in phases.iter_mut() {
                         if let Some(task) = phase.tasks.iter_mut().find(|t| t.id == task_id) {
-                            task.messages.push(message.clone());
+                            task.messages.push(LogLine::new(level, message.clone()));
                             break;
                         }
                     }
@@ -595,6 +976,7 @@ impl App {
                         if let Some(task) = phase.tasks.iter_mut().find(|t| t.id == task_id) {
                             task.status = TaskStatus::Completed;
                             task.result = result.clone();
+                            task.finished_at = now;
                             break;
                         }
                     }
@@ -603,7 +985,9 @@ impl App {
                     for phase in phases.iter_mut() {
                         if let Some(task) = phase.tasks.iter_mut().find(|t| t.id == task_id) {
                             task.status = TaskStatus::Failed;
-                            task.messages.push(format!("Error: {}", error));
+                            task.messages.push(LogLine::new(level, format!("Error: {}", error)));
+                            task.finished_at = now;
+                            task.error = Some(error);
                             break;
                         }
                     }
@@ -612,23 +996,17 @@ impl App {
                     task_id,
                     agent_name,
                     description,
+                    parent_agent,
                 } => {
-                    let agent_id = format!("{}:{}", task_id, agent_name);
                     for phase in phases.iter_mut() {
                         if let Some(task) = phase.tasks.iter_mut().find(|t| t.id == task_id) {
-                            if let Some(agent) = task.agents.iter_mut().find(|a| a.id == agent_id) {
-                                agent.status = AgentStatus::Running;
-                            } else {
-                                task.agents.push(WorkflowAgent {
-                                    id: agent_id,
-                                    task_id: task_id.clone(),
-                                    name: agent_name,
-                                    description,
-                                    status: AgentStatus::Running,
-                                    messages: Vec::new(),
-                                    result: None,
-                                });
-                            }
+                            let idx = start_agent(
+                                task,
+                                agent_name,
+                                description,
+                                parent_agent.as_deref(),
+                            );
+                            task.agents[idx].status = AgentStatus::Running;
                             break;
                         }
                     }
@@ -637,12 +1015,19 @@ impl App {
                     task_id,
                     agent_name,
                     message,
+                    parent_agent,
                 } => {
                     let agent_id = format!("{}:{}", task_id, agent_name);
                     for phase in phases.iter_mut() {
                         if let Some(task) = phase.tasks.iter_mut().find(|t| t.id == task_id) {
-                            if let Some(agent) = task.agents.iter_mut().find(|a| a.id == agent_id) {
-                                agent.messages.push(message.clone());
+                            let idx = match parent_agent.as_deref() {
+                                Some(parent) => {
+                                    Some(start_agent(task, agent_name, String::new(), Some(parent)))
+                                }
+                                None => task.agents.iter().position(|a| a.id == agent_id),
+                            };
+                            if let Some(idx) = idx {
+                                task.agents[idx].messages.push(LogLine::new(level, message.clone()));
                             }
                             break;
                         }
@@ -674,7 +1059,8 @@ impl App {
                         if let Some(task) = phase.tasks.iter_mut().find(|t| t.id == task_id) {
                             if let Some(agent) = task.agents.iter_mut().find(|a| a.id == agent_id) {
                                 agent.status = AgentStatus::Failed;
-                                agent.messages.push(format!("Error: {}", error));
+                                agent.messages.push(LogLine::new(level, format!("Error: {}", error)));
+                                agent.error = Some(error);
                                 break;
                             }
                         }
@@ -694,24 +1080,76 @@ impl App {
                                 status: PhaseStatus::NotStarted,
                                 tasks: Vec::new(),
                                 output_files: Vec::new(),
+                                total_tasks: None,
+                                started_at: None,
+                                finished_at: None,
+                                error: None,
                             });
                         }
                     }
                     if let Some(p) = phases.get_mut(phase) {
-                        p.output_files.push((file_path, description));
+                        // Streamed files are reported once per write; keep one entry
+                        if let Some(entry) = p
+                            .output_files
+                            .iter_mut()
+                            .find(|(path, _)| *path == file_path)
+                        {
+                            entry.1 = description;
+                        } else {
+                            p.output_files.push((file_path, description));
+                        }
+                    }
+                }
+                // Only progress is shown in the tree; other metrics stay in
+                // the execution logs
+                WorkflowLog::Metric {
+                    task_id,
+                    name,
+                    value,
+                    unit: _,
+                } if name == "progress" && (0.0..=1.0).contains(&value) => {
+                    for phase in phases.iter_mut() {
+                        if let Some(task) = phase.tasks.iter_mut().find(|t| t.id == task_id) {
+                            task.progress = Some(value);
+                            break;
+                        }
                     }
                 }
+                WorkflowLog::Usage { .. } => {
+                    // Usage totals are kept per tab by handle_usage_event()
+                }
+                WorkflowLog::Result { .. } => {
+                    // The run's result is kept per tab by handle_result_event()
+                }
                 WorkflowLog::RawOutput { .. } => {
                     // Raw output is handled separately in format_workflow_log()
                     // for display in the raw output pane. This function only updates
                     // the structured log view (phases/tasks/agents tree).
                 }
+                _ => {
+                    // Other metrics, and events added to the SDK after this
+                    // TUI was built
+                }
+            }
+
+            if let Some((task_id, agent_id, value)) = raw_agent_event {
+                for phase in phases.iter_mut() {
+                    if let Some(task) = phase.tasks.iter_mut().find(|t| t.id == task_id) {
+                        if let Some(agent) = task.agents.iter_mut().find(|a| a.id == agent_id) {
+                            agent.raw_events.push(value);
+                        }
+                        break;
+                    }
+                }
             }
         }
     }
 
     pub fn toggle_selected_item(&mut self) {
-        // Toggle expansion of currently selected item
+        // Toggle expansion of currently selected item; files have none
+        if self.selected_file.is_some() {
+            return;
+        }
         if let Some(ref agent_id) = self.selected_agent {
             if self.expanded_agents.contains(agent_id) {
                 self.expanded_agents.remove(agent_id);
@@ -753,6 +1191,7 @@ impl App {
                 }
             } else {
                 // Collapse all
+                self.selected_file = None;
                 self.expanded_phases.clear();
                 self.expanded_tasks.clear();
                 self.expanded_agents.clear();
@@ -765,6 +1204,7 @@ impl App {
         if let Ok(phases) = self.workflow_phases.lock() {
             if self.expanded_phases.len() == phases.len() {
                 // All expanded, collapse all
+                self.selected_file = None;
                 self.expanded_phases.clear();
             } else {
                 // Expand all phases
@@ -819,3 +1259,64 @@ impl App {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nested_agents_follow_their_parent() {
+        let phases = Arc::new(Mutex::new(Vec::new()));
+        let agent = |name: &str, parent: Option<&str>| WorkflowLog::AgentStarted {
+            task_id: "t1".to_string(),
+            agent_name: name.to_string(),
+            description: String::new(),
+            parent_agent: parent.map(String::from),
+        };
+        let events = vec![
+            WorkflowLog::TaskStarted {
+                phase: 0,
+                task_id: "t1".to_string(),
+                description: "Task".to_string(),
+                total_tasks: None,
+            },
+            agent("planner", None),
+            agent("reviewer", None),
+            agent("searcher", Some("planner")),
+            agent("reader", Some("searcher")),
+            // Unknown parents keep the agent at the top level
+            agent("writer", Some("missing")),
+            WorkflowLog::AgentMessage {
+                task_id: "t1".to_string(),
+                agent_name: "checker".to_string(),
+                message: "checking".to_string(),
+                parent_agent: Some("planner".to_string()),
+            },
+        ];
+        for event in events {
+            App::replay_workflow_event(event, &phases);
+        }
+
+        let phases = phases.lock().unwrap();
+        let tree: Vec<(&str, usize)> = phases[0].tasks[0]
+            .agents
+            .iter()
+            .map(|a| (a.name.as_str(), a.depth))
+            .collect();
+        assert_eq!(
+            tree,
+            vec![
+                ("planner", 0),
+                ("searcher", 1),
+                ("reader", 2),
+                ("checker", 1),
+                ("reviewer", 0),
+                ("writer", 0),
+            ]
+        );
+        assert_eq!(
+            phases[0].tasks[0].agents[3].messages,
+            vec![LogLine::new(LogLevel::Info, "checking")]
+        );
+    }
+}
@@ -29,8 +29,7 @@ struct Args {
     #[arg(short = 's', long, action = clap::ArgAction::SetTrue)]
     #[field(
         label = "Slow Mode",
-        description = "[BOOL] Leave empty or type 'true' to enable slow mode",
-        type = "text"
+        description = "[BOOL] Simulate slow execution"
     )]
     slow_mode: bool,
 
@@ -49,6 +48,9 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
+    // Check the arguments against the workflow's field schema
+    args.validate_or_exit();
+
     println!("🚀 Demo Multi-Phase Workflow Started");
     println!("========================================\n");
 
@@ -27,8 +27,7 @@ struct Args {
     #[arg(long, default_value = "false")]
     #[field(
         label = "Test Dangerous Commands",
-        description = "[BOOLEAN] Test blocking dangerous rm commands",
-        type = "text"
+        description = "[BOOLEAN] Test blocking dangerous rm commands"
     )]
     test_dangerous: bool,
 
@@ -58,6 +57,9 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
+    // Check the arguments against the workflow's field schema
+    args.validate_or_exit();
+
     println!("=== Advanced Hooks Demo ===\n");
     println!("This demo shows:");
     println!("1. Blocking dangerous bash commands");
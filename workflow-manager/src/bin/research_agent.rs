@@ -68,6 +68,7 @@
 │ • Phase selection (--phases 0,1,2,3,4,5)                                    │
 │ • Custom prompts (--system-prompt, --append for output style)              │
 │ • YAML validation & repair (Phases 1 & 4 - can run standalone or in flow)  │
+│ • Run-level retry budget (--retry-budget N or $WORKFLOW_RETRY_BUDGET)      │
 └─────────────────────────────────────────────────────────────────────────────┘
 
 EXAMPLE COMMANDS:
@@ -134,8 +135,10 @@ EXAMPLE COMMANDS:
 
 */
 
+use anyhow::Context;
 use clap::Parser;
 use workflow_manager::research::{cli::Args, run_research_workflow, WorkflowConfig};
+use workflow_manager::workflow_utils::{listen_for_control, RetryBudget};
 use workflow_manager_sdk::WorkflowDefinition;
 
 #[tokio::main]
@@ -148,6 +151,24 @@ async fn main() -> anyhow::Result<()> {
         return Ok(());
     }
 
+    // Run in the selected repository; relative paths resolve against it
+    if let Some(dir) = &args.cwd {
+        std::env::set_current_dir(dir)
+            .with_context(|| format!("Failed to change directory to {}", dir))?;
+    }
+
+    // Check the arguments against the workflow's field schema (file paths
+    // resolve against the directory above)
+    args.validate_or_exit();
+
+    // Fix the run-level retry budget before any phase draws from it
+    if let Some(max_retries) = args.retry_budget {
+        RetryBudget::init_global(max_retries);
+    }
+
+    // Let the TUI cancel single research prompts
+    listen_for_control();
+
     // Convert args to config and run workflow
     let config: WorkflowConfig = args.into();
     run_research_workflow(config).await
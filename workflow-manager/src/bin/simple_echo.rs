@@ -53,6 +53,9 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
+    // Check the arguments against the workflow's field schema
+    args.validate_or_exit();
+
     println!("🚀 Simple Echo Workflow Started");
     println!("========================================");
 
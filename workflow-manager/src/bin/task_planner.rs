@@ -1,3 +1,4 @@
+use anyhow::Context;
 use clap::Parser;
 use workflow_manager::task_planner::{cli::Args, run_workflow};
 use workflow_manager_sdk::WorkflowDefinition;
@@ -12,5 +13,15 @@ async fn main() -> anyhow::Result<()> {
         return Ok(());
     }
 
+    // Run in the selected repository; relative paths resolve against it
+    if let Some(dir) = &args.cwd {
+        std::env::set_current_dir(dir)
+            .with_context(|| format!("Failed to change directory to {}", dir))?;
+    }
+
+    // Check the arguments against the workflow's field schema (file paths
+    // resolve against the directory above)
+    args.validate_or_exit();
+
     run_workflow(args).await
 }
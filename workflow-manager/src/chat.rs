@@ -1,16 +1,17 @@
 use claude_agent_sdk::types::{
-    ClaudeAgentOptions, ContentBlock, McpServerConfig, McpServers, Message, PermissionMode,
-    SdkMcpServerMarker, ToolName,
+    ClaudeAgentOptions, ContentBlock, McpServerConfig, McpServers, Message, SdkMcpServerMarker,
 };
 use claude_agent_sdk::ClaudeSDKClient;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::{mpsc, Mutex};
 use workflow_manager_sdk::WorkflowRuntime;
 
 use crate::mcp_tools::create_workflow_mcp_server;
-use crate::app::{AppCommand, TaskRegistry};
+use crate::app::{AppCommand, ChatSettings, TaskRegistry};
 
 /// Initialization result from background task
 pub enum InitResult {
@@ -29,26 +30,128 @@ pub enum ChatResponse {
 }
 
 /// A chat message in the conversation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatMessage {
     pub role: ChatRole,
     pub content: String,
     pub tool_calls: Vec<ToolCall>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ChatRole {
     User,
     Assistant,
+    /// Output of a local slash command, never sent to Claude
+    System,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolCall {
     pub name: String,
     pub input: String,
     pub output: String,
 }
 
+/// Text and tool calls of the response being streamed, so far
+#[derive(Debug, Default)]
+struct PendingResponse {
+    content: String,
+    tool_calls: Vec<ToolCall>,
+    /// The message reached Claude, so an interrupt has a turn to end
+    sent: bool,
+    /// The turn ended; set while the client is still locked
+    finished: bool,
+}
+
+/// Lines of a tool result shown when its call is expanded in the chat
+pub const TOOL_RESULT_PREVIEW_LINES: usize = 10;
+
+/// Lines under an expanded tool call: its input arguments, then the first
+/// [`TOOL_RESULT_PREVIEW_LINES`] of its result
+pub fn tool_call_details(tool_call: &ToolCall) -> Vec<String> {
+    let mut lines = vec!["    Input:".to_string()];
+    if tool_call.input.is_empty() {
+        lines.push("      (none)".to_string());
+    }
+    lines.extend(tool_call.input.lines().map(|line| format!("      {}", line)));
+
+    lines.push("    Result:".to_string());
+    let result_lines = tool_call.output.lines().count();
+    if result_lines == 0 {
+        lines.push("      (none)".to_string());
+    }
+    lines.extend(
+        tool_call
+            .output
+            .lines()
+            .take(TOOL_RESULT_PREVIEW_LINES)
+            .map(|line| format!("      {}", line)),
+    );
+    if result_lines > TOOL_RESULT_PREVIEW_LINES {
+        lines.push(format!(
+            "      ... ({} more lines)",
+            result_lines - TOOL_RESULT_PREVIEW_LINES
+        ));
+    }
+    lines
+}
+
+/// Line ending an assistant message that was interrupted
+pub const INTERRUPTED_MARKER: &str = "[Interrupted]";
+
+/// Format version of the persisted conversation file
+///
+/// Bump this whenever `ChatMessage` changes shape; files written with any
+/// other version are discarded on load.
+const CONVERSATION_VERSION: u32 = 1;
+
+/// Conversation as stored on disk between sessions
+#[derive(Serialize, Deserialize)]
+struct PersistedConversation {
+    version: u32,
+    messages: Vec<ChatMessage>,
+}
+
+/// File holding the conversation of the last session
+pub fn conversation_path() -> Option<PathBuf> {
+    directories::ProjectDirs::from("", "", "workflow-manager")
+        .map(|dirs| dirs.data_dir().join("chat_conversation.json"))
+}
+
+/// Load a persisted conversation, discarding unreadable or outdated files
+pub fn load_conversation(path: &Path) -> Vec<ChatMessage> {
+    let Ok(json) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+
+    match serde_json::from_str::<PersistedConversation>(&json) {
+        Ok(conversation) if conversation.version == CONVERSATION_VERSION => conversation.messages,
+        _ => {
+            let _ = std::fs::remove_file(path);
+            Vec::new()
+        }
+    }
+}
+
+/// Write a conversation to disk; an empty conversation removes the file
+pub fn save_conversation(path: &Path, messages: &[ChatMessage]) -> std::io::Result<()> {
+    if messages.is_empty() {
+        return match std::fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        };
+    }
+
+    let conversation = PersistedConversation {
+        version: CONVERSATION_VERSION,
+        messages: messages.to_vec(),
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(&conversation)?)
+}
+
 /// Active pane in chat view
 #[derive(Debug, Clone, PartialEq)]
 pub enum ActivePane {
@@ -70,6 +173,10 @@ pub struct ChatInterface {
     init_rx: Option<mpsc::UnboundedReceiver<InitResult>>,
     /// Channel for receiving responses from background task
     pub response_rx: Option<mpsc::UnboundedReceiver<ChatResponse>>,
+    /// Background task collecting the response, aborted on interrupt
+    response_task: Option<tokio::task::JoinHandle<()>>,
+    /// What the background task has collected of the response so far
+    pending_response: Arc<std::sync::Mutex<PendingResponse>>,
     /// Whether we're waiting for a response
     pub waiting_for_response: bool,
     /// When we started waiting for response (for timing display)
@@ -86,6 +193,17 @@ pub struct ChatInterface {
     pub auto_scroll: bool,
     /// Currently active pane for keyboard navigation
     pub active_pane: ActivePane,
+    /// Tool call line selected in the messages pane, as (message, tool call)
+    /// indices
+    pub selected_tool: Option<(usize, usize)>,
+    /// Tool calls shown with their input and result
+    pub expanded_tools: HashSet<(usize, usize)>,
+    /// The selection moved and the messages pane should scroll to it
+    pub reveal_selected_tool: bool,
+    /// Model and tools the client is connected with
+    pub settings: ChatSettings,
+    /// Settings being edited in the overlay, applied on Enter
+    pub settings_draft: Option<ChatSettings>,
     /// Runtime for workflow operations
     runtime: Arc<dyn WorkflowRuntime>,
     /// Workflow history for parameter suggestions
@@ -118,6 +236,47 @@ impl ChatInterface {
         task_registry: TaskRegistry,
         tokio_handle: tokio::runtime::Handle,
         database: Arc<std::sync::Mutex<crate::database::Database>>,
+        settings: ChatSettings,
+    ) -> Self {
+        let mut chat = Self::detached(
+            runtime.clone(),
+            history.clone(),
+            command_tx.clone(),
+            task_registry.clone(),
+            tokio_handle.clone(),
+            database,
+        );
+
+        // Restore the conversation of the previous session
+        if let Some(path) = conversation_path() {
+            chat.messages = load_conversation(&path);
+        }
+        chat.settings = settings;
+
+        // Start initialization in background
+        chat.start_initialization(
+            None,
+            runtime,
+            history,
+            command_tx,
+            task_registry,
+            tokio_handle,
+        );
+
+        chat
+    }
+
+    /// Create chat state without connecting to Claude
+    ///
+    /// The interface stays uninitialized until a client is attached, which
+    /// makes it usable for rendering tests.
+    pub fn detached(
+        runtime: Arc<dyn WorkflowRuntime>,
+        history: Arc<Mutex<crate::models::WorkflowHistory>>,
+        command_tx: mpsc::UnboundedSender<AppCommand>,
+        task_registry: TaskRegistry,
+        tokio_handle: tokio::runtime::Handle,
+        database: Arc<std::sync::Mutex<crate::database::Database>>,
     ) -> Self {
         // Load message history from database
         let message_history = database
@@ -126,13 +285,15 @@ impl ChatInterface {
             .get_chat_history(100) // Load last 100 messages
             .unwrap_or_default();
 
-        let mut chat = Self {
+        Self {
             messages: Vec::new(),
             input_buffer: String::new(),
             cursor_position: 0,
             client: None,
             init_rx: None,
             response_rx: None,
+            response_task: None,
+            pending_response: Arc::default(),
             waiting_for_response: false,
             response_start_time: None,
             spinner_frame: 0,
@@ -141,28 +302,31 @@ impl ChatInterface {
             log_scroll: 0,
             auto_scroll: true, // Start with auto-scroll enabled
             active_pane: ActivePane::ChatMessages,
-            runtime: runtime.clone(),
-            history: history.clone(),
-            tokio_handle: tokio_handle.clone(),
+            selected_tool: None,
+            expanded_tools: HashSet::new(),
+            reveal_selected_tool: false,
+            settings: ChatSettings::default(),
+            settings_draft: None,
+            runtime,
+            history,
+            tokio_handle,
             initialized: false,
             init_error: None,
-            command_tx: command_tx.clone(),
-            task_registry: task_registry.clone(),
+            command_tx,
+            task_registry,
             message_history,
             history_index: None,
             history_draft: String::new(),
             database,
-        };
-
-        // Start initialization in background
-        chat.start_initialization(runtime, history, command_tx, task_registry, tokio_handle);
-
-        chat
+        }
     }
 
     /// Start initialization in background
+    ///
+    /// A `previous` client is disconnected before the new one connects.
     fn start_initialization(
         &mut self,
+        previous: Option<Arc<Mutex<ClaudeSDKClient>>>,
         runtime: Arc<dyn WorkflowRuntime>,
         history: Arc<Mutex<crate::models::WorkflowHistory>>,
         command_tx: mpsc::UnboundedSender<AppCommand>,
@@ -174,8 +338,15 @@ impl ChatInterface {
         self.init_rx = Some(rx);
 
         // Spawn initialization task
+        let settings = self.settings.clone();
         tokio_handle.spawn(async move {
-            let result = Self::initialize_internal(runtime, history, command_tx, task_registry).await;
+            if let Some(previous) = previous {
+                // Its CLI process would otherwise outlive the conversation
+                let _ = previous.lock().await.close().await;
+            }
+            let result =
+                Self::initialize_internal(runtime, history, command_tx, task_registry, settings)
+                    .await;
             let _ = tx.send(result);
         });
     }
@@ -186,9 +357,16 @@ impl ChatInterface {
         history: Arc<Mutex<crate::models::WorkflowHistory>>,
         command_tx: mpsc::UnboundedSender<AppCommand>,
         task_registry: TaskRegistry,
+        settings: ChatSettings,
     ) -> InitResult {
         // Create MCP server with workflow tools
-        let mcp_server = create_workflow_mcp_server(runtime, history, command_tx, task_registry);
+        let mcp_server = create_workflow_mcp_server(
+            runtime,
+            history,
+            command_tx,
+            task_registry,
+            settings.read_only,
+        );
 
         // Register MCP server
         let mut mcp_servers = HashMap::new();
@@ -203,15 +381,10 @@ impl ChatInterface {
         // Create options with SDK MCP server
         let options = ClaudeAgentOptions {
             mcp_servers: McpServers::Dict(mcp_servers),
-            allowed_tools: vec![
-                ToolName::new("mcp__workflow_manager__list_workflows"),
-                ToolName::new("mcp__workflow_manager__execute_workflow"),
-                ToolName::new("mcp__workflow_manager__get_workflow_logs"),
-                ToolName::new("mcp__workflow_manager__get_workflow_status"),
-                ToolName::new("mcp__workflow_manager__cancel_workflow"),
-            ],
+            allowed_tools: settings.allowed_tools(),
+            model: settings.model.clone(),
             max_turns: Some(10),
-            permission_mode: Some(PermissionMode::BypassPermissions),
+            permission_mode: Some(settings.permission_mode()),
             ..Default::default()
         };
 
@@ -278,43 +451,118 @@ impl ChatInterface {
 
         // Clone client Arc for background task
         if let Some(client) = self.client.clone() {
-            self.tokio_handle.spawn(async move {
+            let pending = Arc::new(std::sync::Mutex::new(PendingResponse::default()));
+            self.pending_response = pending.clone();
+            self.response_task = Some(self.tokio_handle.spawn(async move {
                 // Send message and collect response
-                let result = Self::send_message_internal(client, message).await;
+                let result = Self::send_message_internal(client, message, pending).await;
 
                 // Send result back via channel
                 let _ = tx.send(result);
+            }));
+        }
+    }
+
+    /// Stop the response being streamed
+    ///
+    /// What arrived so far is kept as the assistant message, marked as
+    /// interrupted, and input is accepted again at once. Claude is told to
+    /// stop in the background; a message sent meanwhile waits for that.
+    pub fn interrupt_response(&mut self) {
+        if !self.waiting_for_response {
+            return;
+        }
+        // A response that finished before the keypress is shown whole
+        self.poll_response();
+        if !self.waiting_for_response {
+            return;
+        }
+        // Aborting releases the client lock held while waiting for a message
+        if let Some(task) = self.response_task.take() {
+            task.abort();
+        }
+        self.waiting_for_response = false;
+        self.response_start_time = None;
+        self.response_rx = None;
+
+        let (mut content, tool_calls) = {
+            let mut pending = self.pending_response.lock().unwrap();
+            let content = std::mem::take(&mut pending.content).trim().to_string();
+            (content, std::mem::take(&mut pending.tool_calls))
+        };
+        if !content.is_empty() {
+            content.push_str("\n\n");
+        }
+        content.push_str(INTERRUPTED_MARKER);
+        self.messages.push(ChatMessage {
+            role: ChatRole::Assistant,
+            content,
+            tool_calls,
+        });
+        self.auto_scroll = true;
+
+        if let Some(client) = self.client.clone() {
+            let pending = self.pending_response.clone();
+            self.tokio_handle.spawn(async move {
+                let mut client = client.lock().await;
+                // With the client locked the aborted task reads no more
+                // messages, so the flags are final
+                let unfinished = {
+                    let pending = pending.lock().unwrap();
+                    pending.sent && !pending.finished
+                };
+                if !unfinished || client.interrupt().await.is_err() {
+                    return;
+                }
+                // Skip the rest of the interrupted turn, so the next response
+                // starts with its own messages
+                while let Some(Ok(message)) = client.next_message().await {
+                    if matches!(message, Message::Result { .. }) {
+                        break;
+                    }
+                }
             });
         }
     }
 
     /// Internal method to send message and collect response (runs in background task)
+    ///
+    /// The response is collected in `pending`, so an interrupt can keep it.
     async fn send_message_internal(
         client: Arc<Mutex<ClaudeSDKClient>>,
         message: String,
+        pending: Arc<std::sync::Mutex<PendingResponse>>,
     ) -> ChatResponse {
         // Lock client and send message
         let send_result = {
             let mut client_guard = client.lock().await;
-            client_guard.send_message(message).await
+            let result = client_guard.send_message(message).await;
+            pending.lock().unwrap().sent = result.is_ok();
+            result
         };
 
         if let Err(e) = send_result {
             return ChatResponse::Error(format!("Failed to send message: {}", e));
         }
 
-        // Collect response
-        let mut assistant_content = String::new();
-        let mut tool_calls = Vec::new();
-
         loop {
             let msg_result = {
                 let mut client_guard = client.lock().await;
-                client_guard.next_message().await
+                let msg_result = client_guard.next_message().await;
+                if matches!(msg_result, None | Some(Err(_)) | Some(Ok(Message::Result { .. }))) {
+                    pending.lock().unwrap().finished = true;
+                }
+                msg_result
             };
 
             match msg_result {
                 Some(Ok(Message::Assistant { message, .. })) => {
+                    let mut response = pending.lock().unwrap();
+                    let PendingResponse {
+                        content: assistant_content,
+                        tool_calls,
+                        ..
+                    } = &mut *response;
                     for block in &message.content {
                         match block {
                             ContentBlock::Text { text } => {
@@ -358,7 +606,8 @@ impl ChatInterface {
                 }
                 Some(Ok(Message::Result { is_error, .. })) => {
                     if is_error {
-                        assistant_content.push_str("\n[Error occurred during conversation]");
+                        let mut response = pending.lock().unwrap();
+                        response.content.push_str("\n[Error occurred during conversation]");
                     }
                     break;
                 }
@@ -370,9 +619,10 @@ impl ChatInterface {
             }
         }
 
+        let mut response = pending.lock().unwrap();
         ChatResponse::Success {
-            content: assistant_content.trim().to_string(),
-            tool_calls,
+            content: std::mem::take(&mut response.content).trim().to_string(),
+            tool_calls: std::mem::take(&mut response.tool_calls),
         }
     }
 
@@ -386,6 +636,7 @@ impl ChatInterface {
                     self.waiting_for_response = false;
                     self.response_start_time = None;
                     self.response_rx = None;
+                    self.response_task = None;
 
                     // Add to message history
                     match response {
@@ -427,6 +678,45 @@ impl ChatInterface {
         }
     }
 
+    /// Persist the conversation so the next session can restore it
+    pub fn save_conversation(&self) {
+        if let Some(path) = conversation_path() {
+            let _ = save_conversation(&path, &self.messages);
+        }
+    }
+
+    /// Start a fresh conversation
+    ///
+    /// Drops the messages in memory and on disk, and reconnects the client so
+    /// Claude does not keep the old context either.
+    pub fn clear_conversation(&mut self) {
+        self.messages.clear();
+        self.selected_tool = None;
+        self.expanded_tools.clear();
+        self.message_scroll = 0;
+        self.log_scroll = 0;
+        self.auto_scroll = true;
+        self.save_conversation();
+        self.reconnect();
+    }
+
+    /// Replace the client with a new one using the current settings
+    ///
+    /// The new client starts without the context of the messages on screen.
+    pub fn reconnect(&mut self) {
+        let previous = self.client.take();
+        self.initialized = false;
+        self.init_error = None;
+        self.start_initialization(
+            previous,
+            self.runtime.clone(),
+            self.history.clone(),
+            self.command_tx.clone(),
+            self.task_registry.clone(),
+            self.tokio_handle.clone(),
+        );
+    }
+
     /// Clear input buffer
     pub fn clear_input(&mut self) {
         self.input_buffer.clear();
@@ -465,6 +755,44 @@ impl ChatInterface {
         self.scroll_offset = self.scroll_offset.saturating_add(1);
     }
 
+    /// Select the next (or previous) tool call line in the messages pane,
+    /// wrapping around
+    pub fn select_tool_call(&mut self, forward: bool) {
+        let calls: Vec<(usize, usize)> = self
+            .messages
+            .iter()
+            .enumerate()
+            .flat_map(|(msg_idx, msg)| (0..msg.tool_calls.len()).map(move |i| (msg_idx, i)))
+            .collect();
+        if calls.is_empty() {
+            return;
+        }
+        let position = self
+            .selected_tool
+            .and_then(|selected| calls.iter().position(|&call| call == selected));
+        let index = match (position, forward) {
+            (Some(i), true) => (i + 1) % calls.len(),
+            (Some(i), false) => (i + calls.len() - 1) % calls.len(),
+            (None, true) => 0,
+            (None, false) => calls.len() - 1,
+        };
+        self.selected_tool = Some(calls[index]);
+        self.reveal_selected_tool = true;
+        self.auto_scroll = false;
+    }
+
+    /// Expand or collapse the selected tool call; false if none is selected
+    pub fn toggle_selected_tool_call(&mut self) -> bool {
+        let Some(selected) = self.selected_tool else {
+            return false;
+        };
+        if !self.expanded_tools.remove(&selected) {
+            self.expanded_tools.insert(selected);
+        }
+        self.reveal_selected_tool = true;
+        true
+    }
+
     /// Switch to next pane (cycle through)
     pub fn next_pane(&mut self) {
         self.active_pane = match self.active_pane {
@@ -492,7 +820,7 @@ impl ChatInterface {
     pub fn calculate_total_lines(&self) -> u16 {
         let mut total_lines = 0u16;
 
-        for msg in &self.messages {
+        for (msg_idx, msg) in self.messages.iter().enumerate() {
             // Role line (e.g., "You: " or "Claude: ")
             total_lines = total_lines.saturating_add(1);
 
@@ -503,7 +831,13 @@ impl ChatInterface {
             // Tool calls display (if any)
             if !msg.tool_calls.is_empty() {
                 total_lines = total_lines.saturating_add(1); // Blank line
-                total_lines = total_lines.saturating_add(1); // "🔧 X tool(s) used"
+            }
+            for (tool_idx, tool_call) in msg.tool_calls.iter().enumerate() {
+                total_lines = total_lines.saturating_add(1); // "🔧 [Tool Used] <name>"
+                if self.expanded_tools.contains(&(msg_idx, tool_idx)) {
+                    let details = tool_call_details(tool_call).len();
+                    total_lines = total_lines.saturating_add(details as u16);
+                }
             }
 
             // Blank line after each message
@@ -531,10 +865,9 @@ impl ChatInterface {
         self.message_scroll = total_lines.saturating_sub(viewport_height);
     }
 
-    /// Get spinner character for current frame
-    pub fn get_spinner_char(&self) -> char {
-        const SPINNER: [char; 8] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧'];
-        SPINNER[self.spinner_frame]
+    /// Get spinner character for current frame from the given animation frames
+    pub fn get_spinner_char(&self, frames: &[char]) -> char {
+        frames[self.spinner_frame % frames.len()]
     }
 
     /// Get loading indicator for title bar
@@ -678,3 +1011,115 @@ impl ChatInterface {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_conversation_path() -> PathBuf {
+        std::env::temp_dir().join(format!("chat_conversation_{}.json", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn test_conversation_round_trip() {
+        let path = temp_conversation_path();
+        let messages = vec![
+            ChatMessage {
+                role: ChatRole::User,
+                content: "List workflows".to_string(),
+                tool_calls: Vec::new(),
+            },
+            ChatMessage {
+                role: ChatRole::Assistant,
+                content: "There are two.".to_string(),
+                tool_calls: vec![ToolCall {
+                    name: "list_workflows".to_string(),
+                    input: "{}".to_string(),
+                    output: "[]".to_string(),
+                }],
+            },
+        ];
+
+        save_conversation(&path, &messages).unwrap();
+        let loaded = load_conversation(&path);
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[1].role, ChatRole::Assistant);
+        assert_eq!(loaded[1].tool_calls[0].name, "list_workflows");
+
+        // Saving an empty conversation removes the file
+        save_conversation(&path, &[]).unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_outdated_conversation_is_discarded() {
+        let path = temp_conversation_path();
+        std::fs::write(&path, r#"{"version": 0, "messages": [{"text": "old"}]}"#).unwrap();
+
+        assert!(load_conversation(&path).is_empty());
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_interrupt_keeps_a_finished_response() {
+        let runtime = crate::runtime::ProcessBasedRuntime::with_database(
+            Vec::new(),
+            crate::database::Database::new_in_memory().unwrap(),
+        )
+        .unwrap();
+        let database = runtime.get_database();
+        let (command_tx, _command_rx) = mpsc::unbounded_channel();
+        let mut chat = ChatInterface::detached(
+            Arc::new(runtime),
+            Arc::default(),
+            command_tx,
+            TaskRegistry::new(),
+            tokio::runtime::Handle::current(),
+            database,
+        );
+
+        // The answer arrived just before the interrupt
+        let (tx, rx) = mpsc::unbounded_channel();
+        chat.response_rx = Some(rx);
+        chat.waiting_for_response = true;
+        tx.send(ChatResponse::Success {
+            content: "The whole answer".to_string(),
+            tool_calls: Vec::new(),
+        })
+        .unwrap();
+
+        chat.interrupt_response();
+        assert!(!chat.waiting_for_response);
+        assert_eq!(chat.messages.len(), 1);
+        assert_eq!(chat.messages[0].content, "The whole answer");
+    }
+
+    #[test]
+    fn test_tool_call_details() {
+        let output: Vec<String> = (1..=12).map(|n| format!("line {}", n)).collect();
+        let tool_call = ToolCall {
+            name: "execute_workflow".to_string(),
+            input: "{\n  \"workflow_id\": \"research\"\n}".to_string(),
+            output: output.join("\n"),
+        };
+        let details = tool_call_details(&tool_call);
+        assert_eq!(
+            details[..4],
+            ["    Input:", "      {", "        \"workflow_id\": \"research\"", "      }"]
+        );
+        assert_eq!(details[4], "    Result:");
+        assert_eq!(details[5], "      line 1");
+        assert_eq!(details.len(), 5 + TOOL_RESULT_PREVIEW_LINES + 1);
+        assert_eq!(details.last().unwrap(), "      ... (2 more lines)");
+
+        let empty = ToolCall {
+            name: "list_workflows".to_string(),
+            input: String::new(),
+            output: String::new(),
+        };
+        assert_eq!(
+            tool_call_details(&empty),
+            ["    Input:", "      (none)", "    Result:", "      (none)"]
+        );
+    }
+}
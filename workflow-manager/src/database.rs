@@ -53,6 +53,9 @@
 //!     binary_path: PathBuf::from("/usr/local/bin/web-search"),
 //!     created_at: Local::now(),
 //!     updated_at: Local::now(),
+//!     input_tokens: None,
+//!     output_tokens: None,
+//!     total_cost_usd: None,
 //! };
 //! db.insert_execution(&exec)?;
 //!
@@ -101,6 +104,7 @@ use anyhow::{anyhow, Result};
 use chrono::{DateTime, Local};
 use rusqlite::{params, Connection, OptionalExtension, Row};
 use serde_json;
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use uuid::Uuid;
@@ -124,6 +128,9 @@ pub struct PersistedExecution {
     pub binary_path: PathBuf,
     pub created_at: DateTime<Local>,
     pub updated_at: DateTime<Local>,
+    pub input_tokens: Option<u64>,
+    pub output_tokens: Option<u64>,
+    pub total_cost_usd: Option<f64>,
 }
 
 /// Log entry with metadata for database storage
@@ -262,6 +269,7 @@ impl Database {
 
         // Run migrations
         self.migrate_to_v2()?;
+        self.migrate_to_v3()?;
 
         Ok(())
     }
@@ -291,6 +299,25 @@ impl Database {
         Ok(())
     }
 
+    /// Migrate database schema to version 3 (token usage per execution)
+    pub fn migrate_to_v3(&self) -> Result<()> {
+        let current = self.get_schema_version()?;
+
+        if current < 3 {
+            self.conn.execute_batch(
+                r#"
+                ALTER TABLE executions ADD COLUMN input_tokens INTEGER;
+                ALTER TABLE executions ADD COLUMN output_tokens INTEGER;
+                ALTER TABLE executions ADD COLUMN total_cost_usd REAL;
+
+                UPDATE schema_version SET version = 3;
+                "#,
+            )?;
+        }
+
+        Ok(())
+    }
+
     /// Get current schema version
     pub fn get_schema_version(&self) -> Result<i32> {
         let version: i32 = self
@@ -323,8 +350,9 @@ impl Database {
             r#"
             INSERT INTO executions (
                 id, workflow_id, workflow_name, status, start_time, end_time,
-                exit_code, binary_path, created_at, updated_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+                exit_code, binary_path, created_at, updated_at,
+                input_tokens, output_tokens, total_cost_usd
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
             "#,
             params![
                 exec.id.to_string(),
@@ -337,6 +365,9 @@ impl Database {
                 binary_path_str,
                 exec.created_at.to_rfc3339(),
                 exec.updated_at.to_rfc3339(),
+                exec.input_tokens.map(|n| n as i64),
+                exec.output_tokens.map(|n| n as i64),
+                exec.total_cost_usd,
             ],
         )?;
 
@@ -367,6 +398,34 @@ impl Database {
         Ok(())
     }
 
+    /// Update the token usage totals of an execution
+    pub fn update_execution_usage(
+        &self,
+        id: &Uuid,
+        input_tokens: u64,
+        output_tokens: u64,
+        total_cost_usd: Option<f64>,
+    ) -> Result<()> {
+        let updated_at = Local::now().to_rfc3339();
+
+        self.conn.execute(
+            r#"
+            UPDATE executions
+            SET input_tokens = ?1, output_tokens = ?2, total_cost_usd = ?3, updated_at = ?4
+            WHERE id = ?5
+            "#,
+            params![
+                input_tokens as i64,
+                output_tokens as i64,
+                total_cost_usd,
+                updated_at,
+                id.to_string()
+            ],
+        )?;
+
+        Ok(())
+    }
+
     /// Get a single execution by ID
     pub fn get_execution(&self, id: &Uuid) -> Result<Option<PersistedExecution>> {
         let result = self
@@ -374,7 +433,8 @@ impl Database {
             .query_row(
                 r#"
                 SELECT id, workflow_id, workflow_name, status, start_time, end_time,
-                       exit_code, binary_path, created_at, updated_at
+                       exit_code, binary_path, created_at, updated_at,
+                       input_tokens, output_tokens, total_cost_usd
                 FROM executions
                 WHERE id = ?1
                 "#,
@@ -397,7 +457,8 @@ impl Database {
             format!(
                 r#"
                 SELECT id, workflow_id, workflow_name, status, start_time, end_time,
-                       exit_code, binary_path, created_at, updated_at
+                       exit_code, binary_path, created_at, updated_at,
+                       input_tokens, output_tokens, total_cost_usd
                 FROM executions
                 WHERE workflow_id = ?1
                 ORDER BY start_time DESC
@@ -408,7 +469,8 @@ impl Database {
             format!(
                 r#"
                 SELECT id, workflow_id, workflow_name, status, start_time, end_time,
-                       exit_code, binary_path, created_at, updated_at
+                       exit_code, binary_path, created_at, updated_at,
+                       input_tokens, output_tokens, total_cost_usd
                 FROM executions
                 ORDER BY start_time DESC
                 LIMIT ?1 OFFSET ?2
@@ -595,7 +657,7 @@ impl Database {
                 COUNT(*) as total,
                 SUM(CASE WHEN status = 'Completed' THEN 1 ELSE 0 END) as completed,
                 SUM(CASE WHEN status = 'Failed' THEN 1 ELSE 0 END) as failed,
-                SUM(CASE WHEN status = 'Running' THEN 1 ELSE 0 END) as running
+                SUM(CASE WHEN status IN ('Running', 'Paused') THEN 1 ELSE 0 END) as running
             FROM executions
             WHERE workflow_id = ?1
             "#,
@@ -619,7 +681,8 @@ impl Database {
         let mut stmt = self.conn.prepare(
             r#"
             SELECT id, workflow_id, workflow_name, status, start_time, end_time,
-                   exit_code, binary_path, created_at, updated_at
+                   exit_code, binary_path, created_at, updated_at,
+                   input_tokens, output_tokens, total_cost_usd
             FROM executions
             WHERE status = ?1
             ORDER BY start_time DESC
@@ -688,12 +751,16 @@ pub struct WorkflowStats {
 // Helper functions for mapping between database and Rust types
 
 /// Convert WorkflowStatus to database string
-fn status_to_string(status: &WorkflowStatus) -> &'static str {
+///
+/// Statuses this build doesn't know yet are stored by their variant name.
+fn status_to_string(status: &WorkflowStatus) -> Cow<'static, str> {
     match status {
-        WorkflowStatus::NotStarted => "NotStarted",
-        WorkflowStatus::Running => "Running",
-        WorkflowStatus::Completed => "Completed",
-        WorkflowStatus::Failed => "Failed",
+        WorkflowStatus::NotStarted => "NotStarted".into(),
+        WorkflowStatus::Running => "Running".into(),
+        WorkflowStatus::Paused => "Paused".into(),
+        WorkflowStatus::Completed => "Completed".into(),
+        WorkflowStatus::Failed => "Failed".into(),
+        status => format!("{:?}", status).into(),
     }
 }
 
@@ -702,6 +769,7 @@ fn string_to_status(s: &str) -> Result<WorkflowStatus> {
     match s {
         "NotStarted" => Ok(WorkflowStatus::NotStarted),
         "Running" => Ok(WorkflowStatus::Running),
+        "Paused" => Ok(WorkflowStatus::Paused),
         "Completed" => Ok(WorkflowStatus::Completed),
         "Failed" => Ok(WorkflowStatus::Failed),
         _ => Err(anyhow!("Unknown workflow status: {}", s)),
@@ -724,6 +792,10 @@ fn log_type_from_log(log: &WorkflowLog) -> String {
         WorkflowLog::AgentFailed { .. } => "AgentFailed",
         WorkflowLog::StateFileCreated { .. } => "StateFileCreated",
         WorkflowLog::RawOutput { .. } => "RawOutput",
+        WorkflowLog::Metric { .. } => "Metric",
+        WorkflowLog::Usage { .. } => "Usage",
+        WorkflowLog::Result { .. } => "Result",
+        _ => "Unknown",
     }
     .to_string()
 }
@@ -740,6 +812,9 @@ fn map_execution_row(row: &Row) -> rusqlite::Result<PersistedExecution> {
     let binary_path_str: String = row.get(7)?;
     let created_at_str: String = row.get(8)?;
     let updated_at_str: String = row.get(9)?;
+    let input_tokens: Option<i64> = row.get(10)?;
+    let output_tokens: Option<i64> = row.get(11)?;
+    let total_cost_usd: Option<f64> = row.get(12)?;
 
     let id = Uuid::parse_str(&id_str).map_err(|e| {
         rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e))
@@ -788,6 +863,9 @@ fn map_execution_row(row: &Row) -> rusqlite::Result<PersistedExecution> {
         binary_path,
         created_at,
         updated_at,
+        input_tokens: input_tokens.map(|n| n as u64),
+        output_tokens: output_tokens.map(|n| n as u64),
+        total_cost_usd,
     })
 }
 
@@ -809,6 +887,9 @@ mod tests {
             binary_path: PathBuf::from("/usr/bin/test"),
             created_at: now,
             updated_at: now,
+            input_tokens: None,
+            output_tokens: None,
+            total_cost_usd: None,
         }
     }
 
@@ -818,7 +899,7 @@ mod tests {
         db.initialize_schema().unwrap();
 
         let version = db.get_schema_version().unwrap();
-        assert_eq!(version, 2); // Updated to v2 with chat_input_history table
+        assert_eq!(version, 3); // v3 adds token usage columns to executions
     }
 
     #[test]
@@ -857,6 +938,25 @@ mod tests {
         assert_eq!(updated.exit_code, Some(0));
     }
 
+    #[test]
+    fn test_update_execution_usage() {
+        let db = Database::new_in_memory().unwrap();
+        db.initialize_schema().unwrap();
+
+        let exec_id = Uuid::new_v4();
+        db.insert_execution(&create_test_execution(exec_id)).unwrap();
+        let before = db.get_execution(&exec_id).unwrap().unwrap();
+        assert_eq!(before.input_tokens, None);
+        assert_eq!(before.total_cost_usd, None);
+
+        db.update_execution_usage(&exec_id, 1500, 420, Some(0.25)).unwrap();
+
+        let updated = db.get_execution(&exec_id).unwrap().unwrap();
+        assert_eq!(updated.input_tokens, Some(1500));
+        assert_eq!(updated.output_tokens, Some(420));
+        assert_eq!(updated.total_cost_usd, Some(0.25));
+    }
+
     #[test]
     fn test_list_executions() {
         let db = Database::new_in_memory().unwrap();
@@ -1,82 +1,446 @@
 use anyhow::Result;
+use serde::Deserialize;
+use std::io::Read;
 use std::path::{Path, PathBuf};
-use std::process::Command;
-use workflow_manager_sdk::{FieldSchema, FullWorkflowMetadata, WorkflowMetadata};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use crate::runtime::{kill_process_tree, try_wait_process_tree};
+use workflow_manager_sdk::{
+    FieldSchema, FullWorkflowMetadata, PipelineDefinition, WorkflowMetadata, WorkflowSource,
+};
+
+/// Workflow binaries asked for their metadata, and workflow files read, at
+/// the same time
+pub const DISCOVERY_CONCURRENCY: usize = 8;
+
+/// Environment variable overriding how long a binary may take to print its
+/// metadata, in seconds
+pub const DISCOVERY_TIMEOUT_ENV: &str = "WF_DISCOVERY_TIMEOUT";
+
+pub const DEFAULT_DISCOVERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Timeout set by `WF_DISCOVERY_TIMEOUT`, or the default if unset or invalid
+fn discovery_timeout_from_env() -> Duration {
+    std::env::var(DISCOVERY_TIMEOUT_ENV)
+        .ok()
+        .and_then(|value| value.trim().parse::<f64>().ok())
+        .filter(|&secs| secs > 0.0 && secs.is_finite())
+        .map(Duration::from_secs_f64)
+        .unwrap_or(DEFAULT_DISCOVERY_TIMEOUT)
+}
+
+/// A workflow binary did not print its metadata in time and was killed
+#[derive(Debug)]
+struct MetadataTimeout(Duration);
+
+impl std::fmt::Display for MetadataTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "no metadata within {:.1}s", self.0.as_secs_f64())
+    }
+}
+
+impl std::error::Error for MetadataTimeout {}
 
 /// Represents a discovered workflow with its metadata and binary path
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct DiscoveredWorkflow {
     pub metadata: WorkflowMetadata,
     pub fields: Vec<FieldSchema>,
+    /// Workflow binary, or the definition file for pipelines
     pub binary_path: PathBuf,
+    /// Set for pipeline workflows (chains of other workflows)
+    pub pipeline: Option<PipelineDefinition>,
+    /// Set for user-defined workflows: command template with `{field}` placeholders
+    pub command: Option<String>,
+    /// Standalone binary from the external workflows directory, described by
+    /// its `--print-metadata` (or `--workflow-metadata`) output
+    pub external: bool,
+}
+
+impl DiscoveredWorkflow {
+    pub fn is_pipeline(&self) -> bool {
+        self.pipeline.is_some()
+    }
+
+    /// Pipelines, command templates and external binaries come from the
+    /// user's config directories
+    pub fn source(&self) -> WorkflowSource {
+        if self.pipeline.is_some() || self.command.is_some() || self.external {
+            WorkflowSource::UserDefined
+        } else {
+            WorkflowSource::BuiltIn
+        }
+    }
+}
+
+/// A user-defined workflow file: metadata and fields plus a command template
+#[derive(Debug, Deserialize)]
+struct UserWorkflowDefinition {
+    #[serde(flatten)]
+    metadata: FullWorkflowMetadata,
+    command: String,
 }
 
 /// Discover all workflows by scanning for binaries and extracting metadata
+///
+/// Invalid user-defined workflow files and binaries that do not answer in
+/// time are skipped with a warning on stderr.
 pub fn discover_workflows() -> Vec<DiscoveredWorkflow> {
+    let (workflows, warnings) = discover_workflows_with_warnings();
+    for warning in warnings {
+        eprintln!("Warning: {}", warning);
+    }
+    workflows
+}
+
+/// Discover all workflows, returning why any user-defined file or
+/// workflow binary was skipped
+///
+/// Binaries are asked for their metadata up to [`DISCOVERY_CONCURRENCY`] at
+/// a time. One that has not answered within the discovery timeout is killed
+/// and skipped with a warning, so a hung binary cannot hold up startup.
+pub fn discover_workflows_with_warnings() -> (Vec<DiscoveredWorkflow>, Vec<String>) {
     let mut workflows = Vec::new();
+    let mut warnings = Vec::new();
 
     // Search paths for workflow binaries
-    let search_paths = get_search_paths();
+    let binaries: Vec<PathBuf> = get_search_paths()
+        .iter()
+        .flat_map(|dir| workflow_binaries(dir))
+        .collect();
+
+    let timeout = discovery_timeout_from_env();
+    let results = map_bounded(&binaries, DISCOVERY_CONCURRENCY, |path| {
+        extract_workflow_metadata(path, timeout)
+    });
+    for (path, result) in binaries.iter().zip(results) {
+        match result {
+            Ok(workflow) => workflows.push(workflow),
+            Err(e) if e.is::<MetadataTimeout>() => {
+                warnings.push(format!("Skipping {}: {}", path.display(), e));
+            }
+            // Not a workflow binary
+            Err(_) => {}
+        }
+    }
+
+    let (user_workflows, user_warnings) = discover_user_workflows();
+    workflows.extend(user_workflows);
+    warnings.extend(user_warnings);
+
+    let (external_workflows, external_warnings) = discover_external_workflows(timeout);
+    workflows.extend(external_workflows);
+    warnings.extend(external_warnings);
 
-    for search_dir in search_paths {
-        if !search_dir.exists() {
+    // Pipelines reference the workflows discovered above by id
+    let (pipelines, pipeline_warnings) = discover_pipelines();
+    workflows.extend(pipelines);
+    warnings.extend(pipeline_warnings);
+
+    (workflows, warnings)
+}
+
+/// Executables in `dir` that may be workflow binaries
+fn workflow_binaries(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut binaries = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        // Skip directories
+        if !path.is_file() {
             continue;
         }
 
-        if let Ok(entries) = std::fs::read_dir(&search_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
+        // Get filename
+        let filename = match path.file_name().and_then(|n| n.to_str()) {
+            Some(name) => name,
+            None => continue,
+        };
 
-                // Skip directories
-                if !path.is_file() {
-                    continue;
-                }
+        // Skip the TUI binary itself
+        if filename == "workflow-manager" {
+            continue;
+        }
 
-                // Get filename
-                let filename = match path.file_name().and_then(|n| n.to_str()) {
-                    Some(name) => name,
-                    None => continue,
-                };
+        // Skip build artifacts (files with hashes after dash, or files with extensions)
+        // Process clean binary names like: test_workflow, research_agent, tasks_agent
+        // Skip files like: test_workflow-abc123def, test_workflow.d
+        if filename.contains('.') {
+            continue;
+        }
 
-                // Skip the TUI binary itself
-                if filename == "workflow-manager" {
+        // Skip if it looks like a hash suffix (has dash followed by hex)
+        if filename.contains('-') {
+            // Allow hyphens in the name (e.g., my-workflow) but not hash suffixes
+            if let Some(after_dash) = filename.split('-').next_back() {
+                // If after the last dash looks like a hash (long hex string), skip it
+                if after_dash.len() > 10 && after_dash.chars().all(|c| c.is_ascii_hexdigit()) {
                     continue;
                 }
+            }
+        }
 
-                // Skip build artifacts (files with hashes after dash, or files with extensions)
-                // Process clean binary names like: test_workflow, research_agent, tasks_agent
-                // Skip files like: test_workflow-abc123def, test_workflow.d
-                if filename.contains('.') {
-                    continue;
-                }
+        // Check if executable
+        if is_executable(&path) {
+            binaries.push(path);
+        }
+    }
+    binaries
+}
 
-                // Skip if it looks like a hash suffix (has dash followed by hex)
-                if filename.contains('-') {
-                    // Allow hyphens in the name (e.g., my-workflow) but not hash suffixes
-                    if let Some(after_dash) = filename.split('-').next_back() {
-                        // If after the last dash looks like a hash (long hex string), skip it
-                        if after_dash.len() > 10
-                            && after_dash.chars().all(|c| c.is_ascii_hexdigit())
-                        {
-                            continue;
-                        }
+/// `f` applied to each item on up to `workers` threads, in item order
+fn map_bounded<T: Sync, R: Send>(
+    items: &[T],
+    workers: usize,
+    f: impl Fn(&T) -> R + Sync,
+) -> Vec<R> {
+    let next = &AtomicUsize::new(0);
+    let f = &f;
+    let mut results: Vec<Option<R>> = items.iter().map(|_| None).collect();
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers.clamp(1, items.len().max(1)))
+            .map(|_| {
+                scope.spawn(move || {
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(item) = items.get(index) else {
+                            break;
+                        };
+                        done.push((index, f(item)));
                     }
-                }
+                    done
+                })
+            })
+            .collect();
+        for handle in handles {
+            for (index, result) in handle.join().expect("discovery worker panicked") {
+                results[index] = Some(result);
+            }
+        }
+    });
 
-                // Check if executable
-                if !is_executable(&path) {
-                    continue;
-                }
+    results
+        .into_iter()
+        .map(|result| result.expect("every item is mapped"))
+        .collect()
+}
 
-                // Try to extract workflow metadata
-                if let Ok(workflow) = extract_workflow_metadata(&path) {
-                    workflows.push(workflow);
-                }
+/// Discover command-template workflows from ~/.config/workflow-manager/workflows/*.json
+pub fn discover_user_workflows() -> (Vec<DiscoveredWorkflow>, Vec<String>) {
+    let mut workflows = Vec::new();
+    let mut warnings = Vec::new();
+
+    let Some(dir) = get_user_workflows_dir() else {
+        return (workflows, warnings);
+    };
+
+    let files = json_files(&dir);
+    let results = map_bounded(&files, DISCOVERY_CONCURRENCY, |path| load_user_workflow(path));
+    for (path, result) in files.iter().zip(results) {
+        match result {
+            Ok(workflow) => workflows.push(workflow),
+            Err(e) => warnings.push(format!(
+                "Skipping invalid workflow {}: {}",
+                path.display(),
+                e
+            )),
+        }
+    }
+
+    (workflows, warnings)
+}
+
+/// The `.json` files in `dir`
+fn json_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("json"))
+        .collect()
+}
+
+/// Discover standalone workflow binaries from ~/.config/workflow-manager/bin/
+///
+/// Every executable there is asked for its metadata with `--print-metadata`,
+/// or `--workflow-metadata` like the built-in ones. Unlike the binaries next
+/// to the TUI, one that fails to answer with valid metadata is skipped with
+/// a warning.
+pub fn discover_external_workflows(timeout: Duration) -> (Vec<DiscoveredWorkflow>, Vec<String>) {
+    let mut workflows = Vec::new();
+    let mut warnings = Vec::new();
+
+    let Some(dir) = get_external_workflows_dir() else {
+        return (workflows, warnings);
+    };
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return (workflows, warnings);
+    };
+    let binaries: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && is_executable(path))
+        .collect();
+
+    let results = map_bounded(&binaries, DISCOVERY_CONCURRENCY, |path| {
+        load_external_workflow(path, timeout)
+    });
+    for (path, result) in binaries.iter().zip(results) {
+        match result {
+            Ok(workflow) => workflows.push(workflow),
+            Err(e) => warnings.push(format!(
+                "Skipping external workflow {}: {}",
+                path.display(),
+                e
+            )),
+        }
+    }
+
+    (workflows, warnings)
+}
+
+/// Directory holding standalone workflow binaries
+fn get_external_workflows_dir() -> Option<PathBuf> {
+    std::env::var("HOME")
+        .ok()
+        .map(|home| PathBuf::from(home).join(".config/workflow-manager/bin"))
+}
+
+/// Directory holding user-defined workflow files
+fn get_user_workflows_dir() -> Option<PathBuf> {
+    std::env::var("HOME")
+        .ok()
+        .map(|home| PathBuf::from(home).join(".config/workflow-manager/workflows"))
+}
+
+/// Load a single user-defined workflow file
+pub fn load_user_workflow(path: &Path) -> Result<DiscoveredWorkflow> {
+    let content = std::fs::read_to_string(path)?;
+    let definition: UserWorkflowDefinition = serde_json::from_str(&content)
+        .map_err(|e| anyhow::anyhow!("Failed to parse workflow JSON: {}", e))?;
+
+    if definition.command.split_whitespace().next().is_none() {
+        anyhow::bail!(
+            "Workflow '{}' has an empty command",
+            definition.metadata.metadata.id
+        );
+    }
+    for name in template_placeholders(&definition.command) {
+        if !definition.metadata.fields.iter().any(|f| f.name == name) {
+            anyhow::bail!(
+                "Command placeholder '{{{}}}' does not match any field",
+                name
+            );
+        }
+    }
+
+    Ok(DiscoveredWorkflow {
+        metadata: definition.metadata.metadata,
+        fields: definition.metadata.fields,
+        binary_path: path.to_path_buf(),
+        pipeline: None,
+        command: Some(definition.command),
+        external: false,
+    })
+}
+
+/// Names of the `{field}` placeholders in a command template
+fn template_placeholders(template: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            break;
+        };
+        names.push(&after[..end]);
+        rest = &after[end + 1..];
+    }
+    names
+}
+
+/// Expand a command template into a program and its arguments
+///
+/// The template is split on whitespace before substitution, so every value
+/// stays a single argument and is never interpreted by a shell. Arguments
+/// left empty after substitution are dropped.
+pub fn expand_command_template(
+    template: &str,
+    params: &std::collections::HashMap<String, String>,
+) -> Vec<String> {
+    template
+        .split_whitespace()
+        .map(|token| {
+            let mut arg = token.to_string();
+            for name in template_placeholders(token) {
+                let value = params.get(name).map(String::as_str).unwrap_or("");
+                arg = arg.replace(&format!("{{{}}}", name), value);
             }
+            arg
+        })
+        .filter(|arg| !arg.is_empty())
+        .collect()
+}
+
+/// Discover pipeline definitions from ~/.config/workflow-manager/pipelines/*.json
+pub fn discover_pipelines() -> (Vec<DiscoveredWorkflow>, Vec<String>) {
+    let mut pipelines = Vec::new();
+    let mut warnings = Vec::new();
+
+    let Some(dir) = get_pipelines_dir() else {
+        return (pipelines, warnings);
+    };
+
+    let files = json_files(&dir);
+    let results = map_bounded(&files, DISCOVERY_CONCURRENCY, |path| load_pipeline(path));
+    for (path, result) in files.iter().zip(results) {
+        match result {
+            Ok(pipeline) => pipelines.push(pipeline),
+            Err(e) => warnings.push(format!(
+                "Skipping invalid pipeline {}: {}",
+                path.display(),
+                e
+            )),
         }
     }
 
-    workflows
+    (pipelines, warnings)
+}
+
+/// Directory holding user pipeline definitions
+fn get_pipelines_dir() -> Option<PathBuf> {
+    std::env::var("HOME")
+        .ok()
+        .map(|home| PathBuf::from(home).join(".config/workflow-manager/pipelines"))
+}
+
+/// Load a single pipeline definition file
+pub fn load_pipeline(path: &Path) -> Result<DiscoveredWorkflow> {
+    let content = std::fs::read_to_string(path)?;
+    let pipeline: PipelineDefinition = serde_json::from_str(&content)
+        .map_err(|e| anyhow::anyhow!("Failed to parse pipeline JSON: {}", e))?;
+
+    if pipeline.stages.is_empty() {
+        anyhow::bail!("Pipeline '{}' has no stages", pipeline.metadata.id);
+    }
+
+    Ok(DiscoveredWorkflow {
+        metadata: pipeline.metadata.clone(),
+        fields: pipeline.fields.clone(),
+        binary_path: path.to_path_buf(),
+        pipeline: Some(pipeline),
+        command: None,
+        external: false,
+    })
 }
 
 /// Get list of directories to search for workflow binaries
@@ -132,35 +496,113 @@ fn is_executable(path: &Path) -> bool {
 }
 
 /// Extract workflow metadata by running the binary with --workflow-metadata flag
-fn extract_workflow_metadata(binary_path: &Path) -> Result<DiscoveredWorkflow> {
-    // Execute: <binary> --workflow-metadata with timeout
-    let output = Command::new(binary_path)
-        .arg("--workflow-metadata")
-        .stdout(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::null()) // Suppress stderr
-        .output();
-
-    let output = match output {
-        Ok(out) => out,
+fn extract_workflow_metadata(
+    binary_path: &Path,
+    timeout: Duration,
+) -> Result<DiscoveredWorkflow> {
+    let full_metadata = query_metadata(binary_path, "--workflow-metadata", timeout)?;
+
+    Ok(DiscoveredWorkflow {
+        metadata: full_metadata.metadata,
+        fields: full_metadata.fields,
+        binary_path: binary_path.to_path_buf(),
+        pipeline: None,
+        command: None,
+        external: false,
+    })
+}
+
+/// Load an external workflow binary by running it with `--print-metadata`
+///
+/// Binaries built with the SDK derive only answer `--workflow-metadata`, so
+/// that is tried next; a binary that timed out is not asked again. It runs
+/// like a built-in workflow, with the CLI arguments of its fields, but is
+/// listed as user-defined and never built with cargo.
+pub fn load_external_workflow(binary_path: &Path, timeout: Duration) -> Result<DiscoveredWorkflow> {
+    let full_metadata = match query_metadata(binary_path, "--print-metadata", timeout) {
+        Err(e) if !e.is::<MetadataTimeout>() => {
+            query_metadata(binary_path, "--workflow-metadata", timeout).map_err(|_| e)?
+        }
+        result => result?,
+    };
+
+    Ok(DiscoveredWorkflow {
+        metadata: full_metadata.metadata,
+        fields: full_metadata.fields,
+        binary_path: binary_path.to_path_buf(),
+        pipeline: None,
+        command: None,
+        external: true,
+    })
+}
+
+/// The `FullWorkflowMetadata` JSON a binary prints when run with `flag`
+///
+/// Whatever the binary started is killed when it exits. A binary that has
+/// not exited within `timeout` is killed too and fails with
+/// [`MetadataTimeout`].
+fn query_metadata(
+    binary_path: &Path,
+    flag: &str,
+    timeout: Duration,
+) -> Result<FullWorkflowMetadata> {
+    let deadline = Instant::now() + timeout;
+    let mut command = Command::new(binary_path);
+    command
+        .arg(flag)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null()); // Suppress stderr
+    // Own process group, so killing it also closes the output of anything
+    // the binary started
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    let mut child = match command.spawn() {
+        Ok(child) => child,
         Err(_) => anyhow::bail!("Failed to execute binary"),
     };
 
-    if !output.status.success() {
+    // Read on another thread, so a binary that never closes its output can
+    // be given up on; the reader is joined on every path
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let (status, output) = std::thread::scope(|scope| {
+        let reader = scope.spawn(move || {
+            let mut bytes = Vec::new();
+            stdout.read_to_end(&mut bytes).map(|_| bytes)
+        });
+        let status: Result<_> = loop {
+            match try_wait_process_tree(&mut child) {
+                Ok(Some(status)) => break Ok(status),
+                Ok(None) if Instant::now() < deadline => {
+                    std::thread::sleep(Duration::from_millis(10))
+                }
+                Ok(None) => {
+                    kill_process_tree(&mut child);
+                    break Err(MetadataTimeout(timeout).into());
+                }
+                Err(e) => {
+                    kill_process_tree(&mut child);
+                    break Err(e.into());
+                }
+            }
+        };
+        (status, reader.join().expect("metadata reader panicked"))
+    });
+    let (status, stdout) = (status?, output?);
+
+    if !status.success() {
         anyhow::bail!("Binary did not return success status");
     }
 
-    let json = String::from_utf8(output.stdout)
+    let json = String::from_utf8(stdout)
         .map_err(|_| anyhow::anyhow!("Binary output was not valid UTF-8"))?;
 
     // Try to parse as FullWorkflowMetadata
-    let full_metadata: FullWorkflowMetadata = serde_json::from_str(&json)
-        .map_err(|e| anyhow::anyhow!("Failed to parse workflow metadata JSON: {}", e))?;
-
-    Ok(DiscoveredWorkflow {
-        metadata: full_metadata.metadata,
-        fields: full_metadata.fields,
-        binary_path: binary_path.to_path_buf(),
-    })
+    serde_json::from_str(&json)
+        .map_err(|e| anyhow::anyhow!("Failed to parse workflow metadata JSON: {}", e))
 }
 
 /// Build command string from field values
@@ -168,6 +610,20 @@ pub fn build_workflow_command(
     workflow: &DiscoveredWorkflow,
     field_values: &std::collections::HashMap<String, String>,
 ) -> String {
+    if let Some(template) = &workflow.command {
+        return expand_command_template(template, field_values)
+            .iter()
+            .map(|arg| {
+                if arg.contains(' ') {
+                    format!("'{}'", arg)
+                } else {
+                    arg.clone()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+    }
+
     let mut cmd = format!("{}", workflow.binary_path.display());
 
     for field in &workflow.fields {
@@ -231,4 +687,191 @@ mod tests {
             "Should discover at least one workflow"
         );
     }
+
+    #[test]
+    fn test_load_pipeline() {
+        let path = std::env::temp_dir().join(format!("pipeline_{}.json", uuid::Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            r#"{
+                "id": "research_then_plan",
+                "name": "Research then Plan",
+                "description": "Research a codebase, then plan tasks",
+                "stages": [
+                    {"workflow_id": "research"},
+                    {
+                        "workflow_id": "task_planner",
+                        "inputs": {
+                            "impl_file": {"from": "state_file", "stage": 0}
+                        }
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let workflow = load_pipeline(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert!(workflow.is_pipeline());
+        assert_eq!(workflow.metadata.id, "research_then_plan");
+        let pipeline = workflow.pipeline.unwrap();
+        assert_eq!(pipeline.stages.len(), 2);
+        assert!(!pipeline.continue_on_failure);
+        assert!(pipeline.stages[1].inputs.contains_key("impl_file"));
+    }
+
+    #[test]
+    fn test_load_pipeline_without_stages_fails() {
+        let path = std::env::temp_dir().join(format!("pipeline_{}.json", uuid::Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            r#"{"id": "empty", "name": "Empty", "description": "", "stages": []}"#,
+        )
+        .unwrap();
+
+        let result = load_pipeline(&path);
+        std::fs::remove_file(&path).ok();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_load_user_workflow() {
+        let path =
+            std::env::temp_dir().join(format!("user_workflow_{}.json", uuid::Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            r#"{
+                "id": "grep_todos",
+                "name": "Grep TODOs",
+                "description": "List TODO comments",
+                "fields": [
+                    {"name": "dir", "field_type": {"type": "text"}, "label": "Directory",
+                     "description": "", "cli_arg": "", "required": true, "default": null}
+                ],
+                "command": "grep -rn TODO {dir}"
+            }"#,
+        )
+        .unwrap();
+
+        let workflow = load_user_workflow(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert!(matches!(workflow.source(), WorkflowSource::UserDefined));
+        assert_eq!(workflow.metadata.id, "grep_todos");
+        assert_eq!(workflow.command.as_deref(), Some("grep -rn TODO {dir}"));
+    }
+
+    #[test]
+    fn test_load_user_workflow_rejects_unknown_placeholder() {
+        let path =
+            std::env::temp_dir().join(format!("user_workflow_{}.json", uuid::Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            r#"{"id": "bad", "name": "Bad", "description": "", "fields": [], "command": "echo {msg}"}"#,
+        )
+        .unwrap();
+
+        let result = load_user_workflow(&path);
+        std::fs::remove_file(&path).ok();
+
+        assert!(result.unwrap_err().to_string().contains("'{msg}'"));
+    }
+
+    #[test]
+    fn test_expand_command_template() {
+        let mut params = std::collections::HashMap::new();
+        params.insert("dir".to_string(), "my project".to_string());
+        params.insert("ext".to_string(), "rs".to_string());
+
+        assert_eq!(
+            expand_command_template("find {dir} -name *.{ext} {depth}", &params),
+            vec!["find", "my project", "-name", "*.rs"]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hung_binary_is_skipped_after_timeout() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("discovery_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let script = |name: &str, body: &str| {
+            let path = dir.join(name);
+            std::fs::write(&path, format!("#!/bin/sh\n{}\n", body)).unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+            path
+        };
+        let metadata = r#"{"id": "quick", "name": "Quick", "description": "", "fields": []}"#;
+        let binaries = vec![
+            // The shell's own child holds the output open too
+            script("hung", "sleep 30"),
+            script("quick", &format!("echo '{}'", metadata)),
+            // Left-behind processes are killed when the binary exits
+            script("forks", &format!("sleep 30 & echo '{}'", metadata)),
+        ];
+
+        let started = Instant::now();
+        let results = map_bounded(&binaries, DISCOVERY_CONCURRENCY, |path| {
+            extract_workflow_metadata(path, Duration::from_millis(500))
+        });
+        assert!(started.elapsed() < Duration::from_secs(10));
+
+        let error = results[0].as_ref().unwrap_err();
+        assert!(error.is::<MetadataTimeout>());
+        assert_eq!(error.to_string(), "no metadata within 0.5s");
+        assert_eq!(results[1].as_ref().unwrap().metadata.id, "quick");
+        assert_eq!(results[2].as_ref().unwrap().metadata.id, "quick");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_load_external_workflow() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("external_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let script = |name: &str, body: &str| {
+            let path = dir.join(name);
+            std::fs::write(&path, format!("#!/bin/sh\n{}\n", body)).unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+            path
+        };
+        let metadata = r#"{"id": "lint", "name": "Lint", "description": "", "fields": []}"#;
+        let lint = script(
+            "lint",
+            &format!("[ \"$1\" = --print-metadata ] && echo '{}'", metadata),
+        );
+        let derived = script(
+            "derived",
+            &format!("[ \"$1\" = --workflow-metadata ] && echo '{}'", metadata),
+        );
+        let broken = script("broken", "echo 'not json'");
+
+        let timeout = Duration::from_secs(5);
+        let workflow = load_external_workflow(&lint, timeout).unwrap();
+        assert_eq!(workflow.metadata.id, "lint");
+        assert_eq!(workflow.binary_path, lint);
+        assert!(matches!(workflow.source(), WorkflowSource::UserDefined));
+        assert!(workflow.command.is_none());
+
+        let workflow = load_external_workflow(&derived, timeout).unwrap();
+        assert_eq!(workflow.binary_path, derived);
+
+        let error = load_external_workflow(&broken, timeout).unwrap_err();
+        assert!(error.to_string().contains("Failed to parse workflow metadata JSON"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_map_bounded_keeps_item_order() {
+        let items: Vec<usize> = (0..50).collect();
+        assert_eq!(map_bounded(&items, 4, |n| n * 2), (0..100).step_by(2).collect::<Vec<_>>());
+        assert!(map_bounded(&Vec::<usize>::new(), 4, |n| *n).is_empty());
+    }
 }
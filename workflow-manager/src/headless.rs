@@ -0,0 +1,137 @@
+//! Non-interactive mode of the main binary, for scripts and CI
+//!
+//! `--list-workflows` prints the metadata of every discovered workflow as a
+//! JSON array. `--run <id> --param name=value ...` runs one workflow without
+//! the TUI and prints each of its log events to stdout as one JSON object per
+//! line, until the workflow finishes. `--params-file` reads the parameters
+//! from a file saved in the edit view, and `--param` overrides them. The
+//! process then exits with the workflow's exit code, or one of the codes
+//! below.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::time::Duration;
+use workflow_manager_sdk::{FullWorkflowMetadata, WorkflowLog, WorkflowRuntime, WorkflowStatus};
+
+use crate::discovery::discover_workflows;
+use crate::runtime::{ProcessBasedRuntime, TIMEOUT_PHASE_NAME};
+
+/// Time between reads of new log events
+const FOLLOW_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Exit code when the workflow was killed for going quiet (as `timeout(1)`)
+pub const EXIT_TIMEOUT: i32 = 124;
+
+/// Exit code when the run was cancelled with Ctrl+C (as a shell, 128 + SIGINT)
+pub const EXIT_CANCELLED: i32 = 130;
+
+/// Exit code of a failed workflow that reported none, e.g. a pipeline
+const EXIT_FAILED: i32 = 1;
+
+/// Parse a `--param` value written as `name=value`
+pub fn parse_param(s: &str) -> std::result::Result<(String, String), String> {
+    let (name, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected name=value, got '{}'", s))?;
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(format!("missing parameter name in '{}'", s));
+    }
+    Ok((name.to_string(), value.to_string()))
+}
+
+/// Print every discovered workflow as a JSON array of `FullWorkflowMetadata`
+pub fn list_workflows() -> Result<()> {
+    let workflows: Vec<FullWorkflowMetadata> = discover_workflows()
+        .into_iter()
+        .map(|w| FullWorkflowMetadata {
+            metadata: w.metadata,
+            fields: w.fields,
+        })
+        .collect();
+    println!("{}", serde_json::to_string_pretty(&workflows)?);
+    Ok(())
+}
+
+/// Run workflow `id` with `params`, streaming its log events as JSON lines,
+/// and return the exit code for this process
+///
+/// Ctrl+C cancels the workflow. Fails only if the workflow cannot start.
+pub async fn run_workflow(id: &str, params: Vec<(String, String)>) -> Result<i32> {
+    let runtime = ProcessBasedRuntime::new()?;
+    let params: HashMap<String, String> = params.into_iter().collect();
+    let handle = runtime.execute_workflow(id, params).await?;
+
+    let mut after_index = None;
+    let mut finished = false;
+    let mut timed_out = false;
+    let mut cancelled = false;
+    // Created once, so a Ctrl+C between two polls is not missed
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    let mut listening = true;
+    loop {
+        let page = runtime.get_logs_since(&handle.id, after_index).await?;
+        for log in &page.logs {
+            println!("{}", serde_json::to_string(log)?);
+            timed_out |= matches!(
+                log,
+                WorkflowLog::PhaseFailed { name, .. } if name == TIMEOUT_PHASE_NAME
+            );
+        }
+        after_index = page.latest_index.or(after_index);
+
+        // One more read after the end, for output still being parsed
+        if finished {
+            break;
+        }
+        finished = !matches!(
+            page.status,
+            WorkflowStatus::Running | WorkflowStatus::NotStarted | WorkflowStatus::Paused
+        );
+
+        tokio::select! {
+            _ = tokio::time::sleep(FOLLOW_INTERVAL) => {}
+            // Read the last events as soon as the workflow exits
+            _ = runtime.wait_for_completion(&handle.id), if !finished => {}
+            signal = &mut ctrl_c, if listening => {
+                listening = false;
+                match signal {
+                    Ok(()) => match runtime.cancel_workflow(&handle.id).await {
+                        Ok(()) => cancelled = true,
+                        // Keep following the run, which may have just exited
+                        Err(e) => eprintln!("Warning: Failed to cancel workflow: {}", e),
+                    },
+                    Err(e) => eprintln!("Warning: Ctrl+C cannot cancel the workflow: {}", e),
+                }
+            }
+        }
+    }
+
+    let summary = runtime.get_execution(&handle.id).await?;
+    Ok(match summary.status {
+        WorkflowStatus::Completed => 0,
+        _ if cancelled => EXIT_CANCELLED,
+        _ if timed_out => EXIT_TIMEOUT,
+        _ => summary.exit_code.filter(|&code| code != 0).unwrap_or(EXIT_FAILED),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_param() {
+        assert_eq!(
+            parse_param("input=a=b").unwrap(),
+            ("input".to_string(), "a=b".to_string())
+        );
+        assert_eq!(
+            parse_param("flag=").unwrap(),
+            ("flag".to_string(), String::new())
+        );
+        assert!(parse_param("input").is_err());
+        assert!(parse_param("=value").is_err());
+    }
+}
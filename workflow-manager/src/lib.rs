@@ -16,6 +16,9 @@ pub mod discovery;
 // Workflow runtime module
 pub mod runtime;
 
+// Pipeline (chained workflow) execution
+pub mod pipeline;
+
 // Database module
 pub mod database;
 
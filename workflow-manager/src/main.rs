@@ -1,4 +1,5 @@
 use anyhow::Result;
+use clap::Parser;
 use crossterm::{
     event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
     execute,
@@ -6,21 +7,91 @@ use crossterm::{
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
-use workflow_manager_sdk::FieldType;
+use std::time::{Duration, Instant};
+use workflow_manager_sdk::{FieldType, WorkflowStatus};
 
 mod app;
 mod chat;
 mod database;
 mod discovery;
+mod headless;
 mod mcp_tools;
 mod models;
+mod pipeline;
 mod runtime;
 mod ui;
 mod utils;
 
+// The research workflow is only in the library; the modules above name it
+// `crate::research` in both crates
+use workflow_manager::research;
+
+use app::Action;
 use models::*;
 
+/// Environment variable overriding the poll interval while something runs
+const POLL_INTERVAL_ENV: &str = "WF_POLL_INTERVAL_MS";
+
+/// Poll interval while workflows run or chat is busy, unless overridden
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Poll interval when nothing can change without input
+const IDLE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Time between spinner frames, independent of the poll interval
+const SPINNER_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Time between redraws that only advance the elapsed times of running
+/// workflows and the pulse of their timeline, which changes every 500 ms
+const CLOCK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Command-line options; without any, the TUI starts
+#[derive(Parser, Debug)]
+#[command(name = "workflow-manager", about = "Launch and monitor workflows")]
+struct Cli {
+    /// Print all workflows with their fields as JSON and exit
+    #[arg(long, conflicts_with = "run")]
+    list_workflows: bool,
+
+    /// Run the workflow with this id without the TUI, printing its log
+    /// events as JSON lines and exiting with its exit code
+    #[arg(long, visible_alias = "headless", value_name = "ID")]
+    run: Option<String>,
+
+    /// Parameter of the workflow given to --run (repeatable)
+    #[arg(
+        long = "param",
+        value_name = "NAME=VALUE",
+        requires = "run",
+        value_parser = headless::parse_param
+    )]
+    params: Vec<(String, String)>,
+
+    /// JSON or YAML file of parameters for --run, as saved in the edit
+    /// view; --param values take precedence
+    #[arg(long, value_name = "PATH", requires = "run")]
+    params_file: Option<std::path::PathBuf>,
+}
+
 fn main() -> Result<()> {
+    let cli = Cli::parse();
+    if cli.list_workflows {
+        return headless::list_workflows();
+    }
+    if let Some(id) = &cli.run {
+        let mut params: Vec<(String, String)> = match &cli.params_file {
+            Some(path) => app::read_params_file(path)?.into_iter().collect(),
+            None => Vec::new(),
+        };
+        params.extend(cli.params);
+        let code = tokio::runtime::Runtime::new()?.block_on(headless::run_workflow(id, params))?;
+        io::Write::flush(&mut io::stdout())?;
+        std::process::exit(code);
+    }
+
+    // Read before the alternate screen hides warnings
+    let poll_interval = poll_interval_from_env();
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -32,7 +103,7 @@ fn main() -> Result<()> {
     let mut app = App::new();
 
     // Run main loop
-    let res = run_app(&mut terminal, &mut app);
+    let res = run_app(&mut terminal, &mut app, poll_interval);
 
     // Restore terminal
     disable_raw_mode()?;
@@ -46,10 +117,66 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()> {
+/// Active poll interval from `WF_POLL_INTERVAL_MS`, if set to a positive
+/// number of milliseconds
+fn poll_interval_from_env() -> Duration {
+    let Ok(value) = std::env::var(POLL_INTERVAL_ENV) else {
+        return DEFAULT_POLL_INTERVAL;
+    };
+    match value.trim().parse::<u64>() {
+        Ok(ms) if ms > 0 => Duration::from_millis(ms),
+        _ => {
+            eprintln!(
+                "Warning: Ignoring {}='{}', expected a positive number of milliseconds",
+                POLL_INTERVAL_ENV, value
+            );
+            DEFAULT_POLL_INTERVAL
+        }
+    }
+}
+
+/// Whether the screen can change without user input
+///
+/// A paused run produces no output until it is resumed, so it counts as idle.
+fn has_activity(app: &App) -> bool {
+    app.workflow_running
+        || app.open_tabs.iter().any(|tab| {
+            matches!(tab.status, WorkflowStatus::Running | WorkflowStatus::NotStarted)
+        })
+        || app.chat.as_ref().is_some_and(|chat| {
+            (!chat.initialized && chat.init_error.is_none()) || chat.waiting_for_response
+        })
+}
+
+/// Chat state shown on screen, compared across polls to detect a response
+fn chat_state(app: &App) -> Option<(bool, bool, bool, usize)> {
+    app.chat.as_ref().map(|chat| {
+        (
+            chat.initialized,
+            chat.init_error.is_some(),
+            chat.waiting_for_response,
+            chat.messages.len(),
+        )
+    })
+}
+
+fn run_app<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    poll_interval: Duration,
+) -> Result<()> {
+    // Redraw only when something may have changed
+    let mut dirty = true;
+    let mut last_spinner_frame = Instant::now();
+    let mut last_clock_tick = Instant::now();
+    let mut seen_log_events = 0;
+
     loop {
+        let busy = has_activity(app);
+
         // 1. Process pending commands (non-blocking) with error handling
         while let Ok(cmd) = app.command_rx.try_recv() {
+            dirty = true;
             if let Err(e) = app.handle_command(cmd.clone()) {
                 // Log to stderr for debugging
                 eprintln!("Error handling command {:?}: {}", cmd, e);
@@ -63,32 +190,69 @@ fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, app: &mut A
         }
 
         // 2. Cleanup expired notifications
-        app.notifications.cleanup_expired();
+        dirty |= app.notifications.cleanup_expired();
+
+        // 3. Poll all running tabs for status changes; their output is
+        // streamed in the background and counted in `log_events`
+        dirty |= app.poll_all_tabs();
+        dirty |= app.poll_running_workflow();
+        let log_events = app.log_events.load(std::sync::atomic::Ordering::Relaxed);
+        if log_events != seen_log_events {
+            seen_log_events = log_events;
+            dirty = true;
+        }
 
-        // 3. Poll all running tabs for output
-        app.poll_all_tabs();
+        // Elapsed times and the timeline pulse advance without new output
+        if busy && last_clock_tick.elapsed() >= CLOCK_INTERVAL {
+            last_clock_tick = Instant::now();
+            dirty = true;
+        }
 
         // Poll chat for initialization and responses
+        let chat_before = chat_state(app);
         if let Some(chat) = &mut app.chat {
             chat.poll_initialization();
             chat.poll_response();
 
             // Update chat spinner animation if initializing or waiting for response
-            if !chat.initialized || chat.waiting_for_response {
+            if (!chat.initialized || chat.waiting_for_response)
+                && last_spinner_frame.elapsed() >= SPINNER_INTERVAL
+            {
                 chat.update_spinner();
+                last_spinner_frame = Instant::now();
+                dirty = true;
             }
         }
+        dirty |= chat_state(app) != chat_before;
 
-        terminal.draw(|f| ui::ui(f, &mut *app))?;
+        if dirty {
+            terminal.draw(|f| ui::ui(f, &mut *app))?;
+            dirty = false;
+        }
 
-        if event::poll(std::time::Duration::from_millis(50))? {
+        let timeout = if busy { poll_interval } else { IDLE_POLL_INTERVAL };
+        if event::poll(timeout)? {
+            // Keys and resizes both need a new frame
+            dirty = true;
             if let Event::Key(key) = event::read()? {
                 if key.kind == KeyEventKind::Press {
+                    // Help overlay: any key dismisses it
+                    if app.show_help {
+                        app.show_help = false;
+                    }
+                    // Command preview overlay: any key dismisses it
+                    else if app.command_preview.is_some() {
+                        app.command_preview = None;
+                    }
                     // Close confirmation dialog
-                    if app.show_close_confirmation {
+                    else if app.show_close_confirmation {
                         match key.code {
                             KeyCode::Char('y') | KeyCode::Char('Y') => {
-                                app.close_tab_confirmed();
+                                if matches!(app.current_view, View::WorkflowRunning(_)) {
+                                    app.cancel_running_workflow_confirmed();
+                                } else {
+                                    app.close_tab_confirmed();
+                                }
                             }
                             KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
                                 app.show_close_confirmation = false;
@@ -96,6 +260,66 @@ fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, app: &mut A
                             _ => {}
                         }
                     }
+                    // Raw JSON debug popup
+                    else if app.show_raw_json {
+                        match key.code {
+                            KeyCode::Down | KeyCode::Char('j') => {
+                                app.scroll_raw_json_down(1);
+                            }
+                            KeyCode::Up | KeyCode::Char('k') => {
+                                app.scroll_raw_json_up(1);
+                            }
+                            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                app.scroll_raw_json_down(15);
+                            }
+                            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                app.scroll_raw_json_up(15);
+                            }
+                            KeyCode::PageDown => {
+                                app.scroll_raw_json_down(15);
+                            }
+                            KeyCode::PageUp => {
+                                app.scroll_raw_json_up(15);
+                            }
+                            KeyCode::Esc | KeyCode::Char('J') | KeyCode::Char('q') => {
+                                app.toggle_raw_json_view();
+                            }
+                            _ => {}
+                        }
+                    }
+                    // Output file viewer
+                    else if app.file_viewer.is_some() {
+                        match key.code {
+                            KeyCode::Down | KeyCode::Char('j') => {
+                                app.scroll_file_viewer_down(1);
+                            }
+                            KeyCode::Up | KeyCode::Char('k') => {
+                                app.scroll_file_viewer_up(1);
+                            }
+                            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                app.scroll_file_viewer_down(15);
+                            }
+                            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                app.scroll_file_viewer_up(15);
+                            }
+                            KeyCode::PageDown => {
+                                app.scroll_file_viewer_down(15);
+                            }
+                            KeyCode::PageUp => {
+                                app.scroll_file_viewer_up(15);
+                            }
+                            KeyCode::Right | KeyCode::Char('l') | KeyCode::Tab => {
+                                app.step_output_file(true);
+                            }
+                            KeyCode::Left | KeyCode::Char('h') | KeyCode::BackTab => {
+                                app.step_output_file(false);
+                            }
+                            KeyCode::Esc | KeyCode::Char('o') | KeyCode::Char('q') => {
+                                app.close_file_viewer();
+                            }
+                            _ => {}
+                        }
+                    }
                     // Dropdown mode
                     else if app.show_dropdown {
                         match key.code {
@@ -108,6 +332,9 @@ fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, app: &mut A
                             KeyCode::Enter => {
                                 app.dropdown_select();
                             }
+                            KeyCode::Char(' ') => {
+                                app.toggle_dropdown_option();
+                            }
                             KeyCode::Esc => {
                                 app.close_dropdown();
                             }
@@ -129,6 +356,9 @@ fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, app: &mut A
                             KeyCode::Esc => {
                                 app.close_file_browser();
                             }
+                            KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                app.view_browsed_file();
+                            }
                             KeyCode::Char(c) => {
                                 // Fuzzy search
                                 app.file_browser_search.push(c);
@@ -139,6 +369,84 @@ fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, app: &mut A
                             _ => {}
                         }
                     }
+                    // Phase tree search prompt
+                    else if let Some(query) = &mut app.search_prompt {
+                        match key.code {
+                            KeyCode::Char(c) => {
+                                query.push(c);
+                            }
+                            KeyCode::Backspace => {
+                                query.pop();
+                            }
+                            KeyCode::Enter => {
+                                app.confirm_search();
+                            }
+                            KeyCode::Esc => {
+                                app.cancel_search();
+                            }
+                            _ => {}
+                        }
+                    }
+                    // Preset name prompt
+                    else if app.saving_preset {
+                        match key.code {
+                            KeyCode::Char(c) => {
+                                app.edit_buffer.push(c);
+                            }
+                            KeyCode::Backspace => {
+                                app.edit_buffer.pop();
+                            }
+                            KeyCode::Enter => {
+                                app.confirm_preset_save();
+                            }
+                            KeyCode::Esc => {
+                                app.cancel_preset_save();
+                            }
+                            _ => {}
+                        }
+                    }
+                    // Params file path prompt
+                    else if app.params_file_prompt.is_some() {
+                        match key.code {
+                            KeyCode::Char(c) => {
+                                app.edit_buffer.push(c);
+                            }
+                            KeyCode::Backspace => {
+                                app.edit_buffer.pop();
+                            }
+                            KeyCode::Tab => {
+                                app.complete_path();
+                            }
+                            KeyCode::Enter => {
+                                app.confirm_params_file_prompt();
+                            }
+                            KeyCode::Esc => {
+                                app.cancel_params_file_prompt();
+                            }
+                            _ => {}
+                        }
+                    }
+                    // Log export path prompt
+                    else if app.export_handle.is_some() {
+                        match key.code {
+                            KeyCode::Char(c) => {
+                                app.edit_buffer.push(c);
+                            }
+                            KeyCode::Backspace => {
+                                app.edit_buffer.pop();
+                            }
+                            KeyCode::Tab => {
+                                app.complete_path();
+                            }
+                            KeyCode::Enter => {
+                                app.confirm_log_export();
+                            }
+                            KeyCode::Esc => {
+                                app.cancel_log_export();
+                            }
+                            _ => {}
+                        }
+                    }
                     // Handle text input mode
                     else if app.is_editing {
                         match key.code {
@@ -181,13 +489,61 @@ fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, app: &mut A
                             }
                             _ => {}
                         }
+                    }
+                    // `z` then a digit: expand the phase tree to that depth
+                    else if app.awaiting_tree_depth {
+                        app.awaiting_tree_depth = false;
+                        if let KeyCode::Char(c @ '0'..='3') = key.code {
+                            app.expand_to_depth(c as usize - '0' as usize);
+                        } else if let Some(action) = app.keymap.resolve(&key, &app.current_view) {
+                            handle_action(app, action);
+                        }
+                    } else if app.chat.as_ref().is_some_and(|c| c.settings_draft.is_some()) {
+                        // Chat settings overlay
+                        match key.code {
+                            KeyCode::Char('m') => {
+                                if let Some(draft) =
+                                    app.chat.as_mut().and_then(|c| c.settings_draft.as_mut())
+                                {
+                                    draft.cycle_model();
+                                }
+                            }
+                            KeyCode::Char('r') => {
+                                if let Some(draft) =
+                                    app.chat.as_mut().and_then(|c| c.settings_draft.as_mut())
+                                {
+                                    draft.read_only = !draft.read_only;
+                                }
+                            }
+                            KeyCode::Enter => app.apply_chat_settings(),
+                            KeyCode::Esc => app.cancel_chat_settings(),
+                            _ => {}
+                        }
                     } else if matches!(app.current_view, View::Chat) {
                         // Chat input mode
+                        let responding = app.chat.as_ref().is_some_and(|c| c.waiting_for_response);
                         match key.code {
+                            KeyCode::Esc if responding => {
+                                // Esc: Stop the response being streamed
+                                if let Some(chat) = &mut app.chat {
+                                    chat.interrupt_response();
+                                }
+                            }
+                            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                // Ctrl+C: Stop the response, if any
+                                if let Some(chat) = &mut app.chat {
+                                    chat.interrupt_response();
+                                }
+                            }
                             KeyCode::Esc => {
                                 // Exit chat view
                                 app.current_view = View::Tabs;
                             }
+                            KeyCode::Char('?')
+                                if app.chat.as_ref().is_some_and(|c| c.input_buffer.is_empty()) =>
+                            {
+                                app.show_help = true;
+                            }
                             KeyCode::Char('q') | KeyCode::Char('Q')
                                 if key
                                     .modifiers
@@ -196,6 +552,22 @@ fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, app: &mut A
                                 // Ctrl+Q to quit
                                 app.should_quit = true;
                             }
+                            KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                // Ctrl+N: Start a fresh conversation
+                                if app.chat.as_ref().is_some_and(|c| c.waiting_for_response) {
+                                    app.notifications.warning(
+                                        "Chat Busy",
+                                        "Wait for the current response before starting a new conversation",
+                                    );
+                                } else if let Some(chat) = &mut app.chat {
+                                    chat.clear_conversation();
+                                    app.notifications.info("New Conversation", "Chat history cleared");
+                                }
+                            }
+                            KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                // Ctrl+O: Choose the model and tools
+                                app.open_chat_settings();
+                            }
                             KeyCode::Up if key.modifiers.contains(KeyModifiers::CONTROL) => {
                                 // Ctrl+Up: Navigate to older message in history
                                 if let Some(chat) = &mut app.chat {
@@ -208,6 +580,27 @@ fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, app: &mut A
                                     chat.history_next();
                                 }
                             }
+                            KeyCode::Up if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                                // Shift+Up: Select the previous tool call
+                                if let Some(chat) = &mut app.chat {
+                                    chat.select_tool_call(false);
+                                }
+                            }
+                            KeyCode::Down if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                                // Shift+Down: Select the next tool call
+                                if let Some(chat) = &mut app.chat {
+                                    chat.select_tool_call(true);
+                                }
+                            }
+                            KeyCode::Enter
+                                if app.chat.as_ref().is_some_and(|c| c.input_buffer.is_empty()) =>
+                            {
+                                // Enter on an empty input: Expand/collapse the
+                                // selected tool call
+                                if let Some(chat) = &mut app.chat {
+                                    chat.toggle_selected_tool_call();
+                                }
+                            }
                             KeyCode::Left => {
                                 // Move cursor left
                                 if let Some(chat) = &mut app.chat {
@@ -254,10 +647,26 @@ fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, app: &mut A
                                 }
                             }
                             KeyCode::Enter => {
+                                let input = app
+                                    .chat
+                                    .as_ref()
+                                    .map(|chat| chat.input_buffer.clone())
+                                    .unwrap_or_default();
+                                // Slash commands run locally, even before the
+                                // client is ready
+                                if app.run_chat_command(&input) {
+                                    if let Some(chat) = &mut app.chat {
+                                        chat.clear_input();
+                                    }
+                                }
                                 // Send message to Claude asynchronously
-                                if let Some(chat) = &mut app.chat {
+                                else if let Some(chat) = &mut app.chat {
                                     if !chat.input_buffer.is_empty() && chat.initialized {
-                                        let msg = chat.input_buffer.clone();
+                                        // `//` escapes a message starting with `/`
+                                        let msg = match chat.input_buffer.strip_prefix("//") {
+                                            Some(rest) => format!("/{}", rest),
+                                            None => chat.input_buffer.clone(),
+                                        };
                                         chat.input_buffer.clear();
                                         chat.cursor_position = 0;
 
@@ -296,272 +705,9 @@ fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, app: &mut A
                             }
                             _ => {}
                         }
-                    } else {
-                        // Normal navigation mode
-                        match key.code {
-                            KeyCode::Char('q') | KeyCode::Char('Q') => {
-                                app.should_quit = true;
-                            }
-                            KeyCode::Char('1') => {
-                                // 1: Switch to left pane (Structured Logs)
-                                if matches!(app.current_view, View::Tabs | View::WorkflowRunning(_)) {
-                                    app.switch_pane_left();
-                                }
-                            }
-                            KeyCode::Char('2') => {
-                                // 2: Switch to right pane (Raw Output)
-                                if matches!(app.current_view, View::Tabs | View::WorkflowRunning(_)) {
-                                    app.switch_pane_right();
-                                }
-                            }
-                            KeyCode::Down | KeyCode::Char('j') => {
-                                if matches!(app.current_view, View::WorkflowRunning(_)) {
-                                    // Check if raw output pane is focused
-                                    use crate::app::WorkflowPane;
-                                    if app.workflow_focused_pane == WorkflowPane::RawOutput {
-                                        app.scroll_raw_output_down();
-                                    } else {
-                                        app.navigate_workflow_down();
-                                        app.update_workflow_scroll(30); // Estimate viewport height
-                                    }
-                                } else if matches!(app.current_view, View::Tabs) {
-                                    // Check if raw output pane is focused in current tab
-                                    use crate::app::WorkflowPane;
-                                    if !app.open_tabs.is_empty()
-                                        && app.open_tabs[app.active_tab_idx].focused_pane == WorkflowPane::RawOutput {
-                                        app.scroll_raw_output_down();
-                                    } else {
-                                        app.navigate_tab_down();
-                                    }
-                                } else {
-                                    app.next();
-                                }
-                            }
-                            KeyCode::Up => {
-                                if matches!(app.current_view, View::WorkflowRunning(_)) {
-                                    // Check if raw output pane is focused
-                                    use crate::app::WorkflowPane;
-                                    if app.workflow_focused_pane == WorkflowPane::RawOutput {
-                                        app.scroll_raw_output_up();
-                                    } else {
-                                        app.navigate_workflow_up();
-                                        app.update_workflow_scroll(30); // Estimate viewport height
-                                    }
-                                } else if matches!(app.current_view, View::Tabs) {
-                                    // Check if raw output pane is focused in current tab
-                                    use crate::app::WorkflowPane;
-                                    if !app.open_tabs.is_empty()
-                                        && app.open_tabs[app.active_tab_idx].focused_pane == WorkflowPane::RawOutput {
-                                        app.scroll_raw_output_up();
-                                    } else {
-                                        app.navigate_tab_up();
-                                    }
-                                } else {
-                                    app.previous();
-                                }
-                            }
-                            KeyCode::Char('k') => {
-                                if matches!(app.current_view, View::WorkflowRunning(_)) {
-                                    // Check if raw output pane is focused
-                                    use crate::app::WorkflowPane;
-                                    if app.workflow_focused_pane == WorkflowPane::RawOutput {
-                                        app.scroll_raw_output_up();
-                                    } else {
-                                        app.navigate_workflow_up();
-                                        app.update_workflow_scroll(30); // Estimate viewport height
-                                    }
-                                } else if matches!(app.current_view, View::Tabs) {
-                                    // Check if raw output pane is focused in current tab
-                                    use crate::app::WorkflowPane;
-                                    if !app.open_tabs.is_empty()
-                                        && app.open_tabs[app.active_tab_idx].focused_pane == WorkflowPane::RawOutput {
-                                        app.scroll_raw_output_up();
-                                    } else {
-                                        app.navigate_tab_up();
-                                    }
-                                } else {
-                                    app.previous();
-                                }
-                            }
-                            KeyCode::Char('K') => {
-                                // K: Kill workflow (in Tabs view)
-                                if matches!(app.current_view, View::Tabs) {
-                                    app.kill_current_tab();
-                                }
-                            }
-                            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                                // Ctrl+D: Scroll down in raw output (half page)
-                                if matches!(app.current_view, View::WorkflowRunning(_)) {
-                                    use crate::app::WorkflowPane;
-                                    if app.workflow_focused_pane == WorkflowPane::RawOutput {
-                                        // Scroll down by half page (assuming ~15 lines)
-                                        for _ in 0..15 {
-                                            app.scroll_raw_output_down();
-                                        }
-                                    }
-                                } else if matches!(app.current_view, View::Tabs) {
-                                    use crate::app::WorkflowPane;
-                                    if !app.open_tabs.is_empty()
-                                        && app.open_tabs[app.active_tab_idx].focused_pane == WorkflowPane::RawOutput {
-                                        // Scroll down by half page (assuming ~15 lines)
-                                        for _ in 0..15 {
-                                            app.scroll_raw_output_down();
-                                        }
-                                    }
-                                }
-                            }
-                            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                                // Ctrl+U: Scroll up in raw output (half page)
-                                if matches!(app.current_view, View::WorkflowRunning(_)) {
-                                    use crate::app::WorkflowPane;
-                                    if app.workflow_focused_pane == WorkflowPane::RawOutput {
-                                        // Scroll up by half page (assuming ~15 lines)
-                                        for _ in 0..15 {
-                                            app.scroll_raw_output_up();
-                                        }
-                                    }
-                                } else if matches!(app.current_view, View::Tabs) {
-                                    use crate::app::WorkflowPane;
-                                    if !app.open_tabs.is_empty()
-                                        && app.open_tabs[app.active_tab_idx].focused_pane == WorkflowPane::RawOutput {
-                                        // Scroll up by half page (assuming ~15 lines)
-                                        for _ in 0..15 {
-                                            app.scroll_raw_output_up();
-                                        }
-                                    }
-                                }
-                            }
-                            KeyCode::Enter => {
-                                match app.current_view {
-                                    View::WorkflowList => app.view_workflow(),
-                                    View::WorkflowEdit(_) => app.start_editing_field(),
-                                    View::WorkflowRunning(_) => {
-                                        app.toggle_selected_item();
-                                        app.update_workflow_scroll(30); // Estimate viewport height
-                                    }
-                                    View::Tabs => app.toggle_tab_item(),
-                                    _ => {}
-                                }
-                            }
-                            KeyCode::Char(' ') => {
-                                if matches!(app.current_view, View::WorkflowRunning(_)) {
-                                    app.toggle_expand_all();
-                                    app.update_workflow_scroll(30); // Estimate viewport height
-                                } else if matches!(app.current_view, View::Tabs) {
-                                    app.toggle_tab_expand_all();
-                                }
-                            }
-                            KeyCode::PageUp | KeyCode::Left | KeyCode::Char('h') => {
-                                if matches!(app.current_view, View::Tabs) {
-                                    app.scroll_agent_messages_up();
-                                }
-                            }
-                            KeyCode::PageDown | KeyCode::Right => {
-                                if matches!(app.current_view, View::Tabs) {
-                                    app.scroll_agent_messages_down();
-                                }
-                            }
-                            KeyCode::Char('v') => {
-                                if matches!(app.current_view, View::WorkflowList) {
-                                    app.view_workflow();
-                                }
-                            }
-                            KeyCode::Char('e') | KeyCode::Char('E') => {
-                                if matches!(app.current_view, View::WorkflowDetail(_)) {
-                                    app.edit_workflow();
-                                } else if matches!(app.current_view, View::Tabs) {
-                                    app.edit_current_tab();
-                                }
-                            }
-                            KeyCode::Char('d') => {
-                                // d: Delete/clear current field value in WorkflowEdit view
-                                if matches!(app.current_view, View::WorkflowEdit(_)) {
-                                    app.delete_current_field();
-                                }
-                            }
-                            KeyCode::Char('l') | KeyCode::Char('L') => match app.current_view {
-                                View::WorkflowDetail(_) | View::WorkflowEdit(_) => {
-                                    app.launch_workflow_in_tab();
-                                }
-                                View::Tabs => {
-                                    app.scroll_agent_messages_down();
-                                }
-                                _ => {}
-                            },
-                            KeyCode::Char('3') => {
-                                if matches!(app.current_view, View::WorkflowRunning(_)) {
-                                    app.toggle_expand_agents();
-                                }
-                            }
-                            KeyCode::Tab => {
-                                // Tab navigation forward
-                                if matches!(app.current_view, View::Tabs) {
-                                    app.next_tab();
-                                }
-                            }
-                            KeyCode::BackTab => {
-                                // Shift+Tab navigation backward
-                                if matches!(app.current_view, View::Tabs) {
-                                    app.previous_tab();
-                                }
-                            }
-                            KeyCode::Char('t') | KeyCode::Char('T') => {
-                                if key
-                                    .modifiers
-                                    .contains(crossterm::event::KeyModifiers::CONTROL)
-                                {
-                                    // Ctrl+T: New tab - enter workflow selection mode
-                                    app.in_new_tab_flow = true;
-                                    app.current_view = View::WorkflowList;
-                                    app.field_values.clear();
-                                    app.selected = 0;
-                                }
-                            }
-                            KeyCode::Char('w') | KeyCode::Char('W') => {
-                                if key
-                                    .modifiers
-                                    .contains(crossterm::event::KeyModifiers::CONTROL)
-                                {
-                                    // Ctrl+W: Close tab
-                                    if matches!(app.current_view, View::Tabs) {
-                                        app.close_current_tab();
-                                    }
-                                }
-                            }
-                            KeyCode::Char('c') | KeyCode::Char('C') => {
-                                // C: Close tab (in Tabs view)
-                                if matches!(app.current_view, View::Tabs) {
-                                    app.close_current_tab();
-                                }
-                            }
-                            KeyCode::Char('r') | KeyCode::Char('R') => {
-                                // R: Rerun workflow (in Tabs view)
-                                if matches!(app.current_view, View::Tabs) {
-                                    app.rerun_current_tab();
-                                }
-                            }
-                            KeyCode::Char('a') | KeyCode::Char('A') => {
-                                // A: Open AI chat interface
-                                if matches!(app.current_view, View::Tabs) {
-                                    app.open_chat();
-                                }
-                            }
-                            KeyCode::Esc | KeyCode::Char('b') => {
-                                // If in chat view, go back to Tabs
-                                if matches!(app.current_view, View::Chat) {
-                                    app.current_view = View::Tabs;
-                                }
-                                // If in new tab flow, return to Tabs view
-                                else if app.in_new_tab_flow {
-                                    app.in_new_tab_flow = false;
-                                    app.current_view = View::Tabs;
-                                    app.field_values.clear();
-                                } else if !matches!(app.current_view, View::WorkflowList) {
-                                    app.back_to_list();
-                                }
-                            }
-                            _ => {}
-                        }
+                    } else if let Some(action) = app.keymap.resolve(&key, &app.current_view) {
+                        // Normal mode: keys go through the configurable keymap
+                        handle_action(app, action);
                     }
                 }
             }
@@ -581,3 +727,189 @@ fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, app: &mut A
     }
     Ok(())
 }
+
+/// Perform a normal-mode action resolved from the keymap
+fn handle_action(app: &mut App, action: Action) {
+    use crate::app::WorkflowPane;
+
+    let raw_output_focused = match app.current_view {
+        View::WorkflowRunning(_) => app.workflow_focused_pane == WorkflowPane::RawOutput,
+        View::Tabs => app
+            .open_tabs
+            .get(app.active_tab_idx)
+            .is_some_and(|tab| tab.focused_pane == WorkflowPane::RawOutput),
+        _ => false,
+    };
+
+    match action {
+        Action::Quit => {
+            app.should_quit = true;
+        }
+        Action::Help => {
+            app.show_help = true;
+        }
+        Action::NavigateDown => match app.current_view {
+            View::WorkflowRunning(_) | View::Tabs if raw_output_focused => {
+                app.scroll_raw_output_down();
+            }
+            View::WorkflowRunning(_) => {
+                app.navigate_workflow_down();
+                app.update_follow();
+                app.update_workflow_scroll(30); // Estimate viewport height
+            }
+            View::Tabs => {
+                app.navigate_tab_down();
+                app.update_follow();
+                app.update_workflow_scroll(30); // Estimate viewport height
+            }
+            View::History => app.execution_history_next(),
+            _ => app.next(),
+        },
+        Action::NavigateUp => match app.current_view {
+            View::WorkflowRunning(_) | View::Tabs if raw_output_focused => {
+                app.scroll_raw_output_up();
+            }
+            View::WorkflowRunning(_) => {
+                app.navigate_workflow_up();
+                app.update_follow();
+                app.update_workflow_scroll(30); // Estimate viewport height
+            }
+            View::Tabs => {
+                app.navigate_tab_up();
+                app.update_follow();
+                app.update_workflow_scroll(30); // Estimate viewport height
+            }
+            View::History => app.execution_history_previous(),
+            _ => app.previous(),
+        },
+        Action::Select => match app.current_view {
+            View::WorkflowList => app.view_workflow(),
+            View::WorkflowEdit(_) => app.start_editing_field(),
+            View::WorkflowRunning(_) => {
+                app.toggle_selected_item();
+                app.update_workflow_scroll(30); // Estimate viewport height
+            }
+            View::Tabs => {
+                app.toggle_tab_item();
+                app.update_workflow_scroll(30); // Estimate viewport height
+            }
+            View::History => app.open_execution_history_entry(),
+            _ => {}
+        },
+        Action::Back => {
+            if matches!(app.current_view, View::History) {
+                app.close_execution_history();
+            }
+            // If in new tab flow, return to Tabs view
+            else if app.in_new_tab_flow {
+                app.in_new_tab_flow = false;
+                app.current_view = View::Tabs;
+                app.field_values.clear();
+            } else if !matches!(app.current_view, View::WorkflowList) {
+                app.back_to_list();
+            }
+        }
+        Action::Toggle => match app.current_view {
+            View::WorkflowRunning(_) => {
+                app.toggle_expand_all();
+                app.update_workflow_scroll(30); // Estimate viewport height
+            }
+            View::Tabs => {
+                app.toggle_tab_expand_all();
+                app.update_workflow_scroll(30); // Estimate viewport height
+            }
+            View::WorkflowEdit(_) => {
+                app.toggle_boolean_field();
+            }
+            _ => {}
+        },
+        Action::ExpandToDepth => app.awaiting_tree_depth = true,
+        Action::ViewWorkflow => app.view_workflow(),
+        Action::ReloadWorkflows => app.reload_workflows(),
+        Action::SavePreset => app.open_preset_save(),
+        Action::LoadPreset => app.show_preset_dropdown(),
+        Action::SaveParamsFile => app.open_params_file_prompt(app::ParamsFileAction::Save),
+        Action::LoadParamsFile => app.open_params_file_prompt(app::ParamsFileAction::Load),
+        Action::ToggleFieldGroup => app.toggle_field_group(),
+        Action::Edit => match app.current_view {
+            View::WorkflowDetail(_) => app.edit_workflow(),
+            View::Tabs => app.edit_current_tab(),
+            View::History => app.edit_execution_history_entry(),
+            _ => {}
+        },
+        Action::ClearField => app.delete_current_field(),
+        Action::ClearHistory => app.clear_field_history(),
+        Action::Launch => app.launch_workflow_in_tab(),
+        Action::PreviewCommand => app.preview_command(),
+        Action::FocusStructuredLogs => app.switch_pane_left(),
+        Action::FocusRawOutput => app.switch_pane_right(),
+        Action::ToggleAgentDetails => app.toggle_expand_agents(),
+        Action::HalfPageDown => {
+            if raw_output_focused {
+                // Scroll down by half page (assuming ~15 lines)
+                for _ in 0..15 {
+                    app.scroll_raw_output_down();
+                }
+            } else {
+                app.page_tree(true);
+            }
+        }
+        Action::HalfPageUp => {
+            if raw_output_focused {
+                // Scroll up by half page (assuming ~15 lines)
+                for _ in 0..15 {
+                    app.scroll_raw_output_up();
+                }
+            } else {
+                app.page_tree(false);
+            }
+        }
+        Action::FirstRow => app.select_tree_edge(false),
+        Action::LastRow => app.select_tree_edge(true),
+        Action::ScrollLeft => app.scroll_tree_horizontally(false),
+        Action::ScrollRight => app.scroll_tree_horizontally(true),
+        Action::ScrollMessagesUp => app.scroll_agent_messages_up(),
+        Action::ScrollMessagesDown => app.scroll_agent_messages_down(),
+        Action::NextPage => app.execution_history_next_page(),
+        Action::PreviousPage => app.execution_history_previous_page(),
+        Action::NextTab => app.next_tab(),
+        Action::PreviousTab => app.previous_tab(),
+        Action::MoveTabLeft => app.move_tab_left(),
+        Action::MoveTabRight => app.move_tab_right(),
+        Action::NewTab => {
+            // Enter workflow selection mode
+            app.in_new_tab_flow = true;
+            app.current_view = View::WorkflowList;
+            app.field_values.clear();
+            app.selected = 0;
+        }
+        Action::CloseTab => app.close_current_tab(),
+        Action::KillTab => match app.current_view {
+            View::Tabs => app.kill_current_tab(),
+            View::WorkflowRunning(_) => app.cancel_running_workflow(),
+            _ => {}
+        },
+        Action::TogglePause => app.toggle_pause_current_tab(),
+        Action::CancelTask => app.cancel_selected_task(),
+        Action::Rerun => app.rerun_current_tab(),
+        Action::OpenChat => app.open_chat(),
+        Action::RawJson => app.toggle_raw_json_view(),
+        Action::OpenHistory => {
+            app.in_new_tab_flow = false;
+            app.open_execution_history();
+        }
+        Action::CycleLogFilter => app.cycle_log_filter(),
+        Action::Search => app.open_search(),
+        Action::NextMatch => app.jump_to_match(true),
+        Action::PreviousMatch => app.jump_to_match(false),
+        Action::JumpToFailed => app.jump_to_failed(),
+        Action::ToggleFollow => app.toggle_follow(),
+        Action::CopyOutput => match app.current_view {
+            View::History => app.copy_execution_history_command(),
+            _ => app.copy_selected_output(),
+        },
+        Action::ExportLogs => app.open_log_export(),
+        Action::OpenOutputFile => app.open_output_file(),
+        Action::OpenArtifact => app.open_result_artifact(),
+    }
+}
@@ -3,32 +3,37 @@ use serde_json::json;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use uuid::Uuid;
-use workflow_manager_sdk::WorkflowRuntime;
+use workflow_manager_sdk::{WorkflowError, WorkflowRuntime};
 
 use crate::models::WorkflowHistory;
 use crate::app::{AppCommand, NotificationLevel, TaskRegistry};
 
-/// Create the workflow manager MCP server with all tools
+/// Create the workflow manager MCP server with all tools, or without those
+/// that launch and cancel workflows when `read_only`
 pub fn create_workflow_mcp_server(
     runtime: Arc<dyn WorkflowRuntime>,
     history: Arc<Mutex<WorkflowHistory>>,
     command_tx: tokio::sync::mpsc::UnboundedSender<AppCommand>,
     task_registry: TaskRegistry,
+    read_only: bool,
 ) -> SdkMcpServer {
-    SdkMcpServer::new("workflow_manager")
+    let server = SdkMcpServer::new("workflow_manager")
         .version("1.0.0")
         .tool(list_workflows_tool(runtime.clone()))
-        .tool(execute_workflow_tool(
-            runtime.clone(),
-            command_tx.clone(),
-            task_registry.clone(),
-        ))
+        .tool(get_workflow_schema_tool(runtime.clone()))
         .tool(get_workflow_logs_tool(runtime.clone()))
+        .tool(get_logs_since_tool(runtime.clone()))
         .tool(get_workflow_status_tool(runtime.clone()))
-        .tool(cancel_workflow_tool(runtime.clone()))
+        .tool(list_running_workflows_tool(runtime.clone()))
         .tool(list_execution_history_tool(runtime.clone()))
-        .tool(get_execution_params_tool(runtime))
-        .tool(get_workflow_history_tool(history))
+        .tool(get_execution_params_tool(runtime.clone()))
+        .tool(get_workflow_history_tool(history));
+    if read_only {
+        return server;
+    }
+    server
+        .tool(execute_workflow_tool(runtime.clone(), command_tx, task_registry))
+        .tool(cancel_workflow_tool(runtime))
 }
 
 /// Tool: list_workflows
@@ -55,6 +60,44 @@ fn list_workflows_tool(runtime: Arc<dyn WorkflowRuntime>) -> SdkMcpTool {
     )
 }
 
+/// Tool: get_workflow_schema
+fn get_workflow_schema_tool(runtime: Arc<dyn WorkflowRuntime>) -> SdkMcpTool {
+    SdkMcpTool::new(
+        "get_workflow_schema",
+        "Get the input fields of a workflow: names, types, labels, whether they are required, and for which phases. Use the field names as execute_workflow params.",
+        json!({
+            "type": "object",
+            "properties": {
+                "workflow_id": {
+                    "type": "string",
+                    "description": "The ID of the workflow (from list_workflows)"
+                }
+            },
+            "required": ["workflow_id"]
+        }),
+        move |params| {
+            let runtime = runtime.clone();
+            Box::pin(async move {
+                let workflow_id = match params.get("workflow_id").and_then(|v| v.as_str()) {
+                    Some(id) => id,
+                    None => return Ok(ToolResult::error("Missing workflow_id")),
+                };
+
+                match runtime.get_workflow_metadata(workflow_id) {
+                    Ok(metadata) => match serde_json::to_string_pretty(&metadata) {
+                        Ok(json) => Ok(ToolResult::text(json)),
+                        Err(e) => Ok(ToolResult::error(format!("Serialization error: {}", e))),
+                    },
+                    Err(e) => Ok(ToolResult::error(format!(
+                        "Failed to get workflow schema: {}",
+                        e
+                    ))),
+                }
+            })
+        },
+    )
+}
+
 /// Tool: execute_workflow
 fn execute_workflow_tool(
     runtime: Arc<dyn WorkflowRuntime>,
@@ -63,7 +106,8 @@ fn execute_workflow_tool(
 ) -> SdkMcpTool {
     SdkMcpTool::new(
         "execute_workflow",
-        "Execute a workflow with provided parameters. Creates a tab in the TUI and streams logs in real-time.",
+        "Execute a workflow with provided parameters. Creates a tab in the TUI and streams logs in real-time. \
+         The reserved 'timeout' parameter kills the workflow after that many seconds without new events.",
         json!({
             "type": "object",
             "properties": {
@@ -153,8 +197,10 @@ fn execute_workflow_tool(
                                     }
                                 }
 
-                                // When logs stream ends, update status
-                                if let Ok(status) = runtime_clone.get_status(&handle_id).await {
+                                // The log stream can close before the exit is recorded
+                                if let Ok(status) =
+                                    runtime_clone.wait_for_completion(&handle_id).await
+                                {
                                     let _ = command_tx_clone.send(AppCommand::UpdateTabStatus {
                                         handle_id,
                                         status,
@@ -177,6 +223,15 @@ fn execute_workflow_tool(
                             serde_json::to_string_pretty(&result).unwrap(),
                         ))
                     }
+                    // Bad params are for the caller to fix, not for the TUI user
+                    Err(WorkflowError::Validation(problems)) => Ok(ToolResult::error(format!(
+                        "Invalid params (see get_workflow_schema):\n  - {}",
+                        problems
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join("\n  - ")
+                    ))),
                     Err(e) => {
                         // Send error notification
                         let _ = command_tx.send(AppCommand::ShowNotification {
@@ -235,6 +290,59 @@ fn get_workflow_logs_tool(runtime: Arc<dyn WorkflowRuntime>) -> SdkMcpTool {
     )
 }
 
+/// Tool: get_logs_since
+fn get_logs_since_tool(runtime: Arc<dyn WorkflowRuntime>) -> SdkMcpTool {
+    SdkMcpTool::new(
+        "get_logs_since",
+        "Follow a workflow execution: get only the log events after after_index, plus latest_index to pass as after_index on the next call and the current status. Stop polling once the status is Completed or Failed.",
+        json!({
+            "type": "object",
+            "properties": {
+                "handle_id": {"type": "string"},
+                "after_index": {
+                    "type": "integer",
+                    "description": "latest_index from the previous call; omit to start from the first event"
+                }
+            },
+            "required": ["handle_id"]
+        }),
+        move |params| {
+            let runtime = runtime.clone();
+            Box::pin(async move {
+                let handle_id_str = match params.get("handle_id").and_then(|v| v.as_str()) {
+                    Some(id) => id,
+                    None => return Ok(ToolResult::error("Missing handle_id")),
+                };
+
+                let handle_id = match Uuid::parse_str(handle_id_str) {
+                    Ok(id) => id,
+                    Err(e) => return Ok(ToolResult::error(format!("Invalid UUID: {}", e))),
+                };
+
+                let after_index = params
+                    .get("after_index")
+                    .and_then(|v| v.as_u64())
+                    .map(|v| v as usize);
+
+                match runtime.get_logs_since(&handle_id, after_index).await {
+                    Ok(page) => {
+                        let result = json!({
+                            "handle_id": handle_id.to_string(),
+                            "status": format!("{:?}", page.status),
+                            "latest_index": page.latest_index,
+                            "logs": page.logs
+                        });
+                        Ok(ToolResult::text(
+                            serde_json::to_string_pretty(&result).unwrap(),
+                        ))
+                    }
+                    Err(e) => Ok(ToolResult::error(format!("Failed to get logs: {}", e))),
+                }
+            })
+        },
+    )
+}
+
 /// Tool: get_workflow_status
 fn get_workflow_status_tool(runtime: Arc<dyn WorkflowRuntime>) -> SdkMcpTool {
     SdkMcpTool::new(
@@ -277,6 +385,41 @@ fn get_workflow_status_tool(runtime: Arc<dyn WorkflowRuntime>) -> SdkMcpTool {
     )
 }
 
+/// Tool: list_running_workflows
+fn list_running_workflows_tool(runtime: Arc<dyn WorkflowRuntime>) -> SdkMcpTool {
+    SdkMcpTool::new(
+        "list_running_workflows",
+        "List the workflow executions that are queued, running or paused, oldest first",
+        json!({"type": "object", "properties": {}}),
+        move |_params| {
+            let runtime = runtime.clone();
+            Box::pin(async move {
+                match runtime.list_running().await {
+                    Ok(running) => {
+                        let result = json!({
+                            "total": running.len(),
+                            "executions": running.iter().map(|(id, workflow_id, status)| {
+                                json!({
+                                    "handle_id": id.to_string(),
+                                    "workflow_id": workflow_id,
+                                    "status": format!("{:?}", status),
+                                })
+                            }).collect::<Vec<_>>()
+                        });
+                        Ok(ToolResult::text(
+                            serde_json::to_string_pretty(&result).unwrap(),
+                        ))
+                    }
+                    Err(e) => Ok(ToolResult::error(format!(
+                        "Failed to list running workflows: {}",
+                        e
+                    ))),
+                }
+            })
+        },
+    )
+}
+
 /// Tool: cancel_workflow
 fn cancel_workflow_tool(runtime: Arc<dyn WorkflowRuntime>) -> SdkMcpTool {
     SdkMcpTool::new(
@@ -377,6 +520,9 @@ fn list_execution_history_tool(runtime: Arc<dyn WorkflowRuntime>) -> SdkMcpTool
                                     "start_time": exec.start_time.to_rfc3339(),
                                     "end_time": exec.end_time.map(|t| t.to_rfc3339()),
                                     "exit_code": exec.exit_code,
+                                    "input_tokens": exec.input_tokens,
+                                    "output_tokens": exec.output_tokens,
+                                    "total_cost_usd": exec.total_cost_usd,
                                 })
                             }).collect::<Vec<_>>()
                         });
@@ -522,7 +668,8 @@ mod tests {
         let history = Arc::new(Mutex::new(WorkflowHistory::default()));
         let (command_tx, _command_rx) = tokio::sync::mpsc::unbounded_channel();
         let task_registry = TaskRegistry::new();
-        let server = create_workflow_mcp_server(runtime, history, command_tx, task_registry);
+        let server =
+            create_workflow_mcp_server(runtime, history, command_tx, task_registry, false);
         println!("MCP Server created: {}", server.name());
     }
 }
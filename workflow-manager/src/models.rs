@@ -4,5 +4,6 @@
 
 // Re-export all public items from the app module
 pub use crate::app::{
-    AgentStatus, App, PhaseStatus, TaskStatus, View, WorkflowHistory, WorkflowPhase, WorkflowTab,
+    AgentStatus, App, LogLine, PhaseStatus, TaskStatus, View, WorkflowHistory, WorkflowPhase,
+    WorkflowTab,
 };
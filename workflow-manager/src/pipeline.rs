@@ -0,0 +1,704 @@
+//! Pipeline workflows: run several workflows in sequence
+//!
+//! A pipeline is declared as a JSON file (see `PipelineDefinition`) listing
+//! sub-workflow ids and how each stage's inputs are wired from pipeline
+//! parameters or from state files produced by earlier stages.
+//!
+//! Each stage is surfaced as a phase of the pipeline execution. Task and agent
+//! events of the stage are re-parented under that phase; the stage's own phase
+//! events are kept as raw output lines.
+
+use anyhow::{anyhow, Result};
+use chrono::Local;
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use tokio::io::BufReader;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+use workflow_manager_sdk::{
+    event_prefix, EventDecoder, PipelineDefinition, PipelineStage, StageInput, StderrLine,
+    WorkflowLog, WorkflowStatus,
+};
+
+use crate::database::Database;
+use crate::discovery::DiscoveredWorkflow;
+use crate::runtime::{
+    build_command, kill_process_tree, publish_log, record_usage, start_control_writer,
+    ExecutionState, LossyLines,
+};
+use crate::utils::{redact_log, redact_secrets, secret_values};
+
+/// Outcome of running a single pipeline stage
+enum StageOutcome {
+    Succeeded,
+    Failed(String),
+    Cancelled,
+}
+
+/// Resolve the parameters for a stage from its input mappings
+///
+/// `state_files[i]` holds the `(path, description)` pairs emitted by stage `i`.
+pub fn resolve_stage_params(
+    stage: &PipelineStage,
+    pipeline_params: &HashMap<String, String>,
+    state_files: &[Vec<(String, String)>],
+) -> Result<HashMap<String, String>> {
+    let mut params = HashMap::new();
+
+    for (field, input) in &stage.inputs {
+        let value = match input {
+            StageInput::Value { value } => value.clone(),
+            StageInput::Param { name } => pipeline_params
+                .get(name)
+                .cloned()
+                .ok_or_else(|| anyhow!("Pipeline parameter '{}' not provided", name))?,
+            StageInput::StateFile { stage, description } => {
+                let files = state_files.get(*stage).ok_or_else(|| {
+                    anyhow!(
+                        "Input '{}' references stage {} which has not run yet",
+                        field,
+                        stage
+                    )
+                })?;
+                files
+                    .iter()
+                    .rev()
+                    .find(|(_, desc)| {
+                        description
+                            .as_ref()
+                            .map(|d| desc.contains(d.as_str()))
+                            .unwrap_or(true)
+                    })
+                    .map(|(path, _)| path.clone())
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "Stage {} produced no matching state file for '{}'",
+                            stage,
+                            field
+                        )
+                    })?
+            }
+        };
+        params.insert(field.clone(), value);
+    }
+
+    Ok(params)
+}
+
+/// Parameters of a stage as far as they are known before the pipeline runs
+///
+/// State files of earlier stages are shown as `<stage N state file>`.
+pub fn preview_stage_params(
+    stage: &PipelineStage,
+    pipeline_params: &HashMap<String, String>,
+) -> Result<HashMap<String, String>> {
+    stage
+        .inputs
+        .iter()
+        .map(|(field, input)| {
+            let value = match input {
+                StageInput::Value { value } => value.clone(),
+                StageInput::Param { name } => pipeline_params
+                    .get(name)
+                    .cloned()
+                    .ok_or_else(|| anyhow!("Pipeline parameter '{}' not provided", name))?,
+                StageInput::StateFile { stage, .. } => format!("<stage {} state file>", stage),
+            };
+            Ok((field.clone(), value))
+        })
+        .collect()
+}
+
+/// Re-parent a sub-workflow event under the phase of its pipeline stage
+///
+/// Task ids are prefixed with the stage index so identically named tasks in
+/// different stages stay distinct in the tree.
+pub fn remap_stage_event(stage_idx: usize, stage_name: &str, log: WorkflowLog) -> WorkflowLog {
+    let task_key = |task_id: String| format!("stage{}_{}", stage_idx, task_id);
+
+    match log {
+        WorkflowLog::PhaseStarted { phase, name, .. } => WorkflowLog::RawOutput {
+            stream: "stderr".to_string(),
+            line: format!("[{}] Phase {} started: {}", stage_name, phase, name),
+        },
+        WorkflowLog::PhaseCompleted { phase, name } => WorkflowLog::RawOutput {
+            stream: "stderr".to_string(),
+            line: format!("[{}] Phase {} completed: {}", stage_name, phase, name),
+        },
+        WorkflowLog::PhaseFailed { phase, name, error } => WorkflowLog::RawOutput {
+            stream: "stderr".to_string(),
+            line: format!(
+                "[{}] Phase {} failed: {} ({})",
+                stage_name, phase, name, error
+            ),
+        },
+        WorkflowLog::TaskStarted {
+            phase: _,
+            task_id,
+            description,
+            total_tasks,
+        } => WorkflowLog::TaskStarted {
+            phase: stage_idx,
+            task_id: task_key(task_id),
+            description,
+            total_tasks,
+        },
+        WorkflowLog::TaskProgress { task_id, message } => WorkflowLog::TaskProgress {
+            task_id: task_key(task_id),
+            message,
+        },
+        WorkflowLog::TaskCompleted { task_id, result } => WorkflowLog::TaskCompleted {
+            task_id: task_key(task_id),
+            result,
+        },
+        WorkflowLog::TaskFailed { task_id, error } => WorkflowLog::TaskFailed {
+            task_id: task_key(task_id),
+            error,
+        },
+        WorkflowLog::AgentStarted {
+            task_id,
+            agent_name,
+            description,
+            parent_agent,
+        } => WorkflowLog::AgentStarted {
+            task_id: task_key(task_id),
+            agent_name,
+            description,
+            parent_agent,
+        },
+        WorkflowLog::AgentMessage {
+            task_id,
+            agent_name,
+            message,
+            parent_agent,
+        } => WorkflowLog::AgentMessage {
+            task_id: task_key(task_id),
+            agent_name,
+            message,
+            parent_agent,
+        },
+        WorkflowLog::AgentCompleted {
+            task_id,
+            agent_name,
+            result,
+        } => WorkflowLog::AgentCompleted {
+            task_id: task_key(task_id),
+            agent_name,
+            result,
+        },
+        WorkflowLog::AgentFailed {
+            task_id,
+            agent_name,
+            error,
+        } => WorkflowLog::AgentFailed {
+            task_id: task_key(task_id),
+            agent_name,
+            error,
+        },
+        WorkflowLog::StateFileCreated {
+            phase: _,
+            file_path,
+            description,
+        } => WorkflowLog::StateFileCreated {
+            phase: stage_idx,
+            file_path,
+            description,
+        },
+        WorkflowLog::Metric {
+            task_id,
+            name,
+            value,
+            unit,
+        } => WorkflowLog::Metric {
+            task_id: task_key(task_id),
+            name,
+            value,
+            unit,
+        },
+        // Raw output, usage, results and events added later pass through
+        other => other,
+    }
+}
+
+/// Broadcasts, buffers and batch-persists the logs of a pipeline execution
+struct LogRecorder {
+    exec_id: Uuid,
+    logs_tx: broadcast::Sender<WorkflowLog>,
+    logs_buffer: Arc<Mutex<Vec<WorkflowLog>>>,
+    executions: Arc<Mutex<HashMap<Uuid, ExecutionState>>>,
+    database: Arc<Mutex<Database>>,
+    pending: Vec<(usize, WorkflowLog)>,
+    last_flush: std::time::Instant,
+}
+
+impl LogRecorder {
+    fn record(&mut self, log: WorkflowLog) {
+        record_usage(&self.exec_id, &log, &self.executions, &self.database);
+        let Some(sequence) = publish_log(&self.logs_buffer, &self.logs_tx, log.clone()) else {
+            return;
+        };
+        self.pending.push((sequence, log));
+
+        if self.pending.len() >= 50 || self.last_flush.elapsed() > std::time::Duration::from_secs(5)
+        {
+            self.flush();
+        }
+    }
+
+    fn flush(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+        let db = self.database.lock().unwrap();
+        if let Err(e) = db.batch_insert_logs(&self.exec_id, &self.pending) {
+            eprintln!("Warning: Failed to batch insert logs: {}", e);
+        }
+        self.pending.clear();
+        self.last_flush = std::time::Instant::now();
+    }
+}
+
+/// Run all stages of a pipeline and update the execution's final status
+pub async fn run_pipeline(
+    exec_id: Uuid,
+    pipeline: PipelineDefinition,
+    params: HashMap<String, String>,
+    workflows: Arc<Mutex<HashMap<String, DiscoveredWorkflow>>>,
+    executions: Arc<Mutex<HashMap<Uuid, ExecutionState>>>,
+    database: Arc<Mutex<Database>>,
+) -> Result<()> {
+    let (logs_tx, logs_buffer) = {
+        let execs = executions.lock().unwrap();
+        let state = execs
+            .get(&exec_id)
+            .ok_or_else(|| anyhow!("Execution not found"))?;
+        (state.logs_tx.clone(), state.logs_buffer.clone())
+    };
+
+    let mut recorder = LogRecorder {
+        exec_id,
+        logs_tx,
+        logs_buffer,
+        executions: executions.clone(),
+        database: database.clone(),
+        pending: Vec::new(),
+        last_flush: std::time::Instant::now(),
+    };
+
+    let total_stages = pipeline.stages.len();
+    let mut state_files: Vec<Vec<(String, String)>> = Vec::new();
+    let mut any_failed = false;
+    let mut cancelled = false;
+
+    for (idx, stage) in pipeline.stages.iter().enumerate() {
+        // Cancelled between two stages, when there was no child to kill
+        if is_cancelled(executions.lock().unwrap().get(&exec_id)) {
+            cancelled = true;
+            break;
+        }
+
+        let workflow = workflows.lock().unwrap().get(&stage.workflow_id).cloned();
+        let stage_name = stage.name.clone().unwrap_or_else(|| {
+            workflow
+                .as_ref()
+                .map(|w| w.metadata.name.clone())
+                .unwrap_or_else(|| stage.workflow_id.clone())
+        });
+
+        recorder.record(WorkflowLog::PhaseStarted {
+            phase: idx,
+            name: stage_name.clone(),
+            total_phases: total_stages,
+        });
+        state_files.push(Vec::new());
+
+        let outcome = match workflow {
+            Some(workflow) if workflow.is_pipeline() => StageOutcome::Failed(format!(
+                "Stage workflow '{}' is itself a pipeline; nesting is not supported",
+                stage.workflow_id
+            )),
+            Some(workflow) => match resolve_stage_params(stage, &params, &state_files) {
+                Ok(stage_params) => {
+                    run_stage(
+                        exec_id,
+                        idx,
+                        &stage_name,
+                        &workflow,
+                        stage_params,
+                        &executions,
+                        &mut recorder,
+                        &mut state_files[idx],
+                    )
+                    .await
+                }
+                Err(e) => StageOutcome::Failed(e.to_string()),
+            },
+            None => StageOutcome::Failed(format!("Workflow '{}' not found", stage.workflow_id)),
+        };
+
+        match outcome {
+            StageOutcome::Succeeded => {
+                recorder.record(WorkflowLog::PhaseCompleted {
+                    phase: idx,
+                    name: stage_name,
+                });
+            }
+            StageOutcome::Failed(error) => {
+                any_failed = true;
+                recorder.record(WorkflowLog::PhaseFailed {
+                    phase: idx,
+                    name: stage_name,
+                    error,
+                });
+                if !stage
+                    .continue_on_failure
+                    .unwrap_or(pipeline.continue_on_failure)
+                {
+                    break;
+                }
+            }
+            StageOutcome::Cancelled => {
+                cancelled = true;
+                recorder.record(WorkflowLog::PhaseFailed {
+                    phase: idx,
+                    name: stage_name,
+                    error: "Cancelled".to_string(),
+                });
+                break;
+            }
+        }
+    }
+
+    recorder.flush();
+
+    // cancel_workflow already marked the execution as failed
+    if cancelled {
+        return Ok(());
+    }
+
+    let mut execs = executions.lock().unwrap();
+    if let Some(state) = execs.get_mut(&exec_id).filter(|state| !is_cancelled(Some(state))) {
+        state.set_status(if any_failed {
+            WorkflowStatus::Failed
+        } else {
+            WorkflowStatus::Completed
+        });
+        state.end_time = Some(Local::now());
+        state.exit_code = Some(if any_failed { 1 } else { 0 });
+
+        let db = database.lock().unwrap();
+        if let Err(e) = db.update_execution(
+            &exec_id,
+            state.status().clone(),
+            state.end_time,
+            state.exit_code,
+        ) {
+            eprintln!("Warning: Failed to update execution in database: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether `cancel_workflow` ended the execution, marking it Failed, or it
+/// is gone
+fn is_cancelled(state: Option<&ExecutionState>) -> bool {
+    !matches!(
+        state.map(|state| state.status()),
+        Some(WorkflowStatus::Running | WorkflowStatus::Paused)
+    )
+}
+
+/// Run one stage process, forwarding its events until it exits
+///
+/// The stage's child process is stored in the pipeline's `ExecutionState`
+/// so `cancel_workflow` can kill it.
+#[allow(clippy::too_many_arguments)]
+async fn run_stage(
+    exec_id: Uuid,
+    stage_idx: usize,
+    stage_name: &str,
+    workflow: &DiscoveredWorkflow,
+    params: HashMap<String, String>,
+    executions: &Arc<Mutex<HashMap<Uuid, ExecutionState>>>,
+    recorder: &mut LogRecorder,
+    state_files: &mut Vec<(String, String)>,
+) -> StageOutcome {
+    // Secrets the stage may echo are masked before they are recorded
+    let secrets = secret_values(&workflow.fields, &params);
+    let mut cmd = build_command(workflow, params);
+    cmd.stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => return StageOutcome::Failed(format!("Failed to spawn workflow process: {}", e)),
+    };
+
+    let (stdout, stderr) = match (child.stdout.take(), child.stderr.take()) {
+        (Some(stdout), Some(stderr)) => (stdout, stderr),
+        _ => {
+            kill_process_tree(&mut child);
+            return StageOutcome::Failed("Failed to capture workflow output".to_string());
+        }
+    };
+
+    {
+        let mut execs = executions.lock().unwrap();
+        match execs.get_mut(&exec_id) {
+            Some(state) if !is_cancelled(Some(state)) => {
+                state.control_tx = start_control_writer(child.stdin.take());
+                state.child = Some(child);
+            }
+            _ => {
+                kill_process_tree(&mut child);
+                return StageOutcome::Cancelled;
+            }
+        }
+    }
+
+    let (stdout, stderr) = match (
+        tokio::process::ChildStdout::from_std(stdout),
+        tokio::process::ChildStderr::from_std(stderr),
+    ) {
+        (Ok(stdout), Ok(stderr)) => (stdout, stderr),
+        _ => {
+            let child = executions
+                .lock()
+                .unwrap()
+                .get_mut(&exec_id)
+                .and_then(|state| state.child.take());
+            if let Some(mut child) = child {
+                kill_process_tree(&mut child);
+            }
+            return StageOutcome::Failed("Failed to read workflow output".to_string());
+        }
+    };
+
+    let mut stdout_lines = LossyLines::new(BufReader::new(stdout));
+    let mut stderr_lines = LossyLines::new(BufReader::new(stderr));
+    let mut stdout_done = false;
+    let mut stderr_done = false;
+    let mut decoder = EventDecoder::new(event_prefix());
+
+    while !(stdout_done && stderr_done) {
+        tokio::select! {
+            line = stderr_lines.next_line(), if !stderr_done => match line {
+                Ok(Some(line)) => {
+                    for part in decoder.push_line(&line) {
+                        let log = match part {
+                            StderrLine::Event(log) => {
+                                remap_stage_event(stage_idx, stage_name, redact_log(log, &secrets))
+                            }
+                            StderrLine::Raw(line) => WorkflowLog::RawOutput {
+                                stream: "stderr".to_string(),
+                                line: redact_secrets(&line, &secrets),
+                            },
+                        };
+                        if let WorkflowLog::StateFileCreated { file_path, description, .. } = &log {
+                            state_files.push((file_path.clone(), description.clone()));
+                        }
+                        recorder.record(log);
+                    }
+                }
+                _ => stderr_done = true,
+            },
+            line = stdout_lines.next_line(), if !stdout_done => match line {
+                Ok(Some(line)) => recorder.record(WorkflowLog::RawOutput {
+                    stream: "stdout".to_string(),
+                    line: redact_secrets(&line, &secrets),
+                }),
+                _ => stdout_done = true,
+            },
+        }
+    }
+
+    // Both streams closed: the process has exited or was killed
+    let child = executions
+        .lock()
+        .unwrap()
+        .get_mut(&exec_id)
+        .and_then(|state| state.child.take());
+
+    let Some(mut child) = child else {
+        // cancel_workflow took and killed the child
+        return StageOutcome::Cancelled;
+    };
+
+    match tokio::task::spawn_blocking(move || child.wait()).await {
+        Ok(Ok(status)) if status.success() => StageOutcome::Succeeded,
+        Ok(Ok(status)) => StageOutcome::Failed(match status.code() {
+            Some(code) => format!("Stage exited with code {}", code),
+            None => "Stage terminated by signal".to_string(),
+        }),
+        Ok(Err(e)) => StageOutcome::Failed(format!("Failed to wait for stage: {}", e)),
+        Err(e) => StageOutcome::Failed(format!("Failed to wait for stage: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stage(inputs: Vec<(&str, StageInput)>) -> PipelineStage {
+        PipelineStage {
+            workflow_id: "task_planner".to_string(),
+            name: None,
+            inputs: inputs
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+            continue_on_failure: None,
+        }
+    }
+
+    #[test]
+    fn test_resolve_value_and_param() {
+        let stage = stage(vec![
+            (
+                "batch_size",
+                StageInput::Value {
+                    value: "4".to_string(),
+                },
+            ),
+            (
+                "dir",
+                StageInput::Param {
+                    name: "codebase".to_string(),
+                },
+            ),
+        ]);
+        let mut pipeline_params = HashMap::new();
+        pipeline_params.insert("codebase".to_string(), "/src".to_string());
+
+        let params = resolve_stage_params(&stage, &pipeline_params, &[]).unwrap();
+        assert_eq!(params.get("batch_size").unwrap(), "4");
+        assert_eq!(params.get("dir").unwrap(), "/src");
+    }
+
+    #[test]
+    fn test_resolve_missing_param_fails() {
+        let stage = stage(vec![(
+            "dir",
+            StageInput::Param {
+                name: "codebase".to_string(),
+            },
+        )]);
+        assert!(resolve_stage_params(&stage, &HashMap::new(), &[]).is_err());
+    }
+
+    #[test]
+    fn test_preview_shows_state_file_placeholders() {
+        let stage = stage(vec![
+            (
+                "impl_file",
+                StageInput::StateFile {
+                    stage: 0,
+                    description: Some("Synthesis".to_string()),
+                },
+            ),
+            (
+                "dir",
+                StageInput::Param {
+                    name: "codebase".to_string(),
+                },
+            ),
+        ]);
+        let mut pipeline_params = HashMap::new();
+        pipeline_params.insert("codebase".to_string(), "/src".to_string());
+
+        let params = preview_stage_params(&stage, &pipeline_params).unwrap();
+        assert_eq!(params.get("impl_file").unwrap(), "<stage 0 state file>");
+        assert_eq!(params.get("dir").unwrap(), "/src");
+        assert!(preview_stage_params(&stage, &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_resolve_state_file_picks_last_match() {
+        let stage = stage(vec![(
+            "impl_file",
+            StageInput::StateFile {
+                stage: 0,
+                description: Some("Synthesis".to_string()),
+            },
+        )]);
+        let state_files = vec![vec![
+            ("a.yaml".to_string(), "Codebase analysis".to_string()),
+            ("doc.md".to_string(), "Synthesis output".to_string()),
+            ("b.yaml".to_string(), "Research results".to_string()),
+        ]];
+
+        let params = resolve_stage_params(&stage, &HashMap::new(), &state_files).unwrap();
+        assert_eq!(params.get("impl_file").unwrap(), "doc.md");
+    }
+
+    #[test]
+    fn test_resolve_state_file_from_future_stage_fails() {
+        let stage = stage(vec![(
+            "impl_file",
+            StageInput::StateFile {
+                stage: 3,
+                description: None,
+            },
+        )]);
+        assert!(resolve_stage_params(&stage, &HashMap::new(), &[vec![]]).is_err());
+    }
+
+    #[test]
+    fn test_remap_stage_event() {
+        let log = remap_stage_event(
+            2,
+            "Plan",
+            WorkflowLog::TaskStarted {
+                phase: 0,
+                task_id: "overview".to_string(),
+                description: "Generate overview".to_string(),
+                total_tasks: None,
+            },
+        );
+        match log {
+            WorkflowLog::TaskStarted { phase, task_id, .. } => {
+                assert_eq!(phase, 2);
+                assert_eq!(task_id, "stage2_overview");
+            }
+            other => panic!("Unexpected event: {:?}", other),
+        }
+
+        let log = remap_stage_event(
+            1,
+            "Plan",
+            WorkflowLog::PhaseStarted {
+                phase: 0,
+                name: "Overview".to_string(),
+                total_phases: 3,
+            },
+        );
+        assert!(matches!(log, WorkflowLog::RawOutput { .. }));
+    }
+
+    #[test]
+    fn test_remap_metric_event() {
+        // Metrics without a unit parse as emitted by `log_metric!`
+        let metric: WorkflowLog = serde_json::from_str(
+            r#"{"type":"metric","task_id":"overview","name":"progress","value":0.5}"#,
+        )
+        .unwrap();
+
+        match remap_stage_event(1, "Plan", metric) {
+            WorkflowLog::Metric {
+                task_id,
+                name,
+                value,
+                unit,
+            } => {
+                assert_eq!(task_id, "stage1_overview");
+                assert_eq!(name, "progress");
+                assert_eq!(value, 0.5);
+                assert!(unit.is_none());
+            }
+            other => panic!("Unexpected event: {:?}", other),
+        }
+    }
+}
@@ -1,7 +1,9 @@
 //! CLI argument parsing for research workflow
 
+use std::collections::HashMap;
+
 use clap::Parser;
-use workflow_manager_sdk::WorkflowDefinition;
+use workflow_manager_sdk::{cli_args, WorkflowDefinition};
 
 /// Research Agent CLI Arguments
 #[derive(Parser, Debug, Clone, WorkflowDefinition)]
@@ -17,7 +19,8 @@ pub struct Args {
         label = "Research Objective",
         description = "[TEXT] What do you want to research about the codebase?",
         type = "text",
-        required_for_phases = "2"
+        required_for_phases = "2",
+        group = "Objective"
     )]
     pub input: Option<String>,
 
@@ -27,7 +30,8 @@ pub struct Args {
         label = "System Prompt",
         description = "[TEXT] Path to prompt writer system prompt file",
         type = "file_path",
-        required_for_phases = "2"
+        required_for_phases = "2",
+        group = "Prompts"
     )]
     pub system_prompt: Option<String>,
 
@@ -37,7 +41,8 @@ pub struct Args {
         label = "Output Style",
         description = "[TEXT] Path to output style format file",
         type = "file_path",
-        required_for_phases = "2"
+        required_for_phases = "2",
+        group = "Prompts"
     )]
     pub append: Option<String>,
 
@@ -46,10 +51,31 @@ pub struct Args {
     #[field(
         label = "Output File",
         description = "[TEXT] Path for final documentation (e.g., docs/guide.md)",
-        type = "file_path"
+        type = "file_path",
+        group = "Output"
     )]
     pub output: Option<String>,
 
+    /// Directory for all generated files (default: OUTPUT/ and RESULTS/)
+    #[arg(long)]
+    #[field(
+        label = "Output Directory",
+        description = "[TEXT] Directory for the analysis, prompts, results and docs (created if missing)",
+        type = "file_path",
+        group = "Output"
+    )]
+    pub output_dir: Option<String>,
+
+    /// Prefix for the names of all generated files
+    #[arg(long)]
+    #[field(
+        label = "Output Prefix",
+        description = "[TEXT] Prepended to every generated file name (e.g., auth_)",
+        type = "text",
+        group = "Output"
+    )]
+    pub output_prefix: Option<String>,
+
     /// Number of research prompts to execute in parallel (default: 1 for sequential)
     #[arg(long, default_value = "1")]
     #[field(
@@ -77,8 +103,9 @@ pub struct Args {
         label = "Analysis File",
         description = "[STATE FILE] Resume with existing codebase analysis",
         type = "state_file",
-        pattern = "codebase_analysis_*.yaml",
-        required_for_phases = "1,2"
+        pattern = "*codebase_analysis_*.yaml",
+        required_for_phases = "1,2",
+        group = "Resume"
     )]
     pub analysis_file: Option<String>,
 
@@ -88,8 +115,9 @@ pub struct Args {
         label = "Prompts File",
         description = "[STATE FILE] Resume with existing research prompts",
         type = "state_file",
-        pattern = "research_prompts_*.yaml",
-        required_for_phases = "3"
+        pattern = "*research_prompts_*.yaml",
+        required_for_phases = "3",
+        group = "Resume"
     )]
     pub prompts_file: Option<String>,
 
@@ -99,8 +127,9 @@ pub struct Args {
         label = "Results File",
         description = "[STATE FILE] Resume with existing research results",
         type = "state_file",
-        pattern = "research_results_*.yaml",
-        required_for_phases = "4,5"
+        pattern = "*research_results_*.yaml",
+        required_for_phases = "4,5",
+        group = "Resume"
     )]
     pub results_file: Option<String>,
 
@@ -109,7 +138,8 @@ pub struct Args {
     #[field(
         label = "Directory",
         description = "[TEXT] Directory to analyze (default: current directory)",
-        type = "file_path"
+        type = "file_path",
+        group = "Codebase"
     )]
     pub dir: Option<String>,
 
@@ -119,16 +149,89 @@ pub struct Args {
         label = "Results Directory",
         description = "[TEXT] Directory containing YAML files to validate",
         type = "file_path",
-        required_for_phases = "4"
+        required_for_phases = "4",
+        group = "Resume"
     )]
     pub results_dir: Option<String>,
 
+    /// Append each research result to the results file as soon as it finishes
+    #[arg(long)]
+    #[field(
+        label = "Stream Results",
+        description = "[TOGGLE] Write each Phase 3 result to the results file as soon as it is ready",
+        type = "boolean"
+    )]
+    pub stream: bool,
+
+    /// Maximum number of retries across all tasks of this run (default: $WORKFLOW_RETRY_BUDGET or 10)
+    #[arg(long)]
+    #[field(
+        label = "Retry Budget",
+        description = "[NUMBER] Total fix retries allowed across the run before failures become terminal",
+        type = "number",
+        min = "0",
+        max = "100"
+    )]
+    pub retry_budget: Option<usize>,
+
+    /// Fix passes over an invalid YAML file before Phase 4 gives up on it (default: 3)
+    #[arg(
+        long,
+        default_value = "3",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    #[field(
+        label = "Max Fix Iterations",
+        description = "[NUMBER] Fix passes over an invalid YAML file before giving up on it",
+        type = "number",
+        min = "1",
+        max = "10"
+    )]
+    pub max_fix_iterations: usize,
+
+    /// Model of the agent of every phase (default: the SDK's default model)
+    #[arg(long)]
+    #[field(
+        label = "Model",
+        description = "[TEXT] Model of the phase agents, e.g. opus for a better synthesis",
+        type = "text",
+        group = "Models"
+    )]
+    pub model: Option<String>,
+
+    /// Model of the sub-agents the phase agents delegate to (default: sonnet)
+    #[arg(long)]
+    #[field(
+        label = "Sub-agent Model",
+        description = "[TEXT] Model of the sub-agents, e.g. haiku for cheaper condensing",
+        type = "text",
+        group = "Models"
+    )]
+    pub subagent_model: Option<String>,
+
+    /// Working directory of the workflow (the codebase to operate on)
+    #[arg(long)]
+    #[field(
+        label = "Working Directory",
+        description = "[TEXT] Repository the workflow runs in (default: current directory)",
+        type = "file_path",
+        group = "Codebase"
+    )]
+    pub cwd: Option<String>,
+
     // Hidden metadata flag
     #[arg(long, hide = true)]
     pub workflow_metadata: bool,
 }
 
 impl Args {
+    /// Arguments of a launch with the TUI's field values, parsed like the
+    /// command line the runtime builds from them
+    pub fn from_field_values(values: &HashMap<String, String>) -> Result<Self, clap::Error> {
+        let program = Self::metadata().id;
+        Self::try_parse_from(std::iter::once(program).chain(cli_args(&Self::fields(), values)))
+    }
+
     /// Parse the comma-separated phases string into a `Vec<u32>`
     pub fn parse_phases(&self) -> Vec<u32> {
         self.phases
@@ -151,8 +254,14 @@ impl From<Args> for crate::research::workflow::WorkflowConfig {
             results_file: args.results_file,
             results_dir: args.results_dir,
             output: args.output,
+            output_dir: args.output_dir,
+            output_prefix: args.output_prefix,
             system_prompt: args.system_prompt,
             append: args.append,
+            stream: args.stream,
+            max_fix_iterations: args.max_fix_iterations,
+            model: args.model,
+            subagent_model: args.subagent_model,
         }
     }
 }
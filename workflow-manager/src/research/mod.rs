@@ -22,8 +22,14 @@
 //!     results_file: None,
 //!     results_dir: None,
 //!     output: None,
+//!     output_dir: None,
+//!     output_prefix: None,
 //!     system_prompt: Some("prompts/writer.md".to_string()),
 //!     append: Some("prompts/style.md".to_string()),
+//!     stream: false,
+//!     max_fix_iterations: 3,
+//!     model: None,
+//!     subagent_model: None,
 //! };
 //!
 //! run_research_workflow(config).await?;
@@ -68,7 +74,7 @@
 //! use std::path::Path;
 //!
 //! # async fn example() -> anyhow::Result<()> {
-//! let analysis = analyze_codebase(Path::new(".")).await?;
+//! let analysis = analyze_codebase(Path::new("."), None).await?;
 //! println!("Codebase analysis complete");
 //! # Ok(())
 //! # }
@@ -80,7 +86,7 @@
 //! use workflow_manager::research::{run_research_workflow, WorkflowConfig};
 //!
 //! # async fn example() -> anyhow::Result<()> {
-//! // Resume from Phase 3 using saved prompts
+//! // Resume from Phase 3 using saved prompts, with opus for every phase agent
 //! let config = WorkflowConfig {
 //!     objective: None,
 //!     phases: vec![3, 4, 5],
@@ -91,8 +97,14 @@
 //!     results_file: None,
 //!     results_dir: None,
 //!     output: Some("docs/guide.md".to_string()),
+//!     output_dir: None,
+//!     output_prefix: None,
 //!     system_prompt: None,
 //!     append: None,
+//!     stream: false,
+//!     max_fix_iterations: 3,
+//!     model: Some("opus".to_string()),
+//!     subagent_model: None,
 //! };
 //!
 //! run_research_workflow(config).await?;
@@ -127,4 +139,7 @@ pub mod phase5_synthesize;
 
 // Re-export commonly used items for convenience
 pub use types::{CodebaseAnalysis, PromptsData, ResearchPrompt, ResearchResult};
-pub use workflow::{run_research_workflow, WorkflowConfig};
+pub use workflow::{
+    estimate_operations, run_research_workflow, validate_config, OperationEstimate,
+    PhaseEstimate, WorkflowConfig,
+};
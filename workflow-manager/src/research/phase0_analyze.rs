@@ -17,7 +17,12 @@ use claude_agent_sdk::ClaudeAgentOptions;
 use std::path::Path;
 
 /// Analyze codebase structure and generate comprehensive overview
-pub async fn analyze_codebase(codebase_path: &Path) -> anyhow::Result<CodebaseAnalysis> {
+///
+/// `model` overrides the default model of the analyzer agent.
+pub async fn analyze_codebase(
+    codebase_path: &Path,
+    model: Option<&str>,
+) -> anyhow::Result<CodebaseAnalysis> {
     let task_id = "analyze";
     let agent_name = "Codebase Analyzer";
 
@@ -122,7 +127,8 @@ Provide detailed structural analysis with accurate, verified metrics."#
         "Analyzing codebase structure",
         analysis_prompt,
         options,
-    );
+    )
+    .with_model(model);
 
     let response_text = execute_agent(config).await?;
 
@@ -13,6 +13,7 @@
 
 use crate::research::phase4_validate::{execute_fix_yaml, validate_yaml_file};
 use crate::research::types::CodebaseAnalysis;
+use crate::workflow_utils::RetryBudget;
 use anyhow::{Context, Result};
 use tokio::fs;
 
@@ -23,12 +24,14 @@ use tokio::fs;
 ///
 /// # Arguments
 /// * `analysis_file_path` - Path to the codebase analysis YAML file to validate
+/// * `model` - Model of the fixer agent, or `None` for the default
 ///
 /// # Returns
 /// * `Ok(CodebaseAnalysis)` - The validated codebase analysis
 /// * `Err(_)` - If validation or fixing fails after multiple attempts
 pub async fn validate_codebase_analysis(
     analysis_file_path: &str,
+    model: Option<&str>,
 ) -> Result<CodebaseAnalysis> {
     println!("\n📋 Validating codebase analysis YAML structure...");
     println!("File: {}", analysis_file_path);
@@ -66,8 +69,16 @@ pub async fn validate_codebase_analysis(
         println!("❌ YAML validation failed");
         println!("Error: {}", error_message.lines().next().unwrap_or("Unknown error"));
 
+        // Every fix after the first is a retry drawn from the run-level budget
+        if iteration > 1 && !RetryBudget::global().try_acquire("validate_analysis") {
+            anyhow::bail!(
+                "Retry budget exhausted while fixing {}. Manual intervention required.",
+                analysis_file_path
+            );
+        }
+
         println!("\n🔧 Attempting to fix YAML with AI agent...");
-        execute_fix_yaml(analysis_file_path, &error_message, None, 1)
+        execute_fix_yaml(analysis_file_path, &error_message, None, 1, model)
             .await
             .with_context(|| {
                 format!("Failed to fix YAML file: {}", analysis_file_path)
@@ -21,6 +21,7 @@ pub async fn generate_prompts(
     codebase_analysis: &CodebaseAnalysis,
     prompt_writer: &str,
     output_style: &str,
+    model: Option<&str>,
 ) -> anyhow::Result<PromptsData> {
     let task_id = "generate";
     let agent_name = "Prompt Generator";
@@ -49,7 +50,8 @@ pub async fn generate_prompts(
         "Generating research prompts",
         format!("Generate research prompts for: {}", objective),
         options,
-    );
+    )
+    .with_model(model);
 
     let response_text = execute_agent(config).await?;
     let yaml_content = extract_yaml(&response_text);
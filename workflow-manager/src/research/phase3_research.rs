@@ -6,28 +6,90 @@
 //! - Takes research prompts from Phase 2
 //! - Spawns concurrent agents (configurable batch size)
 //! - Each agent executes one research prompt with full tool access
-//! - Results are saved as individual YAML files in `RESULTS/`
+//! - Results are saved as individual YAML files in `RESULTS/` (or the
+//!   configured output directory)
 //! - A summary file `research_results_<timestamp>.yaml` tracks all results
 //!
-//! Supports configurable concurrency for efficient parallel execution.
+//! Supports configurable concurrency for efficient parallel execution. In
+//! streaming mode each result is appended to the summary file as soon as its
+//! agent finishes, instead of after the whole batch.
 
 use crate::research::types::{PromptsData, ResearchPrompt, ResearchResult};
-use crate::workflow_utils::{execute_agent, execute_batch, execute_task, AgentConfig};
+use crate::research::workflow::ArtifactPaths;
+use crate::workflow_utils::{
+    execute_agent, execute_batch_with_retries, execute_task_with_retry, AgentConfig,
+    DEFAULT_TASK_RETRIES,
+};
 use anyhow::Context;
 use claude_agent_sdk::{ClaudeAgentOptions, SystemPrompt, SystemPromptPreset};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+use workflow_manager_sdk::log_state_file;
+
+/// Appends finished results to the summary file as YAML list items
+///
+/// Agents finish concurrently, so writes go through a mutex; the file is a
+/// valid YAML list after every append.
+struct StreamingResultsWriter {
+    path: PathBuf,
+    file: Mutex<fs::File>,
+}
+
+impl StreamingResultsWriter {
+    async fn create(path: &Path) -> anyhow::Result<Self> {
+        let file = fs::File::create(path)
+            .await
+            .with_context(|| format!("Failed to create results file: {}", path.display()))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            file: Mutex::new(file),
+        })
+    }
+
+    async fn append(&self, result: &ResearchResult) -> anyhow::Result<()> {
+        let item = serde_yaml::to_string(std::slice::from_ref(result))?;
+        {
+            let mut file = self.file.lock().await;
+            file.write_all(item.as_bytes()).await?;
+            file.flush().await?;
+        }
+
+        log_state_file!(
+            3,
+            self.path.display().to_string(),
+            format!("Research result: {}", result.title)
+        );
+        Ok(())
+    }
+}
 
 /// Execute all research prompts concurrently with configurable batch size
+///
+/// Each result is written to its own file in the results directory of
+/// `paths`. With `stream_to`, each result is instead appended to that file as
+/// soon as it is ready (in completion order) and not kept in memory, so the
+/// returned list is empty; otherwise it keeps prompt order. `model` overrides
+/// the default model of the research agents.
 pub async fn execute_research(
     prompts_data: &PromptsData,
     batch_size: usize,
+    paths: &ArtifactPaths,
+    stream_to: Option<&Path>,
+    model: Option<&str>,
 ) -> anyhow::Result<Vec<ResearchResult>> {
-    // Create RESULTS directory if it doesn't exist
-    fs::create_dir_all("./RESULTS")
-        .await
-        .with_context(|| "Failed to create ./RESULTS directory")?;
+    paths.create_dirs().await?;
+
+    let writer = match stream_to {
+        Some(path) => Some(Arc::new(StreamingResultsWriter::create(path).await?)),
+        None => None,
+    };
 
     let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
+    let paths = paths.clone();
+    let model = model.map(String::from);
 
     println!("{}", "=".repeat(80));
     println!(
@@ -37,40 +99,61 @@ pub async fn execute_research(
     );
     println!("{}", "=".repeat(80));
 
-    // Use execute_batch to handle parallel execution with concurrency control
-    let results = execute_batch(
+    // Run prompts in parallel; a transient agent failure is retried instead
+    // of failing the whole phase
+    let results = execute_batch_with_retries(
         2, // phase
         prompts_data.prompts.clone(),
         batch_size,
+        DEFAULT_TASK_RETRIES,
         move |prompt, ctx| {
             let timestamp = timestamp.clone();
+            let paths = paths.clone();
+            let writer = writer.clone();
+            let model = model.clone();
             async move {
-                // Execute task with automatic logging
-                execute_task(
+                // Execute task with automatic logging and retries
+                let result = execute_task_with_retry(
                     format!("research_{}", ctx.task_number),
                     format!("Research task {}/{}", ctx.task_number, ctx.total_tasks),
                     ctx,
                     || async {
-                        let result =
-                            execute_research_prompt(&prompt, ctx.task_number, &timestamp).await?;
+                        let result = execute_research_prompt(
+                            &prompt,
+                            ctx.task_number,
+                            &paths,
+                            &timestamp,
+                            model.as_deref(),
+                        )
+                        .await?;
                         let summary = format!("Saved to {}", result.response_file);
                         Ok((result, summary))
                     },
                 )
-                .await
+                .await?;
+
+                match writer {
+                    Some(writer) => {
+                        writer.append(&result).await?;
+                        Ok(None)
+                    }
+                    None => Ok(Some(result)),
+                }
             }
         },
     )
     .await?;
 
-    Ok(results)
+    Ok(results.into_iter().flatten().collect())
 }
 
 /// Execute a single research prompt with Claude agent
 pub async fn execute_research_prompt(
     prompt: &ResearchPrompt,
     result_number: usize,
+    paths: &ArtifactPaths,
     timestamp: &str,
+    model: Option<&str>,
 ) -> anyhow::Result<ResearchResult> {
     let task_id = format!("research_{}", result_number);
     let agent_name = format!("Research Agent {}", result_number);
@@ -101,7 +184,8 @@ pub async fn execute_research_prompt(
         format!("Executing: {}", prompt.title),
         prompt.query.clone(),
         options,
-    );
+    )
+    .with_model(model);
 
     let response_text = execute_agent(config).await?;
 
@@ -109,10 +193,14 @@ pub async fn execute_research_prompt(
 
     // Extract YAML and save to file
     let yaml_content = crate::workflow_utils::extract_yaml(&response_text);
-    let response_filename = format!(
-        "./RESULTS/research_result_{}_{}.yaml",
-        result_number, timestamp
-    );
+    let response_filename = paths
+        .results_dir
+        .join(format!(
+            "{}research_result_{}_{}.yaml",
+            paths.prefix, result_number, timestamp
+        ))
+        .display()
+        .to_string();
     fs::write(&response_filename, &yaml_content)
         .await
         .with_context(|| format!("Failed to write research result file: {}", response_filename))?;
@@ -126,3 +214,39 @@ pub async fn execute_research_prompt(
         focus: prompt.focus.clone(),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(title: &str) -> ResearchResult {
+        ResearchResult {
+            title: title.to_string(),
+            query: format!("What about {}?", title),
+            response_file: format!("{}.yaml", title),
+            focus: vec!["focus".to_string()],
+        }
+    }
+
+    fn read_titles(path: &Path) -> Vec<String> {
+        let content = std::fs::read_to_string(path).unwrap();
+        let results: Vec<ResearchResult> = serde_yaml::from_str(&content).unwrap();
+        results.into_iter().map(|r| r.title).collect()
+    }
+
+    #[tokio::test]
+    async fn test_streamed_results_are_readable_before_completion() {
+        let path = std::env::temp_dir().join(format!("stream_{}.yaml", uuid::Uuid::new_v4()));
+        let writer = StreamingResultsWriter::create(&path).await.unwrap();
+
+        // Each append leaves a complete list while the writer is still open
+        writer.append(&result("auth")).await.unwrap();
+        assert_eq!(read_titles(&path), vec!["auth"]);
+
+        writer.append(&result("storage")).await.unwrap();
+        assert_eq!(read_titles(&path), vec!["auth", "storage"]);
+
+        drop(writer);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -7,7 +7,8 @@
 //! - Identifies files with syntax errors
 //! - Uses Claude agents to fix broken YAML files in parallel
 //! - Re-validates after each fix iteration
-//! - Loops until all files are valid
+//! - Loops until all files are valid, or gives up on the files still invalid
+//!   after `max_fix_iterations` passes and reports them as failed tasks
 //!
 //! Can run standalone on a directory of YAML files or as part of the full workflow.
 
@@ -16,6 +17,18 @@ use anyhow::{Context, Result};
 use claude_agent_sdk::{ClaudeAgentOptions, SystemPrompt, SystemPromptPreset};
 use tokio::fs;
 
+/// Fix passes over an invalid file before Phase 4 gives up on it
+pub const DEFAULT_MAX_FIX_ITERATIONS: usize = 3;
+
+/// First line of a validator's output, which names the error
+pub fn first_error_line(error_message: &str) -> &str {
+    error_message
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("Unknown error")
+}
+
 /// Validate YAML file using check_yaml.py script
 pub async fn validate_yaml_file(file_path: &str) -> Result<(String, bool, String)> {
     use tokio::process::Command;
@@ -40,12 +53,13 @@ pub async fn validate_yaml_file(file_path: &str) -> Result<(String, bool, String
     Ok((file_path.to_string(), is_valid, combined_output))
 }
 
-/// Fix invalid YAML file by querying Claude
+/// Fix invalid YAML file by querying Claude, on `model` if given
 pub async fn execute_fix_yaml(
     file_path: &str,
     error_message: &str,
     _prefix: Option<&str>,
     fixer_number: usize,
+    model: Option<&str>,
 ) -> Result<()> {
     let task_id = format!("fix_yaml_{}", fixer_number);
     let agent_name = format!("YAML Fixer {}", fixer_number);
@@ -60,10 +74,7 @@ pub async fn execute_fix_yaml(
         .with_context(|| format!("Failed to read file for YAML fixing: {}", file_path))?;
 
     println!("Read {} bytes from file", broken_yaml.len());
-    println!(
-        "Error: {}",
-        error_message.lines().next().unwrap_or("Unknown error")
-    );
+    println!("Error: {}", first_error_line(error_message));
 
     let fix_prompt = format!(
         r#"The following YAML file has validation errors. Please fix it and output ONLY the corrected YAML.
@@ -100,7 +111,8 @@ Output the fixed YAML wrapped in ```yaml code blocks."#,
         format!("Fixing YAML: {}", file_path),
         fix_prompt,
         options,
-    );
+    )
+    .with_model(model);
 
     let response_text = execute_agent(config).await?;
 
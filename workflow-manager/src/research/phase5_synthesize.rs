@@ -17,10 +17,19 @@ use std::path::Path;
 use crate::workflow_utils::{execute_agent, AgentConfig};
 use claude_agent_sdk::{AgentDefinition, ClaudeAgentOptions, SystemPrompt, SystemPromptPreset};
 
+/// Model of the file-condenser sub-agent unless configured otherwise
+pub const DEFAULT_SUBAGENT_MODEL: &str = "sonnet";
+
 /// Phase 5: Synthesize documentation from research results
+///
+/// `model` overrides the default model of the documentation agent and
+/// `subagent_model` the one of its file-condenser sub-agent, which is
+/// [`DEFAULT_SUBAGENT_MODEL`] otherwise.
 pub async fn synthesize_documentation(
     results_file: &Path,
     output_path: &Path,
+    model: Option<&str>,
+    subagent_model: Option<&str>,
 ) -> Result<()> {
     println!("\n{}", "=".repeat(80));
     println!("PHASE 4: Documentation Synthesis");
@@ -97,7 +106,7 @@ The overview file contains a list of research findings with:
                 description: "Condenses a single research result file while preserving key technical details, code examples, and actionable insights".to_string(),
                 prompt: "You are a technical documentation condenser. Read the provided research result YAML file and create a condensed summary that:\n\n1. Preserves all key technical details and insights\n2. Includes important code examples (condensed if very long)\n3. Maintains actionable recommendations\n4. Reduces verbosity and redundancy\n5. Target output: 5,000-10,000 characters\n\nReturn ONLY the condensed markdown content. Do not write to any files.".to_string(),
                 tools: Some(vec!["Read".to_string()]),
-                model: Some("sonnet".to_string()),
+                model: Some(subagent_model.unwrap_or(DEFAULT_SUBAGENT_MODEL).to_string()),
             },
         )
         .build();
@@ -109,7 +118,8 @@ The overview file contains a list of research findings with:
         "Synthesizing documentation with file-condenser subagent",
         synthesis_prompt,
         options,
-    );
+    )
+    .with_model(model);
 
     execute_agent(config).await?;
 
@@ -16,8 +16,8 @@ use std::{
 use tokio::{fs, sync::Semaphore};
 
 use workflow_manager_sdk::{
-    log_phase_complete, log_phase_start, log_state_file, log_task_complete, log_task_failed,
-    log_task_start,
+    log_phase_complete, log_phase_start, log_result, log_state_file, log_task_complete,
+    log_task_failed, log_task_start,
 };
 
 use crate::research::{
@@ -25,11 +25,14 @@ use crate::research::{
     phase1_validate_analysis::validate_codebase_analysis,
     phase2_prompts::generate_prompts,
     phase3_research::execute_research,
-    phase4_validate::{execute_fix_yaml, find_yaml_files, validate_yaml_file},
+    phase4_validate::{
+        execute_fix_yaml, find_yaml_files, first_error_line, validate_yaml_file,
+        DEFAULT_MAX_FIX_ITERATIONS,
+    },
     phase5_synthesize::synthesize_documentation,
     types::{CodebaseAnalysis, PromptsData, ResearchResult},
 };
-use crate::workflow_utils::{execute_task, TaskContext};
+use crate::workflow_utils::{execute_task, RetryBudget, TaskContext};
 
 /// Configuration for the research workflow
 ///
@@ -72,10 +75,24 @@ pub struct WorkflowConfig {
     pub results_dir: Option<String>,
     /// Output path for final documentation (Phase 5)
     pub output: Option<String>,
+    /// Directory for all generated files, instead of `OUTPUT/` and `RESULTS/`
+    pub output_dir: Option<String>,
+    /// Prepended to the name of every generated file
+    pub output_prefix: Option<String>,
     /// System prompt for prompt generation (required for Phase 2)
     pub system_prompt: Option<String>,
     /// Output style template (required for Phase 2)
     pub append: Option<String>,
+    /// Append each Phase 3 result to the results file as soon as it is ready
+    /// instead of writing them all at the end
+    pub stream: bool,
+    /// Fix passes over an invalid YAML file before Phase 4 reports it as
+    /// unfixable and moves on
+    pub max_fix_iterations: usize,
+    /// Model of the agent of every phase, e.g. `opus` (default: the SDK's)
+    pub model: Option<String>,
+    /// Model of the sub-agents those agents delegate to (default: `sonnet`)
+    pub subagent_model: Option<String>,
 }
 
 impl Default for WorkflowConfig {
@@ -90,12 +107,73 @@ impl Default for WorkflowConfig {
             results_file: None,
             results_dir: None,
             output: None,
+            output_dir: None,
+            output_prefix: None,
             system_prompt: None,
             append: None,
+            stream: false,
+            max_fix_iterations: DEFAULT_MAX_FIX_ITERATIONS,
+            model: None,
+            subagent_model: None,
         }
     }
 }
 
+/// Where the workflow writes its files and how it names them
+///
+/// By default the analysis, prompts and documentation go to `./OUTPUT` and
+/// the research results to `./RESULTS`; with `output_dir` everything goes to
+/// that one directory. Directories are absolute, so the paths reported in
+/// state file events can be used from anywhere.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtifactPaths {
+    pub output_dir: PathBuf,
+    pub results_dir: PathBuf,
+    /// Prepended to every file name
+    pub prefix: String,
+}
+
+impl ArtifactPaths {
+    /// Resolve the configured directories against the current directory
+    pub fn from_config(config: &WorkflowConfig) -> Result<Self> {
+        let cwd = std::env::current_dir().context("Failed to read the current directory")?;
+        let (output_dir, results_dir) = match &config.output_dir {
+            Some(dir) => (cwd.join(dir), cwd.join(dir)),
+            None => (cwd.join("OUTPUT"), cwd.join("RESULTS")),
+        };
+        Ok(Self {
+            output_dir,
+            results_dir,
+            prefix: config.output_prefix.clone().unwrap_or_default(),
+        })
+    }
+
+    /// Create the directories if they are missing
+    pub async fn create_dirs(&self) -> Result<()> {
+        for dir in [&self.output_dir, &self.results_dir] {
+            fs::create_dir_all(dir)
+                .await
+                .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+        }
+        Ok(())
+    }
+
+    /// `<prefix><name>_<timestamp>.<extension>` in the output directory
+    pub fn output_file(&self, name: &str, extension: &str) -> PathBuf {
+        timestamped_file(&self.output_dir, &self.prefix, name, extension)
+    }
+
+    /// `<prefix><name>_<timestamp>.<extension>` in the results directory
+    pub fn results_file(&self, name: &str, extension: &str) -> PathBuf {
+        timestamped_file(&self.results_dir, &self.prefix, name, extension)
+    }
+}
+
+fn timestamped_file(dir: &Path, prefix: &str, name: &str, extension: &str) -> PathBuf {
+    let timestamp = Local::now().format("%Y%m%d_%H%M%S");
+    dir.join(format!("{}{}_{}.{}", prefix, name, timestamp, extension))
+}
+
 /// Load file content or return literal string
 async fn load_prompt_file(file_path: &str) -> Result<String> {
     let path = Path::new(file_path);
@@ -108,6 +186,175 @@ async fn load_prompt_file(file_path: &str) -> Result<String> {
     }
 }
 
+/// Check that every requested phase has its inputs before anything runs
+///
+/// Each phase needs the output of the previous one: either that phase is
+/// selected too, or its saved file is passed in. All problems are reported
+/// together.
+pub fn validate_config(config: &WorkflowConfig) -> Result<()> {
+    let selected = |phase: u32| config.phases.contains(&phase);
+    let mut problems = Vec::new();
+
+    for phase in &config.phases {
+        if *phase > 5 {
+            problems.push(format!("Unknown phase {} (phases are 0-5)", phase));
+        }
+    }
+
+    if (selected(1) || selected(2)) && !selected(0) && config.analysis_file.is_none() {
+        for phase in [1, 2].into_iter().filter(|&p| selected(p)) {
+            problems.push(format!(
+                "Phase {} requires --analysis-file when phase 0 is not selected",
+                phase
+            ));
+        }
+    }
+    if selected(2) {
+        if config.objective.is_none() {
+            problems.push("Phase 2 requires --input".to_string());
+        }
+        if config.system_prompt.is_none() {
+            problems.push("Phase 2 requires --system-prompt".to_string());
+        }
+        if config.append.is_none() {
+            problems.push("Phase 2 requires --append".to_string());
+        }
+    }
+    if selected(3) && !selected(2) && config.prompts_file.is_none() {
+        problems.push("Phase 3 requires --prompts-file when phase 2 is not selected".to_string());
+    }
+    if selected(4) && !selected(3) && config.results_file.is_none() && config.results_dir.is_none()
+    {
+        problems.push(
+            "Phase 4 requires --results-file or --results-dir when phase 3 is not selected"
+                .to_string(),
+        );
+    }
+    if selected(5) && !selected(3) && config.results_file.is_none() {
+        problems.push("Phase 5 requires --results-file when phase 3 is not selected".to_string());
+    }
+    if let Some(prefix) = &config.output_prefix {
+        if prefix.contains(['/', '\\']) {
+            problems.push(format!(
+                "--output-prefix '{}' must not contain path separators; use --output-dir",
+                prefix
+            ));
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        anyhow::bail!(
+            "Invalid research workflow configuration:\n  - {}",
+            problems.join("\n  - ")
+        )
+    }
+}
+
+/// Prompts assumed for Phase 3 when they are generated in the same run
+pub const ESTIMATED_PROMPT_COUNT: usize = 8;
+
+/// Claude queries one phase is expected to issue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhaseEstimate {
+    pub phase: u32,
+    pub name: &'static str,
+    /// Queries of a run where nothing needs fixing
+    pub queries: usize,
+    /// How the count comes about, e.g. "one per prompt, 2 at a time"
+    pub basis: String,
+}
+
+/// Rough scale of a run, from [`estimate_operations`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OperationEstimate {
+    /// Selected phases, in order
+    pub phases: Vec<PhaseEstimate>,
+}
+
+impl OperationEstimate {
+    pub fn total_queries(&self) -> usize {
+        self.phases.iter().map(|phase| phase.queries).sum()
+    }
+}
+
+/// Number of prompts in a saved prompts file, if it can be read
+fn count_saved_prompts(prompts_file: &str) -> Option<usize> {
+    let content = std::fs::read_to_string(prompts_file).ok()?;
+    let prompts: PromptsData = serde_yaml::from_str(&content).ok()?;
+    Some(prompts.prompts.len())
+}
+
+/// Estimate how many Claude queries a run with `config` issues, per phase
+///
+/// A best-effort heuristic to show the scale of a run before launching it,
+/// not a billing guarantee. Phase 3 issues one query per prompt; their number
+/// is read from `prompts_file` when Phase 2 does not run, and assumed to be
+/// [`ESTIMATED_PROMPT_COUNT`] otherwise. Fixes of invalid YAML in Phases 1
+/// and 4 only happen on bad output, so they are mentioned but not counted.
+pub fn estimate_operations(config: &WorkflowConfig) -> OperationEstimate {
+    let mut phases: Vec<u32> = config.phases.iter().copied().filter(|&p| p <= 5).collect();
+    phases.sort_unstable();
+    phases.dedup();
+
+    let phases = phases
+        .into_iter()
+        .map(|phase| {
+            let (name, queries, basis) = match phase {
+                0 => ("Analyze Codebase", 1, "one analysis agent".to_string()),
+                1 => (
+                    "Validate Analysis",
+                    0,
+                    "local check, up to 3 fixes if the YAML is invalid".to_string(),
+                ),
+                2 => ("Generate Prompts", 1, "one prompt generator".to_string()),
+                3 => {
+                    let saved = config
+                        .prompts_file
+                        .as_deref()
+                        .filter(|_| !config.phases.contains(&2))
+                        .and_then(count_saved_prompts);
+                    let (prompts, source) = match saved {
+                        Some(count) => (count, "saved prompts"),
+                        None => (ESTIMATED_PROMPT_COUNT, "prompts, assumed"),
+                    };
+                    let basis = format!(
+                        "one per prompt ({} {}), {} at a time",
+                        prompts,
+                        source,
+                        config.batch_size.max(1)
+                    );
+                    ("Execute Research", prompts, basis)
+                }
+                4 => (
+                    "Validate YAML",
+                    0,
+                    "local check, one fix per invalid result file".to_string(),
+                ),
+                _ => ("Synthesize Docs", 1, "one synthesis agent".to_string()),
+            };
+            PhaseEstimate {
+                phase,
+                name,
+                queries,
+                basis,
+            }
+        })
+        .collect();
+
+    OperationEstimate { phases }
+}
+
+/// Research results whose response file is not among `files`
+fn without_files(results: &[ResearchResult], files: &[(String, String)]) -> Vec<ResearchResult> {
+    results
+        .iter()
+        .filter(|result| !files.iter().any(|(file, _)| *file == result.response_file))
+        .cloned()
+        .collect()
+}
+
 /// Run the complete research workflow with the given configuration
 ///
 /// This is the main entry point for executing the research workflow. It orchestrates
@@ -126,9 +373,15 @@ async fn load_prompt_file(file_path: &str) -> Result<String> {
 /// - **Phase 1**: Validate codebase analysis YAML structure
 /// - **Phase 2**: Generate research prompts and save to `OUTPUT/research_prompts_*.yaml`
 /// - **Phase 3**: Execute research in parallel and save to `RESULTS/research_result_*.yaml`
-/// - **Phase 4**: Validate and fix YAML files iteratively until all are valid
+/// - **Phase 4**: Validate and fix YAML files iteratively until all are valid,
+///   reporting those still invalid after `max_fix_iterations` passes as failed
+///   tasks instead of failing the phase, and leaving them out of the results
+///   overview given to Phase 5
 /// - **Phase 5**: Synthesize documentation and save to output path
 ///
+/// `output_dir` and `output_prefix` change where these files go and how they
+/// are named (see [`ArtifactPaths`]).
+///
 /// # Resumability
 ///
 /// The workflow can be resumed from any phase by providing saved state files:
@@ -139,7 +392,7 @@ async fn load_prompt_file(file_path: &str) -> Result<String> {
 /// # Errors
 ///
 /// Returns an error if:
-/// - Required parameters for a phase are missing
+/// - Required parameters for a phase are missing (see [`validate_config`])
 /// - A phase execution fails
 /// - File I/O operations fail
 ///
@@ -164,18 +417,8 @@ async fn load_prompt_file(file_path: &str) -> Result<String> {
 /// # }
 /// ```
 pub async fn run_research_workflow(config: WorkflowConfig) -> Result<()> {
-    // Validate required arguments based on phases
-    if config.phases.contains(&2) {
-        if config.objective.is_none() {
-            anyhow::bail!("--input is required when running phase 2");
-        }
-        if config.system_prompt.is_none() {
-            anyhow::bail!("--system-prompt is required when running phase 2");
-        }
-        if config.append.is_none() {
-            anyhow::bail!("--append is required when running phase 2");
-        }
-    }
+    // Fail before any work if a requested phase is missing its inputs
+    validate_config(&config)?;
 
     // Change working directory to target directory if specified
     if let Some(dir) = &config.dir {
@@ -194,12 +437,8 @@ pub async fn run_research_workflow(config: WorkflowConfig) -> Result<()> {
     }
 
     // Create directory structure for workflow artifacts
-    fs::create_dir_all("./RESULTS")
-        .await
-        .with_context(|| "Failed to create ./RESULTS directory")?;
-    fs::create_dir_all("./OUTPUT")
-        .await
-        .with_context(|| "Failed to create ./OUTPUT directory")?;
+    let paths = ArtifactPaths::from_config(&config)?;
+    paths.create_dirs().await?;
 
     let mut codebase_analysis: Option<CodebaseAnalysis> = None;
     let mut analysis_file_path: Option<PathBuf> = None;
@@ -222,11 +461,10 @@ pub async fn run_research_workflow(config: WorkflowConfig) -> Result<()> {
             .map(PathBuf::from)
             .unwrap_or_else(|| std::env::current_dir().unwrap());
 
-        let analysis = analyze_codebase(&codebase_path).await?;
+        let analysis = analyze_codebase(&codebase_path, config.model.as_deref()).await?;
 
         // Save analysis to file
-        let timestamp = Local::now().format("%Y%m%d_%H%M%S");
-        let analysis_path = PathBuf::from(format!("./OUTPUT/codebase_analysis_{}.yaml", timestamp));
+        let analysis_path = paths.output_file("codebase_analysis", "yaml");
         let analysis_yaml = serde_yaml::to_string(&analysis)?;
         fs::write(&analysis_path, &analysis_yaml)
             .await
@@ -265,7 +503,8 @@ pub async fn run_research_workflow(config: WorkflowConfig) -> Result<()> {
         })?;
 
         let validated_analysis = validate_codebase_analysis(
-            &analysis_path.display().to_string()
+            &analysis_path.display().to_string(),
+            config.model.as_deref(),
         ).await?;
 
         codebase_analysis = Some(validated_analysis);
@@ -291,12 +530,12 @@ pub async fn run_research_workflow(config: WorkflowConfig) -> Result<()> {
             analysis,
             &prompt_writer,
             &output_style,
+            config.model.as_deref(),
         )
         .await?;
 
         // Save prompts to file
-        let timestamp = Local::now().format("%Y%m%d_%H%M%S");
-        let prompts_path = PathBuf::from(format!("./OUTPUT/research_prompts_{}.yaml", timestamp));
+        let prompts_path = paths.output_file("research_prompts", "yaml");
         let prompts_yaml = serde_yaml::to_string(&prompts)?;
         fs::write(&prompts_path, &prompts_yaml)
             .await
@@ -335,15 +574,40 @@ pub async fn run_research_workflow(config: WorkflowConfig) -> Result<()> {
             anyhow::anyhow!("Phase 2 must run before Phase 3, or provide --prompts-file")
         })?;
 
-        research_results = execute_research(prompts, config.batch_size).await?;
+        let results_path = paths.results_file("research_results", "yaml");
 
-        // Save research results to file
-        let timestamp = Local::now().format("%Y%m%d_%H%M%S");
-        let results_path = PathBuf::from(format!("./RESULTS/research_results_{}.yaml", timestamp));
-        let results_yaml = serde_yaml::to_string(&research_results)?;
-        fs::write(&results_path, &results_yaml)
-            .await
-            .with_context(|| format!("Failed to write results file: {}", results_path.display()))?;
+        research_results = execute_research(
+            prompts,
+            config.batch_size,
+            &paths,
+            config.stream.then_some(results_path.as_path()),
+            config.model.as_deref(),
+        )
+        .await?;
+
+        // Streaming mode already wrote the results and keeps none in memory,
+        // so they are read back for Phase 4
+        let streamed = if config.stream {
+            fs::read_to_string(&results_path).await.with_context(|| {
+                format!("Failed to read results file: {}", results_path.display())
+            })?
+        } else {
+            String::new()
+        };
+        if !streamed.trim().is_empty() {
+            research_results = serde_yaml::from_str(&streamed).with_context(|| {
+                format!("Failed to parse results YAML from: {}", results_path.display())
+            })?;
+        } else {
+            // Save research results to file (an empty run still needs a
+            // valid list)
+            let results_yaml = serde_yaml::to_string(&research_results)?;
+            fs::write(&results_path, &results_yaml)
+                .await
+                .with_context(|| {
+                    format!("Failed to write results file: {}", results_path.display())
+                })?;
+        }
         println!("\n[Phase 3] Results saved to: {}", results_path.display());
 
         log_state_file!(
@@ -409,16 +673,19 @@ pub async fn run_research_workflow(config: WorkflowConfig) -> Result<()> {
             format!("Found {} files with errors", files_with_errors.len())
         );
 
-        // Loop to fix and re-validate until all are valid
+        // Fix and re-validate until all are valid, giving up on the files still
+        // invalid after the last pass so the others can go on to Phase 5
+        let mut unfixable = Vec::new();
         let mut fix_iteration = 0;
-        loop {
-            if files_with_errors.is_empty() {
-                println!("\n✓ All files validated successfully!");
+        while !files_with_errors.is_empty() {
+            if fix_iteration == config.max_fix_iterations {
+                unfixable.append(&mut files_with_errors);
                 break;
             }
 
             fix_iteration += 1;
             let task_id = format!("fix_iteration_{}", fix_iteration);
+
             log_task_start!(
                 4,
                 &task_id,
@@ -429,6 +696,28 @@ pub async fn run_research_workflow(config: WorkflowConfig) -> Result<()> {
                 )
             );
 
+            // Files still broken after a fix pass are retries; draw them from the
+            // run-level budget and give up on those it cannot cover
+            if fix_iteration > 1 {
+                let budget = RetryBudget::global();
+                let (retried, out_of_budget): (Vec<_>, Vec<_>) =
+                    std::mem::take(&mut files_with_errors)
+                        .into_iter()
+                        .partition(|_| budget.try_acquire(&task_id));
+                if !out_of_budget.is_empty() {
+                    println!(
+                        "\n⚠ Retry budget exhausted with {} YAML files still invalid",
+                        out_of_budget.len()
+                    );
+                }
+                unfixable.extend(out_of_budget);
+                files_with_errors = retried;
+                if files_with_errors.is_empty() {
+                    log_task_failed!(&task_id, "Retry budget exhausted before any fix");
+                    break;
+                }
+            }
+
             println!(
                 "\n⚠ Found {} files with errors. Fixing...",
                 files_with_errors.len()
@@ -443,6 +732,7 @@ pub async fn run_research_workflow(config: WorkflowConfig) -> Result<()> {
             for (i, (file, error)) in current_batch.iter().enumerate() {
                 let file = file.clone();
                 let error = error.clone();
+                let model = config.model.clone();
                 let sem = sem.clone();
                 let fixer_number = i + 1;
                 let prefix = format!("[YAML Fixer {}]: ", fixer_number);
@@ -460,7 +750,14 @@ pub async fn run_research_workflow(config: WorkflowConfig) -> Result<()> {
                         format!("Fixing YAML file {}", fixer_number)
                     );
 
-                    let result = execute_fix_yaml(&file, &error, Some(&prefix), fixer_number).await;
+                    let result = execute_fix_yaml(
+                        &file,
+                        &error,
+                        Some(&prefix),
+                        fixer_number,
+                        model.as_deref(),
+                    )
+                    .await;
 
                     if result.is_ok() {
                         log_task_complete!(&fix_task_id, format!("Fixed {}", file));
@@ -472,10 +769,9 @@ pub async fn run_research_workflow(config: WorkflowConfig) -> Result<()> {
                 });
             }
 
-            // Wait for all fixes to complete (fail-fast on error)
-            while let Some(result) = fix_tasks.next().await {
-                result?;
-            }
+            // A fixer that failed leaves its file invalid, which re-validation
+            // catches, so its error was logged and is not needed here
+            while fix_tasks.next().await.is_some() {}
 
             // Re-validate the files we just fixed and repopulate files_with_errors
             for (file, _) in current_batch {
@@ -491,6 +787,49 @@ pub async fn run_research_workflow(config: WorkflowConfig) -> Result<()> {
             );
         }
 
+        if unfixable.is_empty() {
+            println!("\n✓ All files validated successfully!");
+        } else {
+            println!(
+                "\n⚠ {} of {} YAML files could not be fixed:",
+                unfixable.len(),
+                result_files.len()
+            );
+            // One failed task per file, so the TUI lists exactly which ones
+            for (i, (file, error)) in unfixable.iter().enumerate() {
+                println!("  - {}: {}", file, first_error_line(error));
+                let task_id = format!("unfixable_yaml_{}", i + 1);
+                log_task_start!(4, &task_id, format!("Invalid YAML: {}", file));
+                log_task_failed!(
+                    &task_id,
+                    format!("Could not be fixed: {}", first_error_line(error))
+                );
+            }
+
+            // Phase 5 reads every file of its overview, so it gets one
+            // without the invalid ones
+            if !research_results.is_empty() {
+                let valid = without_files(&research_results, &unfixable);
+                let valid_path = paths.results_file("research_results_valid", "yaml");
+                fs::write(&valid_path, serde_yaml::to_string(&valid)?)
+                    .await
+                    .with_context(|| {
+                        format!("Failed to write results file: {}", valid_path.display())
+                    })?;
+                println!(
+                    "[Phase 4] {} valid results saved to: {}",
+                    valid.len(),
+                    valid_path.display()
+                );
+                log_state_file!(
+                    4,
+                    valid_path.display().to_string(),
+                    "Research results without the unfixable files, for Phase 5"
+                );
+                results_file_path = Some(valid_path);
+            }
+        }
+
         log_phase_complete!(4, "Validate YAML");
     }
 
@@ -507,8 +846,7 @@ pub async fn run_research_workflow(config: WorkflowConfig) -> Result<()> {
         let output_path = if let Some(output) = &config.output {
             PathBuf::from(output)
         } else {
-            let timestamp = Local::now().format("%Y%m%d_%H%M%S");
-            PathBuf::from(format!("./OUTPUT/research_output_{}.md", timestamp))
+            paths.output_file("research_output", "md")
         };
 
         execute_task(
@@ -518,9 +856,16 @@ pub async fn run_research_workflow(config: WorkflowConfig) -> Result<()> {
                 phase: 5,
                 task_number: 1,
                 total_tasks: 1,
+                max_retries: 0,
             },
             || async {
-                synthesize_documentation(results_file, &output_path).await?;
+                synthesize_documentation(
+                    results_file,
+                    &output_path,
+                    config.model.as_deref(),
+                    config.subagent_model.as_deref(),
+                )
+                .await?;
                 Ok(((), "Documentation synthesized".to_string()))
             },
         )
@@ -532,6 +877,15 @@ pub async fn run_research_workflow(config: WorkflowConfig) -> Result<()> {
             "Final synthesized documentation"
         );
         log_phase_complete!(5, "Synthesize Docs");
+        // Relative to the workflow's directory, which the TUI does not know
+        let artifact = std::env::current_dir()?.join(&output_path);
+        log_result!(
+            match &config.objective {
+                Some(objective) => format!("Research complete: {}", objective),
+                None => "Research complete".to_string(),
+            },
+            [artifact.display()]
+        );
 
         println!("\n{}", "=".repeat(80));
         println!(
@@ -547,3 +901,159 @@ pub async fn run_research_workflow(config: WorkflowConfig) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::research::cli::Args;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_validate_config_full_run() {
+        let config = WorkflowConfig {
+            objective: Some("How does auth work?".to_string()),
+            system_prompt: Some("writer.md".to_string()),
+            append: Some("style.md".to_string()),
+            ..Default::default()
+        };
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_validate_config_resume_needs_files() {
+        let mut config = WorkflowConfig {
+            phases: vec![3, 4, 5],
+            ..Default::default()
+        };
+        let error = validate_config(&config).unwrap_err().to_string();
+        assert!(error.contains("Phase 3 requires --prompts-file when phase 2 is not selected"));
+        assert!(!error.contains("Phase 4"));
+        assert!(!error.contains("Phase 5"));
+
+        config.prompts_file = Some("prompts.yaml".to_string());
+        assert!(validate_config(&config).is_ok());
+
+        config.phases = vec![5];
+        let error = validate_config(&config).unwrap_err().to_string();
+        assert!(error.contains("Phase 5 requires --results-file"));
+    }
+
+    #[test]
+    fn test_validate_config_reports_every_problem() {
+        let config = WorkflowConfig {
+            phases: vec![2, 7],
+            ..Default::default()
+        };
+        let error = validate_config(&config).unwrap_err().to_string();
+        assert!(error.contains("Unknown phase 7"));
+        assert!(error.contains("Phase 2 requires --analysis-file when phase 0 is not selected"));
+        assert!(error.contains("Phase 2 requires --input"));
+        assert!(error.contains("Phase 2 requires --append"));
+    }
+
+    #[test]
+    fn test_estimate_operations() {
+        let values: HashMap<String, String> = [("phases", "0,1,2,3"), ("batch_size", "2")]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let config = Args::from_field_values(&values).unwrap().into();
+        let estimate = estimate_operations(&config);
+        let queries: Vec<(u32, usize)> =
+            estimate.phases.iter().map(|p| (p.phase, p.queries)).collect();
+        assert_eq!(queries, vec![(0, 1), (1, 0), (2, 1), (3, ESTIMATED_PROMPT_COUNT)]);
+        assert_eq!(estimate.total_queries(), 2 + ESTIMATED_PROMPT_COUNT);
+        assert!(estimate.phases[3].basis.ends_with("2 at a time"));
+
+        // Resuming from saved prompts counts them
+        let path = std::env::temp_dir().join(format!("prompts_{}.yaml", uuid::Uuid::new_v4()));
+        let prompt = "{title: t, query: q, focus: []}";
+        let yaml = format!("objective: o\nprompts: [{}, {}, {}]\n", prompt, prompt, prompt);
+        std::fs::write(&path, yaml).unwrap();
+        let config = WorkflowConfig {
+            phases: vec![3, 5],
+            prompts_file: Some(path.to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        assert_eq!(estimate_operations(&config).total_queries(), 4);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_models_default_to_current_behavior() {
+        let config: WorkflowConfig = Args::from_field_values(&HashMap::new()).unwrap().into();
+        assert_eq!((config.model, config.subagent_model), (None, None));
+
+        let values: HashMap<String, String> = [("model", "opus"), ("subagent_model", "")]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let config: WorkflowConfig = Args::from_field_values(&values).unwrap().into();
+        assert_eq!(config.model.as_deref(), Some("opus"));
+        assert_eq!(config.subagent_model, None);
+    }
+
+    #[test]
+    fn test_max_fix_iterations_from_field_values() {
+        let config: WorkflowConfig = Args::from_field_values(&HashMap::new()).unwrap().into();
+        assert_eq!(config.max_fix_iterations, DEFAULT_MAX_FIX_ITERATIONS);
+
+        let values = HashMap::from([("max_fix_iterations".to_string(), "5".to_string())]);
+        let config: WorkflowConfig = Args::from_field_values(&values).unwrap().into();
+        assert_eq!(config.max_fix_iterations, 5);
+        for invalid in ["many", "0"] {
+            let values = HashMap::from([("max_fix_iterations".to_string(), invalid.to_string())]);
+            assert!(Args::from_field_values(&values).is_err());
+        }
+        let error = "\n  ❌ line 3: bad indent\ndetails";
+        assert_eq!(first_error_line(error), "❌ line 3: bad indent");
+    }
+
+    #[test]
+    fn test_unfixable_files_are_left_out_of_the_overview() {
+        let result = |file: &str| ResearchResult {
+            title: file.to_string(),
+            query: String::new(),
+            response_file: file.to_string(),
+            focus: Vec::new(),
+        };
+        let results = vec![result("a.yaml"), result("b.yaml"), result("c.yaml")];
+        let unfixable = vec![("b.yaml".to_string(), "bad indent".to_string())];
+
+        let valid = without_files(&results, &unfixable);
+        let files: Vec<&str> = valid.iter().map(|r| r.response_file.as_str()).collect();
+        assert_eq!(files, vec!["a.yaml", "c.yaml"]);
+    }
+
+    #[test]
+    fn test_artifact_paths() {
+        let cwd = std::env::current_dir().unwrap();
+        let paths = ArtifactPaths::from_config(&WorkflowConfig::default()).unwrap();
+        assert_eq!(paths.output_dir, cwd.join("OUTPUT"));
+        assert_eq!(paths.results_dir, cwd.join("RESULTS"));
+
+        let config = WorkflowConfig {
+            output_dir: Some("runs/auth".to_string()),
+            output_prefix: Some("auth_".to_string()),
+            ..Default::default()
+        };
+        let paths = ArtifactPaths::from_config(&config).unwrap();
+        assert_eq!(paths.results_dir, cwd.join("runs/auth"));
+        let file = paths.results_file("research_results", "yaml");
+        assert_eq!(file.parent(), Some(cwd.join("runs/auth").as_path()));
+        let name = file.file_name().unwrap().to_str().unwrap();
+        assert!(name.starts_with("auth_research_results_"));
+        assert!(name.ends_with(".yaml"));
+    }
+
+    #[test]
+    fn test_validate_config_rejects_prefix_with_separator() {
+        let config = WorkflowConfig {
+            phases: vec![0],
+            output_prefix: Some("runs/auth_".to_string()),
+            ..Default::default()
+        };
+        let error = validate_config(&config).unwrap_err().to_string();
+        assert!(error.contains("--output-prefix 'runs/auth_' must not contain path separators"));
+    }
+}
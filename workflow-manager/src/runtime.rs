@@ -1,25 +1,33 @@
 use anyhow::{anyhow, Result};
 use chrono::{DateTime, Local};
 use std::collections::HashMap;
-use std::path::PathBuf;
-use std::process::{Child, Command, Stdio};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
 use std::sync::{Arc, Mutex};
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::sync::broadcast;
+use std::time::Duration;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::{broadcast, mpsc, watch, OwnedSemaphorePermit, Semaphore};
 use uuid::Uuid;
 use workflow_manager_sdk::{
-    async_trait, ExecutionSummary, FullWorkflowMetadata, WorkflowHandle, WorkflowLog,
+    async_trait, cli_args, event_prefix, validation::validate_field, EventDecoder,
+    ExecutionSummary, FieldError, FullWorkflowMetadata, LogPage, PipelineDefinition,
+    RestartPolicy, StderrLine, WorkflowControl, WorkflowError, WorkflowHandle, WorkflowLog,
     WorkflowResult, WorkflowRuntime, WorkflowStatus,
 };
 
 use crate::database::{Database, PersistedExecution};
-use crate::discovery::{discover_workflows, DiscoveredWorkflow};
+use crate::discovery::{discover_workflows, expand_command_template, DiscoveredWorkflow};
+use crate::pipeline::{preview_stage_params, run_pipeline};
+use crate::utils::{
+    is_secret, redact_log, redact_secrets, secret_env_var, secret_values, without_secrets,
+};
 
 /// Internal execution state for a running workflow
 pub struct ExecutionState {
     pub workflow_id: String,
     pub workflow_name: String,
-    pub status: WorkflowStatus,
+    /// Only changed through `set_status`, which keeps `status_tx` in step
+    status: WorkflowStatus,
     pub child: Option<Child>,
     pub logs_tx: broadcast::Sender<WorkflowLog>,
     pub binary_path: PathBuf,
@@ -28,7 +36,427 @@ pub struct ExecutionState {
     pub start_time: DateTime<Local>,
     pub end_time: Option<DateTime<Local>>,
     pub params: HashMap<String, String>,
+    /// Values of the secret fields, masked in raw output
+    pub secrets: Vec<String>,
     pub exit_code: Option<i32>,
+    /// Totals of the `Usage` events seen so far (None until the first one)
+    pub usage: Option<UsageTotals>,
+    /// Follows `status`, for `wait_for_completion`
+    status_tx: watch::Sender<WorkflowStatus>,
+    /// Why the runtime stopped the execution, if it did
+    pub stopped: Option<StopReason>,
+    /// Control messages for the current process, written to its stdin by
+    /// the task of [`start_control_writer`]
+    pub control_tx: Option<mpsc::UnboundedSender<WorkflowControl>>,
+}
+
+/// Why the runtime stopped an execution before its process exited
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StopReason {
+    /// Stopped by `cancel_workflow`
+    Cancelled,
+    /// Killed by the inactivity timeout
+    Timeout,
+}
+
+impl From<StopReason> for WorkflowError {
+    fn from(reason: StopReason) -> Self {
+        match reason {
+            StopReason::Cancelled => WorkflowError::Cancelled,
+            StopReason::Timeout => WorkflowError::Timeout,
+        }
+    }
+}
+
+/// A dropped execution kills its process group, e.g. when the TUI exits;
+/// workflows run in their own group, so Ctrl+C in the terminal does not
+/// reach them
+impl Drop for ExecutionState {
+    fn drop(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            kill_process_tree(&mut child);
+        }
+    }
+}
+
+impl ExecutionState {
+    /// State of a new execution of `workflow_id`, starting out with `status`
+    ///
+    /// The other fields start out empty, with a log channel of its own.
+    pub fn new(
+        workflow_id: String,
+        workflow_name: String,
+        binary_path: PathBuf,
+        status: WorkflowStatus,
+    ) -> Self {
+        Self {
+            workflow_id,
+            workflow_name,
+            status_tx: watch::Sender::new(status.clone()),
+            status,
+            child: None,
+            // Capacity 1000 (up from 100) to reduce lagging in high-frequency
+            // workflows
+            logs_tx: broadcast::channel(1000).0,
+            binary_path,
+            logs_buffer: Arc::new(Mutex::new(Vec::new())),
+            start_time: Local::now(),
+            end_time: None,
+            params: HashMap::new(),
+            secrets: Vec::new(),
+            exit_code: None,
+            usage: None,
+            stopped: None,
+            control_tx: None,
+        }
+    }
+
+    pub fn status(&self) -> &WorkflowStatus {
+        &self.status
+    }
+
+    /// Change the status, waking whoever waits for the execution to finish
+    pub fn set_status(&mut self, status: WorkflowStatus) {
+        self.status_tx.send_replace(status.clone());
+        self.status = status;
+    }
+}
+
+/// Token usage and cost accumulated over an execution
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct UsageTotals {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// None while no query has reported a cost
+    pub cost_usd: Option<f64>,
+}
+
+impl UsageTotals {
+    /// Rebuild totals from persisted columns, None if nothing was recorded
+    pub fn from_parts(
+        input_tokens: Option<u64>,
+        output_tokens: Option<u64>,
+        cost_usd: Option<f64>,
+    ) -> Option<Self> {
+        if input_tokens.is_none() && output_tokens.is_none() && cost_usd.is_none() {
+            return None;
+        }
+        Some(Self {
+            input_tokens: input_tokens.unwrap_or(0),
+            output_tokens: output_tokens.unwrap_or(0),
+            cost_usd,
+        })
+    }
+
+    /// Add one `WorkflowLog::Usage` event
+    pub fn add(&mut self, input_tokens: u64, output_tokens: u64, cost_usd: Option<f64>) {
+        self.input_tokens += input_tokens;
+        self.output_tokens += output_tokens;
+        if let Some(cost) = cost_usd {
+            self.cost_usd = Some(self.cost_usd.unwrap_or(0.0) + cost);
+        }
+    }
+}
+
+impl std::fmt::Display for UsageTotals {
+    /// Compact form for the status bar, e.g. `12.3k in / 850 out tokens · $0.42`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn tokens(n: u64) -> String {
+            if n >= 1_000_000 {
+                format!("{:.1}M", n as f64 / 1_000_000.0)
+            } else if n >= 1_000 {
+                format!("{:.1}k", n as f64 / 1_000.0)
+            } else {
+                n.to_string()
+            }
+        }
+
+        write!(
+            f,
+            "{} in / {} out tokens",
+            tokens(self.input_tokens),
+            tokens(self.output_tokens)
+        )?;
+        if let Some(cost) = self.cost_usd {
+            write!(f, " · ${:.2}", cost)?;
+        }
+        Ok(())
+    }
+}
+
+/// Add a `Usage` event to the execution's totals and persist them
+///
+/// Other events are ignored, so this can be called on every parsed log.
+pub(crate) fn record_usage(
+    exec_id: &Uuid,
+    log: &WorkflowLog,
+    executions: &Mutex<HashMap<Uuid, ExecutionState>>,
+    database: &Mutex<Database>,
+) {
+    let WorkflowLog::Usage {
+        input_tokens,
+        output_tokens,
+        cost_usd,
+    } = log
+    else {
+        return;
+    };
+
+    let totals = {
+        let mut execs = executions.lock().unwrap();
+        let Some(state) = execs.get_mut(exec_id) else {
+            return;
+        };
+        let totals = state.usage.get_or_insert_with(UsageTotals::default);
+        totals.add(*input_tokens, *output_tokens, *cost_usd);
+        *totals
+    };
+
+    let db = database.lock().unwrap();
+    if let Err(e) = db.update_execution_usage(
+        exec_id,
+        totals.input_tokens,
+        totals.output_tokens,
+        totals.cost_usd,
+    ) {
+        eprintln!("Warning: Failed to persist usage to database: {}", e);
+    }
+}
+
+/// Reserved parameter: seconds without any new log event after which the
+/// workflow process is killed
+///
+/// Ignored (passed through as a regular value) when the workflow declares a
+/// field with the same name.
+pub const TIMEOUT_PARAM: &str = "timeout";
+
+/// Reserved parameter: working directory of the workflow process
+///
+/// Applied even when the workflow declares a field with the same name, so the
+/// workflow can also read it as an argument. Relative file paths of the
+/// other params are checked against it.
+pub const CWD_PARAM: &str = workflow_manager_sdk::validation::CWD_PARAM;
+
+/// Reserved parameter: variables added to the environment of the workflow
+/// process, see [`parse_env`] for the accepted forms
+pub const ENV_PARAM: &str = "env";
+
+/// Name of the `PhaseFailed` event recorded when a workflow times out
+pub const TIMEOUT_PHASE_NAME: &str = "Timeout";
+
+/// Environment variable capping how many workflows run at the same time
+pub const MAX_CONCURRENT_ENV: &str = "WF_MAX_CONCURRENT";
+
+/// Task id of the `TaskProgress` events logged while an execution is queued
+pub const QUEUE_TASK_ID: &str = "queue";
+
+/// Task id of the `TaskProgress` event logged when a failed workflow is
+/// re-run under its [`RestartPolicy`]
+pub const RESTART_TASK_ID: &str = "restart";
+
+/// How long a possible restart waits for the stderr parser to publish the
+/// last events of the exited process
+const STDERR_DRAIN_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How often `wait_for_process_exit` checks whether the process is done
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Parse the reserved `timeout` parameter; an empty value means no timeout
+pub(crate) fn parse_timeout(params: &HashMap<String, String>) -> Result<Option<Duration>> {
+    let Some(value) = params.get(TIMEOUT_PARAM).map(|v| v.trim()) else {
+        return Ok(None);
+    };
+    if value.is_empty() {
+        return Ok(None);
+    }
+    match value.parse::<u64>() {
+        Ok(secs) if secs > 0 => Ok(Some(Duration::from_secs(secs))),
+        _ => Err(anyhow!("Must be a positive number of seconds, got '{}'", value)),
+    }
+}
+
+/// Parse the reserved `env` parameter into `(key, value)` pairs
+///
+/// Accepts a JSON object (`{"API_BASE": "http://a,b"}`), or `KEY=VALUE`
+/// entries separated by commas or newlines, where `\` escapes the next
+/// character so values can hold commas (`LIST=a\,b`).
+pub(crate) fn parse_env(params: &HashMap<String, String>) -> Result<Vec<(String, String)>> {
+    let Some(value) = params.get(ENV_PARAM).map(|v| v.trim()) else {
+        return Ok(Vec::new());
+    };
+
+    let pairs: Vec<(String, String)> = if value.starts_with('{') {
+        let object: serde_json::Map<String, serde_json::Value> = serde_json::from_str(value)
+            .map_err(|e| anyhow!("Not a valid JSON object: {}", e))?;
+        object
+            .into_iter()
+            .map(|(key, value)| match value {
+                serde_json::Value::String(value) => (key, value),
+                value => (key, value.to_string()),
+            })
+            .collect()
+    } else {
+        split_env_entries(value)
+            .into_iter()
+            .map(|entry| match entry.split_once('=') {
+                Some((key, value)) => Ok((key.trim().to_string(), value.trim().to_string())),
+                None => Err(anyhow!("Entries must look like KEY=VALUE, got '{}'", entry)),
+            })
+            .collect::<Result<_>>()?
+    };
+
+    for (key, _) in &pairs {
+        if key.is_empty() || key.contains(|c: char| c.is_whitespace() || c == '=') {
+            return Err(anyhow!("Invalid variable name '{}'", key));
+        }
+    }
+    Ok(pairs)
+}
+
+/// Split `env` entries at unescaped commas and newlines, dropping the
+/// escaping backslashes and empty entries
+fn split_env_entries(value: &str) -> Vec<String> {
+    let mut entries = Vec::new();
+    let mut entry = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => entry.extend(chars.next()),
+            ',' | '\n' => entries.push(std::mem::take(&mut entry)),
+            c => entry.push(c),
+        }
+    }
+    entries.push(entry);
+    entries
+        .into_iter()
+        .map(|entry| entry.trim().to_string())
+        .filter(|entry| !entry.is_empty())
+        .collect()
+}
+
+/// Check that the reserved `cwd` parameter, when set, is an existing directory
+pub(crate) fn check_cwd(params: &HashMap<String, String>) -> Result<()> {
+    match params.get(CWD_PARAM).map(|v| v.trim()) {
+        Some(dir) if !dir.is_empty() && !Path::new(dir).is_dir() => {
+            Err(anyhow!("Not an existing directory: {}", dir))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Parse a concurrency cap; empty, zero or invalid values mean no cap
+pub(crate) fn parse_max_concurrent(value: &str) -> Option<usize> {
+    value.trim().parse::<usize>().ok().filter(|&n| n > 0)
+}
+
+/// Concurrency cap from `WF_MAX_CONCURRENT`, if set
+fn max_concurrent_from_env() -> Option<usize> {
+    let value = std::env::var(MAX_CONCURRENT_ENV).ok()?;
+    let limit = parse_max_concurrent(&value);
+    if limit.is_none() && !value.trim().is_empty() {
+        eprintln!(
+            "Warning: Ignoring {}='{}', expected a positive integer",
+            MAX_CONCURRENT_ENV, value
+        );
+    }
+    limit
+}
+
+/// Build CLI command from parameters
+///
+/// The reserved `cwd` and `env` parameters set the working directory and
+/// extend the environment of the command. Secret fields are passed in the
+/// environment too (see [`secret_env_var`]), never as arguments.
+pub(crate) fn build_command(
+    workflow: &DiscoveredWorkflow,
+    mut params: HashMap<String, String>,
+) -> Command {
+    // Workflows that declare `cwd` get the absolute path, so changing into it
+    // again from inside the process is harmless
+    let cwd = params
+        .get(CWD_PARAM)
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .map(|dir| std::fs::canonicalize(dir).unwrap_or_else(|_| PathBuf::from(dir)));
+    if let Some(dir) = &cwd {
+        params.insert(CWD_PARAM.to_string(), dir.display().to_string());
+    }
+
+    // Secrets go in the environment, out of the process list
+    let secrets: Vec<(String, String)> = workflow
+        .fields
+        .iter()
+        .filter(|field| is_secret(field))
+        .filter_map(|field| Some((secret_env_var(field), params.remove(&field.name)?)))
+        .filter(|(_, value)| !value.is_empty())
+        .collect();
+
+    let mut cmd = build_args(workflow, &params);
+    if let Some(dir) = cwd {
+        cmd.current_dir(dir);
+    }
+    // Invalid entries are rejected by validate_workflow_inputs
+    cmd.envs(parse_env(&params).unwrap_or_default());
+    cmd.envs(secrets);
+    // Own process group, so killing the workflow also stops the Claude CLI
+    // processes it started (see kill_process_tree)
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+
+    cmd
+}
+
+/// Program and arguments of a workflow's command
+fn build_args(workflow: &DiscoveredWorkflow, params: &HashMap<String, String>) -> Command {
+    // User-defined workflows run their command template instead of a binary
+    if let Some(template) = &workflow.command {
+        let args = expand_command_template(template, params);
+        // An empty program fails at spawn with a regular error
+        let mut cmd = Command::new(args.first().map(String::as_str).unwrap_or_default());
+        cmd.args(args.iter().skip(1));
+        return cmd;
+    }
+
+    let mut cmd = Command::new(&workflow.binary_path);
+    cmd.args(cli_args(&workflow.fields, params));
+    cmd
+}
+
+/// Quote a word for a POSIX shell, leaving plain words as they are
+pub(crate) fn shell_quote(word: &str) -> String {
+    let plain = !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-./:=,@%+".contains(c));
+    if plain {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', "'\\''"))
+    }
+}
+
+/// Render a command as a shell command line, e.g.
+/// `cd /repo && RUST_LOG=debug research_agent --phases 0`
+pub(crate) fn format_command(cmd: &Command) -> String {
+    let mut words = Vec::new();
+    if let Some(dir) = cmd.get_current_dir() {
+        words.push(format!("cd {} &&", shell_quote(&dir.to_string_lossy())));
+    }
+    for (key, value) in cmd.get_envs() {
+        if let Some(value) = value {
+            words.push(format!(
+                "{}={}",
+                key.to_string_lossy(),
+                shell_quote(&value.to_string_lossy())
+            ));
+        }
+    }
+    words.push(shell_quote(&cmd.get_program().to_string_lossy()));
+    words.extend(cmd.get_args().map(|arg| shell_quote(&arg.to_string_lossy())));
+    words.join(" ")
 }
 
 /// Process-based workflow runtime implementation
@@ -39,16 +467,26 @@ pub struct ProcessBasedRuntime {
     executions: Arc<Mutex<HashMap<Uuid, ExecutionState>>>,
     /// SQLite database for persistent workflow execution history
     database: Arc<Mutex<Database>>,
+    /// Slots shared by all executions when concurrency is capped
+    slots: Option<Arc<Semaphore>>,
 }
 
 impl ProcessBasedRuntime {
     /// Create runtime with pre-discovered workflows (avoids duplicate discovery)
+    ///
+    /// Concurrency is capped by `WF_MAX_CONCURRENT` when it is set.
     pub fn new_with_workflows(workflows: Vec<DiscoveredWorkflow>) -> Result<Self> {
-        let workflows_map: HashMap<String, DiscoveredWorkflow> = workflows
-            .into_iter()
-            .map(|w| (w.metadata.id.clone(), w))
-            .collect();
+        Self::new_with_workflows_and_limit(workflows, max_concurrent_from_env())
+    }
 
+    /// Create runtime that runs at most `max_concurrent` workflows at once
+    ///
+    /// Further executions are queued with status `NotStarted` until a
+    /// running one exits or is cancelled. `None` means no cap.
+    pub fn new_with_workflows_and_limit(
+        workflows: Vec<DiscoveredWorkflow>,
+        max_concurrent: Option<usize>,
+    ) -> Result<Self> {
         // Initialize database (same as new())
         let db_path = dirs::home_dir()
             .ok_or_else(|| anyhow!("Could not find home directory"))?
@@ -58,12 +496,27 @@ impl ProcessBasedRuntime {
         std::fs::create_dir_all(db_path.parent().unwrap())?;
 
         let database = Database::new(db_path)?;
+        let mut runtime = Self::with_database(workflows, database)?;
+        runtime.slots = max_concurrent
+            .filter(|&n| n > 0)
+            .map(|n| Arc::new(Semaphore::new(n)));
+        Ok(runtime)
+    }
+
+    /// Create runtime backed by an explicit database (e.g. `Database::new_in_memory()`)
+    pub fn with_database(workflows: Vec<DiscoveredWorkflow>, database: Database) -> Result<Self> {
+        let workflows_map: HashMap<String, DiscoveredWorkflow> = workflows
+            .into_iter()
+            .map(|w| (w.metadata.id.clone(), w))
+            .collect();
+
         database.initialize_schema()?;
 
         let runtime = Self {
             workflows: Arc::new(Mutex::new(workflows_map)),
             executions: Arc::new(Mutex::new(HashMap::new())),
             database: Arc::new(Mutex::new(database)),
+            slots: None,
         };
 
         // Restore from database
@@ -97,32 +550,217 @@ impl ProcessBasedRuntime {
         self.database.clone()
     }
 
+    /// Get reference to the discovered workflows, so new ones can be added
+    /// while running
+    pub fn get_workflow_registry(&self) -> Arc<Mutex<HashMap<String, DiscoveredWorkflow>>> {
+        self.workflows.clone()
+    }
+
     /// Clean up completed/failed workflow executions
     /// Removes execution state for workflows that have finished, freeing memory
     pub fn cleanup_completed_executions(&self) {
         let mut execs = self.executions.lock().unwrap();
-        execs.retain(|_, state| {
-            matches!(state.status, WorkflowStatus::Running)
-        });
+        execs.retain(|_, state| is_in_flight(&state.status));
+    }
+
+    /// Take a free slot without waiting
+    ///
+    /// `Ok(None)` when concurrency is not capped; `Err` carries the
+    /// semaphore to wait on when every slot is taken.
+    fn try_acquire_slot(
+        &self,
+    ) -> std::result::Result<Option<OwnedSemaphorePermit>, Arc<Semaphore>> {
+        match &self.slots {
+            None => Ok(None),
+            Some(slots) => slots
+                .clone()
+                .try_acquire_owned()
+                .map(Some)
+                .map_err(|_| slots.clone()),
+        }
     }
 
-    /// Build CLI command from parameters
-    fn build_command(
+    /// Start a pipeline execution; stages run sequentially in a background task
+    fn execute_pipeline(
         &self,
         workflow: &DiscoveredWorkflow,
+        pipeline: PipelineDefinition,
         params: HashMap<String, String>,
-    ) -> Command {
-        let mut cmd = Command::new(&workflow.binary_path);
+        timeout: Option<Duration>,
+    ) -> WorkflowResult<WorkflowHandle> {
+        let exec_id = Uuid::new_v4();
 
-        for field in &workflow.fields {
-            if let Some(value) = params.get(&field.name) {
-                if !value.is_empty() {
-                    cmd.arg(&field.cli_arg).arg(value);
+        // The whole pipeline holds one slot; its stages run one at a time
+        let slot = self.try_acquire_slot();
+        let status = if slot.is_ok() {
+            WorkflowStatus::Running
+        } else {
+            WorkflowStatus::NotStarted
+        };
+
+        // Its child holds the currently running stage process
+        let mut state = ExecutionState::new(
+            workflow.metadata.id.clone(),
+            workflow.metadata.name.clone(),
+            workflow.binary_path.clone(),
+            status.clone(),
+        );
+        state.params = params.clone();
+        state.secrets = secret_values(&workflow.fields, &params);
+        self.executions.lock().unwrap().insert(exec_id, state);
+
+        // Persist execution to database
+        {
+            let db = self.database.lock().unwrap();
+            let persisted = PersistedExecution {
+                id: exec_id,
+                workflow_id: workflow.metadata.id.clone(),
+                workflow_name: workflow.metadata.name.clone(),
+                status: status.clone(),
+                start_time: Local::now(),
+                end_time: None,
+                exit_code: None,
+                binary_path: workflow.binary_path.clone(),
+                created_at: Local::now(),
+                updated_at: Local::now(),
+                input_tokens: None,
+                output_tokens: None,
+                total_cost_usd: None,
+            };
+
+            if let Err(e) = db.insert_execution(&persisted) {
+                eprintln!("Warning: Failed to persist execution to database: {}", e);
+            }
+
+            // Secrets are kept in memory only
+            let stored_params = without_secrets(&workflow.fields, &params);
+            if let Err(e) = db.insert_params(&exec_id, &stored_params) {
+                eprintln!("Warning: Failed to persist params to database: {}", e);
+            }
+        }
+
+        if slot.is_err() {
+            log_queued(&exec_id, &self.executions, &self.database);
+        }
+
+        let workflows = self.workflows.clone();
+        let executions = self.executions.clone();
+        let database = self.database.clone();
+        tokio::spawn(async move {
+            let _slot = match slot {
+                Ok(slot) => slot,
+                Err(slots) => match wait_for_slot(exec_id, slots, &executions, &database).await {
+                    Some(slot) => Some(slot),
+                    None => return, // Cancelled while queued
+                },
+            };
+
+            // The watchdog kills whichever stage has gone quiet
+            let logs_rx = timeout.and_then(|_| {
+                let execs = executions.lock().unwrap();
+                execs.get(&exec_id).map(|state| state.logs_tx.subscribe())
+            });
+            if let (Some(timeout), Some(logs_rx)) = (timeout, logs_rx) {
+                tokio::spawn(watch_for_timeout(
+                    exec_id,
+                    timeout,
+                    logs_rx,
+                    executions.clone(),
+                    database.clone(),
+                ));
+            }
+
+            if let Err(e) =
+                run_pipeline(exec_id, pipeline, params, workflows, executions.clone(), database)
+                    .await
+            {
+                eprintln!("Error running pipeline: {}", e);
+                let mut execs = executions.lock().unwrap();
+                if let Some(state) = execs.get_mut(&exec_id) {
+                    state.set_status(WorkflowStatus::Failed);
                 }
             }
+        });
+
+        Ok(WorkflowHandle::new(exec_id, workflow.metadata.id.clone()))
+    }
+
+    /// Stop (`paused`) or continue the process group of an execution and
+    /// move it between `Running` and `Paused`
+    ///
+    /// A paused execution keeps its concurrency slot and is exempt from the
+    /// inactivity timeout.
+    fn set_paused(&self, handle_id: &Uuid, paused: bool) -> WorkflowResult<()> {
+        let (from, to) = if paused {
+            (WorkflowStatus::Running, WorkflowStatus::Paused)
+        } else {
+            (WorkflowStatus::Paused, WorkflowStatus::Running)
+        };
+
+        let mut executions = self.executions.lock().unwrap();
+        let state = executions
+            .get_mut(handle_id)
+            .ok_or_else(|| execution_not_found(handle_id))?;
+        if state.status != from {
+            return Err(match state.stopped {
+                Some(reason) => reason.into(),
+                None if !paused && state.status == WorkflowStatus::Running => {
+                    WorkflowError::AlreadyRunning(handle_id.to_string())
+                }
+                None => WorkflowError::other(format!(
+                    "Execution {} is not {}",
+                    handle_id,
+                    if paused { "running" } else { "paused" }
+                )),
+            });
         }
+        // Pipelines have no process between stages
+        let child = state.child.as_ref().ok_or_else(|| no_process(handle_id, state))?;
+        signal_process_group(child, paused)?;
+        state.set_status(to);
 
-        cmd
+        let db = self.database.lock().unwrap();
+        if let Err(e) = db.update_execution(handle_id, state.status.clone(), None, None) {
+            eprintln!("Warning: Failed to update execution in database: {}", e);
+        }
+        Ok(())
+    }
+
+    /// Summary of an execution, from memory while it is tracked there and
+    /// from the database otherwise
+    fn execution_summary(&self, handle_id: &Uuid) -> WorkflowResult<ExecutionSummary> {
+        if let Some(state) = self.executions.lock().unwrap().get(handle_id) {
+            return Ok(ExecutionSummary {
+                id: *handle_id,
+                workflow_id: state.workflow_id.clone(),
+                workflow_name: state.workflow_name.clone(),
+                status: state.status.clone(),
+                start_time: state.start_time,
+                end_time: state.end_time,
+                exit_code: state.exit_code,
+                input_tokens: state.usage.map(|u| u.input_tokens),
+                output_tokens: state.usage.map(|u| u.output_tokens),
+                total_cost_usd: state.usage.and_then(|u| u.cost_usd),
+            });
+        }
+
+        let db = self.database.lock().unwrap();
+        let exec = db
+            .get_execution(handle_id)
+            .map_err(WorkflowError::other)?
+            .ok_or_else(|| execution_not_found(handle_id))?;
+        Ok(ExecutionSummary {
+            id: exec.id,
+            workflow_id: exec.workflow_id,
+            workflow_name: exec.workflow_name,
+            status: exec.status,
+            start_time: exec.start_time,
+            end_time: exec.end_time,
+            exit_code: exec.exit_code,
+            input_tokens: exec.input_tokens,
+            output_tokens: exec.output_tokens,
+            total_cost_usd: exec.total_cost_usd,
+        })
     }
 
     /// Restore past executions from database on startup
@@ -134,8 +772,9 @@ impl ProcessBasedRuntime {
 
         let mut executions = self.executions.lock().unwrap();
         for mut exec in persisted {
-            // Skip Running status - these are stale from previous session
-            if exec.status == WorkflowStatus::Running {
+            // In-flight executions, including queued ones, are stale from the
+            // previous session: their process and slot are gone
+            if is_in_flight(&exec.status) {
                 // Mark as Failed since app was restarted
                 exec.status = WorkflowStatus::Failed;
                 exec.end_time = Some(exec.start_time); // Approximate end time
@@ -155,21 +794,22 @@ impl ProcessBasedRuntime {
             // Load params from database for this execution
             let params = db.get_params(&exec.id)?;
 
-            // Convert to ExecutionState and load into memory
-            let (logs_tx, _) = broadcast::channel(1000);
-            let state = ExecutionState {
-                workflow_id: exec.workflow_id.clone(),
-                workflow_name: exec.workflow_name.clone(),
-                status: exec.status.clone(),
-                child: None, // Cannot restore running process
-                logs_tx,
-                binary_path: exec.binary_path.clone(),
-                logs_buffer: Arc::new(Mutex::new(logs)),
-                start_time: exec.start_time,
-                end_time: exec.end_time,
-                params,
-                exit_code: exec.exit_code,
-            };
+            // Convert to ExecutionState and load into memory; the process
+            // cannot be restored, and secrets were never persisted, so a
+            // restored execution has none to redact
+            let mut state = ExecutionState::new(
+                exec.workflow_id.clone(),
+                exec.workflow_name.clone(),
+                exec.binary_path.clone(),
+                exec.status.clone(),
+            );
+            state.logs_buffer = Arc::new(Mutex::new(logs));
+            state.start_time = exec.start_time;
+            state.end_time = exec.end_time;
+            state.params = params;
+            state.exit_code = exec.exit_code;
+            state.usage =
+                UsageTotals::from_parts(exec.input_tokens, exec.output_tokens, exec.total_cost_usd);
             executions.insert(exec.id, state);
         }
 
@@ -199,7 +839,7 @@ impl WorkflowRuntime for ProcessBasedRuntime {
                 metadata: w.metadata.clone(),
                 fields: w.fields.clone(),
             })
-            .ok_or_else(|| format!("Workflow '{}' not found", id).into())
+            .ok_or_else(|| workflow_not_found(id))
     }
 
     fn validate_workflow_inputs(
@@ -210,16 +850,98 @@ impl WorkflowRuntime for ProcessBasedRuntime {
         let workflows = self.workflows.lock().unwrap();
         let workflow = workflows
             .get(id)
-            .ok_or_else(|| format!("Workflow '{}' not found", id))?;
+            .ok_or_else(|| workflow_not_found(id))?;
+
+        // Collect every problem instead of stopping at the first one
+        let mut problems = Vec::new();
+        let mut problem = |field: &str, message: String| {
+            problems.push(FieldError {
+                field: field.to_string(),
+                message,
+            })
+        };
+
+        let declares = |name: &str| workflow.fields.iter().any(|f| f.name == name);
+
+        let mut unknown: Vec<&String> = params
+            .keys()
+            .filter(|key| {
+                !declares(key) && ![TIMEOUT_PARAM, CWD_PARAM, ENV_PARAM].contains(&key.as_str())
+            })
+            .collect();
+        unknown.sort();
+        for key in unknown {
+            problem(key, "Unknown parameter".to_string());
+        }
 
-        // Check required fields
+        if !declares(TIMEOUT_PARAM) {
+            if let Err(e) = parse_timeout(&params) {
+                problem(TIMEOUT_PARAM, e.to_string());
+            }
+        }
+        if let Err(e) = check_cwd(&params) {
+            problem(CWD_PARAM, e.to_string());
+        }
+        if let Err(e) = parse_env(&params) {
+            problem(ENV_PARAM, e.to_string());
+        }
+
+        // Required fields (relative to the selected phases), types, ranges,
+        // patterns, options and file existence
         for field in &workflow.fields {
-            if field.required && !params.contains_key(&field.name) {
-                return Err(format!("Required field '{}' missing", field.name).into());
+            if let Some(error) = validate_field(field, &params) {
+                problem(&field.name, error);
             }
         }
 
-        Ok(())
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(WorkflowError::Validation(problems))
+        }
+    }
+
+    fn preview_command(
+        &self,
+        id: &str,
+        params: HashMap<String, String>,
+    ) -> WorkflowResult<String> {
+        let workflows = self.workflows.lock().unwrap();
+        let workflow = workflows
+            .get(id)
+            .ok_or_else(|| workflow_not_found(id))?;
+
+        let Some(pipeline) = &workflow.pipeline else {
+            let secrets = secret_values(&workflow.fields, &params);
+            let command = format_command(&build_command(workflow, params));
+            return Ok(redact_secrets(&command, &secrets));
+        };
+
+        let mut lines = Vec::new();
+        for (idx, stage) in pipeline.stages.iter().enumerate() {
+            let stage_workflow = workflows
+                .get(&stage.workflow_id)
+                .ok_or_else(|| workflow_not_found(&stage.workflow_id))?;
+            let stage_params = preview_stage_params(stage, &params)
+                .map_err(|e| {
+                    WorkflowError::Validation(vec![FieldError {
+                        field: format!("stage {}", idx),
+                        message: e.to_string(),
+                    }])
+                })?;
+            lines.push(format!(
+                "# Stage {}: {}",
+                idx,
+                stage
+                    .name
+                    .as_deref()
+                    .unwrap_or(&stage_workflow.metadata.name)
+            ));
+            let secrets = secret_values(&stage_workflow.fields, &stage_params);
+            let command = format_command(&build_command(stage_workflow, stage_params));
+            lines.push(redact_secrets(&command, &secrets));
+        }
+        Ok(lines.join("\n"))
     }
 
     async fn execute_workflow(
@@ -236,40 +958,49 @@ impl WorkflowRuntime for ProcessBasedRuntime {
             workflows
                 .get(id)
                 .cloned()
-                .ok_or_else(|| format!("Workflow '{}' not found", id))?
+                .ok_or_else(|| workflow_not_found(id))?
         };
 
-        // Build command
-        let mut cmd = self.build_command(&workflow, params.clone());
-        cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+        let timeout = if workflow.fields.iter().any(|f| f.name == TIMEOUT_PARAM) {
+            None
+        } else {
+            parse_timeout(&params).map_err(|e| {
+                WorkflowError::Validation(vec![FieldError {
+                    field: TIMEOUT_PARAM.to_string(),
+                    message: e.to_string(),
+                }])
+            })?
+        };
 
-        // Spawn process
-        let child = cmd
-            .spawn()
-            .map_err(|e| anyhow!("Failed to spawn workflow process: {}", e))?;
+        if let Some(pipeline) = workflow.pipeline.clone() {
+            return self.execute_pipeline(&workflow, pipeline, params, timeout);
+        }
 
-        // Create broadcast channel for logs (capacity 1000)
-        // Increased from 100 to reduce lagging in high-frequency workflows
-        let (logs_tx, _) = broadcast::channel(1000);
+        // Spawn process now if a slot is free, otherwise queue it
+        let slot = self.try_acquire_slot();
+        let child = match slot {
+            Ok(_) => Some(spawn_workflow_process(&workflow, &params)?),
+            Err(_) => None,
+        };
+        let status = if child.is_some() {
+            WorkflowStatus::Running
+        } else {
+            WorkflowStatus::NotStarted
+        };
 
         // Generate execution ID
         let exec_id = Uuid::new_v4();
 
         // Store execution state
-        let logs_buffer = Arc::new(Mutex::new(Vec::new()));
-        let state = ExecutionState {
-            workflow_id: id.to_string(),
-            workflow_name: workflow.metadata.name.clone(),
-            status: WorkflowStatus::Running,
-            child: Some(child),
-            logs_tx: logs_tx.clone(),
-            binary_path: workflow.binary_path.clone(),
-            logs_buffer: logs_buffer.clone(),
-            start_time: Local::now(),
-            end_time: None,
-            params: params.clone(),
-            exit_code: None,
-        };
+        let mut state = ExecutionState::new(
+            id.to_string(),
+            workflow.metadata.name.clone(),
+            workflow.binary_path.clone(),
+            status.clone(),
+        );
+        state.child = child;
+        state.params = params.clone();
+        state.secrets = secret_values(&workflow.fields, &params);
         self.executions.lock().unwrap().insert(exec_id, state);
 
         // Persist execution to database
@@ -279,64 +1010,86 @@ impl WorkflowRuntime for ProcessBasedRuntime {
                 id: exec_id,
                 workflow_id: workflow.metadata.id.clone(),
                 workflow_name: workflow.metadata.name.clone(),
-                status: WorkflowStatus::Running,
+                status,
                 start_time: Local::now(),
                 end_time: None,
                 exit_code: None,
                 binary_path: workflow.binary_path.clone(),
                 created_at: Local::now(),
                 updated_at: Local::now(),
+                input_tokens: None,
+                output_tokens: None,
+                total_cost_usd: None,
             };
 
             if let Err(e) = db.insert_execution(&persisted) {
                 eprintln!("Warning: Failed to persist execution to database: {}", e);
             }
 
-            // Persist params
-            if let Err(e) = db.insert_params(&exec_id, &params) {
+            // Persist params; secrets are kept in memory only
+            let stored_params = without_secrets(&workflow.fields, &params);
+            if let Err(e) = db.insert_params(&exec_id, &stored_params) {
                 eprintln!("Warning: Failed to persist params to database: {}", e);
             }
         }
 
-        // Spawn stderr parser task
-        let executions_stderr = self.executions.clone();
-        let database_stderr = self.database.clone();
-        let exec_id_stderr = exec_id;
-        tokio::spawn(async move {
-            if let Err(e) = parse_workflow_stderr(exec_id_stderr, executions_stderr.clone(), database_stderr).await {
-                eprintln!("Error parsing workflow stderr: {}", e);
-                // Mark execution as failed
-                let mut execs = executions_stderr.lock().unwrap();
-                if let Some(state) = execs.get_mut(&exec_id_stderr) {
-                    state.status = WorkflowStatus::Failed;
-                }
-            }
-        });
-
-        // Spawn stdout parser task
-        let executions_stdout = self.executions.clone();
-        let database_stdout = self.database.clone();
-        let exec_id_stdout = exec_id;
-        tokio::spawn(async move {
-            if let Err(e) = parse_workflow_stdout(exec_id_stdout, executions_stdout.clone(), database_stdout).await {
-                eprintln!("Error parsing workflow stdout: {}", e);
-                // Mark execution as failed
-                let mut execs = executions_stdout.lock().unwrap();
-                if let Some(state) = execs.get_mut(&exec_id_stdout) {
-                    state.status = WorkflowStatus::Failed;
-                }
-            }
-        });
-
-        // Spawn task to wait for process exit and update status
-        let executions_wait = self.executions.clone();
-        let database_wait = self.database.clone();
-        let exec_id_wait = exec_id;
-        tokio::spawn(async move {
-            if let Err(e) = wait_for_process_exit(exec_id_wait, executions_wait, database_wait).await {
-                eprintln!("Error waiting for process exit: {}", e);
+        let restart = workflow
+            .metadata
+            .restart_on_failure
+            .map(|policy| Restart::new(workflow.clone(), policy));
+        match slot {
+            Ok(slot) => start_workflow_tasks(
+                exec_id,
+                timeout,
+                restart,
+                slot,
+                self.executions.clone(),
+                self.database.clone(),
+            ),
+            Err(slots) => {
+                log_queued(&exec_id, &self.executions, &self.database);
+
+                let executions = self.executions.clone();
+                let database = self.database.clone();
+                tokio::spawn(async move {
+                    let Some(slot) = wait_for_slot(exec_id, slots, &executions, &database).await
+                    else {
+                        return; // Cancelled while queued
+                    };
+
+                    let child = match spawn_workflow_process(&workflow, &params) {
+                        Ok(child) => child,
+                        Err(e) => {
+                            fail_execution(&exec_id, &e.to_string(), &executions, &database);
+                            return;
+                        }
+                    };
+                    {
+                        let mut execs = executions.lock().unwrap();
+                        match execs.get_mut(&exec_id) {
+                            Some(state) if state.status == WorkflowStatus::Running => {
+                                state.child = Some(child);
+                            }
+                            // Cancelled while the process was being spawned
+                            _ => {
+                                let mut child = child;
+                                kill_process_tree(&mut child);
+                                return;
+                            }
+                        }
+                    }
+
+                    start_workflow_tasks(
+                        exec_id,
+                        timeout,
+                        restart,
+                        Some(slot),
+                        executions,
+                        database,
+                    );
+                });
             }
-        });
+        }
 
         Ok(WorkflowHandle::new(exec_id, id.to_string()))
     }
@@ -348,10 +1101,24 @@ impl WorkflowRuntime for ProcessBasedRuntime {
         let executions = self.executions.lock().unwrap();
         let state = executions
             .get(handle_id)
-            .ok_or_else(|| anyhow!("Execution not found: {}", handle_id))?;
+            .ok_or_else(|| execution_not_found(handle_id))?;
         Ok(state.logs_tx.subscribe())
     }
 
+    async fn subscribe_logs_with_backlog(
+        &self,
+        handle_id: &Uuid,
+    ) -> WorkflowResult<(Vec<WorkflowLog>, broadcast::Receiver<WorkflowLog>)> {
+        let executions = self.executions.lock().unwrap();
+        let state = executions
+            .get(handle_id)
+            .ok_or_else(|| execution_not_found(handle_id))?;
+        // Writers push and send under the buffer lock (see `publish_log`), so
+        // nothing lands between the snapshot and the subscription
+        let logs = state.logs_buffer.lock().unwrap();
+        Ok((logs.clone(), state.logs_tx.subscribe()))
+    }
+
     async fn get_logs(&self, handle_id: &Uuid, limit: Option<usize>) -> WorkflowResult<Vec<WorkflowLog>> {
         // Try in-memory first (for running workflows)
         {
@@ -369,15 +1136,45 @@ impl WorkflowRuntime for ProcessBasedRuntime {
 
         // Not in memory, try database
         let db = self.database.lock().unwrap();
-        db.get_logs(handle_id, limit)
-            .map_err(|e| e.into())
+        db.get_logs(handle_id, limit).map_err(WorkflowError::other)
     }
 
-    async fn get_status(&self, handle_id: &Uuid) -> WorkflowResult<WorkflowStatus> {
-        // Try in-memory first
-        {
-            let executions = self.executions.lock().unwrap();
-            if let Some(state) = executions.get(handle_id) {
+    async fn get_logs_since(
+        &self,
+        handle_id: &Uuid,
+        after_index: Option<usize>,
+    ) -> WorkflowResult<LogPage> {
+        let start = after_index.map_or(0, |index| index + 1);
+        let page = |logs: &[WorkflowLog], status: WorkflowStatus| LogPage {
+            logs: logs.get(start..).unwrap_or_default().to_vec(),
+            latest_index: logs.len().checked_sub(1),
+            status,
+        };
+
+        // In memory, read the buffer behind subscribe_logs
+        {
+            let executions = self.executions.lock().unwrap();
+            if let Some(state) = executions.get(handle_id) {
+                let logs = state.logs_buffer.lock().unwrap();
+                return Ok(page(&logs[..], state.status.clone()));
+            }
+        }
+
+        let db = self.database.lock().unwrap();
+        let status = db
+            .get_execution(handle_id)
+            .map_err(WorkflowError::other)?
+            .map(|exec| exec.status)
+            .ok_or_else(|| execution_not_found(handle_id))?;
+        let logs = db.get_logs(handle_id, None).map_err(WorkflowError::other)?;
+        Ok(page(&logs[..], status))
+    }
+
+    async fn get_status(&self, handle_id: &Uuid) -> WorkflowResult<WorkflowStatus> {
+        // Try in-memory first
+        {
+            let executions = self.executions.lock().unwrap();
+            if let Some(state) = executions.get(handle_id) {
                 return Ok(state.status.clone());
             }
         }
@@ -385,27 +1182,108 @@ impl WorkflowRuntime for ProcessBasedRuntime {
         // Not in memory, try database
         let db = self.database.lock().unwrap();
         db.get_execution(handle_id)
-            .map_err(|e: anyhow::Error| -> Box<dyn std::error::Error + Send + Sync> { e.into() })?
+            .map_err(WorkflowError::other)?
             .map(|exec| exec.status)
-            .ok_or_else(|| anyhow!("Execution not found: {}", handle_id).into())
+            .ok_or_else(|| execution_not_found(handle_id))
+    }
+
+    async fn list_running(&self) -> WorkflowResult<Vec<(Uuid, String, WorkflowStatus)>> {
+        let executions = self.executions.lock().unwrap();
+        let mut running: Vec<_> = executions
+            .iter()
+            .filter(|(_, state)| is_in_flight(&state.status))
+            .collect();
+        running.sort_by_key(|(_, state)| state.start_time);
+        Ok(running
+            .into_iter()
+            .map(|(id, state)| (*id, state.workflow_id.clone(), state.status.clone()))
+            .collect())
+    }
+
+    async fn wait_for_completion(&self, handle_id: &Uuid) -> WorkflowResult<WorkflowStatus> {
+        let status_rx = self
+            .executions
+            .lock()
+            .unwrap()
+            .get(handle_id)
+            .map(|state| state.status_tx.subscribe());
+        // Executions of earlier sessions are only in the database, finished
+        let Some(mut status_rx) = status_rx else {
+            return self.get_status(handle_id).await;
+        };
+        let finished = status_rx
+            .wait_for(|status| !is_in_flight(status))
+            .await
+            .map(|status| status.clone());
+        match finished {
+            Ok(status) => Ok(status),
+            // The state was dropped; the database has the last status
+            Err(_) => self.get_status(handle_id).await,
+        }
+    }
+
+    async fn get_execution(&self, handle_id: &Uuid) -> WorkflowResult<ExecutionSummary> {
+        self.execution_summary(handle_id)
     }
 
     async fn cancel_workflow(&self, handle_id: &Uuid) -> WorkflowResult<()> {
         let mut executions = self.executions.lock().unwrap();
         let state = executions
             .get_mut(handle_id)
-            .ok_or_else(|| anyhow!("Execution not found: {}", handle_id))?;
-
+            .ok_or_else(|| execution_not_found(handle_id))?;
+
+        // A Running execution without a child just exited and its status is
+        // not recorded yet; cancelling keeps it from being restarted. A
+        // NotStarted one is still queued, and its waiting task sees this and
+        // gives up its slot.
+        let cancellable = state.child.is_some()
+            || matches!(state.status, WorkflowStatus::Running | WorkflowStatus::NotStarted);
+        if !cancellable {
+            return Ok(());
+        }
         if let Some(mut child) = state.child.take() {
-            let _ = child.kill();
-            state.status = WorkflowStatus::Failed;
-            state.end_time = Some(Local::now());
             // exit_code remains None when killed
+            kill_process_tree(&mut child);
+        }
+        state.set_status(WorkflowStatus::Failed);
+        state.end_time = Some(Local::now());
+        state.stopped = Some(StopReason::Cancelled);
+
+        let db = self.database.lock().unwrap();
+        if let Err(e) = db.update_execution(handle_id, WorkflowStatus::Failed, state.end_time, None)
+        {
+            eprintln!("Warning: Failed to update execution in database: {}", e);
         }
 
         Ok(())
     }
 
+    async fn pause_workflow(&self, handle_id: &Uuid) -> WorkflowResult<()> {
+        self.set_paused(handle_id, true)
+    }
+
+    async fn resume_workflow(&self, handle_id: &Uuid) -> WorkflowResult<()> {
+        self.set_paused(handle_id, false)
+    }
+
+    async fn cancel_task(&self, handle_id: &Uuid, task_id: &str) -> WorkflowResult<()> {
+        let executions = self.executions.lock().unwrap();
+        let state = executions
+            .get(handle_id)
+            .ok_or_else(|| execution_not_found(handle_id))?;
+        let control_tx = state.control_tx.as_ref().filter(|_| state.child.is_some());
+        let Some(control_tx) = control_tx else {
+            return Err(no_process(handle_id, state));
+        };
+
+        // Queued for the writer task, so a full pipe never blocks the lock
+        control_tx
+            .send(WorkflowControl::CancelTask(task_id.to_string()))
+            .map_err(|_| {
+                WorkflowError::other(format!("Execution {} no longer reads its stdin", handle_id))
+            })
+    }
+
     async fn list_executions(
         &self,
         limit: usize,
@@ -416,7 +1294,9 @@ impl WorkflowRuntime for ProcessBasedRuntime {
         let db = self.database.lock().unwrap();
         let persisted = db
             .list_executions(limit, offset, workflow_id.as_deref())
-            .map_err(|e| anyhow!("Failed to list executions from database: {}", e))?;
+            .map_err(|e| {
+                WorkflowError::other(format!("Failed to list executions from database: {}", e))
+            })?;
 
         // Convert to lightweight summaries
         let summaries = persisted
@@ -429,6 +1309,9 @@ impl WorkflowRuntime for ProcessBasedRuntime {
                 start_time: exec.start_time,
                 end_time: exec.end_time,
                 exit_code: exec.exit_code,
+                input_tokens: exec.input_tokens,
+                output_tokens: exec.output_tokens,
+                total_cost_usd: exec.total_cost_usd,
             })
             .collect();
 
@@ -436,75 +1319,516 @@ impl WorkflowRuntime for ProcessBasedRuntime {
     }
 
     async fn get_params(&self, handle_id: &Uuid) -> WorkflowResult<HashMap<String, String>> {
-        // First check if execution is in memory (running)
-        {
+        // First check if execution is in memory (running); its params still
+        // hold the secrets, for restarts, which callers must not see
+        let running = {
             let executions = self.executions.lock().unwrap();
-            if let Some(state) = executions.get(handle_id) {
-                return Ok(state.params.clone());
-            }
+            executions.get(handle_id).map(|state| {
+                (state.workflow_id.clone(), state.params.clone(), state.secrets.clone())
+            })
+        };
+        if let Some((workflow_id, params, secrets)) = running {
+            let workflows = self.workflows.lock().unwrap();
+            let fields = workflows
+                .get(&workflow_id)
+                .map(|workflow| workflow.fields.as_slice())
+                .unwrap_or_default();
+            // Matching the values too covers a workflow reloaded since
+            let mut params = without_secrets(fields, &params);
+            params.retain(|_, value| !secrets.iter().any(|secret| secret == value.trim()));
+            return Ok(params);
         }
 
         // Not in memory, query database
         let db = self.database.lock().unwrap();
-        db.get_params(handle_id)
-            .map_err(|e| anyhow!("Failed to get params from database: {}", e).into())
+        db.get_params(handle_id).map_err(|e| {
+            WorkflowError::other(format!("Failed to get params from database: {}", e))
+        })
+    }
+}
+
+/// Exit code of a finished process; a process killed by a signal gets the
+/// shell convention `128 + signal`
+pub(crate) fn exit_code_of(status: &ExitStatus) -> Option<i32> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            return Some(128 + signal);
+        }
+    }
+    status.code()
+}
+
+/// Kill a workflow process together with everything it started, and reap it
+///
+/// Workflows run in their own process group, so on Unix the whole group is
+/// killed; otherwise the Claude CLI subprocesses would outlive the workflow.
+pub(crate) fn kill_process_tree(child: &mut Child) {
+    #[cfg(unix)]
+    {
+        // SAFETY: killpg only sends a signal; the group id is the pid of a
+        // child that has not been reaped yet, so it cannot have been reused
+        unsafe {
+            libc::killpg(child.id() as libc::pid_t, libc::SIGKILL);
+        }
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Like [`Child::try_wait`], but once the workflow exited, whatever is left
+/// of its process group (e.g. Claude CLI processes) is killed first
+///
+/// The group is killed before the workflow is reaped, while its pid still
+/// reserves the group id.
+pub(crate) fn try_wait_process_tree(child: &mut Child) -> std::io::Result<Option<ExitStatus>> {
+    #[cfg(unix)]
+    {
+        // SAFETY: waitid with WNOWAIT only inspects the child without
+        // reaping it, writing into the zeroed siginfo_t we own
+        let exited = unsafe {
+            let mut info: libc::siginfo_t = std::mem::zeroed();
+            libc::waitid(
+                libc::P_PID,
+                child.id() as libc::id_t,
+                &mut info,
+                libc::WEXITED | libc::WNOHANG | libc::WNOWAIT,
+            ) == 0
+                && info.si_pid() != 0
+        };
+        if exited {
+            // SAFETY: killpg only sends a signal; the group id cannot have
+            // been reused since the child is not reaped yet
+            unsafe {
+                libc::killpg(child.id() as libc::pid_t, libc::SIGKILL);
+            }
+        }
+    }
+    child.try_wait()
+}
+
+/// Send `SIGSTOP` (`stop`) or `SIGCONT` to the process group of a workflow
+fn signal_process_group(child: &Child, stop: bool) -> WorkflowResult<()> {
+    #[cfg(unix)]
+    {
+        let signal = if stop { libc::SIGSTOP } else { libc::SIGCONT };
+        // SAFETY: as in kill_process_tree, the child has not been reaped
+        if unsafe { libc::killpg(child.id() as libc::pid_t, signal) } != 0 {
+            return Err(WorkflowError::other(std::io::Error::last_os_error()));
+        }
+        Ok(())
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (child, stop);
+        Err(WorkflowError::other(
+            "Pausing workflows needs Unix process signals and is not supported here",
+        ))
+    }
+}
+
+/// Spawn a workflow process with piped output
+fn spawn_workflow_process(
+    workflow: &DiscoveredWorkflow,
+    params: &HashMap<String, String>,
+) -> WorkflowResult<Child> {
+    let mut cmd = build_command(workflow, params.clone());
+    // stdin carries control messages, see `cancel_task`
+    cmd.stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    cmd.spawn().map_err(WorkflowError::Spawn)
+}
+
+/// Queued, running or paused, i.e. still holding or waiting for a slot
+fn is_in_flight(status: &WorkflowStatus) -> bool {
+    matches!(
+        status,
+        WorkflowStatus::Running | WorkflowStatus::NotStarted | WorkflowStatus::Paused
+    )
+}
+
+fn workflow_not_found(id: &str) -> WorkflowError {
+    WorkflowError::NotFound(format!("Workflow '{}'", id))
+}
+
+fn execution_not_found(handle_id: &Uuid) -> WorkflowError {
+    WorkflowError::NotFound(format!("Execution {}", handle_id))
+}
+
+/// Error of an operation that needs the process of an execution that has
+/// none, saying why if the runtime stopped it
+fn no_process(handle_id: &Uuid, state: &ExecutionState) -> WorkflowError {
+    match state.stopped {
+        Some(reason) => reason.into(),
+        None => WorkflowError::other(format!("Execution {} has no process right now", handle_id)),
+    }
+}
+
+/// Re-running of a failed workflow under its [`RestartPolicy`]
+struct Restart {
+    workflow: DiscoveredWorkflow,
+    policy: RestartPolicy,
+    /// Restarts so far
+    count: usize,
+}
+
+impl Restart {
+    fn new(workflow: DiscoveredWorkflow, policy: RestartPolicy) -> Self {
+        Self {
+            workflow,
+            policy,
+            count: 0,
+        }
+    }
+}
+
+/// How a workflow process ended, as seen by `wait_for_process_exit`
+enum ProcessExit {
+    /// The final status is recorded, here or by whoever killed the process
+    Finished,
+    /// It failed and a new process took its place
+    Restarted,
+}
+
+/// Start the output parsers, timeout watchdog and exit watcher of a spawned
+/// workflow; `slot` is released once the process exits or is killed
+///
+/// With `restart`, a failed process is spawned again and gets new parsers;
+/// the watchdog keeps watching the execution across restarts.
+fn start_workflow_tasks(
+    exec_id: Uuid,
+    timeout: Option<Duration>,
+    mut restart: Option<Restart>,
+    slot: Option<OwnedSemaphorePermit>,
+    executions: Arc<Mutex<HashMap<Uuid, ExecutionState>>>,
+    database: Arc<Mutex<Database>>,
+) {
+    // Subscribe before the parsers start so the watchdog sees every event
+    let timeout_rx = timeout.and_then(|timeout| {
+        let execs = executions.lock().unwrap();
+        execs
+            .get(&exec_id)
+            .map(|state| (timeout, state.logs_tx.subscribe()))
+    });
+
+    let mut stderr_parser = start_output_parsers(exec_id, &executions, &database);
+
+    // Spawn watchdog killing the process when it goes quiet for too long
+    if let Some((timeout, logs_rx)) = timeout_rx {
+        tokio::spawn(watch_for_timeout(
+            exec_id,
+            timeout,
+            logs_rx,
+            executions.clone(),
+            database.clone(),
+        ));
+    }
+
+    // Spawn task to wait for process exit and update status
+    tokio::spawn(async move {
+        loop {
+            let exit = wait_for_process_exit(
+                exec_id,
+                &executions,
+                &database,
+                restart.as_mut(),
+                stderr_parser,
+            )
+            .await;
+            match exit {
+                Ok(ProcessExit::Restarted) => {
+                    stderr_parser = start_output_parsers(exec_id, &executions, &database);
+                }
+                Ok(ProcessExit::Finished) => break,
+                Err(e) => {
+                    eprintln!("Error waiting for process exit: {}", e);
+                    break;
+                }
+            }
+        }
+        drop(slot);
+    });
+}
+
+/// Write the control messages sent on the returned channel to `stdin`, one
+/// JSON line each, until the process stops reading
+///
+/// Writing from a task of its own keeps a workflow that doesn't read its
+/// stdin from blocking the caller once the pipe is full.
+pub(crate) fn start_control_writer(
+    stdin: Option<std::process::ChildStdin>,
+) -> Option<mpsc::UnboundedSender<WorkflowControl>> {
+    let mut stdin = tokio::process::ChildStdin::from_std(stdin?).ok()?;
+    let (control_tx, mut control_rx) = mpsc::unbounded_channel::<WorkflowControl>();
+    tokio::spawn(async move {
+        while let Some(message) = control_rx.recv().await {
+            let Ok(mut line) = serde_json::to_string(&message) else {
+                continue;
+            };
+            line.push('\n');
+            if stdin.write_all(line.as_bytes()).await.is_err() || stdin.flush().await.is_err() {
+                break;
+            }
+        }
+    });
+    Some(control_tx)
+}
+
+/// Spawn the stderr and stdout parsers of the execution's current process,
+/// returning the stderr one
+fn start_output_parsers(
+    exec_id: Uuid,
+    executions: &Arc<Mutex<HashMap<Uuid, ExecutionState>>>,
+    database: &Arc<Mutex<Database>>,
+) -> tokio::task::JoinHandle<()> {
+    // Take the pipes now: a short-lived process may already be reaped, and
+    // its child handle gone, by the time the parser tasks run
+    let (stdout, stderr) = {
+        let mut execs = executions.lock().unwrap();
+        match execs.get_mut(&exec_id) {
+            Some(ExecutionState {
+                child: Some(child),
+                control_tx,
+                ..
+            }) => {
+                *control_tx = start_control_writer(child.stdin.take());
+                (child.stdout.take(), child.stderr.take())
+            }
+            _ => (None, None),
+        }
+    };
+
+    // Spawn stderr parser task
+    let executions_stderr = executions.clone();
+    let database_stderr = database.clone();
+    let stderr_parser = tokio::spawn(async move {
+        if let Err(e) =
+            parse_workflow_stderr(exec_id, stderr, executions_stderr.clone(), database_stderr)
+                .await
+        {
+            eprintln!("Error parsing workflow stderr: {}", e);
+            // Mark execution as failed
+            let mut execs = executions_stderr.lock().unwrap();
+            if let Some(state) = execs.get_mut(&exec_id) {
+                state.set_status(WorkflowStatus::Failed);
+            }
+        }
+    });
+
+    // Spawn stdout parser task
+    let executions_stdout = executions.clone();
+    let database_stdout = database.clone();
+    tokio::spawn(async move {
+        if let Err(e) =
+            parse_workflow_stdout(exec_id, stdout, executions_stdout.clone(), database_stdout)
+                .await
+        {
+            eprintln!("Error parsing workflow stdout: {}", e);
+            // Mark execution as failed
+            let mut execs = executions_stdout.lock().unwrap();
+            if let Some(state) = execs.get_mut(&exec_id) {
+                state.set_status(WorkflowStatus::Failed);
+            }
+        }
+    });
+
+    stderr_parser
+}
+
+/// Buffer and broadcast an event, returning its sequence number
+///
+/// Both happen under the buffer lock, which `subscribe_logs_with_backlog`
+/// relies on. `None` if the buffer lock is poisoned.
+pub(crate) fn publish_log(
+    logs_buffer: &Mutex<Vec<WorkflowLog>>,
+    logs_tx: &broadcast::Sender<WorkflowLog>,
+    log: WorkflowLog,
+) -> Option<usize> {
+    let mut buffer = logs_buffer.lock().ok()?;
+    buffer.push(log.clone());
+    let _ = logs_tx.send(log);
+    Some(buffer.len() - 1)
+}
+
+/// Buffer, broadcast and persist an event generated by the runtime itself
+fn record_runtime_log(
+    exec_id: &Uuid,
+    state: &ExecutionState,
+    log: WorkflowLog,
+    database: &Mutex<Database>,
+) {
+    let Some(sequence) = publish_log(&state.logs_buffer, &state.logs_tx, log.clone()) else {
+        return;
+    };
+
+    let db = database.lock().unwrap();
+    if let Err(e) = db.batch_insert_logs(exec_id, &[(sequence, log)]) {
+        eprintln!("Warning: Failed to persist runtime log: {}", e);
+    }
+}
+
+/// Log that an execution is waiting for a free slot
+fn log_queued(
+    exec_id: &Uuid,
+    executions: &Mutex<HashMap<Uuid, ExecutionState>>,
+    database: &Mutex<Database>,
+) {
+    let execs = executions.lock().unwrap();
+    if let Some(state) = execs.get(exec_id) {
+        let log = WorkflowLog::TaskProgress {
+            task_id: QUEUE_TASK_ID.to_string(),
+            message: "Queued: waiting for a running workflow to finish".to_string(),
+        };
+        record_runtime_log(exec_id, state, log, database);
     }
 }
 
-/// Parse workflow stderr for __WF_EVENT__:<JSON> messages and raw output
+/// Wait for a free slot, then mark the queued execution as running
+///
+/// Returns None, releasing the slot, if the execution was cancelled while
+/// it waited.
+async fn wait_for_slot(
+    exec_id: Uuid,
+    slots: Arc<Semaphore>,
+    executions: &Mutex<HashMap<Uuid, ExecutionState>>,
+    database: &Mutex<Database>,
+) -> Option<OwnedSemaphorePermit> {
+    // The semaphore is never closed, so acquiring only fails if it were
+    let slot = slots.acquire_owned().await.ok()?;
+
+    let mut execs = executions.lock().unwrap();
+    let state = execs.get_mut(&exec_id)?;
+    if state.status != WorkflowStatus::NotStarted {
+        return None;
+    }
+    state.set_status(WorkflowStatus::Running);
+    state.start_time = Local::now();
+
+    let log = WorkflowLog::TaskProgress {
+        task_id: QUEUE_TASK_ID.to_string(),
+        message: "Slot available, starting".to_string(),
+    };
+    record_runtime_log(&exec_id, state, log, database);
+
+    let db = database.lock().unwrap();
+    if let Err(e) = db.update_execution(&exec_id, WorkflowStatus::Running, None, None) {
+        eprintln!("Warning: Failed to update execution in database: {}", e);
+    }
+    Some(slot)
+}
+
+/// Mark an execution that could not start as failed, logging why
+fn fail_execution(
+    exec_id: &Uuid,
+    error: &str,
+    executions: &Mutex<HashMap<Uuid, ExecutionState>>,
+    database: &Mutex<Database>,
+) {
+    let mut execs = executions.lock().unwrap();
+    let Some(state) = execs.get_mut(exec_id) else {
+        return;
+    };
+    state.set_status(WorkflowStatus::Failed);
+    state.end_time = Some(Local::now());
+
+    let log = WorkflowLog::RawOutput {
+        stream: "stderr".to_string(),
+        line: error.to_string(),
+    };
+    record_runtime_log(exec_id, state, log, database);
+
+    let db = database.lock().unwrap();
+    if let Err(e) = db.update_execution(exec_id, state.status.clone(), state.end_time, None) {
+        eprintln!("Warning: Failed to update execution in database: {}", e);
+    }
+}
+
+/// Lines of a workflow's output, decoded lossily
+///
+/// Bytes that are not UTF-8 become replacement characters instead of ending
+/// the stream, as they would with `AsyncBufReadExt::lines`. Like that,
+/// `next_line` is cancel safe: a partly read line is kept for the next call.
+pub(crate) struct LossyLines<R> {
+    reader: R,
+    buf: Vec<u8>,
+}
+
+impl<R: AsyncBufRead + Unpin> LossyLines<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Next line without its line ending, or `None` at the end of the stream
+    pub(crate) async fn next_line(&mut self) -> std::io::Result<Option<String>> {
+        let read = self.reader.read_until(b'\n', &mut self.buf).await?;
+        if read == 0 && self.buf.is_empty() {
+            return Ok(None);
+        }
+        let mut line = std::mem::take(&mut self.buf);
+        if line.ends_with(b"\n") {
+            line.pop();
+            if line.ends_with(b"\r") {
+                line.pop();
+            }
+        }
+        Ok(Some(String::from_utf8_lossy(&line).into_owned()))
+    }
+}
+
+/// Parse workflow stderr for event lines (see `event_prefix`) and raw output
 async fn parse_workflow_stderr(
     exec_id: Uuid,
+    stderr: Option<std::process::ChildStderr>,
     executions: Arc<Mutex<HashMap<Uuid, ExecutionState>>>,
     database: Arc<Mutex<Database>>,
 ) -> Result<()> {
-    // Get stderr handle and clone necessary state once to avoid locking on every line
-    let (stderr, logs_tx, logs_buffer) = {
-        let mut execs = executions.lock().unwrap();
+    let stderr = stderr.ok_or_else(|| anyhow!("No stderr available"))?;
+
+    // Clone necessary state once to avoid locking on every line
+    let (logs_tx, logs_buffer, secrets) = {
+        let execs = executions.lock().unwrap();
         let state = execs
-            .get_mut(&exec_id)
+            .get(&exec_id)
             .ok_or_else(|| anyhow!("Execution not found"))?;
-        let stderr = state
-            .child
-            .as_mut()
-            .and_then(|c| c.stderr.take())
-            .ok_or_else(|| anyhow!("No stderr available"))?;
 
         // Clone state we need for parsing to avoid holding lock
-        (stderr, state.logs_tx.clone(), state.logs_buffer.clone())
+        (
+            state.logs_tx.clone(),
+            state.logs_buffer.clone(),
+            state.secrets.clone(),
+        )
     };
 
     // Wrap in tokio async reader
     let stderr = tokio::process::ChildStderr::from_std(stderr)?;
-    let reader = BufReader::new(stderr);
-    let mut lines = reader.lines();
+    let mut lines = LossyLines::new(BufReader::new(stderr));
 
     // Batch logging state
     let mut pending_logs: Vec<(usize, WorkflowLog)> = Vec::new();
     let mut last_flush = std::time::Instant::now();
 
     // Parse lines without holding the main executions lock
+    let mut decoder = EventDecoder::new(event_prefix());
     while let Ok(Some(line)) = lines.next_line().await {
-        let log = if let Some(json_str) = line.strip_prefix("__WF_EVENT__:") {
-            // Structured log event
-            serde_json::from_str::<WorkflowLog>(json_str).ok()
-        } else {
-            // Raw stderr output
-            Some(WorkflowLog::RawOutput {
-                stream: "stderr".to_string(),
-                line,
-            })
-        };
+        for part in decoder.push_line(&line) {
+            // Structured log event, or raw stderr output
+            let log = match part {
+                StderrLine::Event(log) => redact_log(log, &secrets),
+                StderrLine::Raw(line) => WorkflowLog::RawOutput {
+                    stream: "stderr".to_string(),
+                    line: redact_secrets(&line, &secrets),
+                },
+            };
 
-        if let Some(log) = log {
-            // Broadcast to real-time subscribers
-            let _ = logs_tx.send(log.clone());
+            record_usage(&exec_id, &log, &executions, &database);
 
-            // Store in buffer for historical retrieval and get sequence number
-            let sequence = if let Ok(mut buffer) = logs_buffer.lock() {
-                let seq = buffer.len();
-                buffer.push(log.clone());
-                seq
-            } else {
+            // Store for historical retrieval and broadcast to real-time subscribers
+            let Some(sequence) = publish_log(&logs_buffer, &logs_tx, log.clone()) else {
                 continue;
             };
 
@@ -512,7 +1836,9 @@ async fn parse_workflow_stderr(
             pending_logs.push((sequence, log));
 
             // Flush if batch is full or time elapsed
-            if pending_logs.len() >= 50 || last_flush.elapsed() > std::time::Duration::from_secs(5) {
+            if pending_logs.len() >= 50
+                || last_flush.elapsed() > std::time::Duration::from_secs(5)
+            {
                 let db = database.lock().unwrap();
                 if let Err(e) = db.batch_insert_logs(&exec_id, &pending_logs) {
                     eprintln!("Warning: Failed to batch insert logs: {}", e);
@@ -540,29 +1866,30 @@ async fn parse_workflow_stderr(
 /// Parse workflow stdout for raw output
 async fn parse_workflow_stdout(
     exec_id: Uuid,
+    stdout: Option<std::process::ChildStdout>,
     executions: Arc<Mutex<HashMap<Uuid, ExecutionState>>>,
     database: Arc<Mutex<Database>>,
 ) -> Result<()> {
-    // Get stdout handle and clone necessary state once to avoid locking on every line
-    let (stdout, logs_tx, logs_buffer) = {
-        let mut execs = executions.lock().unwrap();
+    let stdout = stdout.ok_or_else(|| anyhow!("No stdout available"))?;
+
+    // Clone necessary state once to avoid locking on every line
+    let (logs_tx, logs_buffer, secrets) = {
+        let execs = executions.lock().unwrap();
         let state = execs
-            .get_mut(&exec_id)
+            .get(&exec_id)
             .ok_or_else(|| anyhow!("Execution not found"))?;
-        let stdout = state
-            .child
-            .as_mut()
-            .and_then(|c| c.stdout.take())
-            .ok_or_else(|| anyhow!("No stdout available"))?;
 
         // Clone state we need for parsing to avoid holding lock
-        (stdout, state.logs_tx.clone(), state.logs_buffer.clone())
+        (
+            state.logs_tx.clone(),
+            state.logs_buffer.clone(),
+            state.secrets.clone(),
+        )
     };
 
     // Wrap in tokio async reader
     let stdout = tokio::process::ChildStdout::from_std(stdout)?;
-    let reader = BufReader::new(stdout);
-    let mut lines = reader.lines();
+    let mut lines = LossyLines::new(BufReader::new(stdout));
 
     // Batch logging state
     let mut pending_logs: Vec<(usize, WorkflowLog)> = Vec::new();
@@ -573,18 +1900,11 @@ async fn parse_workflow_stdout(
         // All stdout is raw output
         let log = WorkflowLog::RawOutput {
             stream: "stdout".to_string(),
-            line,
+            line: redact_secrets(&line, &secrets),
         };
 
-        // Broadcast to real-time subscribers
-        let _ = logs_tx.send(log.clone());
-
-        // Store in buffer for historical retrieval and get sequence number
-        let sequence = if let Ok(mut buffer) = logs_buffer.lock() {
-            let seq = buffer.len();
-            buffer.push(log.clone());
-            seq
-        } else {
+        // Store for historical retrieval and broadcast to real-time subscribers
+        let Some(sequence) = publish_log(&logs_buffer, &logs_tx, log.clone()) else {
             continue;
         };
 
@@ -614,36 +1934,96 @@ async fn parse_workflow_stdout(
 }
 
 /// Wait for workflow process to exit and update status accordingly
+///
+/// A process that failed before completing its last phase is spawned again
+/// instead while `restart` allows it, unless the execution was cancelled.
 async fn wait_for_process_exit(
     exec_id: Uuid,
-    executions: Arc<Mutex<HashMap<Uuid, ExecutionState>>>,
-    database: Arc<Mutex<Database>>,
-) -> Result<()> {
-    // Take the child process from the execution state
-    let mut child = {
-        let mut execs = executions.lock().unwrap();
-        let state = execs
-            .get_mut(&exec_id)
-            .ok_or_else(|| anyhow!("Execution not found"))?;
-        state
-            .child
-            .take()
-            .ok_or_else(|| anyhow!("No child process available"))?
+    executions: &Mutex<HashMap<Uuid, ExecutionState>>,
+    database: &Mutex<Database>,
+    restart: Option<&mut Restart>,
+    stderr_parser: tokio::task::JoinHandle<()>,
+) -> Result<ProcessExit> {
+    // Poll the child while leaving it in the execution state, so that
+    // cancel_workflow and the timeout watchdog can still kill it
+    let exit = loop {
+        {
+            let mut execs = executions.lock().unwrap();
+            let state = execs
+                .get_mut(&exec_id)
+                .ok_or_else(|| anyhow!("Execution not found"))?;
+            match state.child.as_mut() {
+                Some(child) => match try_wait_process_tree(child) {
+                    Ok(Some(status)) => {
+                        state.child = None;
+                        break Ok(status);
+                    }
+                    Ok(None) => {}
+                    // The process can no longer be tracked; don't leave the
+                    // execution Running forever
+                    Err(e) => {
+                        kill_process_tree(child);
+                        state.child = None;
+                        break Err(e);
+                    }
+                },
+                // Killed by cancel_workflow or the watchdog, which set and
+                // persist the final status
+                None => return Ok(ProcessExit::Finished),
+            }
+        }
+        tokio::time::sleep(EXIT_POLL_INTERVAL).await;
     };
 
-    // Wait for the process to exit
-    let exit_status = child.wait()?;
+    let failed = matches!(&exit, Ok(status) if !status.success());
+    let restart = restart.filter(|restart| failed && restart.count < restart.policy.max);
+    if restart.is_some() {
+        // The last events of the process decide whether it finished its work
+        let _ = tokio::time::timeout(STDERR_DRAIN_TIMEOUT, stderr_parser).await;
+    }
 
-    // Update status based on exit code
+    // The exit code decides the final status, whatever events were logged
     let mut execs = executions.lock().unwrap();
     if let Some(state) = execs.get_mut(&exec_id) {
-        state.status = if exit_status.success() {
-            WorkflowStatus::Completed
-        } else {
-            WorkflowStatus::Failed
+        let (status, exit_code) = match &exit {
+            Ok(status) if status.success() => (WorkflowStatus::Completed, status.code()),
+            Ok(status) => (WorkflowStatus::Failed, exit_code_of(status)),
+            Err(_) => (WorkflowStatus::Failed, None),
         };
+
+        // A cancelled execution is no longer Running
+        if let Some(restart) = restart {
+            let finished = state
+                .logs_buffer
+                .lock()
+                .is_ok_and(|logs| final_phase_completed(&logs));
+            if state.status == WorkflowStatus::Running && !finished {
+                match spawn_workflow_process(&restart.workflow, &state.params) {
+                    Ok(child) => {
+                        state.child = Some(child);
+                        restart.count += 1;
+                        let cause = match exit_code {
+                            Some(code) => format!("exit code {}", code),
+                            None => "a signal".to_string(),
+                        };
+                        let log = WorkflowLog::TaskProgress {
+                            task_id: RESTART_TASK_ID.to_string(),
+                            message: format!(
+                                "Restart {}/{} after {}",
+                                restart.count, restart.policy.max, cause
+                            ),
+                        };
+                        record_runtime_log(&exec_id, state, log, database);
+                        return Ok(ProcessExit::Restarted);
+                    }
+                    Err(e) => eprintln!("Warning: Failed to restart workflow: {}", e),
+                }
+            }
+        }
+
+        state.set_status(status);
         state.end_time = Some(Local::now());
-        state.exit_code = exit_status.code();
+        state.exit_code = exit_code;
 
         // Persist completion to database
         let db = database.lock().unwrap();
@@ -656,16 +2036,147 @@ async fn wait_for_process_exit(
             eprintln!("Warning: Failed to update execution in database: {}", e);
         }
     }
+    drop(execs);
+
+    exit.map_err(|e| anyhow!("Failed to wait for workflow process: {}", e))?;
 
     // Note: ExecutionState is kept in HashMap for historical log retrieval
     // The broadcast channel will close naturally when parser tasks complete and drop their senders
 
-    Ok(())
+    Ok(ProcessExit::Finished)
+}
+
+/// Whether the events include `PhaseCompleted` for the last phase, as
+/// announced by the `total_phases` of `PhaseStarted`
+fn final_phase_completed(logs: &[WorkflowLog]) -> bool {
+    let last_phase = logs.iter().rev().find_map(|log| match log {
+        WorkflowLog::PhaseStarted { total_phases, .. } => Some(total_phases.saturating_sub(1)),
+        _ => None,
+    });
+    last_phase.is_some_and(|last| {
+        logs.iter()
+            .any(|log| matches!(log, WorkflowLog::PhaseCompleted { phase, .. } if *phase == last))
+    })
+}
+
+/// Kill the workflow process once `timeout` passes without any new log event
+///
+/// Every event restarts the timer, so only stalled workflows are killed;
+/// paused ones are quiet on purpose and get a fresh timer, and so do
+/// pipelines between two stages. A timeout is recorded as a `PhaseFailed`
+/// event named [`TIMEOUT_PHASE_NAME`] on the last started phase and the
+/// execution is marked failed.
+async fn watch_for_timeout(
+    exec_id: Uuid,
+    timeout: Duration,
+    mut logs_rx: broadcast::Receiver<WorkflowLog>,
+    executions: Arc<Mutex<HashMap<Uuid, ExecutionState>>>,
+    database: Arc<Mutex<Database>>,
+) {
+    let mut current_phase = 0;
+    let mut child = loop {
+        match tokio::time::timeout(timeout, logs_rx.recv()).await {
+            Ok(Ok(WorkflowLog::PhaseStarted { phase, .. })) => current_phase = phase,
+            Ok(Ok(_)) | Ok(Err(broadcast::error::RecvError::Lagged(_))) => {}
+            Ok(Err(broadcast::error::RecvError::Closed)) => return,
+            Err(_) => {
+                let mut execs = executions.lock().unwrap();
+                let Some(state) = execs.get_mut(&exec_id) else {
+                    return;
+                };
+                match state.status {
+                    WorkflowStatus::Running => {}
+                    WorkflowStatus::Paused => continue,
+                    _ => return,
+                }
+                // No child: the process just exited and its exit watcher is
+                // about to record the status, or a pipeline is between stages
+                let Some(child) = state.child.take() else {
+                    continue;
+                };
+
+                state.set_status(WorkflowStatus::Failed);
+                state.end_time = Some(Local::now());
+                state.stopped = Some(StopReason::Timeout);
+
+                let log = WorkflowLog::PhaseFailed {
+                    phase: current_phase,
+                    name: TIMEOUT_PHASE_NAME.to_string(),
+                    error: format!(
+                        "No workflow events for {}s; process killed",
+                        timeout.as_secs()
+                    ),
+                };
+                record_runtime_log(&exec_id, state, log, &database);
+
+                let db = database.lock().unwrap();
+                if let Err(e) =
+                    db.update_execution(&exec_id, state.status.clone(), state.end_time, None)
+                {
+                    eprintln!("Warning: Failed to update execution in database: {}", e);
+                }
+                break child;
+            }
+        }
+    };
+
+    // Kill and reap outside the executions lock, as waiting can block
+    let _ = tokio::task::spawn_blocking(move || kill_process_tree(&mut child)).await;
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use workflow_manager_sdk::{WorkflowMetadata, EVENT_PREFIX};
+
+    fn demo_state(status: WorkflowStatus) -> ExecutionState {
+        ExecutionState::new("demo".to_string(), "Demo".to_string(), PathBuf::from("demo"), status)
+    }
+
+    /// Workflow that runs its `script` parameter with `sh -c`
+    fn sh_workflow(id: &str) -> DiscoveredWorkflow {
+        let fields = serde_json::from_value(serde_json::json!([
+            {"name": "script", "field_type": {"type": "text"}, "label": "Script",
+             "description": "", "cli_arg": "-c", "required": true, "default": null}
+        ]))
+        .unwrap();
+        DiscoveredWorkflow {
+            metadata: WorkflowMetadata {
+                id: id.to_string(),
+                name: id.to_string(),
+                ..Default::default()
+            },
+            fields,
+            binary_path: PathBuf::from("sh"),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_invalid_utf8_does_not_end_the_stream() {
+        let event = r#"{"type":"phase_started","phase":0,"name":"Analyze","total_phases":1}"#;
+        let mut stream = Vec::new();
+        stream.extend_from_slice(b"caf\xe9 au lait\r\n");
+        stream.extend_from_slice(format!("{}{}\n", EVENT_PREFIX, event).as_bytes());
+        stream.extend_from_slice(b"\xff\xfe binary\n");
+        stream.extend_from_slice(format!("{}{}", EVENT_PREFIX, event).as_bytes());
+
+        let mut lines = LossyLines::new(BufReader::new(stream.as_slice()));
+        let mut decoder = EventDecoder::new(EVENT_PREFIX);
+        let mut events = 0;
+        let mut raw = Vec::new();
+        while let Some(line) = lines.next_line().await.unwrap() {
+            for part in decoder.push_line(&line) {
+                match part {
+                    StderrLine::Event(WorkflowLog::PhaseStarted { .. }) => events += 1,
+                    StderrLine::Event(other) => panic!("unexpected event {:?}", other),
+                    StderrLine::Raw(line) => raw.push(line),
+                }
+            }
+        }
+        assert_eq!(events, 2);
+        assert_eq!(raw, vec!["caf\u{fffd} au lait", "\u{fffd}\u{fffd} binary"]);
+    }
 
     #[tokio::test]
     async fn test_runtime_creation() {
@@ -689,4 +2200,677 @@ mod tests {
             println!("  Fields: {}", workflow.fields.len());
         }
     }
+
+    #[test]
+    fn test_validate_inputs_reports_every_problem() {
+        let fields = serde_json::from_value(serde_json::json!([
+            {"name": "input", "field_type": {"type": "text"}, "label": "Input",
+             "description": "", "cli_arg": "--input", "required": true, "default": null},
+            {"name": "batch_size", "field_type": {"type": "number", "min": 1, "max": 10},
+             "label": "Batch Size", "description": "", "cli_arg": "--batch-size",
+             "required": false, "default": null}
+        ]))
+        .unwrap();
+        let workflow = DiscoveredWorkflow {
+            metadata: WorkflowMetadata {
+                id: "demo".to_string(),
+                name: "Demo".to_string(),
+                ..Default::default()
+            },
+            fields,
+            binary_path: PathBuf::from("demo"),
+            ..Default::default()
+        };
+        let runtime =
+            ProcessBasedRuntime::with_database(vec![workflow], Database::new_in_memory().unwrap())
+                .unwrap();
+
+        let mut params = HashMap::new();
+        params.insert("batch_size".to_string(), "abc".to_string());
+        params.insert("verbose".to_string(), "true".to_string());
+
+        let error = runtime.validate_workflow_inputs("demo", params).unwrap_err();
+        assert!(matches!(error, WorkflowError::Validation(ref problems) if problems.len() == 3));
+        let error = error.to_string();
+        assert!(error.contains("verbose: Unknown parameter"));
+        assert!(error.contains("input: Required"));
+        assert!(error.contains("batch_size: 'abc' is not a number"));
+
+        let mut params = HashMap::new();
+        params.insert("input".to_string(), "hello".to_string());
+        params.insert("batch_size".to_string(), "4".to_string());
+        params.insert(TIMEOUT_PARAM.to_string(), "0".to_string());
+        let error = runtime
+            .validate_workflow_inputs("demo", params.clone())
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("timeout: Must be a positive number of seconds"));
+
+        params.insert(TIMEOUT_PARAM.to_string(), "30".to_string());
+        assert!(runtime.validate_workflow_inputs("demo", params.clone()).is_ok());
+
+        params.insert(CWD_PARAM.to_string(), "/does/not/exist".to_string());
+        params.insert(ENV_PARAM.to_string(), "RUST_LOG".to_string());
+        let error = runtime
+            .validate_workflow_inputs("demo", params.clone())
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("cwd: Not an existing directory"));
+        assert!(error.contains("env: Entries must look like KEY=VALUE"));
+
+        let dir = std::env::temp_dir();
+        params.insert(CWD_PARAM.to_string(), dir.display().to_string());
+        params.insert(ENV_PARAM.to_string(), "RUST_LOG=debug".to_string());
+        assert!(runtime.validate_workflow_inputs("demo", params.clone()).is_ok());
+
+        assert!(matches!(
+            runtime.validate_workflow_inputs("missing", params),
+            Err(WorkflowError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_export_logs_writes_summary_then_events() {
+        let runtime =
+            ProcessBasedRuntime::with_database(Vec::new(), Database::new_in_memory().unwrap())
+                .unwrap();
+        let exec_id = Uuid::new_v4();
+        let logs = vec![
+            WorkflowLog::PhaseStarted {
+                phase: 0,
+                name: "Analyze".to_string(),
+                total_phases: 1,
+            },
+            WorkflowLog::RawOutput {
+                stream: "stdout".to_string(),
+                line: "done".to_string(),
+            },
+        ];
+        let mut state = demo_state(WorkflowStatus::Completed);
+        state.logs_buffer = Arc::new(Mutex::new(logs));
+        state.end_time = Some(Local::now());
+        state.exit_code = Some(0);
+        runtime.executions.lock().unwrap().insert(exec_id, state);
+
+        let path = std::env::temp_dir().join(format!("export_{}.jsonl", exec_id));
+        runtime.export_logs(&exec_id, &path).await.unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 3);
+        let summary: ExecutionSummary = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(summary.id, exec_id);
+        assert_eq!(summary.workflow_id, "demo");
+        assert!(matches!(
+            serde_json::from_str::<WorkflowLog>(lines[1]).unwrap(),
+            WorkflowLog::PhaseStarted { .. }
+        ));
+        assert!(matches!(
+            serde_json::from_str::<WorkflowLog>(lines[2]).unwrap(),
+            WorkflowLog::RawOutput { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_logs_since_returns_only_new_events() {
+        let runtime =
+            ProcessBasedRuntime::with_database(Vec::new(), Database::new_in_memory().unwrap())
+                .unwrap();
+        let exec_id = Uuid::new_v4();
+        let line = |n: usize| WorkflowLog::RawOutput {
+            stream: "stdout".to_string(),
+            line: format!("line {}", n),
+        };
+        let logs_buffer = Arc::new(Mutex::new(vec![line(0), line(1)]));
+        let mut state = demo_state(WorkflowStatus::Running);
+        state.logs_buffer = logs_buffer.clone();
+        runtime.executions.lock().unwrap().insert(exec_id, state);
+
+        let page = runtime.get_logs_since(&exec_id, None).await.unwrap();
+        assert_eq!(page.logs.len(), 2);
+        assert_eq!(page.latest_index, Some(1));
+        assert_eq!(page.status, WorkflowStatus::Running);
+
+        logs_buffer.lock().unwrap().push(line(2));
+        let page = runtime
+            .get_logs_since(&exec_id, page.latest_index)
+            .await
+            .unwrap();
+        assert!(matches!(&page.logs[..], [WorkflowLog::RawOutput { line, .. }] if line == "line 2"));
+        assert_eq!(page.latest_index, Some(2));
+
+        let page = runtime.get_logs_since(&exec_id, Some(2)).await.unwrap();
+        assert!(page.logs.is_empty());
+        assert_eq!(page.latest_index, Some(2));
+
+        assert!(runtime.get_logs_since(&Uuid::new_v4(), None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_backlog_and_stream_cover_every_event_once() {
+        let runtime =
+            ProcessBasedRuntime::with_database(Vec::new(), Database::new_in_memory().unwrap())
+                .unwrap();
+        let exec_id = Uuid::new_v4();
+        let line = |n: usize| WorkflowLog::RawOutput {
+            stream: "stdout".to_string(),
+            line: n.to_string(),
+        };
+        let logs_tx = broadcast::channel(2000).0;
+        let logs_buffer = Arc::new(Mutex::new(Vec::new()));
+        let mut state = demo_state(WorkflowStatus::Running);
+        state.logs_tx = logs_tx.clone();
+        state.logs_buffer = logs_buffer.clone();
+        runtime.executions.lock().unwrap().insert(exec_id, state);
+
+        // Subscribe while a writer is publishing
+        let writer = std::thread::spawn(move || {
+            for n in 0..1000 {
+                publish_log(&logs_buffer, &logs_tx, line(n));
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        let (backlog, mut logs_rx) = runtime.subscribe_logs_with_backlog(&exec_id).await.unwrap();
+        writer.join().unwrap();
+
+        let seen: Vec<String> = backlog
+            .into_iter()
+            .chain(std::iter::from_fn(|| logs_rx.try_recv().ok()))
+            .map(|log| match log {
+                WorkflowLog::RawOutput { line, .. } => line,
+                other => panic!("unexpected event {:?}", other),
+            })
+            .collect();
+        let expected: Vec<String> = (0..1000).map(|n| n.to_string()).collect();
+        assert_eq!(seen, expected);
+
+        assert!(runtime.subscribe_logs_with_backlog(&Uuid::new_v4()).await.is_err());
+    }
+
+    #[test]
+    fn test_parse_timeout() {
+        let mut params = HashMap::new();
+        assert_eq!(parse_timeout(&params).unwrap(), None);
+
+        params.insert(TIMEOUT_PARAM.to_string(), " 90 ".to_string());
+        assert_eq!(
+            parse_timeout(&params).unwrap(),
+            Some(Duration::from_secs(90))
+        );
+
+        params.insert(TIMEOUT_PARAM.to_string(), "1.5".to_string());
+        assert!(parse_timeout(&params).is_err());
+    }
+
+    #[test]
+    fn test_parse_env() {
+        let mut params = HashMap::new();
+        assert!(parse_env(&params).unwrap().is_empty());
+
+        params.insert(ENV_PARAM.to_string(), "A=1, B = two words ,C=,".to_string());
+        assert_eq!(
+            parse_env(&params).unwrap(),
+            vec![
+                ("A".to_string(), "1".to_string()),
+                ("B".to_string(), "two words".to_string()),
+                ("C".to_string(), String::new()),
+            ]
+        );
+
+        params.insert(ENV_PARAM.to_string(), "=1".to_string());
+        assert!(parse_env(&params).is_err());
+        params.insert(ENV_PARAM.to_string(), "MY VAR=1".to_string());
+        assert!(parse_env(&params).is_err());
+    }
+
+    #[test]
+    fn test_parse_env_json_newlines_and_escapes() {
+        let mut params = HashMap::new();
+        params.insert(
+            ENV_PARAM.to_string(),
+            r#"{"API_BASE": "http://a,b", "RETRIES": 3}"#.to_string(),
+        );
+        let mut env = parse_env(&params).unwrap();
+        env.sort();
+        assert_eq!(
+            env,
+            vec![
+                ("API_BASE".to_string(), "http://a,b".to_string()),
+                ("RETRIES".to_string(), "3".to_string()),
+            ]
+        );
+
+        params.insert(ENV_PARAM.to_string(), "A=1\nLIST=x\\,y\\\\\nB=2".to_string());
+        assert_eq!(
+            parse_env(&params).unwrap(),
+            vec![
+                ("A".to_string(), "1".to_string()),
+                ("LIST".to_string(), "x,y\\".to_string()),
+                ("B".to_string(), "2".to_string()),
+            ]
+        );
+
+        params.insert(ENV_PARAM.to_string(), "{\"A\": ".to_string());
+        assert!(parse_env(&params).is_err());
+        params.insert(ENV_PARAM.to_string(), r#"{"MY VAR": "1"}"#.to_string());
+        assert!(parse_env(&params).is_err());
+    }
+
+    #[test]
+    fn test_preview_command() {
+        let fields = serde_json::from_value(serde_json::json!([
+            {"name": "input", "field_type": {"type": "text"}, "label": "Input",
+             "description": "", "cli_arg": "--input", "required": true, "default": null},
+            {"name": "verbose", "field_type": {"type": "boolean"}, "label": "Verbose",
+             "description": "", "cli_arg": "--verbose", "required": false, "default": null},
+            {"name": "dry_run", "field_type": {"type": "boolean"}, "label": "Dry Run",
+             "description": "", "cli_arg": "--dry-run", "required": false, "default": null},
+            {"name": "tags", "field_type": {"type": "multi_select", "options": ["a", "b c"]},
+             "label": "Tags", "description": "", "cli_arg": "--tags", "required": false,
+             "default": null},
+            {"name": "api_key", "field_type": {"type": "secret"}, "label": "API Key",
+             "description": "", "cli_arg": "--api-key", "required": false, "default": null}
+        ]))
+        .unwrap();
+        let workflow = DiscoveredWorkflow {
+            metadata: WorkflowMetadata {
+                id: "demo".to_string(),
+                name: "Demo".to_string(),
+                ..Default::default()
+            },
+            fields,
+            binary_path: PathBuf::from("/bin/demo"),
+            ..Default::default()
+        };
+        let runtime =
+            ProcessBasedRuntime::with_database(vec![workflow], Database::new_in_memory().unwrap())
+                .unwrap();
+
+        let mut params = HashMap::new();
+        params.insert("input".to_string(), "it's done".to_string());
+        params.insert("verbose".to_string(), "true".to_string());
+        params.insert(ENV_PARAM.to_string(), "RUST_LOG=debug".to_string());
+        assert_eq!(
+            runtime.preview_command("demo", params.clone()).unwrap(),
+            "RUST_LOG=debug /bin/demo --input 'it'\\''s done' --verbose"
+        );
+
+        // Params as stored for a past run: off flags are left out, a
+        // multi-select stays one argument and unknown params are ignored
+        params.insert("dry_run".to_string(), "false".to_string());
+        params.insert("tags".to_string(), "a,b c".to_string());
+        params.insert("removed".to_string(), "1".to_string());
+        assert_eq!(
+            runtime.preview_command("demo", params).unwrap(),
+            "RUST_LOG=debug /bin/demo --input 'it'\\''s done' --verbose --tags 'a,b c'"
+        );
+        assert!(runtime.preview_command("missing", HashMap::new()).is_err());
+
+        // Secrets are passed in the environment, and masked in the preview
+        let mut params = HashMap::new();
+        params.insert("input".to_string(), "x".to_string());
+        params.insert("api_key".to_string(), "sk-123".to_string());
+        assert_eq!(
+            runtime.preview_command("demo", params.clone()).unwrap(),
+            "WF_SECRET_API_KEY=**** /bin/demo --input x"
+        );
+        let workflow = runtime.workflows.lock().unwrap()["demo"].clone();
+        let cmd = build_command(&workflow, params);
+        assert!(cmd.get_args().all(|arg| arg != "sk-123"));
+    }
+
+    #[test]
+    fn test_parse_max_concurrent() {
+        assert_eq!(parse_max_concurrent("4"), Some(4));
+        assert_eq!(parse_max_concurrent(" 2 "), Some(2));
+        assert_eq!(parse_max_concurrent("0"), None);
+        assert_eq!(parse_max_concurrent("many"), None);
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_cap_queues_executions() {
+        let workflow = sh_workflow("sleepy");
+        let mut runtime =
+            ProcessBasedRuntime::with_database(vec![workflow], Database::new_in_memory().unwrap())
+                .unwrap();
+        runtime.slots = Some(Arc::new(Semaphore::new(1)));
+
+        // Each run holds its slot until the gate file exists
+        let gate = std::env::temp_dir().join(format!("gate_{}", Uuid::new_v4()));
+        let mut params = HashMap::new();
+        params.insert(
+            "script".to_string(),
+            format!("while [ ! -e {} ]; do sleep 0.05; done", gate.display()),
+        );
+        let first = runtime
+            .execute_workflow("sleepy", params.clone())
+            .await
+            .unwrap();
+        let second = runtime
+            .execute_workflow("sleepy", params.clone())
+            .await
+            .unwrap();
+        let third = runtime.execute_workflow("sleepy", params).await.unwrap();
+
+        let running = runtime.list_running().await.unwrap();
+        let ids: Vec<Uuid> = running.iter().map(|(id, _, _)| *id).collect();
+        assert_eq!(ids, [first.id, second.id, third.id]);
+        assert_eq!(running[0].1, "sleepy");
+        assert_eq!(running[1].2, WorkflowStatus::NotStarted);
+
+        assert_eq!(
+            runtime.get_status(&first.id).await.unwrap(),
+            WorkflowStatus::Running
+        );
+        assert_eq!(
+            runtime.get_status(&second.id).await.unwrap(),
+            WorkflowStatus::NotStarted
+        );
+        let logs = runtime.get_logs(&second.id, None).await.unwrap();
+        assert!(matches!(
+            &logs[0],
+            WorkflowLog::TaskProgress { task_id, .. } if task_id == QUEUE_TASK_ID
+        ));
+
+        // Cancelling a queued execution fails it without ever starting it
+        runtime.cancel_workflow(&third.id).await.unwrap();
+        assert_eq!(
+            runtime.get_status(&third.id).await.unwrap(),
+            WorkflowStatus::Failed
+        );
+
+        // The second execution starts once the first exits
+        std::fs::write(&gate, "").unwrap();
+        for _ in 0..50 {
+            if runtime.get_status(&second.id).await.unwrap() == WorkflowStatus::Completed {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        assert_eq!(
+            runtime.get_status(&first.id).await.unwrap(),
+            WorkflowStatus::Completed
+        );
+        assert_eq!(
+            runtime.get_status(&second.id).await.unwrap(),
+            WorkflowStatus::Completed
+        );
+        assert_eq!(
+            runtime.get_status(&third.id).await.unwrap(),
+            WorkflowStatus::Failed
+        );
+        assert!(runtime.list_running().await.unwrap().is_empty());
+        let _ = std::fs::remove_file(&gate);
+    }
+
+    #[tokio::test]
+    async fn test_restore_fails_stale_executions() {
+        let database = Database::new_in_memory().unwrap();
+        database.initialize_schema().unwrap();
+        let mut ids = Vec::new();
+        for status in [
+            WorkflowStatus::NotStarted,
+            WorkflowStatus::Running,
+            WorkflowStatus::Completed,
+        ] {
+            let id = Uuid::new_v4();
+            database
+                .insert_execution(&PersistedExecution {
+                    id,
+                    workflow_id: "demo".to_string(),
+                    workflow_name: "Demo".to_string(),
+                    status,
+                    start_time: Local::now(),
+                    end_time: None,
+                    exit_code: None,
+                    binary_path: PathBuf::from("/bin/demo"),
+                    created_at: Local::now(),
+                    updated_at: Local::now(),
+                    input_tokens: None,
+                    output_tokens: None,
+                    total_cost_usd: None,
+                })
+                .unwrap();
+            ids.push(id);
+        }
+
+        // Nothing of the previous session is still queued or running
+        let runtime = ProcessBasedRuntime::with_database(Vec::new(), database).unwrap();
+        let statuses = [
+            WorkflowStatus::Failed,
+            WorkflowStatus::Failed,
+            WorkflowStatus::Completed,
+        ];
+        for (id, status) in ids.iter().zip(statuses) {
+            assert_eq!(runtime.get_status(id).await.unwrap(), status);
+        }
+        assert!(runtime.list_running().await.unwrap().is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_pause_and_resume_workflow() {
+        let workflow = sh_workflow("sleepy");
+        let runtime =
+            ProcessBasedRuntime::with_database(vec![workflow], Database::new_in_memory().unwrap())
+                .unwrap();
+
+        let mut params = HashMap::new();
+        params.insert("script".to_string(), "sleep 0.3".to_string());
+        let handle = runtime.execute_workflow("sleepy", params).await.unwrap();
+
+        runtime.pause_workflow(&handle.id).await.unwrap();
+        assert_eq!(
+            runtime.get_status(&handle.id).await.unwrap(),
+            WorkflowStatus::Paused
+        );
+        assert!(runtime.pause_workflow(&handle.id).await.is_err());
+
+        // A stopped process does not finish
+        tokio::time::sleep(Duration::from_millis(600)).await;
+        assert_eq!(
+            runtime.get_status(&handle.id).await.unwrap(),
+            WorkflowStatus::Paused
+        );
+
+        runtime.resume_workflow(&handle.id).await.unwrap();
+        assert!(matches!(
+            runtime.resume_workflow(&handle.id).await,
+            Err(WorkflowError::AlreadyRunning(_))
+        ));
+        for _ in 0..50 {
+            if runtime.get_status(&handle.id).await.unwrap() == WorkflowStatus::Completed {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        assert_eq!(
+            runtime.get_status(&handle.id).await.unwrap(),
+            WorkflowStatus::Completed
+        );
+        assert!(runtime.resume_workflow(&handle.id).await.is_err());
+
+        // A cancelled execution says so
+        let mut params = HashMap::new();
+        params.insert("script".to_string(), "sleep 5".to_string());
+        let handle = runtime.execute_workflow("sleepy", params).await.unwrap();
+        runtime.cancel_workflow(&handle.id).await.unwrap();
+        assert!(matches!(
+            runtime.pause_workflow(&handle.id).await,
+            Err(WorkflowError::Cancelled)
+        ));
+
+        // The history sees it stop too, not only the in-memory state
+        let persisted = runtime.database.lock().unwrap().get_execution(&handle.id).unwrap();
+        let persisted = persisted.unwrap();
+        assert_eq!(persisted.status, WorkflowStatus::Failed);
+        assert!(persisted.end_time.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_task_writes_control_message() {
+        let workflow = sh_workflow("listener");
+        let runtime =
+            ProcessBasedRuntime::with_database(vec![workflow], Database::new_in_memory().unwrap())
+                .unwrap();
+
+        let mut params = HashMap::new();
+        params.insert("script".to_string(), "read line; echo \"$line\"".to_string());
+        let handle = runtime.execute_workflow("listener", params).await.unwrap();
+        runtime.cancel_task(&handle.id, "research_2").await.unwrap();
+
+        let status = tokio::time::timeout(
+            Duration::from_secs(5),
+            runtime.wait_for_completion(&handle.id),
+        )
+        .await
+        .expect("the workflow reads the message and exits")
+        .unwrap();
+        assert_eq!(status, WorkflowStatus::Completed);
+
+        // The stdout parser may still be catching up
+        tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let logs = runtime.get_logs(&handle.id, None).await.unwrap();
+                if logs.iter().any(|log| matches!(
+                    log,
+                    WorkflowLog::RawOutput { line, .. } if line == r#"{"cancel_task":"research_2"}"#
+                )) {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .expect("the workflow echoes the control message");
+        assert!(runtime.cancel_task(&handle.id, "research_2").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_silent_exit_code_decides_status() {
+        let workflow = sh_workflow("shell");
+        let runtime =
+            ProcessBasedRuntime::with_database(vec![workflow], Database::new_in_memory().unwrap())
+                .unwrap();
+
+        let runtime = &runtime;
+        let run = move |script: &str| {
+            let mut params = HashMap::new();
+            params.insert("script".to_string(), script.to_string());
+            runtime.execute_workflow("shell", params)
+        };
+        let ok = run("exit 0").await.unwrap();
+        let crashed = run("exit 3").await.unwrap();
+        let killed = run("kill -9 $$").await.unwrap();
+
+        for _ in 0..50 {
+            let execution = runtime.get_execution(&killed.id).await.unwrap();
+            if execution.status != WorkflowStatus::Running {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        tokio::time::sleep(Duration::from_millis(300)).await;
+
+        let ok = runtime.get_execution(&ok.id).await.unwrap();
+        assert_eq!(ok.status, WorkflowStatus::Completed);
+        assert_eq!(ok.exit_code, Some(0));
+
+        let crashed = runtime.get_execution(&crashed.id).await.unwrap();
+        assert_eq!(crashed.status, WorkflowStatus::Failed);
+        assert_eq!(crashed.exit_code, Some(3));
+
+        let killed = runtime.get_execution(&killed.id).await.unwrap();
+        assert_eq!(killed.status, WorkflowStatus::Failed);
+        assert_eq!(killed.exit_code, Some(128 + 9));
+
+        // The final status is persisted too
+        let persisted = runtime.database.lock().unwrap().get_execution(&crashed.id).unwrap();
+        assert_eq!(persisted.unwrap().exit_code, Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_failed_workflow_is_restarted() {
+        let mut workflow = sh_workflow("flaky");
+        workflow.metadata.restart_on_failure = Some(RestartPolicy { max: 2 });
+        let runtime =
+            ProcessBasedRuntime::with_database(vec![workflow], Database::new_in_memory().unwrap())
+                .unwrap();
+        let runs = std::env::temp_dir().join(format!("restart_{}", Uuid::new_v4()));
+
+        let runtime = &runtime;
+        let run = move |script: String| async move {
+            let mut params = HashMap::new();
+            params.insert("script".to_string(), script);
+            let handle = runtime.execute_workflow("flaky", params).await.unwrap();
+            for _ in 0..100 {
+                if runtime.get_status(&handle.id).await.unwrap() != WorkflowStatus::Running {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            handle
+        };
+
+        // Every run fails: the first one and two restarts
+        let crashed = run(format!("echo run >> {}; exit 3", runs.display())).await;
+        let execution = runtime.get_execution(&crashed.id).await.unwrap();
+        assert_eq!(execution.status, WorkflowStatus::Failed);
+        assert_eq!(execution.exit_code, Some(3));
+        assert_eq!(std::fs::read_to_string(&runs).unwrap().lines().count(), 3);
+        let logs = runtime.get_logs(&crashed.id, None).await.unwrap();
+        let restarts: Vec<&str> = logs
+            .iter()
+            .filter_map(|log| match log {
+                WorkflowLog::TaskProgress { task_id, message } if task_id == RESTART_TASK_ID => {
+                    Some(message.as_str())
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            restarts,
+            vec!["Restart 1/2 after exit code 3", "Restart 2/2 after exit code 3"]
+        );
+
+        // A failure after the last phase completed is not restarted
+        let events = [
+            r#"{"type":"phase_started","phase":0,"name":"Only","total_phases":1}"#,
+            r#"{"type":"phase_completed","phase":0,"name":"Only"}"#,
+        ];
+        let script = events
+            .iter()
+            .map(|event| format!("echo '{}{}' >&2", event_prefix(), event))
+            .collect::<Vec<_>>()
+            .join("; ");
+        let finished = run(format!("{}; exit 1", script)).await;
+        let logs = runtime.get_logs(&finished.id, None).await.unwrap();
+        assert!(!logs.iter().any(|log| matches!(
+            log,
+            WorkflowLog::TaskProgress { task_id, .. } if task_id == RESTART_TASK_ID
+        )));
+        assert_eq!(
+            runtime.get_status(&finished.id).await.unwrap(),
+            WorkflowStatus::Failed
+        );
+
+        std::fs::remove_file(&runs).unwrap();
+    }
+
+    #[test]
+    fn test_usage_totals_accumulate() {
+        assert_eq!(UsageTotals::from_parts(None, None, None), None);
+
+        let mut totals = UsageTotals::default();
+        totals.add(12_000, 300, None);
+        assert_eq!(totals.cost_usd, None);
+        assert_eq!(totals.to_string(), "12.0k in / 300 out tokens");
+
+        totals.add(345, 550, Some(0.12));
+        totals.add(0, 0, Some(0.3));
+        assert_eq!(totals.input_tokens, 12_345);
+        assert_eq!(totals.output_tokens, 850);
+        assert_eq!(totals.to_string(), "12.3k in / 850 out tokens · $0.42");
+    }
 }
@@ -119,6 +119,15 @@ pub struct Args {
     )]
     pub dir: Option<String>,
 
+    /// Working directory of the workflow (the codebase to operate on)
+    #[arg(long)]
+    #[field(
+        label = "Working Directory",
+        description = "[TEXT] Repository the workflow runs in (default: current directory)",
+        type = "file_path"
+    )]
+    pub cwd: Option<String>,
+
     // Hidden metadata flag
     #[arg(long, hide = true)]
     pub workflow_metadata: bool,
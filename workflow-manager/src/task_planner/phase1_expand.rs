@@ -1,7 +1,8 @@
 //! Phase 1: Expand tasks into detailed specifications
 //!
 //! This phase:
-//! - Generates execution plan (AI dependency analysis or simple batching)
+//! - Generates execution plan (AI dependency analysis, falling back to
+//!   dependency order when the AI plan is unusable, or simple batching)
 //! - Executes suborchestrators in parallel batches
 //! - Each suborchestrator coordinates 4 specialized sub-agents:
 //!   - @files: Identifies files to create/modify
@@ -11,16 +12,19 @@
 //! - Outputs detailed task specifications (tasks.yaml)
 
 use crate::task_planner::utils::{
-    build_execution_batches_fallback, generate_ai_execution_plan, generate_simple_execution_plan,
-    get_task_id, get_task_name, parse_execution_plan,
+    check_dependency_cycles, generate_ai_execution_plan, generate_simple_execution_plan,
+    get_task_id, get_task_name, plan_or_fallback_batches,
+};
+use crate::workflow_utils::{
+    execute_agent, execute_batch, execute_task, extract_yaml, parse_yaml_multi, top_level_keys,
+    validate_yaml_against_template, AgentConfig,
 };
-use crate::workflow_utils::{execute_agent, execute_batch, execute_task, extract_yaml, parse_yaml_multi, AgentConfig};
 use anyhow::{Context, Result};
 use claude_agent_sdk::{AgentDefinition, ClaudeAgentOptions};
 use serde_yaml::Value;
 use std::path::{Path, PathBuf};
 use tokio::fs;
-use workflow_manager_sdk::log_state_file;
+use workflow_manager_sdk::{log_state_file, log_task_complete, log_task_progress, log_task_start};
 
 /// Sanitize task name for use in filename
 fn sanitize_filename(name: &str) -> String {
@@ -275,6 +279,20 @@ IMPORTANT: Run all agents in parallel for maximum efficiency:
 
     // Extract YAML from response
     let yaml_content = extract_yaml(&response);
+
+    // Fail fast on well-formed YAML that doesn't follow the template; a
+    // template that isn't a plain mapping can't be checked this way
+    if let Ok(template_keys) = top_level_keys(task_template) {
+        let template_keys: Vec<&str> = template_keys.iter().map(String::as_str).collect();
+        validate_yaml_against_template(&yaml_content, &template_keys).map_err(|errors| {
+            anyhow::anyhow!(
+                "Task {} does not match the task template: {}",
+                task_id,
+                errors.join("; ")
+            )
+        })?;
+    }
+
     Ok(yaml_content)
 }
 
@@ -301,15 +319,26 @@ pub async fn expand_tasks(
     let batches = if simple_batching {
         generate_simple_execution_plan(&tasks, batch_size)?
     } else {
-        // AI-based dependency analysis
-        let execution_plan_yaml = generate_ai_execution_plan(tasks_overview_yaml).await?;
-
-        // Try to parse execution plan, fallback to simple analysis if it fails
-        parse_execution_plan(&execution_plan_yaml, &tasks)
-            .unwrap_or_else(|e| {
-                println!("Failed to parse execution plan: {}", e);
-                build_execution_batches_fallback(&tasks)
-            })
+        // A cycle would leave its tasks out of every batch
+        check_dependency_cycles(&tasks)?;
+
+        // AI-based dependency analysis; a plan that failed, doesn't parse or
+        // schedules nothing falls back to batching by dependencies instead of
+        // aborting the run
+        log_task_start!(1, "plan_batches", "Planning execution batches");
+        let plan = generate_ai_execution_plan(tasks_overview_yaml).await;
+        let (batches, fallback) = plan_or_fallback_batches(plan, &tasks);
+        if let Some(reason) = fallback {
+            log_task_progress!(
+                "plan_batches",
+                format!(
+                    "Warning: AI execution plan unusable ({}); batching by dependencies instead",
+                    reason
+                )
+            );
+        }
+        log_task_complete!("plan_batches", format!("{} batch(es)", batches.len()));
+        batches
     };
 
     println!("Execution plan: {} batch(es)", batches.len());
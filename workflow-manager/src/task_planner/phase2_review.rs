@@ -5,18 +5,28 @@
 //! - Executes review suborchestrators in parallel batches
 //! - Each suborchestrator coordinates @reviewer agents
 //! - Validates: completeness, consistency, correctness, testability
+//! - Logs each task's assessment and rewrites a partial report as each batch
+//!   completes, so a crash keeps the reviews done so far
 //! - Generates final review report
 
 use crate::task_planner::utils::{get_task_id, get_task_name};
-use crate::workflow_utils::{execute_agent, execute_batch, execute_task, parse_yaml_multi, AgentConfig};
+use crate::workflow_utils::{
+    execute_agent, execute_batch, execute_task, extract_json, parse_yaml_multi, AgentConfig,
+};
 use anyhow::{Context, Result};
 use claude_agent_sdk::{AgentDefinition, ClaudeAgentOptions};
 use serde_yaml::Value;
 use std::collections::HashMap;
-use std::path::Path;
-use tokio::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use workflow_manager_sdk::{log_task_complete, log_task_start};
+
+/// Where the review report is written, partial until the review completes
+const REPORT_PATH: &str = "task_review_report.txt";
 
 /// Review result structure (parsed from JSON)
+#[derive(Clone)]
 struct ReviewResult {
     task_id: u32,
     success: bool,
@@ -24,6 +34,14 @@ struct ReviewResult {
     summary: String,
 }
 
+/// Outcome of the review, as saved in the report
+pub struct ReviewReport {
+    /// Absolute path of the saved report
+    pub path: PathBuf,
+    pub approved: usize,
+    pub total: usize,
+}
+
 /// Execute review suborchestrator for a batch of tasks
 async fn review_batch(
     batch: Vec<(Value, Value)>, // (overview, detailed) pairs
@@ -161,26 +179,9 @@ Run ALL @reviewer agents in PARALLEL, then combine results into JSON array."#,
 
     let response = execute_agent(config).await?;
 
-    // Parse JSON response
-    let json_str = if response.contains("```json") {
-        response
-            .split("```json")
-            .nth(1)
-            .and_then(|s| s.split("```").next())
-            .unwrap_or(&response)
-            .trim()
-    } else if response.contains("```") {
-        response
-            .split("```")
-            .nth(1)
-            .and_then(|s| s.split("```").next())
-            .unwrap_or(&response)
-            .trim()
-    } else {
-        response.trim()
-    };
-
-    let json_value: serde_json::Value = serde_json::from_str(json_str)
+    // Parse JSON response, which may come with a sentence around it
+    let json_str = extract_json(&response);
+    let json_value: serde_json::Value = serde_json::from_str(&json_str)
         .context("Failed to parse review results JSON")?;
 
     let results_array = json_value
@@ -227,7 +228,7 @@ pub async fn review_tasks(
     impl_md: &str,
     task_template: &str,
     batch_size: usize,
-) -> Result<()> {
+) -> Result<ReviewReport> {
     println!("\n{}", "=".repeat(80));
     println!("PHASE 2: Batched Review - Validate Tasks");
     println!("{}", "=".repeat(80));
@@ -282,6 +283,8 @@ pub async fn review_tasks(
     let impl_md_clone = impl_md.to_string();
     let task_template_clone = task_template.to_string();
     let num_batches = batches.len();
+    let total_tasks = task_pairs.len();
+    let reviewed: Arc<Mutex<Vec<ReviewResult>>> = Arc::default();
 
     let all_results = execute_batch(
         2, // phase number
@@ -290,6 +293,7 @@ pub async fn review_tasks(
         move |batch, ctx| {
             let impl_md = impl_md_clone.clone();
             let task_template = task_template_clone.clone();
+            let reviewed = reviewed.clone();
             async move {
                 // Get batch task IDs for logging
                 let task_ids: Vec<u32> = batch.iter().filter_map(|(overview, _)| get_task_id(overview)).collect();
@@ -309,6 +313,19 @@ pub async fn review_tasks(
                     }
                 ).await?;
 
+                log_review_results(&results);
+
+                // Rewrite the partial report with every review so far; the
+                // lock keeps batches finishing together from interleaving
+                let mut reviewed = reviewed.lock().await;
+                reviewed.extend(results.iter().cloned());
+                reviewed.sort_by_key(|result| result.task_id);
+                let report = format_review_report(&reviewed, Some(total_tasks));
+                if let Err(e) = crate::utils::write_atomically(Path::new(REPORT_PATH), &report) {
+                    println!("Warning: Failed to write partial review report: {:#}", e);
+                }
+                drop(reviewed);
+
                 // Return tuple for execute_batch
                 Ok((results, format!("Batch {} complete", ctx.task_number)))
             }
@@ -323,44 +340,43 @@ pub async fn review_tasks(
         .collect();
 
     // Generate report
-    generate_review_report(&all_review_results).await?;
-
-    Ok(())
+    generate_review_report(&all_review_results).await
 }
 
-/// Generate final review report
-async fn generate_review_report(results: &[ReviewResult]) -> Result<()> {
-    println!("\n{}", "=".repeat(80));
-    println!("FINAL REPORT: Main Orchestrator Summary");
-    println!("{}", "=".repeat(80));
+/// Log the assessment of each reviewed task as a completed task of its own
+fn log_review_results(results: &[ReviewResult]) {
+    for result in results {
+        let task_id = format!("review_task_{}", result.task_id);
+        log_task_start!(2, &task_id, format!("Review of task {}", result.task_id));
+        let assessment = if result.success {
+            format!("APPROVED: {}", result.summary)
+        } else {
+            format!(
+                "NEEDS REVISION ({} issues): {}",
+                result.issues.len(),
+                result.summary
+            )
+        };
+        log_task_complete!(&task_id, assessment);
+    }
+}
 
+/// Text of the review report, marked partial when `total_tasks` more are
+/// still under review
+fn format_review_report(results: &[ReviewResult], total_tasks: Option<usize>) -> String {
     let approved = results.iter().filter(|r| r.success).count();
     let needs_revision = results.len() - approved;
 
-    println!("Total tasks reviewed: {}", results.len());
-    println!("✓ Approved: {}", approved);
-    println!("✗ Needs revision: {}\n", needs_revision);
-
-    if needs_revision > 0 {
-        println!("Tasks requiring revision:\n");
-        for result in results {
-            if !result.success {
-                println!("  Task {}:", result.task_id);
-                for issue in &result.issues {
-                    println!("    - {}", issue);
-                }
-                println!("    Summary: {}\n", result.summary);
-            }
-        }
-    } else {
-        println!("✓ All tasks approved! Ready for implementation.\n");
-    }
-
-    // Save report to file
-    let report_path = Path::new("task_review_report.txt");
     let mut report = String::new();
     report.push_str(&"=".repeat(80));
-    report.push_str("\nTASK REVIEW REPORT\n");
+    match total_tasks {
+        Some(total) => report.push_str(&format!(
+            "\nTASK REVIEW REPORT (PARTIAL: {} of {} tasks reviewed)\n",
+            results.len(),
+            total
+        )),
+        None => report.push_str("\nTASK REVIEW REPORT\n"),
+    }
     report.push_str(&"=".repeat(80));
     report.push_str(&format!("\n\nTotal tasks: {}\n", results.len()));
     report.push_str(&format!("Approved: {}\n", approved));
@@ -385,9 +401,76 @@ async fn generate_review_report(results: &[ReviewResult]) -> Result<()> {
         }
         report.push_str("\n");
     }
+    report
+}
+
+/// Generate final review report, replacing the partial one
+async fn generate_review_report(results: &[ReviewResult]) -> Result<ReviewReport> {
+    println!("\n{}", "=".repeat(80));
+    println!("FINAL REPORT: Main Orchestrator Summary");
+    println!("{}", "=".repeat(80));
+
+    let approved = results.iter().filter(|r| r.success).count();
+    let needs_revision = results.len() - approved;
+
+    println!("Total tasks reviewed: {}", results.len());
+    println!("✓ Approved: {}", approved);
+    println!("✗ Needs revision: {}\n", needs_revision);
+
+    if needs_revision > 0 {
+        println!("Tasks requiring revision:\n");
+        for result in results {
+            if !result.success {
+                println!("  Task {}:", result.task_id);
+                for issue in &result.issues {
+                    println!("    - {}", issue);
+                }
+                println!("    Summary: {}\n", result.summary);
+            }
+        }
+    } else {
+        println!("✓ All tasks approved! Ready for implementation.\n");
+    }
 
-    fs::write(report_path, report).await?;
+    // Save report to file
+    let report_path = Path::new(REPORT_PATH);
+    crate::utils::write_atomically(report_path, &format_review_report(results, None))?;
     println!("✓ Full report saved to: {}", report_path.display());
 
-    Ok(())
+    Ok(ReviewReport {
+        path: std::env::current_dir()?.join(report_path),
+        approved,
+        total: results.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_review_report() {
+        let results = vec![
+            ReviewResult {
+                task_id: 1,
+                success: true,
+                issues: Vec::new(),
+                summary: "Complete".to_string(),
+            },
+            ReviewResult {
+                task_id: 2,
+                success: false,
+                issues: vec!["No tests".to_string()],
+                summary: "Missing tests".to_string(),
+            },
+        ];
+
+        let partial = format_review_report(&results, Some(5));
+        assert!(partial.contains("TASK REVIEW REPORT (PARTIAL: 2 of 5 tasks reviewed)"));
+        let report = format_review_report(&results, None);
+        assert!(report.contains("\nTASK REVIEW REPORT\n"));
+        assert!(report.contains("Approved: 1\nNeeds revision: 1\n"));
+        let revision = "Task 2: NEEDS REVISION\nSummary: Missing tests\nIssues:\n  - No tests\n";
+        assert!(report.contains(revision));
+    }
 }
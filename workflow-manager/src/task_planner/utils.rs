@@ -4,7 +4,7 @@ use crate::workflow_utils::{execute_agent, AgentConfig};
 use anyhow::{Context, Result};
 use claude_agent_sdk::ClaudeAgentOptions;
 use serde_yaml::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Extract task ID from a task YAML value
 pub fn get_task_id(task: &Value) -> Option<u32> {
@@ -19,6 +19,85 @@ pub fn get_task_name(task: &Value) -> Option<&str> {
     task.get("task")?.get("name")?.as_str()
 }
 
+/// IDs of the tasks a task requires to be completed first
+pub fn get_task_dependencies(task: &Value) -> Vec<u32> {
+    task.get("task")
+        .and_then(|t| t.get("dependencies"))
+        .and_then(|d| d.get("requires_completion_of"))
+        .and_then(|r| r.as_sequence())
+        .map(|deps| {
+            deps.iter()
+                .filter_map(|dep| dep.get("task_id")?.as_u64())
+                .map(|id| id as u32)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// A dependency cycle among the tasks, if there is one, as the IDs of its
+/// members with each requiring the next and the last requiring the first
+///
+/// Dependencies on tasks that are not in the list cannot form a cycle and
+/// are ignored.
+pub fn find_dependency_cycle(tasks: &[Value]) -> Option<Vec<u32>> {
+    let ids: HashSet<u32> = tasks.iter().filter_map(get_task_id).collect();
+    let mut remaining: HashMap<u32, Vec<u32>> = tasks
+        .iter()
+        .filter_map(|task| {
+            let mut deps = get_task_dependencies(task);
+            deps.retain(|dep| ids.contains(dep));
+            Some((get_task_id(task)?, deps))
+        })
+        .collect();
+
+    // Drop the tasks that could be scheduled; the rest sit on or behind a cycle
+    loop {
+        let ready: Vec<u32> = remaining
+            .iter()
+            .filter(|(_, deps)| deps.iter().all(|dep| !remaining.contains_key(dep)))
+            .map(|(id, _)| *id)
+            .collect();
+        if ready.is_empty() {
+            break;
+        }
+        for id in ready {
+            remaining.remove(&id);
+        }
+    }
+
+    // Each task left requires another one left, so following them loops
+    let mut path = vec![*remaining.keys().min()?];
+    loop {
+        let current = path[path.len() - 1];
+        let next = remaining[&current]
+            .iter()
+            .copied()
+            .filter(|dep| remaining.contains_key(dep))
+            .min()?;
+        if let Some(start) = path.iter().position(|&id| id == next) {
+            return Some(path.split_off(start));
+        }
+        path.push(next);
+    }
+}
+
+/// Fail if the tasks' dependencies form a cycle, naming its members
+pub fn check_dependency_cycles(tasks: &[Value]) -> Result<()> {
+    let Some(cycle) = find_dependency_cycle(tasks) else {
+        return Ok(());
+    };
+    let chain: Vec<String> = cycle
+        .iter()
+        .chain(cycle.first())
+        .map(|id| id.to_string())
+        .collect();
+    anyhow::bail!(
+        "Tasks {} depend on each other in a cycle (each requires the next to complete \
+         first); fix requires_completion_of in the tasks overview",
+        chain.join(" -> ")
+    )
+}
+
 /// Generate simple execution plan (fixed-size batches)
 pub fn generate_simple_execution_plan(
     tasks: &[Value],
@@ -170,9 +249,36 @@ pub fn parse_execution_plan(
         }
     }
 
+    // Whatever is left was never referenced by the plan and won't be expanded
+    if !task_map.is_empty() {
+        let mut unscheduled: Vec<u32> = task_map.into_keys().collect();
+        unscheduled.sort_unstable();
+        println!(
+            "Warning: {} task(s) not in any batch of the execution plan, skipping: {:?}",
+            unscheduled.len(),
+            unscheduled
+        );
+    }
+
     Ok(batches)
 }
 
+/// Batches of the AI execution plan, or the dependency-aware fallback when
+/// the plan failed, doesn't parse or schedules nothing
+///
+/// The fallback comes with the reason the plan was not used.
+pub fn plan_or_fallback_batches(
+    plan: Result<String>,
+    tasks: &[Value],
+) -> (Vec<Vec<Value>>, Option<String>) {
+    let reason = match plan.and_then(|yaml| parse_execution_plan(&yaml, tasks)) {
+        Ok(batches) if !batches.is_empty() || tasks.is_empty() => return (batches, None),
+        Ok(_) => "it has no batches".to_string(),
+        Err(e) => format!("{:#}", e),
+    };
+    (build_execution_batches_fallback(tasks), Some(reason))
+}
+
 /// Fallback: Simple dependency analysis if execution plan fails
 pub fn build_execution_batches_fallback(tasks: &[Value]) -> Vec<Vec<Value>> {
     println!("Using fallback dependency analysis");
@@ -222,9 +328,11 @@ pub fn build_execution_batches_fallback(tasks: &[Value]) -> Vec<Vec<Value>> {
 
             if can_run {
                 current_batch.push(task.clone());
-                scheduled.insert(task_id);
             }
         }
+        // Only tasks of earlier batches count as done, so a task never runs
+        // alongside one it depends on
+        scheduled.extend(current_batch.iter().filter_map(get_task_id));
 
         if current_batch.is_empty() {
             // Circular dependency or error - add remaining tasks
@@ -249,3 +357,64 @@ pub fn build_execution_batches_fallback(tasks: &[Value]) -> Vec<Vec<Value>> {
 
     batches
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: u32, requires: &[u32]) -> Value {
+        let deps: Vec<String> = requires.iter().map(|id| format!("{{task_id: {}}}", id)).collect();
+        serde_yaml::from_str(&format!(
+            "task: {{id: {}, dependencies: {{requires_completion_of: [{}]}}}}",
+            id,
+            deps.join(", ")
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_find_dependency_cycle() {
+        // 4 depends on the cycle without being part of it; 9 does not exist
+        let tasks = vec![
+            task(1, &[]),
+            task(2, &[3, 1]),
+            task(3, &[5]),
+            task(4, &[2, 9]),
+            task(5, &[2]),
+        ];
+        assert_eq!(get_task_dependencies(&tasks[1]), vec![3, 1]);
+        assert_eq!(find_dependency_cycle(&tasks), Some(vec![2, 3, 5]));
+        let error = check_dependency_cycles(&tasks).unwrap_err().to_string();
+        assert!(error.starts_with("Tasks 2 -> 3 -> 5 -> 2 depend on each other"));
+
+        assert_eq!(find_dependency_cycle(&[task(7, &[7])]), Some(vec![7]));
+
+        let acyclic = vec![task(1, &[]), task(2, &[1]), task(3, &[1, 2]), task(4, &[9])];
+        assert_eq!(find_dependency_cycle(&acyclic), None);
+        assert!(check_dependency_cycles(&acyclic).is_ok());
+    }
+
+    #[test]
+    fn test_unusable_plan_falls_back_to_dependency_order() {
+        let tasks = vec![task(1, &[]), task(2, &[1]), task(3, &[])];
+        let ids = |batches: &[Vec<Value>]| -> Vec<Vec<u32>> {
+            batches.iter().map(|batch| batch.iter().filter_map(get_task_id).collect()).collect()
+        };
+
+        let plan = "execution_plan:\n  batches:\n    - tasks: [{task_id: 2}, {task_id: 1}]\n";
+        let (batches, fallback) = plan_or_fallback_batches(Ok(plan.to_string()), &tasks);
+        assert_eq!(ids(&batches), vec![vec![2, 1]]);
+        assert_eq!(fallback, None);
+
+        for plan in [
+            Ok("execution_plan: [unclosed".to_string()),
+            Ok("execution_plan:\n  batches: []\n".to_string()),
+            Err(anyhow::anyhow!("agent failed")),
+        ] {
+            let (batches, fallback) = plan_or_fallback_batches(plan, &tasks);
+            // Task 2 still waits for task 1
+            assert_eq!(ids(&batches), vec![vec![1, 3], vec![2]]);
+            assert!(fallback.is_some());
+        }
+    }
+}
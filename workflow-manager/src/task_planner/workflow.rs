@@ -6,7 +6,8 @@ use anyhow::{Context, Result};
 use std::path::PathBuf;
 use tokio::fs;
 use workflow_manager_sdk::{
-    log_phase_complete, log_phase_start, log_state_file, log_task_complete, log_task_start,
+    log_phase_complete, log_phase_failed, log_phase_start, log_result, log_state_file,
+    log_task_complete, log_task_start,
 };
 
 /// Main workflow function that orchestrates all phases
@@ -108,7 +109,8 @@ pub async fn run_workflow(args: Args) -> Result<()> {
 
         let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
 
-        task_files = phase1_expand::expand_tasks(
+        // Fail the phase, not just the run, so the TUI shows why
+        task_files = match phase1_expand::expand_tasks(
             &tasks_overview_yaml,
             &task_template,
             args.simple_batching,
@@ -116,7 +118,14 @@ pub async fn run_workflow(args: Args) -> Result<()> {
             &output_dir,
             &timestamp,
         )
-        .await?;
+        .await
+        {
+            Ok(task_files) => task_files,
+            Err(e) => {
+                log_phase_failed!(1, "Expand Tasks", format!("{:#}", e));
+                return Err(e);
+            }
+        };
 
         println!("\n✓ Saved {} task files:", task_files.len());
         for path in &task_files {
@@ -179,7 +188,7 @@ pub async fn run_workflow(args: Args) -> Result<()> {
                 format!("Failed to read task template: {}", task_template_path)
             })?;
 
-        phase2_review::review_tasks(
+        let report = phase2_review::review_tasks(
             &tasks_overview_yaml,
             &tasks_yaml,
             &impl_md,
@@ -189,6 +198,10 @@ pub async fn run_workflow(args: Args) -> Result<()> {
         .await?;
 
         log_phase_complete!(2, "Review complete");
+        log_result!(
+            format!("Review complete: {}/{} tasks approved", report.approved, report.total),
+            [report.path.display()]
+        );
     }
 
     println!("\n{}", "=".repeat(80));
@@ -2,133 +2,25 @@
 
 use ratatui::{
     layout::{Constraint, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Wrap},
     Frame,
 };
 
+use super::components::centered_rect;
+use super::markdown::render_markdown;
 use crate::chat::{self, ActivePane};
 use crate::models::App;
 
-/// Parse message content with basic markdown formatting into styled lines
-fn format_message_content(content: &str) -> Vec<Line<'static>> {
-    let mut lines = Vec::new();
-
-    for line in content.lines() {
-        let trimmed = line.trim();
-
-        // Skip completely empty lines but preserve them as blank lines
-        if trimmed.is_empty() {
-            lines.push(Line::from(""));
-            continue;
-        }
-
-        // Detect list items (numbered or bulleted)
-        let (is_list, indent_level, list_content) = if let Some(rest) = trimmed.strip_prefix("**") {
-            // Bold headers (e.g., "**Workflow Management Tools:**")
-            if let Some(end_idx) = rest.find("**") {
-                let bold_text = &rest[..end_idx];
-                let after = &rest[end_idx + 2..];
-
-                let mut spans = vec![
-                    Span::styled(
-                        format!("  {}", bold_text),
-                        Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
-                    ),
-                ];
-
-                if !after.is_empty() {
-                    spans.push(Span::styled(
-                        after.to_string(),
-                        Style::default().fg(Color::White),
-                    ));
-                }
-
-                lines.push(Line::from(spans));
-                continue;
-            }
-            (false, 0, trimmed)
-        } else if let Some(rest) = trimmed.strip_prefix(|c: char| c.is_ascii_digit()) {
-            // Numbered list (e.g., "1. Item")
-            if let Some(rest) = rest.strip_prefix(". ") {
-                (true, 1, rest)
-            } else {
-                (false, 0, trimmed)
-            }
-        } else if let Some(rest) = trimmed.strip_prefix("- ") {
-            // Bullet list
-            (true, 1, rest)
-        } else if let Some(rest) = trimmed.strip_prefix("* ") {
-            // Asterisk list
-            (true, 1, rest)
-        } else {
-            (false, 0, trimmed)
-        };
-
-        // Format the content with inline markdown
-        let formatted_spans = if list_content.contains("**") {
-            // Parse inline bold
-            let mut spans = Vec::new();
-            let mut remaining = list_content;
-            let indent = "  ".repeat(indent_level);
-
-            if is_list {
-                spans.push(Span::raw(format!("{}• ", indent)));
-            } else {
-                spans.push(Span::raw(format!("{}", indent)));
-            }
-
-            while let Some(start) = remaining.find("**") {
-                // Add text before bold
-                if start > 0 {
-                    spans.push(Span::raw(remaining[..start].to_string()));
-                }
-
-                // Find end of bold
-                if let Some(end) = remaining[start + 2..].find("**") {
-                    let bold_text = &remaining[start + 2..start + 2 + end];
-                    spans.push(Span::styled(
-                        bold_text.to_string(),
-                        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
-                    ));
-                    remaining = &remaining[start + 2 + end + 2..];
-                } else {
-                    // No closing **, just add the rest
-                    spans.push(Span::raw(remaining[start..].to_string()));
-                    break;
-                }
-            }
-
-            // Add any remaining text
-            if !remaining.is_empty() {
-                spans.push(Span::raw(remaining.to_string()));
-            }
-
-            spans
-        } else {
-            // No inline formatting
-            let indent = "  ".repeat(indent_level);
-            if is_list {
-                vec![Span::raw(format!("{}• {}", indent, list_content))]
-            } else {
-                vec![Span::raw(format!("{}{}", indent, list_content))]
-            }
-        };
-
-        lines.push(Line::from(formatted_spans));
-    }
-
-    lines
-}
-
 pub fn render_chat(f: &mut Frame, area: Rect, app: &mut App) {
+    let icons = app.icons;
     let chat = match &mut app.chat {
         Some(c) => c,
         None => {
             let error = Paragraph::new("Chat unavailable - runtime initialization failed")
                 .block(Block::default().borders(Borders::ALL).title(" Error "))
-                .style(Style::default().fg(Color::Red));
+                .style(Style::default().fg(app.theme.failed));
             f.render_widget(error, area);
             return;
         }
@@ -150,10 +42,15 @@ pub fn render_chat(f: &mut Frame, area: Rect, app: &mut App) {
 
     // === RENDER CHAT MESSAGES ===
     let mut message_lines = Vec::new();
+    // Inside the borders; code blocks are fitted to this width
+    let content_width = messages_area.width.saturating_sub(2) as usize;
+
+    // Line of the selected tool call, to scroll it into view
+    let mut selected_tool_line = None;
 
     // Show loading animation if not initialized
     if !chat.initialized && chat.init_error.is_none() {
-        let spinner = chat.get_spinner_char();
+        let spinner = chat.get_spinner_char(icons.spinner);
 
         message_lines.push(Line::from(""));
         message_lines.push(Line::from(""));
@@ -162,46 +59,50 @@ pub fn render_chat(f: &mut Frame, area: Rect, app: &mut App) {
             Span::styled(
                 format!("    {} ", spinner),
                 Style::default()
-                    .fg(Color::White)
+                    .fg(app.theme.text)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
                 "Initializing Claude...",
-                Style::default().fg(Color::Yellow),
+                Style::default().fg(app.theme.accent),
             ),
         ]));
         message_lines.push(Line::from(""));
         message_lines.push(Line::from(Span::styled(
             "    Please wait while we set up your AI assistant",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(app.theme.dim),
         )));
     } else if let Some(error) = &chat.init_error {
         // Show error state
         message_lines.push(Line::from(""));
         message_lines.push(Line::from(""));
         message_lines.push(Line::from(Span::styled(
-            "    ✗ Initialization Failed",
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            format!("    {} Initialization Failed", icons.error),
+            Style::default().fg(app.theme.failed).add_modifier(Modifier::BOLD),
         )));
         message_lines.push(Line::from(""));
         message_lines.push(Line::from(Span::styled(
             format!("    {}", error),
-            Style::default().fg(Color::Red),
+            Style::default().fg(app.theme.failed),
         )));
     } else {
         // Normal chat mode - show messages
-        for msg in &chat.messages {
+        for (msg_idx, msg) in chat.messages.iter().enumerate() {
             let role_style = match msg.role {
                 chat::ChatRole::User => Style::default()
-                    .fg(Color::White)
+                    .fg(app.theme.text)
                     .add_modifier(Modifier::BOLD),
                 chat::ChatRole::Assistant => Style::default()
-                    .fg(Color::White)
+                    .fg(app.theme.text)
+                    .add_modifier(Modifier::BOLD),
+                chat::ChatRole::System => Style::default()
+                    .fg(app.theme.info)
                     .add_modifier(Modifier::BOLD),
             };
             let role_text = match msg.role {
                 chat::ChatRole::User => "You",
                 chat::ChatRole::Assistant => "Claude",
+                chat::ChatRole::System => "Workflow Manager",
             };
 
             message_lines.push(Line::from(vec![Span::styled(
@@ -209,19 +110,57 @@ pub fn render_chat(f: &mut Frame, area: Rect, app: &mut App) {
                 role_style,
             )]));
 
-            // Use formatted message content with markdown parsing
-            let formatted_lines = format_message_content(&msg.content);
-            message_lines.extend(formatted_lines);
+            match msg.role {
+                chat::ChatRole::System => {
+                    // Slash command output, kept verbatim so columns line up
+                    message_lines.extend(msg.content.lines().map(|line| {
+                        Line::from(Span::styled(
+                            format!("  {}", line),
+                            Style::default().fg(app.theme.secondary),
+                        ))
+                    }));
+                }
+                chat::ChatRole::Assistant => {
+                    message_lines.extend(render_markdown(
+                        &msg.content,
+                        content_width,
+                        icons,
+                        &app.theme,
+                    ));
+                }
+                chat::ChatRole::User => {
+                    message_lines
+                        .extend(msg.content.lines().map(|line| Line::from(line.to_string())));
+                }
+            }
 
-            // Show tool calls (simplified - details in logs pane)
+            // One line per tool call; Shift+Up/Down selects one and Enter
+            // shows its input and result
             if !msg.tool_calls.is_empty() {
                 message_lines.push(Line::from(""));
-                message_lines.push(Line::from(vec![
-                    Span::styled(
-                        format!("  🔧 {} tool(s) used (see logs →)", msg.tool_calls.len()),
-                        Style::default().fg(Color::Yellow),
+            }
+            for (tool_idx, tool_call) in msg.tool_calls.iter().enumerate() {
+                let key = (msg_idx, tool_idx);
+                let expanded = chat.expanded_tools.contains(&key);
+                let mut style = Style::default().fg(app.theme.accent);
+                if chat.selected_tool == Some(key) {
+                    selected_tool_line = Some(message_lines.len());
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+                message_lines.push(Line::from(Span::styled(
+                    format!(
+                        "  {} {} [Tool Used] {}",
+                        if expanded { icons.expanded } else { icons.collapsed },
+                        icons.tool,
+                        tool_call.name
                     ),
-                ]));
+                    style,
+                )));
+                if expanded {
+                    message_lines.extend(chat::tool_call_details(tool_call).into_iter().map(
+                        |line| Line::from(Span::styled(line, Style::default().fg(app.theme.dim))),
+                    ));
+                }
             }
 
             message_lines.push(Line::from(""));
@@ -229,64 +168,65 @@ pub fn render_chat(f: &mut Frame, area: Rect, app: &mut App) {
 
         if chat.waiting_for_response {
             // Animated loading indicator like Claude Code
-            let spinner = chat.get_spinner_char();
+            let spinner = chat.get_spinner_char(icons.spinner);
             let elapsed = chat.get_elapsed_seconds().unwrap_or(0);
 
             message_lines.push(Line::from(vec![
                 Span::styled(
                     format!("{} ", spinner),
                     Style::default()
-                        .fg(Color::White)
+                        .fg(app.theme.text)
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::styled(
                     "Thinking",
-                    Style::default().fg(Color::Yellow),
+                    Style::default().fg(app.theme.accent),
                 ),
                 Span::styled(
-                    "…",
-                    Style::default().fg(Color::DarkGray),
+                    icons.ellipsis,
+                    Style::default().fg(app.theme.dim),
                 ),
             ]));
 
             message_lines.push(Line::from(vec![
                 Span::styled(
-                    "  (esc to interrupt",
-                    Style::default().fg(Color::DarkGray),
+                    "  (esc or ctrl+c to interrupt",
+                    Style::default().fg(app.theme.dim),
                 ),
                 Span::styled(
-                    format!(" · {}s)", elapsed),
-                    Style::default().fg(Color::DarkGray),
+                    format!(" {} {}s)", icons.middle_dot, elapsed),
+                    Style::default().fg(app.theme.dim),
                 ),
             ]));
         }
     }
 
     // Determine title and border style
+    let settings = chat.settings.summary();
     let (chat_title, chat_border_style) = if !chat.initialized && chat.init_error.is_none() {
         // Loading state - animated dots
         let indicator = chat.get_loading_indicator();
         (
-            format!(" Chat with Claude [{}] ", indicator),
-            Style::default().fg(Color::Yellow),
+            format!(" Chat with Claude ({}) [{}] ", settings, indicator),
+            Style::default().fg(app.theme.accent),
         )
     } else if chat.init_error.is_some() {
         // Error state
         (
-            " Chat with Claude [✗] ".to_string(),
-            Style::default().fg(Color::Red),
+            format!(" Chat with Claude ({}) [{}] ", settings, icons.error),
+            Style::default().fg(app.theme.failed),
         )
     } else if matches!(chat.active_pane, ActivePane::ChatMessages) {
         // Ready state - active pane
         (
-            " Chat with Claude [✓] ".to_string(),
-            Style::default().fg(Color::White),
+            format!(" Chat with Claude ({}) [{}] ", settings, icons.success),
+            Style::default().fg(app.theme.text),
         )
     } else {
         // Ready state - inactive pane
         (
-            " Chat with Claude [✓] ".to_string(),
-            Style::default().fg(Color::DarkGray),
+            format!(" Chat with Claude ({}) [{}] ", settings, icons.success),
+            Style::default().fg(app.theme.dim),
         )
     };
 
@@ -296,6 +236,19 @@ pub fn render_chat(f: &mut Frame, area: Rect, app: &mut App) {
     // Auto-scroll to bottom if enabled
     chat.auto_scroll_to_bottom(viewport_height);
 
+    // Bring a newly selected tool call into view
+    if chat.reveal_selected_tool {
+        chat.reveal_selected_tool = false;
+        if let Some(line) = selected_tool_line {
+            let line = line as u16;
+            if line < chat.message_scroll {
+                chat.message_scroll = line;
+            } else if line >= chat.message_scroll.saturating_add(viewport_height) {
+                chat.message_scroll = line + 1 - viewport_height;
+            }
+        }
+    }
+
     let messages_widget = Paragraph::new(message_lines)
         .block(
             Block::default()
@@ -312,17 +265,17 @@ pub fn render_chat(f: &mut Frame, area: Rect, app: &mut App) {
     let (input_title, input_style) = if let Some(error) = &chat.init_error {
         (
             format!(" Error: {} ", error),
-            Style::default().fg(Color::Red),
+            Style::default().fg(app.theme.failed),
         )
     } else if !chat.initialized {
         (
             " Please wait... ".to_string(),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(app.theme.dim),
         )
     } else {
         (
             " Type your message (Enter to send, Tab to switch pane) ".to_string(),
-            Style::default().fg(Color::White),
+            Style::default().fg(app.theme.text),
         )
     };
 
@@ -352,9 +305,9 @@ pub fn render_chat(f: &mut Frame, area: Rect, app: &mut App) {
 
     // === RENDER TOOL CALL LOGS ===
     let log_border_style = if matches!(chat.active_pane, ActivePane::Logs) {
-        Style::default().fg(Color::Yellow)
+        Style::default().fg(app.theme.accent)
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(app.theme.dim)
     };
 
     // Build log lines from tool calls in message history
@@ -366,12 +319,17 @@ pub fn render_chat(f: &mut Frame, area: Rect, app: &mut App) {
             let context = match msg.role {
                 chat::ChatRole::User => format!("After user message #{}", msg_idx + 1),
                 chat::ChatRole::Assistant => format!("Claude's response #{}", msg_idx + 1),
+                chat::ChatRole::System => format!("Command output #{}", msg_idx + 1),
             };
             log_lines.push(Line::from(vec![
                 Span::styled(
-                    format!("═══ {} ═══", context),
+                    format!(
+                        "{sep} {} {sep}",
+                        context,
+                        sep = icons.heavy_separator.repeat(3)
+                    ),
                     Style::default()
-                        .fg(Color::White)
+                        .fg(app.theme.text)
                         .add_modifier(Modifier::BOLD),
                 ),
             ]));
@@ -381,16 +339,16 @@ pub fn render_chat(f: &mut Frame, area: Rect, app: &mut App) {
             for (tool_idx, tool_call) in msg.tool_calls.iter().enumerate() {
                 log_lines.push(Line::from(vec![
                     Span::styled(
-                        format!("🔧 Tool #{}: ", tool_idx + 1),
-                        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                        format!("{} Tool #{}: ", icons.tool, tool_idx + 1),
+                        Style::default().fg(app.theme.accent).add_modifier(Modifier::BOLD),
                     ),
-                    Span::styled(&tool_call.name, Style::default().fg(Color::White)),
+                    Span::styled(&tool_call.name, Style::default().fg(app.theme.text)),
                 ]));
 
                 // Input (parameters)
                 if !tool_call.input.is_empty() {
                     log_lines.push(Line::from(vec![
-                        Span::styled("  Input: ", Style::default().fg(Color::Gray)),
+                        Span::styled("  Input: ", Style::default().fg(app.theme.secondary)),
                         Span::raw(&tool_call.input),
                     ]));
                 }
@@ -399,7 +357,7 @@ pub fn render_chat(f: &mut Frame, area: Rect, app: &mut App) {
                 if !tool_call.output.is_empty() {
                     log_lines.push(Line::from(Span::styled(
                         "  Output:",
-                        Style::default().fg(Color::Gray),
+                        Style::default().fg(app.theme.secondary),
                     )));
 
                     // Split output into lines and indent
@@ -411,7 +369,7 @@ pub fn render_chat(f: &mut Frame, area: Rect, app: &mut App) {
                     if tool_call.output.lines().count() > 50 {
                         log_lines.push(Line::from(Span::styled(
                             "    ... (truncated)",
-                            Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC),
+                            Style::default().fg(app.theme.dim).add_modifier(Modifier::ITALIC),
                         )));
                     }
                 }
@@ -425,13 +383,13 @@ pub fn render_chat(f: &mut Frame, area: Rect, app: &mut App) {
         log_lines.push(Line::from(Span::styled(
             "No tool calls yet...",
             Style::default()
-                .fg(Color::DarkGray)
+                .fg(app.theme.dim)
                 .add_modifier(Modifier::ITALIC),
         )));
         log_lines.push(Line::from(""));
         log_lines.push(Line::from(Span::styled(
             "Tool calls will appear here when Claude uses workflows.",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(app.theme.dim),
         )));
     }
 
@@ -447,3 +405,55 @@ pub fn render_chat(f: &mut Frame, area: Rect, app: &mut App) {
 
     f.render_widget(logs_widget, right_area);
 }
+
+/// Overlay choosing the model and tools of the chat
+pub fn render_chat_settings(f: &mut Frame, area: Rect, app: &App) {
+    let Some(draft) = app.chat.as_ref().and_then(|chat| chat.settings_draft.as_ref()) else {
+        return;
+    };
+    let popup_area = centered_rect(60, 30, area);
+    let key = Style::default().add_modifier(Modifier::BOLD);
+    let value = Style::default().fg(app.theme.accent);
+    let tools = if draft.read_only {
+        "read-only (cannot launch or cancel workflows)"
+    } else {
+        "all (can launch and cancel workflows)"
+    };
+
+    let text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(" [m]", key),
+            Span::raw(" Model: "),
+            Span::styled(draft.model_label(), value),
+        ]),
+        Line::from(vec![
+            Span::styled(" [r]", key),
+            Span::raw(" Tools: "),
+            Span::styled(tools, value),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            " Applying reconnects Claude without the earlier context",
+            Style::default().fg(app.theme.dim),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(" [Enter]", key),
+            Span::raw(" Apply  "),
+            Span::styled("[Esc]", key),
+            Span::raw(" Cancel"),
+        ]),
+    ];
+
+    let paragraph = Paragraph::new(text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.accent))
+            .title(" Chat Settings ")
+            .style(Style::default().bg(app.theme.background)),
+    );
+
+    f.render_widget(ratatui::widgets::Clear, popup_area);
+    f.render_widget(paragraph, popup_area);
+}
@@ -1,21 +1,47 @@
 //! Reusable UI components (dropdowns, file browser, helpers)
 
+use chrono::{DateTime, Local};
 use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
 use ratatui::{
     layout::{Constraint, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
     Frame,
 };
+use regex::Regex;
 use std::path::PathBuf;
+use std::time::Duration;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+use workflow_manager_sdk::LogLevel;
 
-use crate::models::App;
+use super::icons::IconSet;
+use super::theme::Theme;
+use crate::app::ParamsFileAction;
+use crate::models::{AgentStatus, App, LogLine, PhaseStatus, TaskStatus, View, WorkflowPhase};
+
+/// Narrowest preview worth showing; below this the preview is dropped entirely
+pub const PREVIEW_MIN_WIDTH: usize = 8;
+
+/// Widest preview shown, even on very wide terminals
+pub const PREVIEW_MAX_WIDTH: usize = 120;
+
+const ELLIPSIS: &str = "...";
 
 pub fn render_dropdown(f: &mut Frame, area: Rect, app: &App) {
-    // Check if we're showing history or file paths
-    let (item_count, title) = if !app.history_items.is_empty() {
+    // Check if we're showing field options, presets, history or file paths
+    let (item_count, title) = if !app.option_items.is_empty() {
+        let title = if app.option_multi {
+            " Options (Space: toggle, Enter: save) "
+        } else {
+            " Options "
+        };
+        (app.option_items.len(), title)
+    } else if !app.preset_items.is_empty() {
+        (app.preset_items.len(), " Presets (Enter: load) ")
+    } else if !app.history_items.is_empty() {
         (app.history_items.len(), " History ")
     } else if !app.dropdown_items.is_empty() {
         (app.dropdown_items.len(), " Tab Completion ")
@@ -24,7 +50,10 @@ pub fn render_dropdown(f: &mut Frame, area: Rect, app: &App) {
     };
 
     // Calculate dropdown position (below current field)
-    let field_offset = app.edit_field_index * 4; // Each field takes ~4 lines
+    let field_offset = match app.current_view {
+        View::WorkflowEdit(idx) => app.edit_field_line(idx),
+        _ => 0,
+    };
     let dropdown_y = area.y + field_offset as u16 + 4;
     let dropdown_height = std::cmp::min(10, item_count as u16 + 2);
 
@@ -35,9 +64,45 @@ pub fn render_dropdown(f: &mut Frame, area: Rect, app: &App) {
         height: dropdown_height,
     };
 
-    let items: Vec<ListItem> = if !app.history_items.is_empty() {
-        // History dropdown
-        app.history_items
+    let items: Vec<ListItem> = if !app.option_items.is_empty() {
+        // Select / MultiSelect options
+        app.option_items
+            .iter()
+            .enumerate()
+            .map(|(i, option)| {
+                let is_selected = i == app.dropdown_selected;
+
+                let style = if is_selected {
+                    Style::default()
+                        .fg(app.theme.highlight_text)
+                        .bg(app.theme.highlight)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(app.theme.text)
+                };
+
+                let label = if !app.option_multi {
+                    option.clone()
+                } else if app.option_checked.contains(option) {
+                    format!("{} {}", app.icons.checked, option)
+                } else {
+                    format!("{} {}", app.icons.unchecked, option)
+                };
+
+                ListItem::new(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled(label, style),
+                ]))
+            })
+            .collect()
+    } else if !app.history_items.is_empty() || !app.preset_items.is_empty() {
+        // Preset or history dropdown
+        let values = if app.preset_items.is_empty() {
+            &app.history_items
+        } else {
+            &app.preset_items
+        };
+        values
             .iter()
             .enumerate()
             .map(|(i, value)| {
@@ -45,11 +110,11 @@ pub fn render_dropdown(f: &mut Frame, area: Rect, app: &App) {
 
                 let style = if is_selected {
                     Style::default()
-                        .fg(Color::Black)
-                        .bg(Color::Yellow)
+                        .fg(app.theme.highlight_text)
+                        .bg(app.theme.highlight)
                         .add_modifier(Modifier::BOLD)
                 } else {
-                    Style::default().fg(Color::White)
+                    Style::default().fg(app.theme.text)
                 };
 
                 ListItem::new(Line::from(vec![
@@ -83,13 +148,13 @@ pub fn render_dropdown(f: &mut Frame, area: Rect, app: &App) {
 
                 let style = if is_selected {
                     Style::default()
-                        .fg(Color::Black)
-                        .bg(Color::Yellow)
+                        .fg(app.theme.highlight_text)
+                        .bg(app.theme.highlight)
                         .add_modifier(Modifier::BOLD)
                 } else if is_dir {
-                    Style::default().fg(Color::White)
+                    Style::default().fg(app.theme.text)
                 } else {
-                    Style::default().fg(Color::White)
+                    Style::default().fg(app.theme.text)
                 };
 
                 ListItem::new(Line::from(Span::styled(name, style)))
@@ -115,9 +180,9 @@ pub fn render_dropdown(f: &mut Frame, area: Rect, app: &App) {
     let list = List::new(visible_items).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Yellow))
+            .border_style(Style::default().fg(app.theme.accent))
             .title(title)
-            .style(Style::default().bg(Color::DarkGray)),
+            .style(Style::default().bg(app.theme.surface)),
     );
 
     f.render_widget(ratatui::widgets::Clear, dropdown_area);
@@ -152,21 +217,29 @@ pub fn render_file_browser(f: &mut Frame, area: Rect, app: &App) {
             let is_selected = *original_idx == app.file_browser_selected;
             let is_dir = path.is_dir();
 
-            let icon = if is_dir { "📁" } else { "📄" };
+            let icon = if is_dir {
+                app.icons.folder
+            } else {
+                app.icons.file
+            };
             let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("..");
 
             let style = if is_selected {
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(app.theme.accent)
                     .add_modifier(Modifier::BOLD)
             } else if is_dir {
-                Style::default().fg(Color::White)
+                Style::default().fg(app.theme.text)
             } else {
-                Style::default().fg(Color::White)
+                Style::default().fg(app.theme.text)
             };
 
             ListItem::new(Line::from(vec![
-                Span::raw(if is_selected { "▶ " } else { "  " }),
+                Span::raw(if is_selected {
+                    format!("{} ", app.icons.selected)
+                } else {
+                    "  ".to_string()
+                }),
                 Span::raw(format!("{} ", icon)),
                 Span::styled(name, style),
             ]))
@@ -174,7 +247,10 @@ pub fn render_file_browser(f: &mut Frame, area: Rect, app: &App) {
         .collect();
 
     let title = if app.file_browser_search.is_empty() {
-        format!(" File Browser: {} ", app.current_dir.display())
+        format!(
+            " File Browser: {} [Ctrl+O] View ",
+            app.current_dir.display()
+        )
     } else {
         format!(" File Browser [search: {}] ", app.file_browser_search)
     };
@@ -183,13 +259,195 @@ pub fn render_file_browser(f: &mut Frame, area: Rect, app: &App) {
         Block::default()
             .borders(Borders::ALL)
             .title(title)
-            .style(Style::default().bg(Color::Black)),
+            .style(Style::default().bg(app.theme.background)),
     );
 
     f.render_widget(ratatui::widgets::Clear, popup_area);
     f.render_widget(list, popup_area);
 }
 
+/// Path prompt for exporting an execution's structured log
+pub fn render_export_prompt(f: &mut Frame, area: Rect, app: &App) {
+    let popup_area = centered_rect(70, 20, area);
+
+    let text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw(" "),
+            Span::styled(app.edit_buffer.as_str(), Style::default().fg(app.theme.text)),
+            Span::styled(app.icons.cursor, Style::default().fg(app.theme.accent)),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(" [Tab]", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" Complete  "),
+            Span::styled("[Enter]", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" Export  "),
+            Span::styled("[Esc]", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" Cancel"),
+        ]),
+    ];
+
+    let paragraph = Paragraph::new(text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.accent))
+            .title(" Export Logs (JSON Lines) ")
+            .style(Style::default().bg(app.theme.background)),
+    );
+
+    f.render_widget(ratatui::widgets::Clear, popup_area);
+    f.render_widget(paragraph, popup_area);
+}
+
+pub fn render_params_file_prompt(f: &mut Frame, area: Rect, app: &App) {
+    let (title, confirm) = match app.params_file_prompt {
+        Some(ParamsFileAction::Save) => (" Save Params To (JSON or YAML) ", " Save  "),
+        _ => (" Load Params From (JSON or YAML) ", " Load  "),
+    };
+    let popup_area = centered_rect(70, 20, area);
+
+    let text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw(" "),
+            Span::styled(app.edit_buffer.as_str(), Style::default().fg(app.theme.text)),
+            Span::styled(app.icons.cursor, Style::default().fg(app.theme.accent)),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(" [Tab]", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" Complete  "),
+            Span::styled("[Enter]", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(confirm),
+            Span::styled("[Esc]", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" Cancel"),
+        ]),
+    ];
+
+    let paragraph = Paragraph::new(text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.accent))
+            .title(title)
+            .style(Style::default().bg(app.theme.background)),
+    );
+
+    f.render_widget(ratatui::widgets::Clear, popup_area);
+    f.render_widget(paragraph, popup_area);
+}
+
+pub fn render_preset_prompt(f: &mut Frame, area: Rect, app: &App) {
+    let popup_area = centered_rect(60, 20, area);
+
+    let text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw(" "),
+            Span::styled(app.edit_buffer.as_str(), Style::default().fg(app.theme.text)),
+            Span::styled(app.icons.cursor, Style::default().fg(app.theme.accent)),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(" [Enter]", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" Save (replaces a preset of the same name)  "),
+            Span::styled("[Esc]", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" Cancel"),
+        ]),
+    ];
+
+    let paragraph = Paragraph::new(text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.accent))
+            .title(" Save Preset As ")
+            .style(Style::default().bg(app.theme.background)),
+    );
+
+    f.render_widget(ratatui::widgets::Clear, popup_area);
+    f.render_widget(paragraph, popup_area);
+}
+
+pub fn render_search_prompt(f: &mut Frame, area: Rect, app: &App) {
+    let Some(query) = &app.search_prompt else {
+        return;
+    };
+    let popup_area = centered_rect(60, 20, area);
+
+    let text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw(" /"),
+            Span::styled(query.as_str(), Style::default().fg(app.theme.text)),
+            Span::styled(app.icons.cursor, Style::default().fg(app.theme.accent)),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(" [Enter]", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" Search (empty clears)  "),
+            Span::styled("[Esc]", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" Cancel"),
+        ]),
+    ];
+
+    let paragraph = Paragraph::new(text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.accent))
+            .title(" Search Phases, Tasks and Messages ")
+            .style(Style::default().bg(app.theme.background)),
+    );
+
+    f.render_widget(ratatui::widgets::Clear, popup_area);
+    f.render_widget(paragraph, popup_area);
+}
+
+/// Overlay with the command a launch would run; input problems reported by
+/// the runtime follow the command after a blank line
+pub fn render_command_preview(f: &mut Frame, area: Rect, app: &App) {
+    let Some(preview) = &app.command_preview else {
+        return;
+    };
+    let popup_area = centered_rect(80, 50, area);
+
+    let (command, problems) = preview.split_once("\n\n").unwrap_or((preview, ""));
+    let mut text: Vec<Line> = command
+        .lines()
+        .map(|line| {
+            let color = if line.starts_with('#') {
+                app.theme.dim
+            } else {
+                app.theme.text
+            };
+            Line::from(Span::styled(line, Style::default().fg(color)))
+        })
+        .collect();
+    if !problems.is_empty() {
+        text.push(Line::from(""));
+        text.extend(
+            problems
+                .lines()
+                .map(|line| Line::from(Span::styled(line, Style::default().fg(app.theme.failed)))),
+        );
+    }
+    text.push(Line::from(""));
+    text.push(Line::from(Span::styled(
+        "Press any key to close",
+        Style::default().fg(app.theme.dim),
+    )));
+
+    let paragraph = Paragraph::new(text).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.info))
+            .title(" Command Preview ")
+            .style(Style::default().bg(app.theme.background)),
+    );
+
+    f.render_widget(ratatui::widgets::Clear, popup_area);
+    f.render_widget(paragraph, popup_area);
+}
+
 /// Helper to create a centered rect
 pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = ratatui::layout::Layout::default()
@@ -210,3 +468,671 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         ])
         .split(popup_layout[1])[1]
 }
+
+/// Columns to scroll lines in a pane `width` columns wide: the requested
+/// offset, but no further than it takes to show the end of the widest line
+pub fn horizontal_offset(lines: &[Line], requested: usize, width: u16) -> u16 {
+    let widest = lines.iter().map(Line::width).max().unwrap_or(0);
+    requested.min(widest.saturating_sub(width as usize)) as u16
+}
+
+/// Truncate text to fit within `max_width` terminal columns.
+///
+/// Cuts on grapheme boundaries (never splits a multi-byte character or emoji)
+/// and appends "..." when the text had to be shortened.
+pub fn truncate_to_width(text: &str, max_width: usize) -> String {
+    if text.width() <= max_width {
+        return text.to_string();
+    }
+
+    let budget = max_width.saturating_sub(ELLIPSIS.len());
+    let mut result = String::new();
+    let mut used = 0;
+    for grapheme in text.graphemes(true) {
+        let w = grapheme.width();
+        if used + w > budget {
+            break;
+        }
+        result.push_str(grapheme);
+        used += w;
+    }
+    result.push_str(ELLIPSIS);
+    result
+}
+
+/// Build a " - message" preview that fills the space left on a line.
+///
+/// `line_spans` are the spans already on the line and `pane_width` is the
+/// inner width of the pane the line is rendered in. Returns `None` when
+/// there is not enough room for a useful preview.
+pub fn message_preview(line_spans: &[Span], message: &str, pane_width: u16) -> Option<String> {
+    const SEPARATOR: &str = " - ";
+
+    let used: usize = line_spans.iter().map(|s| s.content.width()).sum();
+    let available = (pane_width as usize)
+        .saturating_sub(used + SEPARATOR.len())
+        .min(PREVIEW_MAX_WIDTH);
+    if available < PREVIEW_MIN_WIDTH {
+        return None;
+    }
+
+    Some(format!(
+        "{}{}",
+        SEPARATOR,
+        truncate_to_width(message, available)
+    ))
+}
+
+/// Preview on the line of a collapsed node: its error in red if it failed,
+/// otherwise its last message, dimmed
+pub fn node_preview(
+    line_spans: &[Span],
+    error: Option<&str>,
+    last_message: Option<&str>,
+    pane_width: u16,
+    theme: &Theme,
+) -> Option<Span<'static>> {
+    let (message, color) = match (error, last_message) {
+        (Some(error), _) => (error, theme.failed),
+        (None, Some(message)) => (message, theme.dim),
+        (None, None) => return None,
+    };
+    let preview = message_preview(line_spans, message, pane_width)?;
+    Some(Span::styled(preview, Style::default().fg(color)))
+}
+
+/// Style of a task or agent message in the tree; errors are red
+pub fn tree_message_style(message: &LogLine, theme: &Theme) -> Style {
+    let color = match message.level {
+        LogLevel::Error => theme.failed,
+        _ => theme.secondary,
+    };
+    Style::default().fg(color)
+}
+
+/// Cells in the inline task progress gauge
+pub const GAUGE_WIDTH: usize = 10;
+
+/// Inline gauge such as "[████░░░░░░] 42%" for a fraction in 0.0-1.0
+pub fn progress_gauge(fraction: f64, icons: &IconSet) -> String {
+    let fraction = fraction.clamp(0.0, 1.0);
+    let filled = (fraction * GAUGE_WIDTH as f64).round() as usize;
+    format!(
+        "[{}{}] {:>3.0}%",
+        icons.gauge_filled.repeat(filled),
+        icons.gauge_empty.repeat(GAUGE_WIDTH - filled),
+        fraction * 100.0
+    )
+}
+
+/// Overall progress of a phase tree, shown above the tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProgressSummary {
+    /// 1-based number of the running phase, or of the last one started
+    pub current_phase: usize,
+    pub total_phases: usize,
+    pub completed_tasks: usize,
+    pub total_tasks: usize,
+    pub agents: usize,
+    /// Any phase, task or agent failed
+    pub failed: bool,
+    /// Every phase completed
+    pub completed: bool,
+}
+
+impl ProgressSummary {
+    pub fn from_phases(phases: &[WorkflowPhase]) -> Self {
+        let tasks = || phases.iter().flat_map(|p| p.tasks.iter());
+        let agents = || tasks().flat_map(|t| t.agents.iter());
+
+        let current_phase = phases
+            .iter()
+            .position(|p| p.status == PhaseStatus::Running)
+            .map(|idx| idx + 1)
+            .unwrap_or_else(|| {
+                phases
+                    .iter()
+                    .filter(|p| p.status != PhaseStatus::NotStarted)
+                    .count()
+            });
+
+        Self {
+            current_phase,
+            total_phases: phases.len(),
+            completed_tasks: tasks()
+                .filter(|t| t.status == TaskStatus::Completed)
+                .count(),
+            total_tasks: tasks().count(),
+            agents: agents().count(),
+            failed: phases.iter().any(|p| p.status == PhaseStatus::Failed)
+                || tasks().any(|t| t.status == TaskStatus::Failed)
+                || agents().any(|a| a.status == AgentStatus::Failed),
+            completed: !phases.is_empty()
+                && phases.iter().all(|p| p.status == PhaseStatus::Completed),
+        }
+    }
+
+    /// One-line text such as "Phase 2/5 · 3/8 tasks complete · 12 agents"
+    pub fn text(&self, icons: &IconSet) -> String {
+        format!(
+            "Phase {}/{} {dot} {}/{} tasks complete {dot} {} agent{}",
+            self.current_phase,
+            self.total_phases,
+            self.completed_tasks,
+            self.total_tasks,
+            self.agents,
+            if self.agents == 1 { "" } else { "s" },
+            dot = icons.middle_dot,
+        )
+    }
+}
+
+/// A running workflow without events for this long is flagged as stalled
+pub const STALL_WARNING: Duration = Duration::from_secs(120);
+
+/// Status strip of a tab: its tasks by status, the time since launch and
+/// when the latest event arrived
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TabStatus {
+    pub running_tasks: usize,
+    pub completed_tasks: usize,
+    pub failed_tasks: usize,
+    /// Since launch, up to now while running and up to the latest event after
+    pub elapsed: Option<Duration>,
+    pub last_event: Option<DateTime<Local>>,
+    /// Running, but nothing arrived for [`STALL_WARNING`]
+    pub stalled: bool,
+}
+
+impl TabStatus {
+    pub fn new(
+        phases: &[WorkflowPhase],
+        running: bool,
+        start_time: Option<DateTime<Local>>,
+        last_event: Option<DateTime<Local>>,
+        now: DateTime<Local>,
+    ) -> Self {
+        let tasks = || phases.iter().flat_map(|p| p.tasks.iter());
+        let count = |status: TaskStatus| tasks().filter(|t| t.status == status).count();
+        let end = if running { Some(now) } else { last_event };
+        let since = |from: DateTime<Local>, to: DateTime<Local>| (to - from).to_std().ok();
+
+        Self {
+            running_tasks: count(TaskStatus::Running),
+            completed_tasks: count(TaskStatus::Completed),
+            failed_tasks: count(TaskStatus::Failed),
+            elapsed: start_time.zip(end).and_then(|(start, end)| since(start, end)),
+            last_event,
+            stalled: running
+                && last_event
+                    .or(start_time)
+                    .and_then(|at| since(at, now))
+                    .is_some_and(|quiet| quiet >= STALL_WARNING),
+        }
+    }
+
+    /// One-line text such as "2 running · 3 completed · 0 failed · 4m 12s since
+    /// launch · last event 14:03:22 (5s ago)"
+    pub fn text(&self, icons: &IconSet, now: DateTime<Local>) -> String {
+        let mut text = format!(
+            "{} running {dot} {} completed {dot} {} failed",
+            self.running_tasks,
+            self.completed_tasks,
+            self.failed_tasks,
+            dot = icons.middle_dot,
+        );
+        if let Some(elapsed) = self.elapsed {
+            text.push_str(&format!(
+                " {} {} since launch",
+                icons.middle_dot,
+                format_duration(elapsed)
+            ));
+        }
+        match self.last_event {
+            Some(at) => {
+                let at_text = at.format("%H:%M:%S");
+                text.push_str(&format!(" {} last event {}", icons.middle_dot, at_text));
+                if let Ok(ago) = (now - at).to_std() {
+                    text.push_str(&format!(" ({} ago)", format_duration(ago)));
+                }
+            }
+            None => text.push_str(&format!(" {} no events yet", icons.middle_dot)),
+        }
+        text
+    }
+}
+
+/// Render the progress summary line: red if anything failed, green once
+/// every phase completed, yellow otherwise
+pub fn render_progress_summary(f: &mut Frame, area: Rect, app: &App, summary: &ProgressSummary) {
+    let color = if summary.failed {
+        app.theme.failed
+    } else if summary.completed {
+        app.theme.completed
+    } else {
+        app.theme.running
+    };
+    let text = truncate_to_width(
+        &format!(" {}", summary.text(app.icons)),
+        area.width as usize,
+    );
+    let line = Line::from(Span::styled(
+        text,
+        Style::default().fg(color).add_modifier(Modifier::BOLD),
+    ));
+    f.render_widget(Paragraph::new(line), area);
+}
+
+/// Cells of the phase timeline, as (phase index, width), for the phases
+/// with a start time
+///
+/// Each phase gets one cell and the rest of `width` is shared in proportion
+/// to the phases' durations, so the widths add up to `width`. Phases never
+/// started, or replayed without timing, are left out.
+pub fn phase_timeline(phases: &[WorkflowPhase], width: usize) -> Vec<(usize, usize)> {
+    let timed: Vec<(usize, f64)> = phases
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, phase)| Some((idx, phase.elapsed()?.as_secs_f64())))
+        .take(width)
+        .collect();
+    let spare = width - timed.len();
+    let total: f64 = timed.iter().map(|(_, secs)| secs).sum();
+
+    let mut so_far = 0.0;
+    let mut given = 0;
+    timed
+        .iter()
+        .enumerate()
+        .map(|(n, &(idx, secs))| {
+            so_far += secs;
+            // Equal shares when every phase took no time at all
+            let share = if total > 0.0 {
+                so_far / total
+            } else {
+                (n + 1) as f64 / timed.len() as f64
+            };
+            let end = (share * spare as f64).round() as usize;
+            let extra = end - given;
+            given = end;
+            (idx, 1 + extra)
+        })
+        .collect()
+}
+
+/// Render the phase timeline: a bar per phase as wide as its share of the
+/// run, green once completed, red if failed and pulsing yellow while running
+pub fn render_phase_timeline(f: &mut Frame, area: Rect, app: &App, phases: &[WorkflowPhase]) {
+    let pulse = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .is_ok_and(|since| since.as_millis() / 500 % 2 == 0);
+
+    let spans: Vec<Span> = phase_timeline(phases, area.width as usize)
+        .into_iter()
+        .map(|(idx, width)| {
+            let phase = &phases[idx];
+            let color = match phase.status {
+                PhaseStatus::Completed => app.theme.completed,
+                PhaseStatus::Failed => app.theme.failed,
+                PhaseStatus::Running if pulse => app.theme.running,
+                _ => app.theme.dim,
+            };
+            // Names only go into bars with room for more than the ellipsis
+            let label = match phase.elapsed() {
+                Some(elapsed) if width > ELLIPSIS.len() + 1 => truncate_to_width(
+                    &format!(" {} {}", phase.name, format_duration(elapsed)),
+                    width,
+                ),
+                _ => String::new(),
+            };
+            let padding = " ".repeat(width.saturating_sub(label.width()));
+            Span::styled(
+                label + &padding,
+                Style::default().fg(app.theme.background).bg(color),
+            )
+        })
+        .collect();
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// Compact duration such as "45s", "3m 05s" or "1h 02m"
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match (secs / 3600, secs / 60 % 60, secs % 60) {
+        (0, 0, s) => format!("{}s", s),
+        (0, m, s) => format!("{}m {:02}s", m, s),
+        (h, m, _) => format!("{}h {:02}m", h, m),
+    }
+}
+
+/// Timing shown after a tree node: the elapsed (or total) time, and the
+/// estimated time left if known
+pub fn timing_label(
+    elapsed: Option<Duration>,
+    eta: Option<Duration>,
+    icons: &IconSet,
+) -> Option<String> {
+    let elapsed = format_duration(elapsed?);
+    Some(match eta {
+        Some(eta) => format!(
+            " {} {} ETA ~{}",
+            elapsed,
+            icons.middle_dot,
+            format_duration(eta)
+        ),
+        None => format!(" {}", elapsed),
+    })
+}
+
+/// Drop task and agent messages below `min_level` from a phase snapshot
+///
+/// Phases, tasks and agents themselves stay, so navigation still matches
+/// what is drawn.
+pub fn filter_phase_messages(phases: &mut [WorkflowPhase], min_level: LogLevel) {
+    let visible = |msg: &LogLine| msg.level >= min_level;
+    for task in phases.iter_mut().flat_map(|p| p.tasks.iter_mut()) {
+        task.messages.retain(visible);
+        for agent in &mut task.agents {
+            agent.messages.retain(visible);
+        }
+    }
+}
+
+/// Whether a raw output line passes the filter
+pub fn raw_line_visible(line: &LogLine, min_level: LogLevel) -> bool {
+    line.level >= min_level
+}
+
+/// Restyle the parts of each span that `re` matches
+///
+/// Matches are found per span, so one that crosses a style change is not
+/// highlighted.
+pub fn highlight_matches<'a>(lines: Vec<Line<'a>>, re: &Regex, style: Style) -> Vec<Line<'a>> {
+    lines
+        .into_iter()
+        .map(|line| {
+            let mut spans = Vec::new();
+            for span in line.spans {
+                if !re.is_match(&span.content) {
+                    spans.push(span);
+                    continue;
+                }
+                let mut last = 0;
+                for m in re.find_iter(&span.content).filter(|m| !m.is_empty()) {
+                    if m.start() > last {
+                        spans.push(Span::styled(
+                            span.content[last..m.start()].to_string(),
+                            span.style,
+                        ));
+                    }
+                    spans.push(Span::styled(m.as_str().to_string(), span.style.patch(style)));
+                    last = m.end();
+                }
+                if last < span.content.len() {
+                    spans.push(Span::styled(span.content[last..].to_string(), span.style));
+                }
+            }
+            Line::from(spans).style(line.style)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight_matches() {
+        use ratatui::style::Color;
+
+        let re = Regex::new("main").unwrap();
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let lines = vec![Line::from(vec![Span::raw("src/"), Span::styled("main.rs", bold)])];
+
+        let lines = highlight_matches(lines, &re, Style::default().bg(Color::Yellow));
+        let spans = &lines[0].spans;
+        assert_eq!(spans.len(), 3);
+        assert_eq!(spans[1].content, "main");
+        assert_eq!(spans[1].style.bg, Some(Color::Yellow));
+        assert!(spans[1].style.add_modifier.contains(Modifier::BOLD));
+        assert_eq!(spans[2].content, ".rs");
+        assert_eq!(spans[2].style, bold);
+    }
+
+    #[test]
+    fn test_progress_gauge() {
+        let icons = &super::super::icons::ASCII_ICONS;
+        assert_eq!(progress_gauge(0.0, icons), "[..........]   0%");
+        assert_eq!(progress_gauge(0.42, icons), "[####......]  42%");
+        assert_eq!(progress_gauge(1.0, icons), "[##########] 100%");
+        assert_eq!(progress_gauge(7.0, icons), "[##########] 100%");
+    }
+
+    #[test]
+    fn test_raw_line_visible() {
+        use workflow_manager_sdk::WorkflowLog;
+
+        let raw = |line: &str| WorkflowLog::RawOutput {
+            stream: "stderr".to_string(),
+            line: line.to_string(),
+        };
+        let line = |text: &str| LogLine::of(&raw(text), text);
+        assert!(raw_line_visible(&line("Compiling foo"), LogLevel::Debug));
+        assert!(!raw_line_visible(&line("Compiling foo"), LogLevel::Info));
+        assert!(raw_line_visible(&line("[WARN] disk almost full"), LogLevel::Warn));
+        assert!(!raw_line_visible(&line("warning: unused"), LogLevel::Error));
+
+        // The level is the event's, whatever the displayed text says
+        let decorated = LogLine::of(&raw("warning: unused"), "ERROR: warning: unused");
+        assert!(!raw_line_visible(&decorated, LogLevel::Error));
+    }
+
+    #[test]
+    fn test_horizontal_offset_stops_at_the_widest_line() {
+        let lines = vec![Line::from("short"), Line::from("a".repeat(30))];
+        assert_eq!(horizontal_offset(&lines, 8, 20), 8);
+        assert_eq!(horizontal_offset(&lines, 16, 20), 10);
+        assert_eq!(horizontal_offset(&lines, 16, 40), 0);
+    }
+
+    #[test]
+    fn test_truncate_short_text_unchanged() {
+        assert_eq!(truncate_to_width("hello", 10), "hello");
+        assert_eq!(truncate_to_width("hello", 5), "hello");
+    }
+
+    #[test]
+    fn test_truncate_adds_ellipsis() {
+        assert_eq!(truncate_to_width("hello world", 8), "hello...");
+    }
+
+    #[test]
+    fn test_truncate_multibyte_does_not_panic() {
+        let text = "día de café ☕☕☕ über";
+        let truncated = truncate_to_width(text, 10);
+        assert!(truncated.width() <= 10);
+        assert!(truncated.ends_with("..."));
+    }
+
+    #[test]
+    fn test_truncate_wide_graphemes() {
+        // Each CJK character is two columns wide
+        let truncated = truncate_to_width("漢字漢字漢字", 7);
+        assert_eq!(truncated, "漢字...");
+    }
+
+    #[test]
+    fn test_message_preview_fits_pane() {
+        let spans = vec![Span::raw("Phase 0: Analyze")];
+        let preview = message_preview(&spans, &"x".repeat(200), 40).unwrap();
+        assert!(spans[0].content.width() + preview.width() <= 40);
+        assert!(preview.starts_with(" - "));
+    }
+
+    #[test]
+    fn test_message_preview_too_narrow() {
+        let spans = vec![Span::raw("A very long phase name that fills the pane")];
+        assert!(message_preview(&spans, "message", 40).is_none());
+    }
+
+    #[test]
+    fn test_node_preview_prefers_the_error() {
+        let theme = &crate::ui::theme::DARK_THEME;
+        let spans = vec![Span::raw("Phase 0")];
+        let preview = node_preview(&spans, Some("timed out"), Some("last"), 60, theme).unwrap();
+        assert_eq!(preview.content, " - timed out");
+        assert_eq!(preview.style.fg, Some(theme.failed));
+
+        let preview = node_preview(&spans, None, Some("last"), 60, theme).unwrap();
+        assert_eq!(preview.style.fg, Some(theme.dim));
+        assert!(node_preview(&spans, None, None, 60, theme).is_none());
+
+        let error = LogLine::new(LogLevel::Error, "boom");
+        let info = LogLine::new(LogLevel::Info, "read 3 files");
+        assert_eq!(tree_message_style(&error, theme).fg, Some(theme.failed));
+        assert_eq!(tree_message_style(&info, theme).fg, Some(theme.secondary));
+    }
+
+    #[test]
+    fn test_progress_summary() {
+        use crate::app::{WorkflowAgent, WorkflowTask};
+
+        let task = |id: &str, status: TaskStatus, agents: usize| WorkflowTask {
+            id: id.to_string(),
+            status,
+            agents: (0..agents)
+                .map(|i| WorkflowAgent {
+                    id: format!("{}:{}", id, i),
+                    task_id: id.to_string(),
+                    name: format!("agent{}", i),
+                    status: AgentStatus::Completed,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let phase = |id: usize, status: PhaseStatus, tasks: Vec<WorkflowTask>| WorkflowPhase {
+            id,
+            name: format!("Phase {}", id),
+            status,
+            tasks,
+            ..Default::default()
+        };
+
+        let mut phases = vec![
+            phase(0, PhaseStatus::Completed, vec![task("a", TaskStatus::Completed, 2)]),
+            phase(
+                1,
+                PhaseStatus::Running,
+                vec![
+                    task("b", TaskStatus::Completed, 1),
+                    task("c", TaskStatus::Running, 0),
+                ],
+            ),
+            phase(2, PhaseStatus::NotStarted, Vec::new()),
+        ];
+        let summary = ProgressSummary::from_phases(&phases);
+        assert_eq!(
+            summary.text(&super::super::icons::ASCII_ICONS),
+            format!(
+                "Phase 2/3 {dot} 2/3 tasks complete {dot} 3 agents",
+                dot = super::super::icons::ASCII_ICONS.middle_dot
+            )
+        );
+        assert!(!summary.failed && !summary.completed);
+
+        phases[1].tasks[1].status = TaskStatus::Failed;
+        phases[1].status = PhaseStatus::Failed;
+        let summary = ProgressSummary::from_phases(&phases);
+        assert_eq!(summary.current_phase, 2);
+        assert!(summary.failed);
+
+        for phase in &mut phases {
+            phase.status = PhaseStatus::Completed;
+        }
+        phases[1].tasks[1].status = TaskStatus::Completed;
+        let summary = ProgressSummary::from_phases(&phases);
+        assert_eq!(summary.current_phase, 3);
+        assert!(summary.completed && !summary.failed);
+    }
+
+    #[test]
+    fn test_phase_timeline() {
+        let now = std::time::Instant::now();
+        let phase = |secs: Option<u64>| WorkflowPhase {
+            id: 0,
+            status: PhaseStatus::Completed,
+            started_at: secs.map(|secs| now - Duration::from_secs(secs)),
+            finished_at: secs.map(|_| now),
+            ..Default::default()
+        };
+
+        // 1 cell each, then 20 more shared 10s : 30s
+        let phases = vec![phase(Some(10)), phase(None), phase(Some(30))];
+        assert_eq!(phase_timeline(&phases, 22), vec![(0, 6), (2, 16)]);
+
+        let instant = vec![phase(Some(0)), phase(Some(0)), phase(Some(0))];
+        let widths: Vec<usize> = phase_timeline(&instant, 10).iter().map(|(_, w)| *w).collect();
+        assert_eq!(widths.iter().sum::<usize>(), 10);
+        assert_eq!(phase_timeline(&instant, 2).len(), 2);
+        assert!(phase_timeline(&[phase(None)], 10).is_empty());
+    }
+
+    #[test]
+    fn test_tab_status() {
+        use crate::app::WorkflowTask;
+
+        let task = |id: &str, status: TaskStatus| WorkflowTask {
+            id: id.to_string(),
+            status,
+            ..Default::default()
+        };
+        let phases = vec![WorkflowPhase {
+            id: 0,
+            name: "Build".to_string(),
+            status: PhaseStatus::Running,
+            tasks: vec![
+                task("a", TaskStatus::Completed),
+                task("b", TaskStatus::Running),
+                task("c", TaskStatus::Failed),
+            ],
+            ..Default::default()
+        }];
+        let start = Local::now();
+        let last_event = start + chrono::Duration::seconds(30);
+        let now = start + chrono::Duration::seconds(200);
+
+        let status = TabStatus::new(&phases, true, Some(start), Some(last_event), now);
+        assert_eq!((status.running_tasks, status.completed_tasks, status.failed_tasks), (1, 1, 1));
+        assert_eq!(status.elapsed, Some(Duration::from_secs(200)));
+        assert!(status.stalled);
+        let icons = &super::super::icons::ASCII_ICONS;
+        assert!(status.text(icons, now).ends_with("(2m 50s ago)"));
+
+        // A finished run stops the clock at its latest event
+        let status = TabStatus::new(&phases, false, Some(start), Some(last_event), now);
+        assert_eq!(status.elapsed, Some(Duration::from_secs(30)));
+        assert!(!status.stalled);
+        let status = TabStatus::new(&phases, true, Some(start), None, start);
+        assert!(status.text(icons, start).ends_with("no events yet"));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(7)), "7s");
+        assert_eq!(format_duration(Duration::from_secs(185)), "3m 05s");
+        assert_eq!(format_duration(Duration::from_secs(3720)), "1h 02m");
+
+        let icons = &super::super::icons::ASCII_ICONS;
+        assert_eq!(timing_label(None, None, icons), None);
+        assert_eq!(
+            timing_label(Some(Duration::from_secs(65)), None, icons).unwrap(),
+            " 1m 05s"
+        );
+        assert_eq!(
+            timing_label(Some(Duration::from_secs(65)), Some(Duration::from_secs(130)), icons)
+                .unwrap(),
+            format!(" 1m 05s {} ETA ~2m 10s", icons.middle_dot)
+        );
+    }
+}
@@ -0,0 +1,206 @@
+//! File viewer overlay with basic syntax coloring
+//!
+//! Markdown goes through the chat's renderer. YAML gets its keys, list
+//! markers, scalars and comments colored line by line. Anything else is
+//! shown as plain text.
+
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::components::centered_rect;
+use super::markdown::render_markdown;
+use super::theme::Theme;
+use crate::models::App;
+
+/// How the content of a file is colored, from its extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileSyntax {
+    Yaml,
+    Markdown,
+    Plain,
+}
+
+impl FileSyntax {
+    pub fn from_path(path: &str) -> Self {
+        let extension = std::path::Path::new(path)
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase());
+        match extension.as_deref() {
+            Some("yaml" | "yml") => FileSyntax::Yaml,
+            Some("md" | "markdown") => FileSyntax::Markdown,
+            _ => FileSyntax::Plain,
+        }
+    }
+}
+
+/// Key and rest of a `key: value` line, without the colon
+fn yaml_key(text: &str) -> Option<(&str, &str)> {
+    let colon = text.find(": ").or_else(|| text.strip_suffix(':').map(str::len))?;
+    let key = &text[..colon];
+    // Flow collections and quoted scalars are values, not keys
+    if key.is_empty() || key.starts_with(['"', '\'', '[', '{', '#']) {
+        return None;
+    }
+    Some((key, &text[colon + 1..]))
+}
+
+/// Style of a scalar value by its kind
+fn yaml_value_style(value: &str, theme: &Theme) -> Style {
+    let value = value.trim();
+    let color = if value.starts_with(['"', '\'']) {
+        theme.success
+    } else if value.starts_with(['|', '>', '&', '*', '!']) {
+        theme.dim
+    } else if value.parse::<f64>().is_ok() || matches!(value, "true" | "false" | "null" | "~") {
+        theme.info
+    } else {
+        theme.text
+    };
+    Style::default().fg(color)
+}
+
+/// One line of YAML, colored
+pub fn yaml_line(line: &str, theme: &Theme) -> Line<'static> {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('#') || trimmed == "---" || trimmed == "..." {
+        return Line::from(Span::styled(line.to_string(), Style::default().fg(theme.dim)));
+    }
+
+    let mut spans = vec![Span::raw(line[..line.len() - trimmed.len()].to_string())];
+    let mut rest = trimmed;
+    if let Some(item) = rest.strip_prefix("- ") {
+        spans.push(Span::styled("- ", Style::default().fg(theme.secondary)));
+        rest = item;
+    }
+    if let Some((key, value)) = yaml_key(rest) {
+        spans.push(Span::styled(key.to_string(), Style::default().fg(theme.header)));
+        spans.push(Span::styled(":", Style::default().fg(theme.dim)));
+        rest = value;
+    }
+    if !rest.is_empty() {
+        spans.push(Span::styled(rest.to_string(), yaml_value_style(rest, theme)));
+    }
+    Line::from(spans)
+}
+
+/// Overlay with the content of the file in `app.file_viewer`
+pub fn render_file_viewer(f: &mut Frame, area: Rect, app: &mut App) {
+    let popup_area = centered_rect(80, 80, area);
+    let (icons, theme) = (app.icons, app.theme);
+    let Some(viewer) = &mut app.file_viewer else {
+        return;
+    };
+
+    // Code blocks depend on the fences above, so markdown is rendered whole,
+    // once per width, and scrolled in rendered lines
+    let width = popup_area.width.saturating_sub(2) as usize;
+    let syntax = FileSyntax::from_path(&viewer.current_file().0);
+    if syntax == FileSyntax::Markdown && viewer.rendered.as_ref().map(|(w, _)| *w) != Some(width)
+    {
+        let lines = render_markdown(&viewer.lines.join("\n"), width, icons, &theme);
+        viewer.scroll = viewer.scroll.min(lines.len().saturating_sub(1));
+        viewer.rendered = Some((width, lines));
+    }
+    let viewer = &*viewer;
+    let (path, description) = viewer.current_file();
+
+    // Only the lines in view are colored
+    let total = viewer.line_count();
+    let height = popup_area.height.saturating_sub(2) as usize;
+    let start = viewer.scroll.min(total);
+    let end = (viewer.scroll + height).min(total);
+    let lines: Vec<Line> = match syntax {
+        _ if total == 0 => {
+            vec![Line::from(Span::styled("(empty file)", Style::default().fg(app.theme.dim)))]
+        }
+        FileSyntax::Markdown => match &viewer.rendered {
+            Some((_, rendered)) => rendered[start..end].to_vec(),
+            None => Vec::new(),
+        },
+        FileSyntax::Yaml => viewer.lines[start..end]
+            .iter()
+            .map(|line| yaml_line(line, &app.theme))
+            .collect(),
+        FileSyntax::Plain => viewer.lines[start..end]
+            .iter()
+            .map(|line| Line::from(line.as_str()))
+            .collect(),
+    };
+
+    let mut title = format!(" {} ", path);
+    if !description.is_empty() {
+        title.push_str(&format!("- {} ", description));
+    }
+    if viewer.truncated {
+        title.push_str("(first 1 MiB) ");
+    }
+    title.push_str(&format!(
+        "({}/{}) [{}/jk] Scroll ",
+        (start + 1).min(total),
+        total,
+        app.icons.up_down
+    ));
+    if viewer.files.len() > 1 {
+        title.push_str(&format!(
+            "[{}/hl] File {}/{} ",
+            app.icons.left_right,
+            viewer.current + 1,
+            viewer.files.len()
+        ));
+    }
+    title.push_str("[Esc/o] Close ");
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(app.theme.info))
+            .style(Style::default().fg(app.theme.text).bg(app.theme.background)),
+    );
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(paragraph, popup_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::theme::DARK_THEME;
+
+    #[test]
+    fn test_yaml_line_colors_keys_and_scalars() {
+        let theme = &DARK_THEME;
+        let line = yaml_line("  - name: \"plan\"", theme);
+        let parts: Vec<(&str, Option<ratatui::style::Color>)> = line
+            .spans
+            .iter()
+            .map(|span| (span.content.as_ref(), span.style.fg))
+            .collect();
+        assert_eq!(
+            parts,
+            vec![
+                ("  ", None),
+                ("- ", Some(theme.secondary)),
+                ("name", Some(theme.header)),
+                (":", Some(theme.dim)),
+                (" \"plan\"", Some(theme.success)),
+            ]
+        );
+
+        let line = yaml_line("retries: 3", theme);
+        assert_eq!(line.spans.last().unwrap().style.fg, Some(theme.info));
+        let line = yaml_line("# comment: not a key", theme);
+        assert_eq!(line.spans.len(), 1);
+        let line = yaml_line("- \"a: b\"", theme);
+        assert_eq!(line.spans.last().unwrap().content, "\"a: b\"");
+
+        assert_eq!(FileSyntax::from_path("out/tasks.YML"), FileSyntax::Yaml);
+        assert_eq!(FileSyntax::from_path("README.md"), FileSyntax::Markdown);
+        assert_eq!(FileSyntax::from_path("Makefile"), FileSyntax::Plain);
+    }
+}
@@ -2,12 +2,15 @@
 
 use ratatui::{
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
+use workflow_manager_sdk::{LogLevel, WorkflowStatus};
 
+use super::help::{footer_entries, Keys};
+use crate::app::Action;
 use crate::models::{App, View};
 
 pub fn render_header(f: &mut Frame, area: Rect, app: &App) {
@@ -18,13 +21,14 @@ pub fn render_header(f: &mut Frame, area: Rect, app: &App) {
         View::WorkflowRunning(_) => "Workflow Manager v0.2.0 - Running Workflow",
         View::Tabs => "Workflow Manager v0.2.0 - Running Workflows",
         View::Chat => "Workflow Manager v0.2.0 - AI Chat",
+        View::History => "Workflow Manager v0.2.0 - Execution History",
     };
 
     let header = Paragraph::new(Line::from(vec![
         Span::styled(
             title,
             Style::default()
-                .fg(Color::White)
+                .fg(app.theme.header)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw("      "),
@@ -36,101 +40,79 @@ pub fn render_header(f: &mut Frame, area: Rect, app: &App) {
 }
 
 pub fn render_footer(f: &mut Frame, area: Rect, app: &App) {
-    let footer_text = match app.current_view {
-        View::WorkflowList => Line::from(vec![
-            Span::styled("[↑↓]", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" Navigate  "),
-            Span::styled("[Enter/V]", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" View  "),
-            Span::styled("[Q]", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" Quit"),
-        ]),
-        View::WorkflowDetail(_) => Line::from(vec![
-            Span::styled("[E]", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" Edit  "),
-            Span::styled("[Esc/B]", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" Back  "),
-            Span::styled("[Q]", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" Quit"),
-        ]),
-        View::WorkflowEdit(_) => {
-            if app.is_editing {
-                Line::from(vec![
-                    Span::styled(
-                        "TYPE",
-                        Style::default()
-                            .fg(Color::White)
-                            .add_modifier(Modifier::BOLD),
-                    ),
-                    Span::raw(" to edit  "),
-                    Span::styled("[Enter]", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Save  "),
-                    Span::styled("[Esc]", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Cancel  "),
-                    Span::styled("[Backspace]", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Delete"),
-                ])
-            } else {
-                Line::from(vec![
-                    Span::styled("[↑↓]", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Navigate  "),
-                    Span::styled("[Enter]", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Edit  "),
-                    Span::styled("[L]", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Launch  "),
-                    Span::styled("[Esc/B]", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Back  "),
-                    Span::styled("[Q]", Style::default().add_modifier(Modifier::BOLD)),
-                    Span::raw(" Quit"),
-                ])
+    let icons = app.icons;
+    let mut log_filter = app.log_filter;
+    let mut paused = false;
+    let mut spans = Vec::new();
+    match app.current_view {
+        View::WorkflowEdit(_) if app.is_editing => {
+            spans.push(Span::styled(
+                "TYPE",
+                Style::default()
+                    .fg(app.theme.text)
+                    .add_modifier(Modifier::BOLD),
+            ));
+            spans.push(Span::raw(" to edit  "));
+        }
+        View::Tabs => {
+            let active_tab = app.open_tabs.get(app.active_tab_idx);
+            // Token usage of the active execution, once it has reported any
+            let usage = active_tab.and_then(|tab| *tab.usage.lock().unwrap());
+            log_filter = active_tab.map_or(LogLevel::Debug, |tab| tab.log_filter);
+            paused = active_tab.is_some_and(|tab| tab.status == WorkflowStatus::Paused);
+            if active_tab.is_some_and(|tab| tab.read_only) {
+                spans.push(Span::styled(
+                    "READ-ONLY",
+                    Style::default()
+                        .fg(app.theme.secondary)
+                        .add_modifier(Modifier::BOLD),
+                ));
+                spans.push(Span::raw("  │  "));
+            }
+            if paused {
+                spans.push(Span::styled(
+                    format!("{} PAUSED", icons.paused),
+                    Style::default()
+                        .fg(app.theme.workflow_status(&WorkflowStatus::Paused))
+                        .add_modifier(Modifier::BOLD),
+                ));
+                spans.push(Span::raw("  │  "));
+            }
+            if let Some(usage) = usage {
+                spans.push(Span::styled(
+                    usage.to_string(),
+                    Style::default().fg(app.theme.info),
+                ));
+                spans.push(Span::raw("  │  "));
             }
         }
-        View::WorkflowRunning(_) => Line::from(vec![
-            Span::styled("[↑↓/jk]", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" Navigate  "),
-            Span::styled("[Enter]", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" Expand/Collapse  "),
-            Span::styled("[Space]", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" Toggle All  "),
-            Span::styled("[Esc/B]", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" Back  "),
-            Span::styled("[Q]", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" Quit"),
-        ]),
-        View::Tabs => Line::from(vec![
-            Span::styled("[↑↓/jk]", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" Navigate  "),
-            Span::styled("[←→/hl]", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" Scroll Agent  "),
-            Span::styled("[Enter]", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" Expand  "),
-            Span::styled("[Space]", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" Toggle All  "),
-            Span::styled("[Tab]", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" Switch  "),
-            Span::styled("[E]", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" Edit  "),
-            Span::styled("[R]", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" Rerun  "),
-            Span::styled("[C]", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" Close  "),
-            Span::styled("[A]", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" AI Chat  "),
-            Span::styled("[Q]", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" Quit"),
-        ]),
-        View::Chat => Line::from(vec![
-            Span::styled("[↑↓]", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" Scroll  "),
-            Span::styled("[Enter]", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" Send  "),
-            Span::styled("[Esc]", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" Back  "),
-            Span::styled("[Ctrl+Q]", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" Quit"),
-        ]),
-    };
+        _ => {}
+    }
+
+    // The keys come from the help table and the active keymap, so the
+    // footer and `?` agree
+    let mut first = true;
+    for entry in footer_entries(&app.current_view, app.is_editing) {
+        // Only the first of the alternative keys fits the footer
+        let Some(keys) = entry.alternatives(&app.keymap).into_iter().next() else {
+            continue;
+        };
+        let label = match entry.keys {
+            Keys::Bound(Action::CycleLogFilter) => format!("Level: {}", log_filter.label()),
+            Keys::Bound(Action::TogglePause) if paused => "Resume".to_string(),
+            _ => entry.footer.unwrap_or_default().to_string(),
+        };
+        if !first {
+            spans.push(Span::raw("  "));
+        }
+        first = false;
+        spans.push(Span::styled(
+            format!("[{}]", keys),
+            Style::default().add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::raw(format!(" {}", label)));
+    }
 
-    let footer = Paragraph::new(footer_text).block(Block::default().borders(Borders::ALL));
+    let footer = Paragraph::new(Line::from(spans)).block(Block::default().borders(Borders::ALL));
     f.render_widget(footer, area);
 }
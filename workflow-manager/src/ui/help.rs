@@ -0,0 +1,450 @@
+//! Keybinding help overlay, toggled with `?`
+
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use super::components::centered_rect;
+use crate::app::{Action, KeyMap};
+use crate::models::{App, View};
+
+/// The keys of a help entry
+pub enum Keys {
+    /// The keys bound to the action in the active keymap
+    Bound(Action),
+    /// The keys of two opposite actions, shown side by side as `Up/Down`
+    Pair(Action, Action),
+    /// Keys handled outside the keymap, such as text input and chat
+    Fixed(&'static str),
+}
+
+/// A key (or key combination) and what it does
+pub struct HelpEntry {
+    pub keys: Keys,
+    pub description: &'static str,
+    /// Short label of the key in the footer, if it is listed there
+    pub footer: Option<&'static str>,
+}
+
+const fn entry(keys: Keys, description: &'static str) -> HelpEntry {
+    HelpEntry {
+        keys,
+        description,
+        footer: None,
+    }
+}
+
+const fn key(action: Action, description: &'static str) -> HelpEntry {
+    entry(Keys::Bound(action), description)
+}
+
+const fn pair(first: Action, second: Action, description: &'static str) -> HelpEntry {
+    entry(Keys::Pair(first, second), description)
+}
+
+const fn fixed(keys: &'static str, description: &'static str) -> HelpEntry {
+    entry(Keys::Fixed(keys), description)
+}
+
+impl HelpEntry {
+    const fn footer(self, label: &'static str) -> Self {
+        Self {
+            footer: Some(label),
+            ..self
+        }
+    }
+
+    /// The alternative keys of the entry, e.g. `["Up/Down", "k/j"]`
+    ///
+    /// Empty when the keymap left the action without keys.
+    pub fn alternatives(&self, keymap: &KeyMap) -> Vec<String> {
+        match self.keys {
+            Keys::Bound(action) => {
+                let combos = keymap.keys(action);
+                combos
+                    .iter()
+                    .map(|combo| combo.to_string())
+                    // `Q` next to `q` adds nothing
+                    .filter(|name| {
+                        let lower = name.to_lowercase();
+                        lower == *name || !combos.iter().any(|combo| combo.to_string() == lower)
+                    })
+                    .collect()
+            }
+            Keys::Pair(first, second) => keymap
+                .keys(first)
+                .iter()
+                .zip(keymap.keys(second))
+                .map(|(first, second)| format!("{}/{}", first, second))
+                .collect(),
+            Keys::Fixed(keys) => vec![keys.to_string()],
+        }
+    }
+}
+
+const WORKFLOW_LIST_KEYS: &[HelpEntry] = &[
+    pair(Action::NavigateUp, Action::NavigateDown, "Navigate").footer("Navigate"),
+    key(Action::Select, "View workflow details").footer("View"),
+    key(Action::ViewWorkflow, "View workflow details"),
+    key(Action::OpenHistory, "Browse past executions"),
+    key(
+        Action::ReloadWorkflows,
+        "Reload workflows (new and changed definitions)",
+    ),
+    key(Action::Back, "Back to tabs (when opening a new tab)"),
+    key(Action::Help, "Show this help").footer("Help"),
+    key(Action::Quit, "Quit").footer("Quit"),
+];
+
+const WORKFLOW_DETAIL_KEYS: &[HelpEntry] = &[
+    key(Action::Edit, "Edit parameters").footer("Edit"),
+    key(Action::Launch, "Launch in new tab"),
+    key(Action::PreviewCommand, "Preview the command to run"),
+    key(Action::Back, "Back to the workflow list").footer("Back"),
+    key(Action::Help, "Show this help").footer("Help"),
+    key(Action::Quit, "Quit").footer("Quit"),
+];
+
+const WORKFLOW_EDIT_KEYS: &[HelpEntry] = &[
+    pair(Action::NavigateUp, Action::NavigateDown, "Select field").footer("Navigate"),
+    key(Action::Select, "Edit field / save value").footer("Edit"),
+    fixed("Tab", "Complete path or show history (while editing)"),
+    fixed("/", "Open file browser (while editing an empty field)"),
+    fixed("Ctrl+O", "View the highlighted file (in the file browser)"),
+    key(Action::Toggle, "Toggle boolean field"),
+    key(Action::ClearField, "Clear field value"),
+    key(
+        Action::ClearHistory,
+        "Forget the remembered values of the field",
+    ),
+    key(Action::SavePreset, "Save the values as a named preset").footer("Save Preset"),
+    key(Action::LoadPreset, "Load a preset").footer("Load Preset"),
+    key(
+        Action::SaveParamsFile,
+        "Save the values to a JSON or YAML file",
+    ),
+    key(
+        Action::LoadParamsFile,
+        "Load the values from a JSON or YAML file",
+    ),
+    key(
+        Action::ToggleFieldGroup,
+        "Collapse / expand the section of the field",
+    ),
+    key(Action::Launch, "Launch in new tab").footer("Launch"),
+    key(Action::PreviewCommand, "Preview the command to run"),
+    key(Action::Back, "Cancel editing / back to the workflow list").footer("Back"),
+    key(Action::Help, "Show this help").footer("Help"),
+    key(Action::Quit, "Quit").footer("Quit"),
+];
+
+const WORKFLOW_RUNNING_KEYS: &[HelpEntry] = &[
+    pair(
+        Action::NavigateUp,
+        Action::NavigateDown,
+        "Navigate or scroll the focused pane",
+    )
+    .footer("Navigate"),
+    key(Action::Select, "Expand/collapse selected item").footer("Expand/Collapse"),
+    key(Action::Toggle, "Toggle expand all").footer("Toggle All"),
+    key(
+        Action::ExpandToDepth,
+        "Then 0 - 3: collapse all / expand to phases, tasks, agents",
+    ),
+    key(Action::ToggleFollow, "Follow new activity on/off"),
+    key(Action::Search, "Search the tree"),
+    pair(
+        Action::NextMatch,
+        Action::PreviousMatch,
+        "Next / previous match",
+    ),
+    key(
+        Action::JumpToFailed,
+        "Jump to the next failed phase, task or agent",
+    ),
+    pair(
+        Action::FocusStructuredLogs,
+        Action::FocusRawOutput,
+        "Focus structured logs / raw output pane",
+    ),
+    key(Action::ToggleAgentDetails, "Toggle agent details"),
+    pair(
+        Action::HalfPageDown,
+        Action::HalfPageUp,
+        "Page the tree selection or raw output down / up",
+    ),
+    pair(Action::FirstRow, Action::LastRow, "First / last row of the tree"),
+    pair(
+        Action::ScrollLeft,
+        Action::ScrollRight,
+        "Scroll the phase tree left / right",
+    ),
+    key(Action::KillTab, "Kill the workflow").footer("Kill"),
+    key(Action::CancelTask, "Cancel the selected task"),
+    key(
+        Action::CycleLogFilter,
+        "Cycle minimum log level (Debug/Info/Warn/Error)",
+    )
+    .footer("Level"),
+    key(Action::CopyOutput, "Copy output of the selected item"),
+    key(Action::ExportLogs, "Export the structured log to a file").footer("Export"),
+    key(
+        Action::OpenOutputFile,
+        "View the selected output file",
+    ),
+    key(Action::Back, "Back to the workflow list").footer("Back"),
+    key(Action::Help, "Show this help").footer("Help"),
+    key(Action::Quit, "Quit").footer("Quit"),
+];
+
+const TABS_KEYS: &[HelpEntry] = &[
+    pair(
+        Action::NavigateUp,
+        Action::NavigateDown,
+        "Navigate or scroll the focused pane",
+    )
+    .footer("Navigate"),
+    key(Action::Select, "Expand/collapse selected item").footer("Expand"),
+    key(Action::Toggle, "Toggle expand all").footer("Toggle All"),
+    key(
+        Action::ExpandToDepth,
+        "Then 0 - 3: collapse all / expand to phases, tasks, agents",
+    ),
+    key(Action::ToggleFollow, "Follow new activity on/off"),
+    key(Action::Search, "Search the tree"),
+    pair(
+        Action::NextMatch,
+        Action::PreviousMatch,
+        "Next / previous match",
+    ),
+    key(
+        Action::JumpToFailed,
+        "Jump to the next failed phase, task or agent",
+    ),
+    pair(
+        Action::FocusStructuredLogs,
+        Action::FocusRawOutput,
+        "Focus structured logs / raw output pane",
+    ),
+    pair(
+        Action::ScrollMessagesUp,
+        Action::ScrollMessagesDown,
+        "Scroll agent messages",
+    )
+    .footer("Scroll Agent"),
+    pair(
+        Action::HalfPageDown,
+        Action::HalfPageUp,
+        "Page the tree selection or raw output down / up",
+    ),
+    pair(Action::FirstRow, Action::LastRow, "First / last row of the tree"),
+    pair(
+        Action::ScrollLeft,
+        Action::ScrollRight,
+        "Scroll the phase tree left / right",
+    ),
+    pair(Action::NextTab, Action::PreviousTab, "Switch tabs").footer("Switch"),
+    pair(
+        Action::MoveTabLeft,
+        Action::MoveTabRight,
+        "Move the tab left / right",
+    ),
+    key(Action::NewTab, "New tab"),
+    key(Action::CloseTab, "Close tab").footer("Close"),
+    key(Action::KillTab, "Kill running workflow"),
+    key(Action::TogglePause, "Pause / resume running workflow").footer("Pause"),
+    key(Action::CancelTask, "Cancel the selected task"),
+    key(Action::Rerun, "Rerun workflow").footer("Rerun"),
+    key(Action::Edit, "Edit and rerun").footer("Edit"),
+    key(Action::OpenChat, "Open chat interface").footer("AI Chat"),
+    key(
+        Action::RawJson,
+        "Show raw JSON events of the selected agent",
+    )
+    .footer("Raw JSON"),
+    key(Action::OpenHistory, "Browse past executions"),
+    key(
+        Action::CycleLogFilter,
+        "Cycle minimum log level (Debug/Info/Warn/Error)",
+    )
+    .footer("Level"),
+    key(Action::CopyOutput, "Copy output of the selected item"),
+    key(Action::ExportLogs, "Export the structured log to a file").footer("Export"),
+    key(
+        Action::OpenOutputFile,
+        "View the selected output file",
+    ),
+    key(
+        Action::OpenArtifact,
+        "View the selected artifact of the run's result",
+    ),
+    key(Action::Help, "Show this help").footer("Help"),
+    key(Action::Quit, "Quit").footer("Quit"),
+];
+
+const CHAT_KEYS: &[HelpEntry] = &[
+    fixed("Enter", "Send message").footer("Send"),
+    fixed("Ctrl+Up / Ctrl+Down", "Browse message history"),
+    fixed("Ctrl+N", "Start a new conversation").footer("New Chat"),
+    fixed("Ctrl+O", "Choose the model and read-only tools"),
+    fixed("Shift+Up / Shift+Down", "Select a tool call"),
+    fixed(
+        "Enter (empty input)",
+        "Show / hide the input and result of the tool call",
+    ),
+    fixed(
+        "/help + Enter",
+        "List slash commands (/clear, /runs, /cancel <n>)",
+    ),
+    fixed("Left/Right, Home/End", "Move cursor"),
+    fixed("Tab", "Switch panes"),
+    fixed("Up/Down", "Scroll active pane").footer("Scroll"),
+    fixed("?", "Show this help (when the input is empty)").footer("Help"),
+    fixed("Esc or Ctrl+C", "Stop the response being streamed"),
+    fixed("Esc", "Back to tabs (when no response is streamed)").footer("Back"),
+    fixed("Ctrl+Q", "Quit").footer("Quit"),
+];
+
+/// Keys while a field value is typed in the edit view
+const FIELD_INPUT_KEYS: &[HelpEntry] = &[
+    fixed("Enter", "Save the value").footer("Save"),
+    fixed("Esc", "Cancel editing").footer("Cancel"),
+    fixed("Backspace", "Delete a character").footer("Delete"),
+];
+
+const HISTORY_KEYS: &[HelpEntry] = &[
+    pair(Action::NavigateUp, Action::NavigateDown, "Select execution").footer("Navigate"),
+    pair(
+        Action::NextPage,
+        Action::PreviousPage,
+        "Next / previous page",
+    )
+    .footer("Page"),
+    key(Action::Select, "Open the execution in a tab").footer("Open"),
+    key(Action::Edit, "Edit its parameters and relaunch").footer("Edit & Rerun"),
+    key(Action::CopyOutput, "Copy the command that reproduces it"),
+    key(Action::Back, "Back").footer("Back"),
+    key(Action::Help, "Show this help").footer("Help"),
+    key(Action::Quit, "Quit").footer("Quit"),
+];
+
+/// Keybindings available in the given view
+pub fn help_entries(view: &View) -> &'static [HelpEntry] {
+    match view {
+        View::WorkflowList => WORKFLOW_LIST_KEYS,
+        View::WorkflowDetail(_) => WORKFLOW_DETAIL_KEYS,
+        View::WorkflowEdit(_) => WORKFLOW_EDIT_KEYS,
+        View::WorkflowRunning(_) => WORKFLOW_RUNNING_KEYS,
+        View::Tabs => TABS_KEYS,
+        View::Chat => CHAT_KEYS,
+        View::History => HISTORY_KEYS,
+    }
+}
+
+/// The keys of the view listed in its footer, in help order
+pub fn footer_entries(view: &View, is_editing: bool) -> impl Iterator<Item = &'static HelpEntry> {
+    let entries = match view {
+        View::WorkflowEdit(_) if is_editing => FIELD_INPUT_KEYS,
+        _ => help_entries(view),
+    };
+    entries.iter().filter(|entry| entry.footer.is_some())
+}
+
+fn view_title(view: &View) -> &'static str {
+    match view {
+        View::WorkflowList => "Workflow List",
+        View::WorkflowDetail(_) => "Workflow Details",
+        View::WorkflowEdit(_) => "Edit Workflow",
+        View::WorkflowRunning(_) => "Running Workflow",
+        View::Tabs => "Tabs",
+        View::Chat => "Chat",
+        View::History => "Execution History",
+    }
+}
+
+pub fn render_help(f: &mut Frame, area: Rect, app: &App) {
+    let popup_area = centered_rect(60, 70, area);
+
+    // Keys as bound in the active keymap; unbound actions are left out
+    let entries: Vec<(String, &HelpEntry)> = help_entries(&app.current_view)
+        .iter()
+        .map(|entry| (entry.alternatives(&app.keymap).join(" or "), entry))
+        .filter(|(keys, _)| !keys.is_empty())
+        .collect();
+    let key_width = entries
+        .iter()
+        .map(|(keys, _)| keys.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut text = vec![Line::from("")];
+    for (keys, entry) in entries {
+        text.push(Line::from(vec![
+            Span::styled(
+                format!("  {:<width$}  ", keys, width = key_width),
+                Style::default()
+                    .fg(app.theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(entry.description, Style::default().fg(app.theme.text)),
+        ]));
+    }
+    text.push(Line::from(""));
+    text.push(Line::from(Span::styled(
+        "  Press any key to close",
+        Style::default().fg(app.theme.dim),
+    )));
+
+    let paragraph = Paragraph::new(text).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.theme.info))
+            .title(format!(" Keys: {} ", view_title(&app.current_view)))
+            .style(Style::default().bg(app.theme.background)),
+    );
+
+    f.render_widget(ratatui::widgets::Clear, popup_area);
+    f.render_widget(paragraph, popup_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_view_has_help_entries() {
+        let views = [
+            View::WorkflowList,
+            View::WorkflowDetail(0),
+            View::WorkflowEdit(0),
+            View::WorkflowRunning(0),
+            View::Tabs,
+            View::Chat,
+            View::History,
+        ];
+        let keymap = KeyMap::default();
+        for view in &views {
+            let entries = help_entries(view);
+            assert!(!entries.is_empty(), "no help for {:?}", view);
+            assert!(entries.iter().all(|entry| {
+                !entry.alternatives(&keymap).is_empty() && !entry.description.is_empty()
+            }));
+            assert!(footer_entries(view, false).any(|entry| entry.description == "Quit"));
+        }
+    }
+
+    #[test]
+    fn test_help_shows_the_active_keymap() {
+        let keymap = KeyMap::from_toml("Quit = \"ctrl+q\"").unwrap();
+        let quit = key(Action::Quit, "Quit");
+        assert_eq!(quit.alternatives(&keymap), ["Ctrl+Q"]);
+        assert_eq!(quit.alternatives(&KeyMap::default()), ["q"]);
+
+        let navigate = pair(Action::NavigateUp, Action::NavigateDown, "Navigate");
+        assert_eq!(navigate.alternatives(&keymap), ["Up/Down", "k/j"]);
+    }
+}
@@ -0,0 +1,94 @@
+//! Execution history view rendering
+
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+use workflow_manager_sdk::ExecutionSummary;
+
+use crate::models::App;
+
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+pub fn render_history(f: &mut Frame, area: Rect, app: &App) {
+    let title = format!(
+        " Execution History - Page {}{} ",
+        app.execution_history_page + 1,
+        if app.execution_history_has_more {
+            " (more)"
+        } else {
+            ""
+        }
+    );
+    let block = Block::default().borders(Borders::ALL).title(title);
+
+    if app.execution_history.is_empty() {
+        let empty = Paragraph::new(Line::from(Span::styled(
+            "No past executions",
+            Style::default().fg(app.theme.dim),
+        )))
+        .block(block);
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let items: Vec<ListItem> = app
+        .execution_history
+        .iter()
+        .enumerate()
+        .map(|(i, execution)| history_item(app, execution, i == app.execution_history_selected))
+        .collect();
+
+    let mut state = ListState::default();
+    state.select(Some(app.execution_history_selected));
+    f.render_stateful_widget(List::new(items).block(block), area, &mut state);
+}
+
+fn history_item<'a>(app: &App, execution: &'a ExecutionSummary, is_selected: bool) -> ListItem<'a> {
+    let status_color = app.theme.workflow_status(&execution.status);
+    let bullet = if is_selected { app.icons.selected } else { " " };
+    let name_style = if is_selected {
+        Style::default()
+            .fg(app.theme.selected)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(app.theme.secondary)
+    };
+
+    let end = execution
+        .end_time
+        .map(|t| t.format(TIME_FORMAT).to_string())
+        .unwrap_or_else(|| "-".to_string());
+    let exit_code = execution
+        .exit_code
+        .map(|c| c.to_string())
+        .unwrap_or_else(|| "-".to_string());
+
+    ListItem::new(vec![
+        Line::from(vec![
+            Span::raw(format!(" {} ", bullet)),
+            Span::styled(
+                format!("{} ", app.icons.workflow_status(&execution.status)),
+                Style::default().fg(status_color),
+            ),
+            Span::styled(&execution.workflow_name, name_style),
+            Span::styled(
+                format!("  {:?}", execution.status),
+                Style::default().fg(status_color),
+            ),
+        ]),
+        Line::from(Span::styled(
+            format!(
+                "     {} -> {}  exit: {}  {}",
+                execution.start_time.format(TIME_FORMAT),
+                end,
+                exit_code,
+                execution.id
+            ),
+            Style::default().fg(app.theme.dim),
+        )),
+    ])
+}
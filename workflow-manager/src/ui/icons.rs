@@ -0,0 +1,269 @@
+//! Central icon set for the TUI
+//!
+//! Every glyph the views draw comes from an [`IconSet`], so limited terminals
+//! and screen readers can swap the unicode icons for plain ASCII. The mode is
+//! set with `icons = "ascii"` in `~/.config/workflow-manager/config.toml`
+//! (default: `unicode`), and `WORKFLOW_MANAGER_ICONS=ascii` overrides it for
+//! a single run.
+
+use serde::Deserialize;
+use workflow_manager_sdk::WorkflowStatus;
+
+use crate::models::{AgentStatus, PhaseStatus, TaskStatus};
+
+/// Environment variable overriding the configured icon mode (`unicode` or `ascii`)
+pub const ICON_MODE_ENV: &str = "WORKFLOW_MANAGER_ICONS";
+
+/// Icon mode picked in the config file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IconMode {
+    #[default]
+    Unicode,
+    Ascii,
+}
+
+/// Glyphs used by the views
+#[derive(Debug, PartialEq, Eq)]
+pub struct IconSet {
+    // Status markers (phases, tasks, agents)
+    pub not_started: &'static str,
+    pub running: &'static str,
+    pub completed: &'static str,
+    pub failed: &'static str,
+    /// Running marker in the tab bar
+    pub active: &'static str,
+    /// Paused workflow marker
+    pub paused: &'static str,
+
+    // Tree and list markers
+    pub expanded: &'static str,
+    pub collapsed: &'static str,
+    pub selected: &'static str,
+    pub bullet: &'static str,
+    /// Multi-select option markers
+    pub checked: &'static str,
+    pub unchecked: &'static str,
+
+    // Objects
+    pub file: &'static str,
+    pub folder: &'static str,
+    pub tool: &'static str,
+
+    // Notification levels and output banners
+    pub error: &'static str,
+    pub warning: &'static str,
+    pub info: &'static str,
+    pub success: &'static str,
+
+    // Key hints
+    pub up_down: &'static str,
+    pub left_right: &'static str,
+    pub arrow_right: &'static str,
+
+    // Drawing
+    pub separator: &'static str,
+    pub heavy_separator: &'static str,
+    pub ellipsis: &'static str,
+    pub cursor: &'static str,
+    pub middle_dot: &'static str,
+    /// Filled and empty cells of progress gauges
+    pub gauge_filled: &'static str,
+    pub gauge_empty: &'static str,
+    /// Animation frames for loading spinners
+    pub spinner: &'static [char],
+}
+
+pub static UNICODE_ICONS: IconSet = IconSet {
+    not_started: "○",
+    running: "▶",
+    completed: "✓",
+    failed: "✗",
+    active: "●",
+    paused: "⏸",
+    expanded: "▼",
+    collapsed: "▶",
+    selected: "▶",
+    bullet: "•",
+    checked: "☑",
+    unchecked: "☐",
+    file: "📄",
+    folder: "📁",
+    tool: "🔧",
+    error: "✗",
+    warning: "⚠",
+    info: "ℹ",
+    success: "✓",
+    up_down: "↑↓",
+    left_right: "←→",
+    arrow_right: "→",
+    separator: "━",
+    heavy_separator: "═",
+    ellipsis: "…",
+    cursor: "█",
+    middle_dot: "·",
+    gauge_filled: "█",
+    gauge_empty: "░",
+    spinner: &['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧'],
+};
+
+pub static ASCII_ICONS: IconSet = IconSet {
+    not_started: "[ ]",
+    running: "[>]",
+    completed: "[ok]",
+    failed: "[x]",
+    active: "[*]",
+    paused: "[||]",
+    expanded: "v",
+    collapsed: ">",
+    selected: ">",
+    bullet: "*",
+    checked: "[x]",
+    unchecked: "[ ]",
+    file: "[f]",
+    folder: "[d]",
+    tool: "[t]",
+    error: "[x]",
+    warning: "[!]",
+    info: "[i]",
+    success: "[ok]",
+    up_down: "Up/Dn",
+    left_right: "Lt/Rt",
+    arrow_right: "->",
+    separator: "-",
+    heavy_separator: "=",
+    ellipsis: "...",
+    cursor: "_",
+    middle_dot: "-",
+    gauge_filled: "#",
+    gauge_empty: ".",
+    spinner: &['|', '/', '-', '\\'],
+};
+
+impl IconSet {
+    /// Icon set selected by `WORKFLOW_MANAGER_ICONS`, or by the configured
+    /// mode when it is not set
+    pub fn from_env_or(configured: IconMode) -> &'static IconSet {
+        match std::env::var(ICON_MODE_ENV) {
+            Ok(mode) => Self::from_mode(&mode),
+            Err(_) => Self::for_mode(configured),
+        }
+    }
+
+    pub fn for_mode(mode: IconMode) -> &'static IconSet {
+        match mode {
+            IconMode::Unicode => &UNICODE_ICONS,
+            IconMode::Ascii => &ASCII_ICONS,
+        }
+    }
+
+    /// Icon set for a mode name; anything but `ascii` uses unicode
+    pub fn from_mode(mode: &str) -> &'static IconSet {
+        if mode.trim().eq_ignore_ascii_case("ascii") {
+            &ASCII_ICONS
+        } else {
+            &UNICODE_ICONS
+        }
+    }
+
+    pub fn phase_status(&self, status: &PhaseStatus) -> &'static str {
+        match status {
+            PhaseStatus::NotStarted => self.not_started,
+            PhaseStatus::Running => self.running,
+            PhaseStatus::Completed => self.completed,
+            PhaseStatus::Failed => self.failed,
+        }
+    }
+
+    pub fn task_status(&self, status: &TaskStatus) -> &'static str {
+        match status {
+            TaskStatus::NotStarted => self.not_started,
+            TaskStatus::Running => self.running,
+            TaskStatus::Completed => self.completed,
+            TaskStatus::Failed => self.failed,
+        }
+    }
+
+    pub fn agent_status(&self, status: &AgentStatus) -> &'static str {
+        match status {
+            AgentStatus::NotStarted => self.not_started,
+            AgentStatus::Running => self.running,
+            AgentStatus::Completed => self.completed,
+            AgentStatus::Failed => self.failed,
+        }
+    }
+
+    pub fn workflow_status(&self, status: &WorkflowStatus) -> &'static str {
+        match status {
+            WorkflowStatus::NotStarted => self.not_started,
+            WorkflowStatus::Running => self.active,
+            WorkflowStatus::Paused => self.paused,
+            WorkflowStatus::Completed => self.completed,
+            WorkflowStatus::Failed => self.failed,
+            _ => self.not_started,
+        }
+    }
+
+    /// Expand/collapse marker for tree nodes
+    pub fn expand(&self, is_expanded: bool) -> &'static str {
+        if is_expanded {
+            self.expanded
+        } else {
+            self.collapsed
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_mode() {
+        assert_eq!(IconSet::from_mode("ascii"), &ASCII_ICONS);
+        assert_eq!(IconSet::from_mode(" ASCII "), &ASCII_ICONS);
+        assert_eq!(IconSet::from_mode("unicode"), &UNICODE_ICONS);
+        assert_eq!(IconSet::from_mode(""), &UNICODE_ICONS);
+        assert_eq!(IconSet::for_mode(IconMode::Ascii), &ASCII_ICONS);
+        assert_eq!(IconSet::for_mode(IconMode::default()), &UNICODE_ICONS);
+    }
+
+    #[test]
+    fn test_ascii_icons_are_ascii() {
+        let icons = &ASCII_ICONS;
+        for glyph in [
+            icons.not_started,
+            icons.running,
+            icons.completed,
+            icons.failed,
+            icons.active,
+            icons.paused,
+            icons.expanded,
+            icons.collapsed,
+            icons.selected,
+            icons.bullet,
+            icons.checked,
+            icons.unchecked,
+            icons.file,
+            icons.folder,
+            icons.tool,
+            icons.error,
+            icons.warning,
+            icons.info,
+            icons.success,
+            icons.up_down,
+            icons.left_right,
+            icons.arrow_right,
+            icons.separator,
+            icons.heavy_separator,
+            icons.ellipsis,
+            icons.cursor,
+            icons.middle_dot,
+            icons.gauge_filled,
+            icons.gauge_empty,
+        ] {
+            assert!(glyph.is_ascii(), "{:?} is not ASCII", glyph);
+        }
+        assert!(icons.spinner.iter().all(char::is_ascii));
+    }
+}
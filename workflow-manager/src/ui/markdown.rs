@@ -0,0 +1,249 @@
+//! Lightweight markdown rendering for assistant chat messages
+//!
+//! Covers what Claude commonly writes: headings, bullet and numbered lists,
+//! bold, italics, inline code and fenced code blocks. Every source line
+//! becomes exactly one rendered line, so the chat's line count for
+//! auto-scroll stays in step with the content.
+
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+use unicode_width::UnicodeWidthStr;
+
+use super::components::truncate_to_width;
+use super::icons::IconSet;
+use super::theme::Theme;
+
+/// Render `content` into styled lines for a pane `width` columns wide
+///
+/// Code block lines are cut to `width` and padded to fill it, so they keep
+/// their alignment and the surrounding paragraph never word-wraps them.
+pub fn render_markdown(
+    content: &str,
+    width: usize,
+    icons: &IconSet,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+
+        if let Some(fence) = trimmed.strip_prefix("```") {
+            in_code_block = !in_code_block;
+            // The opening fence shows the language, the closing one is blank
+            let label = if in_code_block { fence.trim() } else { "" };
+            lines.push(code_line(
+                label,
+                width,
+                Style::default().fg(theme.dim).bg(theme.surface),
+            ));
+            continue;
+        }
+
+        if in_code_block {
+            lines.push(code_line(
+                line,
+                width,
+                Style::default().fg(theme.text).bg(theme.surface),
+            ));
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            lines.push(Line::from(""));
+            continue;
+        }
+
+        let indent = "  ".repeat((line.len() - trimmed.len()) / 2);
+
+        if let Some(heading) = heading_text(trimmed) {
+            lines.push(Line::from(Span::styled(
+                format!("{}{}", indent, heading),
+                Style::default().fg(theme.header).add_modifier(Modifier::BOLD),
+            )));
+            continue;
+        }
+
+        let (prefix, text) = match list_item(trimmed, icons) {
+            Some((marker, item)) => (format!("{}{} ", indent, marker), item),
+            None => (indent, trimmed),
+        };
+
+        let mut spans = vec![Span::raw(prefix)];
+        spans.extend(inline_spans(text, theme));
+        lines.push(Line::from(spans));
+    }
+
+    lines
+}
+
+/// Text of an ATX heading (`# Title` up to `###### Title`)
+fn heading_text(line: &str) -> Option<&str> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    line[level..].strip_prefix(' ').map(str::trim)
+}
+
+/// Marker to show and text of a bullet (`-`, `*`, `+`) or numbered list item
+fn list_item<'a>(line: &'a str, icons: &IconSet) -> Option<(String, &'a str)> {
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(item) = line.strip_prefix(bullet) {
+            return Some((icons.bullet.to_string(), item));
+        }
+    }
+
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits == 0 {
+        return None;
+    }
+    let rest = &line[digits..];
+    rest.strip_prefix(". ")
+        .or_else(|| rest.strip_prefix(") "))
+        .map(|item| (format!("{}.", &line[..digits]), item))
+}
+
+/// One code block line on the code background, cut or padded to `width`
+fn code_line(text: &str, width: usize, style: Style) -> Line<'static> {
+    let text = format!(" {}", text.replace('\t', "    "));
+    let mut text = truncate_to_width(&text, width);
+    let padding = width.saturating_sub(text.width());
+    text.push_str(&" ".repeat(padding));
+    Line::from(Span::styled(text, style))
+}
+
+/// Split a line of prose into spans for bold, italic and inline code
+fn inline_spans(text: &str, theme: &Theme) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut prev: Option<char> = None;
+    let mut i = 0;
+
+    while i < text.len() {
+        let rest = &text[i..];
+        if let Some((len, inner, style)) = inline_marker(rest, prev, theme) {
+            if !plain.is_empty() {
+                spans.push(Span::raw(std::mem::take(&mut plain)));
+            }
+            spans.push(Span::styled(inner.to_string(), style));
+            prev = rest[..len].chars().last();
+            i += len;
+            continue;
+        }
+
+        let ch = rest.chars().next().unwrap_or_default();
+        plain.push(ch);
+        prev = Some(ch);
+        i += ch.len_utf8();
+    }
+
+    if !plain.is_empty() {
+        spans.push(Span::raw(plain));
+    }
+    spans
+}
+
+/// Formatted span starting at the beginning of `rest`, if any
+///
+/// Returns the byte length consumed including the markers, the text
+/// between them and its style.
+fn inline_marker<'a>(
+    rest: &'a str,
+    prev: Option<char>,
+    theme: &Theme,
+) -> Option<(usize, &'a str, Style)> {
+    if let Some(after) = rest.strip_prefix('`') {
+        let end = after.find('`').filter(|&end| end > 0)?;
+        let style = Style::default().fg(theme.accent).bg(theme.surface);
+        return Some((end + 2, &after[..end], style));
+    }
+
+    // `_` inside a word (snake_case) is not emphasis
+    let in_word = prev.is_some_and(char::is_alphanumeric);
+
+    for delim in ["**", "__"] {
+        if let Some(after) = rest.strip_prefix(delim) {
+            if delim == "__" && in_word {
+                return None;
+            }
+            let end = after.find(delim).filter(|&end| end > 0)?;
+            let style = Style::default().add_modifier(Modifier::BOLD);
+            return Some((end + 4, &after[..end], style));
+        }
+    }
+
+    for delim in ['*', '_'] {
+        if let Some(after) = rest.strip_prefix(delim) {
+            if (delim == '_' && in_word) || after.starts_with(char::is_whitespace) {
+                return None;
+            }
+            let end = after.find(delim).filter(|&end| end > 0)?;
+            let inner = &after[..end];
+            if inner.ends_with(char::is_whitespace) {
+                return None;
+            }
+            if delim == '_' && after[end + 1..].starts_with(char::is_alphanumeric) {
+                return None;
+            }
+            let style = Style::default().add_modifier(Modifier::ITALIC);
+            return Some((end + 2, inner, style));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::icons::ASCII_ICONS;
+    use crate::ui::theme::DARK_THEME;
+
+    fn line_text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_inline_formatting() {
+        let spans = inline_spans("use **bold**, *it* and `code`", &DARK_THEME);
+        let texts: Vec<&str> = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(texts, ["use ", "bold", ", ", "it", " and ", "code"]);
+        assert!(spans[1].style.add_modifier.contains(Modifier::BOLD));
+        assert!(spans[3].style.add_modifier.contains(Modifier::ITALIC));
+        assert_eq!(spans[5].style.bg, Some(DARK_THEME.surface));
+
+        // Identifiers and lone markers stay plain
+        let spans = inline_spans("set max_retry_count * 2", &DARK_THEME);
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].content, "set max_retry_count * 2");
+    }
+
+    #[test]
+    fn test_lists_and_headings() {
+        let lines = render_markdown("## Steps\n- first\n  2. second", 40, &ASCII_ICONS, &DARK_THEME);
+        assert_eq!(line_text(&lines[0]), "Steps");
+        assert_eq!(line_text(&lines[1]), format!("{} first", ASCII_ICONS.bullet));
+        assert_eq!(line_text(&lines[2]), "  2. second");
+    }
+
+    #[test]
+    fn test_code_block_is_padded_and_not_wrapped() {
+        let content = "```rust\nfn main() {\n    let **x** = 1; // a very long comment\n}\n```";
+        let lines = render_markdown(content, 20, &ASCII_ICONS, &DARK_THEME);
+
+        // One rendered line per source line, fences included
+        assert_eq!(lines.len(), 5);
+        assert_eq!(line_text(&lines[0]).trim_end(), " rust");
+        assert_eq!(line_text(&lines[1]), " fn main() {        ");
+        // Long lines are cut rather than wrapped, and markdown is left alone
+        assert_eq!(line_text(&lines[2]), "     let **x** = ...");
+        for line in &lines {
+            assert_eq!(line_text(line).width(), 20);
+            assert_eq!(line.spans[0].style.bg, Some(DARK_THEME.surface));
+        }
+    }
+}
@@ -14,18 +14,31 @@ use crate::models::{App, View};
 // Module declarations
 mod chat_view;
 mod components;
+mod file_viewer;
 mod header_footer;
+mod help;
+mod history_view;
+pub mod icons;
+mod markdown;
 mod notifications;
 mod tab_views;
+pub mod theme;
 mod workflow_views;
 
 // Re-export public functions
-pub use chat_view::render_chat;
-pub use components::{render_dropdown, render_file_browser};
+pub use chat_view::{render_chat, render_chat_settings};
+pub use components::{
+    render_command_preview, render_dropdown, render_export_prompt, render_file_browser,
+    render_params_file_prompt, render_preset_prompt, render_search_prompt,
+};
+pub use file_viewer::render_file_viewer;
 pub use header_footer::{render_footer, render_header};
+pub use help::render_help;
+pub use history_view::render_history;
 pub use notifications::render_notifications;
 pub use tab_views::{
-    render_close_confirmation, render_empty_tabs, render_tab_bar, render_tab_content,
+    render_close_confirmation, render_empty_tabs, render_raw_json_popup, render_tab_bar,
+    render_tab_content,
 };
 pub use workflow_views::{
     render_workflow_detail, render_workflow_edit, render_workflow_list, render_workflow_running,
@@ -42,6 +55,10 @@ pub fn ui(f: &mut Frame, app: &mut App) {
         ])
         .split(f.area());
 
+    if let View::WorkflowEdit(idx) = app.current_view {
+        app.refresh_operation_estimate(idx);
+    }
+
     // Header
     render_header(f, chunks[0], app);
 
@@ -63,12 +80,13 @@ pub fn ui(f: &mut Frame, app: &mut App) {
         match app.current_view {
             View::Tabs => {
                 if app.open_tabs.is_empty() {
-                    render_empty_tabs(f, tab_chunks[1]);
+                    render_empty_tabs(f, tab_chunks[1], app);
                 } else if let Some(tab) = app.open_tabs.get(app.active_tab_idx) {
                     render_tab_content(f, tab_chunks[1], app, tab);
                 }
             }
             View::Chat => render_chat(f, tab_chunks[1], app),
+            View::History => render_history(f, tab_chunks[1], app),
             View::WorkflowList => render_workflow_list(f, tab_chunks[1], app),
             View::WorkflowDetail(idx) => render_workflow_detail(f, tab_chunks[1], app, idx),
             View::WorkflowEdit(idx) => render_workflow_edit(f, tab_chunks[1], app, idx),
@@ -82,6 +100,7 @@ pub fn ui(f: &mut Frame, app: &mut App) {
             View::WorkflowEdit(idx) => render_workflow_edit(f, chunks[1], app, idx),
             View::WorkflowRunning(idx) => render_workflow_running(f, chunks[1], app, idx),
             View::Chat => render_chat(f, chunks[1], app),
+            View::History => render_history(f, chunks[1], app),
             View::Tabs => {
                 // Should not happen
                 let placeholder = Paragraph::new("Error: Tabs view without tab mode");
@@ -93,6 +112,31 @@ pub fn ui(f: &mut Frame, app: &mut App) {
     // Footer
     render_footer(f, chunks[2], app);
 
+    // Preset name prompt overlay
+    if app.saving_preset {
+        render_preset_prompt(f, f.area(), app);
+    }
+
+    // Phase tree search prompt overlay
+    if app.search_prompt.is_some() {
+        render_search_prompt(f, f.area(), app);
+    }
+
+    // Log export prompt overlay (below the completion dropdown)
+    if app.export_handle.is_some() {
+        render_export_prompt(f, f.area(), app);
+    }
+
+    // Params file prompt overlay (below the completion dropdown)
+    if app.params_file_prompt.is_some() {
+        render_params_file_prompt(f, f.area(), app);
+    }
+
+    // Chat settings overlay
+    if app.chat.as_ref().is_some_and(|chat| chat.settings_draft.is_some()) {
+        render_chat_settings(f, f.area(), app);
+    }
+
     // Dropdown overlay
     if app.show_dropdown {
         render_dropdown(f, chunks[1], app);
@@ -105,9 +149,29 @@ pub fn ui(f: &mut Frame, app: &mut App) {
 
     // Close confirmation overlay
     if app.show_close_confirmation {
-        render_close_confirmation(f, f.area());
+        render_close_confirmation(f, f.area(), app);
+    }
+
+    // Raw JSON debug overlay
+    if app.show_raw_json {
+        render_raw_json_popup(f, chunks[1], app);
+    }
+
+    // Output file viewer overlay
+    if app.file_viewer.is_some() {
+        render_file_viewer(f, chunks[1], app);
+    }
+
+    // Command preview overlay
+    if app.command_preview.is_some() {
+        render_command_preview(f, f.area(), app);
     }
 
     // Notifications overlay (always render if there are active notifications)
     render_notifications(f, app, f.area());
+
+    // Keybinding help overlay (on top of everything)
+    if app.show_help {
+        render_help(f, f.area(), app);
+    }
 }
@@ -2,7 +2,7 @@
 
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Wrap},
     Frame,
@@ -44,10 +44,10 @@ pub fn render_notifications(f: &mut Frame, app: &App, area: Rect) {
 
     for (idx, notification) in notifications.iter().take(3).enumerate() {
         let (bg_color, fg_color, icon) = match notification.level {
-            NotificationLevel::Error => (Color::Red, Color::White, "✗"),
-            NotificationLevel::Warning => (Color::Yellow, Color::Black, "⚠"),
-            NotificationLevel::Info => (Color::Blue, Color::White, "ℹ"),
-            NotificationLevel::Success => (Color::Green, Color::White, "✓"),
+            NotificationLevel::Error => (app.theme.failed, app.theme.text, app.icons.error),
+            NotificationLevel::Warning => (app.theme.warning, app.theme.highlight_text, app.icons.warning),
+            NotificationLevel::Info => (app.theme.notice, app.theme.text, app.icons.info),
+            NotificationLevel::Success => (app.theme.success, app.theme.text, app.icons.success),
         };
 
         let text = vec![
@@ -2,15 +2,18 @@
 
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph},
     Frame,
 };
-use workflow_manager_sdk::WorkflowStatus;
 
-use super::components::centered_rect;
-use crate::app::WorkflowPane;
+use super::components::{
+    centered_rect, filter_phase_messages, highlight_matches, horizontal_offset, node_preview,
+    progress_gauge, raw_line_visible, render_phase_timeline, render_progress_summary, timing_label,
+    tree_message_style, truncate_to_width, ProgressSummary, TabStatus,
+};
+use crate::app::{RunResult, WorkflowPane};
 use crate::models::*;
 
 pub fn render_tab_bar(f: &mut Frame, area: Rect, app: &App) {
@@ -41,29 +44,20 @@ pub fn render_tab_bar(f: &mut Frame, area: Rect, app: &App) {
         let is_active = real_idx == app.active_tab_idx;
 
         // Truncate name if too long
-        let name = if tab.workflow_name.len() > 10 {
-            format!("{}...", &tab.workflow_name[..7])
-        } else {
-            tab.workflow_name.clone()
-        };
+        let name = truncate_to_width(&tab.workflow_name, 10);
 
         // Status icon
-        let icon = match tab.status {
-            WorkflowStatus::Running => "●",
-            WorkflowStatus::Completed => "✓",
-            WorkflowStatus::Failed => "✗",
-            WorkflowStatus::NotStarted => "○",
-        };
+        let icon = app.icons.workflow_status(&tab.status);
 
         let title = format!("[ {} #{} {} ]", name, tab.instance_number, icon);
 
         let style = if is_active {
             Style::default()
-                .fg(Color::White)
-                .bg(Color::DarkGray)
+                .fg(app.theme.text)
+                .bg(app.theme.surface)
                 .add_modifier(Modifier::BOLD)
         } else {
-            Style::default().fg(Color::Gray)
+            Style::default().fg(app.theme.secondary)
         };
 
         spans.push(Span::styled(title, style));
@@ -81,18 +75,18 @@ pub fn render_tab_bar(f: &mut Frame, area: Rect, app: &App) {
     spans.push(Span::styled(
         "[+ New]",
         Style::default()
-            .fg(Color::White)
+            .fg(app.theme.text)
             .add_modifier(Modifier::BOLD),
     ));
 
     let tabs_line = Line::from(spans);
-    let separator = Line::from("━".repeat(area.width as usize));
+    let separator = Line::from(app.icons.separator.repeat(area.width as usize));
 
     let paragraph = Paragraph::new(vec![tabs_line, separator]);
     f.render_widget(paragraph, area);
 }
 
-pub fn render_empty_tabs(f: &mut Frame, area: Rect) {
+pub fn render_empty_tabs(f: &mut Frame, area: Rect, app: &App) {
     let text = vec![
         Line::from(""),
         Line::from(""),
@@ -100,28 +94,28 @@ pub fn render_empty_tabs(f: &mut Frame, area: Rect) {
         Line::from(Span::styled(
             "No workflows running",
             Style::default()
-                .fg(Color::Gray)
+                .fg(app.theme.secondary)
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
         Line::from(Span::styled(
             "Press [Ctrl+T] or click [+ New]",
-            Style::default().fg(Color::White),
+            Style::default().fg(app.theme.text),
         )),
         Line::from(Span::styled(
             "to start a new workflow",
-            Style::default().fg(Color::White),
+            Style::default().fg(app.theme.text),
         )),
     ];
 
     let paragraph = Paragraph::new(text)
         .block(Block::default().borders(Borders::NONE))
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(app.theme.text));
 
     f.render_widget(paragraph, area);
 }
 
-pub fn render_close_confirmation(f: &mut Frame, area: Rect) {
+pub fn render_close_confirmation(f: &mut Frame, area: Rect, app: &App) {
     let popup_area = centered_rect(50, 30, area);
 
     let text = vec![
@@ -129,30 +123,30 @@ pub fn render_close_confirmation(f: &mut Frame, area: Rect) {
         Line::from(Span::styled(
             "Close Running Workflow?",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(app.theme.accent)
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
         Line::from(Span::styled(
             "This workflow is still running.",
-            Style::default().fg(Color::White),
+            Style::default().fg(app.theme.text),
         )),
         Line::from(Span::styled(
             "Closing will kill the process.",
-            Style::default().fg(Color::White),
+            Style::default().fg(app.theme.text),
         )),
         Line::from(""),
         Line::from(vec![
             Span::styled(
                 "[Y]",
                 Style::default()
-                    .fg(Color::White)
+                    .fg(app.theme.text)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(" Yes  "),
             Span::styled(
                 "[N]",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                Style::default().fg(app.theme.failed).add_modifier(Modifier::BOLD),
             ),
             Span::raw(" No"),
         ]),
@@ -161,14 +155,148 @@ pub fn render_close_confirmation(f: &mut Frame, area: Rect) {
     let paragraph = Paragraph::new(text).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Yellow))
-            .style(Style::default().bg(Color::Black)),
+            .border_style(Style::default().fg(app.theme.accent))
+            .style(Style::default().bg(app.theme.background)),
     );
 
     f.render_widget(paragraph, popup_area);
 }
 
-pub fn render_tab_content(f: &mut Frame, area: Rect, _app: &App, tab: &WorkflowTab) {
+/// Artifacts listed in the result banner; `O` opens all of them
+const BANNER_ARTIFACTS: usize = 3;
+
+/// Bordered banner with the summary and artifacts of a run's `Result` event
+fn render_result_banner(
+    f: &mut Frame,
+    area: Rect,
+    app: &App,
+    result: &RunResult,
+    selected: Option<usize>,
+) {
+    let width = area.width.saturating_sub(4) as usize;
+    let mut lines = vec![Line::from(Span::styled(
+        truncate_to_width(&result.summary, width),
+        Style::default().fg(app.theme.text).add_modifier(Modifier::BOLD),
+    ))];
+    // Scroll the list so the selected artifact stays in view
+    let shown = result.artifacts.len().min(BANNER_ARTIFACTS);
+    let first = selected
+        .map_or(0, |idx| (idx + 1).saturating_sub(shown))
+        .min(result.artifacts.len() - shown);
+    for (i, artifact) in result.artifacts.iter().enumerate().skip(first).take(shown) {
+        let style = if selected == Some(i) {
+            Style::default().fg(app.theme.info).add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().fg(app.theme.info)
+        };
+        lines.push(Line::from(Span::styled(
+            truncate_to_width(&format!("{}. {}", i + 1, artifact), width),
+            style,
+        )));
+    }
+    if result.artifacts.len() > shown {
+        lines.push(Line::from(Span::styled(
+            format!("+{} more", result.artifacts.len() - shown),
+            Style::default().fg(app.theme.dim),
+        )));
+    }
+
+    let mut title = " Result ".to_string();
+    if !result.artifacts.is_empty() {
+        title.push_str("[O] Open selected artifact ");
+    }
+    let banner = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(app.theme.success)),
+    );
+    f.render_widget(banner, area);
+}
+
+/// Rows taken by the result banner, borders included
+fn result_banner_height(result: &RunResult) -> u16 {
+    let artifacts = result.artifacts.len().min(BANNER_ARTIFACTS + 1);
+    3 + artifacts as u16
+}
+
+/// Status strip of the tab, in the warning color once the run looks stalled
+fn render_tab_status(f: &mut Frame, area: Rect, app: &App, tab: &WorkflowTab) {
+    let now = chrono::Local::now();
+    let running = tab.status == workflow_manager_sdk::WorkflowStatus::Running;
+    let last_event = tab.last_event.lock().ok().and_then(|at| *at);
+    let status = match tab.workflow_phases.lock() {
+        Ok(phases) => TabStatus::new(&phases, running, tab.start_time, last_event, now),
+        Err(_) => return,
+    };
+
+    let color = if status.stalled {
+        app.theme.warning
+    } else {
+        app.theme.dim
+    };
+    let text = truncate_to_width(
+        &format!(" {}", status.text(app.icons, now)),
+        area.width as usize,
+    );
+    let line = Line::from(Span::styled(text, Style::default().fg(color)));
+    f.render_widget(Paragraph::new(line), area);
+}
+
+pub fn render_tab_content(f: &mut Frame, area: Rect, app: &App, tab: &WorkflowTab) {
+    // The result of a finished run goes first, above everything else
+    let result = tab.result.lock().ok().and_then(|result| result.clone());
+    let area = match &result {
+        Some(result) => {
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(result_banner_height(result)), Constraint::Min(0)])
+                .split(area);
+            render_result_banner(f, rows[0], app, result, tab.selected_artifact);
+            rows[1]
+        }
+        None => area,
+    };
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(area);
+    render_tab_status(f, rows[0], app, tab);
+    let area = rows[1];
+
+    // Overall progress line above the panes, once there are phases
+    let summary = tab
+        .workflow_phases
+        .lock()
+        .ok()
+        .filter(|phases| !phases.is_empty())
+        .map(|phases| ProgressSummary::from_phases(&phases));
+    let area = match &summary {
+        Some(summary) => {
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(1), Constraint::Min(0)])
+                .split(area);
+            render_progress_summary(f, rows[0], app, summary);
+            rows[1]
+        }
+        None => area,
+    };
+
+    // Phase timeline below the panes, once a phase has started
+    let area = match tab.workflow_phases.lock() {
+        Ok(phases) if phases.iter().any(|phase| phase.started_at.is_some()) => {
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(1)])
+                .split(area);
+            render_phase_timeline(f, rows[1], app, &phases);
+            rows[0]
+        }
+        _ => area,
+    };
+
     // Split area into two vertical panes (50/50)
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
@@ -178,6 +306,9 @@ pub fn render_tab_content(f: &mut Frame, area: Rect, _app: &App, tab: &WorkflowT
     let left_pane = chunks[0];
     let right_pane = chunks[1];
 
+    // Collapsed-item previews fill whatever is left of the pane's inner width
+    let preview_width = left_pane.width.saturating_sub(2);
+
     // Build structured logs for left pane
     let mut structured_logs: Vec<Line> = Vec::new();
 
@@ -185,94 +316,91 @@ pub fn render_tab_content(f: &mut Frame, area: Rect, _app: &App, tab: &WorkflowT
     structured_logs.push(Line::from(vec![
         Span::styled(
             format!("Handle ID: {}", tab.runtime_handle_id),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(app.theme.dim),
         ),
     ]));
 
     // Display hierarchical phase/task/agent structure
-    let phases_snapshot: Vec<WorkflowPhase> = if let Ok(phases) = tab.workflow_phases.lock() {
+    let mut phases_snapshot: Vec<WorkflowPhase> = if let Ok(phases) = tab.workflow_phases.lock() {
         phases.clone()
     } else {
         Vec::new()
     };
+    filter_phase_messages(&mut phases_snapshot, tab.log_filter);
 
     if !phases_snapshot.is_empty() {
         for phase in &phases_snapshot {
             // Phase header
-            let phase_icon = match phase.status {
-                PhaseStatus::NotStarted => "○",
-                PhaseStatus::Running => "▶",
-                PhaseStatus::Completed => "✓",
-                PhaseStatus::Failed => "✗",
-            };
-            let phase_color = match phase.status {
-                PhaseStatus::NotStarted => Color::Gray,
-                PhaseStatus::Running => Color::Yellow,
-                PhaseStatus::Completed => Color::White,
-                PhaseStatus::Failed => Color::Red,
-            };
+            let phase_icon = app.icons.phase_status(&phase.status);
+            let phase_color = app.theme.phase_status(&phase.status);
 
             let is_expanded = tab.expanded_phases.contains(&phase.id);
-            let expand_icon = if is_expanded { "▼" } else { "▶" };
+            let expand_icon = app.icons.expand(is_expanded);
             let is_selected = tab.selected_phase == phase.id
                 && tab.selected_task.is_none()
-                && tab.selected_agent.is_none();
+                && tab.selected_agent.is_none()
+                && tab.selected_file.is_none()
+                && tab.selected_artifact.is_none();
 
             let mut phase_spans = vec![
                 Span::styled(format!("{} ", phase_icon), Style::default().fg(phase_color)),
                 Span::styled(
                     format!("{} ", expand_icon),
-                    Style::default().fg(Color::White),
+                    Style::default().fg(app.theme.text),
                 ),
                 Span::styled(
                     format!("Phase {}: {}", phase.id, phase.name),
                     if is_selected {
                         Style::default()
-                            .fg(Color::White)
+                            .fg(app.theme.selected)
                             .add_modifier(Modifier::BOLD | Modifier::REVERSED)
                     } else {
                         Style::default()
-                            .fg(Color::White)
+                            .fg(app.theme.text)
                             .add_modifier(Modifier::BOLD)
                     },
                 ),
             ];
 
-            // Show last task message if collapsed
-            if !is_expanded && !phase.tasks.is_empty() {
-                if let Some(last_task) = phase.tasks.last() {
-                    if let Some(last_msg) = last_task.messages.last() {
-                        let preview = if last_msg.len() > 40 {
-                            format!(" - {}...", &last_msg[..40])
-                        } else {
-                            format!(" - {}", last_msg)
-                        };
-                        phase_spans
-                            .push(Span::styled(preview, Style::default().fg(Color::DarkGray)));
-                    }
+            if let Some(timing) = timing_label(phase.elapsed(), phase.eta(), app.icons) {
+                phase_spans.push(Span::styled(timing, Style::default().fg(app.theme.dim)));
+            }
+
+            // Show the error, or else the last task message, if collapsed
+            if !is_expanded {
+                let last_msg = phase.tasks.last().and_then(|task| task.messages.last());
+                if let Some(preview) = node_preview(
+                    &phase_spans,
+                    phase.error.as_deref(),
+                    last_msg.map(|msg| msg.text.as_str()),
+                    preview_width,
+                    &app.theme,
+                ) {
+                    phase_spans.push(preview);
                 }
             }
 
             structured_logs.push(Line::from(phase_spans));
 
             if is_expanded {
+                // Full error of a failed phase above its tasks
+                if let Some(error) = &phase.error {
+                    structured_logs.push(Line::from(vec![
+                        Span::raw("  "),
+                        Span::styled(
+                            format!("Error: {}", error),
+                            Style::default().fg(app.theme.failed),
+                        ),
+                    ]));
+                }
+
                 // Display tasks
                 for task in &phase.tasks {
-                    let task_icon = match task.status {
-                        TaskStatus::NotStarted => "○",
-                        TaskStatus::Running => "▶",
-                        TaskStatus::Completed => "✓",
-                        TaskStatus::Failed => "✗",
-                    };
-                    let task_color = match task.status {
-                        TaskStatus::NotStarted => Color::Gray,
-                        TaskStatus::Running => Color::Yellow,
-                        TaskStatus::Completed => Color::White,
-                        TaskStatus::Failed => Color::Red,
-                    };
+                    let task_icon = app.icons.task_status(&task.status);
+                    let task_color = app.theme.task_status(&task.status);
 
                     let task_expanded = tab.expanded_tasks.contains(&task.id);
-                    let task_expand_icon = if task_expanded { "▼" } else { "▶" };
+                    let task_expand_icon = app.icons.expand(task_expanded);
                     let is_task_selected = tab.selected_phase == phase.id
                         && Some(&task.id) == tab.selected_task.as_ref()
                         && tab.selected_agent.is_none();
@@ -282,30 +410,40 @@ pub fn render_tab_content(f: &mut Frame, area: Rect, _app: &App, tab: &WorkflowT
                         Span::styled(format!("{} ", task_icon), Style::default().fg(task_color)),
                         Span::styled(
                             format!("{} ", task_expand_icon),
-                            Style::default().fg(Color::White),
+                            Style::default().fg(app.theme.text),
                         ),
                         Span::styled(
                             &task.description,
                             if is_task_selected {
                                 Style::default()
-                                    .fg(Color::White)
+                                    .fg(app.theme.selected)
                                     .add_modifier(Modifier::REVERSED)
                             } else {
-                                Style::default().fg(Color::White)
+                                Style::default().fg(app.theme.text)
                             },
                         ),
                     ];
 
-                    // Show last message if collapsed
-                    if !task_expanded && !task.messages.is_empty() {
-                        if let Some(last_msg) = task.messages.last() {
-                            let preview = if last_msg.len() > 30 {
-                                format!(" - {}...", &last_msg[..30])
-                            } else {
-                                format!(" - {}", last_msg)
-                            };
-                            task_spans
-                                .push(Span::styled(preview, Style::default().fg(Color::DarkGray)));
+                    if let Some(progress) = task.progress {
+                        task_spans.push(Span::styled(
+                            format!(" {}", progress_gauge(progress, app.icons)),
+                            Style::default().fg(app.theme.info),
+                        ));
+                    }
+                    if let Some(timing) = timing_label(task.elapsed(), None, app.icons) {
+                        task_spans.push(Span::styled(timing, Style::default().fg(app.theme.dim)));
+                    }
+
+                    // Show the error, or else the last message, if collapsed
+                    if !task_expanded {
+                        if let Some(preview) = node_preview(
+                            &task_spans,
+                            task.error.as_deref(),
+                            task.messages.last().map(|msg| msg.text.as_str()),
+                            preview_width,
+                            &app.theme,
+                        ) {
+                            task_spans.push(preview);
                         }
                     }
 
@@ -316,64 +454,54 @@ pub fn render_tab_content(f: &mut Frame, area: Rect, _app: &App, tab: &WorkflowT
                         for msg in &task.messages {
                             structured_logs.push(Line::from(vec![
                                 Span::raw("    "),
-                                Span::styled(msg, Style::default().fg(Color::Gray)),
+                                Span::styled(msg.text.as_str(), tree_message_style(msg, &app.theme)),
                             ]));
                         }
 
                         // Display agents
                         for agent in &task.agents {
-                            let agent_icon = match agent.status {
-                                AgentStatus::NotStarted => "○",
-                                AgentStatus::Running => "▶",
-                                AgentStatus::Completed => "✓",
-                                AgentStatus::Failed => "✗",
-                            };
-                            let agent_color = match agent.status {
-                                AgentStatus::NotStarted => Color::Gray,
-                                AgentStatus::Running => Color::Yellow,
-                                AgentStatus::Completed => Color::White,
-                                AgentStatus::Failed => Color::Red,
-                            };
+                            // Agents started by other agents sit under them
+                            let nest = "  ".repeat(agent.depth);
+                            let agent_icon = app.icons.agent_status(&agent.status);
+                            let agent_color = app.theme.agent_status(&agent.status);
 
                             let agent_expanded = tab.expanded_agents.contains(&agent.id);
-                            let agent_expand_icon = if agent_expanded { "▼" } else { "▶" };
+                            let agent_expand_icon = app.icons.expand(agent_expanded);
                             let is_agent_selected = Some(&agent.id) == tab.selected_agent.as_ref();
 
                             let agent_spans = vec![
-                                Span::raw("    "),
+                                Span::raw(format!("    {}", nest)),
                                 Span::styled(
                                     format!("{} ", agent_icon),
                                     Style::default().fg(agent_color),
                                 ),
                                 Span::styled(
                                     format!("{} ", agent_expand_icon),
-                                    Style::default().fg(Color::White),
+                                    Style::default().fg(app.theme.text),
                                 ),
                                 Span::styled(
                                     format!("@{}", agent.name),
                                     if is_agent_selected {
                                         Style::default()
-                                            .fg(Color::Magenta)
+                                            .fg(app.theme.agent)
                                             .add_modifier(Modifier::REVERSED)
                                     } else {
-                                        Style::default().fg(Color::Magenta)
+                                        Style::default().fg(app.theme.agent)
                                     },
                                 ),
                             ];
 
-                            // Show last line when collapsed
+                            // Show the error, or else the last line, when collapsed
                             let mut agent_line_spans = agent_spans;
-                            if !agent_expanded && !agent.messages.is_empty() {
-                                if let Some(last_msg) = agent.messages.last() {
-                                    let preview = if last_msg.len() > 50 {
-                                        format!(" - {}...", &last_msg[..50])
-                                    } else {
-                                        format!(" - {}", last_msg)
-                                    };
-                                    agent_line_spans.push(Span::styled(
-                                        preview,
-                                        Style::default().fg(Color::DarkGray),
-                                    ));
+                            if !agent_expanded {
+                                if let Some(preview) = node_preview(
+                                    &agent_line_spans,
+                                    agent.error.as_deref(),
+                                    agent.messages.last().map(|msg| msg.text.as_str()),
+                                    preview_width,
+                                    &app.theme,
+                                ) {
+                                    agent_line_spans.push(preview);
                                 }
                             }
                             structured_logs.push(Line::from(agent_line_spans));
@@ -397,15 +525,16 @@ pub fn render_tab_content(f: &mut Frame, area: Rect, _app: &App, tab: &WorkflowT
 
                                     for msg in &agent.messages[start..end] {
                                         structured_logs.push(Line::from(vec![
-                                            Span::raw("      "),
-                                            Span::styled(msg, Style::default().fg(Color::Gray)),
+                                            Span::raw(format!("      {}", nest)),
+                                            Span::styled(msg.text.as_str(), tree_message_style(msg, &app.theme)),
                                         ]));
                                     }
 
                                     // Show scroll indicator if there are more messages
                                     if total_messages > window_size {
                                         let indicator = format!(
-                                            "      [Showing {}-{} of {}]",
+                                            "      {}[Showing {}-{} of {}]",
+                                            nest,
                                             start + 1,
                                             end,
                                             total_messages
@@ -413,7 +542,7 @@ pub fn render_tab_content(f: &mut Frame, area: Rect, _app: &App, tab: &WorkflowT
                                         structured_logs.push(Line::from(vec![Span::styled(
                                             indicator,
                                             Style::default()
-                                                .fg(Color::White)
+                                                .fg(app.theme.text)
                                                 .add_modifier(Modifier::ITALIC),
                                         )]));
                                     }
@@ -427,15 +556,29 @@ pub fn render_tab_content(f: &mut Frame, area: Rect, _app: &App, tab: &WorkflowT
                 if !phase.output_files.is_empty() {
                     structured_logs.push(Line::from(vec![
                         Span::raw("  "),
-                        Span::styled("Output files:", Style::default().fg(Color::White)),
+                        Span::styled("Output files:", Style::default().fg(app.theme.text)),
                     ]));
-                    for (path, desc) in &phase.output_files {
+                    for (file_idx, (path, desc)) in phase.output_files.iter().enumerate() {
+                        let is_file_selected = tab.selected_phase == phase.id
+                            && tab.selected_file == Some(file_idx);
                         structured_logs.push(Line::from(vec![
                             Span::raw("    "),
-                            Span::styled("📄 ", Style::default().fg(Color::White)),
-                            Span::styled(path, Style::default().fg(Color::Yellow)),
+                            Span::styled(
+                                format!("{} ", app.icons.file),
+                                Style::default().fg(app.theme.text),
+                            ),
+                            Span::styled(
+                                path,
+                                if is_file_selected {
+                                    Style::default()
+                                        .fg(app.theme.accent)
+                                        .add_modifier(Modifier::REVERSED)
+                                } else {
+                                    Style::default().fg(app.theme.accent)
+                                },
+                            ),
                             Span::raw(" - "),
-                            Span::styled(desc, Style::default().fg(Color::Gray)),
+                            Span::styled(desc, Style::default().fg(app.theme.secondary)),
                         ]));
                     }
                 }
@@ -447,24 +590,54 @@ pub fn render_tab_content(f: &mut Frame, area: Rect, _app: &App, tab: &WorkflowT
     let mut raw_output: Vec<Line> = Vec::new();
     if let Ok(output) = tab.workflow_output.lock() {
         for line in output.iter() {
-            raw_output.push(Line::from(line.clone()));
+            if raw_line_visible(line, tab.log_filter) {
+                raw_output.push(Line::from(line.text.clone()));
+            }
         }
     }
 
     // Determine focused pane styling
     let left_border_style = if tab.focused_pane == WorkflowPane::StructuredLogs {
-        Style::default().fg(Color::White)
+        Style::default().fg(app.theme.text)
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(app.theme.dim)
     };
 
     let right_border_style = if tab.focused_pane == WorkflowPane::RawOutput {
-        Style::default().fg(Color::White)
+        Style::default().fg(app.theme.text)
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(app.theme.dim)
     };
 
-    let left_title = format!(" {} #{} ", tab.workflow_name, tab.instance_number);
+    let mut left_title = format!(
+        " {} #{} {}",
+        tab.workflow_name,
+        tab.instance_number,
+        if tab.follow { "[FOLLOW] " } else { "" }
+    );
+
+    if let Some(re) = &tab.search {
+        let style = Style::default()
+            .fg(app.theme.highlight_text)
+            .bg(app.theme.highlight);
+        structured_logs = highlight_matches(structured_logs, re, style);
+    }
+
+    // Scrolled sideways, the title shows the first visible column
+    let inner_width = left_pane.width.saturating_sub(2);
+    let h_scroll = horizontal_offset(&structured_logs, tab.h_scroll, inner_width);
+    if h_scroll > 0 {
+        left_title.push_str(&format!("[col {}] ", h_scroll + 1));
+    }
+
+    // Following keeps the end of the tree at the bottom of the pane
+    let scroll_offset = if tab.follow {
+        structured_logs
+            .len()
+            .saturating_sub(left_pane.height.saturating_sub(2) as usize)
+    } else {
+        tab.scroll_offset
+    };
 
     // Render left pane (Structured Logs)
     let left_paragraph = Paragraph::new(structured_logs)
@@ -474,7 +647,7 @@ pub fn render_tab_content(f: &mut Frame, area: Rect, _app: &App, tab: &WorkflowT
                 .title(left_title)
                 .border_style(left_border_style),
         )
-        .scroll((tab.scroll_offset as u16, 0));
+        .scroll((scroll_offset as u16, h_scroll));
 
     f.render_widget(left_paragraph, left_pane);
 
@@ -490,3 +663,44 @@ pub fn render_tab_content(f: &mut Frame, area: Rect, _app: &App, tab: &WorkflowT
 
     f.render_widget(right_paragraph, right_pane);
 }
+
+pub fn render_raw_json_popup(f: &mut Frame, area: Rect, app: &App) {
+    let popup_area = centered_rect(80, 80, area);
+
+    let (agent_name, json_lines) = app
+        .selected_agent_raw_json()
+        .unwrap_or_else(|| ("(no agent)".to_string(), Vec::new()));
+
+    let total = json_lines.len();
+    let lines: Vec<Line> = if json_lines.is_empty() {
+        vec![Line::from(Span::styled(
+            "No raw events recorded for this agent",
+            Style::default().fg(app.theme.dim),
+        ))]
+    } else {
+        json_lines
+            .into_iter()
+            .skip(app.raw_json_scroll)
+            .map(|line| Line::from(Span::styled(line, Style::default().fg(app.theme.text))))
+            .collect()
+    };
+
+    let title = format!(
+        " Raw JSON: {} ({}/{}) [{}/jk] Scroll  [Esc/J] Close ",
+        agent_name,
+        (app.raw_json_scroll + 1).min(total),
+        total,
+        app.icons.up_down
+    );
+
+    f.render_widget(Clear, popup_area);
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(app.theme.info))
+            .style(Style::default().bg(app.theme.background)),
+    );
+
+    f.render_widget(paragraph, popup_area);
+}
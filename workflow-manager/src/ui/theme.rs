@@ -0,0 +1,301 @@
+//! Color theme for the TUI
+//!
+//! Every color the views draw comes from a [`Theme`] role, so the palette can
+//! be adapted to light terminals. The theme starts from a built-in preset
+//! (`dark`, the default, or `light`) and any role can be overridden in
+//! `~/.config/workflow-manager/theme.toml`:
+//!
+//! ```toml
+//! preset = "light"
+//! running = "#af8700"
+//! agent = "blue"
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context, Result};
+use ratatui::style::Color;
+use workflow_manager_sdk::WorkflowStatus;
+
+use crate::models::{AgentStatus, PhaseStatus, TaskStatus};
+
+/// Named color roles used by the views
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    // Text
+    /// Header bar title
+    pub header: Color,
+    /// Regular text
+    pub text: Color,
+    /// Labels and log messages
+    pub secondary: Color,
+    /// Hints, collapsed previews and placeholders
+    pub dim: Color,
+    /// The selected item of a list or tree
+    pub selected: Color,
+    /// Paths, prompts, key names and popup borders
+    pub accent: Color,
+    /// Progress, token usage and informational borders
+    pub info: Color,
+    /// Agent names
+    pub agent: Color,
+
+    // Status
+    pub not_started: Color,
+    pub running: Color,
+    pub completed: Color,
+    /// Failures and error messages
+    pub failed: Color,
+
+    // Notification backgrounds (errors use `failed`)
+    pub success: Color,
+    pub warning: Color,
+    pub notice: Color,
+
+    // Surfaces
+    /// Background of the highlighted dropdown or file browser row
+    pub highlight: Color,
+    /// Text on `highlight`
+    pub highlight_text: Color,
+    /// Active tab, completion list and chat code block background
+    pub surface: Color,
+    /// Popup background
+    pub background: Color,
+}
+
+/// The original palette, for dark terminals
+pub const DARK_THEME: Theme = Theme {
+    header: Color::White,
+    text: Color::White,
+    secondary: Color::Gray,
+    dim: Color::DarkGray,
+    selected: Color::White,
+    accent: Color::Yellow,
+    info: Color::Cyan,
+    agent: Color::Magenta,
+    not_started: Color::Gray,
+    running: Color::Yellow,
+    completed: Color::White,
+    failed: Color::Red,
+    success: Color::Green,
+    warning: Color::Yellow,
+    notice: Color::Blue,
+    highlight: Color::Yellow,
+    highlight_text: Color::Black,
+    surface: Color::DarkGray,
+    background: Color::Black,
+};
+
+/// Dark text and deeper accents for light terminals
+pub const LIGHT_THEME: Theme = Theme {
+    header: Color::Black,
+    text: Color::Black,
+    secondary: Color::DarkGray,
+    dim: Color::Gray,
+    selected: Color::Black,
+    accent: Color::Rgb(175, 95, 0),
+    info: Color::Rgb(0, 110, 130),
+    agent: Color::Rgb(135, 0, 135),
+    not_started: Color::DarkGray,
+    running: Color::Rgb(175, 95, 0),
+    completed: Color::Black,
+    failed: Color::Rgb(190, 0, 0),
+    success: Color::Green,
+    warning: Color::Yellow,
+    notice: Color::Blue,
+    highlight: Color::Rgb(255, 215, 95),
+    highlight_text: Color::Black,
+    surface: Color::Rgb(215, 215, 215),
+    background: Color::White,
+};
+
+/// Role names accepted in `theme.toml`
+const ROLES: &[&str] = &[
+    "header",
+    "text",
+    "secondary",
+    "dim",
+    "selected",
+    "accent",
+    "info",
+    "agent",
+    "not_started",
+    "running",
+    "completed",
+    "failed",
+    "success",
+    "warning",
+    "notice",
+    "highlight",
+    "highlight_text",
+    "surface",
+    "background",
+];
+
+impl Default for Theme {
+    fn default() -> Self {
+        DARK_THEME
+    }
+}
+
+impl Theme {
+    /// Built-in preset by name (`dark` or `light`)
+    pub fn preset(name: &str) -> Option<Theme> {
+        match name.trim().to_lowercase().as_str() {
+            "dark" => Some(DARK_THEME),
+            "light" => Some(LIGHT_THEME),
+            _ => None,
+        }
+    }
+
+    /// Location of the theme file, next to the user-defined workflows
+    pub fn path() -> Option<PathBuf> {
+        std::env::var("HOME")
+            .ok()
+            .map(|home| PathBuf::from(home).join(".config/workflow-manager/theme.toml"))
+    }
+
+    /// Load the theme file, or the dark preset if it does not exist
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::from_toml(&content).with_context(|| format!("Invalid theme {}", path.display()))
+    }
+
+    /// Build a theme from a `theme.toml` file: a `preset` plus role overrides
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let table: toml::Table = toml::from_str(content)?;
+
+        let mut theme = match table.get("preset") {
+            None => Self::default(),
+            Some(toml::Value::String(name)) => Self::preset(name)
+                .ok_or_else(|| anyhow!("unknown preset '{}' (expected dark or light)", name))?,
+            Some(_) => bail!("preset must be a string"),
+        };
+
+        for (role, value) in table.iter().filter(|(key, _)| *key != "preset") {
+            let Some(slot) = theme.role_mut(role) else {
+                bail!(
+                    "unknown role '{}' (expected one of {})",
+                    role,
+                    ROLES.join(", ")
+                );
+            };
+            let toml::Value::String(color) = value else {
+                bail!("{}: color must be a string", role);
+            };
+            *slot = Color::from_str(color)
+                .map_err(|_| anyhow!("{}: invalid color '{}'", role, color))?;
+        }
+
+        Ok(theme)
+    }
+
+    fn role_mut(&mut self, role: &str) -> Option<&mut Color> {
+        Some(match role {
+            "header" => &mut self.header,
+            "text" => &mut self.text,
+            "secondary" => &mut self.secondary,
+            "dim" => &mut self.dim,
+            "selected" => &mut self.selected,
+            "accent" => &mut self.accent,
+            "info" => &mut self.info,
+            "agent" => &mut self.agent,
+            "not_started" => &mut self.not_started,
+            "running" => &mut self.running,
+            "completed" => &mut self.completed,
+            "failed" => &mut self.failed,
+            "success" => &mut self.success,
+            "warning" => &mut self.warning,
+            "notice" => &mut self.notice,
+            "highlight" => &mut self.highlight,
+            "highlight_text" => &mut self.highlight_text,
+            "surface" => &mut self.surface,
+            "background" => &mut self.background,
+            _ => return None,
+        })
+    }
+
+    pub fn phase_status(&self, status: &PhaseStatus) -> Color {
+        match status {
+            PhaseStatus::NotStarted => self.not_started,
+            PhaseStatus::Running => self.running,
+            PhaseStatus::Completed => self.completed,
+            PhaseStatus::Failed => self.failed,
+        }
+    }
+
+    pub fn task_status(&self, status: &TaskStatus) -> Color {
+        match status {
+            TaskStatus::NotStarted => self.not_started,
+            TaskStatus::Running => self.running,
+            TaskStatus::Completed => self.completed,
+            TaskStatus::Failed => self.failed,
+        }
+    }
+
+    pub fn agent_status(&self, status: &AgentStatus) -> Color {
+        match status {
+            AgentStatus::NotStarted => self.not_started,
+            AgentStatus::Running => self.running,
+            AgentStatus::Completed => self.completed,
+            AgentStatus::Failed => self.failed,
+        }
+    }
+
+    pub fn workflow_status(&self, status: &WorkflowStatus) -> Color {
+        match status {
+            WorkflowStatus::NotStarted => self.not_started,
+            WorkflowStatus::Running => self.running,
+            WorkflowStatus::Paused => self.info,
+            WorkflowStatus::Completed => self.completed,
+            WorkflowStatus::Failed => self.failed,
+            _ => self.not_started,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preset() {
+        assert_eq!(Theme::preset("dark"), Some(DARK_THEME));
+        assert_eq!(Theme::preset(" Light "), Some(LIGHT_THEME));
+        assert_eq!(Theme::preset("solarized"), None);
+    }
+
+    #[test]
+    fn test_every_role_is_configurable() {
+        let mut theme = Theme::default();
+        for role in ROLES {
+            assert!(theme.role_mut(role).is_some(), "role {} not settable", role);
+        }
+    }
+
+    #[test]
+    fn test_from_toml() {
+        let theme = Theme::from_toml(
+            r##"
+            preset = "light"
+            running = "#ff8800"
+            agent = "blue"
+            "##,
+        )
+        .unwrap();
+        assert_eq!(theme.running, Color::Rgb(255, 136, 0));
+        assert_eq!(theme.agent, Color::Blue);
+        assert_eq!(theme.text, LIGHT_THEME.text);
+
+        assert_eq!(Theme::from_toml("").unwrap(), DARK_THEME);
+        assert!(Theme::from_toml("preset = \"neon\"").is_err());
+        assert!(Theme::from_toml("border = \"red\"").is_err());
+        assert!(Theme::from_toml("text = \"not-a-color\"").is_err());
+        assert!(Theme::from_toml("text = 3").is_err());
+    }
+}
@@ -2,15 +2,26 @@
 
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph},
     Frame,
 };
-use workflow_manager_sdk::{FieldType, WorkflowSource};
+use workflow_manager_sdk::{
+    validation::{is_field_required, is_required_missing},
+    FieldSchema, FieldType, WorkflowSource,
+};
 
-use crate::app::WorkflowPane;
+use super::components::{
+    filter_phase_messages, highlight_matches, horizontal_offset, node_preview, progress_gauge,
+    raw_line_visible, render_phase_timeline, render_progress_summary, timing_label,
+    tree_message_style, truncate_to_width, ProgressSummary, PREVIEW_MAX_WIDTH, PREVIEW_MIN_WIDTH,
+};
+use crate::app::{field_groups, has_field_groups, WorkflowPane};
+use crate::utils::is_secret;
 use crate::models::*;
+use unicode_width::UnicodeWidthStr;
+use crate::research::OperationEstimate;
 
 pub fn render_workflow_list(f: &mut Frame, area: Rect, app: &App) {
     let block = Block::default()
@@ -26,7 +37,7 @@ pub fn render_workflow_list(f: &mut Frame, area: Rect, app: &App) {
         .enumerate()
         .map(|(i, workflow)| {
             let is_selected = i == app.selected;
-            let bullet = if is_selected { "▶" } else { " " };
+            let bullet = if is_selected { app.icons.selected } else { " " };
 
             let source_label = match workflow.source {
                 WorkflowSource::BuiltIn => "[Built-in]",
@@ -40,9 +51,9 @@ pub fn render_workflow_list(f: &mut Frame, area: Rect, app: &App) {
                         &workflow.info.name,
                         Style::default()
                             .fg(if is_selected {
-                                Color::White
+                                app.theme.selected
                             } else {
-                                Color::Gray
+                                app.theme.secondary
                             })
                             .add_modifier(if is_selected {
                                 Modifier::BOLD
@@ -51,11 +62,11 @@ pub fn render_workflow_list(f: &mut Frame, area: Rect, app: &App) {
                             }),
                     ),
                     Span::raw(" "),
-                    Span::styled(source_label, Style::default().fg(Color::DarkGray)),
+                    Span::styled(source_label, Style::default().fg(app.theme.dim)),
                 ]),
                 Line::from(vec![Span::styled(
                     format!("     {}", workflow.info.description),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(app.theme.dim),
                 )]),
                 Line::from(""),
             ];
@@ -74,7 +85,7 @@ pub fn render_workflow_detail(f: &mut Frame, area: Rect, app: &App, idx: usize)
         None => {
             let error = Paragraph::new("Workflow not found")
                 .block(Block::default().borders(Borders::ALL))
-                .style(Style::default().fg(Color::Red));
+                .style(Style::default().fg(app.theme.failed));
             f.render_widget(error, area);
             return;
         }
@@ -87,38 +98,38 @@ pub fn render_workflow_detail(f: &mut Frame, area: Rect, app: &App, idx: usize)
 
     let mut info_lines = vec![
         Line::from(vec![
-            Span::styled("Name: ", Style::default().fg(Color::Gray)),
+            Span::styled("Name: ", Style::default().fg(app.theme.secondary)),
             Span::styled(
                 &workflow.info.name,
                 Style::default()
-                    .fg(Color::White)
+                    .fg(app.theme.text)
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("ID: ", Style::default().fg(Color::Gray)),
-            Span::styled(&workflow.info.id, Style::default().fg(Color::White)),
+            Span::styled("ID: ", Style::default().fg(app.theme.secondary)),
+            Span::styled(&workflow.info.id, Style::default().fg(app.theme.text)),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Description: ", Style::default().fg(Color::Gray)),
+            Span::styled("Description: ", Style::default().fg(app.theme.secondary)),
             Span::styled(
                 &workflow.info.description,
-                Style::default().fg(Color::White),
+                Style::default().fg(app.theme.text),
             ),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Source: ", Style::default().fg(Color::Gray)),
-            Span::styled(source_text, Style::default().fg(Color::Yellow)),
+            Span::styled("Source: ", Style::default().fg(app.theme.secondary)),
+            Span::styled(source_text, Style::default().fg(app.theme.accent)),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Fields: ", Style::default().fg(Color::Gray)),
+            Span::styled("Fields: ", Style::default().fg(app.theme.secondary)),
             Span::styled(
                 format!("{}", workflow.info.fields.len()),
-                Style::default().fg(Color::White),
+                Style::default().fg(app.theme.text),
             ),
         ]),
         Line::from(""),
@@ -132,13 +143,13 @@ pub fn render_workflow_detail(f: &mut Frame, area: Rect, app: &App, idx: usize)
         .count();
 
     info_lines.push(Line::from(vec![
-        Span::styled("Arguments: ", Style::default().fg(Color::Gray)),
+        Span::styled("Arguments: ", Style::default().fg(app.theme.secondary)),
         Span::styled(
             format!("{} configured", configured_count),
             Style::default().fg(if configured_count > 0 {
-                Color::White
+                app.theme.text
             } else {
-                Color::DarkGray
+                app.theme.dim
             }),
         ),
     ]));
@@ -153,19 +164,26 @@ pub fn render_workflow_detail(f: &mut Frame, area: Rect, app: &App, idx: usize)
             .unwrap_or("");
 
         let (display_value, value_style) = if value.is_empty() {
-            ("<empty>".to_string(), Style::default().fg(Color::DarkGray))
-        } else if value.len() > 60 {
+            ("<empty>".to_string(), Style::default().fg(app.theme.dim))
+        } else if is_secret(field) {
+            (app.icons.bullet.repeat(4), Style::default().fg(app.theme.text))
+        } else {
+            // Inner width minus the "  • label: " prefix
+            let value_width = (area.width as usize)
+                .saturating_sub(2 + 4 + field.label.width() + 2)
+                .clamp(PREVIEW_MIN_WIDTH, PREVIEW_MAX_WIDTH);
             (
-                format!("{}...", &value[..60]),
-                Style::default().fg(Color::White),
+                truncate_to_width(value, value_width),
+                Style::default().fg(app.theme.text),
             )
-        } else {
-            (value.to_string(), Style::default().fg(Color::White))
         };
 
         info_lines.push(Line::from(vec![
-            Span::styled("  • ", Style::default().fg(Color::DarkGray)),
-            Span::styled(&field.label, Style::default().fg(Color::White)),
+            Span::styled(
+                format!("  {} ", app.icons.bullet),
+                Style::default().fg(app.theme.dim),
+            ),
+            Span::styled(&field.label, Style::default().fg(app.theme.text)),
             Span::raw(": "),
             Span::styled(display_value, value_style),
         ]));
@@ -177,7 +195,7 @@ pub fn render_workflow_detail(f: &mut Frame, area: Rect, app: &App, idx: usize)
         Span::styled(
             "[L]",
             Style::default()
-                .fg(Color::White)
+                .fg(app.theme.text)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw(" Launch workflow"),
@@ -197,133 +215,269 @@ pub fn render_workflow_edit(f: &mut Frame, area: Rect, app: &App, idx: usize) {
         None => {
             let error = Paragraph::new("Workflow not found")
                 .block(Block::default().borders(Borders::ALL))
-                .style(Style::default().fg(Color::Red));
+                .style(Style::default().fg(app.theme.failed));
             f.render_widget(error, area);
             return;
         }
     };
 
-    let items: Vec<ListItem> = workflow
+    let field_errors = app.validation_errors.get(&workflow.info.id);
+
+    // Values as typed, so requirements follow the phases field while it is
+    // being edited
+    let live_values = app.live_field_values(idx);
+    let missing = workflow
         .info
         .fields
         .iter()
-        .enumerate()
-        .map(|(i, field)| {
-            let is_selected = i == app.edit_field_index;
-            let is_editing_this = is_selected && app.is_editing;
+        .filter(|field| is_required_missing(field, &live_values))
+        .count();
 
-            // Get current value and display based on field type
-            let current_value = if is_editing_this {
-                &app.edit_buffer
-            } else {
-                app.field_values
-                    .get(&field.name)
-                    .map(|s| s.as_str())
-                    .unwrap_or("")
-            };
+    // Research runs show the number of queries they are about to issue
+    let area = if let Some((_, estimate)) = &app.operation_estimate {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(estimate.phases.len() as u16 + 2),
+            ])
+            .split(area);
+        render_operation_estimate(f, rows[1], app, estimate);
+        rows[0]
+    } else {
+        area
+    };
 
-            let (display_text, is_empty) = match &field.field_type {
-                FieldType::PhaseSelector { .. } => {
-                    // Just show the value as-is (e.g., "0,1,2,3,4")
-                    if current_value.is_empty() {
-                        ("<empty>", true)
-                    } else {
-                        (current_value, false)
-                    }
+    let field_item = |i: usize, field: &FieldSchema| {
+        let is_selected = i == app.edit_field_index;
+        let is_editing_this = is_selected && app.is_editing;
+
+        // Get current value and display based on field type
+        let current_value = if is_editing_this {
+            &app.edit_buffer
+        } else {
+            app.field_values
+                .get(&field.name)
+                .map(|s| s.as_str())
+                .unwrap_or("")
+        };
+
+        let toggle_text;
+        let masked;
+        let (display_text, is_empty) = match &field.field_type {
+            FieldType::Boolean { default } => {
+                let enabled = if current_value.is_empty() {
+                    *default
+                } else {
+                    current_value.eq_ignore_ascii_case("true")
+                };
+                toggle_text = if enabled {
+                    format!("{} enabled", app.icons.checked)
+                } else {
+                    format!("{} disabled", app.icons.unchecked)
+                };
+                (toggle_text.as_str(), false)
+            }
+            // Only the length of a secret shows while typing it
+            FieldType::Secret if !current_value.is_empty() => {
+                let count = if is_editing_this {
+                    current_value.chars().count()
+                } else {
+                    4
+                };
+                masked = app.icons.bullet.repeat(count);
+                (masked.as_str(), false)
+            }
+            FieldType::PhaseSelector { .. } => {
+                // Just show the value as-is (e.g., "0,1,2,3,4")
+                if current_value.is_empty() {
+                    ("<empty>", true)
+                } else {
+                    (current_value, false)
                 }
-                FieldType::StateFile { pattern, .. } => {
-                    if current_value.is_empty() {
-                        let msg = format!("<select file matching {}>", pattern);
-                        (Box::leak(msg.into_boxed_str()) as &str, true)
-                    } else {
-                        (current_value, false)
-                    }
+            }
+            FieldType::StateFile { pattern, .. } => {
+                if current_value.is_empty() {
+                    let msg = format!("<select file matching {}>", pattern);
+                    (Box::leak(msg.into_boxed_str()) as &str, true)
+                } else {
+                    (current_value, false)
                 }
-                _ => {
-                    if current_value.is_empty() {
-                        ("<empty>", true)
-                    } else {
-                        (current_value, false)
-                    }
+            }
+            _ => {
+                if current_value.is_empty() {
+                    ("<empty>", true)
+                } else {
+                    (current_value, false)
                 }
-            };
+            }
+        };
+
+        let value_style = if is_editing_this {
+            Style::default()
+                .fg(app.theme.text)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+        } else if is_selected {
+            Style::default()
+                .fg(app.theme.accent)
+                .add_modifier(Modifier::BOLD)
+        } else if is_empty {
+            Style::default()
+                .fg(app.theme.dim)
+                .add_modifier(Modifier::ITALIC)
+        } else {
+            Style::default().fg(app.theme.text)
+        };
+
+        let is_required = is_field_required(field, &live_values);
+
+        let required_marker = if is_required { "*" } else { "" };
+        let label_color = if is_required_missing(field, &live_values) {
+            app.theme.failed
+        } else {
+            app.theme.text
+        };
 
-            let value_style = if is_editing_this {
+        let mut lines = vec![
+            Line::from(vec![Span::styled(
+                format!("{}{}: ", field.label, required_marker),
+                Style::default().fg(label_color).add_modifier(Modifier::BOLD),
+            )]),
+            Line::from(vec![Span::styled(
+                format!("  {}", field.description),
+                Style::default().fg(app.theme.dim),
+            )]),
+            Line::from(vec![
+                Span::raw("  "),
+                Span::styled(display_text.to_string(), value_style),
+                if is_editing_this {
+                    Span::styled(
+                        format!(" {}", app.icons.cursor),
+                        Style::default().fg(app.theme.text),
+                    )
+                } else {
+                    Span::raw("")
+                },
+            ]),
+        ];
+
+        // Error from the last failed launch, until the field is fixed
+        if let Some(error) = field_errors.and_then(|errors| errors.get(&field.name)) {
+            lines.push(Line::from(Span::styled(
+                format!("  {} {}", app.icons.error, error),
+                Style::default().fg(app.theme.failed),
+            )));
+        }
+        lines.push(Line::from(""));
+
+        ListItem::new(lines)
+    };
+
+    // Fields under a header per group; collapsed groups show only the header
+    let with_headers = has_field_groups(&workflow.info.fields);
+    let mut items: Vec<ListItem> = Vec::new();
+    for (group, indices) in field_groups(&workflow.info.fields) {
+        let collapsed = app.is_field_group_collapsed(&workflow.info.id, group);
+        if with_headers {
+            let is_selected = collapsed && indices.contains(&app.edit_field_index);
+            let header_style = if is_selected {
                 Style::default()
-                    .fg(Color::White)
-                    .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
-            } else if is_selected {
+                    .fg(app.theme.accent)
+                    .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+            } else {
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(app.theme.info)
                     .add_modifier(Modifier::BOLD)
-            } else if is_empty {
-                Style::default()
-                    .fg(Color::DarkGray)
-                    .add_modifier(Modifier::ITALIC)
-            } else {
-                Style::default().fg(Color::White)
             };
-
-            // Determine if field is required based on selected phases
-            let is_required = if let Some(required_phases) = &field.required_for_phases {
-                // Get selected phases from the "phases" field value
-                let selected_phases: Vec<usize> = app
-                    .field_values
-                    .get("phases")
-                    .map(|v| v.split(',').filter_map(|s| s.trim().parse().ok()).collect())
-                    .unwrap_or_default();
-
-                // Field is required if the EARLIEST selected phase needs this field
-                // (e.g., phases "1,2,3,4" only needs requirements for phase 1)
-                selected_phases
-                    .iter()
-                    .min()
-                    .map(|min_phase| required_phases.contains(min_phase))
-                    .unwrap_or(false)
+            let marker = if collapsed {
+                app.icons.collapsed
             } else {
-                field.required
+                app.icons.expanded
             };
+            let mut spans = vec![Span::styled(format!("{} {}", marker, group), header_style)];
+            if collapsed {
+                spans.push(Span::styled(
+                    format!(" ({} fields)", indices.len()),
+                    Style::default().fg(app.theme.dim),
+                ));
+                // Hidden errors still show on the header
+                let invalid = indices
+                    .iter()
+                    .filter(|&&i| {
+                        field_errors.is_some_and(|errors| {
+                            errors.contains_key(&workflow.info.fields[i].name)
+                        })
+                    })
+                    .count();
+                if invalid > 0 {
+                    spans.push(Span::styled(
+                        format!(" {} {} invalid", app.icons.error, invalid),
+                        Style::default().fg(app.theme.failed),
+                    ));
+                }
+            }
+            items.push(ListItem::new(Line::from(spans)));
+        }
+        if !collapsed {
+            for i in indices {
+                items.push(field_item(i, &workflow.info.fields[i]));
+            }
+        }
+    }
 
-            let required_marker = if is_required { "*" } else { "" };
-
-            let lines = vec![
-                Line::from(vec![Span::styled(
-                    format!("{}{}: ", field.label, required_marker),
-                    Style::default()
-                        .fg(Color::White)
-                        .add_modifier(Modifier::BOLD),
-                )]),
-                Line::from(vec![Span::styled(
-                    format!("  {}", field.description),
-                    Style::default().fg(Color::DarkGray),
-                )]),
-                Line::from(vec![
-                    Span::raw("  "),
-                    Span::styled(display_text, value_style),
-                    if is_editing_this {
-                        Span::styled(" █", Style::default().fg(Color::White))
-                    } else {
-                        Span::raw("")
-                    },
-                ]),
-                Line::from(""),
-            ];
-
-            ListItem::new(lines)
-        })
-        .collect();
-
-    let title = if app.is_editing {
+    let mut title = if app.is_editing {
         format!(" Configure: {} [EDITING] ", workflow.info.name)
+    } else if let Some(errors) = field_errors {
+        format!(
+            " Configure: {} [{} invalid field(s)] ",
+            workflow.info.name,
+            errors.len()
+        )
     } else {
         format!(" Configure: {} ", workflow.info.name)
     };
+    match missing {
+        0 => {}
+        1 => title.push_str("[1 required field missing] "),
+        n => title.push_str(&format!("[{} required fields missing] ", n)),
+    }
 
     let list = List::new(items).block(Block::default().borders(Borders::ALL).title(title));
 
     f.render_widget(list, area);
 }
 
+/// Estimated Claude queries of the configured run, one line per phase
+fn render_operation_estimate(f: &mut Frame, area: Rect, app: &App, estimate: &OperationEstimate) {
+    let lines: Vec<Line> = estimate
+        .phases
+        .iter()
+        .map(|phase| {
+            Line::from(vec![
+                Span::styled(
+                    format!("Phase {} {}: ", phase.phase, phase.name),
+                    Style::default().fg(app.theme.text),
+                ),
+                Span::styled(
+                    format!("~{} ", phase.queries),
+                    Style::default()
+                        .fg(app.theme.info)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!("({})", phase.basis), Style::default().fg(app.theme.dim)),
+            ])
+        })
+        .collect();
+
+    let title = format!(
+        " Estimate: ~{} Claude queries (heuristic, not a billing guarantee) ",
+        estimate.total_queries()
+    );
+    let paragraph =
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(title));
+    f.render_widget(paragraph, area);
+}
+
 pub fn render_workflow_running(f: &mut Frame, area: Rect, app: &App, idx: usize) {
     let workflow = match app.workflows.get(idx) {
         Some(w) => w,
@@ -335,6 +489,38 @@ pub fn render_workflow_running(f: &mut Frame, area: Rect, app: &App, idx: usize)
         }
     };
 
+    // Overall progress line above the panes, once there are phases
+    let summary = app
+        .workflow_phases
+        .lock()
+        .ok()
+        .filter(|phases| !phases.is_empty())
+        .map(|phases| ProgressSummary::from_phases(&phases));
+    let area = match &summary {
+        Some(summary) => {
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(1), Constraint::Min(0)])
+                .split(area);
+            render_progress_summary(f, rows[0], app, summary);
+            rows[1]
+        }
+        None => area,
+    };
+
+    // Phase timeline below the panes, once a phase has started
+    let area = match app.workflow_phases.lock() {
+        Ok(phases) if phases.iter().any(|phase| phase.started_at.is_some()) => {
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(1)])
+                .split(area);
+            render_phase_timeline(f, rows[1], app, &phases);
+            rows[0]
+        }
+        _ => area,
+    };
+
     // Split area into two vertical panes (50/50)
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
@@ -344,93 +530,92 @@ pub fn render_workflow_running(f: &mut Frame, area: Rect, app: &App, idx: usize)
     let left_pane = chunks[0];
     let right_pane = chunks[1];
 
+    // Collapsed-item previews fill whatever is left of the pane's inner width
+    let preview_width = left_pane.width.saturating_sub(2);
+
     // Build structured logs for left pane
     let mut structured_logs: Vec<Line> = Vec::new();
 
     // Display hierarchical phase/task/agent structure
-    let phases_snapshot: Vec<WorkflowPhase> = if let Ok(phases) = app.workflow_phases.lock() {
+    let mut phases_snapshot: Vec<WorkflowPhase> = if let Ok(phases) = app.workflow_phases.lock() {
         phases.clone()
     } else {
         Vec::new()
     };
+    filter_phase_messages(&mut phases_snapshot, app.log_filter);
 
     if !phases_snapshot.is_empty() {
         for phase in &phases_snapshot {
             // Phase header
-            let phase_icon = match phase.status {
-                PhaseStatus::NotStarted => "○",
-                PhaseStatus::Running => "▶",
-                PhaseStatus::Completed => "✓",
-                PhaseStatus::Failed => "✗",
-            };
-            let phase_color = match phase.status {
-                PhaseStatus::NotStarted => Color::Gray,
-                PhaseStatus::Running => Color::Yellow,
-                PhaseStatus::Completed => Color::White,
-                PhaseStatus::Failed => Color::Red,
-            };
+            let phase_icon = app.icons.phase_status(&phase.status);
+            let phase_color = app.theme.phase_status(&phase.status);
 
             let is_expanded = app.expanded_phases.contains(&phase.id);
-            let expand_icon = if is_expanded { "▼" } else { "▶" };
+            let expand_icon = app.icons.expand(is_expanded);
             let is_selected = app.selected_phase == phase.id
                 && app.selected_task.is_none()
-                && app.selected_agent.is_none();
+                && app.selected_agent.is_none()
+                && app.selected_file.is_none();
 
             let mut phase_spans = vec![
                 Span::styled(format!("{} ", phase_icon), Style::default().fg(phase_color)),
                 Span::styled(
                     format!("{} ", expand_icon),
-                    Style::default().fg(Color::White),
+                    Style::default().fg(app.theme.text),
                 ),
                 Span::styled(
                     format!("Phase {}: {}", phase.id, phase.name),
                     if is_selected {
                         Style::default()
-                            .fg(Color::White)
+                            .fg(app.theme.selected)
                             .add_modifier(Modifier::BOLD | Modifier::REVERSED)
                     } else {
                         Style::default()
-                            .fg(Color::White)
+                            .fg(app.theme.text)
                             .add_modifier(Modifier::BOLD)
                     },
                 ),
             ];
 
-            // Show last task message if collapsed
-            if !is_expanded && !phase.tasks.is_empty() {
-                if let Some(last_task) = phase.tasks.last() {
-                    if let Some(last_msg) = last_task.messages.last() {
-                        let preview = if last_msg.len() > 40 {
-                            format!(" - {}...", &last_msg[..40])
-                        } else {
-                            format!(" - {}", last_msg)
-                        };
-                        phase_spans
-                            .push(Span::styled(preview, Style::default().fg(Color::DarkGray)));
-                    }
+            if let Some(timing) = timing_label(phase.elapsed(), phase.eta(), app.icons) {
+                phase_spans.push(Span::styled(timing, Style::default().fg(app.theme.dim)));
+            }
+
+            // Show the error, or else the last task message, if collapsed
+            if !is_expanded {
+                let last_msg = phase.tasks.last().and_then(|task| task.messages.last());
+                if let Some(preview) = node_preview(
+                    &phase_spans,
+                    phase.error.as_deref(),
+                    last_msg.map(|msg| msg.text.as_str()),
+                    preview_width,
+                    &app.theme,
+                ) {
+                    phase_spans.push(preview);
                 }
             }
 
             structured_logs.push(Line::from(phase_spans));
 
             if is_expanded {
+                // Full error of a failed phase above its tasks
+                if let Some(error) = &phase.error {
+                    structured_logs.push(Line::from(vec![
+                        Span::raw("  "),
+                        Span::styled(
+                            format!("Error: {}", error),
+                            Style::default().fg(app.theme.failed),
+                        ),
+                    ]));
+                }
+
                 // Display tasks
                 for task in &phase.tasks {
-                    let task_icon = match task.status {
-                        TaskStatus::NotStarted => "○",
-                        TaskStatus::Running => "▶",
-                        TaskStatus::Completed => "✓",
-                        TaskStatus::Failed => "✗",
-                    };
-                    let task_color = match task.status {
-                        TaskStatus::NotStarted => Color::Gray,
-                        TaskStatus::Running => Color::Yellow,
-                        TaskStatus::Completed => Color::White,
-                        TaskStatus::Failed => Color::Red,
-                    };
+                    let task_icon = app.icons.task_status(&task.status);
+                    let task_color = app.theme.task_status(&task.status);
 
                     let task_expanded = app.expanded_tasks.contains(&task.id);
-                    let task_expand_icon = if task_expanded { "▼" } else { "▶" };
+                    let task_expand_icon = app.icons.expand(task_expanded);
                     let is_task_selected = app.selected_phase == phase.id
                         && Some(&task.id) == app.selected_task.as_ref()
                         && app.selected_agent.is_none();
@@ -440,30 +625,40 @@ pub fn render_workflow_running(f: &mut Frame, area: Rect, app: &App, idx: usize)
                         Span::styled(format!("{} ", task_icon), Style::default().fg(task_color)),
                         Span::styled(
                             format!("{} ", task_expand_icon),
-                            Style::default().fg(Color::White),
+                            Style::default().fg(app.theme.text),
                         ),
                         Span::styled(
                             &task.description,
                             if is_task_selected {
                                 Style::default()
-                                    .fg(Color::White)
+                                    .fg(app.theme.selected)
                                     .add_modifier(Modifier::REVERSED)
                             } else {
-                                Style::default().fg(Color::White)
+                                Style::default().fg(app.theme.text)
                             },
                         ),
                     ];
 
-                    // Show last message if collapsed
-                    if !task_expanded && !task.messages.is_empty() {
-                        if let Some(last_msg) = task.messages.last() {
-                            let preview = if last_msg.len() > 30 {
-                                format!(" - {}...", &last_msg[..30])
-                            } else {
-                                format!(" - {}", last_msg)
-                            };
-                            task_spans
-                                .push(Span::styled(preview, Style::default().fg(Color::DarkGray)));
+                    if let Some(progress) = task.progress {
+                        task_spans.push(Span::styled(
+                            format!(" {}", progress_gauge(progress, app.icons)),
+                            Style::default().fg(app.theme.info),
+                        ));
+                    }
+                    if let Some(timing) = timing_label(task.elapsed(), None, app.icons) {
+                        task_spans.push(Span::styled(timing, Style::default().fg(app.theme.dim)));
+                    }
+
+                    // Show the error, or else the last message, if collapsed
+                    if !task_expanded {
+                        if let Some(preview) = node_preview(
+                            &task_spans,
+                            task.error.as_deref(),
+                            task.messages.last().map(|msg| msg.text.as_str()),
+                            preview_width,
+                            &app.theme,
+                        ) {
+                            task_spans.push(preview);
                         }
                     }
 
@@ -474,59 +669,64 @@ pub fn render_workflow_running(f: &mut Frame, area: Rect, app: &App, idx: usize)
                         for msg in &task.messages {
                             structured_logs.push(Line::from(vec![
                                 Span::raw("    "),
-                                Span::styled(msg, Style::default().fg(Color::Gray)),
+                                Span::styled(msg.text.as_str(), tree_message_style(msg, &app.theme)),
                             ]));
                         }
 
                         // Display agents
                         for agent in &task.agents {
-                            let agent_icon = match agent.status {
-                                AgentStatus::NotStarted => "○",
-                                AgentStatus::Running => "▶",
-                                AgentStatus::Completed => "✓",
-                                AgentStatus::Failed => "✗",
-                            };
-                            let agent_color = match agent.status {
-                                AgentStatus::NotStarted => Color::Gray,
-                                AgentStatus::Running => Color::Yellow,
-                                AgentStatus::Completed => Color::White,
-                                AgentStatus::Failed => Color::Red,
-                            };
+                            // Agents started by other agents sit under them
+                            let nest = "  ".repeat(agent.depth);
+                            let agent_icon = app.icons.agent_status(&agent.status);
+                            let agent_color = app.theme.agent_status(&agent.status);
 
                             let agent_expanded = app.expanded_agents.contains(&agent.id);
-                            let agent_expand_icon = if agent_expanded { "▼" } else { "▶" };
+                            let agent_expand_icon = app.icons.expand(agent_expanded);
                             let is_agent_selected = Some(&agent.id) == app.selected_agent.as_ref();
 
-                            let agent_spans = vec![
-                                Span::raw("    "),
+                            let mut agent_spans = vec![
+                                Span::raw(format!("    {}", nest)),
                                 Span::styled(
                                     format!("{} ", agent_icon),
                                     Style::default().fg(agent_color),
                                 ),
                                 Span::styled(
                                     format!("{} ", agent_expand_icon),
-                                    Style::default().fg(Color::White),
+                                    Style::default().fg(app.theme.text),
                                 ),
                                 Span::styled(
                                     format!("@{}", agent.name),
                                     if is_agent_selected {
                                         Style::default()
-                                            .fg(Color::Magenta)
+                                            .fg(app.theme.agent)
                                             .add_modifier(Modifier::REVERSED)
                                     } else {
-                                        Style::default().fg(Color::Magenta)
+                                        Style::default().fg(app.theme.agent)
                                     },
                                 ),
                             ];
 
+                            // The error of a failed agent shows on its line
+                            if !agent_expanded {
+                                if let Some(preview) = node_preview(
+                                    &agent_spans,
+                                    agent.error.as_deref(),
+                                    None,
+                                    preview_width,
+                                    &app.theme,
+                                ) {
+                                    agent_spans.push(preview);
+                                }
+                            }
+
                             structured_logs.push(Line::from(agent_spans));
 
                             // Show last message in full detail if collapsed
                             if !agent_expanded && !agent.messages.is_empty() {
                                 if let Some(last_msg) = agent.messages.last() {
                                     structured_logs.push(Line::from(vec![
-                                        Span::raw("      "),
-                                        Span::styled(last_msg, Style::default().fg(Color::Gray)),
+                                        Span::raw(format!("      {}", nest)),
+                                        Span::styled(last_msg.text.as_str(), tree_message_style(last_msg, &app.theme)),
                                     ]));
                                 }
                             }
@@ -535,8 +735,8 @@ pub fn render_workflow_running(f: &mut Frame, area: Rect, app: &App, idx: usize)
                                 // Display agent messages
                                 for msg in &agent.messages {
                                     structured_logs.push(Line::from(vec![
-                                        Span::raw("      "),
-                                        Span::styled(msg, Style::default().fg(Color::Gray)),
+                                        Span::raw(format!("      {}", nest)),
+                                        Span::styled(msg.text.as_str(), tree_message_style(msg, &app.theme)),
                                     ]));
                                 }
                             }
@@ -548,14 +748,25 @@ pub fn render_workflow_running(f: &mut Frame, area: Rect, app: &App, idx: usize)
                 if !phase.output_files.is_empty() {
                     structured_logs.push(Line::from(vec![
                         Span::raw("  "),
-                        Span::styled("Output files:", Style::default().fg(Color::White)),
+                        Span::styled("Output files:", Style::default().fg(app.theme.text)),
                     ]));
-                    for (path, desc) in &phase.output_files {
+                    for (file_idx, (path, desc)) in phase.output_files.iter().enumerate() {
+                        let is_file_selected = app.selected_phase == phase.id
+                            && app.selected_file == Some(file_idx);
                         structured_logs.push(Line::from(vec![
                             Span::raw("    "),
-                            Span::styled(format!("📄 {}", path), Style::default().fg(Color::White)),
+                            Span::styled(
+                                format!("{} {}", app.icons.file, path),
+                                if is_file_selected {
+                                    Style::default()
+                                        .fg(app.theme.text)
+                                        .add_modifier(Modifier::REVERSED)
+                                } else {
+                                    Style::default().fg(app.theme.text)
+                                },
+                            ),
                             Span::raw(" - "),
-                            Span::styled(desc, Style::default().fg(Color::Gray)),
+                            Span::styled(desc, Style::default().fg(app.theme.secondary)),
                         ]));
                     }
                 }
@@ -569,32 +780,58 @@ pub fn render_workflow_running(f: &mut Frame, area: Rect, app: &App, idx: usize)
     let mut raw_output: Vec<Line> = Vec::new();
     if let Ok(output) = app.workflow_output.lock() {
         for line in output.iter() {
-            raw_output.push(Line::from(line.clone()));
+            if raw_line_visible(line, app.log_filter) {
+                raw_output.push(Line::from(line.text.clone()));
+            }
         }
     }
 
     // Determine focused pane styling
     let left_border_style = if app.workflow_focused_pane == WorkflowPane::StructuredLogs {
-        Style::default().fg(Color::White)
+        Style::default().fg(app.theme.text)
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(app.theme.dim)
     };
 
     let right_border_style = if app.workflow_focused_pane == WorkflowPane::RawOutput {
-        Style::default().fg(Color::White)
+        Style::default().fg(app.theme.text)
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(app.theme.dim)
     };
 
-    let left_title = format!(
-        " Structured Logs {} ",
+    let mut left_title = format!(
+        " Structured Logs {} {}",
         if app.workflow_running {
             "[IN PROGRESS]"
         } else {
             "[COMPLETED]"
-        }
+        },
+        if app.workflow_follow { "[FOLLOW] " } else { "" }
     );
 
+    if let Some(re) = &app.search {
+        let style = Style::default()
+            .fg(app.theme.highlight_text)
+            .bg(app.theme.highlight);
+        structured_logs = highlight_matches(structured_logs, re, style);
+    }
+
+    // Scrolled sideways, the title shows the first visible column
+    let inner_width = left_pane.width.saturating_sub(2);
+    let h_scroll = horizontal_offset(&structured_logs, app.workflow_h_scroll, inner_width);
+    if h_scroll > 0 {
+        left_title.push_str(&format!("[col {}] ", h_scroll + 1));
+    }
+
+    // Following keeps the end of the tree at the bottom of the pane
+    let scroll_offset = if app.workflow_follow {
+        structured_logs
+            .len()
+            .saturating_sub(left_pane.height.saturating_sub(2) as usize)
+    } else {
+        app.workflow_scroll_offset
+    };
+
     // Render left pane (Structured Logs)
     let left_paragraph = Paragraph::new(structured_logs)
         .block(
@@ -603,7 +840,7 @@ pub fn render_workflow_running(f: &mut Frame, area: Rect, app: &App, idx: usize)
                 .title(left_title)
                 .border_style(left_border_style),
         )
-        .scroll((app.workflow_scroll_offset as u16, 0));
+        .scroll((scroll_offset as u16, h_scroll));
 
     f.render_widget(left_paragraph, left_pane);
 
@@ -1,8 +1,11 @@
 //! Utility functions for workflow and history management
 
 use anyhow::Result;
-use std::path::PathBuf;
-use workflow_manager_sdk::{Workflow, WorkflowInfo, WorkflowSource, WorkflowStatus};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use workflow_manager_sdk::{
+    FieldSchema, FieldType, Workflow, WorkflowInfo, WorkflowLog, WorkflowStatus,
+};
 
 use crate::models::WorkflowHistory;
 
@@ -17,14 +20,16 @@ pub fn history_file_path() -> PathBuf {
     }
 }
 
-/// Load workflow history from disk
+/// Load workflow history from disk, deduplicated and capped
 pub fn load_history() -> WorkflowHistory {
     let path = history_file_path();
-    if let Ok(content) = std::fs::read_to_string(&path) {
+    let mut history = if let Ok(content) = std::fs::read_to_string(&path) {
         serde_json::from_str(&content).unwrap_or_default()
     } else {
         WorkflowHistory::default()
-    }
+    };
+    history.normalize();
+    history
 }
 
 /// Save workflow history to disk
@@ -34,7 +39,19 @@ pub fn save_history(history: &WorkflowHistory) -> Result<()> {
         std::fs::create_dir_all(parent)?;
     }
     let content = serde_json::to_string_pretty(history)?;
-    std::fs::write(path, content)?;
+    write_atomically(&path, &content)
+}
+
+/// Write `content` to a temp file next to `path`, then rename it over `path`
+///
+/// A crash mid-write leaves the previous file intact instead of a truncated
+/// one.
+pub fn write_atomically(path: &Path, content: &str) -> Result<()> {
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+    std::fs::write(&tmp_path, content)?;
+    std::fs::rename(&tmp_path, path)?;
     Ok(())
 }
 
@@ -47,6 +64,7 @@ pub fn load_workflows() -> Vec<Workflow> {
         crate::discovery::discover_workflows()
             .into_iter()
             .map(|dw| Workflow {
+                source: dw.source(),
                 info: WorkflowInfo {
                     id: dw.metadata.id.clone(),
                     name: dw.metadata.name.clone(),
@@ -56,9 +74,177 @@ pub fn load_workflows() -> Vec<Workflow> {
                     fields: dw.fields,
                     progress_messages: vec![],
                 },
-                source: WorkflowSource::BuiltIn,
             }),
     );
 
     workflows
 }
+
+/// Shown instead of secret values in raw output and command previews
+pub const SECRET_MASK: &str = "****";
+
+/// Whether `field` holds a secret that must not be shown or written to disk
+pub fn is_secret(field: &FieldSchema) -> bool {
+    matches!(field.field_type, FieldType::Secret)
+}
+
+/// Environment variable a secret field is passed in, instead of its CLI flag
+/// where it would be visible to other users in the process list
+///
+/// The variable the field defaults from (clap's `env`) when it has one,
+/// otherwise `WF_SECRET_<NAME>`.
+pub fn secret_env_var(field: &FieldSchema) -> String {
+    field
+        .default_env
+        .clone()
+        .unwrap_or_else(|| format!("WF_SECRET_{}", field.name.to_uppercase()))
+}
+
+/// `values` without the secret fields, for anything persisted
+pub fn without_secrets(
+    fields: &[FieldSchema],
+    values: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    values
+        .iter()
+        .filter(|(name, _)| !fields.iter().any(|f| &f.name == *name && is_secret(f)))
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect()
+}
+
+/// Non-empty values of the secret fields, longest first so that a secret
+/// containing another one is masked whole
+pub fn secret_values(fields: &[FieldSchema], values: &HashMap<String, String>) -> Vec<String> {
+    let mut secrets: Vec<String> = fields
+        .iter()
+        .filter(|f| is_secret(f))
+        .filter_map(|f| values.get(&f.name))
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .collect();
+    secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+    secrets
+}
+
+/// `line` with every secret replaced by [`SECRET_MASK`]
+pub fn redact_secrets(line: &str, secrets: &[String]) -> String {
+    secrets
+        .iter()
+        .fold(line.to_string(), |line, secret| line.replace(secret.as_str(), SECRET_MASK))
+}
+
+/// `log` with every secret replaced by [`SECRET_MASK`] in all of its text,
+/// including the fields of structured events
+///
+/// An event that no longer parses once masked (a secret inside one of its
+/// enum values) is kept as masked raw output instead.
+pub fn redact_log(log: WorkflowLog, secrets: &[String]) -> WorkflowLog {
+    if secrets.is_empty() {
+        return log;
+    }
+    let Ok(mut value) = serde_json::to_value(&log) else {
+        return log;
+    };
+    redact_value(&mut value, secrets);
+    serde_json::from_value(value.clone()).unwrap_or_else(|_| WorkflowLog::RawOutput {
+        stream: "stderr".to_string(),
+        line: value.to_string(),
+    })
+}
+
+fn redact_value(value: &mut serde_json::Value, secrets: &[String]) {
+    match value {
+        serde_json::Value::String(text) => *text = redact_secrets(text, secrets),
+        serde_json::Value::Array(items) => {
+            items.iter_mut().for_each(|item| redact_value(item, secrets))
+        }
+        serde_json::Value::Object(fields) => {
+            fields.values_mut().for_each(|field| redact_value(field, secrets))
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secrets_are_dropped_and_redacted() {
+        let fields = vec![
+            FieldSchema::new("input", FieldType::Text),
+            FieldSchema::new("api_key", FieldType::Secret),
+        ];
+        let values: HashMap<String, String> = [("input", "hello"), ("api_key", "sk-123")]
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+
+        let kept = without_secrets(&fields, &values);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept["input"], "hello");
+
+        let secrets = secret_values(&fields, &values);
+        assert_eq!(
+            redact_secrets("--api-key sk-123 --input hello", &secrets),
+            "--api-key **** --input hello"
+        );
+
+        let log = WorkflowLog::TaskProgress {
+            task_id: "fetch".to_string(),
+            message: "using key sk-123".to_string(),
+        };
+        match redact_log(log, &secrets) {
+            WorkflowLog::TaskProgress { message, .. } => assert_eq!(message, "using key ****"),
+            other => panic!("unexpected event {:?}", other),
+        }
+        assert_eq!(secret_env_var(&fields[1]), "WF_SECRET_API_KEY");
+    }
+
+    #[test]
+    fn test_history_is_deduplicated_and_capped() {
+        let fields = vec![
+            FieldSchema::new("input", FieldType::Text),
+            FieldSchema::new("api_key", FieldType::Secret),
+        ];
+        let run = |input: &str| -> HashMap<String, String> {
+            [("input", input), ("api_key", "sk-123"), ("unknown", "x")]
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+
+        let mut history = WorkflowHistory::default();
+        for i in 0..60 {
+            history.record("research", &fields, &run(&format!("q{}", i)));
+        }
+        history.record("research", &fields, &run("q30"));
+        history.record("research", &fields, &run(""));
+        let values = history.values("research", "input");
+        assert_eq!(values.len(), crate::app::MAX_VALUES_PER_FIELD);
+        assert_eq!(values[..2], ["q30", "q59"]);
+        assert_eq!(values.iter().filter(|v| *v == "q30").count(), 1);
+        assert!(history.values("research", "api_key").is_empty());
+        assert!(history.values("research", "unknown").is_empty());
+
+        // Files written before the cap may repeat values and hold secrets
+        let mut old = WorkflowHistory::default();
+        old.workflows.entry("research".to_string()).or_default().extend([
+            ("input".to_string(), vec!["a".into(), "".into(), "b".into(), "a".into()]),
+            ("api_key".to_string(), vec!["sk-123".into()]),
+        ]);
+        old.normalize();
+        old.forget_secrets("research", &fields);
+        assert_eq!(old.values("research", "input"), ["a", "b"]);
+        assert!(!old.workflows["research"].contains_key("api_key"));
+
+        assert_eq!(old.clear_field("research", "input"), 2);
+        assert!(old.workflows.is_empty());
+
+        let path = std::env::temp_dir().join(format!("history_{}.json", uuid::Uuid::new_v4()));
+        write_atomically(&path, "{}").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{}");
+        assert!(!path.with_extension("json.tmp").exists());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,10 +1,21 @@
 //! Agent execution utilities with stream handling and sub-agent detection
 
-use anyhow::Result;
-use claude_agent_sdk::{query, ClaudeAgentOptions, ContentBlock, Message};
+use anyhow::{anyhow, Result};
+use claude_agent_sdk::{query, ClaudeAgentOptions, ClaudeSDKClient, ContentBlock, Message};
 use futures::{Stream, StreamExt};
 use std::collections::HashMap;
-use workflow_manager_sdk::{log_agent_complete, log_agent_failed, log_agent_message, log_agent_start};
+use tokio_util::sync::CancellationToken;
+use workflow_manager_sdk::{
+    log_agent_complete, log_agent_failed, log_agent_message, log_agent_start, WorkflowLog,
+};
+
+use super::batch::task_cancellation;
+use super::mcp::SharedMcpServers;
+use super::retry::{backoff_delay, is_retryable, RetryBudget};
+
+/// Times [`execute_agent`] re-runs a query that failed before producing any
+/// content, unless the config sets another limit
+pub const DEFAULT_AGENT_RETRIES: usize = 2;
 
 /// Configuration for agent execution
 pub struct AgentConfig {
@@ -18,6 +29,13 @@ pub struct AgentConfig {
     pub prompt: String,
     /// Claude agent options (system prompt, tools, sub-agents, etc.)
     pub options: ClaudeAgentOptions,
+    /// Shared MCP servers merged into `options` when the agent runs
+    pub mcp_servers: SharedMcpServers,
+    /// Retries of a query whose CLI process failed before any content
+    pub max_retries: usize,
+    /// Stops the query, interrupting and closing its CLI process; defaults
+    /// to the token of the enclosing cancellable task, if any
+    pub cancellation: Option<CancellationToken>,
 }
 
 impl AgentConfig {
@@ -35,7 +53,44 @@ impl AgentConfig {
             description: description.into(),
             prompt: prompt.into(),
             options,
+            mcp_servers: SharedMcpServers::default(),
+            max_retries: DEFAULT_AGENT_RETRIES,
+            cancellation: None,
+        }
+    }
+
+    /// Allow `max_retries` retries of a query that fails before any content
+    /// (0 turns them off)
+    pub fn with_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Stop the agent when `token` is cancelled
+    ///
+    /// The CLI is sent an interrupt and its process closed, instead of the
+    /// query stream merely being dropped.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Give the agent the servers of `servers`, typically a registry built
+    /// once by the workflow and passed to each of its agents
+    pub fn with_mcp_servers(mut self, servers: &SharedMcpServers) -> Self {
+        self.mcp_servers = servers.clone();
+        self
+    }
+
+    /// Run the agent on `model` instead of the one set in its options
+    ///
+    /// `None` keeps the options as they are, so callers can pass a
+    /// configured model straight through.
+    pub fn with_model(mut self, model: Option<&str>) -> Self {
+        if let Some(model) = model {
+            self.options.model = Some(model.to_string());
         }
+        self
     }
 }
 
@@ -43,39 +98,68 @@ impl AgentConfig {
 ///
 /// Handles:
 /// - Agent start/complete/failed logging
+/// - Injection of shared MCP servers (see [`SharedMcpServers`])
 /// - Stream processing with TUI logging
 /// - Sub-agent delegation detection
 /// - Text, tool use, and tool result logging
+/// - Retrying a query whose CLI process fails (e.g. the pipe closes) before
+///   any assistant content arrived, up to `config.max_retries` times with
+///   exponential backoff, drawn from the run-level [`RetryBudget::global`].
+///   A failure after content was streamed is not retried, so partial work is
+///   never duplicated.
+/// - Cancellation through `config.cancellation` or the token of the task
+///   it runs in (see [`task_cancellation`]): the CLI is interrupted, its
+///   process closed and an error returned.
 ///
 /// Returns the full response text collected from all Text blocks.
 ///
 /// # Example
 /// ```rust
-/// let config = AgentConfig {
-///     task_id: "research_1".to_string(),
-///     agent_name: "Research Agent".to_string(),
-///     description: "Researching authentication".to_string(),
-///     prompt: "How does authentication work?".to_string(),
-///     options: ClaudeAgentOptions::builder()
+/// let config = AgentConfig::new(
+///     "research_1",
+///     "Research Agent",
+///     "Researching authentication",
+///     "How does authentication work?",
+///     ClaudeAgentOptions::builder()
 ///         .system_prompt("You are a researcher...")
 ///         .build(),
-/// };
+/// );
 ///
 /// let response = execute_agent(config).await?;
 /// ```
 pub async fn execute_agent(config: AgentConfig) -> Result<String> {
     log_agent_start!(&config.task_id, &config.agent_name, &config.description);
 
-    // Query Claude
-    let stream = query(&config.prompt, Some(config.options))
-        .await
-        .map_err(|e| {
-            log_agent_failed!(&config.task_id, &config.agent_name, e.to_string());
-            e
-        })?;
+    let mut options = config.options;
+    config.mcp_servers.apply(&mut options);
+    let cancellation = config.cancellation.or_else(task_cancellation);
+
+    let attempts = retry_until_streamed(
+        &config.task_id,
+        &config.agent_name,
+        config.max_retries,
+        RetryBudget::global(),
+        || async {
+            match &cancellation {
+                Some(token) => {
+                    query_cancellable(
+                        &config.prompt,
+                        options.clone(),
+                        &config.task_id,
+                        &config.agent_name,
+                        token,
+                    )
+                    .await
+                }
+                None => {
+                    query_once(&config.prompt, options.clone(), &config.task_id, &config.agent_name)
+                        .await
+                }
+            }
+        },
+    );
 
-    // Handle stream
-    match handle_stream(stream, &config.task_id, &config.agent_name).await {
+    match attempts.await {
         Ok(response) => {
             log_agent_complete!(&config.task_id, &config.agent_name, "Completed");
             Ok(response)
@@ -87,6 +171,112 @@ pub async fn execute_agent(config: AgentConfig) -> Result<String> {
     }
 }
 
+/// Run one query, returning its response and whether any assistant content
+/// was streamed before it ended
+///
+/// Dropping the stream on failure ends the CLI process of the query.
+async fn query_once(
+    prompt: &str,
+    options: ClaudeAgentOptions,
+    task_id: &str,
+    agent_name: &str,
+) -> (Result<String>, bool) {
+    let stream = match query(prompt, Some(options)).await {
+        Ok(stream) => stream,
+        Err(e) => return (Err(e.into()), false),
+    };
+    let mut streamed = false;
+    let response = handle_stream(stream, task_id, agent_name, &mut streamed).await;
+    (response, streamed)
+}
+
+/// Like [`query_once`], but stopped by `token`
+///
+/// The query runs on a [`ClaudeSDKClient`] so that on cancellation the CLI
+/// can be sent an interrupt and its transport closed, which reaps the
+/// process; dropping a [`query`] stream does not reliably do that.
+async fn query_cancellable(
+    prompt: &str,
+    options: ClaudeAgentOptions,
+    task_id: &str,
+    agent_name: &str,
+    token: &CancellationToken,
+) -> (Result<String>, bool) {
+    let cancelled = || anyhow!("Agent '{}' was cancelled", agent_name);
+    if token.is_cancelled() {
+        return (Err(cancelled()), false);
+    }
+
+    let client = match ClaudeSDKClient::new(options, None).await {
+        Ok(client) => tokio::sync::Mutex::new(client),
+        Err(e) => return (Err(e.into()), false),
+    };
+    if let Err(e) = client.lock().await.send_message(prompt).await {
+        let _ = client.lock().await.close().await;
+        return (Err(e.into()), false);
+    }
+
+    // The client stays connected after the turn, so its result ends the query
+    let stream = futures::stream::unfold(Some(&client), |client| async move {
+        let client = client?;
+        let message = client.lock().await.next_message().await?;
+        let next = match &message {
+            Ok(Message::Result { .. }) => None,
+            _ => Some(client),
+        };
+        Some((message, next))
+    });
+    let mut streamed = false;
+    let response = tokio::select! {
+        response = handle_stream(stream, task_id, agent_name, &mut streamed) => Some(response),
+        () = token.cancelled() => None,
+    };
+
+    let mut client = client.lock().await;
+    let response = match response {
+        Some(response) => response,
+        None => {
+            let _ = client.interrupt().await;
+            Err(cancelled())
+        }
+    };
+    let _ = client.close().await;
+    (response, streamed)
+}
+
+/// Run `attempt` until it succeeds, retrying recoverable errors (see
+/// [`is_retryable`]) of attempts that streamed nothing
+async fn retry_until_streamed<F, Fut>(
+    task_id: &str,
+    agent_name: &str,
+    max_retries: usize,
+    budget: &RetryBudget,
+    mut attempt: F,
+) -> Result<String>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = (Result<String>, bool)>,
+{
+    let mut retry = 0;
+    loop {
+        match attempt().await {
+            (Ok(response), _) => return Ok(response),
+            (Err(e), false)
+                if retry < max_retries && is_retryable(&e) && budget.try_acquire(task_id) =>
+            {
+                retry += 1;
+                log_agent_message!(
+                    task_id,
+                    agent_name,
+                    format!("⟳ Retry {}/{} after error: {}", retry, max_retries, e)
+                );
+                tokio::time::sleep(backoff_delay(retry)).await;
+            }
+            (Err(e), _) => return Err(e),
+        }
+    }
+}
+
 /// Tracks active sub-agent delegations
 struct DelegationTracker {
     active: HashMap<String, String>,
@@ -143,6 +333,14 @@ fn extract_subagent_name(input: &serde_json::Value) -> Option<String> {
     None
 }
 
+/// Extract `(input_tokens, output_tokens)` from a result message's usage object
+///
+/// Missing or malformed counts are read as zero.
+fn extract_usage_tokens(usage: &serde_json::Value) -> (u64, u64) {
+    let count = |key: &str| usage.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+    (count("input_tokens"), count("output_tokens"))
+}
+
 /// Extract detailed information from tool use for better logging
 ///
 /// Returns a formatted string with tool-specific details extracted from input JSON
@@ -263,33 +461,105 @@ fn extract_tool_details(tool_name: &str, input: &serde_json::Value) -> String {
     }
 }
 
+/// Event of an agent's response stream
+#[derive(Debug)]
+pub enum StreamEvent {
+    /// Text generated since the previous event, sent while a message is
+    /// still being written when `include_partial_messages` is set
+    PartialText { text: String },
+    /// A complete message
+    Message(Message),
+}
+
+/// Text delta of a raw `stream_event`, if it carries one
+fn partial_text(event: &serde_json::Value) -> Option<&str> {
+    if event.get("type")?.as_str()? != "content_block_delta" {
+        return None;
+    }
+    let delta = event.get("delta")?;
+    if delta.get("type")?.as_str()? != "text_delta" {
+        return None;
+    }
+    delta.get("text")?.as_str()
+}
+
+/// Turn the raw stream events of a query into [`StreamEvent::PartialText`]
+/// deltas, passing every other message through
+///
+/// Stream events other than text deltas (message and block boundaries,
+/// tool input deltas) are dropped.
+pub fn with_partial_text(
+    stream: impl Stream<Item = claude_agent_sdk::error::Result<Message>>,
+) -> impl Stream<Item = claude_agent_sdk::error::Result<StreamEvent>> {
+    stream.filter_map(|message| async move {
+        match message {
+            Ok(Message::StreamEvent { event, .. }) => partial_text(&event)
+                .map(|text| Ok(StreamEvent::PartialText { text: text.to_string() })),
+            message => Some(message.map(StreamEvent::Message)),
+        }
+    })
+}
+
 /// Handle agent stream - logs everything to TUI and stdout, collects text
 ///
 /// Features:
 /// - Logs all text content to TUI and prints to stdout
+/// - Logs partial text line by line as it arrives, when the query streams it
 /// - Detects and logs sub-agent delegations (Task tool with @agent)
 /// - Logs tool usage
 /// - Tracks tool results and matches them to delegations
 /// - Returns full response text
+///
+/// `streamed` is set once any assistant content arrives.
 async fn handle_stream(
     stream: impl Stream<Item = claude_agent_sdk::error::Result<Message>>,
     task_id: &str,
     agent_name: &str,
+    streamed: &mut bool,
 ) -> Result<String> {
     let mut response_text = String::new();
-    let mut stream = Box::pin(stream);
+    let mut stream = Box::pin(with_partial_text(stream));
     let mut delegations = DelegationTracker::new();
-
-    while let Some(message) = stream.next().await {
-        match message? {
+    // Streamed text of the current message, logged up to its last newline
+    let mut partial = String::new();
+    let mut logged = 0;
+
+    while let Some(event) = stream.next().await {
+        let message = match event? {
+            StreamEvent::PartialText { text } => {
+                *streamed = true;
+                partial.push_str(&text);
+                if let Some(end) = partial.rfind('\n').filter(|&end| end >= logged) {
+                    for line in partial[logged..end].lines() {
+                        log_agent_message!(task_id, agent_name, line);
+                    }
+                    logged = end + 1;
+                }
+                continue;
+            }
+            StreamEvent::Message(message) => message,
+        };
+        match message {
             Message::Assistant { message, .. } => {
+                *streamed = true;
                 for block in &message.content {
                     match block {
                         ContentBlock::Text { text } => {
                             // Print to stdout
                             println!("{}", text);
-                            // Log to TUI
-                            log_agent_message!(task_id, agent_name, text);
+                            // Log to TUI, leaving out the lines already streamed
+                            if partial.starts_with(text.as_str()) {
+                                let streamed = logged.min(text.len());
+                                if streamed < text.len() {
+                                    log_agent_message!(task_id, agent_name, &text[streamed..]);
+                                }
+                                partial.drain(..text.len());
+                                logged -= streamed;
+                            } else {
+                                log_agent_message!(task_id, agent_name, text);
+                                partial.clear();
+                                logged = 0;
+                            }
                             // Collect for return
                             response_text.push_str(text);
                         }
@@ -329,7 +599,22 @@ async fn handle_stream(
                     }
                 }
             }
-            Message::Result { .. } => break,
+            Message::Result {
+                usage,
+                total_cost_usd,
+                ..
+            } => {
+                // Report this query's usage so the runtime can total it per execution
+                let usage = serde_json::to_value(&usage).unwrap_or_default();
+                let (input_tokens, output_tokens) = extract_usage_tokens(&usage);
+                WorkflowLog::Usage {
+                    input_tokens,
+                    output_tokens,
+                    cost_usd: total_cost_usd,
+                }
+                .emit();
+                break;
+            }
             _ => {}
         }
     }
@@ -386,6 +671,62 @@ mod tests {
         assert_eq!(extract_subagent_name(&input), None);
     }
 
+    fn transport_error() -> anyhow::Error {
+        std::io::Error::new(std::io::ErrorKind::BrokenPipe, "pipe closed").into()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retries_queries_that_streamed_nothing() {
+        let budget = RetryBudget::new(10);
+        let mut calls = 0;
+        let response = retry_until_streamed("task", "agent", 2, &budget, || {
+            calls += 1;
+            let result = if calls < 3 {
+                Err(transport_error())
+            } else {
+                Ok("done".to_string())
+            };
+            async move { (result, false) }
+        })
+        .await;
+        assert_eq!(response.unwrap(), "done");
+        assert_eq!(calls, 3);
+        assert_eq!(budget.used(), 2);
+
+        // Out of retries
+        let mut calls = 0;
+        let response = retry_until_streamed("task", "agent", 1, &budget, || {
+            calls += 1;
+            async { (Err(transport_error()), false) }
+        })
+        .await;
+        assert!(response.is_err());
+        assert_eq!(calls, 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_no_retry_after_content_or_for_other_errors() {
+        let budget = RetryBudget::new(10);
+        let mut calls = 0;
+        let response = retry_until_streamed("task", "agent", 2, &budget, || {
+            calls += 1;
+            async { (Err(transport_error()), true) }
+        })
+        .await;
+        assert!(response.is_err());
+        assert_eq!(calls, 1);
+
+        let mut calls = 0;
+        let response = retry_until_streamed("task", "agent", 2, &budget, || {
+            calls += 1;
+            async { (Err(anyhow::anyhow!("invalid YAML")), false) }
+        })
+        .await;
+        assert!(response.is_err());
+        assert_eq!(calls, 1);
+        assert_eq!(budget.used(), 0);
+    }
+
     #[test]
     fn test_delegation_tracker() {
         let mut tracker = DelegationTracker::new();
@@ -398,6 +739,17 @@ mod tests {
         assert_eq!(tracker.complete_delegation("tool3"), None);
     }
 
+    #[test]
+    fn test_extract_usage_tokens() {
+        let usage = serde_json::json!({
+            "input_tokens": 1200,
+            "output_tokens": 340,
+            "cache_read_input_tokens": 50
+        });
+        assert_eq!(extract_usage_tokens(&usage), (1200, 340));
+        assert_eq!(extract_usage_tokens(&serde_json::Value::Null), (0, 0));
+    }
+
     #[test]
     fn test_extract_tool_details_read() {
         let input = serde_json::json!({
@@ -486,6 +838,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_partial_text() {
+        let delta = serde_json::json!({
+            "type": "content_block_delta",
+            "index": 0,
+            "delta": {"type": "text_delta", "text": "Hello"}
+        });
+        assert_eq!(partial_text(&delta), Some("Hello"));
+
+        let tool_input = serde_json::json!({
+            "type": "content_block_delta",
+            "delta": {"type": "input_json_delta", "partial_json": "{\"a\""}
+        });
+        assert_eq!(partial_text(&tool_input), None);
+        assert_eq!(partial_text(&serde_json::json!({"type": "message_stop"})), None);
+    }
+
     #[test]
     fn test_extract_tool_details_unknown_tool() {
         let input = serde_json::json!({});
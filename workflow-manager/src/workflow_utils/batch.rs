@@ -1,10 +1,23 @@
 //! Batch execution utilities for parallel task processing
+//!
+//! Single tasks of a batch can be cancelled from the TUI: the runtime writes
+//! [`WorkflowControl`] messages to the workflow's stdin. Workflows opt in by
+//! calling [`listen_for_control`] at startup; tasks run through
+//! [`execute_task`](super::task::execute_task) then stop when cancelled and
+//! the rest of the batch carries on. Agents of a cancelled task interrupt
+//! their Claude CLI process through the task's [`CancellationToken`].
 
 use anyhow::{anyhow, Result};
 use futures::{stream::FuturesUnordered, Future, StreamExt};
+use std::fmt;
 use std::pin::Pin;
-use std::sync::Arc;
-use tokio::sync::Semaphore;
+use std::collections::HashSet;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::{watch, Semaphore};
+use tokio_util::sync::CancellationToken;
+use workflow_manager_sdk::WorkflowControl;
 
 /// Context provided to each task in a batch
 #[derive(Debug, Clone, Copy)]
@@ -15,6 +28,117 @@ pub struct TaskContext {
     pub task_number: usize,
     /// Total number of tasks in this batch
     pub total_tasks: usize,
+    /// Retries allowed on recoverable errors, honored by
+    /// [`execute_task_with_retry`](super::task::execute_task_with_retry)
+    pub max_retries: usize,
+}
+
+/// Task retries used by phases that opt into [`execute_batch_with_retries`]
+pub const DEFAULT_TASK_RETRIES: usize = 2;
+
+/// Error of a task cancelled through a control message; batches drop such
+/// tasks from their results instead of failing
+#[derive(Debug)]
+pub struct TaskCancelled(pub String);
+
+impl fmt::Display for TaskCancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Task '{}' was cancelled", self.0)
+    }
+}
+
+impl std::error::Error for TaskCancelled {}
+
+/// Time a cancelled task gets to stop its agents before it is dropped
+const CANCEL_GRACE: Duration = Duration::from_secs(5);
+
+tokio::task_local! {
+    /// Token of the task run by [`run_cancellable`] on this tokio task
+    static TASK_CANCELLATION: CancellationToken;
+}
+
+/// Token cancelled when the task running the caller is cancelled, if the
+/// caller runs inside [`run_cancellable`]
+pub fn task_cancellation() -> Option<CancellationToken> {
+    TASK_CANCELLATION.try_with(CancellationToken::clone).ok()
+}
+
+/// Ids of the tasks cancelled so far, as they arrive on stdin
+///
+/// A set rather than a stream of ids, so a task that starts after its
+/// cancellation arrived (e.g. one still waiting for a batch slot) sees it.
+fn cancelled_tasks() -> &'static watch::Sender<HashSet<String>> {
+    static CANCELLED: OnceLock<watch::Sender<HashSet<String>>> = OnceLock::new();
+    CANCELLED.get_or_init(|| watch::Sender::new(HashSet::new()))
+}
+
+/// Mark task `task_id` as cancelled
+fn cancel_task(task_id: String) {
+    cancelled_tasks().send_modify(|cancelled| {
+        cancelled.insert(task_id);
+    });
+}
+
+/// Read control messages from stdin for the rest of the run
+///
+/// Call once from within the tokio runtime, before the batches start. Lines
+/// that are not control messages are ignored.
+pub fn listen_for_control() {
+    tokio::spawn(async {
+        let mut lines = BufReader::new(tokio::io::stdin()).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if let Ok(WorkflowControl::CancelTask(task_id)) = serde_json::from_str(&line) {
+                cancel_task(task_id);
+            }
+        }
+    });
+}
+
+/// Run `future` unless task `task_id` is cancelled first, in which case
+/// [`TaskCancelled`] is returned
+///
+/// On cancellation the token seen by [`task_cancellation`] inside `future`
+/// is cancelled, and `future` gets [`CANCEL_GRACE`] to stop its agents
+/// before it is dropped.
+pub async fn run_cancellable<R>(
+    task_id: &str,
+    future: impl Future<Output = Result<R>>,
+) -> Result<R> {
+    let token = CancellationToken::new();
+    let future = TASK_CANCELLATION.scope(token.clone(), future);
+    tokio::pin!(future);
+
+    let mut cancellations = cancelled_tasks().subscribe();
+    let cancelled = cancellations.wait_for(|cancelled| cancelled.contains(task_id));
+
+    tokio::select! {
+        // Checked first, so a task cancelled before it started never runs
+        biased;
+        // The sender is static and never closes, so this only returns once
+        // the task is cancelled
+        _ = cancelled => {}
+        result = &mut future => return result,
+    }
+
+    token.cancel();
+    let _ = tokio::time::timeout(CANCEL_GRACE, future).await;
+    Err(TaskCancelled(task_id.to_string()).into())
+}
+
+/// Keep the results of a batch, skipping cancelled tasks and failing on the
+/// first other error
+async fn collect_results<R>(
+    mut tasks: impl futures::Stream<Item = Result<R>> + Unpin,
+) -> Result<Vec<R>> {
+    let mut results = Vec::new();
+    while let Some(result) = tasks.next().await {
+        match result {
+            Ok(result) => results.push(result),
+            Err(e) if e.is::<TaskCancelled>() => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(results)
 }
 
 /// Execute items in parallel batches with concurrency control
@@ -29,7 +153,8 @@ pub struct TaskContext {
 /// Vector of results in order of completion (not input order)
 ///
 /// # Error Handling
-/// Fails fast - if any task fails, execution stops and error is returned
+/// Fails fast - if any task fails, execution stops and error is returned.
+/// Tasks cancelled through a control message are left out of the results.
 ///
 /// # Example
 /// ```rust
@@ -49,6 +174,30 @@ pub async fn execute_batch<T, F, Fut, R>(
     batch_size: usize,
     task_executor: F,
 ) -> Result<Vec<R>>
+where
+    T: Send + 'static,
+    R: Send + 'static,
+    F: Fn(T, TaskContext) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<R>> + Send + 'static,
+{
+    execute_batch_with_retries(phase, items, batch_size, 0, task_executor).await
+}
+
+/// Execute items in parallel batches, allowing each task `max_retries`
+/// retries on recoverable errors
+///
+/// The limit is passed to each task through [`TaskContext::max_retries`];
+/// tasks run through
+/// [`execute_task_with_retry`](super::task::execute_task_with_retry) retry
+/// transport and timeout errors with exponential backoff and fail
+/// immediately on anything else. Otherwise behaves like [`execute_batch`].
+pub async fn execute_batch_with_retries<T, F, Fut, R>(
+    phase: usize,
+    items: Vec<T>,
+    batch_size: usize,
+    max_retries: usize,
+    task_executor: F,
+) -> Result<Vec<R>>
 where
     T: Send + 'static,
     R: Send + 'static,
@@ -58,7 +207,7 @@ where
     let total = items.len();
     let sem = Arc::new(Semaphore::new(batch_size));
     let executor = Arc::new(task_executor);
-    let mut tasks = FuturesUnordered::new();
+    let tasks = FuturesUnordered::new();
 
     // Push all tasks to FuturesUnordered with semaphore control
     for (idx, item) in items.into_iter().enumerate() {
@@ -70,6 +219,7 @@ where
             phase,
             task_number: task_num,
             total_tasks: total,
+            max_retries,
         };
 
         tasks.push(async move {
@@ -85,21 +235,18 @@ where
     }
 
     // Collect results as they complete (fail-fast on first error)
-    let mut results = Vec::new();
-    while let Some(result) = tasks.next().await {
-        results.push(result?);
-    }
-
-    Ok(results)
+    collect_results(tasks).await
 }
 
 /// Execute items in parallel batches (boxed future version for complex closures)
 ///
 /// Use this variant when the compiler has trouble inferring types with complex closures.
+/// Each task is allowed `max_retries` retries, as in [`execute_batch_with_retries`].
 pub async fn execute_batch_boxed<T, R, F>(
     phase: usize,
     items: Vec<T>,
     batch_size: usize,
+    max_retries: usize,
     task_executor: F,
 ) -> Result<Vec<R>>
 where
@@ -107,38 +254,7 @@ where
     R: Send + 'static,
     F: Fn(T, TaskContext) -> Pin<Box<dyn Future<Output = Result<R>> + Send>> + Send + Sync + 'static,
 {
-    let total = items.len();
-    let sem = Arc::new(Semaphore::new(batch_size));
-    let executor = Arc::new(task_executor);
-    let mut tasks = FuturesUnordered::new();
-
-    for (idx, item) in items.into_iter().enumerate() {
-        let sem = sem.clone();
-        let executor = executor.clone();
-        let task_num = idx + 1;
-
-        let ctx = TaskContext {
-            phase,
-            task_number: task_num,
-            total_tasks: total,
-        };
-
-        tasks.push(async move {
-            let _permit = sem
-                .acquire()
-                .await
-                .map_err(|_| anyhow!("Semaphore closed"))?;
-
-            executor(item, ctx).await
-        });
-    }
-
-    let mut results = Vec::new();
-    while let Some(result) = tasks.next().await {
-        results.push(result?);
-    }
-
-    Ok(results)
+    execute_batch_with_retries(phase, items, batch_size, max_retries, task_executor).await
 }
 
 #[cfg(test)]
@@ -156,6 +272,7 @@ mod tests {
             |item, ctx| async move {
                 assert!(ctx.task_number >= 1 && ctx.task_number <= 5);
                 assert_eq!(ctx.total_tasks, 5);
+                assert_eq!(ctx.max_retries, 0);
                 Ok(item * 2)
             }
         ).await.unwrap();
@@ -186,4 +303,51 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Failed at 3"));
     }
+
+    #[tokio::test]
+    async fn test_cancelled_task_leaves_batch_running() {
+        // Task 2 of the batch waits for a slot, and is cancelled before that
+        let batch = execute_batch(1, vec![1, 2, 3], 1, |item, _ctx| async move {
+            run_cancellable(&format!("stuck_{}", item), async move {
+                if item == 1 {
+                    cancel_task("stuck_2".to_string());
+                }
+                if item == 2 {
+                    std::future::pending::<()>().await;
+                }
+                Ok(item * 2)
+            })
+            .await
+        });
+        let mut results = tokio::time::timeout(std::time::Duration::from_secs(5), batch)
+            .await
+            .expect("the cancelled task does not hold up the batch")
+            .unwrap();
+
+        results.sort();
+        assert_eq!(results, vec![2, 6]);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_task_cancels_its_token() {
+        let stopped = Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+        let task = run_cancellable("token_1", {
+            let stopped = Arc::clone(&stopped);
+            async move {
+                let token = task_cancellation().unwrap();
+                cancel_task("token_1".to_string());
+                token.cancelled().await;
+                stopped.store(true, std::sync::atomic::Ordering::SeqCst);
+                Ok(())
+            }
+        });
+        let result = tokio::time::timeout(std::time::Duration::from_secs(5), task)
+            .await
+            .expect("the task stops when its token is cancelled");
+
+        assert!(result.unwrap_err().is::<TaskCancelled>());
+        assert!(stopped.load(std::sync::atomic::Ordering::SeqCst));
+        assert!(task_cancellation().is_none());
+    }
 }
@@ -0,0 +1,102 @@
+//! JSON extraction from agent responses
+
+use super::yaml::extract_code_block;
+
+/// Byte length of the array or object at the start of `text`, up to and
+/// including its matching bracket
+///
+/// Brackets in strings don't count. Returns `None` if the brackets are
+/// mismatched or never closed.
+fn balanced_len(text: &str) -> Option<usize> {
+    let mut closers = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '[' => closers.push(']'),
+            '{' => closers.push('}'),
+            ']' | '}' => {
+                if closers.pop() != Some(c) {
+                    return None;
+                }
+                if closers.is_empty() {
+                    return Some(i + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// First balanced array or object in the text that is valid JSON
+///
+/// Skips bracketed prose such as `[see below]` on the way.
+pub fn find_json_value(text: &str) -> Option<&str> {
+    text.char_indices()
+        .filter(|(_, c)| matches!(c, '[' | '{'))
+        .find_map(|(start, _)| {
+            let candidate = &text[start..start + balanced_len(&text[start..])?];
+            serde_json::from_str::<serde_json::Value>(candidate)
+                .is_ok()
+                .then_some(candidate)
+        })
+}
+
+/// Extract JSON content from markdown code blocks, raw text or prose
+///
+/// Handles:
+/// - ```json blocks
+/// - Generic ``` blocks
+/// - Raw JSON text
+/// - JSON after or between sentences, such as `Here are the results: [...]`
+///
+/// Returns the trimmed text as is when it holds no valid JSON, so parsing it
+/// reports the actual problem.
+pub fn extract_json(text: &str) -> String {
+    let json = extract_code_block(text, "json").unwrap_or(text).trim();
+    if serde_json::from_str::<serde_json::Value>(json).is_ok() {
+        return json.to_string();
+    }
+    find_json_value(json)
+        .or_else(|| find_json_value(text))
+        .unwrap_or(json)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_json() {
+        let block = "Results:\n```json\n[{\"task_id\": 1}]\n```\n";
+        assert_eq!(extract_json(block), r#"[{"task_id": 1}]"#);
+        assert_eq!(extract_json("  {\"a\": 1}\n"), r#"{"a": 1}"#);
+
+        let prose = "Here are the results [2 tasks]:\n[{\"summary\": \"uses ] and {\", \
+                     \"issues\": []}, {\"ok\": true}]\nLet me know!";
+        assert_eq!(
+            extract_json(prose),
+            r#"[{"summary": "uses ] and {", "issues": []}, {"ok": true}]"#
+        );
+        assert_eq!(
+            find_json_value(r#"{"quote": "a \"}\" b"} trailing"#),
+            Some(r#"{"quote": "a \"}\" b"}"#)
+        );
+
+        assert_eq!(extract_json("No results [yet]."), "No results [yet].");
+        assert_eq!(find_json_value("[1, 2"), None);
+        assert_eq!(find_json_value("{]"), None);
+    }
+}
@@ -0,0 +1,185 @@
+//! Shared MCP servers injected into every agent run
+//!
+//! Workflows build their own `ClaudeAgentOptions`, so project-specific tools
+//! would otherwise have to be wired into each phase by hand. Servers
+//! registered in a [`SharedMcpServers`] are merged into `options.mcp_servers`
+//! by [`execute_agent`](super::execute_agent) for every agent configured
+//! with it. Each registry is independent, so two workflows (or two tests) in
+//! one process don't see each other's servers.
+//!
+//! # Merge precedence
+//!
+//! Servers declared by the workflow itself win: a shared server whose name is
+//! already present in the workflow's `mcp_servers` is skipped. When the
+//! workflow restricts `allowed_tools`, each injected server is allowed as a
+//! whole (`mcp__<name>`) so its tools are usable without listing them.
+//!
+//! # Example
+//! ```rust
+//! use claude_agent_sdk::mcp::SdkMcpServer;
+//! use workflow_manager::workflow_utils::SharedMcpServers;
+//!
+//! use workflow_manager::workflow_utils::AgentConfig;
+//!
+//! // At startup, before any phase runs
+//! let servers = SharedMcpServers::new();
+//! servers.register_sdk("project_tools", SdkMcpServer::new("project_tools"));
+//!
+//! // For each agent
+//! let config = AgentConfig::new("t1", "agent", "description", "prompt", Default::default())
+//!     .with_mcp_servers(&servers);
+//! ```
+
+use claude_agent_sdk::mcp::SdkMcpServer;
+use claude_agent_sdk::types::{
+    ClaudeAgentOptions, McpServerConfig, McpServers, SdkMcpServerMarker, ToolName,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Registry of MCP servers shared by the agents configured with it
+#[derive(Clone, Default)]
+pub struct SharedMcpServers {
+    servers: Arc<Mutex<HashMap<String, McpServerConfig>>>,
+}
+
+impl SharedMcpServers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register any MCP server configuration under `name`
+    ///
+    /// Registering the same name twice replaces the previous server.
+    pub fn register(&self, name: impl Into<String>, config: McpServerConfig) {
+        self.servers.lock().unwrap().insert(name.into(), config);
+    }
+
+    /// Register an in-process SDK MCP server under `name`
+    pub fn register_sdk(&self, name: impl Into<String>, server: SdkMcpServer) {
+        let name = name.into();
+        let config = McpServerConfig::Sdk(SdkMcpServerMarker {
+            name: name.clone(),
+            instance: Arc::new(server),
+        });
+        self.register(name, config);
+    }
+
+    /// Names of the registered servers, sorted
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.servers.lock().unwrap().keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Merge the shared servers into `options`, keeping workflow-declared
+    /// servers on name conflicts
+    pub fn apply(&self, options: &mut ClaudeAgentOptions) {
+        let shared = self.servers.lock().unwrap().clone();
+        if shared.is_empty() {
+            return;
+        }
+
+        let mut servers = match std::mem::take(&mut options.mcp_servers) {
+            McpServers::Dict(servers) => servers,
+            McpServers::Empty => HashMap::new(),
+            other => {
+                // Servers loaded from a config path can't be merged in-process
+                eprintln!(
+                    "Warning: workflow declares MCP servers by path; shared MCP servers not injected"
+                );
+                options.mcp_servers = other;
+                return;
+            }
+        };
+
+        let mut injected = Vec::new();
+        for (name, config) in shared {
+            if !servers.contains_key(&name) {
+                servers.insert(name.clone(), config);
+                injected.push(name);
+            }
+        }
+        options.mcp_servers = McpServers::Dict(servers);
+
+        if !options.allowed_tools.is_empty() {
+            injected.sort();
+            for name in injected {
+                options
+                    .allowed_tools
+                    .push(ToolName::new(format!("mcp__{}", name)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server_name(options: &ClaudeAgentOptions, key: &str) -> Option<String> {
+        match &options.mcp_servers {
+            McpServers::Dict(servers) => match servers.get(key)? {
+                McpServerConfig::Sdk(marker) => Some(marker.name.clone()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_apply_injects_into_empty_options() {
+        let shared = SharedMcpServers::new();
+        shared.register_sdk("project_tools", SdkMcpServer::new("project_tools"));
+
+        let mut options = ClaudeAgentOptions::default();
+        shared.apply(&mut options);
+
+        assert_eq!(
+            server_name(&options, "project_tools").as_deref(),
+            Some("project_tools")
+        );
+        assert!(options.allowed_tools.is_empty());
+    }
+
+    #[test]
+    fn test_workflow_declared_server_wins() {
+        let shared = SharedMcpServers::new();
+        shared.register(
+            "tools",
+            McpServerConfig::Sdk(SdkMcpServerMarker {
+                name: "shared".to_string(),
+                instance: Arc::new(SdkMcpServer::new("shared")),
+            }),
+        );
+        shared.register_sdk("extra", SdkMcpServer::new("extra"));
+
+        let mut declared = HashMap::new();
+        declared.insert(
+            "tools".to_string(),
+            McpServerConfig::Sdk(SdkMcpServerMarker {
+                name: "workflow".to_string(),
+                instance: Arc::new(SdkMcpServer::new("workflow")),
+            }),
+        );
+        let mut options = ClaudeAgentOptions {
+            mcp_servers: McpServers::Dict(declared),
+            allowed_tools: vec![ToolName::new("Read")],
+            ..Default::default()
+        };
+        shared.apply(&mut options);
+
+        assert_eq!(server_name(&options, "tools").as_deref(), Some("workflow"));
+        assert_eq!(server_name(&options, "extra").as_deref(), Some("extra"));
+        // "Read" plus the injected "mcp__extra"
+        assert_eq!(options.allowed_tools.len(), 2);
+    }
+
+    #[test]
+    fn test_names_sorted() {
+        let shared = SharedMcpServers::new();
+        shared.register_sdk("b", SdkMcpServer::new("b"));
+        shared.register_sdk("a", SdkMcpServer::new("a"));
+        assert_eq!(shared.names(), vec!["a".to_string(), "b".to_string()]);
+    }
+}
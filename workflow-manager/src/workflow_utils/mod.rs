@@ -1,18 +1,37 @@
 //! Workflow utilities for standardized batch/task/agent execution
 //!
 //! This module provides reusable components for workflow phases:
-//! - **batch**: Parallel execution with concurrency control
+//! - **batch**: Parallel execution with concurrency control and cancellation
+//!   of single tasks
 //! - **task**: Task-level logging and execution
 //! - **agent**: Agent execution with stream handling and sub-agent detection
+//! - **json**: JSON extraction from agent responses
+//! - **mcp**: Shared MCP servers merged into every agent's options
+//! - **retry**: Run-level retry budget shared across tasks, backoff and
+//!   retryable-error classification
 //! - **yaml**: YAML extraction, parsing, and validation
 
 pub mod agent;
 pub mod batch;
+pub mod json;
+pub mod mcp;
+pub mod retry;
 pub mod task;
 pub mod yaml;
 
 // Re-export commonly used types and functions
-pub use agent::{execute_agent, AgentConfig};
-pub use batch::{execute_batch, TaskContext};
-pub use task::execute_task;
-pub use yaml::{clean_yaml, extract_yaml, parse_yaml, parse_yaml_multi, validate_yaml_syntax};
+pub use agent::{
+    execute_agent, with_partial_text, AgentConfig, StreamEvent, DEFAULT_AGENT_RETRIES,
+};
+pub use batch::{
+    execute_batch, execute_batch_with_retries, listen_for_control, task_cancellation,
+    TaskCancelled, TaskContext, DEFAULT_TASK_RETRIES,
+};
+pub use json::{extract_json, find_json_value};
+pub use mcp::SharedMcpServers;
+pub use retry::RetryBudget;
+pub use task::{execute_task, execute_task_with_retry};
+pub use yaml::{
+    clean_yaml, extract_code_block, extract_yaml, parse_yaml, parse_yaml_multi, top_level_keys,
+    validate_yaml_against_template, validate_yaml_syntax,
+};
@@ -0,0 +1,240 @@
+//! Run-level retry budget shared by all tasks of a workflow run
+//!
+//! Individual retry loops (YAML fix loops, task retries) each have their own
+//! limits, but on a bad day they can collectively burn through rate limits.
+//! The budget caps the total number of retries across the whole run: once it
+//! is exhausted, further failures are terminal and a warning is emitted.
+//!
+//! The process-wide budget is read from `WORKFLOW_RETRY_BUDGET` (default 10)
+//! unless a workflow configures it explicitly with [`RetryBudget::init_global`].
+//!
+//! [`is_retryable`] and [`backoff_delay`] decide which task errors are worth
+//! another attempt and how long to wait before it.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use workflow_manager_sdk::log_task_progress;
+
+/// Environment variable holding the maximum number of retries per run
+pub const RETRY_BUDGET_ENV: &str = "WORKFLOW_RETRY_BUDGET";
+
+/// Retries allowed per run when nothing is configured
+pub const DEFAULT_RETRY_BUDGET: usize = 10;
+
+/// Delay before the first task retry; doubled for every further retry
+pub const RETRY_BASE_DELAY: Duration = Duration::from_secs(2);
+
+/// Longest delay between two task retries
+pub const RETRY_MAX_DELAY: Duration = Duration::from_secs(60);
+
+static GLOBAL_BUDGET: OnceLock<RetryBudget> = OnceLock::new();
+
+#[derive(Debug)]
+struct BudgetState {
+    max_retries: usize,
+    used: AtomicUsize,
+    warned: AtomicBool,
+}
+
+/// Shared counter of retries remaining for a workflow run
+///
+/// Cloning is cheap; clones share the same counter.
+#[derive(Debug, Clone)]
+pub struct RetryBudget {
+    state: Arc<BudgetState>,
+}
+
+impl RetryBudget {
+    /// Create a budget allowing `max_retries` retries in total
+    pub fn new(max_retries: usize) -> Self {
+        Self {
+            state: Arc::new(BudgetState {
+                max_retries,
+                used: AtomicUsize::new(0),
+                warned: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    /// Create a budget from `WORKFLOW_RETRY_BUDGET`, falling back to the default
+    pub fn from_env() -> Self {
+        let max_retries = std::env::var(RETRY_BUDGET_ENV)
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(DEFAULT_RETRY_BUDGET);
+        Self::new(max_retries)
+    }
+
+    /// Process-wide budget shared by every retry loop of this run
+    pub fn global() -> &'static RetryBudget {
+        GLOBAL_BUDGET.get_or_init(Self::from_env)
+    }
+
+    /// Set the process-wide budget explicitly (e.g. from a CLI flag)
+    ///
+    /// Has no effect if the global budget was already used.
+    pub fn init_global(max_retries: usize) -> &'static RetryBudget {
+        GLOBAL_BUDGET.get_or_init(|| Self::new(max_retries))
+    }
+
+    /// Consume one retry for `task_id`
+    ///
+    /// Returns `false` when the budget is exhausted; the caller must treat the
+    /// failure as terminal. The first time this happens a warning is emitted
+    /// on the task so it shows up in the TUI.
+    pub fn try_acquire(&self, task_id: &str) -> bool {
+        let acquired = self
+            .state
+            .used
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |used| {
+                (used < self.state.max_retries).then_some(used + 1)
+            })
+            .is_ok();
+
+        if !acquired && !self.state.warned.swap(true, Ordering::SeqCst) {
+            let message = format!(
+                "⚠ Retry budget exhausted ({} retries used); further failures are terminal",
+                self.state.max_retries
+            );
+            eprintln!("{}", message);
+            log_task_progress!(task_id, message);
+        }
+
+        acquired
+    }
+
+    /// Retries still available
+    pub fn remaining(&self) -> usize {
+        self.state
+            .max_retries
+            .saturating_sub(self.state.used.load(Ordering::SeqCst))
+    }
+
+    /// Retries consumed so far
+    pub fn used(&self) -> usize {
+        self.state.used.load(Ordering::SeqCst)
+    }
+
+    /// Total retries allowed
+    pub fn max_retries(&self) -> usize {
+        self.state.max_retries
+    }
+}
+
+/// Whether a failed task may succeed when simply run again
+///
+/// Transport and timeout failures are recoverable: errors from the Claude
+/// agent SDK (CLI process and stream errors), transient I/O errors (see
+/// [`is_transient_io`]) and elapsed tokio timeouts anywhere in the error
+/// chain. Everything else, such as invalid input, a missing file or
+/// unparsable agent output, fails the task immediately.
+pub fn is_retryable(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        cause.is::<claude_agent_sdk::error::ClaudeError>()
+            || cause
+                .downcast_ref::<std::io::Error>()
+                .is_some_and(is_transient_io)
+            || cause.is::<tokio::time::error::Elapsed>()
+    })
+}
+
+/// Whether an I/O error is about a connection or a call that may work when
+/// tried again, rather than about the file system or the input
+pub fn is_transient_io(error: &std::io::Error) -> bool {
+    use std::io::ErrorKind;
+    matches!(
+        error.kind(),
+        ErrorKind::ConnectionRefused
+            | ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::NotConnected
+            | ErrorKind::BrokenPipe
+            | ErrorKind::TimedOut
+            | ErrorKind::Interrupted
+            | ErrorKind::WouldBlock
+            | ErrorKind::UnexpectedEof
+    )
+}
+
+/// Exponential backoff before retry number `retry` (1-based)
+pub fn backoff_delay(retry: usize) -> Duration {
+    let factor = 1u32 << retry.saturating_sub(1).min(16);
+    RETRY_BASE_DELAY.saturating_mul(factor).min(RETRY_MAX_DELAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget_exhausts() {
+        let budget = RetryBudget::new(2);
+        assert!(budget.try_acquire("task"));
+        assert!(budget.try_acquire("task"));
+        assert!(!budget.try_acquire("task"));
+        assert!(!budget.try_acquire("task"));
+        assert_eq!(budget.used(), 2);
+        assert_eq!(budget.remaining(), 0);
+    }
+
+    #[test]
+    fn test_budget_shared_between_clones() {
+        let budget = RetryBudget::new(3);
+        let other = budget.clone();
+        assert!(budget.try_acquire("a"));
+        assert!(other.try_acquire("b"));
+        assert_eq!(budget.remaining(), 1);
+        assert_eq!(other.used(), 2);
+    }
+
+    #[test]
+    fn test_zero_budget_never_retries() {
+        let budget = RetryBudget::new(0);
+        assert!(!budget.try_acquire("task"));
+        assert_eq!(budget.max_retries(), 0);
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_cap() {
+        assert_eq!(backoff_delay(1), RETRY_BASE_DELAY);
+        assert_eq!(backoff_delay(2), RETRY_BASE_DELAY * 2);
+        assert_eq!(backoff_delay(3), RETRY_BASE_DELAY * 4);
+        assert_eq!(backoff_delay(100), RETRY_MAX_DELAY);
+    }
+
+    #[test]
+    fn test_only_transport_errors_are_retryable() {
+        let io = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "reset");
+        assert!(is_retryable(
+            &anyhow::Error::new(io).context("query failed")
+        ));
+        assert!(!is_retryable(&anyhow::anyhow!("invalid YAML")));
+
+        let missing = std::io::Error::new(std::io::ErrorKind::NotFound, "no such file");
+        assert!(!is_retryable(
+            &anyhow::Error::new(missing).context("reading prompts")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_budget_concurrent_acquire() {
+        let budget = RetryBudget::new(5);
+        let mut handles = Vec::new();
+        for i in 0..20 {
+            let budget = budget.clone();
+            handles.push(tokio::spawn(async move {
+                budget.try_acquire(&format!("task_{}", i))
+            }));
+        }
+
+        let mut granted = 0;
+        for handle in handles {
+            if handle.await.unwrap() {
+                granted += 1;
+            }
+        }
+        assert_eq!(granted, 5);
+        assert_eq!(budget.remaining(), 0);
+    }
+}
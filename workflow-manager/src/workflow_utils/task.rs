@@ -1,9 +1,10 @@
 //! Task execution utilities with automatic logging
 
-use crate::workflow_utils::batch::TaskContext;
+use crate::workflow_utils::batch::{run_cancellable, TaskContext};
+use crate::workflow_utils::retry::{backoff_delay, is_retryable, RetryBudget};
 use anyhow::Result;
 use std::future::Future;
-use workflow_manager_sdk::{log_task_complete, log_task_failed, log_task_start};
+use workflow_manager_sdk::{log_task_complete, log_task_failed, log_task_progress, log_task_start};
 
 /// Execute a single task with automatic logging
 ///
@@ -12,6 +13,10 @@ use workflow_manager_sdk::{log_task_complete, log_task_failed, log_task_start};
 /// - `log_task_complete` on success
 /// - `log_task_failed` on error
 ///
+/// A task cancelled through a control message (see
+/// [`listen_for_control`](super::batch::listen_for_control)) fails with
+/// [`TaskCancelled`](super::batch::TaskCancelled).
+///
 /// # Arguments
 /// - `task_id`: Unique identifier for this task
 /// - `description`: Human-readable description
@@ -47,7 +52,83 @@ where
     log_task_start!(ctx.phase, &task_id, &description, ctx.total_tasks);
 
     // Execute task
-    match executor().await {
+    match run_cancellable(&task_id, executor()).await {
+        Ok((result, summary)) => {
+            log_task_complete!(&task_id, summary);
+            Ok(result)
+        }
+        Err(e) => {
+            log_task_failed!(&task_id, e.to_string());
+            Err(e)
+        }
+    }
+}
+
+/// Execute a single task with automatic logging, retrying recoverable errors
+///
+/// Like [`execute_task`], but a failure that [`is_retryable`] reports as
+/// recoverable (transport, timeout) runs `executor` again, up to
+/// `ctx.max_retries` times with exponential backoff. Each retry is logged as
+/// task progress ("retry N/M") and drawn from the run-level
+/// [`RetryBudget::global`]. Other errors, or an exhausted budget, fail the
+/// task immediately.
+pub async fn execute_task_with_retry<F, Fut, R>(
+    task_id: impl Into<String>,
+    description: impl Into<String>,
+    ctx: TaskContext,
+    executor: F,
+) -> Result<R>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<(R, String)>>,
+{
+    run_with_retry(
+        task_id.into(),
+        description.into(),
+        ctx,
+        RetryBudget::global(),
+        executor,
+    )
+    .await
+}
+
+async fn run_with_retry<F, Fut, R>(
+    task_id: String,
+    description: String,
+    ctx: TaskContext,
+    budget: &RetryBudget,
+    mut executor: F,
+) -> Result<R>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<(R, String)>>,
+{
+    log_task_start!(ctx.phase, &task_id, &description, ctx.total_tasks);
+
+    let mut retry = 0;
+    let attempts = async {
+        loop {
+            match executor().await {
+                Ok(done) => return Ok(done),
+                Err(e)
+                    if retry < ctx.max_retries
+                        && is_retryable(&e)
+                        && budget.try_acquire(&task_id) =>
+                {
+                    retry += 1;
+                    log_task_progress!(
+                        &task_id,
+                        format!("retry {}/{} after error: {}", retry, ctx.max_retries, e)
+                    );
+                    tokio::time::sleep(backoff_delay(retry)).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    };
+
+    // Cancelling the task also stops its retries
+    match run_cancellable(&task_id, attempts).await {
         Ok((result, summary)) => {
             log_task_complete!(&task_id, summary);
             Ok(result)
@@ -93,6 +174,7 @@ mod tests {
             phase: 1,
             task_number: 1,
             total_tasks: 1,
+            max_retries: 0,
         };
 
         let result = execute_task("test_task", "Test task", ctx, || async {
@@ -110,6 +192,7 @@ mod tests {
             phase: 1,
             task_number: 1,
             total_tasks: 1,
+            max_retries: 0,
         };
 
         let result = execute_task("test_task", "Test task", ctx, || async {
@@ -119,4 +202,65 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    fn transient_error() -> anyhow::Error {
+        std::io::Error::new(std::io::ErrorKind::TimedOut, "CLI timed out").into()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retry_recovers_after_transient_failures() {
+        let ctx = TaskContext {
+            phase: 1,
+            task_number: 1,
+            total_tasks: 1,
+            max_retries: 3,
+        };
+        let budget = RetryBudget::new(10);
+        let mut attempts = 0;
+
+        let result = run_with_retry("flaky".into(), "Flaky task".into(), ctx, &budget, || {
+            attempts += 1;
+            let attempt = attempts;
+            async move {
+                if attempt <= 2 {
+                    Err(transient_error())
+                } else {
+                    Ok((attempt, "Done".to_string()))
+                }
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(result, 3);
+        assert_eq!(budget.used(), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retry_gives_up_on_non_retryable_error() {
+        let ctx = TaskContext {
+            phase: 1,
+            task_number: 1,
+            total_tasks: 1,
+            max_retries: 3,
+        };
+        let budget = RetryBudget::new(10);
+        let mut attempts = 0;
+
+        let result = run_with_retry(
+            "invalid".into(),
+            "Invalid task".into(),
+            ctx,
+            &budget,
+            || {
+                attempts += 1;
+                async { Err::<(i32, String), _>(anyhow::anyhow!("invalid input")) }
+            },
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+        assert_eq!(budget.used(), 0);
+    }
 }
@@ -3,36 +3,78 @@
 use anyhow::{Context, Result};
 use serde::de::DeserializeOwned;
 
+/// A line opening or closing a fenced code block
+struct Fence<'a> {
+    /// Byte offsets of the line, including its newline
+    start: usize,
+    end: usize,
+    indent: usize,
+    /// Language tag after the backticks, empty for a closing fence
+    info: &'a str,
+}
+
+fn fences(text: &str) -> Vec<Fence<'_>> {
+    let mut fences = Vec::new();
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if let Some(info) = trimmed.strip_prefix("```") {
+            fences.push(Fence {
+                start: offset,
+                end: offset + line.len(),
+                indent: line.len() - trimmed.len(),
+                info: info.trim(),
+            });
+        }
+        offset += line.len();
+    }
+    fences
+}
+
+/// Content of the first fenced code block tagged `language`, or else of the
+/// first fenced block of any language
+///
+/// Fences inside the block don't end it: those indented deeper than the
+/// opening one are content (such as code in a YAML block scalar), and the
+/// others are balanced, a tagged fence opening a nested block and a bare one
+/// closing it. A block left open runs to the end of the text. Returns `None`
+/// if the text has no fenced block.
+pub fn extract_code_block<'a>(text: &'a str, language: &str) -> Option<&'a str> {
+    let fences = fences(text);
+    let opening = fences
+        .iter()
+        .position(|fence| fence.info.eq_ignore_ascii_case(language))
+        .or_else(|| (!fences.is_empty()).then_some(0))?;
+    let open = &fences[opening];
+
+    let mut depth = 0;
+    for fence in &fences[opening + 1..] {
+        if fence.indent > open.indent {
+            continue;
+        }
+        if !fence.info.is_empty() {
+            depth += 1;
+        } else if depth == 0 {
+            return Some(&text[open.end..fence.start]);
+        } else {
+            depth -= 1;
+        }
+    }
+    // Unclosed, or closed at the end of the last line
+    Some(text[open.end..].trim_end().trim_end_matches("```"))
+}
+
 /// Extract YAML content from markdown code blocks or raw text
 ///
 /// Handles:
 /// - ```yaml blocks
 /// - Generic ``` blocks
+/// - Code blocks nested in either (see [`extract_code_block`])
 /// - Raw YAML text
 /// - Removes leading document separator (---)
 pub fn extract_yaml(text: &str) -> String {
-    let yaml = if text.contains("```yaml") {
-        // Extract from ```yaml block
-        let yaml_start = text.find("```yaml").unwrap() + 7;
-        let yaml_end = text[yaml_start..]
-            .rfind("```")
-            .map(|pos| pos + yaml_start)
-            .unwrap_or(text.len());
-        text[yaml_start..yaml_end].trim().to_string()
-    } else if text.contains("```") {
-        // Extract from generic ``` block
-        let yaml_start = text.find("```").unwrap() + 3;
-        let yaml_end = text[yaml_start..]
-            .rfind("```")
-            .map(|pos| pos + yaml_start)
-            .unwrap_or(text.len());
-        text[yaml_start..yaml_end].trim().to_string()
-    } else {
-        // Assume raw YAML
-        text.trim().to_string()
-    };
-
-    clean_yaml(&yaml)
+    let yaml = extract_code_block(text, "yaml").unwrap_or(text);
+    clean_yaml(yaml)
 }
 
 /// Clean YAML by removing document separators and normalizing whitespace
@@ -41,7 +83,7 @@ pub fn extract_yaml(text: &str) -> String {
 /// - Leading `---` document separator
 /// - Trailing whitespace
 pub fn clean_yaml(yaml: &str) -> String {
-    yaml.trim_start_matches("---").trim().to_string()
+    yaml.trim().trim_start_matches("---").trim().to_string()
 }
 
 /// Parse YAML string into a typed structure with better error messages
@@ -102,6 +144,49 @@ pub fn validate_yaml_syntax(yaml: &str) -> Result<()> {
     Ok(())
 }
 
+/// Top-level keys of a YAML mapping, in document order
+///
+/// Useful to derive the required keys from a template document.
+pub fn top_level_keys(yaml: &str) -> Result<Vec<String>> {
+    let value: serde_yaml::Value =
+        serde_yaml::from_str(yaml).context("Failed to parse YAML")?;
+    let mapping = value
+        .as_mapping()
+        .context("Expected a mapping at the top level")?;
+    Ok(mapping
+        .keys()
+        .filter_map(|key| key.as_str().map(String::from))
+        .collect())
+}
+
+/// Validate that YAML has the top-level keys of a template
+///
+/// Catches well-formed YAML with the wrong structure. Returns one message
+/// per missing key, or a single message if the YAML does not parse or is not
+/// a mapping. Extra keys are allowed.
+pub fn validate_yaml_against_template(
+    yaml: &str,
+    template_keys: &[&str],
+) -> std::result::Result<(), Vec<String>> {
+    let value: serde_yaml::Value = serde_yaml::from_str(yaml)
+        .map_err(|e| vec![format!("Invalid YAML: {}", e)])?;
+    let mapping = value
+        .as_mapping()
+        .ok_or_else(|| vec!["Expected a mapping at the top level".to_string()])?;
+
+    let missing: Vec<String> = template_keys
+        .iter()
+        .filter(|key| !mapping.contains_key(**key))
+        .map(|key| format!("Missing top-level key '{}'", key))
+        .collect();
+
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(missing)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,6 +230,53 @@ count: 42
         assert!(yaml.contains("title: Test"));
     }
 
+    #[test]
+    fn test_extract_yaml_with_nested_fences() {
+        let text = r#"
+Here is the task:
+
+```yaml
+task:
+  id: 3
+tests:
+  implementation:
+    code: |
+      ```rust
+      #[test]
+      fn parses() {}
+      ```
+    notes: |
+      ```
+      cargo test
+      ```
+```
+
+Run it with:
+
+```bash
+cargo test
+```
+        "#;
+
+        let yaml = extract_yaml(text);
+        assert!(yaml.starts_with("task:"));
+        assert!(yaml.contains("      ```rust\n      #[test]"));
+        assert!(yaml.ends_with("      cargo test\n      ```"));
+        assert!(validate_yaml_syntax(&yaml).is_ok());
+
+        // Balanced fences at the same indentation nest too
+        let text = "```markdown\n# Usage\n```bash\nrun\n```\nDone\n```\nAfter";
+        assert_eq!(
+            extract_code_block(text, "markdown"),
+            Some("# Usage\n```bash\nrun\n```\nDone\n")
+        );
+
+        assert_eq!(extract_code_block("```yaml\na: 1\n", "yaml"), Some("a: 1"));
+        assert_eq!(extract_code_block("```yaml\na: 1```", "yaml"), Some("a: 1"));
+        assert_eq!(extract_code_block("```json\n{}\n```", "yaml"), Some("{}\n"));
+        assert_eq!(extract_code_block("a: 1", "yaml"), None);
+    }
+
     #[test]
     fn test_extract_yaml_raw() {
         let text = r#"
@@ -192,4 +324,33 @@ count: 2
         assert!(validate_yaml_syntax("title: Test").is_ok());
         assert!(validate_yaml_syntax("invalid: [unclosed").is_err());
     }
+
+    #[test]
+    fn test_validate_yaml_against_template() {
+        let template = "task:\n  id: 0\ncontext:\n  description: ''\nfiles: []\n";
+        let keys = top_level_keys(template).unwrap();
+        assert_eq!(keys, ["task", "context", "files"]);
+        let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+
+        let complete = "task:\n  id: 6\ncontext: {}\nfiles: []\nextra: true\n";
+        assert!(validate_yaml_against_template(complete, &keys).is_ok());
+
+        let errors = validate_yaml_against_template("task:\n  id: 6\n", &keys).unwrap_err();
+        assert_eq!(
+            errors,
+            [
+                "Missing top-level key 'context'",
+                "Missing top-level key 'files'"
+            ]
+        );
+
+        let errors = validate_yaml_against_template("- task", &keys).unwrap_err();
+        assert_eq!(errors, ["Expected a mapping at the top level"]);
+        assert_eq!(
+            validate_yaml_against_template("task: [unclosed", &keys)
+                .unwrap_err()
+                .len(),
+            1
+        );
+    }
 }
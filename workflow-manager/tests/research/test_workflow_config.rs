@@ -17,6 +17,7 @@ fn test_workflow_config_default() {
     assert!(config.output.is_none());
     assert!(config.system_prompt.is_none());
     assert!(config.append.is_none());
+    assert!(!config.stream);
 }
 
 #[test]
@@ -72,8 +73,14 @@ fn test_workflow_config_full_custom() {
         results_file: Some("results.yaml".to_string()),
         results_dir: Some("./RESULTS".to_string()),
         output: Some("output.md".to_string()),
+        output_dir: Some("./reports".to_string()),
+        output_prefix: Some("rust_ecosystem".to_string()),
         system_prompt: Some("prompts/writer.md".to_string()),
         append: Some("prompts/style.md".to_string()),
+        stream: true,
+        max_fix_iterations: 5,
+        model: Some("opus".to_string()),
+        subagent_model: Some("haiku".to_string()),
     };
 
     assert_eq!(config.objective, Some("Test objective".to_string()));
@@ -85,11 +92,17 @@ fn test_workflow_config_full_custom() {
     assert_eq!(config.results_file, Some("results.yaml".to_string()));
     assert_eq!(config.results_dir, Some("./RESULTS".to_string()));
     assert_eq!(config.output, Some("output.md".to_string()));
+    assert_eq!(config.output_dir, Some("./reports".to_string()));
+    assert_eq!(config.output_prefix, Some("rust_ecosystem".to_string()));
     assert_eq!(
         config.system_prompt,
         Some("prompts/writer.md".to_string())
     );
     assert_eq!(config.append, Some("prompts/style.md".to_string()));
+    assert!(config.stream);
+    assert_eq!(config.max_fix_iterations, 5);
+    assert_eq!(config.model, Some("opus".to_string()));
+    assert_eq!(config.subagent_model, Some("haiku".to_string()));
 }
 
 #[test]
@@ -0,0 +1,350 @@
+//! Common utilities for UI snapshot tests
+//!
+//! Views are rendered into a ratatui `TestBackend` and the resulting buffer is
+//! compared, as plain text, against `tests/ui/snapshots/<name>.snap`.
+//!
+//! A missing snapshot fails the test. Set `UPDATE_SNAPSHOTS=1` to record new
+//! snapshots or re-record them after an intended UI change.
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use ratatui::{backend::TestBackend, buffer::Buffer, Terminal};
+use unicode_width::UnicodeWidthStr;
+use uuid::Uuid;
+use workflow_manager::app::{
+    AgentStatus, App, LogLine, PhaseStatus, TaskStatus, WorkflowAgent, WorkflowPane,
+    WorkflowPhase, WorkflowTab, WorkflowTask,
+};
+use workflow_manager::chat::{ChatInterface, ChatMessage, ChatRole, ToolCall};
+use workflow_manager::database::Database;
+use workflow_manager::runtime::ProcessBasedRuntime;
+use workflow_manager_sdk::{
+    FieldSchema, LogLevel, Workflow, WorkflowInfo, WorkflowMetadata, WorkflowRuntime,
+    WorkflowSource, WorkflowStatus,
+};
+
+pub const WIDTH: u16 = 100;
+pub const HEIGHT: u16 = 32;
+
+/// Long unicode text mixing accents, CJK and emoji (wide and multi-byte chars)
+pub const LONG_UNICODE: &str =
+    "Análisis de código: 漢字とかなの混在テキスト 🚀🔥 über naïve café — résumé of every módulo in the crate";
+
+/// Render the whole UI for `app` into a buffer of the given size
+pub fn render(app: &mut App, width: u16, height: u16) -> String {
+    let backend = TestBackend::new(width, height);
+    let mut terminal = Terminal::new(backend).unwrap();
+    terminal.draw(|f| workflow_manager::ui::ui(f, app)).unwrap();
+    buffer_to_string(terminal.backend().buffer())
+}
+
+/// Plain-text view of a buffer, one line per row with trailing spaces removed
+pub fn buffer_to_string(buffer: &Buffer) -> String {
+    let width = buffer.area.width as usize;
+    buffer
+        .content
+        .chunks(width)
+        .map(|row| {
+            // A wide glyph is followed by blank filler cells, which are not
+            // drawn as text
+            let mut line = String::new();
+            let mut skip = 0;
+            for cell in row {
+                if skip > 0 {
+                    skip -= 1;
+                    continue;
+                }
+                line.push_str(cell.symbol());
+                skip = cell.symbol().width().saturating_sub(1);
+            }
+            line.trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n"
+}
+
+fn snapshot_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("ui")
+        .join("snapshots")
+}
+
+/// Compare `actual` against the stored snapshot `name`
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path = snapshot_dir().join(format!("{}.snap", name));
+    let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+
+    if update {
+        std::fs::create_dir_all(snapshot_dir()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        eprintln!("Recorded snapshot {}", path.display());
+        return;
+    }
+
+    let Ok(expected) = std::fs::read_to_string(&path) else {
+        panic!(
+            "Missing snapshot {} (set UPDATE_SNAPSHOTS=1 to record it)",
+            path.display()
+        );
+    };
+    if expected != actual {
+        panic!(
+            "Snapshot `{}` does not match (set UPDATE_SNAPSHOTS=1 to accept)\n\
+             --- expected\n{}\n--- actual\n{}",
+            name, expected, actual
+        );
+    }
+}
+
+fn workflow(json: serde_json::Value, source: WorkflowSource) -> Workflow {
+    let metadata: WorkflowMetadata = serde_json::from_value(json.clone()).unwrap();
+    let fields: Vec<FieldSchema> = serde_json::from_value(json["fields"].clone()).unwrap();
+    Workflow {
+        info: WorkflowInfo {
+            id: metadata.id.clone(),
+            name: metadata.name.clone(),
+            description: metadata.description.clone(),
+            status: WorkflowStatus::NotStarted,
+            metadata,
+            fields,
+            progress_messages: vec![],
+        },
+        source,
+    }
+}
+
+/// Catalog used by every snapshot: a multi-field workflow, a unicode-heavy
+/// one and a user-defined pipeline
+pub fn sample_workflows() -> Vec<Workflow> {
+    vec![
+        workflow(
+            serde_json::json!({
+                "id": "research_agent",
+                "name": "Research Agent Workflow",
+                "description": "Multi-phase research: analyze codebase, generate prompts, execute research, synthesize",
+                "fields": [
+                    {"name": "input", "field_type": {"type": "text"}, "label": "Research Objective",
+                     "description": "[TEXT] What do you want to research?", "cli_arg": "--input",
+                     "required": false, "default": null, "required_for_phases": [2]},
+                    {"name": "phases", "field_type": {"type": "phase_selector", "total_phases": 6},
+                     "label": "Phases", "description": "[PHASES] Select which phases to run",
+                     "cli_arg": "--phases", "required": false, "default": "0,1,2,3,4,5"},
+                    {"name": "batch_size", "field_type": {"type": "number", "min": 1, "max": 10},
+                     "label": "Batch Size", "description": "[NUMBER] Parallel execution batch size (1-10)",
+                     "cli_arg": "--batch-size", "required": false, "default": "1"},
+                    {"name": "analysis_file",
+                     "field_type": {"type": "state_file", "pattern": "codebase_analysis_*.yaml"},
+                     "label": "Analysis File", "description": "[STATE FILE] Resume with existing codebase analysis",
+                     "cli_arg": "--analysis-file", "required": false, "default": null,
+                     "required_for_phases": [1, 2]}
+                ]
+            }),
+            WorkflowSource::BuiltIn,
+        ),
+        workflow(
+            serde_json::json!({
+                "id": "translate",
+                "name": "Übersetzung 翻訳 🚀 Workflow",
+                "description": LONG_UNICODE,
+                "fields": [
+                    {"name": "text", "field_type": {"type": "text"}, "label": "Texte à traduire",
+                     "description": "[TEXT] 翻訳するテキスト", "cli_arg": "--text",
+                     "required": true, "default": null}
+                ]
+            }),
+            WorkflowSource::BuiltIn,
+        ),
+        workflow(
+            serde_json::json!({
+                "id": "research_then_plan",
+                "name": "Research then Plan",
+                "description": "Research a codebase, then turn the findings into tasks",
+                "fields": []
+            }),
+            WorkflowSource::UserDefined,
+        ),
+    ]
+}
+
+/// App with the sample catalog and deterministic (unicode) icons
+pub fn sample_app() -> App {
+    let mut app = App::with_workflows(sample_workflows());
+    app.icons = &workflow_manager::ui::icons::UNICODE_ICONS;
+    app
+}
+
+fn agent(task_id: &str, name: &str, status: AgentStatus, messages: Vec<String>) -> WorkflowAgent {
+    WorkflowAgent {
+        id: format!("{}:{}", task_id, name),
+        task_id: task_id.to_string(),
+        name: name.to_string(),
+        description: format!("{} agent", name),
+        status,
+        messages: messages
+            .into_iter()
+            .map(|message| LogLine::new(LogLevel::Info, message))
+            .collect(),
+        ..Default::default()
+    }
+}
+
+/// Phases covering every status, nested agents and long unicode messages
+pub fn sample_phases() -> Vec<WorkflowPhase> {
+    let researcher_messages = (1..=7)
+        .map(|i| format!("Step {}: {}", i, LONG_UNICODE))
+        .collect();
+
+    vec![
+        WorkflowPhase {
+            id: 0,
+            name: "Analyze Codebase".to_string(),
+            status: PhaseStatus::Completed,
+            tasks: vec![WorkflowTask {
+                id: "analyze".to_string(),
+                description: "Analyzing codebase structure".to_string(),
+                status: TaskStatus::Completed,
+                messages: vec![LogLine::new(LogLevel::Info, "Found 42 Rust files")],
+                result: Some("Saved analysis".to_string()),
+                ..Default::default()
+            }],
+            output_files: vec![(
+                "codebase_analysis_20250101_120000.yaml".to_string(),
+                "Codebase analysis".to_string(),
+            )],
+            ..Default::default()
+        },
+        WorkflowPhase {
+            id: 1,
+            name: "Execute Research".to_string(),
+            status: PhaseStatus::Running,
+            tasks: vec![
+                WorkflowTask {
+                    id: "research_1".to_string(),
+                    phase: 1,
+                    description: "Researching 認証システム 🔐".to_string(),
+                    status: TaskStatus::Running,
+                    agents: vec![
+                        agent(
+                            "research_1",
+                            "researcher",
+                            AgentStatus::Running,
+                            researcher_messages,
+                        ),
+                        agent(
+                            "research_1",
+                            "reviewer",
+                            AgentStatus::Failed,
+                            vec![format!("Rejected: {}", LONG_UNICODE)],
+                        ),
+                    ],
+                    messages: vec![LogLine::new(LogLevel::Info, LONG_UNICODE)],
+                    progress: Some(0.42),
+                    ..Default::default()
+                },
+                WorkflowTask {
+                    id: "research_2".to_string(),
+                    phase: 1,
+                    description: "Researching database layer".to_string(),
+                    status: TaskStatus::Failed,
+                    messages: vec![LogLine::new(LogLevel::Error, "Error: rate limited")],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        },
+        WorkflowPhase {
+            id: 2,
+            name: "Synthesize Documentation".to_string(),
+            status: PhaseStatus::NotStarted,
+            ..Default::default()
+        },
+    ]
+}
+
+pub fn sample_output() -> Vec<LogLine> {
+    vec![
+        LogLine::new(LogLevel::Debug, "Starting research workflow"),
+        LogLine::new(LogLevel::Debug, format!("[stderr] {}", LONG_UNICODE)),
+        LogLine::new(LogLevel::Info, "✅ Workflow completed successfully"),
+    ]
+}
+
+/// Build a tab for workflow `workflow_idx` of the sample catalog
+pub fn tab(
+    app: &App,
+    workflow_idx: usize,
+    instance_number: usize,
+    status: WorkflowStatus,
+    phases: Vec<WorkflowPhase>,
+) -> WorkflowTab {
+    let workflow = &app.workflows[workflow_idx];
+    WorkflowTab {
+        id: format!("{}_{}", workflow.info.id, instance_number),
+        workflow_idx,
+        workflow_name: workflow.info.name.clone(),
+        instance_number,
+        start_time: None,
+        status,
+        runtime_handle_id: Uuid::from_u128(instance_number as u128),
+        exit_code: None,
+        completed_at: None,
+        workflow_phases: Arc::new(Mutex::new(phases)),
+        workflow_output: Arc::new(Mutex::new(sample_output())),
+        usage: Arc::new(Mutex::new(None)),
+        result: Arc::new(Mutex::new(None)),
+        last_event: Arc::new(Mutex::new(None)),
+        field_values: HashMap::new(),
+        scroll_offset: 0,
+        expanded_phases: HashSet::new(),
+        expanded_tasks: HashSet::new(),
+        expanded_agents: HashSet::new(),
+        selected_phase: 0,
+        selected_task: None,
+        selected_agent: None,
+        selected_file: None,
+        selected_artifact: None,
+        search: None,
+        agent_scroll_offsets: HashMap::new(),
+        focused_pane: WorkflowPane::StructuredLogs,
+        follow: true,
+        raw_output_scroll_offset: 0,
+        h_scroll: 0,
+        log_filter: LogLevel::Debug,
+        saved_logs: None,
+        read_only: false,
+    }
+}
+
+/// Attach a chat interface that never connects to Claude
+pub fn attach_chat(app: &mut App, messages: Vec<ChatMessage>) {
+    // `Database::new_in_memory` is only compiled for the crate's unit tests;
+    // SQLite's ":memory:" path gives the same throwaway database here
+    let database = Database::new(PathBuf::from(":memory:")).unwrap();
+    let runtime = ProcessBasedRuntime::with_database(Vec::new(), database).unwrap();
+    let database = runtime.get_database();
+    let runtime = Arc::new(runtime) as Arc<dyn WorkflowRuntime>;
+
+    let mut chat = ChatInterface::detached(
+        runtime,
+        Arc::new(tokio::sync::Mutex::new(app.history.clone())),
+        app.command_tx.clone(),
+        app.task_registry.clone(),
+        app.tokio_runtime.handle().clone(),
+        database,
+    );
+    chat.initialized = true;
+    chat.messages = messages;
+    app.chat = Some(chat);
+}
+
+pub fn message(role: ChatRole, content: &str, tool_calls: Vec<ToolCall>) -> ChatMessage {
+    ChatMessage {
+        role,
+        content: content.to_string(),
+        tool_calls,
+    }
+}
@@ -0,0 +1,7 @@
+//! Snapshot tests for the list, edit, running, tabs, chat and history views
+
+mod common;
+mod test_chat_view;
+mod test_history_view;
+mod test_tab_views;
+mod test_workflow_views;
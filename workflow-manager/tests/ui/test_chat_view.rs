@@ -0,0 +1,42 @@
+//! Snapshots for the chat view
+
+use workflow_manager::app::View;
+use workflow_manager::chat::{ChatRole, ToolCall};
+
+use super::common::*;
+
+#[test]
+fn test_chat_unavailable() {
+    let mut app = sample_app();
+    app.current_view = View::Chat;
+
+    assert_snapshot("chat_unavailable", &render(&mut app, WIDTH, HEIGHT));
+}
+
+#[test]
+fn test_chat_with_messages() {
+    let mut app = sample_app();
+    app.current_view = View::Chat;
+    attach_chat(
+        &mut app,
+        vec![
+            message(ChatRole::User, "Which workflows can I run?", vec![]),
+            message(
+                ChatRole::Assistant,
+                "**Available workflows:**\n1. Research Agent Workflow\n- Übersetzung 翻訳 🚀 Workflow\n\nPick one to start.",
+                vec![ToolCall {
+                    name: "list_workflows".to_string(),
+                    input: "{}".to_string(),
+                    output: "[\"research_agent\", \"translate\"]".to_string(),
+                }],
+            ),
+            message(ChatRole::User, LONG_UNICODE, vec![]),
+        ],
+    );
+    if let Some(chat) = app.chat.as_mut() {
+        chat.input_buffer = "Run the 翻訳 workflow".to_string();
+        chat.cursor_position = chat.input_buffer.chars().count();
+    }
+
+    assert_snapshot("chat_messages", &render(&mut app, WIDTH, HEIGHT));
+}
@@ -0,0 +1,52 @@
+//! Snapshots for the execution history view
+
+use chrono::{Local, TimeZone};
+use uuid::Uuid;
+use workflow_manager::app::View;
+use workflow_manager_sdk::{ExecutionSummary, WorkflowStatus};
+
+use super::common::*;
+
+fn execution(
+    n: u128,
+    name: &str,
+    status: WorkflowStatus,
+    exit_code: Option<i32>,
+) -> ExecutionSummary {
+    let start_time = Local.with_ymd_and_hms(2025, 1, 2, 10, n as u32, 0).unwrap();
+    ExecutionSummary {
+        id: Uuid::from_u128(n),
+        workflow_id: name.to_lowercase(),
+        workflow_name: name.to_string(),
+        end_time: exit_code.map(|_| start_time + chrono::Duration::seconds(42)),
+        start_time,
+        status,
+        exit_code,
+        input_tokens: None,
+        output_tokens: None,
+        total_cost_usd: None,
+    }
+}
+
+#[test]
+fn test_history_empty() {
+    let mut app = sample_app();
+    app.current_view = View::History;
+
+    assert_snapshot("history_empty", &render(&mut app, WIDTH, HEIGHT));
+}
+
+#[test]
+fn test_history_with_executions() {
+    let mut app = sample_app();
+    app.current_view = View::History;
+    app.execution_history = vec![
+        execution(3, "Research", WorkflowStatus::Running, None),
+        execution(2, "Research", WorkflowStatus::Failed, Some(1)),
+        execution(1, "Planner", WorkflowStatus::Completed, Some(0)),
+    ];
+    app.execution_history_selected = 1;
+    app.execution_history_has_more = true;
+
+    assert_snapshot("history_with_executions", &render(&mut app, WIDTH, HEIGHT));
+}
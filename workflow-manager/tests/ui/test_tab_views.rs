@@ -0,0 +1,217 @@
+//! Snapshots for the tabbed running-workflows view
+
+use std::path::PathBuf;
+
+use workflow_manager::app::{FileViewer, RunResult, View};
+use workflow_manager::ui::icons::ASCII_ICONS;
+use workflow_manager_sdk::WorkflowStatus;
+
+use super::common::*;
+
+#[test]
+fn test_tabs_empty() {
+    let mut app = sample_app();
+    app.current_view = View::Tabs;
+
+    assert_snapshot("tabs_empty", &render(&mut app, WIDTH, HEIGHT));
+}
+
+#[test]
+fn test_tabs_collapsed() {
+    let mut app = sample_app();
+    app.current_view = View::Tabs;
+    let tab = tab(&app, 0, 1, WorkflowStatus::Running, sample_phases());
+    app.open_tabs.push(tab);
+
+    assert_snapshot("tabs_collapsed", &render(&mut app, WIDTH, HEIGHT));
+}
+
+#[test]
+fn test_tabs_expanded_with_selected_agent() {
+    let mut app = sample_app();
+    app.current_view = View::Tabs;
+    let mut tab = tab(&app, 0, 1, WorkflowStatus::Running, sample_phases());
+    tab.expanded_phases.extend([0, 1]);
+    tab.expanded_tasks.insert("research_1".to_string());
+    tab.expanded_agents
+        .insert("research_1:researcher".to_string());
+    tab.selected_phase = 1;
+    tab.selected_task = Some("research_1".to_string());
+    tab.selected_agent = Some("research_1:researcher".to_string());
+    app.open_tabs.push(tab);
+
+    assert_snapshot("tabs_expanded", &render(&mut app, WIDTH, HEIGHT));
+}
+
+#[test]
+fn test_tabs_read_only_refuses_kill() {
+    let mut app = sample_app();
+    app.current_view = View::Tabs;
+    let mut tab = tab(&app, 0, 1, WorkflowStatus::Completed, sample_phases());
+    tab.read_only = true;
+    app.open_tabs.push(tab);
+
+    assert!(render(&mut app, WIDTH, HEIGHT).contains("READ-ONLY"));
+
+    app.kill_current_tab();
+    assert_eq!(app.open_tabs[0].status, WorkflowStatus::Completed);
+    assert!(render(&mut app, WIDTH, HEIGHT).contains("Cannot Kill"));
+}
+
+#[test]
+fn test_tabs_scroll_follows_jumps_and_edges() {
+    let mut app = sample_app();
+    app.current_view = View::Tabs;
+    let template = sample_phases().remove(0);
+    let phases = (0..40)
+        .map(|id| {
+            let mut phase = template.clone();
+            phase.id = id;
+            phase.name = if id == 35 { "Needle".to_string() } else { format!("Phase {}", id) };
+            phase
+        })
+        .collect();
+    app.open_tabs.push(tab(&app, 0, 1, WorkflowStatus::Running, phases));
+    app.open_tabs.push(tab(&app, 0, 2, WorkflowStatus::Running, sample_phases()));
+
+    app.select_tree_edge(false);
+    assert!(!app.open_tabs[0].follow);
+    assert_eq!(app.open_tabs[0].scroll_offset, 0);
+
+    app.search_prompt = Some("needle".to_string());
+    app.confirm_search();
+    assert_eq!(app.open_tabs[0].selected_phase, 35);
+    assert!(app.open_tabs[0].scroll_offset > 0);
+    // Scroll positions and searches are per tab
+    assert_eq!(app.open_tabs[1].scroll_offset, 0);
+    assert!(app.open_tabs[1].search.is_none());
+
+    app.page_tree(false);
+    assert_eq!(app.open_tabs[0].selected_phase, 20);
+    assert!(!app.open_tabs[0].follow);
+
+    app.select_tree_edge(true);
+    assert_eq!(app.open_tabs[0].selected_phase, 39);
+    assert!(app.open_tabs[0].follow);
+}
+
+#[test]
+fn test_tabs_result_artifacts_are_selectable() {
+    let dir = std::env::temp_dir().join(format!("tab_views_{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let artifacts: Vec<String> = (1..=5)
+        .map(|i| {
+            let path = dir.join(format!("{}.md", i));
+            std::fs::write(&path, format!("# Artifact {}", i)).unwrap();
+            path.to_string_lossy().to_string()
+        })
+        .collect();
+
+    let mut app = sample_app();
+    app.current_view = View::Tabs;
+    let tab = tab(&app, 0, 1, WorkflowStatus::Completed, sample_phases());
+    *tab.result.lock().unwrap() = Some(RunResult {
+        summary: "Done".to_string(),
+        artifacts,
+    });
+    app.open_tabs.push(tab);
+
+    // Nothing opens until an artifact is selected
+    app.open_result_artifact();
+    assert!(app.file_viewer.is_none());
+
+    // Above the first phase is the last artifact, scrolled into the banner
+    app.navigate_tab_up();
+    assert_eq!(app.open_tabs[0].selected_artifact, Some(4));
+    let screen = render(&mut app, WIDTH, HEIGHT);
+    assert!(screen.contains("5. /") && !screen.contains("2. /"));
+
+    app.navigate_tab_up();
+    app.open_result_artifact();
+    let viewer = app.file_viewer.take().unwrap();
+    assert_eq!(viewer.files.len(), 1);
+    assert_eq!(viewer.lines, vec!["# Artifact 4"]);
+
+    app.navigate_tab_down();
+    app.navigate_tab_down();
+    assert_eq!(app.open_tabs[0].selected_artifact, None);
+    assert_eq!(app.open_tabs[0].selected_phase, 0);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_tabs_many_open() {
+    let mut app = sample_app();
+    app.current_view = View::Tabs;
+    let statuses = [
+        WorkflowStatus::Running,
+        WorkflowStatus::Completed,
+        WorkflowStatus::Failed,
+        WorkflowStatus::NotStarted,
+    ];
+    for i in 0..15 {
+        let tab = tab(
+            &app,
+            i % app.workflows.len(),
+            i + 1,
+            statuses[i % statuses.len()].clone(),
+            Vec::new(),
+        );
+        app.open_tabs.push(tab);
+    }
+    app.active_tab_idx = 12;
+
+    assert_snapshot("tabs_many", &render(&mut app, WIDTH, HEIGHT));
+}
+
+#[test]
+fn test_tabs_ascii_icons() {
+    let mut app = sample_app();
+    app.icons = &ASCII_ICONS;
+    app.current_view = View::Tabs;
+    let mut tab = tab(&app, 0, 1, WorkflowStatus::Failed, sample_phases());
+    tab.expanded_phases.extend([0, 1]);
+    app.open_tabs.push(tab);
+
+    assert_snapshot("tabs_ascii", &render(&mut app, WIDTH, HEIGHT));
+}
+
+#[test]
+fn test_tabs_narrow_unicode() {
+    let mut app = sample_app();
+    app.current_view = View::Tabs;
+    let mut tab = tab(&app, 1, 1, WorkflowStatus::Running, sample_phases());
+    tab.expanded_phases.insert(1);
+    app.open_tabs.push(tab);
+
+    assert_snapshot("tabs_narrow_unicode", &render(&mut app, 40, HEIGHT));
+}
+
+#[test]
+fn test_tabs_markdown_file_scrolled_into_code_block() {
+    let mut app = sample_app();
+    app.current_view = View::Tabs;
+    let tab = tab(&app, 0, 1, WorkflowStatus::Completed, sample_phases());
+    app.open_tabs.push(tab);
+    let content = "# Plan\n\nSteps:\n\n```rust\nfn main() {\n    run();\n}\n```\n\n- done";
+    app.file_viewer = Some(FileViewer {
+        files: vec![("plan.md".to_string(), "Plan".to_string())],
+        current: 0,
+        base_dir: PathBuf::from("/"),
+        lines: content.lines().map(String::from).collect(),
+        scroll: 0,
+        truncated: false,
+        rendered: None,
+    });
+    render(&mut app, WIDTH, HEIGHT);
+    let count = app.file_viewer.as_ref().unwrap().line_count();
+
+    // Scrolling is clamped to the rendered lines, and a fence above the
+    // view still styles the code
+    app.scroll_file_viewer_down(100);
+    assert_eq!(app.file_viewer.as_ref().unwrap().scroll, count - 1);
+    app.scroll_file_viewer_up(count - 6);
+
+    assert_snapshot("tabs_markdown_file_scrolled", &render(&mut app, WIDTH, HEIGHT));
+}
@@ -0,0 +1,96 @@
+//! Snapshots for the workflow list, edit and running views
+
+use workflow_manager::app::View;
+
+use super::common::*;
+
+#[test]
+fn test_workflow_list_view() {
+    let mut app = sample_app();
+    app.current_view = View::WorkflowList;
+    app.selected = 1;
+
+    assert_snapshot("workflow_list", &render(&mut app, WIDTH, HEIGHT));
+}
+
+#[test]
+fn test_workflow_list_help_overlay() {
+    let mut app = sample_app();
+    app.current_view = View::WorkflowList;
+    app.show_help = true;
+
+    assert_snapshot("workflow_list_help", &render(&mut app, WIDTH, HEIGHT));
+}
+
+#[test]
+fn test_workflow_edit_view() {
+    let mut app = sample_app();
+    app.current_view = View::WorkflowEdit(0);
+    app.edit_field_index = 1;
+    app.field_values
+        .insert("input".to_string(), LONG_UNICODE.to_string());
+    app.field_values
+        .insert("phases".to_string(), "1,2".to_string());
+
+    assert_snapshot("workflow_edit", &render(&mut app, WIDTH, HEIGHT));
+}
+
+#[test]
+fn test_workflow_edit_view_with_validation_errors() {
+    let mut app = sample_app();
+    app.current_view = View::WorkflowEdit(0);
+    app.field_values
+        .insert("phases".to_string(), "1,2".to_string());
+    app.field_values
+        .insert("batch_size".to_string(), "0".to_string());
+
+    assert!(!app.validate_launch(0));
+    // Render only the inline errors, not the transient notification
+    let ids: Vec<usize> = app
+        .notifications
+        .get_active()
+        .iter()
+        .map(|n| n.id)
+        .collect();
+    for id in ids {
+        app.notifications.dismiss(id);
+    }
+
+    assert_snapshot("workflow_edit_errors", &render(&mut app, WIDTH, HEIGHT));
+}
+
+#[test]
+fn test_workflow_edit_view_while_editing() {
+    let mut app = sample_app();
+    app.current_view = View::WorkflowEdit(1);
+    app.edit_field_index = 0;
+    app.is_editing = true;
+    app.edit_buffer = "Grüße aus 東京 🗼".to_string();
+
+    assert_snapshot("workflow_edit_editing", &render(&mut app, WIDTH, HEIGHT));
+}
+
+#[test]
+fn test_workflow_running_view() {
+    let mut app = sample_app();
+    app.current_view = View::WorkflowRunning(0);
+    app.workflow_running = true;
+    *app.workflow_phases.lock().unwrap() = sample_phases();
+    *app.workflow_output.lock().unwrap() = sample_output();
+    app.expanded_phases.extend([0, 1]);
+    app.expanded_tasks.insert("research_1".to_string());
+    app.selected_phase = 1;
+    app.selected_task = Some("research_1".to_string());
+
+    assert_snapshot("workflow_running", &render(&mut app, WIDTH, HEIGHT));
+}
+
+#[test]
+fn test_workflow_running_view_narrow() {
+    let mut app = sample_app();
+    app.current_view = View::WorkflowRunning(0);
+    *app.workflow_phases.lock().unwrap() = sample_phases();
+
+    // Collapsed previews must truncate multi-byte text without panicking
+    assert_snapshot("workflow_running_narrow", &render(&mut app, 40, HEIGHT));
+}
@@ -0,0 +1,6 @@
+//! Snapshot tests for TUI rendering
+//!
+//! This file serves as the entry point for Cargo's integration test runner.
+//! The actual test modules are organized in the tests/ui/ subdirectory.
+
+mod ui;